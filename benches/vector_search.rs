@@ -0,0 +1,99 @@
+//! Performance benchmarks for semantic search vector lookups
+//!
+//! Compares the exact search inside DuckDB with the HNSW index on the same
+//! synthetic embeddings.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::path::PathBuf;
+use swissarmyhammer::search::{
+    ChunkType, CodeChunk, ContentHash, Embedding, Language, SemanticConfig, VectorStorage,
+};
+use tempfile::TempDir;
+
+const EMBEDDING_COUNT: usize = 10_000;
+const DIMENSION: usize = 384;
+
+/// Deterministic pseudo-random vector for the given seed
+fn synthetic_vector(seed: usize) -> Vec<f32> {
+    let mut state = (seed as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    (0..DIMENSION)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 2000) as f32 / 1000.0 - 1.0
+        })
+        .collect()
+}
+
+/// Create a storage holding `EMBEDDING_COUNT` chunks with embeddings
+fn create_test_storage() -> (VectorStorage, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let config = SemanticConfig {
+        database_path: temp_dir.path().join("semantic.db"),
+        ..SemanticConfig::default()
+    };
+    let storage = VectorStorage::new(config).unwrap();
+    storage.initialize().unwrap();
+
+    let (chunks, embeddings): (Vec<_>, Vec<_>) = (0..EMBEDDING_COUNT)
+        .map(|i| {
+            let chunk = CodeChunk {
+                id: format!("chunk-{i}"),
+                file_path: PathBuf::from(format!("src/file_{}.rs", i / 20)),
+                language: Language::Rust,
+                content: format!("fn function_{i}() {{}}"),
+                start_line: 1,
+                end_line: 1,
+                chunk_type: ChunkType::Function,
+                content_hash: ContentHash(format!("hash-{i}")),
+                symbol: None,
+            };
+            let embedding = Embedding {
+                chunk_id: chunk.id.clone(),
+                vector: synthetic_vector(i),
+            };
+            (chunk, embedding)
+        })
+        .unzip();
+    storage
+        .store_chunks_and_embeddings_transaction(&chunks, &embeddings)
+        .unwrap();
+
+    (storage, temp_dir)
+}
+
+/// Benchmark exact search against the ANN index
+fn bench_similarity_search(c: &mut Criterion) {
+    let (storage, _temp_dir) = create_test_storage();
+    let query = synthetic_vector(EMBEDDING_COUNT + 1);
+
+    // Build the ANN index outside the measured loop
+    storage.similarity_search(&query, 10, 0.0).unwrap();
+
+    let mut group = c.benchmark_group("similarity_search");
+    group.sample_size(20);
+
+    group.bench_function("exact_10k", |b| {
+        b.iter(|| {
+            let results = storage
+                .similarity_search_exact(black_box(&query), 10, 0.0)
+                .unwrap();
+            black_box(results);
+        })
+    });
+
+    group.bench_function("hnsw_10k", |b| {
+        b.iter(|| {
+            let results = storage
+                .similarity_search(black_box(&query), 10, 0.0)
+                .unwrap();
+            black_box(results);
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(vector_search_benches, bench_similarity_search);
+criterion_main!(vector_search_benches);
@@ -420,12 +420,103 @@ pub enum FlowSubcommand {
         /// Quiet mode - only show errors
         #[arg(short, long)]
         quiet: bool,
+
+        /// Record agent responses to a cassette file for later replay
+        #[arg(long, value_name = "PATH", conflicts_with = "replay")]
+        record: Option<String>,
+
+        /// Answer prompts from a recorded cassette file instead of running the agent
+        #[arg(long, value_name = "PATH")]
+        replay: Option<String>,
     },
-    /// Resume a paused workflow run
+    /// Resume a paused or interrupted workflow run
+    #[command(long_about = "
+Resume a workflow run from its last checkpoint.
+
+Runs are checkpointed after every state transition and action result, so a run
+that was interrupted (Ctrl+C, timeout, crash, killed process) picks up where it
+stopped without repeating actions that already finished. An action that failed
+is run again. A run left marked running counts as interrupted once the process
+that executed it has exited.
+
+Examples:
+  swissarmyhammer flow resume 01H8XYZ123ABC456DEF789GHI0   # Resume a specific run
+  swissarmyhammer flow resume --latest                     # Resume the most recent interrupted run
+")]
     Resume {
         /// Run ID to resume
+        #[arg(required_unless_present = "latest", conflicts_with = "latest")]
+        run_id: Option<String>,
+
+        /// Resume the most recently started run that was interrupted
+        #[arg(long)]
+        latest: bool,
+
+        /// Interactive mode - prompt at each state
+        #[arg(short, long)]
+        interactive: bool,
+
+        /// Execution timeout (e.g., 30s, 5m, 1h)
+        #[arg(long)]
+        timeout: Option<String>,
+
+        /// Quiet mode - only show errors
+        #[arg(short, long)]
+        quiet: bool,
+    },
+    /// Approve a workflow run that is waiting for approval and resume it
+    #[command(long_about = "
+Approve a workflow run paused by an `await approval` action and resume it.
+
+The decision and approver are stored in the run context under `approval`, so
+transitions can branch on `approval.decision`.
+
+Examples:
+  swissarmyhammer flow approve 01H8XYZ123ABC456DEF789GHI0
+  swissarmyhammer flow approve 01H8XYZ123ABC456DEF789GHI0 --approver alice
+")]
+    Approve {
+        /// Run ID to approve
         run_id: String,
 
+        /// Name recorded as the approver (defaults to the current user)
+        #[arg(long)]
+        approver: Option<String>,
+
+        /// Interactive mode - prompt at each state
+        #[arg(short, long)]
+        interactive: bool,
+
+        /// Execution timeout (e.g., 30s, 5m, 1h)
+        #[arg(long)]
+        timeout: Option<String>,
+
+        /// Quiet mode - only show errors
+        #[arg(short, long)]
+        quiet: bool,
+    },
+    /// Reject a workflow run that is waiting for approval and resume it
+    #[command(long_about = "
+Reject a workflow run paused by an `await approval` action and resume it.
+
+The approval action fails with the given reason, so the run takes its failure
+transition, or fails if it has none.
+
+Examples:
+  swissarmyhammer flow reject 01H8XYZ123ABC456DEF789GHI0 --reason \"Tests are red\"
+")]
+    Reject {
+        /// Run ID to reject
+        run_id: String,
+
+        /// Why the run was rejected
+        #[arg(long)]
+        reason: String,
+
+        /// Name recorded as the approver (defaults to the current user)
+        #[arg(long)]
+        approver: Option<String>,
+
         /// Interactive mode - prompt at each state
         #[arg(short, long)]
         interactive: bool,
@@ -478,7 +569,7 @@ pub enum FlowSubcommand {
         #[arg(short = 'n', long)]
         tail: Option<usize>,
 
-        /// Filter logs by level (info, warn, error)
+        /// Only show events at or above this level (debug, info, warn, error)
         #[arg(long)]
         level: Option<String>,
     },
@@ -566,6 +657,71 @@ for better discoverability and clearer intent.
         #[arg(long)]
         timeout: Option<String>,
 
+        /// Quiet mode - only show errors
+        #[arg(short, long)]
+        quiet: bool,
+    },
+    /// Run workflows when their file or schedule triggers fire
+    #[command(long_about = "
+Watch the current directory and run workflows when their triggers fire.
+
+Workflows declare triggers in their front matter:
+
+  triggers:
+    - files: [\"src/**/*.rs\", \"Cargo.toml\"]
+      debounce: 2s
+    - schedule: \"0 9 * * 1-5\"
+
+File changes are collected until they have been quiet for the debounce time.
+A workflow only runs once at a time; triggers firing while it runs start one
+more run when it finishes. The event is available to the workflow as
+`trigger`, e.g. `{{ trigger.paths }}`. Stop watching with Ctrl+C.
+
+Git triggers are run by the hooks `flow hooks` installs, not by watch.
+
+Examples:
+  swissarmyhammer flow watch
+  swissarmyhammer flow watch lint docs
+")]
+    Watch {
+        /// Only watch these workflows (default: all workflows with triggers)
+        workflows: Vec<String>,
+
+        /// Quiet mode - only show errors
+        #[arg(short, long)]
+        quiet: bool,
+    },
+    /// Install git hooks that run workflows with git triggers
+    #[command(long_about = "
+Install post-commit and pre-push hooks into the repository that run the
+workflows declaring `git: post-commit` or `git: pre-push` triggers.
+
+A pre-push workflow that does not complete stops the push. Existing hooks not
+installed by sah are kept unless --force is given, which moves them to
+<hook>.sah-backup and runs them before the workflows. --uninstall puts them back.
+
+Examples:
+  swissarmyhammer flow hooks
+  swissarmyhammer flow hooks --uninstall
+")]
+    Hooks {
+        /// Remove the hooks instead of installing them
+        #[arg(long)]
+        uninstall: bool,
+
+        /// Replace existing hooks that were not installed by sah, keeping them as <hook>.sah-backup
+        #[arg(long, conflicts_with = "uninstall")]
+        force: bool,
+    },
+    /// Run the workflows triggered by a git hook (called by installed hooks)
+    Trigger {
+        /// Git hook that fired (post-commit or pre-push)
+        event: String,
+
+        /// Arguments git passed to the hook
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+
         /// Quiet mode - only show errors
         #[arg(short, long)]
         quiet: bool,
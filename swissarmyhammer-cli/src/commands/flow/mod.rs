@@ -15,14 +15,19 @@ use std::io::{self, Write};
 use std::time::Duration;
 use swissarmyhammer::common::mcp_errors::ToSwissArmyHammerError;
 use swissarmyhammer::workflow::{
-    ExecutionVisualizer, ExecutorError, MemoryWorkflowStorage, StateId, TransitionKey, Workflow,
-    WorkflowExecutor, WorkflowName, WorkflowResolver, WorkflowRunId, WorkflowRunStatus,
-    WorkflowRunStorageBackend, WorkflowStorage, WorkflowStorageBackend,
+    default_approver, ApprovalDecision, Cassette, EventLevel, ExecutionEvent, ExecutionVisualizer,
+    ExecutorError, MemoryWorkflowStorage, RunEventLog, StateId, TransitionKey, TriggerEvent,
+    Workflow, WorkflowExecutor, WorkflowName, WorkflowResolver, WorkflowRun, WorkflowRunId,
+    WorkflowRunStatus, WorkflowRunStorageBackend, WorkflowStorage, WorkflowStorageBackend,
+    RECORD_CASSETTE_KEY, REPLAY_CASSETTE_KEY,
 };
 use swissarmyhammer::{Result, SwissArmyHammerError};
+use swissarmyhammer_config::AgentExecutorType;
 use tokio::signal;
 use tokio::time::timeout;
 
+mod triggers;
+
 /// Help text for the flow command
 pub const DESCRIPTION: &str = include_str!("description.md");
 
@@ -57,6 +62,8 @@ pub async fn run_flow_command(
             test,
             timeout: timeout_str,
             quiet,
+            record,
+            replay,
         } => {
             let all_vars = vars;
 
@@ -69,6 +76,9 @@ pub async fn run_flow_command(
                     test_mode: test,
                     timeout_str,
                     quiet,
+                    record,
+                    replay,
+                    trigger: None,
                 },
                 _template_context,
             )
@@ -76,10 +86,48 @@ pub async fn run_flow_command(
         }
         FlowSubcommand::Resume {
             run_id,
+            latest,
+            interactive,
+            timeout: timeout_str,
+            quiet,
+        } => resume_workflow_command(run_id, latest, interactive, timeout_str, quiet).await,
+        FlowSubcommand::Approve {
+            run_id,
+            approver,
+            interactive,
+            timeout: timeout_str,
+            quiet,
+        } => {
+            decide_approval_command(
+                run_id,
+                ApprovalDecision::Approved,
+                None,
+                approver,
+                interactive,
+                timeout_str,
+                quiet,
+            )
+            .await
+        }
+        FlowSubcommand::Reject {
+            run_id,
+            reason,
+            approver,
             interactive,
             timeout: timeout_str,
             quiet,
-        } => resume_workflow_command(run_id, interactive, timeout_str, quiet).await,
+        } => {
+            decide_approval_command(
+                run_id,
+                ApprovalDecision::Rejected,
+                Some(reason),
+                approver,
+                interactive,
+                timeout_str,
+                quiet,
+            )
+            .await
+        }
         FlowSubcommand::List {
             format,
             verbose,
@@ -129,11 +177,23 @@ pub async fn run_flow_command(
                     test_mode: true,
                     timeout_str,
                     quiet,
+                    record: None,
+                    replay: None,
+                    trigger: None,
                 },
                 _template_context,
             )
             .await
         }
+        FlowSubcommand::Watch { workflows, quiet } => {
+            triggers::watch_command(workflows, quiet, _template_context).await
+        }
+        FlowSubcommand::Hooks { uninstall, force } => {
+            triggers::hooks_command(uninstall, force).await
+        }
+        FlowSubcommand::Trigger { event, args, quiet } => {
+            triggers::trigger_command(event, args, quiet, _template_context).await
+        }
     }
 }
 
@@ -146,13 +206,31 @@ pub struct WorkflowCommandConfig {
     pub test_mode: bool,
     pub timeout_str: Option<String>,
     pub quiet: bool,
+    /// Cassette file to record agent responses to
+    pub record: Option<String>,
+    /// Cassette file to replay agent responses from
+    pub replay: Option<String>,
+    /// Event that started the run, placed in its context under `trigger`
+    pub trigger: Option<TriggerEvent>,
 }
 
 /// Execute a workflow
 pub async fn run_workflow_command(
     config: WorkflowCommandConfig,
-    _template_context: &swissarmyhammer_config::TemplateContext,
+    template_context: &swissarmyhammer_config::TemplateContext,
 ) -> Result<()> {
+    execute_workflow_command(config, template_context)
+        .await
+        .map(|_| ())
+}
+
+/// Execute a workflow, returning the status the run ended in
+///
+/// Dry runs and test mode do not create a run and return `None`.
+pub async fn execute_workflow_command(
+    config: WorkflowCommandConfig,
+    _template_context: &swissarmyhammer_config::TemplateContext,
+) -> Result<Option<WorkflowRunStatus>> {
     // Use proper WorkflowStorage with embedded builtins
     let workflow_storage = tokio::task::spawn_blocking(WorkflowStorage::file_system)
         .await
@@ -220,7 +298,7 @@ pub async fn run_workflow_command(
             );
         }
 
-        return Ok(());
+        return Ok(None);
     }
 
     if config.test_mode {
@@ -289,7 +367,7 @@ pub async fn run_workflow_command(
             println!("✅ Full transition coverage achieved!");
         }
 
-        return Ok(());
+        return Ok(None);
     }
 
     tracing::info!("🚀 Starting workflow: {}", workflow.name);
@@ -303,8 +381,11 @@ pub async fn run_workflow_command(
         ));
     }
 
-    // Create executor
+    // Create executor that checkpoints the run so it can be resumed if interrupted
+    // and records everything it does in the run's event log
     let mut executor = WorkflowExecutor::new();
+    executor.set_checkpoint_storage(create_local_workflow_run_storage()?);
+    executor.set_event_log_dir(local_event_log_dir());
 
     // Create workflow run
     let mut run = executor.start_workflow(workflow.clone()).map_err(|e| {
@@ -317,6 +398,9 @@ pub async fn run_workflow_command(
     // Set initial variables
     run.context.set_workflow_vars(variables.clone());
 
+    // Agents that drive their own tool-calling loop need the MCP tools
+    register_agent_tools(&run).await?;
+
     // Merge configuration context into workflow context
     // Configuration has lower precedence than workflow variables (CLI args have highest precedence)
     // Note: WorkflowTemplateContext already manages template context internally
@@ -332,6 +416,11 @@ pub async fn run_workflow_command(
             .insert("_quiet".to_string(), serde_json::Value::Bool(true));
     }
 
+    // Let the workflow see what started it, e.g. the changed files or the commit
+    if let Some(trigger) = &config.trigger {
+        trigger.inject_into(&mut run.context);
+    }
+
     // Set timeout in context for actions to use
     if let Some(timeout_duration) = timeout_duration {
         run.context.insert(
@@ -340,6 +429,28 @@ pub async fn run_workflow_command(
         );
     }
 
+    // Record or replay agent responses; the setting is checkpointed with the
+    // context so a resumed run keeps using the same cassette. Only this fresh
+    // start empties the recording cassette; a resumed run appends to it.
+    if let Some(path) = &config.record {
+        Cassette::start_recording(path)?;
+        run.context.insert(
+            RECORD_CASSETTE_KEY.to_string(),
+            serde_json::Value::String(path.clone()),
+        );
+    }
+    if let Some(path) = &config.replay {
+        if !std::path::Path::new(path).exists() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Replay cassette not found: {path}"
+            )));
+        }
+        run.context.insert(
+            REPLAY_CASSETTE_KEY.to_string(),
+            serde_json::Value::String(path.clone()),
+        );
+    }
+
     // Setup signal handling for graceful shutdown
     let (shutdown_tx, mut shutdown_rx) = tokio::sync::mpsc::channel(1);
     let shutdown_tx_clone = shutdown_tx.clone();
@@ -355,12 +466,12 @@ pub async fn run_workflow_command(
             result = execute_workflow_with_progress(&mut executor, &mut run, config.interactive) => result,
             _ = timeout(timeout_duration, future::pending::<()>()) => {
                 tracing::warn!("Workflow execution timed out");
-                run.status = WorkflowRunStatus::Cancelled;
+                run.cancel(format!("Timed out after {timeout_duration:?}"));
                 Ok(())
             },
             _ = shutdown_rx.recv() => {
                 tracing::info!("Workflow execution interrupted by user");
-                run.status = WorkflowRunStatus::Cancelled;
+                run.cancel("Interrupted by user");
                 Ok(())
             }
         }
@@ -369,23 +480,28 @@ pub async fn run_workflow_command(
             result = execute_workflow_with_progress(&mut executor, &mut run, config.interactive) => result,
             _ = shutdown_rx.recv() => {
                 tracing::info!("Workflow execution interrupted by user");
-                run.status = WorkflowRunStatus::Cancelled;
+                run.cancel("Interrupted by user");
                 Ok(())
             }
         }
     };
 
-    // Create local workflow run storage (only store failed runs for debugging)
+    // Create local workflow run storage (failed and interrupted runs are kept for debugging and resume)
     let mut run_storage = create_local_workflow_run_storage()?;
 
+    if execution_result.is_ok() {
+        executor.log_run_outcome(&run);
+    }
+
     match execution_result {
         Ok(_) => match run.status {
             WorkflowRunStatus::Completed => {
                 tracing::info!("✅ Workflow completed successfully");
                 tracing::info!("🆔 Run ID: {}", workflow_run_id_to_string(&run.id));
 
-                // Don't store successful runs to avoid accumulating thousands of runs
-                // Only failed runs are stored for debugging purposes
+                // Don't keep successful runs to avoid accumulating thousands of runs,
+                // so discard the checkpoint written during execution
+                remove_run_checkpoint(run_storage.as_mut(), &run.id);
             }
             WorkflowRunStatus::Failed => {
                 tracing::error!("❌ Workflow failed");
@@ -397,7 +513,10 @@ pub async fn run_workflow_command(
                 }
             }
             WorkflowRunStatus::Cancelled => {
-                tracing::warn!("🚫 Workflow cancelled");
+                tracing::warn!(
+                    "🚫 Workflow cancelled: {}",
+                    run.cancel_reason().unwrap_or("no reason given")
+                );
                 tracing::info!("🆔 Run ID: {}", workflow_run_id_to_string(&run.id));
 
                 // Store cancelled runs for debugging
@@ -408,11 +527,18 @@ pub async fn run_workflow_command(
             _ => {
                 tracing::info!("⏸️  Workflow paused");
                 tracing::info!("🆔 Run ID: {}", workflow_run_id_to_string(&run.id));
+                report_pending_approval(&run);
+
+                // Store paused runs so they can be resumed
+                if let Err(storage_err) = run_storage.store_run(&run) {
+                    tracing::warn!("Failed to store paused run: {}", storage_err);
+                }
             }
         },
         Err(e) => {
             tracing::error!("❌ Workflow execution failed: {}", e);
             run.fail();
+            executor.log_run_failure(&run, &e.to_string());
 
             // Store failed runs for debugging
             if let Err(storage_err) = run_storage.store_run(&run) {
@@ -424,23 +550,76 @@ pub async fn run_workflow_command(
         }
     }
 
-    Ok(())
+    Ok(Some(run.status))
 }
 
-/// Resume a workflow run
-async fn resume_workflow_command(
+/// Tell the user how to decide the approval a paused run is waiting for
+fn report_pending_approval(run: &WorkflowRun) {
+    if let Some(pending) = run.pending_approval() {
+        let run_id = workflow_run_id_to_string(&run.id);
+        tracing::info!("✋ Waiting for approval: {}", pending.message);
+        tracing::info!("   Approve with: sah flow approve {}", run_id);
+        tracing::info!(
+            "   Reject with:  sah flow reject {} --reason \"...\"",
+            run_id
+        );
+    }
+}
+
+/// Record a decision on a run that is waiting for approval, then resume it
+async fn decide_approval_command(
     run_id: String,
+    decision: ApprovalDecision,
+    reason: Option<String>,
+    approver: Option<String>,
     interactive: bool,
     timeout_str: Option<String>,
     quiet: bool,
 ) -> Result<()> {
-    let mut storage = WorkflowStorage::file_system()?;
+    let mut storage = create_local_workflow_run_storage()?;
+    let mut run = load_workflow_run(storage.as_ref(), &parse_workflow_run_id(&run_id)?)?;
 
-    // Parse run ID
-    let run_id_typed = parse_workflow_run_id(&run_id)?;
+    let approver = approver.unwrap_or_else(default_approver);
+    let record = run.record_approval(decision, approver, reason)?;
+    storage.store_run(&run)?;
 
-    // Get the run
-    let mut run = storage.get_run(&run_id_typed)?;
+    match record.decision {
+        ApprovalDecision::Approved => {
+            println!("✅ {} approved: {}", record.approver, record.message)
+        }
+        ApprovalDecision::Rejected => {
+            println!("🚫 {} rejected: {}", record.approver, record.message)
+        }
+    }
+
+    resume_workflow_command(Some(run_id), false, interactive, timeout_str, quiet).await
+}
+
+/// Resume a workflow run
+async fn resume_workflow_command(
+    run_id: Option<String>,
+    latest: bool,
+    interactive: bool,
+    timeout_str: Option<String>,
+    quiet: bool,
+) -> Result<()> {
+    let mut storage = create_local_workflow_run_storage()?;
+
+    // Find the run to resume
+    let mut run = if latest {
+        match storage.latest_resumable_run()? {
+            Some(run) => run,
+            None => {
+                println!("❌ No interrupted workflow runs found to resume");
+                return Ok(());
+            }
+        }
+    } else {
+        let run_id = run_id.ok_or_else(|| {
+            SwissArmyHammerError::Other("A run ID or --latest is required".to_string())
+        })?;
+        load_workflow_run(storage.as_ref(), &parse_workflow_run_id(&run_id)?)?
+    };
 
     // Check if run can be resumed
     if run.status == WorkflowRunStatus::Completed {
@@ -453,6 +632,14 @@ async fn resume_workflow_command(
         return Ok(());
     }
 
+    if run.is_budget_cancelled() {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Cannot resume workflow run {}: it ran out of budget ({}). Start a new run instead.",
+            workflow_run_id_to_string(&run.id),
+            run.cancel_reason().unwrap_or_default()
+        )));
+    }
+
     // Parse timeout
     let timeout_duration = if let Some(timeout_str) = timeout_str {
         Some(parse_duration(&timeout_str)?)
@@ -461,6 +648,7 @@ async fn resume_workflow_command(
     };
 
     println!("🔄 Resuming workflow: {}", run.workflow.name);
+    println!("🆔 Run ID: {}", workflow_run_id_to_string(&run.id));
     println!("🔄 From state: {}", run.current_state);
 
     // Set quiet mode in context for actions to use
@@ -469,8 +657,18 @@ async fn resume_workflow_command(
             .insert("_quiet".to_string(), serde_json::Value::Bool(true));
     }
 
-    // Create executor
+    // Create executor that keeps checkpointing the resumed run and appending to its event log
     let mut executor = WorkflowExecutor::new();
+    executor.set_checkpoint_storage(create_local_workflow_run_storage()?);
+    executor.set_event_log_dir(local_event_log_dir());
+
+    register_agent_tools(&run).await?;
+
+    // Skip any work the last checkpoint shows was already done
+    executor
+        .restore_checkpoint(&mut run)
+        .await
+        .map_err(|e| handle_executor_error(e, "Failed to restore workflow checkpoint"))?;
 
     // Setup signal handling for graceful shutdown
     let (shutdown_tx, mut shutdown_rx) = tokio::sync::mpsc::channel(1);
@@ -482,17 +680,20 @@ async fn resume_workflow_command(
     });
 
     // Resume workflow execution
-    let execution_result = if let Some(timeout_duration) = timeout_duration {
+    let execution_result = if executor.is_workflow_finished(&run) {
+        // The checkpoint showed the final state had already completed
+        Ok(())
+    } else if let Some(timeout_duration) = timeout_duration {
         tokio::select! {
             result = execute_workflow_with_progress(&mut executor, &mut run, interactive) => result,
             _ = timeout(timeout_duration, future::pending::<()>()) => {
                 tracing::warn!("Workflow execution timed out");
-                run.status = WorkflowRunStatus::Cancelled;
+                run.cancel(format!("Timed out after {timeout_duration:?}"));
                 Ok(())
             },
             _ = shutdown_rx.recv() => {
                 tracing::info!("Workflow execution interrupted by user");
-                run.status = WorkflowRunStatus::Cancelled;
+                run.cancel("Interrupted by user");
                 Ok(())
             }
         }
@@ -501,37 +702,49 @@ async fn resume_workflow_command(
             result = execute_workflow_with_progress(&mut executor, &mut run, interactive) => result,
             _ = shutdown_rx.recv() => {
                 tracing::info!("Workflow execution interrupted by user");
-                run.status = WorkflowRunStatus::Cancelled;
+                run.cancel("Interrupted by user");
                 Ok(())
             }
         }
     };
 
-    // Store the updated run
-    storage.store_run(&run)?;
+    if execution_result.is_ok() {
+        executor.log_run_outcome(&run);
+    }
 
     match execution_result {
         Ok(_) => match run.status {
             WorkflowRunStatus::Completed => {
                 tracing::info!("✅ Workflow resumed and completed successfully");
+
+                // Completed runs are not kept, matching `flow run`
+                remove_run_checkpoint(storage.as_mut(), &run.id);
+                return Ok(());
             }
             WorkflowRunStatus::Failed => {
                 tracing::error!("❌ Workflow resumed but failed");
             }
             WorkflowRunStatus::Cancelled => {
-                tracing::warn!("🚫 Workflow resumed but was cancelled");
+                tracing::warn!(
+                    "🚫 Workflow resumed but was cancelled: {}",
+                    run.cancel_reason().unwrap_or("no reason given")
+                );
             }
             _ => {
                 tracing::info!("⏸️  Workflow resumed and paused");
+                report_pending_approval(&run);
             }
         },
         Err(e) => {
             tracing::error!("❌ Workflow resume failed: {}", e);
             run.fail();
-            // Skip storage for now - run storage was only for persistence
+            executor.log_run_failure(&run, &e.to_string());
         }
     }
 
+    // Store the updated run
+    storage.store_run(&run)?;
+
     Ok(())
 }
 
@@ -709,8 +922,6 @@ fn display_workflows_to_writer<W: Write>(
 
 /// Check workflow run status
 async fn status_workflow_command(run_id: String, format: OutputFormat, watch: bool) -> Result<()> {
-    let storage = WorkflowStorage::file_system()?;
-
     // Parse run ID
     let run_id_typed = parse_workflow_run_id(&run_id)?;
 
@@ -718,7 +929,10 @@ async fn status_workflow_command(run_id: String, format: OutputFormat, watch: bo
         println!("👁️  Watching workflow run status (Press Ctrl+C to stop)...");
 
         loop {
-            match storage.get_run(&run_id_typed) {
+            // Re-open storage each time so checkpoints written by the running process are picked up
+            let storage = create_local_workflow_run_storage()?;
+
+            match load_workflow_run(storage.as_ref(), &run_id_typed) {
                 Ok(run) => {
                     print_run_status(&run, &format)?;
 
@@ -743,7 +957,8 @@ async fn status_workflow_command(run_id: String, format: OutputFormat, watch: bo
             }
         }
     } else {
-        let run = storage.get_run(&run_id_typed)?;
+        let storage = create_local_workflow_run_storage()?;
+        let run = load_workflow_run(storage.as_ref(), &run_id_typed)?;
         print_run_status(&run, &format)?;
     }
 
@@ -757,41 +972,156 @@ async fn logs_workflow_command(
     tail: Option<usize>,
     level: Option<String>,
 ) -> Result<()> {
-    let storage = WorkflowStorage::file_system()?;
-
-    // Parse run ID
+    // Parse run ID and minimum level
     let run_id_typed = parse_workflow_run_id(&run_id)?;
+    let min_level = level
+        .as_deref()
+        .map(str::parse::<EventLevel>)
+        .transpose()
+        .map_err(SwissArmyHammerError::Other)?;
+
+    let log_path = RunEventLog::path_for(local_event_log_dir(), &run_id_typed);
+    if !log_path.exists() {
+        // Runs recorded before event logging only have their state history,
+        // which never grows
+        if follow {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Run {run_id} has no event log to follow; run `sah flow logs {run_id}` without --follow"
+            )));
+        }
+        let storage = create_local_workflow_run_storage()?;
+        let run = load_workflow_run(storage.as_ref(), &run_id_typed)?;
+        return print_run_logs(&run, tail, &level);
+    }
 
-    let run = storage.get_run(&run_id_typed)?;
+    let events = RunEventLog::read_from(&log_path)?;
+    println!("📄 Logs for run {run_id}");
+    println!();
+    for line in filter_run_events(&events, min_level, tail)
+        .into_iter()
+        .flat_map(format_run_event)
+    {
+        println!("{line}");
+    }
 
-    if follow {
-        println!("📄 Following logs for run {run_id} (Press Ctrl+C to stop)...");
+    if !follow || events.iter().any(is_run_outcome_event) {
+        return Ok(());
+    }
+    if is_run_abandoned(&run_id_typed) {
+        println!("⚠️  Run {run_id} stopped without finishing; its process is no longer running");
+        return Ok(());
+    }
 
-        loop {
-            let updated_run = storage.get_run(&run_id_typed)?;
-            print_run_logs(&updated_run, tail, &level)?;
+    println!("📄 Following logs for run {run_id} (Press Ctrl+C to stop)...");
+    let mut printed = events.len();
+    loop {
+        // Check for Ctrl+C while waiting for new events
+        if (tokio::time::timeout(Duration::from_secs(1), signal::ctrl_c()).await).is_ok() {
+            println!("\n🛑 Stopped following logs");
+            break;
+        }
 
-            // Exit if workflow is completed
-            if updated_run.status == WorkflowRunStatus::Completed
-                || updated_run.status == WorkflowRunStatus::Failed
-                || updated_run.status == WorkflowRunStatus::Cancelled
-            {
-                break;
-            }
+        let events = RunEventLog::read_from(&log_path)?;
+        let new_events = events.get(printed..).unwrap_or_default();
+        printed = events.len();
 
-            // Check for Ctrl+C
-            if (tokio::time::timeout(Duration::from_secs(1), signal::ctrl_c()).await).is_ok() {
-                println!("\n🛑 Stopped following logs");
-                break;
-            }
+        for line in filter_run_events(new_events, min_level, None)
+            .into_iter()
+            .flat_map(format_run_event)
+        {
+            println!("{line}");
+        }
+
+        // Exit once the run has finished or stopped
+        if new_events.iter().any(is_run_outcome_event) {
+            break;
+        }
+
+        // A run whose process crashed or was killed never logs an outcome
+        if new_events.is_empty() && is_run_abandoned(&run_id_typed) {
+            println!(
+                "⚠️  Run {run_id} stopped without finishing; its process is no longer running"
+            );
+            break;
         }
-    } else {
-        print_run_logs(&run, tail, &level)?;
     }
 
     Ok(())
 }
 
+/// Check whether a run is still marked running although the process executing it has exited
+fn is_run_abandoned(run_id: &WorkflowRunId) -> bool {
+    create_local_workflow_run_storage()
+        .ok()
+        .and_then(|storage| storage.get_run(run_id).ok())
+        .is_some_and(|run| run.status == WorkflowRunStatus::Running && run.is_resumable())
+}
+
+/// Select the events at or above `min_level`, keeping only the last `tail` of them
+fn filter_run_events(
+    events: &[ExecutionEvent],
+    min_level: Option<EventLevel>,
+    tail: Option<usize>,
+) -> Vec<&ExecutionEvent> {
+    let filtered: Vec<&ExecutionEvent> = events
+        .iter()
+        .filter(|event| min_level.is_none_or(|level| event.level >= level))
+        .collect();
+
+    match tail {
+        Some(tail_count) if filtered.len() > tail_count => {
+            filtered[filtered.len() - tail_count..].to_vec()
+        }
+        _ => filtered,
+    }
+}
+
+/// Check whether an event records that the run stopped executing
+fn is_run_outcome_event(event: &ExecutionEvent) -> bool {
+    event
+        .run_status()
+        .is_some_and(|status| status != WorkflowRunStatus::Running)
+}
+
+/// Format an event as display lines, followed by any captured output
+fn format_run_event(event: &ExecutionEvent) -> Vec<String> {
+    let level = match event.level {
+        EventLevel::Debug => "DEBUG".dimmed(),
+        EventLevel::Info => "INFO ".green(),
+        EventLevel::Warn => "WARN ".yellow(),
+        EventLevel::Error => "ERROR".red(),
+    };
+    let state = event
+        .state
+        .as_ref()
+        .map(|state| format!(" [{state}]"))
+        .unwrap_or_default();
+
+    let mut lines = vec![format!(
+        "{} {}{} {}: {}",
+        event.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+        level,
+        state,
+        event.event_type,
+        event.details
+    )];
+
+    // Show the text an action produced, such as command output or an agent response
+    if let Some(data) = &event.data {
+        for field in ["user_prompt", "response", "stdout", "stderr"] {
+            if let Some(text) = data.get(field).and_then(|v| v.as_str()) {
+                if text.trim().is_empty() {
+                    continue;
+                }
+                lines.push(format!("    {field}:"));
+                lines.extend(text.lines().map(|line| format!("      {line}")));
+            }
+        }
+    }
+
+    lines
+}
+
 /// Handle ExecutorError and check for abort condition
 fn handle_executor_error(executor_error: ExecutorError, _context: &str) -> SwissArmyHammerError {
     // Convert ExecutorError directly to SwissArmyHammerError using From trait
@@ -1258,7 +1588,7 @@ async fn execute_workflow_test_mode(
     initial_variables: HashMap<String, serde_json::Value>,
     timeout_duration: Option<Duration>,
 ) -> Result<WorkflowCoverage> {
-    use swissarmyhammer::workflow::{ConditionType, WorkflowRun};
+    use swissarmyhammer::workflow::ConditionType;
 
     let mut coverage = WorkflowCoverage {
         visited_states: HashSet::new(),
@@ -1388,15 +1718,63 @@ async fn execute_workflow_test_mode(
     Ok(coverage)
 }
 
+/// Make the MCP tools available to the configured agent executor if it calls tools itself
+async fn register_agent_tools(run: &WorkflowRun) -> Result<()> {
+    if run.context.get_executor_type() != AgentExecutorType::OpenAiCompatible {
+        return Ok(());
+    }
+
+    let tool_context = crate::mcp_integration::CliToolContext::new()
+        .await
+        .map_err(|e| {
+            SwissArmyHammerError::Other(format!("Failed to create tool context for agent: {e}"))
+        })?;
+    swissarmyhammer::workflow::set_agent_tool_provider(std::sync::Arc::new(
+        tool_context.agent_tool_provider(),
+    ));
+    Ok(())
+}
+
+/// Load a workflow run from local run storage, falling back to the user-level run storage
+fn load_workflow_run(
+    local_storage: &dyn WorkflowRunStorageBackend,
+    run_id: &WorkflowRunId,
+) -> Result<WorkflowRun> {
+    match local_storage.get_run(run_id) {
+        Ok(run) => Ok(run),
+        Err(SwissArmyHammerError::WorkflowRunNotFound(_)) => {
+            WorkflowStorage::file_system()?.get_run(run_id)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Remove the checkpoint of a run that no longer needs to be kept
+fn remove_run_checkpoint(storage: &mut dyn WorkflowRunStorageBackend, run_id: &WorkflowRunId) {
+    match storage.remove_run(run_id) {
+        Ok(()) | Err(SwissArmyHammerError::WorkflowRunNotFound(_)) => {}
+        Err(e) => tracing::warn!("Failed to remove checkpoint for run {}: {}", run_id, e),
+    }
+}
+
+/// Directory holding the JSONL event logs of local workflow runs
+///
+/// Event logs are kept outside the run checkpoints so they survive the
+/// removal of a completed run's checkpoint.
+fn local_event_log_dir() -> std::path::PathBuf {
+    std::path::Path::new(swissarmyhammer::workflow::LOCAL_WORKFLOW_RUNS_DIR).join("logs")
+}
+
 /// Create a local workflow run storage that stores runs in .swissarmyhammer/workflow-runs directory
 fn create_local_workflow_run_storage() -> Result<Box<dyn WorkflowRunStorageBackend>> {
     use std::fs;
 
     // Create local .swissarmyhammer/workflow-runs directory
-    let local_dir = std::path::PathBuf::from(".swissarmyhammer/workflow-runs");
+    let local_dir = std::path::PathBuf::from(swissarmyhammer::workflow::LOCAL_WORKFLOW_RUNS_DIR);
     fs::create_dir_all(&local_dir).map_err(|e| {
         SwissArmyHammerError::Other(format!(
-            "Failed to create .swissarmyhammer/workflow-runs directory: {e}"
+            "Failed to create {} directory: {e}",
+            local_dir.display()
         ))
     })?;
 
@@ -1411,6 +1789,7 @@ fn create_local_workflow_run_storage() -> Result<Box<dyn WorkflowRunStorageBacke
 #[cfg(test)]
 mod tests {
     use super::*;
+    use swissarmyhammer::workflow::ExecutionEventType;
 
     #[test]
     fn test_parse_duration() {
@@ -1442,6 +1821,70 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_filter_run_events_by_level_and_tail() {
+        let events = vec![
+            ExecutionEvent::new(ExecutionEventType::Started, "started".to_string()),
+            ExecutionEvent::new(ExecutionEventType::VariableChanged, "x = 1".to_string()),
+            ExecutionEvent::new(ExecutionEventType::ShellOutput, "exit 1".to_string())
+                .with_level(EventLevel::Warn),
+            ExecutionEvent::new(ExecutionEventType::Failed, "boom".to_string()),
+        ];
+
+        assert_eq!(filter_run_events(&events, None, None).len(), 4);
+
+        let warnings = filter_run_events(&events, Some(EventLevel::Warn), None);
+        let details: Vec<&str> = warnings.iter().map(|e| e.details.as_str()).collect();
+        assert_eq!(details, vec!["exit 1", "boom"]);
+
+        let tail = filter_run_events(&events, Some(EventLevel::Info), Some(1));
+        assert_eq!(tail.len(), 1);
+        assert_eq!(tail[0].details, "boom");
+    }
+
+    #[test]
+    fn test_format_run_event_includes_captured_output() {
+        let mut event = ExecutionEvent::new(ExecutionEventType::ShellOutput, "ran".to_string())
+            .with_data(serde_json::json!({"stdout": "line one\nline two", "stderr": ""}));
+        event.state = Some(StateId::new("build"));
+
+        let lines = format_run_event(&event);
+        assert!(lines[0].contains("[build]"));
+        assert!(lines[0].contains("ShellOutput: ran"));
+        assert_eq!(lines[1], "    stdout:");
+        assert_eq!(lines[2], "      line one");
+        assert_eq!(lines[3], "      line two");
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_run_outcome_event_detection() {
+        let mut executor = WorkflowExecutor::new();
+        let mut run = WorkflowRun::new(Workflow::new(
+            WorkflowName::new("outcome"),
+            "Outcome test".to_string(),
+            StateId::new("start"),
+        ));
+
+        executor.log_run_outcome(&run);
+        assert!(!is_run_outcome_event(
+            executor.get_history().last().unwrap()
+        ));
+
+        run.status = WorkflowRunStatus::Paused;
+        executor.log_run_outcome(&run);
+        assert!(is_run_outcome_event(executor.get_history().last().unwrap()));
+
+        // A failure is recorded once, with its error
+        let before = executor.get_history().len();
+        run.fail();
+        executor.log_run_failure(&run, "state exploded");
+        let failures: Vec<&ExecutionEvent> = executor.get_history()[before..].iter().collect();
+        assert_eq!(failures.len(), 1);
+        assert!(is_run_outcome_event(failures[0]));
+        assert!(failures[0].details.contains("state exploded"));
+    }
+
     #[tokio::test]
     async fn test_execute_workflow_test_mode_simple_workflow() {
         use swissarmyhammer::workflow::{
@@ -0,0 +1,699 @@
+//! Starting workflows from their front matter triggers
+//!
+//! `flow watch` stays in the foreground and runs workflows when files they
+//! watch change or their schedule comes due. `flow hooks` installs git hooks
+//! that call `flow trigger`, which runs the workflows declaring that hook.
+
+use super::{execute_workflow_command, WorkflowCommandConfig};
+use is_terminal::IsTerminal;
+use std::future;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use swissarmyhammer::git::git2_utils;
+use swissarmyhammer::workflow::{
+    workflow_triggers, CronSchedule, GitHookEvent, TriggerDebouncer, TriggerEvent,
+    TriggerFileWatcher, TriggerGuard, Workflow, WorkflowName, WorkflowRunStatus, WorkflowStorage,
+    WorkflowTrigger,
+};
+use swissarmyhammer::{Result, SwissArmyHammerError};
+use swissarmyhammer_config::TemplateContext;
+use tokio::signal;
+use tokio::sync::mpsc;
+
+/// Marker identifying hooks written by `flow hooks`
+const HOOK_MARKER: &str = "# Installed by swissarmyhammer: sah flow hooks";
+
+/// Outcome of a triggered run, sent back to the watch loop
+type RunOutcome = (WorkflowName, Result<Option<WorkflowRunStatus>>);
+
+/// A workflow together with the triggers it declares
+struct TriggeredWorkflow {
+    name: WorkflowName,
+    triggers: Vec<WorkflowTrigger>,
+}
+
+/// The next due time of a workflow's schedule
+struct ScheduledRun {
+    workflow: WorkflowName,
+    cron: String,
+    schedule: CronSchedule,
+    next: Option<(Instant, TriggerEvent)>,
+}
+
+impl ScheduledRun {
+    fn new(workflow: WorkflowName, cron: String) -> Result<Self> {
+        let schedule = CronSchedule::parse(&cron).map_err(SwissArmyHammerError::Other)?;
+        let mut scheduled = Self {
+            workflow,
+            cron,
+            schedule,
+            next: None,
+        };
+        scheduled.plan();
+        Ok(scheduled)
+    }
+
+    /// Work out when the schedule is due next
+    fn plan(&mut self) {
+        self.next = self.schedule.next_local().map(|(scheduled_at, wait)| {
+            (
+                Instant::now() + wait,
+                TriggerEvent::Schedule {
+                    cron: self.cron.clone(),
+                    scheduled_at,
+                },
+            )
+        });
+    }
+
+    fn due_at(&self) -> Option<Instant> {
+        self.next.as_ref().map(|(due, _)| *due)
+    }
+}
+
+/// Load the workflows that declare triggers, limited to `names` if any are given
+fn load_triggered_workflows(names: &[String]) -> Result<Vec<TriggeredWorkflow>> {
+    let storage = WorkflowStorage::file_system()?;
+    let workflows = storage.list_workflows()?;
+    select_triggered_workflows(workflows, names)
+}
+
+/// Pick the workflows that declare triggers, limited to `names` if any are given
+fn select_triggered_workflows(
+    workflows: Vec<Workflow>,
+    names: &[String],
+) -> Result<Vec<TriggeredWorkflow>> {
+    if let Some(missing) = names
+        .iter()
+        .find(|name| !workflows.iter().any(|w| w.name.as_str() == name.as_str()))
+    {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Workflow '{missing}' not found"
+        )));
+    }
+
+    Ok(workflows
+        .into_iter()
+        .filter(|workflow| names.is_empty() || names.iter().any(|n| n == workflow.name.as_str()))
+        .filter_map(|workflow| {
+            let triggers = workflow_triggers(&workflow);
+            (!triggers.is_empty()).then_some(TriggeredWorkflow {
+                name: workflow.name,
+                triggers,
+            })
+        })
+        .collect())
+}
+
+/// Describe what started a run for the progress output
+fn describe_trigger(event: &TriggerEvent) -> String {
+    match event {
+        TriggerEvent::Files { paths } if paths.len() == 1 => format!("{} changed", paths[0]),
+        TriggerEvent::Files { paths } => format!("{} files changed", paths.len()),
+        TriggerEvent::Git { event, .. } => format!("git {event}"),
+        TriggerEvent::Schedule { cron, .. } => format!("schedule '{cron}'"),
+    }
+}
+
+/// Run a workflow for a trigger without prompting for anything
+async fn run_triggered_workflow(
+    workflow: &WorkflowName,
+    event: TriggerEvent,
+    quiet: bool,
+    template_context: &TemplateContext,
+) -> Result<Option<WorkflowRunStatus>> {
+    execute_workflow_command(
+        WorkflowCommandConfig {
+            workflow_name: workflow.to_string(),
+            vars: Vec::new(),
+            interactive: false,
+            dry_run: false,
+            test_mode: false,
+            timeout_str: None,
+            quiet,
+            record: None,
+            replay: None,
+            trigger: Some(event),
+        },
+        template_context,
+    )
+    .await
+}
+
+/// Run a workflow for a trigger in the background, reporting back on `done`
+fn spawn_triggered_run(
+    workflow: WorkflowName,
+    event: TriggerEvent,
+    quiet: bool,
+    template_context: &TemplateContext,
+    done: &mpsc::UnboundedSender<RunOutcome>,
+) {
+    tracing::info!("⚡ Running {} ({})", workflow, describe_trigger(&event));
+    let template_context = template_context.clone();
+    let done = done.clone();
+    // Workflow runs are not Send, so they run on the watch loop's LocalSet
+    tokio::task::spawn_local(async move {
+        let outcome = run_triggered_workflow(&workflow, event, quiet, &template_context).await;
+        let _ = done.send((workflow, outcome));
+    });
+}
+
+/// Report how a triggered run ended
+fn report_outcome(workflow: &WorkflowName, outcome: &Result<Option<WorkflowRunStatus>>) {
+    match outcome {
+        Ok(Some(WorkflowRunStatus::Completed)) | Ok(None) => {
+            tracing::info!("✅ {} completed", workflow)
+        }
+        Ok(Some(status)) => tracing::warn!("⚠️  {} ended {:?}", workflow, status),
+        Err(e) => tracing::error!("❌ {} failed: {}", workflow, e),
+    }
+}
+
+/// Wait for the next batch of file changes, or forever if nothing is watched
+async fn next_file_changes(watcher: &mut Option<TriggerFileWatcher>) -> Option<Vec<PathBuf>> {
+    match watcher {
+        Some(watcher) => watcher.next_changes().await,
+        None => future::pending().await,
+    }
+}
+
+/// Run workflows when their file or schedule triggers fire, until interrupted
+pub async fn watch_command(
+    workflows: Vec<String>,
+    quiet: bool,
+    template_context: &TemplateContext,
+) -> Result<()> {
+    let triggered: Vec<TriggeredWorkflow> = load_triggered_workflows(&workflows)?
+        .into_iter()
+        .map(|mut workflow| {
+            workflow
+                .triggers
+                .retain(|trigger| !matches!(trigger, WorkflowTrigger::Git { .. }));
+            workflow
+        })
+        .filter(|workflow| !workflow.triggers.is_empty())
+        .collect();
+    if triggered.is_empty() {
+        return Err(SwissArmyHammerError::Other(
+            "No workflows declare file or schedule triggers to watch".to_string(),
+        ));
+    }
+
+    let mut schedules = Vec::new();
+    for workflow in &triggered {
+        for trigger in &workflow.triggers {
+            if let WorkflowTrigger::Schedule { cron } = trigger {
+                schedules.push(ScheduledRun::new(workflow.name.clone(), cron.clone())?);
+            }
+        }
+    }
+
+    let watches_files = triggered.iter().any(|workflow| {
+        workflow
+            .triggers
+            .iter()
+            .any(|trigger| matches!(trigger, WorkflowTrigger::Files { .. }))
+    });
+    let mut file_watcher = if watches_files {
+        Some(TriggerFileWatcher::new(".")?)
+    } else {
+        None
+    };
+
+    for workflow in &triggered {
+        tracing::info!(
+            "👀 Watching {} ({} triggers)",
+            workflow.name,
+            workflow.triggers.len()
+        );
+    }
+    tracing::info!("Press Ctrl+C to stop");
+
+    let local = tokio::task::LocalSet::new();
+    local
+        .run_until(async move {
+            let (done_tx, mut done_rx) = mpsc::unbounded_channel::<RunOutcome>();
+            let mut debouncer = TriggerDebouncer::new();
+            let mut guard = TriggerGuard::new();
+            let mut in_flight = 0usize;
+
+            loop {
+                let next_due = schedules
+                    .iter()
+                    .filter_map(ScheduledRun::due_at)
+                    .chain(debouncer.next_due())
+                    .min();
+                let wait = async {
+                    match next_due {
+                        Some(due) => tokio::time::sleep_until(due.into()).await,
+                        None => future::pending().await,
+                    }
+                };
+
+                let mut due = Vec::new();
+                tokio::select! {
+                    _ = signal::ctrl_c() => break,
+                    Some(paths) = next_file_changes(&mut file_watcher) => {
+                        for workflow in &triggered {
+                            let fired: Vec<&WorkflowTrigger> = workflow
+                                .triggers
+                                .iter()
+                                .filter(|trigger| paths.iter().any(|path| trigger.matches_path(path)))
+                                .collect();
+                            let Some(debounce) = fired.iter().map(|trigger| trigger.debounce()).max() else {
+                                continue;
+                            };
+                            let matching = paths
+                                .iter()
+                                .filter(|path| fired.iter().any(|trigger| trigger.matches_path(path)));
+                            debouncer.push(
+                                workflow.name.clone(),
+                                TriggerEvent::files(matching),
+                                debounce,
+                                Instant::now(),
+                            );
+                        }
+                    }
+                    _ = wait => {
+                        let now = Instant::now();
+                        due.extend(debouncer.take_due(now));
+                        for scheduled in &mut schedules {
+                            if scheduled.due_at().is_some_and(|at| at <= now) {
+                                if let Some((_, event)) = scheduled.next.take() {
+                                    due.push((scheduled.workflow.clone(), event));
+                                }
+                                scheduled.plan();
+                            }
+                        }
+                    }
+                    Some((workflow, outcome)) = done_rx.recv() => {
+                        in_flight -= 1;
+                        report_outcome(&workflow, &outcome);
+                        if let Some(event) = guard.finish(&workflow) {
+                            in_flight += 1;
+                            spawn_triggered_run(workflow, event, quiet, template_context, &done_tx);
+                        }
+                    }
+                }
+
+                for (workflow, event) in due {
+                    match guard.start(&workflow, event) {
+                        Some(event) => {
+                            in_flight += 1;
+                            spawn_triggered_run(workflow, event, quiet, template_context, &done_tx);
+                        }
+                        None => tracing::info!(
+                            "⏳ {} is still running, it will run again once it finishes",
+                            workflow
+                        ),
+                    }
+                }
+            }
+
+            // Runs still in flight are cancelled by their own interrupt handling;
+            // wait for them so their state is stored for resuming
+            tracing::info!("Stopping watch");
+            while in_flight > 0 {
+                match done_rx.recv().await {
+                    Some((workflow, outcome)) => report_outcome(&workflow, &outcome),
+                    None => break,
+                }
+                in_flight -= 1;
+            }
+        })
+        .await;
+
+    Ok(())
+}
+
+/// Directory git runs hooks from, honouring `core.hooksPath`
+fn git_hooks_dir() -> Result<PathBuf> {
+    let repo = git2_utils::discover_repository(".")?;
+    let configured = repo
+        .config()
+        .ok()
+        .and_then(|config| config.get_path("core.hooksPath").ok());
+    match configured {
+        Some(path) if path.is_absolute() => Ok(path),
+        Some(path) => Ok(git2_utils::get_work_dir(&repo)?
+            .unwrap_or_else(|| repo.path().to_path_buf())
+            .join(path)),
+        None => Ok(git2_utils::get_git_dir(&repo)?.join("hooks")),
+    }
+}
+
+/// Suffix of the file a replaced hook is kept in
+const HOOK_BACKUP_SUFFIX: &str = ".sah-backup";
+
+/// Script git runs for a hook
+///
+/// A hook that was replaced with `--force` runs first, and a failure stops the
+/// workflows from running, as it would have stopped git. Git passes pre-push
+/// the refs being pushed on stdin, so the script reads them once and hands a
+/// copy to each hook.
+fn hook_script(event: GitHookEvent) -> String {
+    match event {
+        GitHookEvent::PrePush => format!(
+            "#!/bin/sh\n{HOOK_MARKER}\n\
+             input=$(cat)\n\
+             hook_input() {{ [ -z \"$input\" ] || printf '%s\\n' \"$input\"; }}\n\
+             if [ -x \"$0{HOOK_BACKUP_SUFFIX}\" ]; then hook_input | \"$0{HOOK_BACKUP_SUFFIX}\" \"$@\" || exit $?; fi\n\
+             hook_input | sah flow trigger {event} \"$@\"\n"
+        ),
+        GitHookEvent::PostCommit => format!(
+            "#!/bin/sh\n{HOOK_MARKER}\n\
+             if [ -x \"$0{HOOK_BACKUP_SUFFIX}\" ]; then \"$0{HOOK_BACKUP_SUFFIX}\" \"$@\" || exit $?; fi\n\
+             exec sah flow trigger {event} \"$@\"\n"
+        ),
+    }
+}
+
+/// File a replaced hook is kept in
+fn hook_backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(HOOK_BACKUP_SUFFIX);
+    PathBuf::from(backup)
+}
+
+/// Whether a hook file was written by `flow hooks`
+fn is_installed_hook(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .map(|content| content.contains(HOOK_MARKER))
+        .unwrap_or(false)
+}
+
+/// Write the hook scripts into `hooks_dir`, returning the hooks written
+///
+/// Hooks that were not written by `flow hooks` are left alone unless `force` is
+/// set, in which case they are moved to `<hook>.sah-backup` and run before the
+/// workflows.
+fn install_git_hooks(hooks_dir: &Path, force: bool) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(hooks_dir)?;
+
+    let hooks: Vec<(GitHookEvent, PathBuf)> = GitHookEvent::ALL
+        .into_iter()
+        .map(|event| (event, hooks_dir.join(event.as_str())))
+        .collect();
+    if !force {
+        if let Some((_, foreign)) = hooks
+            .iter()
+            .find(|(_, path)| path.exists() && !is_installed_hook(path))
+        {
+            return Err(SwissArmyHammerError::Other(format!(
+                "{} already exists and was not installed by sah; use --force to replace it",
+                foreign.display()
+            )));
+        }
+    }
+
+    for (event, path) in &hooks {
+        if path.exists() && !is_installed_hook(path) {
+            let backup = hook_backup_path(path);
+            if backup.exists() {
+                return Err(SwissArmyHammerError::Other(format!(
+                    "{} already exists; remove it before replacing {}",
+                    backup.display(),
+                    path.display()
+                )));
+            }
+            std::fs::rename(path, &backup)?;
+        }
+        std::fs::write(path, hook_script(*event))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+        }
+    }
+    Ok(hooks.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Remove the hook scripts written by `flow hooks`, returning the hooks removed
+///
+/// Hooks that were replaced with `--force` are put back.
+fn uninstall_git_hooks(hooks_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+    for event in GitHookEvent::ALL {
+        let path = hooks_dir.join(event.as_str());
+        if is_installed_hook(&path) {
+            std::fs::remove_file(&path)?;
+            let backup = hook_backup_path(&path);
+            if backup.exists() {
+                std::fs::rename(&backup, &path)?;
+            }
+            removed.push(path);
+        }
+    }
+    Ok(removed)
+}
+
+/// Install or remove the git hooks that run workflows with git triggers
+pub async fn hooks_command(uninstall: bool, force: bool) -> Result<()> {
+    let hooks_dir = git_hooks_dir()?;
+
+    if uninstall {
+        let removed = uninstall_git_hooks(&hooks_dir)?;
+        if removed.is_empty() {
+            println!("No sah git hooks installed in {}", hooks_dir.display());
+        }
+        for path in removed {
+            println!("Removed {}", path.display());
+        }
+        return Ok(());
+    }
+
+    for path in install_git_hooks(&hooks_dir, force)? {
+        println!("Installed {}", path.display());
+    }
+    Ok(())
+}
+
+/// Run the workflows that declare a git hook trigger
+///
+/// Called by the installed hooks. Fails if any of the runs does not complete,
+/// so a failing `pre-push` workflow stops the push.
+pub async fn trigger_command(
+    event: String,
+    args: Vec<String>,
+    quiet: bool,
+    template_context: &TemplateContext,
+) -> Result<()> {
+    let event: GitHookEvent = event.parse().map_err(SwissArmyHammerError::Other)?;
+    let triggered: Vec<WorkflowName> = load_triggered_workflows(&[])?
+        .into_iter()
+        .filter(|workflow| workflow.triggers.contains(&WorkflowTrigger::Git { event }))
+        .map(|workflow| workflow.name)
+        .collect();
+    if triggered.is_empty() {
+        tracing::debug!("No workflows triggered by git {}", event);
+        return Ok(());
+    }
+
+    let sha = git2_utils::discover_repository(".")
+        .and_then(|repo| git2_utils::rev_parse(&repo, "HEAD"))
+        .ok();
+    // git describes the refs being pushed on the pre-push hook's standard input
+    let input: Vec<String> = if event == GitHookEvent::PrePush && !io::stdin().is_terminal() {
+        io::stdin()
+            .lock()
+            .lines()
+            .map_while(std::result::Result::ok)
+            .filter(|line| !line.trim().is_empty())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let trigger = TriggerEvent::Git {
+        event,
+        sha,
+        args,
+        input,
+    };
+
+    let mut failed = Vec::new();
+    for workflow in triggered {
+        tracing::info!("⚡ Running {} ({})", workflow, describe_trigger(&trigger));
+        let outcome =
+            run_triggered_workflow(&workflow, trigger.clone(), quiet, template_context).await;
+        report_outcome(&workflow, &outcome);
+        if !matches!(outcome, Ok(Some(WorkflowRunStatus::Completed)) | Ok(None)) {
+            failed.push(workflow.to_string());
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(SwissArmyHammerError::Other(format!(
+            "Workflows triggered by git {event} did not complete: {}",
+            failed.join(", ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swissarmyhammer::workflow::{set_workflow_triggers, StateId};
+    use tempfile::TempDir;
+
+    fn workflow_with_triggers(name: &str, triggers: &[WorkflowTrigger]) -> Workflow {
+        let mut workflow = Workflow::new(
+            WorkflowName::new(name),
+            format!("{name} workflow"),
+            StateId::new("start"),
+        );
+        set_workflow_triggers(&mut workflow, triggers);
+        workflow
+    }
+
+    #[test]
+    fn test_select_triggered_workflows() {
+        let post_commit = WorkflowTrigger::Git {
+            event: GitHookEvent::PostCommit,
+        };
+        let workflows = vec![
+            workflow_with_triggers("lint", std::slice::from_ref(&post_commit)),
+            workflow_with_triggers("plain", &[]),
+        ];
+
+        let selected = select_triggered_workflows(workflows.clone(), &[]).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].name.as_str(), "lint");
+        assert_eq!(selected[0].triggers, vec![post_commit]);
+
+        let selected = select_triggered_workflows(workflows.clone(), &["plain".to_string()]);
+        assert!(selected.unwrap().is_empty());
+        assert!(select_triggered_workflows(workflows, &["missing".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_install_and_uninstall_git_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let hooks_dir = temp_dir.path().join("hooks");
+
+        let installed = install_git_hooks(&hooks_dir, false).unwrap();
+        assert_eq!(installed.len(), GitHookEvent::ALL.len());
+        let pre_push = std::fs::read_to_string(hooks_dir.join("pre-push")).unwrap();
+        assert!(pre_push.starts_with("#!/bin/sh\n"));
+        assert!(pre_push.contains("sah flow trigger pre-push \"$@\""));
+
+        // Reinstalling over our own hooks is fine
+        install_git_hooks(&hooks_dir, false).unwrap();
+
+        let removed = uninstall_git_hooks(&hooks_dir).unwrap();
+        assert_eq!(removed.len(), GitHookEvent::ALL.len());
+        assert!(!hooks_dir.join("post-commit").exists());
+    }
+
+    #[test]
+    fn test_install_git_hooks_keeps_foreign_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let foreign = temp_dir.path().join("post-commit");
+        std::fs::write(&foreign, "#!/bin/sh\necho mine\n").unwrap();
+
+        assert!(install_git_hooks(temp_dir.path(), false).is_err());
+        assert!(!temp_dir.path().join("pre-push").exists());
+        assert!(uninstall_git_hooks(temp_dir.path()).unwrap().is_empty());
+        assert!(foreign.exists());
+
+        install_git_hooks(temp_dir.path(), true).unwrap();
+        assert!(is_installed_hook(&foreign));
+    }
+
+    #[test]
+    fn test_force_install_backs_up_and_chains_foreign_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let foreign = temp_dir.path().join("post-commit");
+        let backup = temp_dir.path().join("post-commit.sah-backup");
+        std::fs::write(&foreign, "#!/bin/sh\necho mine\n").unwrap();
+
+        install_git_hooks(temp_dir.path(), true).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&backup).unwrap(),
+            "#!/bin/sh\necho mine\n"
+        );
+        let hook = std::fs::read_to_string(&foreign).unwrap();
+        assert!(hook.contains("\"$0.sah-backup\" \"$@\" || exit $?"));
+        assert!(!temp_dir.path().join("pre-push.sah-backup").exists());
+
+        // Reinstalling keeps the backup, uninstalling puts the foreign hook back
+        install_git_hooks(temp_dir.path(), true).unwrap();
+        assert!(backup.exists());
+        uninstall_git_hooks(temp_dir.path()).unwrap();
+        assert!(!backup.exists());
+        assert_eq!(
+            std::fs::read_to_string(&foreign).unwrap(),
+            "#!/bin/sh\necho mine\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pre_push_hook_passes_refs_to_chained_hook_and_trigger() {
+        use std::io::Write as _;
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let hooks_dir = temp_dir.path().join("hooks");
+        let bin_dir = temp_dir.path().join("bin");
+        std::fs::create_dir_all(&hooks_dir).unwrap();
+        std::fs::create_dir_all(&bin_dir).unwrap();
+
+        // Both the replaced hook and a stand-in for `sah` record what they read
+        let write_script = |path: &Path, output: &str| {
+            std::fs::write(
+                path,
+                format!("#!/bin/sh\ncat > \"$HOOK_TEST_OUT/{output}\"\n"),
+            )
+            .unwrap();
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        write_script(&hooks_dir.join("pre-push"), "backup");
+        write_script(&bin_dir.join("sah"), "trigger");
+        install_git_hooks(&hooks_dir, true).unwrap();
+
+        let refs = "refs/heads/main 1111 refs/heads/main 2222\n\
+                    refs/heads/dev 3333 refs/heads/dev 4444\n";
+        let path = format!(
+            "{}:{}",
+            bin_dir.display(),
+            std::env::var("PATH").unwrap_or_default()
+        );
+        let mut child = std::process::Command::new(hooks_dir.join("pre-push"))
+            .args(["origin", "git@example.com:repo.git"])
+            .env("PATH", path)
+            .env("HOOK_TEST_OUT", temp_dir.path())
+            .stdin(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(refs.as_bytes())
+            .unwrap();
+        assert!(child.wait().unwrap().success());
+
+        for output in ["backup", "trigger"] {
+            assert_eq!(
+                std::fs::read_to_string(temp_dir.path().join(output)).unwrap(),
+                refs,
+                "{output} hook input"
+            );
+        }
+    }
+
+    #[test]
+    fn test_describe_trigger() {
+        assert_eq!(
+            describe_trigger(&TriggerEvent::files(["src/main.rs"])),
+            "src/main.rs changed"
+        );
+        assert_eq!(
+            describe_trigger(&TriggerEvent::files(["a.rs", "b.rs"])),
+            "2 files changed"
+        );
+    }
+}
@@ -18,6 +18,8 @@ pub async fn handle_command(template_context: &swissarmyhammer_config::TemplateC
         test: false,
         timeout: None,
         quiet: false,
+        record: None,
+        replay: None,
     };
 
     crate::commands::flow::handle_command(subcommand, template_context).await
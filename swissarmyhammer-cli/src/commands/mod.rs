@@ -10,5 +10,6 @@ pub mod flow;
 pub mod implement;
 pub mod plan;
 pub mod prompt;
+pub mod search;
 pub mod serve;
 pub mod validate;
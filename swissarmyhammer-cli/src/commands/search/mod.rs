@@ -0,0 +1,87 @@
+//! Search command implementation
+//!
+//! Most `search` subcommands are generated from the search MCP tools. `search
+//! watch` is not a tool call: it stays in the foreground and keeps the index
+//! current as files change, which `serve --watch-index` also does in the
+//! background of the MCP server.
+
+use crate::exit_codes::{EXIT_ERROR, EXIT_SUCCESS};
+use std::future::Future;
+use std::path::Path;
+use std::time::Duration;
+use swissarmyhammer::search::{watch_index, EmbeddingEngine, IndexWatchOptions, SemanticConfig};
+use swissarmyhammer::{Result, SwissArmyHammerError};
+
+/// Handle the `search watch` command
+///
+/// Watches the current directory until interrupted with Ctrl+C.
+pub async fn handle_watch_command(matches: &clap::ArgMatches) -> i32 {
+    let mut options = IndexWatchOptions::default();
+    if let Some(debounce) = matches.get_one::<u64>("debounce") {
+        options.debounce = Duration::from_millis(*debounce);
+    }
+    if let Some(batch_size) = matches.get_one::<usize>("batch-size") {
+        options.batch_size = *batch_size;
+    }
+
+    tracing::info!("Press Ctrl+C to stop");
+    let shutdown = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+    match watch_command(options, shutdown).await {
+        Ok(()) => EXIT_SUCCESS,
+        Err(e) => {
+            eprintln!("Search watch failed: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+/// Keep the default search index current with the current directory until
+/// `shutdown` resolves
+pub async fn watch_command(
+    options: IndexWatchOptions,
+    shutdown: impl Future<Output = ()>,
+) -> Result<()> {
+    let config = SemanticConfig::default();
+    let embedding_engine = EmbeddingEngine::from_configuration()
+        .await
+        .map_err(SwissArmyHammerError::Semantic)?;
+    watch_index(
+        &config,
+        embedding_engine,
+        Path::new("."),
+        &options,
+        shutdown,
+    )
+    .await
+    .map_err(SwissArmyHammerError::Semantic)
+}
+
+/// Keep the search index current in the background while the server runs
+///
+/// The watcher gets its own thread and runtime so indexing never holds up
+/// requests, and stops when the process exits.
+pub fn spawn_background_watch() {
+    let spawned = std::thread::Builder::new()
+        .name("search-watch".to_string())
+        .spawn(|| {
+            let runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    tracing::error!("Failed to start search index watcher: {}", e);
+                    return;
+                }
+            };
+            let watch = watch_command(IndexWatchOptions::default(), std::future::pending());
+            if let Err(e) = runtime.block_on(watch) {
+                tracing::error!("Search index watcher stopped: {}", e);
+            }
+        });
+    if let Err(e) = spawned {
+        tracing::error!("Failed to start search index watcher: {}", e);
+    }
+}
@@ -40,6 +40,10 @@ pub async fn handle_command(
     matches: &clap::ArgMatches,
     _template_context: &swissarmyhammer_config::TemplateContext,
 ) -> i32 {
+    if matches!(matches.try_get_one::<bool>("watch-index"), Ok(Some(true))) {
+        crate::commands::search::spawn_background_watch();
+    }
+
     // Check for HTTP subcommand
     match matches.subcommand() {
         Some(("http", http_matches)) => handle_http_serve(http_matches).await,
@@ -293,9 +293,17 @@ invoked via stdio (e.g., by Claude Code). The server will:
 Example:
   swissarmyhammer serve
   swissarmyhammer serve http --port 8080 --host 127.0.0.1
+  swissarmyhammer serve --watch-index  # Keep the search index current
   # Or configure in Claude Code's MCP settings
                 ",
                 )
+                .arg(
+                    Arg::new("watch-index")
+                        .long("watch-index")
+                        .help("Keep the search index current as files change while serving")
+                        .global(true)
+                        .action(ArgAction::SetTrue),
+                )
                 .subcommand(
                     Command::new("http")
                         .about("Start HTTP MCP server")
@@ -501,9 +509,52 @@ Example:
             }
         }
 
+        // Static commands that run in the foreground rather than as a tool call
+        if category_name == "search" {
+            cmd = cmd.subcommand(Self::build_search_watch_command());
+        }
+
         cmd
     }
 
+    /// Build the `search watch` command, which has no MCP tool behind it
+    fn build_search_watch_command() -> Command {
+        Command::new("watch")
+            .about("Keep the search index current as files change")
+            .long_about(
+                "
+Watches the current directory and re-indexes files as they change, until
+interrupted. Only changed files are re-chunked and re-embedded; deleted and
+renamed files are removed from the index. Files excluded by .gitignore are
+left out.
+
+Run `search index` once first so files that changed while nothing was
+watching are indexed too. Use `search status` to see how far behind the
+index is.
+
+Example:
+  swissarmyhammer search watch
+  swissarmyhammer search watch --debounce 2000
+                ",
+            )
+            .arg(
+                Arg::new("debounce")
+                    .long("debounce")
+                    .help("Milliseconds to collect changes before indexing them")
+                    .value_name("MS")
+                    .default_value("500")
+                    .value_parser(clap::value_parser!(u64)),
+            )
+            .arg(
+                Arg::new("batch-size")
+                    .long("batch-size")
+                    .help("Most changed files indexed together")
+                    .value_name("FILES")
+                    .default_value("64")
+                    .value_parser(clap::value_parser!(usize)),
+            )
+    }
+
     /// Build a command for a specific MCP tool from pre-computed data
     fn build_tool_command_from_data(&self, tool_data: &CommandData) -> Command {
         let mut cmd =
@@ -948,17 +999,116 @@ Examples:
                             .long("quiet")
                             .help("Quiet mode - only show errors")
                             .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("record")
+                            .long("record")
+                            .help("Record agent responses to a cassette file for later replay")
+                            .value_name("PATH")
+                            .conflicts_with("replay"),
+                    )
+                    .arg(
+                        Arg::new("replay")
+                            .long("replay")
+                            .help("Answer prompts from a recorded cassette file instead of running the agent")
+                            .value_name("PATH"),
                     ),
             )
             .subcommand(
                 Command::new("resume")
-                    .about("Resume a paused workflow run")
+                    .about("Resume a paused or interrupted workflow run")
                     .arg(
                         Arg::new("run_id")
                             .help("Run ID to resume")
                             .value_name("RUN_ID")
+                            .required_unless_present("latest")
+                            .conflicts_with("latest"),
+                    )
+                    .arg(
+                        Arg::new("latest")
+                            .long("latest")
+                            .help("Resume the most recently started run that was interrupted")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("interactive")
+                            .short('i')
+                            .long("interactive")
+                            .help("Interactive mode - prompt at each state")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("timeout")
+                            .long("timeout")
+                            .help("Execution timeout")
+                            .value_name("DURATION"),
+                    )
+                    .arg(
+                        Arg::new("quiet")
+                            .short('q')
+                            .long("quiet")
+                            .help("Quiet mode - only show errors")
+                            .action(ArgAction::SetTrue),
+                    ),
+            )
+            .subcommand(
+                Command::new("approve")
+                    .about("Approve a workflow run that is waiting for approval and resume it")
+                    .arg(
+                        Arg::new("run_id")
+                            .help("Run ID to approve")
+                            .value_name("RUN_ID")
                             .required(true),
                     )
+                    .arg(
+                        Arg::new("approver")
+                            .long("approver")
+                            .help("Name recorded as the approver (defaults to the current user)")
+                            .value_name("NAME"),
+                    )
+                    .arg(
+                        Arg::new("interactive")
+                            .short('i')
+                            .long("interactive")
+                            .help("Interactive mode - prompt at each state")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("timeout")
+                            .long("timeout")
+                            .help("Execution timeout")
+                            .value_name("DURATION"),
+                    )
+                    .arg(
+                        Arg::new("quiet")
+                            .short('q')
+                            .long("quiet")
+                            .help("Quiet mode - only show errors")
+                            .action(ArgAction::SetTrue),
+                    ),
+            )
+            .subcommand(
+                Command::new("reject")
+                    .about("Reject a workflow run that is waiting for approval and resume it")
+                    .arg(
+                        Arg::new("run_id")
+                            .help("Run ID to reject")
+                            .value_name("RUN_ID")
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("reason")
+                            .long("reason")
+                            .help("Why the run was rejected")
+                            .value_name("TEXT")
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("approver")
+                            .long("approver")
+                            .help("Name recorded as the approver (defaults to the current user)")
+                            .value_name("NAME"),
+                    )
                     .arg(
                         Arg::new("interactive")
                             .short('i')
@@ -1054,7 +1204,7 @@ Examples:
                     .arg(
                         Arg::new("level")
                             .long("level")
-                            .help("Filter logs by level")
+                            .help("Only show events at or above this level (debug, info, warn, error)")
                             .value_name("LEVEL"),
                     ),
             )
@@ -1166,5 +1316,65 @@ Examples:
                             .action(ArgAction::SetTrue),
                     ),
             )
+            .subcommand(
+                Command::new("watch")
+                    .about("Run workflows when their file or schedule triggers fire")
+                    .arg(
+                        Arg::new("workflows")
+                            .help("Only watch these workflows (default: all workflows with triggers)")
+                            .value_name("WORKFLOW")
+                            .action(ArgAction::Append),
+                    )
+                    .arg(
+                        Arg::new("quiet")
+                            .short('q')
+                            .long("quiet")
+                            .help("Quiet mode - only show errors")
+                            .action(ArgAction::SetTrue),
+                    ),
+            )
+            .subcommand(
+                Command::new("hooks")
+                    .about("Install git hooks that run workflows with git triggers")
+                    .arg(
+                        Arg::new("uninstall")
+                            .long("uninstall")
+                            .help("Remove the hooks instead of installing them")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("force")
+                            .long("force")
+                            .help("Replace existing hooks that were not installed by sah")
+                            .action(ArgAction::SetTrue)
+                            .conflicts_with("uninstall"),
+                    ),
+            )
+            .subcommand(
+                Command::new("trigger")
+                    .about("Run the workflows triggered by a git hook (called by installed hooks)")
+                    .arg(
+                        Arg::new("event")
+                            .help("Git hook that fired")
+                            .value_name("EVENT")
+                            .value_parser(["post-commit", "pre-push"])
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("args")
+                            .help("Arguments git passed to the hook")
+                            .value_name("ARGS")
+                            .action(ArgAction::Append)
+                            .trailing_var_arg(true)
+                            .allow_hyphen_values(true),
+                    )
+                    .arg(
+                        Arg::new("quiet")
+                            .short('q')
+                            .long("quiet")
+                            .help("Quiet mode - only show errors")
+                            .action(ArgAction::SetTrue),
+                    ),
+            )
     }
 }
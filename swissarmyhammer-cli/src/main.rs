@@ -230,6 +230,10 @@ async fn handle_dynamic_matches(
         }
         Some(("plan", sub_matches)) => handle_plan_command(sub_matches, &template_context).await,
         Some(("implement", _sub_matches)) => handle_implement_command(&template_context).await,
+        Some(("search", sub_matches)) if sub_matches.subcommand_name() == Some("watch") => {
+            let watch_matches = sub_matches.subcommand_matches("watch").unwrap();
+            commands::search::handle_watch_command(watch_matches).await
+        }
         Some((category, sub_matches)) => match sub_matches.subcommand() {
             Some((tool_name, tool_matches)) => {
                 handle_dynamic_tool_command(category, tool_name, tool_matches, cli_tool_context)
@@ -515,6 +519,8 @@ async fn handle_flow_command(
             let test = sub_matches.get_flag("test");
             let timeout = sub_matches.get_one::<String>("timeout").cloned();
             let quiet = sub_matches.get_flag("quiet");
+            let record = sub_matches.get_one::<String>("record").cloned();
+            let replay = sub_matches.get_one::<String>("replay").cloned();
 
             FlowSubcommand::Run {
                 workflow,
@@ -524,16 +530,52 @@ async fn handle_flow_command(
                 test,
                 timeout,
                 quiet,
+                record,
+                replay,
             }
         }
         Some(("resume", sub_matches)) => {
-            let run_id = sub_matches.get_one::<String>("run_id").cloned().unwrap();
+            let run_id = sub_matches.get_one::<String>("run_id").cloned();
+            let latest = sub_matches.get_flag("latest");
             let interactive = sub_matches.get_flag("interactive");
             let timeout = sub_matches.get_one::<String>("timeout").cloned();
             let quiet = sub_matches.get_flag("quiet");
 
             FlowSubcommand::Resume {
                 run_id,
+                latest,
+                interactive,
+                timeout,
+                quiet,
+            }
+        }
+        Some(("approve", sub_matches)) => {
+            let run_id = sub_matches.get_one::<String>("run_id").cloned().unwrap();
+            let approver = sub_matches.get_one::<String>("approver").cloned();
+            let interactive = sub_matches.get_flag("interactive");
+            let timeout = sub_matches.get_one::<String>("timeout").cloned();
+            let quiet = sub_matches.get_flag("quiet");
+
+            FlowSubcommand::Approve {
+                run_id,
+                approver,
+                interactive,
+                timeout,
+                quiet,
+            }
+        }
+        Some(("reject", sub_matches)) => {
+            let run_id = sub_matches.get_one::<String>("run_id").cloned().unwrap();
+            let reason = sub_matches.get_one::<String>("reason").cloned().unwrap();
+            let approver = sub_matches.get_one::<String>("approver").cloned();
+            let interactive = sub_matches.get_flag("interactive");
+            let timeout = sub_matches.get_one::<String>("timeout").cloned();
+            let quiet = sub_matches.get_flag("quiet");
+
+            FlowSubcommand::Reject {
+                run_id,
+                reason,
+                approver,
                 interactive,
                 timeout,
                 quiet,
@@ -647,6 +689,31 @@ async fn handle_flow_command(
                 quiet,
             }
         }
+        Some(("watch", sub_matches)) => {
+            let workflows = sub_matches
+                .get_many::<String>("workflows")
+                .map(|vals| vals.cloned().collect())
+                .unwrap_or_default();
+            let quiet = sub_matches.get_flag("quiet");
+
+            FlowSubcommand::Watch { workflows, quiet }
+        }
+        Some(("hooks", sub_matches)) => {
+            let uninstall = sub_matches.get_flag("uninstall");
+            let force = sub_matches.get_flag("force");
+
+            FlowSubcommand::Hooks { uninstall, force }
+        }
+        Some(("trigger", sub_matches)) => {
+            let event = sub_matches.get_one::<String>("event").cloned().unwrap();
+            let args = sub_matches
+                .get_many::<String>("args")
+                .map(|vals| vals.cloned().collect())
+                .unwrap_or_default();
+            let quiet = sub_matches.get_flag("quiet");
+
+            FlowSubcommand::Trigger { event, args, quiet }
+        }
         _ => {
             eprintln!("No flow subcommand specified");
             return EXIT_ERROR;
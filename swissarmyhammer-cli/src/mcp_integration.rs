@@ -11,7 +11,7 @@ use swissarmyhammer_tools::{
     register_file_tools, register_issue_tools, register_memo_tools, register_search_tools,
     register_shell_tools, register_web_fetch_tools, register_web_search_tools,
 };
-use swissarmyhammer_tools::{ToolContext, ToolRegistry};
+use swissarmyhammer_tools::{RegistryToolProvider, ToolContext, ToolRegistry};
 use tokio::sync::{Mutex, RwLock};
 
 /// Type alias for issue storage to reduce complexity
@@ -62,9 +62,9 @@ impl CliToolContext {
     ) -> Result<IssueStorageArc, Box<dyn std::error::Error>> {
         // Create storage with working directory - no global directory changes needed
         // This avoids race conditions in parallel test execution
-        let storage = swissarmyhammer::issues::FileSystemIssueStorage::new_default_in(working_dir)?;
+        let storage = swissarmyhammer::issues::create_issue_storage_in(working_dir)?;
 
-        Ok(Arc::new(RwLock::new(Box::new(storage))))
+        Ok(Arc::new(RwLock::new(storage)))
     }
 
     /// Create git operations handler
@@ -154,6 +154,11 @@ impl CliToolContext {
         self.tool_registry.clone()
     }
 
+    /// Expose the tools of this context to agent executors that call tools themselves
+    pub fn agent_tool_provider(&self) -> RegistryToolProvider {
+        RegistryToolProvider::new(self.tool_registry.clone(), self.tool_context.clone())
+    }
+
     /// Create arguments map from vector of key-value pairs for testing
     #[allow(dead_code)]
     pub fn create_arguments(&self, args: Vec<(&str, Value)>) -> Map<String, Value> {
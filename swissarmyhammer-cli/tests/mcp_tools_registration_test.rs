@@ -4,9 +4,9 @@
 
 use swissarmyhammer_tools::mcp::tool_registry::ToolRegistry;
 use swissarmyhammer_tools::mcp::tool_registry::{
-    register_abort_tools, register_file_tools, register_issue_tools, register_memo_tools,
-    register_notify_tools, register_outline_tools, register_search_tools, register_shell_tools,
-    register_todo_tools, register_web_fetch_tools, register_web_search_tools,
+    register_abort_tools, register_file_tools, register_flow_tools, register_issue_tools,
+    register_memo_tools, register_notify_tools, register_outline_tools, register_search_tools,
+    register_shell_tools, register_todo_tools, register_web_fetch_tools, register_web_search_tools,
 };
 
 /// Test that verifies all expected MCP tools are registered
@@ -17,6 +17,7 @@ fn test_mcp_tools_are_registered() {
     // This mirrors exactly what McpServer does in its constructor
     register_abort_tools(&mut registry);
     register_file_tools(&mut registry);
+    register_flow_tools(&mut registry);
     register_issue_tools(&mut registry);
     register_memo_tools(&mut registry);
     register_notify_tools(&mut registry);
@@ -45,16 +46,32 @@ fn test_mcp_tools_are_registered() {
         "files_edit",
         "files_glob",
         "files_grep",
+        "flow_approve",
+        "issue_archive",
+        "issue_bulk",
         "issue_create",
+        "issue_graph",
+        "issue_history",
         "issue_list",
+        "issue_reopen",
+        "issue_revert",
         "issue_show",
+        "issue_sync",
         "memo_create",
         "memo_list",
         "memo_get",
+        "memo_history",
+        "memo_restore",
+        "memo_diff",
+        "memo_export",
+        "memo_import",
+        "memo_links",
+        "memo_tags",
         "notify_create",
         "outline_generate",
         "search_index",
         "search_query",
+        "search_status",
         "shell_execute",
         "todo_create",
         "todo_show",
@@ -138,6 +155,7 @@ fn test_cli_categories_are_available() {
     // Register all tools
     register_abort_tools(&mut registry);
     register_file_tools(&mut registry);
+    register_flow_tools(&mut registry);
     register_issue_tools(&mut registry);
     register_memo_tools(&mut registry);
     register_notify_tools(&mut registry);
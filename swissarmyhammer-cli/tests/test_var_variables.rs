@@ -25,6 +25,8 @@ async fn run_workflow_with_vars_in_process(
         test: false,
         timeout: Some("2s".to_string()), // Use 2 second timeout for fast tests
         quiet: true,
+        record: None,
+        replay: None,
     };
 
     let test_context = swissarmyhammer_config::TemplateContext::new();
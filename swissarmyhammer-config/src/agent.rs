@@ -18,6 +18,9 @@ pub enum AgentExecutorType {
     ClaudeCode,
     /// Use local LlamaAgent with in-process execution
     LlamaAgent,
+    /// Call an OpenAI-compatible `/v1/chat/completions` HTTP endpoint
+    #[serde(rename = "openai-compatible")]
+    OpenAiCompatible,
 }
 
 /// Complete agent configuration with executor-specific settings
@@ -42,6 +45,8 @@ pub enum AgentExecutorConfig {
     ClaudeCode(ClaudeCodeConfig),
     #[serde(rename = "llama-agent")]
     LlamaAgent(LlamaAgentConfig),
+    #[serde(rename = "openai-compatible")]
+    OpenAiCompatible(OpenAiCompatibleConfig),
 }
 
 /// Configuration for Claude Code CLI execution
@@ -65,6 +70,71 @@ pub struct LlamaAgentConfig {
     pub mcp_server: McpServerConfig,
 }
 
+/// Configuration for an OpenAI-compatible chat completions server
+///
+/// Works with any server that implements `/v1/chat/completions`, such as vLLM,
+/// LM Studio, Ollama or the OpenAI API itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAiCompatibleConfig {
+    /// Base URL of the API, including the version prefix (e.g. `http://localhost:11434/v1`)
+    #[serde(default = "default_openai_base_url")]
+    pub base_url: String,
+    /// Model name sent with every request
+    pub model: String,
+    /// Sampling temperature
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    /// Maximum number of tokens to generate per response
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    /// Name of the environment variable holding the API key (no key is sent if unset)
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    /// API key to send, taking precedence over `api_key_env`
+    ///
+    /// Never written back out when the configuration is serialized.
+    #[serde(default, skip_serializing)]
+    pub api_key: Option<String>,
+    /// Maximum number of tool-calling round trips per prompt
+    #[serde(default = "default_max_tool_iterations")]
+    pub max_tool_iterations: u32,
+}
+
+fn default_openai_base_url() -> String {
+    "http://localhost:8000/v1".to_string()
+}
+
+fn default_max_tool_iterations() -> u32 {
+    20
+}
+
+impl OpenAiCompatibleConfig {
+    /// Create a configuration for `model` served at `base_url`
+    pub fn new(base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            model: model.into(),
+            temperature: None,
+            max_tokens: None,
+            api_key_env: None,
+            api_key: None,
+            max_tool_iterations: default_max_tool_iterations(),
+        }
+    }
+
+    /// Get the configured API key, reading it from `api_key_env` if not given directly
+    pub fn api_key(&self) -> Option<String> {
+        self.api_key
+            .clone()
+            .or_else(|| {
+                self.api_key_env
+                    .as_ref()
+                    .and_then(|name| std::env::var(name).ok())
+            })
+            .filter(|key| !key.is_empty())
+    }
+}
+
 /// Model configuration for LlamaAgent
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelConfig {
@@ -128,6 +198,7 @@ impl AgentConfig {
         match &self.executor {
             AgentExecutorConfig::ClaudeCode(_) => AgentExecutorType::ClaudeCode,
             AgentExecutorConfig::LlamaAgent(_) => AgentExecutorType::LlamaAgent,
+            AgentExecutorConfig::OpenAiCompatible(_) => AgentExecutorType::OpenAiCompatible,
         }
     }
 
@@ -146,6 +217,14 @@ impl AgentConfig {
             quiet: false,
         }
     }
+
+    /// Create configuration for an OpenAI-compatible HTTP endpoint
+    pub fn openai_compatible(config: OpenAiCompatibleConfig) -> Self {
+        Self {
+            executor: AgentExecutorConfig::OpenAiCompatible(config),
+            quiet: false,
+        }
+    }
 }
 
 impl LlamaAgentConfig {
@@ -235,7 +314,7 @@ mod tests {
                 assert!(claude_config.claude_path.is_none());
                 assert!(claude_config.args.is_empty());
             }
-            AgentExecutorConfig::LlamaAgent(_) => panic!("Should be Claude Code config"),
+            _ => panic!("Should be Claude Code config"),
         }
     }
 
@@ -251,10 +330,51 @@ mod tests {
             AgentExecutorConfig::LlamaAgent(agent_config) => {
                 assert_eq!(agent_config.mcp_server.timeout_seconds, 10);
             }
-            AgentExecutorConfig::ClaudeCode(_) => panic!("Should be LlamaAgent config"),
+            _ => panic!("Should be LlamaAgent config"),
+        }
+    }
+
+    #[test]
+    fn test_agent_config_openai_compatible_yaml() {
+        let yaml = r#"
+executor:
+  type: openai-compatible
+  config:
+    base_url: http://localhost:11434/v1
+    model: qwen2.5-coder
+    temperature: 0.2
+    api_key_env: TEST_OPENAI_COMPAT_KEY_UNSET
+quiet: false
+"#;
+        let config: AgentConfig = serde_yaml::from_str(yaml).expect("Failed to parse YAML");
+        assert_eq!(config.executor_type(), AgentExecutorType::OpenAiCompatible);
+
+        match config.executor {
+            AgentExecutorConfig::OpenAiCompatible(openai_config) => {
+                assert_eq!(openai_config.base_url, "http://localhost:11434/v1");
+                assert_eq!(openai_config.model, "qwen2.5-coder");
+                assert_eq!(openai_config.temperature, Some(0.2));
+                assert_eq!(openai_config.max_tokens, None);
+                assert_eq!(openai_config.max_tool_iterations, 20);
+                assert!(openai_config.api_key().is_none());
+            }
+            _ => panic!("Should be OpenAI-compatible config"),
         }
     }
 
+    #[test]
+    fn test_openai_compatible_api_key_is_not_serialized() {
+        let mut config = OpenAiCompatibleConfig::new("http://localhost:11434/v1", "m");
+        config.api_key_env = Some("TEST_OPENAI_COMPAT_KEY_UNSET".to_string());
+        assert!(config.api_key().is_none());
+
+        config.api_key = Some("direct-secret".to_string());
+        assert_eq!(config.api_key().as_deref(), Some("direct-secret"));
+
+        let yaml = serde_yaml::to_string(&config).expect("Failed to serialize to YAML");
+        assert!(!yaml.contains("direct-secret"));
+    }
+
     #[test]
     fn test_configuration_serialization_yaml() {
         let config = AgentConfig::llama_agent(LlamaAgentConfig::for_testing());
@@ -194,7 +194,7 @@ pub mod template_context;
 // Re-export main types for easier access
 pub use agent::{
     AgentConfig, AgentExecutorConfig, AgentExecutorType, ClaudeCodeConfig, LlamaAgentConfig,
-    McpServerConfig, ModelConfig, ModelSource,
+    McpServerConfig, ModelConfig, ModelSource, OpenAiCompatibleConfig,
 };
 pub use discovery::{ConfigurationDiscovery, DiscoveryPaths};
 pub use env_vars::EnvVarSubstitution;
@@ -50,7 +50,7 @@ pub use mcp::{
     register_search_tools, register_shell_tools, register_todo_tools, register_web_fetch_tools,
     register_web_search_tools,
 };
-pub use mcp::{ToolContext, ToolRegistry};
+pub use mcp::{RegistryToolProvider, ToolContext, ToolRegistry};
 
 /// Version of this crate
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,127 @@
+//! Adapter exposing the MCP tool registry to agent executors
+//!
+//! Agent executors that run their own tool-calling loop, such as the
+//! OpenAI-compatible executor, find tools through
+//! [`swissarmyhammer::workflow::AgentToolProvider`]. This module implements that
+//! trait on top of a [`ToolRegistry`] and its [`ToolContext`].
+
+use super::tool_registry::{ToolContext, ToolRegistry};
+use rmcp::model::{CallToolResult, RawContent};
+use serde_json::Value;
+use std::sync::Arc;
+use swissarmyhammer::workflow::{AgentTool, AgentToolProvider};
+
+/// Agent tool provider backed by the MCP tool registry
+pub struct RegistryToolProvider {
+    registry: Arc<ToolRegistry>,
+    context: ToolContext,
+}
+
+impl RegistryToolProvider {
+    /// Create a provider exposing every tool in `registry`
+    pub fn new(registry: Arc<ToolRegistry>, context: ToolContext) -> Self {
+        Self { registry, context }
+    }
+
+    /// Join the text content of a tool result
+    fn result_text(result: &CallToolResult) -> String {
+        result
+            .content
+            .iter()
+            .filter_map(|content| match &content.raw {
+                RawContent::Text(text) => Some(text.text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[async_trait::async_trait]
+impl AgentToolProvider for RegistryToolProvider {
+    fn tools(&self) -> Vec<AgentTool> {
+        let mut tools: Vec<AgentTool> = self
+            .registry
+            .list_tool_names()
+            .into_iter()
+            .filter_map(|name| self.registry.get_tool(&name))
+            .map(|tool| AgentTool {
+                name: tool.name().to_string(),
+                description: tool.description().to_string(),
+                parameters: tool.schema(),
+            })
+            .collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        tools
+    }
+
+    async fn call_tool(&self, name: &str, arguments: Value) -> Result<String, String> {
+        let tool = self
+            .registry
+            .get_tool(name)
+            .ok_or_else(|| format!("Unknown tool: {name}"))?;
+
+        let arguments = match arguments {
+            Value::Object(map) => map,
+            Value::Null => serde_json::Map::new(),
+            other => return Err(format!("Tool arguments must be an object, got {other}")),
+        };
+
+        let result = tool
+            .execute(arguments, &self.context)
+            .await
+            .map_err(|e| e.message.to_string())?;
+
+        let text = Self::result_text(&result);
+        if result.is_error == Some(true) {
+            Err(text)
+        } else {
+            Ok(text)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::register_memo_tools;
+    use crate::test_utils::create_test_context;
+
+    async fn memo_provider() -> RegistryToolProvider {
+        let mut registry = ToolRegistry::new();
+        register_memo_tools(&mut registry);
+        RegistryToolProvider::new(Arc::new(registry), create_test_context().await)
+    }
+
+    #[tokio::test]
+    async fn test_registry_tool_provider_lists_tools_with_schemas() {
+        let provider = memo_provider().await;
+        let tools = provider.tools();
+
+        let create = tools.iter().find(|t| t.name == "memo_create").unwrap();
+        assert!(!create.description.is_empty());
+        assert_eq!(create.parameters["type"], "object");
+
+        let mut names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        let sorted = names.clone();
+        names.sort();
+        assert_eq!(names, sorted);
+    }
+
+    #[tokio::test]
+    async fn test_registry_tool_provider_reports_unknown_tool_and_bad_arguments() {
+        let provider = memo_provider().await;
+
+        let error = provider
+            .call_tool("does_not_exist", serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(error.contains("Unknown tool"));
+
+        let error = provider
+            .call_tool("memo_create", serde_json::json!(["not", "an", "object"]))
+            .await
+            .unwrap_err();
+        assert!(error.contains("must be an object"));
+    }
+}
@@ -80,12 +80,23 @@ pub struct DeleteMemoRequest {
 /// ```ignore
 /// SearchMemosRequest {
 ///     query: "meeting notes project".to_string(),
+///     mode: None,
+/// }
+/// ```
+///
+/// Search by meaning as well as by keywords:
+/// ```ignore
+/// SearchMemosRequest {
+///     query: "how do we ship a release".to_string(),
+///     mode: Some("hybrid".to_string()),
 /// }
 /// ```
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct SearchMemosRequest {
     /// Search query string to match against memo titles and content
     pub query: String,
+    /// How to rank memos: keyword (default), semantic or hybrid
+    pub mode: Option<String>,
 }
 
 /// Request to list all memos
@@ -110,12 +121,180 @@ pub struct ListMemosRequest {
 /// Get all memo content for AI context:
 /// ```ignore
 /// GetAllContextRequest {
-///     // No parameters needed
+///     id: None,
+///     depth: None,
+///     query: None,
+///     max_tokens: None,
+/// }
+/// ```
+///
+/// Get a memo and the memos within two links of it:
+/// ```ignore
+/// GetAllContextRequest {
+///     id: Some("Auth_Research".to_string()),
+///     depth: Some(2),
+///     query: None,
+///     max_tokens: None,
+/// }
+/// ```
+///
+/// Get the memos most relevant to a task within 4000 tokens:
+/// ```ignore
+/// GetAllContextRequest {
+///     id: None,
+///     depth: None,
+///     query: Some("Fix the login redirect loop".to_string()),
+///     max_tokens: Some(4000),
 /// }
 /// ```
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
 pub struct GetAllContextRequest {
-    // No parameters needed - returns all memo content formatted for AI consumption
+    /// Only include this memo and its linked neighbourhood instead of every memo
+    pub id: Option<String>,
+    /// How many links to follow from `id` (default 1)
+    pub depth: Option<usize>,
+    /// Query or task description to rank memos against
+    pub query: Option<String>,
+    /// Token budget for the context
+    pub max_tokens: Option<usize>,
+}
+
+/// Request to get the links of a memo, or the memos linking to a target
+///
+/// # Examples
+///
+/// Links into and out of a memo:
+/// ```ignore
+/// MemoLinksRequest {
+///     id: Some("Auth_Research".to_string()),
+///     kind: None,
+///     target: None,
+/// }
+/// ```
+///
+/// Memos referencing a file:
+/// ```ignore
+/// MemoLinksRequest {
+///     id: None,
+///     kind: Some("file".to_string()),
+///     target: Some("src/auth.rs".to_string()),
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct MemoLinksRequest {
+    /// Memo whose outgoing links and backlinks to show
+    pub id: Option<String>,
+    /// Kind of target to find backlinks for (memo, issue, file)
+    pub kind: Option<String>,
+    /// Memo title or ID, issue name, or repository path to find backlinks for
+    pub target: Option<String>,
+}
+
+/// Request to list memo tags, or the memos carrying a tag
+///
+/// # Examples
+///
+/// Memos tagged `auth`:
+/// ```ignore
+/// MemoTagsRequest {
+///     tag: Some("auth".to_string()),
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct MemoTagsRequest {
+    /// Tag whose memos to list; every tag with its count when omitted
+    pub tag: Option<String>,
+}
+
+/// Request to show the revisions of a memo, or the memos in the trash
+///
+/// # Examples
+///
+/// Revisions of a memo:
+/// ```ignore
+/// MemoHistoryRequest {
+///     id: Some("01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string()),
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct MemoHistoryRequest {
+    /// Memo whose revisions to show; the trash is listed when omitted
+    pub id: Option<String>,
+}
+
+/// Request to restore a memo from the trash or to an earlier revision
+///
+/// # Examples
+///
+/// ```ignore
+/// RestoreMemoRequest {
+///     id: "01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string(),
+///     revision: Some(2),
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct RestoreMemoRequest {
+    /// ID of the memo to restore
+    pub id: String,
+    /// Revision whose content to restore; only takes the memo out of the trash when omitted
+    pub revision: Option<u32>,
+}
+
+/// Request to compare two revisions of a memo
+///
+/// # Examples
+///
+/// ```ignore
+/// DiffMemoRequest {
+///     id: "01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string(),
+///     from: Some(1),
+///     to: None,
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct DiffMemoRequest {
+    /// ID of the memo to compare
+    pub id: String,
+    /// Older revision, defaulting to the one before `to`
+    pub from: Option<u32>,
+    /// Newer revision, defaulting to the latest
+    pub to: Option<u32>,
+}
+
+/// Request to export every memo
+///
+/// # Examples
+///
+/// ```ignore
+/// ExportMemosRequest {
+///     format: Some("obsidian".to_string()),
+///     path: "notes-vault".to_string(),
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ExportMemosRequest {
+    /// Export layout: obsidian, markdown or json (default: markdown)
+    pub format: Option<String>,
+    /// Directory to write markdown files into, or the JSON bundle file
+    pub path: String,
+}
+
+/// Request to import memos from an export
+///
+/// # Examples
+///
+/// ```ignore
+/// ImportMemosRequest {
+///     path: "notes-vault".to_string(),
+///     dry_run: Some(true),
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct ImportMemosRequest {
+    /// Directory of markdown files, or a JSON bundle file
+    pub path: String,
+    /// Only report what would be imported
+    pub dry_run: Option<bool>,
 }
 
 #[cfg(test)]
@@ -178,12 +357,18 @@ mod tests {
     fn test_search_memos_request_serialization() {
         let request = SearchMemosRequest {
             query: "test search".to_string(),
+            mode: Some("hybrid".to_string()),
         };
 
         let json = serde_json::to_string(&request).unwrap();
         let deserialized: SearchMemosRequest = serde_json::from_str(&json).unwrap();
 
         assert_eq!(request.query, deserialized.query);
+        assert_eq!(request.mode, deserialized.mode);
+
+        let without_mode: SearchMemosRequest =
+            serde_json::from_str(r#"{"query": "test search"}"#).unwrap();
+        assert_eq!(without_mode.mode, None);
     }
 
     #[test]
@@ -202,15 +387,71 @@ mod tests {
 
     #[test]
     fn test_get_all_context_request_serialization() {
-        let request = GetAllContextRequest {};
+        let request = GetAllContextRequest {
+            id: Some("Auth_Research".to_string()),
+            depth: Some(2),
+            query: Some("login redirect".to_string()),
+            max_tokens: Some(4000),
+        };
 
         let json = serde_json::to_string(&request).unwrap();
         let deserialized: GetAllContextRequest = serde_json::from_str(&json).unwrap();
 
-        // No fields to compare for empty struct
-        assert_eq!(
-            std::mem::size_of_val(&request),
-            std::mem::size_of_val(&deserialized)
-        );
+        assert_eq!(request.id, deserialized.id);
+        assert_eq!(request.depth, deserialized.depth);
+        assert_eq!(request.query, deserialized.query);
+        assert_eq!(request.max_tokens, deserialized.max_tokens);
+
+        // All fields are optional
+        let empty: GetAllContextRequest = serde_json::from_str("{}").unwrap();
+        assert!(empty.id.is_none() && empty.depth.is_none());
+        assert!(empty.query.is_none() && empty.max_tokens.is_none());
+    }
+
+    #[test]
+    fn test_memo_links_request_serialization() {
+        let request = MemoLinksRequest {
+            id: None,
+            kind: Some("file".to_string()),
+            target: Some("src/auth.rs".to_string()),
+        };
+
+        let json = serde_json::to_string(&request).unwrap();
+        let deserialized: MemoLinksRequest = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(request.kind, deserialized.kind);
+        assert_eq!(request.target, deserialized.target);
+    }
+
+    #[test]
+    fn test_memo_revision_requests_serialization() {
+        let request = RestoreMemoRequest {
+            id: "01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string(),
+            revision: Some(2),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        let deserialized: RestoreMemoRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(request.id, deserialized.id);
+        assert_eq!(request.revision, deserialized.revision);
+
+        let diff: DiffMemoRequest =
+            serde_json::from_str(r#"{"id": "01ARZ3NDEKTSV4RRFFQ69G5FAV", "from": 1}"#).unwrap();
+        assert_eq!(diff.from, Some(1));
+        assert_eq!(diff.to, None);
+
+        let history: MemoHistoryRequest = serde_json::from_str("{}").unwrap();
+        assert!(history.id.is_none());
+    }
+
+    #[test]
+    fn test_memo_exchange_requests_serialization() {
+        let export: ExportMemosRequest =
+            serde_json::from_str(r#"{"format": "json", "path": "memos.json"}"#).unwrap();
+        assert_eq!(export.format.as_deref(), Some("json"));
+        assert_eq!(export.path, "memos.json");
+
+        let import: ImportMemosRequest = serde_json::from_str(r#"{"path": "vault"}"#).unwrap();
+        assert_eq!(import.path, "vault");
+        assert_eq!(import.dry_run, None);
     }
 }
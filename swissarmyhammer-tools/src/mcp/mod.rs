@@ -4,6 +4,7 @@
 //! and various tools through the Model Context Protocol.
 
 // Module declarations
+pub mod agent_tools;
 pub mod error_handling;
 pub mod file_watcher;
 pub mod http_server;
@@ -24,14 +25,15 @@ pub mod utils;
 mod tests;
 
 // Re-export commonly used items from submodules
+pub use agent_tools::RegistryToolProvider;
 pub use http_server::{start_http_server, start_in_process_mcp_server, McpServerHandle};
 pub use server::McpServer;
 pub use tool_handlers::ToolHandlers;
 pub use tool_registry::{
-    register_abort_tools, register_file_tools, register_issue_tools, register_memo_tools,
-    register_notify_tools, register_outline_tools, register_search_tools, register_shell_tools,
-    register_todo_tools, register_web_fetch_tools, register_web_search_tools, ToolContext,
-    ToolRegistry,
+    register_abort_tools, register_file_tools, register_flow_tools, register_issue_tools,
+    register_memo_tools, register_notify_tools, register_outline_tools, register_search_tools,
+    register_shell_tools, register_todo_tools, register_web_fetch_tools, register_web_search_tools,
+    ToolContext, ToolRegistry,
 };
 pub use types::{GetPromptRequest, ListPromptsRequest};
 
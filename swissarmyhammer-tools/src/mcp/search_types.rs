@@ -62,6 +62,37 @@ pub struct SearchIndexResponse {
 /// SearchQueryRequest {
 ///     query: "async function implementation".to_string(),
 ///     limit: 5,
+///     mode: None,
+///     rerank: false,
+///     kind: None,
+///     path: None,
+///     explain: false,
+/// }
+/// ```
+///
+/// Find an exact identifier, reranking the fused results:
+/// ```ignore
+/// SearchQueryRequest {
+///     query: "validate_branch_name".to_string(),
+///     limit: 10,
+///     mode: Some("hybrid".to_string()),
+///     rerank: true,
+///     kind: None,
+///     path: None,
+///     explain: false,
+/// }
+/// ```
+///
+/// Find traits in the `git` module:
+/// ```ignore
+/// SearchQueryRequest {
+///     query: "branch operations".to_string(),
+///     limit: 10,
+///     mode: None,
+///     rerank: false,
+///     kind: Some("trait".to_string()),
+///     path: Some("git::*".to_string()),
+///     explain: false,
 /// }
 /// ```
 #[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
@@ -71,6 +102,18 @@ pub struct SearchQueryRequest {
     /// Number of results to return
     #[serde(default = "default_search_limit")]
     pub limit: usize,
+    /// How to rank results: semantic (default), lexical or hybrid
+    pub mode: Option<String>,
+    /// Rescore the top results with a local cross-encoder model
+    #[serde(default)]
+    pub rerank: bool,
+    /// Only return symbols of this kind, such as function, method, struct or trait
+    pub kind: Option<String>,
+    /// Only return symbols whose qualified path matches this glob, such as `git::*`
+    pub path: Option<String>,
+    /// Include the score and rank each search signal gave every result
+    #[serde(default)]
+    pub explain: bool,
 }
 
 fn default_search_limit() -> usize {
@@ -94,8 +137,34 @@ pub struct SearchResult {
     pub language: Option<String>,
     /// Type of code chunk (Function, Class, etc.)
     pub chunk_type: Option<String>,
+    /// Qualified path of the symbol the chunk defines, such as `git::GitOperations::new`
+    pub symbol: Option<String>,
+    /// Kind of the symbol the chunk defines, such as method or trait
+    pub symbol_kind: Option<String>,
+    /// Signature of the symbol the chunk defines
+    pub signature: Option<String>,
     /// Excerpt with highlighted matches
     pub excerpt: String,
+    /// How each search signal scored the result, when explaining the search
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scores: Option<SearchScores>,
+}
+
+/// The score and rank each search signal gave a result
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SearchScores {
+    /// Vector similarity with the query
+    pub vector_score: Option<f32>,
+    /// Position in the vector similarity ranking, starting at 1
+    pub vector_rank: Option<usize>,
+    /// BM25 score from the lexical index
+    pub lexical_score: Option<f32>,
+    /// Position in the lexical ranking, starting at 1
+    pub lexical_rank: Option<usize>,
+    /// Reciprocal rank fusion score, 1.0 for a result every signal ranked first
+    pub fused_score: Option<f32>,
+    /// Cross-encoder relevance between 0.0 and 1.0, when results were reranked
+    pub rerank_score: Option<f32>,
 }
 
 /// Response from semantic search query
@@ -111,6 +180,44 @@ pub struct SearchQueryResponse {
     pub execution_time_ms: u64,
 }
 
+/// Request for the state of the search index
+///
+/// # Examples
+///
+/// Check whether the index is current:
+/// ```ignore
+/// SearchStatusRequest {
+///     // No parameters needed
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, schemars::JsonSchema)]
+pub struct SearchStatusRequest {
+    // No parameters needed for the index status
+}
+
+/// State of the search index and the watcher keeping it current
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct SearchStatusResponse {
+    /// Summary of the index state
+    pub message: String,
+    /// Number of indexed files
+    pub indexed_files: usize,
+    /// Number of indexed code chunks
+    pub total_chunks: usize,
+    /// Whether `search watch` or `serve --watch-index` is keeping the index current
+    pub watching: bool,
+    /// Changed files waiting to be indexed by the watcher
+    pub queue_depth: usize,
+    /// How long the oldest waiting change has waited, in milliseconds
+    pub staleness_ms: u64,
+    /// When the watcher last wrote changes to the index (RFC 3339)
+    pub last_indexed_at: Option<String>,
+    /// Files the watcher failed to index
+    pub watch_errors: usize,
+    /// The watcher's most recent failure
+    pub last_error: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,6 +243,11 @@ mod tests {
 
         assert_eq!(request.query, "test");
         assert_eq!(request.limit, 10); // Default value
+        assert_eq!(request.mode, None);
+        assert!(!request.rerank);
+        assert_eq!(request.kind, None);
+        assert_eq!(request.path, None);
+        assert!(!request.explain);
     }
 
     #[test]
@@ -147,6 +259,25 @@ mod tests {
         assert_eq!(request.limit, 5);
     }
 
+    #[test]
+    fn test_search_query_request_mode_and_rerank() {
+        let json = r#"{"query": "validate_branch_name", "mode": "hybrid", "rerank": true, "explain": true}"#;
+        let request: SearchQueryRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.mode.as_deref(), Some("hybrid"));
+        assert!(request.rerank);
+        assert!(request.explain);
+    }
+
+    #[test]
+    fn test_search_query_request_symbol_filters() {
+        let json = r#"{"query": "branch", "kind": "trait", "path": "git::*"}"#;
+        let request: SearchQueryRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.kind.as_deref(), Some("trait"));
+        assert_eq!(request.path.as_deref(), Some("git::*"));
+    }
+
     #[test]
     fn test_search_result_serialization() {
         let result = SearchResult {
@@ -157,10 +288,15 @@ mod tests {
             similarity_score: 0.85,
             language: Some("rust".to_string()),
             chunk_type: Some("Function".to_string()),
+            symbol: Some("main".to_string()),
+            symbol_kind: Some("function".to_string()),
+            signature: Some("fn main()".to_string()),
             excerpt: "...fn main() {...".to_string(),
+            scores: None,
         };
 
         let serialized = serde_json::to_string(&result).unwrap();
+        assert!(!serialized.contains("scores"));
         let deserialized: SearchResult = serde_json::from_str(&serialized).unwrap();
 
         assert_eq!(result.file_path, deserialized.file_path);
@@ -170,6 +306,7 @@ mod tests {
         assert_eq!(result.similarity_score, deserialized.similarity_score);
         assert_eq!(result.language, deserialized.language);
         assert_eq!(result.chunk_type, deserialized.chunk_type);
+        assert_eq!(result.symbol, deserialized.symbol);
         assert_eq!(result.excerpt, deserialized.excerpt);
     }
 }
@@ -10,11 +10,11 @@ use std::sync::Arc;
 use swissarmyhammer::common::rate_limiter::get_rate_limiter;
 use swissarmyhammer::file_watcher::{FileWatcher, FileWatcherCallback};
 use swissarmyhammer::git::GitOperations;
-use swissarmyhammer::issues::{FileSystemIssueStorage, IssueStorage};
+use swissarmyhammer::issues::{create_issue_storage_in, with_actor, IssueActor};
 use swissarmyhammer::memoranda::{MarkdownMemoStorage, MemoStorage};
 use swissarmyhammer::workflow::{
     FileSystemWorkflowRunStorage, FileSystemWorkflowStorage, WorkflowRunStorageBackend,
-    WorkflowStorage, WorkflowStorageBackend,
+    WorkflowStorage, WorkflowStorageBackend, LOCAL_WORKFLOW_RUNS_DIR,
 };
 use swissarmyhammer::{PromptLibrary, PromptResolver, Result, SwissArmyHammerError};
 use swissarmyhammer_config::TemplateContext;
@@ -22,10 +22,10 @@ use tokio::sync::{Mutex, RwLock};
 
 use super::tool_handlers::ToolHandlers;
 use super::tool_registry::{
-    register_abort_tools, register_file_tools, register_issue_tools, register_memo_tools,
-    register_notify_tools, register_outline_tools, register_search_tools, register_shell_tools,
-    register_todo_tools, register_web_fetch_tools, register_web_search_tools, ToolContext,
-    ToolRegistry,
+    register_abort_tools, register_file_tools, register_flow_tools, register_issue_tools,
+    register_memo_tools, register_notify_tools, register_outline_tools, register_search_tools,
+    register_shell_tools, register_todo_tools, register_web_fetch_tools, register_web_search_tools,
+    ToolContext, ToolRegistry,
 };
 
 /// MCP server for serving prompts and workflows
@@ -104,8 +104,8 @@ impl McpServer {
                 })?;
             }
 
-            // Create storage
-            let storage = FileSystemIssueStorage::new_default().map_err(|e| {
+            // Create storage, synced with a remote tracker when one is configured
+            let storage = create_issue_storage_in(&work_dir).map_err(|e| {
                 tracing::error!("Failed to create issue storage: {}", e);
                 SwissArmyHammerError::Other(format!("Failed to create issue storage: {e}"))
             })?;
@@ -119,7 +119,7 @@ impl McpServer {
                 }
             }
 
-            Box::new(storage) as Box<dyn IssueStorage>
+            storage
         };
 
         // Initialize memo storage with default location, fallback to temp dir for tests
@@ -157,17 +157,21 @@ impl McpServer {
 
         // Initialize tool registry and context
         let mut tool_registry = ToolRegistry::new();
-        let tool_context = Arc::new(ToolContext::new(
-            Arc::new(tool_handlers.clone()),
-            issue_storage.clone(),
-            git_ops_arc.clone(),
-            memo_storage_arc.clone(),
-            get_rate_limiter().clone(),
-        ));
+        let tool_context = Arc::new(
+            ToolContext::new(
+                Arc::new(tool_handlers.clone()),
+                issue_storage.clone(),
+                git_ops_arc.clone(),
+                memo_storage_arc.clone(),
+                get_rate_limiter().clone(),
+            )
+            .with_workflow_runs_dir(work_dir.join(LOCAL_WORKFLOW_RUNS_DIR)),
+        );
 
         // Register all available tools
         register_abort_tools(&mut tool_registry);
         register_file_tools(&mut tool_registry);
+        register_flow_tools(&mut tool_registry);
         register_issue_tools(&mut tool_registry);
         register_memo_tools(&mut tool_registry);
         register_notify_tools(&mut tool_registry);
@@ -834,11 +838,22 @@ impl ServerHandler for McpServer {
     async fn call_tool(
         &self,
         request: CallToolRequestParam,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> std::result::Result<CallToolResult, McpError> {
         if let Some(tool) = self.tool_registry.get_tool(&request.name) {
-            tool.execute(request.arguments.unwrap_or_default(), &self.tool_context)
-                .await
+            // Attribute issue changes made by the tool to the client of this session
+            let actor = IssueActor::Mcp {
+                client: context
+                    .peer
+                    .peer_info()
+                    .map(|info| info.client_info.name.clone())
+                    .unwrap_or_else(|| "unknown".to_string()),
+            };
+            with_actor(
+                actor,
+                tool.execute(request.arguments.unwrap_or_default(), &self.tool_context),
+            )
+            .await
         } else {
             Err(McpError::invalid_request(
                 format!("Unknown tool: {}", request.name),
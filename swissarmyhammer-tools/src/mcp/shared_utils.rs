@@ -71,6 +71,12 @@ impl McpErrorHandler {
             SwissArmyHammerError::IssueAlreadyExists(num) => {
                 McpError::invalid_params(format!("Issue already exists: #{num:06}"), None)
             }
+            SwissArmyHammerError::IssueConflict(details) => {
+                McpError::invalid_params(format!("Issue conflict: {details}"), None)
+            }
+            SwissArmyHammerError::IssuesBlocked(details) => {
+                McpError::invalid_params(format!("All pending issues are blocked: {details}"), None)
+            }
             SwissArmyHammerError::MemoNotFound(id) => {
                 McpError::invalid_params(format!("Memo not found: {id}"), None)
             }
@@ -431,6 +437,8 @@ mod tests {
             content: "This is a long piece of content that should be truncated in the preview to show only the first part".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            tags: Vec::new(),
+            links: Vec::new(),
         };
 
         let preview = McpFormatter::format_memo_preview(&memo, 50);
@@ -222,11 +222,13 @@ use super::tool_handlers::ToolHandlers;
 use rmcp::model::{Annotated, CallToolResult, RawContent, RawTextContent, Tool};
 use rmcp::ErrorData as McpError;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use swissarmyhammer::common::rate_limiter::RateLimitChecker;
 use swissarmyhammer::git::GitOperations;
 use swissarmyhammer::issues::IssueStorage;
 use swissarmyhammer::memoranda::MemoStorage;
+use swissarmyhammer::workflow::LOCAL_WORKFLOW_RUNS_DIR;
 use tokio::sync::{Mutex, RwLock};
 
 /// Context shared by all tools during execution
@@ -291,6 +293,12 @@ pub struct ToolContext {
     /// Provides configurable rate limiting for MCP operations. The trait-based
     /// design allows for easy testing with mock implementations.
     pub rate_limiter: Arc<dyn RateLimitChecker>,
+
+    /// Directory workflow runs are stored in
+    ///
+    /// Defaults to the local runs directory of the flow CLI, relative to the
+    /// current directory.
+    pub workflow_runs_dir: PathBuf,
 }
 
 impl ToolContext {
@@ -308,8 +316,15 @@ impl ToolContext {
             git_ops,
             memo_storage,
             rate_limiter,
+            workflow_runs_dir: PathBuf::from(LOCAL_WORKFLOW_RUNS_DIR),
         }
     }
+
+    /// Use `workflow_runs_dir` as the directory workflow runs are stored in
+    pub fn with_workflow_runs_dir(mut self, workflow_runs_dir: impl Into<PathBuf>) -> Self {
+        self.workflow_runs_dir = workflow_runs_dir.into();
+        self
+    }
 }
 
 /// Trait defining the interface for all MCP tools
@@ -1236,6 +1251,12 @@ pub fn register_file_tools(registry: &mut ToolRegistry) {
     files::register_file_tools(registry);
 }
 
+/// Register all workflow run tools with the registry
+pub fn register_flow_tools(registry: &mut ToolRegistry) {
+    use super::tools::flow;
+    flow::register_flow_tools(registry);
+}
+
 /// Register all issue-related tools with the registry
 pub fn register_issue_tools(registry: &mut ToolRegistry) {
     use super::tools::issues;
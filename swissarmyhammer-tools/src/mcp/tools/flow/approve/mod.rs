@@ -0,0 +1,417 @@
+//! Workflow approval tool for MCP operations
+//!
+//! This module provides the FlowApproveTool for deciding on workflow runs paused at an
+//! `await approval` action. The decision is recorded on the stored run, which is then
+//! resumed like `sah flow approve` does.
+//!
+//! The resumed run executes its remaining states, including agent prompts that may call
+//! back into the MCP server this tool is called through, so it continues on its own
+//! thread while the tool responds. It is checkpointed to the server's runs directory as
+//! it goes, and can be followed with `sah flow logs --follow`.
+
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::thread::JoinHandle;
+use swissarmyhammer::workflow::{
+    default_approver, ApprovalDecision, ApprovalRecord, FileSystemWorkflowRunStorage,
+    WorkflowExecutor, WorkflowRunId, WorkflowRunStatus, WorkflowRunStorageBackend,
+};
+use swissarmyhammer::{Result, SwissArmyHammerError};
+
+/// Decision requested through the tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlowDecision {
+    /// Let the run continue
+    Approve,
+    /// Stop the run at its approval gate
+    Reject,
+}
+
+impl From<FlowDecision> for ApprovalDecision {
+    fn from(decision: FlowDecision) -> Self {
+        match decision {
+            FlowDecision::Approve => ApprovalDecision::Approved,
+            FlowDecision::Reject => ApprovalDecision::Rejected,
+        }
+    }
+}
+
+/// Request structure for approving or rejecting a workflow run
+#[derive(Debug, Deserialize)]
+pub struct FlowApproveRequest {
+    /// ID of the paused workflow run
+    pub run_id: String,
+    /// Whether to approve or reject the run
+    pub decision: FlowDecision,
+    /// Reason for the decision
+    pub reason: Option<String>,
+    /// Name recorded as the approver
+    pub approver: Option<String>,
+}
+
+/// Record a decision on a run stored in `runs_dir`
+fn record_decision(runs_dir: &Path, request: FlowApproveRequest) -> Result<ApprovalRecord> {
+    let run_id = WorkflowRunId::parse(&request.run_id).map_err(|e| {
+        SwissArmyHammerError::Other(format!("Invalid workflow run ID '{}': {e}", request.run_id))
+    })?;
+
+    let mut storage = FileSystemWorkflowRunStorage::new(runs_dir)?;
+    let mut run = storage.get_run(&run_id)?;
+
+    let approver = request.approver.unwrap_or_else(default_approver);
+    let record = run.record_approval(request.decision.into(), approver, request.reason)?;
+    storage.store_run(&run)?;
+    Ok(record)
+}
+
+/// Resume the run `run_id` stored in `runs_dir` until it finishes or stops again
+///
+/// Progress is checkpointed to `runs_dir` and logged next to it, as for
+/// `sah flow resume`. A run that fails is stored as failed, and a completed
+/// run is removed.
+async fn resume_run(runs_dir: &Path, run_id: &WorkflowRunId) -> Result<WorkflowRunStatus> {
+    let mut storage = FileSystemWorkflowRunStorage::new(runs_dir)?;
+    let mut run = storage.get_run(run_id)?;
+
+    let mut executor = WorkflowExecutor::new()
+        .with_checkpoint_storage(Box::new(FileSystemWorkflowRunStorage::new(runs_dir)?))
+        .with_event_log_dir(runs_dir.join("logs"));
+    executor.restore_checkpoint(&mut run).await?;
+
+    let result = if executor.is_workflow_finished(&run) {
+        Ok(())
+    } else {
+        executor.execute_state(&mut run).await
+    };
+    match result {
+        Ok(()) => executor.log_run_outcome(&run),
+        Err(e) => {
+            run.fail();
+            executor.log_run_failure(&run, &e.to_string());
+        }
+    }
+
+    if run.status == WorkflowRunStatus::Completed {
+        storage.remove_run(run_id)?;
+    } else {
+        storage.store_run(&run)?;
+    }
+    Ok(run.status)
+}
+
+/// Resume a decided run on its own thread
+///
+/// Workflow runs are not `Send`, so the run gets a runtime of its own rather
+/// than a task on the server's runtime.
+fn spawn_resume(runs_dir: PathBuf, run_id: WorkflowRunId) -> JoinHandle<Result<WorkflowRunStatus>> {
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let result = runtime.block_on(resume_run(&runs_dir, &run_id));
+        match &result {
+            Ok(status) => {
+                tracing::info!("Workflow run {} stopped with status {:?}", run_id, status)
+            }
+            Err(e) => tracing::error!("Failed to resume workflow run {}: {}", run_id, e),
+        }
+        result
+    })
+}
+
+/// Tool for approving or rejecting workflow runs that wait for approval
+#[derive(Default)]
+pub struct FlowApproveTool;
+
+impl FlowApproveTool {
+    /// Creates a new instance of the FlowApproveTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for FlowApproveTool {
+    fn name(&self) -> &'static str {
+        "flow_approve"
+    }
+
+    fn description(&self) -> &'static str {
+        include_str!("description.md")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "run_id": {
+                    "type": "string",
+                    "description": "ID of the paused workflow run"
+                },
+                "decision": {
+                    "type": "string",
+                    "enum": ["approve", "reject"],
+                    "description": "Whether to approve or reject the run"
+                },
+                "reason": {
+                    "type": "string",
+                    "description": "Reason for the decision"
+                },
+                "approver": {
+                    "type": "string",
+                    "description": "Name recorded as the approver (default: current user)"
+                }
+            },
+            "required": ["run_id", "decision"]
+        })
+    }
+
+    fn hidden_from_cli(&self) -> bool {
+        true
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: FlowApproveRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        context
+            .rate_limiter
+            .check_rate_limit("unknown", "flow_approve", 1)
+            .map_err(|e| {
+                tracing::warn!("Rate limit exceeded for flow approval: {}", e);
+                McpError::invalid_params(e.to_string(), None)
+            })?;
+
+        McpValidation::validate_not_empty(&request.run_id, "workflow run ID")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate workflow run ID"))?;
+
+        let run_id = request.run_id.clone();
+        tracing::debug!(
+            "Recording {:?} for workflow run {}",
+            request.decision,
+            run_id
+        );
+
+        let parsed_run_id =
+            WorkflowRunId::parse(&run_id).map_err(|e| McpError::invalid_params(e, None))?;
+        let runs_dir = context.workflow_runs_dir.clone();
+        let record = record_decision(&runs_dir, request)
+            .map_err(|e| McpErrorHandler::handle_error(e, "record workflow approval"))?;
+
+        tracing::info!(
+            "{} {} workflow run {}",
+            record.approver,
+            record.decision,
+            run_id
+        );
+        spawn_resume(runs_dir, parsed_run_id);
+        Ok(BaseToolImpl::create_success_response(format!(
+            "Workflow run {run_id} {} by {}: {}. The run has been resumed; follow it with `sah flow logs {run_id} --follow`.",
+            record.decision, record.approver, record.message
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::tool_registry::ToolRegistry;
+    use std::time::{Duration, Instant};
+    use swissarmyhammer::workflow::{
+        ConditionType, PendingApproval, State, StateId, StateType, Transition, TransitionCondition,
+        Workflow, WorkflowName, WorkflowRun,
+    };
+    use tempfile::TempDir;
+
+    fn state(id: &str, description: &str, is_terminal: bool) -> State {
+        State {
+            id: StateId::new(id),
+            description: description.to_string(),
+            state_type: StateType::Normal,
+            is_terminal,
+            allows_parallel: false,
+            metadata: Default::default(),
+        }
+    }
+
+    fn transition(from: &str, to: &str) -> Transition {
+        Transition {
+            from_state: StateId::new(from),
+            to_state: StateId::new(to),
+            condition: TransitionCondition {
+                condition_type: ConditionType::Always,
+                expression: None,
+            },
+            action: None,
+            metadata: Default::default(),
+        }
+    }
+
+    /// Store a run paused at `gate`, which is followed by a second gate at `announce`
+    fn store_paused_run(runs_dir: &Path) -> WorkflowRun {
+        let mut workflow = Workflow::new(
+            WorkflowName::new("release"),
+            "Release workflow".to_string(),
+            StateId::new("gate"),
+        );
+        workflow.add_state(state("gate", "await approval \"Ship it?\"", false));
+        workflow.add_state(state("announce", "await approval \"Announce it?\"", false));
+        workflow.add_state(state("done", "Released", true));
+        workflow.add_transition(transition("gate", "announce"));
+        workflow.add_transition(transition("announce", "done"));
+
+        let mut run = WorkflowRun::new(workflow);
+        run.status = WorkflowRunStatus::Paused;
+        run.set_pending_approval(&PendingApproval::new(StateId::new("gate"), "Ship it?"));
+
+        let mut storage = FileSystemWorkflowRunStorage::new(runs_dir).unwrap();
+        storage.store_run(&run).unwrap();
+        run
+    }
+
+    fn request(run: &WorkflowRun, decision: FlowDecision) -> FlowApproveRequest {
+        FlowApproveRequest {
+            run_id: run.id.to_string(),
+            decision,
+            reason: Some("Tests are red".to_string()),
+            approver: Some("bob".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_flow_approve_tool_name() {
+        let tool = FlowApproveTool::new();
+        assert_eq!(tool.name(), "flow_approve");
+    }
+
+    #[test]
+    fn test_flow_approve_tool_schema() {
+        let tool = FlowApproveTool::new();
+        let schema = tool.schema();
+
+        let properties = schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("run_id"));
+        assert!(properties.contains_key("decision"));
+        assert!(properties.contains_key("reason"));
+        assert!(properties.contains_key("approver"));
+
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.contains(&serde_json::Value::String("run_id".to_string())));
+        assert!(required.contains(&serde_json::Value::String("decision".to_string())));
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        let mut args = serde_json::Map::new();
+        args.insert(
+            "run_id".to_string(),
+            serde_json::json!("01K1G8Q5ZK6WJ9B3X2C7N4M8RT"),
+        );
+        args.insert("decision".to_string(), serde_json::json!("reject"));
+
+        let request: FlowApproveRequest = BaseToolImpl::parse_arguments(args.clone()).unwrap();
+        assert_eq!(request.decision, FlowDecision::Reject);
+        assert_eq!(request.reason, None);
+
+        args.insert("decision".to_string(), serde_json::json!("maybe"));
+        let result: std::result::Result<FlowApproveRequest, McpError> =
+            BaseToolImpl::parse_arguments(args);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_record_decision_updates_stored_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let run = store_paused_run(temp_dir.path());
+
+        let record = record_decision(temp_dir.path(), request(&run, FlowDecision::Reject)).unwrap();
+        assert_eq!(record.decision, ApprovalDecision::Rejected);
+        assert_eq!(record.approver, "bob");
+        assert_eq!(record.message, "Ship it?");
+
+        let storage = FileSystemWorkflowRunStorage::new(temp_dir.path()).unwrap();
+        let stored = storage.get_run(&run.id).unwrap();
+        assert!(stored.pending_approval().is_none());
+    }
+
+    #[test]
+    fn test_record_decision_requires_pending_approval() {
+        let temp_dir = TempDir::new().unwrap();
+        let run = store_paused_run(temp_dir.path());
+        record_decision(temp_dir.path(), request(&run, FlowDecision::Approve)).unwrap();
+
+        let error =
+            record_decision(temp_dir.path(), request(&run, FlowDecision::Approve)).unwrap_err();
+        assert!(error.to_string().contains("is not waiting for approval"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_run_continues_past_approved_gate() {
+        let temp_dir = TempDir::new().unwrap();
+        let run = store_paused_run(temp_dir.path());
+        record_decision(temp_dir.path(), request(&run, FlowDecision::Approve)).unwrap();
+
+        let status = resume_run(temp_dir.path(), &run.id).await.unwrap();
+
+        // The run moved on and stopped at the next gate
+        assert_eq!(status, WorkflowRunStatus::Paused);
+        let storage = FileSystemWorkflowRunStorage::new(temp_dir.path()).unwrap();
+        let stored = storage.get_run(&run.id).unwrap();
+        assert_eq!(stored.current_state, StateId::new("announce"));
+        assert_eq!(stored.pending_approval().unwrap().message, "Announce it?");
+    }
+
+    #[tokio::test]
+    async fn test_execute_resumes_run_in_context_runs_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let run = store_paused_run(temp_dir.path());
+        let context = crate::test_utils::create_test_context()
+            .await
+            .with_workflow_runs_dir(temp_dir.path());
+
+        let mut args = serde_json::Map::new();
+        args.insert("run_id".to_string(), serde_json::json!(run.id.to_string()));
+        args.insert("decision".to_string(), serde_json::json!("approve"));
+        let result = FlowApproveTool::new()
+            .execute(args, &context)
+            .await
+            .unwrap();
+        assert_eq!(result.is_error, Some(false));
+
+        // The run is resumed in the background until it reaches the next gate
+        let storage = FileSystemWorkflowRunStorage::new(temp_dir.path()).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            let stored = storage.get_run(&run.id).unwrap();
+            if stored.current_state == StateId::new("announce")
+                && stored.status == WorkflowRunStatus::Paused
+            {
+                break;
+            }
+            assert!(
+                Instant::now() < deadline,
+                "run did not reach the next state: {:?} in {}",
+                stored.status,
+                stored.current_state
+            );
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    #[test]
+    fn test_tool_registration() {
+        let mut registry = ToolRegistry::new();
+        super::super::register_flow_tools(&mut registry);
+
+        assert!(registry.get_tool("flow_approve").is_some());
+    }
+}
@@ -0,0 +1,19 @@
+//! Workflow run tools for MCP operations
+//!
+//! This module provides tools for acting on workflow runs started with `sah flow run`.
+//! Runs are stored in the `.swissarmyhammer/workflow-runs` directory of the server's
+//! working directory, the same location the flow CLI reads them from, so a decision
+//! made through MCP is picked up by `sah flow resume`.
+//!
+//! ## Available Tools
+//!
+//! - **approve**: Approve or reject a workflow run paused at an `await approval` action
+
+pub mod approve;
+
+use crate::mcp::tool_registry::ToolRegistry;
+
+/// Register all workflow run tools with the registry
+pub fn register_flow_tools(registry: &mut ToolRegistry) {
+    registry.register(approve::FlowApproveTool::new());
+}
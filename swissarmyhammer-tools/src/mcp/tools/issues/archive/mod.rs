@@ -0,0 +1,86 @@
+//! Issue archive tool for MCP operations
+//!
+//! This module provides the ArchiveIssueTool for moving stale issues out
+//! of the way through the MCP protocol.
+
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+
+/// Request structure for archiving an issue
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ArchiveIssueRequest {
+    /// Name of the issue
+    pub name: String,
+}
+
+/// Tool for archiving issues
+#[derive(Default)]
+pub struct ArchiveIssueTool;
+
+impl ArchiveIssueTool {
+    /// Creates a new instance of the ArchiveIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for ArchiveIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_archive"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "archive")
+            .unwrap_or("Move an issue into the archived issues")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Name of the issue to archive"
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: ArchiveIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(&request.name, "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+
+        context
+            .rate_limiter
+            .check_rate_limit("unknown", "issue_archive", 1)
+            .map_err(|e| {
+                tracing::warn!("Rate limit exceeded for issue archive: {}", e);
+                McpError::invalid_params(e.to_string(), None)
+            })?;
+
+        let issue_storage = context.issue_storage.write().await;
+        match issue_storage.archive_issue(&request.name).await {
+            Ok(issue) => {
+                tracing::info!("Archived issue '{}'", issue.name);
+                Ok(create_success_response(format!(
+                    "Archived issue {}",
+                    issue.name
+                )))
+            }
+            Err(e) => Err(McpErrorHandler::handle_error(e, "archive issue")),
+        }
+    }
+}
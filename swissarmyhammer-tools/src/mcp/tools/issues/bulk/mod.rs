@@ -0,0 +1,225 @@
+//! Issue bulk operation tool for MCP operations
+//!
+//! This module provides the BulkIssuesTool for completing, archiving, deleting
+//! or relabeling every issue matched by a name glob or labels through the MCP
+//! protocol.
+
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+use swissarmyhammer::issues::{BulkResult, IssueFilter, IssueStatus};
+
+/// Request structure for a bulk operation
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BulkIssuesRequest {
+    /// Operation to apply (complete, archive, delete, relabel)
+    pub action: String,
+    /// Glob matched against issue names
+    pub name: Option<String>,
+    /// Labels an issue must all carry
+    pub labels: Option<Vec<String>>,
+    /// Only issues with this status (active, completed, archived)
+    pub status: Option<String>,
+    /// Labels to add when relabeling
+    pub add_labels: Option<Vec<String>>,
+    /// Labels to remove when relabeling
+    pub remove_labels: Option<Vec<String>>,
+    /// List the matching issues without changing them
+    pub dry_run: Option<bool>,
+}
+
+/// Tool for applying one operation to many issues
+#[derive(Default)]
+pub struct BulkIssuesTool;
+
+impl BulkIssuesTool {
+    /// Creates a new instance of the BulkIssuesTool
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Format the outcome of a bulk operation
+    fn format_result(action: &str, result: &BulkResult) -> String {
+        let mut response = format!(
+            "Matched {} issues, {} {}",
+            result.matched(),
+            action,
+            result.succeeded.len()
+        );
+        if !result.succeeded.is_empty() {
+            response.push_str(&format!(": {}", result.succeeded.join(", ")));
+        }
+        for failure in &result.failed {
+            response.push_str(&format!("\n❌ {}: {}", failure.name, failure.error));
+        }
+        response
+    }
+}
+
+#[async_trait]
+impl McpTool for BulkIssuesTool {
+    fn name(&self) -> &'static str {
+        "issue_bulk"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "bulk").unwrap_or(
+            "Complete, archive, delete or relabel every issue matching a name glob or labels",
+        )
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "description": "Operation to apply to every matching issue",
+                    "enum": ["complete", "archive", "delete", "relabel"]
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Glob matched against issue names, such as 'auth_*'"
+                },
+                "labels": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Only issues carrying all of these labels"
+                },
+                "status": {
+                    "type": "string",
+                    "description": "Only issues with this status; active and completed issues otherwise",
+                    "enum": ["active", "completed", "archived"]
+                },
+                "add_labels": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Labels to add when relabeling"
+                },
+                "remove_labels": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Labels to remove when relabeling"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "List the matching issues without changing them",
+                    "default": false
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: BulkIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        context
+            .rate_limiter
+            .check_rate_limit("unknown", "issue_bulk", 1)
+            .map_err(|e| {
+                tracing::warn!("Rate limit exceeded for issue bulk operation: {}", e);
+                McpError::invalid_params(e.to_string(), None)
+            })?;
+
+        let filter = IssueFilter {
+            name: request.name.filter(|name| !name.trim().is_empty()),
+            labels: request.labels.unwrap_or_default(),
+            status: request
+                .status
+                .as_deref()
+                .map(str::parse::<IssueStatus>)
+                .transpose()
+                .map_err(|e| McpError::invalid_params(e, None))?,
+        };
+        if filter.is_unrestricted() {
+            return Err(McpError::invalid_params(
+                "A bulk operation needs a name pattern or labels to select issues".to_string(),
+                None,
+            ));
+        }
+
+        let add = request.add_labels.unwrap_or_default();
+        let remove = request.remove_labels.unwrap_or_default();
+        if request.action == "relabel" && add.is_empty() && remove.is_empty() {
+            return Err(McpError::invalid_params(
+                "Relabeling needs add_labels or remove_labels".to_string(),
+                None,
+            ));
+        }
+
+        if request.dry_run.unwrap_or(false) {
+            let issue_storage = context.issue_storage.read().await;
+            let matched = issue_storage
+                .select_issues(&filter)
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "select issues"))?;
+            let names: Vec<String> = matched.into_iter().map(|info| info.issue.name).collect();
+            return Ok(BaseToolImpl::create_success_response(format!(
+                "Would {} {} issues: {}",
+                request.action,
+                names.len(),
+                names.join(", ")
+            )));
+        }
+
+        let issue_storage = context.issue_storage.write().await;
+        let (result, verb) = match request.action.as_str() {
+            "complete" => (issue_storage.bulk_complete(&filter).await, "completed"),
+            "archive" => (issue_storage.bulk_archive(&filter).await, "archived"),
+            "delete" => (issue_storage.bulk_delete(&filter).await, "deleted"),
+            "relabel" => (
+                issue_storage.bulk_relabel(&filter, &add, &remove).await,
+                "relabeled",
+            ),
+            other => {
+                return Err(McpError::invalid_params(
+                    format!(
+                        "Unknown bulk action '{other}', expected one of complete, archive, delete, relabel"
+                    ),
+                    None,
+                ))
+            }
+        };
+        let result =
+            result.map_err(|e| McpErrorHandler::handle_error(e, "run bulk issue operation"))?;
+
+        tracing::info!(
+            "Bulk {} matched {} issues, {} failed",
+            request.action,
+            result.matched(),
+            result.failed.len()
+        );
+        Ok(BaseToolImpl::create_success_response(Self::format_result(
+            verb, &result,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swissarmyhammer::issues::BulkFailure;
+
+    #[test]
+    fn test_format_result_lists_failures() {
+        let result = BulkResult {
+            succeeded: vec!["a".to_string(), "b".to_string()],
+            failed: vec![BulkFailure {
+                name: "c".to_string(),
+                error: "Issue not found: c".to_string(),
+            }],
+        };
+        assert_eq!(
+            BulkIssuesTool::format_result("archived", &result),
+            "Matched 3 issues, archived 2: a, b\n❌ c: Issue not found: c"
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! Issue dependency graph tool for MCP operations
+//!
+//! This module provides the GraphIssuesTool for rendering and analysing the
+//! dependencies between issues through the MCP protocol.
+
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+use swissarmyhammer::issues::IssueGraph;
+
+/// Request structure for the issue dependency graph
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GraphIssuesRequest {
+    /// Output format (summary, mermaid, dot, json)
+    pub format: Option<String>,
+    /// Include completed issues in the graph
+    pub include_completed: Option<bool>,
+}
+
+/// Tool for rendering the issue dependency graph
+#[derive(Default)]
+pub struct GraphIssuesTool;
+
+impl GraphIssuesTool {
+    /// Creates a new instance of the GraphIssuesTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for GraphIssuesTool {
+    fn name(&self) -> &'static str {
+        "issue_graph"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "graph")
+            .unwrap_or("Render the dependency graph between issues and analyse it")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "format": {
+                    "type": "string",
+                    "description": "Output format - summary, mermaid, dot, or json",
+                    "default": "summary",
+                    "enum": ["summary", "mermaid", "dot", "json"]
+                },
+                "include_completed": {
+                    "type": "boolean",
+                    "description": "Include completed issues in the graph",
+                    "default": true
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: GraphIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        context
+            .rate_limiter
+            .check_rate_limit("unknown", "issue_graph", 1)
+            .map_err(|e| {
+                tracing::warn!("Rate limit exceeded for issue graph: {}", e);
+                McpError::invalid_params(e.to_string(), None)
+            })?;
+
+        let issue_storage = context.issue_storage.read().await;
+        let issue_infos = issue_storage
+            .list_issues_info()
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "list issues for graph"))?;
+        drop(issue_storage);
+
+        // Analyse the full graph so dependencies on completed issues still count
+        let full_graph = IssueGraph::from_issues(&issue_infos);
+        let analysis = full_graph.analyze();
+        let graph = if request.include_completed.unwrap_or(true) {
+            full_graph
+        } else {
+            full_graph.without_completed()
+        };
+
+        let format = request.format.unwrap_or_else(|| "summary".to_string());
+        let response = match format.as_str() {
+            "mermaid" => graph.to_mermaid(),
+            "dot" => graph.to_dot(),
+            "json" => serde_json::to_string_pretty(&serde_json::json!({
+                "graph": graph,
+                "analysis": analysis,
+            }))
+            .map_err(|e| {
+                McpError::internal_error(format!("Failed to serialize issue graph: {e}"), None)
+            })?,
+            "summary" => format!(
+                "📊 Issues: {} ({} dependencies)\n{}\n```mermaid\n{}```\n",
+                graph.nodes.len(),
+                graph.dependencies.len(),
+                analysis.format_report(),
+                graph.to_mermaid()
+            ),
+            other => {
+                return Err(McpError::invalid_params(
+                    format!("Unknown format '{other}', expected summary, mermaid, dot or json"),
+                    None,
+                ))
+            }
+        };
+
+        tracing::info!(
+            "Built issue graph with {} issues and {} cycles",
+            graph.nodes.len(),
+            analysis.cycles.len()
+        );
+        Ok(BaseToolImpl::create_success_response(&response))
+    }
+}
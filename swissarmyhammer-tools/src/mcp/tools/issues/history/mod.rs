@@ -0,0 +1,128 @@
+//! Issue history tool for MCP operations
+//!
+//! This module provides the IssueHistoryTool for showing the recorded changes
+//! of an issue through the MCP protocol.
+
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+use swissarmyhammer::issues::{IssueChange, IssueHistoryEntry};
+
+/// Request structure for showing issue history
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IssueHistoryRequest {
+    /// Name of the issue
+    pub name: String,
+    /// Include the diff of each content change
+    pub diffs: Option<bool>,
+    /// Return the entries as JSON
+    pub json: Option<bool>,
+}
+
+/// Tool for showing the history of an issue
+#[derive(Default)]
+pub struct IssueHistoryTool;
+
+impl IssueHistoryTool {
+    /// Creates a new instance of the IssueHistoryTool
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Format history entries as a timeline
+    fn format_history(name: &str, entries: &[IssueHistoryEntry], diffs: bool) -> String {
+        let mut result = format!("📜 History of {name} ({} revisions)\n", entries.len());
+        for entry in entries {
+            result.push_str(&format!(
+                "\n#{} {} {} — {}\n",
+                entry.revision,
+                entry.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+                entry.actor,
+                entry.change.summary()
+            ));
+            if diffs {
+                if let IssueChange::Edited { diff, .. } | IssueChange::Reverted { diff, .. } =
+                    &entry.change
+                {
+                    result.push_str(&format!("```diff\n{diff}```\n"));
+                }
+            }
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl McpTool for IssueHistoryTool {
+    fn name(&self) -> &'static str {
+        "issue_history"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "history")
+            .unwrap_or("Show the recorded changes of an issue")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Name of the issue"
+                },
+                "diffs": {
+                    "type": "boolean",
+                    "description": "Include the diff of each content change",
+                    "default": false
+                },
+                "json": {
+                    "type": "boolean",
+                    "description": "Return the history entries as JSON",
+                    "default": false
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: IssueHistoryRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(&request.name, "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+
+        context
+            .rate_limiter
+            .check_rate_limit("unknown", "issue_history", 1)
+            .map_err(|e| {
+                tracing::warn!("Rate limit exceeded for issue history: {}", e);
+                McpError::invalid_params(e.to_string(), None)
+            })?;
+
+        let issue_storage = context.issue_storage.read().await;
+        let entries = issue_storage
+            .issue_history(&request.name)
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "get issue history"))?;
+
+        let response = if request.json.unwrap_or(false) {
+            serde_json::to_string_pretty(&entries).map_err(|e| {
+                McpError::internal_error(format!("Failed to serialize issue history: {e}"), None)
+            })?
+        } else if entries.is_empty() {
+            format!("No history recorded for issue {}", request.name)
+        } else {
+            Self::format_history(&request.name, &entries, request.diffs.unwrap_or(false))
+        };
+
+        Ok(BaseToolImpl::create_success_response(&response))
+    }
+}
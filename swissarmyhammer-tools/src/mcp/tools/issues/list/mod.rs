@@ -8,7 +8,8 @@ use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
 use serde::{Deserialize, Serialize};
-use swissarmyhammer::issues::{Issue, IssueInfo};
+use std::collections::HashSet;
+use swissarmyhammer::issues::{Issue, IssueInfo, IssueMetadata, IssuePriority};
 
 /// Request structure for listing issues
 #[derive(Debug, Deserialize, Serialize)]
@@ -19,6 +20,21 @@ pub struct ListIssuesRequest {
     pub show_active: Option<bool>,
     /// Output format (table, json, markdown)
     pub format: Option<String>,
+    /// Only include issues with this priority
+    pub priority: Option<String>,
+    /// Only include issues carrying all of these labels
+    pub labels: Option<Vec<String>>,
+    /// Only include issues whose dependencies are all completed
+    pub ready: Option<bool>,
+}
+
+/// An issue as written by the JSON format, with its front matter metadata
+#[derive(Debug, Serialize)]
+struct ListedIssue<'a> {
+    #[serde(flatten)]
+    issue: &'a Issue,
+    #[serde(flatten)]
+    metadata: IssueMetadata,
 }
 
 /// Tool for listing issues
@@ -60,7 +76,11 @@ impl ListIssuesTool {
             result.push('\n');
             result.push_str("Active Issues:\n");
             for issue_info in active_issue_infos {
-                result.push_str(&format!("  🔄 {}\n", issue_info.issue.name));
+                result.push_str(&format!(
+                    "  🔄 {}{}\n",
+                    issue_info.issue.name,
+                    Self::format_metadata_summary(&issue_info.issue.metadata())
+                ));
             }
         }
 
@@ -78,6 +98,22 @@ impl ListIssuesTool {
         result
     }
 
+    /// Format priority and labels for a single table line
+    fn format_metadata_summary(metadata: &IssueMetadata) -> String {
+        let mut summary = String::new();
+        if let Some(priority) = metadata.priority {
+            summary.push_str(&format!(" [{priority}]"));
+        }
+        if !metadata.labels.is_empty() {
+            summary.push_str(&format!(" ({})", metadata.labels.join(", ")));
+        }
+        if !metadata.depends_on.is_empty() || !metadata.blocked_by.is_empty() {
+            let dependencies: Vec<_> = metadata.dependencies().collect();
+            summary.push_str(&format!(" ⛓ {}", dependencies.join(", ")));
+        }
+        summary
+    }
+
     /// Format issues as markdown
     fn format_as_markdown(issue_infos: &[IssueInfo]) -> String {
         let mut result = String::from("# Issues\n\n");
@@ -102,6 +138,28 @@ impl ListIssuesTool {
                 "- **Created**: {}\n",
                 issue_info.created_at.format("%Y-%m-%d")
             ));
+            let metadata = issue_info.issue.metadata();
+            if let Some(priority) = metadata.priority {
+                result.push_str(&format!("- **Priority**: {priority}\n"));
+            }
+            if !metadata.labels.is_empty() {
+                result.push_str(&format!("- **Labels**: {}\n", metadata.labels.join(", ")));
+            }
+            if let Some(estimate) = &metadata.estimate {
+                result.push_str(&format!("- **Estimate**: {estimate}\n"));
+            }
+            if !metadata.depends_on.is_empty() {
+                result.push_str(&format!(
+                    "- **Depends on**: {}\n",
+                    metadata.depends_on.join(", ")
+                ));
+            }
+            if !metadata.blocked_by.is_empty() {
+                result.push_str(&format!(
+                    "- **Blocked by**: {}\n",
+                    metadata.blocked_by.join(", ")
+                ));
+            }
             result.push_str(&format!(
                 "- **File**: {}\n\n",
                 issue_info.file_path.display()
@@ -149,6 +207,21 @@ impl McpTool for ListIssuesTool {
                     "description": "Output format - table, json, or markdown",
                     "default": "table",
                     "enum": ["table", "json", "markdown"]
+                },
+                "priority": {
+                    "type": "string",
+                    "description": "Only include issues with this priority",
+                    "enum": ["critical", "high", "medium", "low"]
+                },
+                "labels": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Only include issues carrying all of these labels"
+                },
+                "ready": {
+                    "type": "boolean",
+                    "description": "Only include issues whose dependencies are all completed",
+                    "default": false
                 }
             },
             "required": []
@@ -187,6 +260,19 @@ impl McpTool for ListIssuesTool {
         let show_completed = request.show_completed.unwrap_or(false);
         let show_active = request.show_active.unwrap_or(true);
         let format = request.format.unwrap_or_else(|| "table".to_string());
+        let priority = request
+            .priority
+            .as_deref()
+            .map(str::parse::<IssuePriority>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let labels = request.labels.unwrap_or_default();
+        let ready_only = request.ready.unwrap_or(false);
+        let completed_names: HashSet<String> = all_issue_infos
+            .iter()
+            .filter(|info| info.completed)
+            .map(|info| info.issue.name.clone())
+            .collect();
 
         // Filter issues based on criteria
         let filtered_issue_infos: Vec<_> = all_issue_infos
@@ -202,14 +288,25 @@ impl McpTool for ListIssuesTool {
                     true // default: show all
                 }
             })
+            .filter(|issue_info| {
+                if priority.is_none() && labels.is_empty() && !ready_only {
+                    return true;
+                }
+                let metadata = issue_info.issue.metadata();
+                priority.is_none_or(|priority| metadata.effective_priority() == priority)
+                    && labels.iter().all(|label| metadata.has_label(label))
+                    && (!ready_only || metadata.is_ready(&completed_names))
+            })
             .collect();
 
         let response = match format.as_str() {
             "json" => {
-                // Convert IssueInfo to Issue for JSON serialization
-                let issues_for_json: Vec<Issue> = filtered_issue_infos
+                let issues_for_json: Vec<ListedIssue> = filtered_issue_infos
                     .iter()
-                    .map(|info| info.issue.clone())
+                    .map(|info| ListedIssue {
+                        issue: &info.issue,
+                        metadata: info.issue.metadata(),
+                    })
                     .collect();
                 serde_json::to_string_pretty(&issues_for_json).map_err(|e| {
                     McpError::internal_error(format!("Failed to serialize issues: {e}"), None)
@@ -223,3 +320,144 @@ impl McpTool for ListIssuesTool {
         Ok(BaseToolImpl::create_success_response(&response))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::tool_handlers::ToolHandlers;
+    use std::sync::Arc;
+    use swissarmyhammer::issues::{FileSystemIssueStorage, IssueStorage};
+    use swissarmyhammer::memoranda::{mock_storage::MockMemoStorage, MemoStorage};
+    use tempfile::TempDir;
+    use tokio::sync::{Mutex, RwLock};
+
+    /// Build a context whose issue storage holds a few issues with front matter
+    async fn context_with_issues(temp_dir: &TempDir) -> ToolContext {
+        let storage = FileSystemIssueStorage::new(temp_dir.path().join("issues")).unwrap();
+        for (name, content) in [
+            ("setup", "---\npriority: high\n---\nSet up the database"),
+            (
+                "login",
+                "---\npriority: critical\nlabels: [bug, auth]\ndepends_on: [setup]\n---\nFix login",
+            ),
+            (
+                "logout",
+                "---\nlabels: [auth]\ndepends_on: [signup]\n---\nAdd logout",
+            ),
+            ("signup", "Add signup"),
+        ] {
+            storage
+                .create_issue(name.to_string(), content.to_string())
+                .await
+                .unwrap();
+        }
+        storage.complete_issue("setup").await.unwrap();
+
+        let memo_storage: Arc<RwLock<Box<dyn MemoStorage>>> =
+            Arc::new(RwLock::new(Box::new(MockMemoStorage::new())));
+        ToolContext::new(
+            Arc::new(ToolHandlers::new(memo_storage.clone())),
+            Arc::new(RwLock::new(Box::new(storage) as Box<dyn IssueStorage>)),
+            Arc::new(Mutex::new(None)),
+            memo_storage,
+            Arc::new(swissarmyhammer::common::rate_limiter::MockRateLimiter),
+        )
+    }
+
+    /// List issues as JSON and return their names in listing order
+    async fn list_names(context: &ToolContext, arguments: serde_json::Value) -> Vec<String> {
+        let mut arguments = arguments.as_object().unwrap().clone();
+        arguments.insert("format".to_string(), serde_json::json!("json"));
+        let result = ListIssuesTool::new()
+            .execute(arguments, context)
+            .await
+            .unwrap();
+        let issues: Vec<serde_json::Value> =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text).unwrap();
+        let mut names: Vec<String> = issues
+            .iter()
+            .map(|issue| issue["name"].as_str().unwrap().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn test_filter_by_priority() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = context_with_issues(&temp_dir).await;
+
+        assert_eq!(
+            list_names(&context, serde_json::json!({"priority": "critical"})).await,
+            vec!["login"]
+        );
+        assert_eq!(
+            list_names(
+                &context,
+                serde_json::json!({"priority": "high", "show_completed": true})
+            )
+            .await,
+            vec!["setup"]
+        );
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("priority".to_string(), serde_json::json!("urgent"));
+        assert!(ListIssuesTool::new()
+            .execute(arguments, &context)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_filter_by_labels() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = context_with_issues(&temp_dir).await;
+
+        assert_eq!(
+            list_names(&context, serde_json::json!({"labels": ["auth"]})).await,
+            vec!["login", "logout"]
+        );
+        assert_eq!(
+            list_names(&context, serde_json::json!({"labels": ["auth", "bug"]})).await,
+            vec!["login"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filter_ready_issues() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = context_with_issues(&temp_dir).await;
+
+        // logout waits on signup, which is still active
+        assert_eq!(
+            list_names(&context, serde_json::json!({"ready": true})).await,
+            vec!["login", "signup"]
+        );
+        assert_eq!(
+            list_names(&context, serde_json::json!({})).await,
+            vec!["login", "logout", "signup"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_json_output_includes_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let context = context_with_issues(&temp_dir).await;
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("format".to_string(), serde_json::json!("json"));
+        arguments.insert("labels".to_string(), serde_json::json!(["bug"]));
+        let result = ListIssuesTool::new()
+            .execute(arguments, &context)
+            .await
+            .unwrap();
+        let issues: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text).unwrap();
+
+        assert_eq!(issues[0]["name"], "login");
+        assert_eq!(issues[0]["priority"], "critical");
+        assert_eq!(issues[0]["labels"], serde_json::json!(["bug", "auth"]));
+        assert_eq!(issues[0]["depends_on"], serde_json::json!(["setup"]));
+        assert!(issues[0]["content"].as_str().unwrap().contains("Fix login"));
+    }
+}
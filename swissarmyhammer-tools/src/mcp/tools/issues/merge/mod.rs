@@ -9,6 +9,7 @@ use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
 use swissarmyhammer::common::create_abort_file_current_dir;
+use swissarmyhammer::issues::IssueChange;
 
 /// Tool for merging an issue work branch
 #[derive(Default)]
@@ -179,6 +180,23 @@ impl McpTool for MergeIssueTool {
                         let commit_info = match ops.get_last_commit_info() {
                             Ok(info) => {
                                 let parts: Vec<&str> = info.split('|').collect();
+                                let change = IssueChange::Merged {
+                                    branch: source_branch.clone(),
+                                    target: target_branch.clone(),
+                                    commit: parts[0].to_string(),
+                                };
+                                if let Err(e) = context
+                                    .issue_storage
+                                    .read()
+                                    .await
+                                    .record_issue_change(&issue_name, change)
+                                    .await
+                                {
+                                    tracing::warn!(
+                                        "Failed to record merge in issue history: {}",
+                                        e
+                                    );
+                                }
                                 if parts.len() >= 4 {
                                     format!(
                                         "\n\nMerge commit: {}\nMessage: {}\nAuthor: {}\nDate: {}",
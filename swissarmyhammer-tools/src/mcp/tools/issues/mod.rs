@@ -64,13 +64,27 @@
 //! - **update**: Modify existing issue content and metadata
 //! - **work**: Switch to or create a work branch for an issue
 //! - **merge**: Merge completed issue work back to source branch
+//! - **graph**: Render and analyse the dependency graph between issues
+//! - **sync**: Synchronize issues with a remote issue tracker
+//! - **history**: Show the recorded changes of an issue
+//! - **revert**: Restore the content of an issue from a prior revision
+//! - **reopen**: Move a completed or archived issue back to the active issues
+//! - **archive**: Move a stale issue into `./issues/archived/`
+//! - **bulk**: Complete, archive, delete or relabel issues matching a name glob or labels
 
 pub mod all_complete;
+pub mod archive;
+pub mod bulk;
 pub mod create;
+pub mod graph;
+pub mod history;
 pub mod list;
 pub mod mark_complete;
 pub mod merge;
+pub mod reopen;
+pub mod revert;
 pub mod show;
+pub mod sync;
 pub mod update;
 pub mod work;
 
@@ -86,4 +100,11 @@ pub fn register_issue_tools(registry: &mut ToolRegistry) {
     registry.register(update::UpdateIssueTool::new());
     registry.register(work::WorkIssueTool::new());
     registry.register(merge::MergeIssueTool::new());
+    registry.register(graph::GraphIssuesTool::new());
+    registry.register(sync::SyncIssuesTool::new());
+    registry.register(history::IssueHistoryTool::new());
+    registry.register(revert::RevertIssueTool::new());
+    registry.register(reopen::ReopenIssueTool::new());
+    registry.register(archive::ArchiveIssueTool::new());
+    registry.register(bulk::BulkIssuesTool::new());
 }
@@ -0,0 +1,86 @@
+//! Issue reopen tool for MCP operations
+//!
+//! This module provides the ReopenIssueTool for moving completed or archived
+//! issues back to the active issues through the MCP protocol.
+
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+
+/// Request structure for reopening an issue
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReopenIssueRequest {
+    /// Name of the issue
+    pub name: String,
+}
+
+/// Tool for reopening completed or archived issues
+#[derive(Default)]
+pub struct ReopenIssueTool;
+
+impl ReopenIssueTool {
+    /// Creates a new instance of the ReopenIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for ReopenIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_reopen"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "reopen")
+            .unwrap_or("Move a completed or archived issue back to the active issues")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Name of the completed or archived issue to reopen"
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: ReopenIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(&request.name, "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+
+        context
+            .rate_limiter
+            .check_rate_limit("unknown", "issue_reopen", 1)
+            .map_err(|e| {
+                tracing::warn!("Rate limit exceeded for issue reopen: {}", e);
+                McpError::invalid_params(e.to_string(), None)
+            })?;
+
+        let issue_storage = context.issue_storage.write().await;
+        match issue_storage.reopen_issue(&request.name).await {
+            Ok(issue) => {
+                tracing::info!("Reopened issue '{}'", issue.name);
+                Ok(create_success_response(format!(
+                    "Reopened issue {}",
+                    issue.name
+                )))
+            }
+            Err(e) => Err(McpErrorHandler::handle_error(e, "reopen issue")),
+        }
+    }
+}
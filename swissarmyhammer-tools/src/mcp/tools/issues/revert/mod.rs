@@ -0,0 +1,93 @@
+//! Issue revert tool for MCP operations
+//!
+//! This module provides the RevertIssueTool for restoring an issue's content
+//! from its history through the MCP protocol.
+
+use crate::mcp::responses::create_success_response;
+use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+
+/// Request structure for reverting an issue
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RevertIssueRequest {
+    /// Name of the issue
+    pub name: String,
+    /// Revision whose content to restore
+    pub revision: u32,
+}
+
+/// Tool for restoring a prior revision of an issue
+#[derive(Default)]
+pub struct RevertIssueTool;
+
+impl RevertIssueTool {
+    /// Creates a new instance of the RevertIssueTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for RevertIssueTool {
+    fn name(&self) -> &'static str {
+        "issue_revert"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "revert")
+            .unwrap_or("Restore the content an issue had at a prior revision")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Name of the issue"
+                },
+                "revision": {
+                    "type": "integer",
+                    "description": "Revision number from issue_history whose content to restore",
+                    "minimum": 1
+                }
+            },
+            "required": ["name", "revision"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: RevertIssueRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        McpValidation::validate_not_empty(&request.name, "issue name")
+            .map_err(|e| McpErrorHandler::handle_error(e, "validate issue name"))?;
+
+        context
+            .rate_limiter
+            .check_rate_limit("unknown", "issue_revert", 1)
+            .map_err(|e| {
+                tracing::warn!("Rate limit exceeded for issue revert: {}", e);
+                McpError::invalid_params(e.to_string(), None)
+            })?;
+
+        let issue_storage = context.issue_storage.write().await;
+        match issue_storage
+            .revert_issue(&request.name, request.revision)
+            .await
+        {
+            Ok(issue) => Ok(create_success_response(format!(
+                "Reverted issue {} to the content of revision {}",
+                issue.name, request.revision
+            ))),
+            Err(e) => Err(McpErrorHandler::handle_error(e, "revert issue")),
+        }
+    }
+}
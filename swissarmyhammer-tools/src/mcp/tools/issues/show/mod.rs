@@ -39,16 +39,55 @@ impl ShowIssueTool {
         }
     }
 
+    /// Format a dependency with its completion status
+    ///
+    /// `completed` is `None` when no issue with that name exists.
+    fn format_dependency(name: &str, completed: Option<bool>) -> String {
+        match completed {
+            Some(true) => format!("✅ {name}"),
+            Some(false) => format!("🔄 {name}"),
+            None => format!("❓ {name} (not found)"),
+        }
+    }
+
     /// Format issue for display
-    fn format_issue_display(issue_info: &IssueInfo) -> String {
-        let status = Self::format_issue_status(issue_info.completed);
+    ///
+    /// `dependencies` holds the completion status of each issue named in the
+    /// front matter's `depends_on` and `blocked_by` lists.
+    fn format_issue_display(
+        issue_info: &IssueInfo,
+        dependencies: &[(String, Option<bool>)],
+    ) -> String {
+        let status = if issue_info.archived {
+            "📦 Archived"
+        } else {
+            Self::format_issue_status(issue_info.completed)
+        };
+        let metadata = issue_info.issue.metadata();
 
         let mut result = format!("{} Issue: {}\n", status, issue_info.issue.name);
         result.push_str(&format!("📁 File: {}\n", issue_info.file_path.display()));
         result.push_str(&format!(
-            "📅 Created: {}\n\n",
+            "📅 Created: {}\n",
             issue_info.created_at.format("%Y-%m-%d %H:%M:%S")
         ));
+        if let Some(priority) = metadata.priority {
+            result.push_str(&format!("🔥 Priority: {priority}\n"));
+        }
+        if !metadata.labels.is_empty() {
+            result.push_str(&format!("🏷️ Labels: {}\n", metadata.labels.join(", ")));
+        }
+        if let Some(estimate) = &metadata.estimate {
+            result.push_str(&format!("⏱️ Estimate: {estimate}\n"));
+        }
+        if !dependencies.is_empty() {
+            let formatted: Vec<_> = dependencies
+                .iter()
+                .map(|(name, completed)| Self::format_dependency(name, *completed))
+                .collect();
+            result.push_str(&format!("⛓ Dependencies: {}\n", formatted.join(", ")));
+        }
+        result.push('\n');
         result.push_str(&issue_info.issue.content);
 
         result
@@ -183,7 +222,20 @@ impl McpTool for ShowIssueTool {
         let response = if request.raw.unwrap_or(false) {
             issue_info.issue.content
         } else {
-            Self::format_issue_display(&issue_info)
+            let metadata = issue_info.issue.metadata();
+            let mut dependencies = Vec::new();
+            if metadata.dependencies().next().is_some() {
+                let issue_storage = context.issue_storage.read().await;
+                for name in metadata.dependencies() {
+                    let completed = issue_storage
+                        .get_issue_info(name)
+                        .await
+                        .ok()
+                        .map(|info| info.completed);
+                    dependencies.push((name.to_string(), completed));
+                }
+            }
+            Self::format_issue_display(&issue_info, &dependencies)
         };
 
         tracing::info!("Showed issue {}", issue_info.issue.name);
@@ -0,0 +1,101 @@
+//! Issue sync tool for MCP operations
+//!
+//! This module provides the SyncIssuesTool for synchronizing local issues with
+//! a remote issue tracker through the MCP protocol.
+
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+use swissarmyhammer::issues::ConflictStrategy;
+
+/// Request structure for syncing issues
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SyncIssuesRequest {
+    /// How to resolve issues changed on both sides (report, local, remote)
+    pub strategy: Option<String>,
+}
+
+/// Tool for syncing issues with a remote issue tracker
+#[derive(Default)]
+pub struct SyncIssuesTool;
+
+impl SyncIssuesTool {
+    /// Creates a new instance of the SyncIssuesTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for SyncIssuesTool {
+    fn name(&self) -> &'static str {
+        "issue_sync"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("issues", "sync")
+            .unwrap_or("Synchronize local issues with the configured remote issue tracker")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "strategy": {
+                    "type": "string",
+                    "description": "How to resolve issues changed both locally and remotely - report, local, or remote",
+                    "default": "report",
+                    "enum": ["report", "local", "remote"]
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: SyncIssuesRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        context
+            .rate_limiter
+            .check_rate_limit("unknown", "issue_sync", 1)
+            .map_err(|e| {
+                tracing::warn!("Rate limit exceeded for issue sync: {}", e);
+                McpError::invalid_params(e.to_string(), None)
+            })?;
+
+        let strategy: ConflictStrategy = match request.strategy.as_deref() {
+            Some(strategy) => strategy
+                .parse()
+                .map_err(|e: String| McpError::invalid_params(e, None))?,
+            None => ConflictStrategy::default(),
+        };
+
+        let issue_storage = context.issue_storage.write().await;
+        let report = issue_storage
+            .sync(strategy)
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "sync issues"))?;
+
+        let response = match report {
+            Some(report) => {
+                tracing::info!(
+                    "Synced issues: {} pushed, {} pulled, {} conflicts",
+                    report.pushed.len() + report.created_remote.len(),
+                    report.pulled.len() + report.created_local.len(),
+                    report.conflicts.len()
+                );
+                report.summary()
+            }
+            None => "No remote issue tracker is configured. Add an [issues.remote] section to sah.toml to enable syncing.".to_string(),
+        };
+
+        Ok(BaseToolImpl::create_success_response(&response))
+    }
+}
@@ -10,6 +10,7 @@ use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
 use swissarmyhammer::common::create_abort_file_current_dir;
+use swissarmyhammer::issues::IssueChange;
 
 /// Tool for switching to work on an issue
 #[derive(Default)]
@@ -97,9 +98,18 @@ impl McpTool for WorkIssueTool {
 
             match git_ops.as_mut() {
                 Some(ops) => match ops.create_work_branch(&branch_name) {
-                    Ok(branch_name) => Ok(create_success_response(format!(
-                        "Switched to work branch: {branch_name}"
-                    ))),
+                    Ok(branch_name) => {
+                        let change = IssueChange::BranchLinked {
+                            branch: branch_name.clone(),
+                        };
+                        if let Err(e) = issue_storage.record_issue_change(&issue.name, change).await
+                        {
+                            tracing::warn!("Failed to record work branch in issue history: {}", e);
+                        }
+                        Ok(create_success_response(format!(
+                            "Switched to work branch: {branch_name}"
+                        )))
+                    }
                     Err(e) => Err(McpErrorHandler::handle_error(e, "create work branch")),
                 },
                 None => Err(McpError::internal_error(
@@ -0,0 +1,151 @@
+//! Memo diff tool for MCP operations
+//!
+//! This module provides the DiffMemoTool for comparing two revisions of a memo.
+
+use crate::mcp::memo_types::DiffMemoRequest;
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::MemoId;
+
+/// Tool for comparing two revisions of a memo
+#[derive(Default)]
+pub struct DiffMemoTool;
+
+impl DiffMemoTool {
+    /// Creates a new instance of the DiffMemoTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for DiffMemoTool {
+    fn name(&self) -> &'static str {
+        "memo_diff"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "diff")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "ID of the memo to compare"
+                },
+                "from": {
+                    "type": "integer",
+                    "description": "Older revision; defaults to the revision before `to`",
+                    "minimum": 1
+                },
+                "to": {
+                    "type": "integer",
+                    "description": "Newer revision; defaults to the latest revision",
+                    "minimum": 1
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: DiffMemoRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        tracing::debug!(
+            "Comparing memo {} revisions {:?}..{:?}",
+            request.id,
+            request.from,
+            request.to
+        );
+
+        let memo_id = MemoId::from_string(request.id.clone()).map_err(|_| {
+            McpError::invalid_params(format!("Invalid memo ID format: {}", request.id), None)
+        })?;
+
+        let memo_storage = context.memo_storage.read().await;
+        let diff = memo_storage
+            .diff_memo(&memo_id, request.from, request.to)
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "diff memo revisions"))?;
+
+        if diff.is_empty() {
+            return Ok(BaseToolImpl::create_success_response(format!(
+                "The compared revisions of memo {memo_id} are identical"
+            )));
+        }
+        Ok(BaseToolImpl::create_success_response(diff))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[test]
+    fn test_diff_memo_tool_new() {
+        let tool = DiffMemoTool::new();
+        assert_eq!(tool.name(), "memo_diff");
+        assert!(!tool.description().is_empty());
+        assert_eq!(tool.schema()["required"], serde_json::json!(["id"]));
+    }
+
+    #[tokio::test]
+    async fn test_diff_memo_tool_execute() {
+        let tool = DiffMemoTool::new();
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        let memo = memo_storage
+            .create_memo("Plan".to_string(), "step one\nstep two\n".to_string())
+            .await
+            .unwrap();
+        memo_storage
+            .update_memo(&memo.id, "step one\nstep three\n".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage); // Release the lock
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!(memo.id.to_string()));
+        let text = tool
+            .execute(arguments.clone(), &context)
+            .await
+            .unwrap()
+            .content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text.contains(&format!("--- a/{}@1", memo.id)));
+        assert!(text.contains(&format!("+++ b/{}@2", memo.id)));
+        assert!(text.contains("-step two"));
+        assert!(text.contains("+step three"));
+
+        arguments.insert("from".to_string(), serde_json::json!(2));
+        let text = tool
+            .execute(arguments.clone(), &context)
+            .await
+            .unwrap()
+            .content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text.ends_with("are identical"));
+
+        arguments.insert("to".to_string(), serde_json::json!(9));
+        assert!(tool.execute(arguments, &context).await.is_err());
+    }
+}
@@ -0,0 +1,161 @@
+//! Memo export tool for MCP operations
+//!
+//! This module provides the ExportMemoTool for writing every memo out as an
+//! Obsidian vault, a markdown tree or a JSON bundle.
+
+use crate::mcp::memo_types::ExportMemosRequest;
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use std::path::PathBuf;
+use swissarmyhammer::memoranda::exchange::export_memos;
+use swissarmyhammer::memoranda::MemoExportFormat;
+
+/// Tool for exporting memos
+#[derive(Default)]
+pub struct ExportMemoTool;
+
+impl ExportMemoTool {
+    /// Creates a new instance of the ExportMemoTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for ExportMemoTool {
+    fn name(&self) -> &'static str {
+        "memo_export"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "export")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "format": {
+                    "type": "string",
+                    "description": "Export layout",
+                    "enum": ["obsidian", "markdown", "json"],
+                    "default": "markdown"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Directory to write markdown files into, or the JSON bundle file"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: ExportMemosRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let format = request
+            .format
+            .as_deref()
+            .map(str::parse::<MemoExportFormat>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?
+            .unwrap_or_default();
+        if request.path.trim().is_empty() {
+            return Err(McpError::invalid_params(
+                "Export path cannot be empty".to_string(),
+                None,
+            ));
+        }
+        let destination = PathBuf::from(&request.path);
+
+        tracing::debug!("Exporting memos as {} to {}", format, destination.display());
+
+        let memo_storage = context.memo_storage.read().await;
+        let memos = memo_storage
+            .list_memos()
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "list memos"))?;
+        drop(memo_storage);
+
+        let report = export_memos(&memos, format, &destination)
+            .map_err(|e| McpErrorHandler::handle_error(e, "export memos"))?;
+
+        tracing::info!(
+            "Exported {} memos to {}",
+            report.memos,
+            destination.display()
+        );
+        Ok(BaseToolImpl::create_success_response(format!(
+            "Exported {} memos as {} to {} ({} files)",
+            report.memos,
+            format,
+            destination.display(),
+            report.files.len()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_export_memo_tool_new() {
+        let tool = ExportMemoTool::new();
+        assert_eq!(tool.name(), "memo_export");
+        assert!(!tool.description().is_empty());
+        assert_eq!(tool.schema()["required"], serde_json::json!(["path"]));
+    }
+
+    #[tokio::test]
+    async fn test_export_memo_tool_execute() {
+        let tool = ExportMemoTool::new();
+        let context = create_test_context().await;
+        let temp_dir = TempDir::new().unwrap();
+
+        let memo_storage = context.memo_storage.write().await;
+        memo_storage
+            .create_memo("Plan".to_string(), "Ship it".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage); // Release the lock
+
+        let vault = temp_dir.path().join("vault");
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("format".to_string(), serde_json::json!("obsidian"));
+        arguments.insert(
+            "path".to_string(),
+            serde_json::json!(vault.to_string_lossy()),
+        );
+        let text = tool.execute(arguments, &context).await.unwrap().content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text.starts_with("Exported 1 memos as obsidian to "));
+        let note = std::fs::read_to_string(vault.join("Plan.md")).unwrap();
+        assert!(note.contains("aliases:"));
+        assert!(note.ends_with("---\nShip it"));
+    }
+
+    #[tokio::test]
+    async fn test_export_memo_tool_rejects_unknown_format() {
+        let tool = ExportMemoTool::new();
+        let context = create_test_context().await;
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("format".to_string(), serde_json::json!("html"));
+        arguments.insert("path".to_string(), serde_json::json!("out"));
+        assert!(tool.execute(arguments, &context).await.is_err());
+    }
+}
@@ -66,12 +66,18 @@ impl McpTool for GetMemoTool {
         match memo_storage.get_memo(&memo_id).await {
             Ok(memo) => {
                 tracing::info!("Retrieved memo {}", memo.id);
+                let tags = if memo.tags.is_empty() {
+                    String::new()
+                } else {
+                    format!("\nTags: {}", memo.tags.join(", "))
+                };
                 Ok(BaseToolImpl::create_success_response(format!(
-                    "Memo found:\n\nID: {}\nTitle: {}\nCreated: {}\nUpdated: {}\n\nContent:\n{}",
+                    "Memo found:\n\nID: {}\nTitle: {}\nCreated: {}\nUpdated: {}{}\n\nContent:\n{}",
                     memo.id,
                     memo.title,
                     crate::mcp::shared_utils::McpFormatter::format_timestamp(memo.created_at),
                     crate::mcp::shared_utils::McpFormatter::format_timestamp(memo.updated_at),
+                    tags,
                     memo.content
                 )))
             }
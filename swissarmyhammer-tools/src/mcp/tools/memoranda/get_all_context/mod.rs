@@ -1,21 +1,98 @@
 //! Memo get all context tool for MCP operations
 //!
-//! This module provides the GetAllContextMemoTool for retrieving all memo content formatted for AI context consumption.
+//! This module provides the GetAllContextMemoTool for retrieving all memo content formatted for AI context consumption,
+//! only a memo and the memos linked to it, or the memos most relevant to a task packed into a token budget.
 
 use crate::mcp::memo_types::GetAllContextRequest;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
+use std::sync::Arc;
+use swissarmyhammer::memoranda::{
+    ContextInclusion, Memo, MemoContext, MemoId, RankedContextOptions, TokenCounter,
+};
 
 /// Tool for getting all memo content formatted for AI context consumption
 #[derive(Default)]
-pub struct GetAllContextMemoTool;
+pub struct GetAllContextMemoTool {
+    /// Counter for token budgets; the process-wide tokenizer when unset
+    token_counter: Option<Arc<TokenCounter>>,
+}
 
 impl GetAllContextMemoTool {
+    /// Links followed from the requested memo when no depth is given
+    const DEFAULT_LINK_DEPTH: usize = 1;
+
     /// Creates a new instance of the GetAllContextMemoTool
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Count tokens with `counter` instead of the process-wide tokenizer
+    pub fn with_token_counter(mut self, counter: Arc<TokenCounter>) -> Self {
+        self.token_counter = Some(counter);
+        self
+    }
+
+    /// Format memos for context, separated by a rule
+    fn format_memos(memos: &[Memo]) -> String {
+        memos
+            .iter()
+            .map(|memo| {
+                format!(
+                    "=== {} (ID: {}) ===\nCreated: {}\nUpdated: {}\n\n{}",
+                    memo.title,
+                    memo.id,
+                    crate::mcp::shared_utils::McpFormatter::format_timestamp(memo.created_at),
+                    crate::mcp::shared_utils::McpFormatter::format_timestamp(memo.updated_at),
+                    memo.content
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(&format!("\n\n{}\n\n", "=".repeat(80)))
+    }
+
+    /// Format a ranked context followed by what was included and dropped
+    fn format_ranked_context(query: Option<&str>, context: &MemoContext) -> String {
+        let budget = match context.max_tokens {
+            Some(max_tokens) => format!("{} of {max_tokens} tokens", context.used_tokens),
+            None => format!("{} tokens", context.used_tokens),
+        };
+        let subject = match query {
+            Some(query) => format!("Memo context for '{query}'"),
+            None => "Memo context".to_string(),
+        };
+        let included: Vec<String> = context
+            .included()
+            .map(|entry| match entry.inclusion {
+                ContextInclusion::Truncated => format!(
+                    "{} (truncated, {} of {} tokens)",
+                    entry.title, entry.included_tokens, entry.tokens
+                ),
+                _ => format!("{} ({} tokens)", entry.title, entry.tokens),
+            })
+            .collect();
+        let dropped: Vec<String> = context
+            .dropped()
+            .map(|entry| format!("{} ({} tokens)", entry.title, entry.tokens))
+            .collect();
+
+        let mut response = format!(
+            "{subject} ({} of {} memos, {budget}):\n\n{}\n\nIncluded: {}",
+            included.len(),
+            context.entries.len(),
+            context.text,
+            if included.is_empty() {
+                "none".to_string()
+            } else {
+                included.join(", ")
+            }
+        );
+        if !dropped.is_empty() {
+            response.push_str(&format!("\nDropped: {}", dropped.join(", ")));
+        }
+        response
     }
 }
 
@@ -33,7 +110,27 @@ impl McpTool for GetAllContextMemoTool {
     fn schema(&self) -> serde_json::Value {
         serde_json::json!({
             "type": "object",
-            "properties": {},
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Only include this memo and the memos linked to or from it"
+                },
+                "depth": {
+                    "type": "integer",
+                    "description": "How many links to follow from the memo given by id",
+                    "minimum": 0,
+                    "default": 1
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Query or task description to rank memos by relevance and recency"
+                },
+                "max_tokens": {
+                    "type": "integer",
+                    "description": "Token budget; the best ranked memos are packed into it and the tail is truncated or dropped",
+                    "minimum": 1
+                }
+            },
             "required": []
         })
     }
@@ -47,11 +144,81 @@ impl McpTool for GetAllContextMemoTool {
         arguments: serde_json::Map<String, serde_json::Value>,
         context: &ToolContext,
     ) -> std::result::Result<CallToolResult, McpError> {
-        let _request: GetAllContextRequest = BaseToolImpl::parse_arguments(arguments)?;
+        let request: GetAllContextRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let memo_storage = context.memo_storage.read().await;
+
+        if let Some(id) = request.id {
+            let depth = request.depth.unwrap_or(Self::DEFAULT_LINK_DEPTH);
+            tracing::debug!("Getting memo context around {} at depth {}", id, depth);
+
+            let memo_id = MemoId::from_string(id.clone()).map_err(|_| {
+                McpError::invalid_params(format!("Invalid memo ID format: {id}"), None)
+            })?;
+            let memos = memo_storage
+                .memo_neighbourhood(&memo_id, depth)
+                .await
+                .map_err(|e| {
+                    crate::mcp::shared_utils::McpErrorHandler::handle_error(
+                        e,
+                        "get memo neighbourhood",
+                    )
+                })?;
+
+            let linked = memos.len() - 1;
+            let plural_suffix = if linked == 1 { "" } else { "s" };
+            return Ok(BaseToolImpl::create_success_response(format!(
+                "Memo context for '{}' and {linked} linked memo{plural_suffix} (depth {depth}):\n\n{}",
+                memos[0].title,
+                Self::format_memos(&memos)
+            )));
+        }
+
+        if request.query.is_some() || request.max_tokens.is_some() {
+            let query = request.query.filter(|query| !query.trim().is_empty());
+            tracing::debug!(
+                "Getting ranked memo context for {:?} within {:?} tokens",
+                query,
+                request.max_tokens
+            );
+
+            let options = RankedContextOptions {
+                query: query.clone(),
+                max_tokens: request.max_tokens,
+                ..Default::default()
+            };
+            let counter = self
+                .token_counter
+                .clone()
+                .unwrap_or_else(TokenCounter::shared);
+            let context = memo_storage
+                .ranked_context(&options, &counter)
+                .await
+                .map_err(|e| {
+                    crate::mcp::shared_utils::McpErrorHandler::handle_error(
+                        e,
+                        "get ranked memo context",
+                    )
+                })?;
+            if context.entries.is_empty() {
+                return Ok(BaseToolImpl::create_success_response(
+                    "No memos available".to_string(),
+                ));
+            }
+
+            tracing::info!(
+                "Packed {} of {} memos into {} tokens",
+                context.included().count(),
+                context.entries.len(),
+                context.used_tokens
+            );
+            return Ok(BaseToolImpl::create_success_response(
+                Self::format_ranked_context(query.as_deref(), &context),
+            ));
+        }
 
         tracing::debug!("Getting all memo context");
 
-        let memo_storage = context.memo_storage.read().await;
         match memo_storage.list_memos().await {
             Ok(memos) => {
                 tracing::info!("Retrieved {} memos for context", memos.len());
@@ -64,24 +231,7 @@ impl McpTool for GetAllContextMemoTool {
                     let mut sorted_memos = memos;
                     sorted_memos.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
 
-                    let context = sorted_memos
-                        .iter()
-                        .map(|memo| {
-                            format!(
-                                "=== {} (ID: {}) ===\nCreated: {}\nUpdated: {}\n\n{}",
-                                memo.title,
-                                memo.id,
-                                crate::mcp::shared_utils::McpFormatter::format_timestamp(
-                                    memo.created_at
-                                ),
-                                crate::mcp::shared_utils::McpFormatter::format_timestamp(
-                                    memo.updated_at
-                                ),
-                                memo.content
-                            )
-                        })
-                        .collect::<Vec<_>>()
-                        .join(&format!("\n\n{}\n\n", "=".repeat(80)));
+                    let context = Self::format_memos(&sorted_memos);
 
                     let memo_count = sorted_memos.len();
                     let plural_suffix = if memo_count == 1 { "" } else { "s" };
@@ -116,7 +266,10 @@ mod tests {
         let schema = tool.schema();
 
         assert_eq!(schema["type"], "object");
-        assert_eq!(schema["properties"], serde_json::json!({}));
+        assert_eq!(schema["properties"]["id"]["type"], "string");
+        assert_eq!(schema["properties"]["depth"]["type"], "integer");
+        assert_eq!(schema["properties"]["query"]["type"], "string");
+        assert_eq!(schema["properties"]["max_tokens"]["type"], "integer");
         assert_eq!(schema["required"], serde_json::json!([]));
     }
 
@@ -220,6 +373,105 @@ mod tests {
         // Should use singular form "memo" not "memos" for single result
     }
 
+    #[tokio::test]
+    async fn test_get_all_context_memo_tool_execute_neighbourhood() {
+        let tool = GetAllContextMemoTool::new();
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        let design = memo_storage
+            .create_memo("Design".to_string(), "The design".to_string())
+            .await
+            .unwrap();
+        memo_storage
+            .create_memo("Review".to_string(), "Reviewed [[Design]]".to_string())
+            .await
+            .unwrap();
+        memo_storage
+            .create_memo("Unrelated".to_string(), "Other notes".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage); // Release the lock
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!(design.id.to_string()));
+
+        let call_result = tool.execute(arguments, &context).await.unwrap();
+        let text = call_result.content[0].as_text().unwrap().text.clone();
+        assert!(text.starts_with("Memo context for 'Design' and 1 linked memo (depth 1)"));
+        assert!(text.contains("Reviewed [[Design]]"));
+        assert!(!text.contains("Other notes"));
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!("missing"));
+        assert!(tool.execute(arguments, &context).await.is_err());
+    }
+
+    #[test]
+    fn test_format_ranked_context() {
+        use swissarmyhammer::memoranda::ContextEntry;
+
+        let entry = |title: &str, tokens, included_tokens, inclusion| ContextEntry {
+            id: MemoId::from_filename(title),
+            title: title.to_string(),
+            score: 0.5,
+            tokens,
+            included_tokens,
+            inclusion,
+        };
+        let context = MemoContext {
+            text: "...".to_string(),
+            entries: vec![
+                entry("Deploy", 40, 40, ContextInclusion::Full),
+                entry("Release", 300, 58, ContextInclusion::Truncated),
+                entry("Groceries", 20, 0, ContextInclusion::Dropped),
+            ],
+            used_tokens: 98,
+            max_tokens: Some(100),
+        };
+
+        assert_eq!(
+            GetAllContextMemoTool::format_ranked_context(Some("ship it"), &context),
+            "Memo context for 'ship it' (2 of 3 memos, 98 of 100 tokens):\n\n...\n\n\
+             Included: Deploy (40 tokens), Release (truncated, 58 of 300 tokens)\n\
+             Dropped: Groceries (20 tokens)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_all_context_memo_tool_execute_ranked() {
+        let tool =
+            GetAllContextMemoTool::new().with_token_counter(Arc::new(TokenCounter::Estimate));
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        memo_storage
+            .create_memo(
+                "Login bug".to_string(),
+                "The login redirect loops".to_string(),
+            )
+            .await
+            .unwrap();
+        memo_storage
+            .create_memo("Groceries".to_string(), "Milk and bread".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage); // Release the lock
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("query".to_string(), serde_json::json!("fix login redirect"));
+        arguments.insert("max_tokens".to_string(), serde_json::json!(100_000));
+
+        let call_result = tool.execute(arguments, &context).await.unwrap();
+        let text = call_result.content[0].as_text().unwrap().text.clone();
+        assert!(text.starts_with("Memo context for 'fix login redirect' (2 of 2 memos"));
+        assert!(
+            text.find("The login redirect loops").unwrap() < text.find("Milk and bread").unwrap()
+        );
+        assert!(text.contains("Included: Login bug ("));
+        assert!(!text.contains("Dropped:"));
+    }
+
     #[tokio::test]
     async fn test_get_all_context_memo_tool_execute_with_invalid_arguments() {
         let tool = GetAllContextMemoTool::new();
@@ -0,0 +1,195 @@
+//! Memo history tool for MCP operations
+//!
+//! This module provides the HistoryMemoTool for showing the revisions of a memo,
+//! or the memos waiting in the trash.
+
+use crate::mcp::memo_types::MemoHistoryRequest;
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::{MemoId, MemoRevision, TrashedMemo};
+
+/// Tool for showing memo revisions and the trash
+#[derive(Default)]
+pub struct HistoryMemoTool;
+
+impl HistoryMemoTool {
+    /// Creates a new instance of the HistoryMemoTool
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Format the revisions of a memo, one per line
+    fn format_history(id: &MemoId, history: &[MemoRevision]) -> String {
+        let mut response = format!("History of memo {id} ({} revisions):", history.len());
+        for revision in history {
+            response.push_str(&format!(
+                "\n- #{} {} {} ({} chars)",
+                revision.revision,
+                revision.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+                revision.change.summary(),
+                revision.content.len()
+            ));
+        }
+        response
+    }
+
+    /// Format the memos in the trash, one per line
+    fn format_trash(trashed: &[TrashedMemo]) -> String {
+        if trashed.is_empty() {
+            return "The memo trash is empty".to_string();
+        }
+
+        let mut response = format!("Memos in the trash ({}):", trashed.len());
+        for memo in trashed {
+            response.push_str(&format!(
+                "\n- {} (ID: {}) deleted {}, removed after {}",
+                memo.title,
+                memo.id,
+                memo.deleted_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                memo.expires_at.format("%Y-%m-%d")
+            ));
+        }
+        response
+    }
+}
+
+#[async_trait]
+impl McpTool for HistoryMemoTool {
+    fn name(&self) -> &'static str {
+        "memo_history"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "history")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Memo whose revisions to show; lists the trash when omitted"
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: MemoHistoryRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let memo_storage = context.memo_storage.read().await;
+
+        let Some(id) = request.id else {
+            tracing::debug!("Listing the memo trash");
+            let trashed = memo_storage
+                .list_trash()
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "list memo trash"))?;
+            return Ok(BaseToolImpl::create_success_response(Self::format_trash(
+                &trashed,
+            )));
+        };
+
+        tracing::debug!("Getting history of memo {}", id);
+        let memo_id = MemoId::from_string(id.clone())
+            .map_err(|_| McpError::invalid_params(format!("Invalid memo ID format: {id}"), None))?;
+        let history = memo_storage
+            .memo_history(&memo_id)
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "get memo history"))?;
+
+        Ok(BaseToolImpl::create_success_response(Self::format_history(
+            &memo_id, &history,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[test]
+    fn test_history_memo_tool_new() {
+        let tool = HistoryMemoTool::new();
+        assert_eq!(tool.name(), "memo_history");
+        assert!(!tool.description().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_history_memo_tool_execute() {
+        let tool = HistoryMemoTool::new();
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        let memo = memo_storage
+            .create_memo("Plan".to_string(), "first".to_string())
+            .await
+            .unwrap();
+        memo_storage
+            .update_memo(&memo.id, "second".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage); // Release the lock
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!(memo.id.to_string()));
+        let text = tool.execute(arguments, &context).await.unwrap().content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text.starts_with(&format!("History of memo {} (2 revisions):", memo.id)));
+        assert!(text.contains(" created (5 chars)"));
+        assert!(text.contains(" updated (6 chars)"));
+    }
+
+    #[tokio::test]
+    async fn test_history_memo_tool_lists_trash() {
+        let tool = HistoryMemoTool::new();
+        let context = create_test_context().await;
+
+        let text = tool
+            .execute(serde_json::Map::new(), &context)
+            .await
+            .unwrap()
+            .content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert_eq!(text, "The memo trash is empty");
+
+        let memo_storage = context.memo_storage.write().await;
+        let memo = memo_storage
+            .create_memo("Plan".to_string(), "content".to_string())
+            .await
+            .unwrap();
+        memo_storage.delete_memo(&memo.id).await.unwrap();
+        drop(memo_storage); // Release the lock
+
+        let text = tool
+            .execute(serde_json::Map::new(), &context)
+            .await
+            .unwrap()
+            .content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text.starts_with(&format!(
+            "Memos in the trash (1):\n- Plan (ID: {}) deleted",
+            memo.id
+        )));
+    }
+}
@@ -0,0 +1,217 @@
+//! Memo import tool for MCP operations
+//!
+//! This module provides the ImportMemoTool for reading memos from an export,
+//! an Obsidian vault or a directory of markdown files.
+
+use crate::mcp::memo_types::ImportMemosRequest;
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use std::path::PathBuf;
+use swissarmyhammer::memoranda::exchange::{import_memos, ImportOutcome};
+use swissarmyhammer::memoranda::ImportReport;
+
+/// Tool for importing memos
+#[derive(Default)]
+pub struct ImportMemoTool;
+
+impl ImportMemoTool {
+    /// Creates a new instance of the ImportMemoTool
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Format an import report, one memo per line
+    fn format_report(report: &ImportReport) -> String {
+        let (created, duplicates, failed) = (
+            report.created().count(),
+            report.duplicates().count(),
+            report.failed().count(),
+        );
+        let mut response = if report.dry_run {
+            format!(
+                "Dry run: would import {created} memos, skipping {duplicates} duplicates and {failed} failures"
+            )
+        } else {
+            format!(
+                "Imported {created} memos, skipped {duplicates} duplicates and {failed} failures"
+            )
+        };
+
+        for entry in &report.entries {
+            let line = match &entry.outcome {
+                ImportOutcome::Created(id) => format!("+ {} (ID: {id})", entry.title),
+                ImportOutcome::Duplicate { existing, reason } => {
+                    format!("= {} (same {reason} as {existing})", entry.title)
+                }
+                ImportOutcome::Failed(error) => {
+                    format!("! {} ({}): {error}", entry.title, entry.source.display())
+                }
+            };
+            response.push('\n');
+            response.push_str(&line);
+        }
+        response
+    }
+}
+
+#[async_trait]
+impl McpTool for ImportMemoTool {
+    fn name(&self) -> &'static str {
+        "memo_import"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "import")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory of markdown files, or a JSON bundle file"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Only report what would be imported",
+                    "default": false
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: ImportMemosRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let source = PathBuf::from(&request.path);
+        let dry_run = request.dry_run.unwrap_or(false);
+        tracing::debug!(
+            "Importing memos from {} (dry run: {})",
+            source.display(),
+            dry_run
+        );
+
+        let memo_storage = context.memo_storage.write().await;
+        let report = import_memos(&**memo_storage, &source, dry_run)
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "import memos"))?;
+
+        tracing::info!(
+            "Imported {} memos from {}",
+            report.created().count(),
+            source.display()
+        );
+        Ok(BaseToolImpl::create_success_response(Self::format_report(
+            &report,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_import_memo_tool_new() {
+        let tool = ImportMemoTool::new();
+        assert_eq!(tool.name(), "memo_import");
+        assert!(!tool.description().is_empty());
+        assert_eq!(tool.schema()["required"], serde_json::json!(["path"]));
+    }
+
+    #[tokio::test]
+    async fn test_import_memo_tool_dry_run_and_import() {
+        let tool = ImportMemoTool::new();
+        let context = create_test_context().await;
+        let temp_dir = TempDir::new().unwrap();
+
+        let memo_storage = context.memo_storage.write().await;
+        let existing = memo_storage
+            .create_memo("Plan".to_string(), "Ship it".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage); // Release the lock
+
+        std::fs::write(temp_dir.path().join("Plan.md"), "Another plan").unwrap();
+        std::fs::write(
+            temp_dir.path().join("Research.md"),
+            "---\ntitle: Auth Research\n---\nTokens expire",
+        )
+        .unwrap();
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert(
+            "path".to_string(),
+            serde_json::json!(temp_dir.path().to_string_lossy()),
+        );
+        arguments.insert("dry_run".to_string(), serde_json::json!(true));
+        let text = tool
+            .execute(arguments.clone(), &context)
+            .await
+            .unwrap()
+            .content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text
+            .starts_with("Dry run: would import 1 memos, skipping 1 duplicates and 0 failures\n"));
+        assert!(text.contains(&format!("= Plan (same title as {})", existing.id)));
+        assert!(text.contains("+ Auth Research (ID: "));
+        assert_eq!(
+            context
+                .memo_storage
+                .read()
+                .await
+                .list_memos()
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        arguments.insert("dry_run".to_string(), serde_json::json!(false));
+        let text = tool.execute(arguments, &context).await.unwrap().content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text.starts_with("Imported 1 memos, skipped 1 duplicates"));
+        assert_eq!(
+            context
+                .memo_storage
+                .read()
+                .await
+                .list_memos()
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_memo_tool_missing_source() {
+        let tool = ImportMemoTool::new();
+        let context = create_test_context().await;
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert(
+            "path".to_string(),
+            serde_json::json!("/nonexistent/memo/export"),
+        );
+        assert!(tool.execute(arguments, &context).await.is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! Memo links tool for MCP operations
+//!
+//! This module provides the LinksMemoTool for showing the `[[wiki-links]]` into
+//! and out of a memo, or the memos referencing another memo, an issue or a file.
+
+use crate::mcp::memo_types::MemoLinksRequest;
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::{Backlink, LinkKind, MemoId, MemoLinks};
+
+/// Tool for showing memo links and backlinks
+#[derive(Default)]
+pub struct LinksMemoTool;
+
+impl LinksMemoTool {
+    /// Creates a new instance of the LinksMemoTool
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Format backlinks, one linking memo per line
+    fn format_backlinks(backlinks: &[Backlink]) -> String {
+        backlinks
+            .iter()
+            .map(|backlink| {
+                format!(
+                    "- {} (ID: {}): {}",
+                    backlink.title, backlink.memo_id, backlink.link
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Format the links into and out of a memo
+    fn format_memo_links(id: &MemoId, links: &MemoLinks) -> String {
+        let mut response = format!(
+            "Links of memo {id}:\n\nOutgoing ({}):",
+            links.outgoing.len()
+        );
+        for link in &links.outgoing {
+            response.push_str(&format!("\n- {link}"));
+        }
+        response.push_str(&format!("\n\nBacklinks ({}):", links.backlinks.len()));
+        if !links.backlinks.is_empty() {
+            response.push('\n');
+            response.push_str(&Self::format_backlinks(&links.backlinks));
+        }
+        response
+    }
+}
+
+#[async_trait]
+impl McpTool for LinksMemoTool {
+    fn name(&self) -> &'static str {
+        "memo_links"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "links")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Memo whose outgoing links and backlinks to show"
+                },
+                "kind": {
+                    "type": "string",
+                    "description": "Kind of target to find backlinks for",
+                    "enum": ["memo", "issue", "file"],
+                    "default": "memo"
+                },
+                "target": {
+                    "type": "string",
+                    "description": "Memo title or ID, issue name, or repository path to find backlinks for"
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: MemoLinksRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let memo_storage = context.memo_storage.read().await;
+
+        if let Some(id) = request.id {
+            tracing::debug!("Getting links of memo {}", id);
+            let memo_id = MemoId::from_string(id.clone()).map_err(|_| {
+                McpError::invalid_params(format!("Invalid memo ID format: {id}"), None)
+            })?;
+            let links = memo_storage
+                .memo_links(&memo_id)
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "get memo links"))?;
+            return Ok(BaseToolImpl::create_success_response(
+                Self::format_memo_links(&memo_id, &links),
+            ));
+        }
+
+        let Some(target) = request.target.filter(|target| !target.trim().is_empty()) else {
+            return Err(McpError::invalid_params(
+                "Provide a memo id, or a target to find backlinks for".to_string(),
+                None,
+            ));
+        };
+        let kind = request
+            .kind
+            .as_deref()
+            .map(str::parse::<LinkKind>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?
+            .unwrap_or(LinkKind::Memo);
+
+        tracing::debug!("Finding memos linking to {} '{}'", kind, target);
+        let backlinks = memo_storage
+            .backlinks(kind, &target)
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "find memo backlinks"))?;
+
+        if backlinks.is_empty() {
+            return Ok(BaseToolImpl::create_success_response(format!(
+                "No memos link to {kind} '{target}'"
+            )));
+        }
+        Ok(BaseToolImpl::create_success_response(format!(
+            "Memos linking to {kind} '{target}' ({}):\n{}",
+            backlinks.len(),
+            Self::format_backlinks(&backlinks)
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[test]
+    fn test_links_memo_tool_new() {
+        let tool = LinksMemoTool::new();
+        assert_eq!(tool.name(), "memo_links");
+        assert!(!tool.description().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_links_memo_tool_execute() {
+        let tool = LinksMemoTool::new();
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        let research = memo_storage
+            .create_memo(
+                "Auth Research".to_string(),
+                "Notes on [[file:src/auth.rs]]".to_string(),
+            )
+            .await
+            .unwrap();
+        memo_storage
+            .create_memo(
+                "Plan".to_string(),
+                "Builds on [[Auth Research]], fixes [[issue:fix_login]]".to_string(),
+            )
+            .await
+            .unwrap();
+        drop(memo_storage); // Release the lock
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!(research.id.to_string()));
+        let text = tool.execute(arguments, &context).await.unwrap().content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text.contains("Outgoing (1):\n- [[file:src/auth.rs]]"));
+        assert!(text.contains("Backlinks (1):\n- Plan"));
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("kind".to_string(), serde_json::json!("issue"));
+        arguments.insert("target".to_string(), serde_json::json!("fix_login"));
+        let text = tool.execute(arguments, &context).await.unwrap().content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text.starts_with("Memos linking to issue 'fix_login' (1):\n- Plan"));
+    }
+
+    #[tokio::test]
+    async fn test_links_memo_tool_requires_id_or_target() {
+        let tool = LinksMemoTool::new();
+        let context = create_test_context().await;
+
+        assert!(tool
+            .execute(serde_json::Map::new(), &context)
+            .await
+            .is_err());
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("kind".to_string(), serde_json::json!("website"));
+        arguments.insert("target".to_string(), serde_json::json!("x"));
+        assert!(tool.execute(arguments, &context).await.is_err());
+    }
+}
@@ -121,6 +121,8 @@ mod tests {
             content: "This is a long piece of content that should be truncated in the preview to show only the first part".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            tags: Vec::new(),
+            links: Vec::new(),
         };
 
         let preview = crate::mcp::shared_utils::McpFormatter::format_memo_preview(&memo, 50);
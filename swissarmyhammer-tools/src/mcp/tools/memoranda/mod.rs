@@ -16,6 +16,7 @@
 //! - **Title**: Human-readable memo identifier
 //! - **Content**: Markdown-formatted memo body
 //! - **Metadata**: Creation timestamp and other system information
+//! - **Tags & Links**: Parsed from the content on save and indexed for backlink queries
 //!
 //! ## Tool Categories
 //!
@@ -23,12 +24,25 @@
 //! - **create**: Generate new memos with titles and content
 //! - **get**: Retrieve individual memos by ULID
 //! - **update**: Modify existing memo content (title remains unchanged)
-//! - **delete**: Permanently remove memos (irreversible operation)
+//! - **delete**: Move memos to the trash, where they are kept for 30 days
 //!
 //! ### Discovery & Search
 //! - **list**: Get all memos with metadata previews
 //! - **search**: Full-text search across titles and content
-//! - **get_all_context**: Retrieve all memo content for AI context consumption
+//! - **get_all_context**: Retrieve all memo content, or a memo and its linked neighbourhood, for AI context consumption
+//!
+//! ### Revisions & Trash
+//! - **history**: Show the revisions of a memo, or the memos in the trash
+//! - **restore**: Bring a memo back from the trash or to an earlier revision
+//! - **diff**: Compare two revisions of a memo
+//!
+//! ### Export & Import
+//! - **export**: Write every memo out as an Obsidian vault, a markdown tree or a JSON bundle
+//! - **import**: Read memos back from an export or any markdown directory, skipping duplicates
+//!
+//! ### Links & Tags
+//! - **links**: Show `[[wiki-links]]` into and out of a memo, or the memos referencing a memo, issue or file
+//! - **tags**: List memo tags, or the memos carrying a tag
 //!
 //! ## MCP Integration Patterns
 //!
@@ -47,10 +61,17 @@
 
 pub mod create;
 pub mod delete;
+pub mod diff;
+pub mod export;
 pub mod get;
 pub mod get_all_context;
+pub mod history;
+pub mod import;
+pub mod links;
 pub mod list;
+pub mod restore;
 pub mod search;
+pub mod tags;
 pub mod update;
 
 use crate::mcp::tool_registry::ToolRegistry;
@@ -63,5 +84,12 @@ pub fn register_memoranda_tools(registry: &mut ToolRegistry) {
     registry.register(get::GetMemoTool::new());
     registry.register(update::UpdateMemoTool::new());
     registry.register(delete::DeleteMemoTool::new());
+    registry.register(history::HistoryMemoTool::new());
+    registry.register(restore::RestoreMemoTool::new());
+    registry.register(diff::DiffMemoTool::new());
+    registry.register(export::ExportMemoTool::new());
+    registry.register(import::ImportMemoTool::new());
     registry.register(search::SearchMemoTool::new());
+    registry.register(links::LinksMemoTool::new());
+    registry.register(tags::TagsMemoTool::new());
 }
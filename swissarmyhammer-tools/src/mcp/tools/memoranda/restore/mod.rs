@@ -0,0 +1,169 @@
+//! Memo restore tool for MCP operations
+//!
+//! This module provides the RestoreMemoTool for bringing a memo back from the
+//! trash or setting it back to an earlier revision.
+
+use crate::mcp::memo_types::RestoreMemoRequest;
+use crate::mcp::shared_utils::{McpErrorHandler, McpFormatter};
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::MemoId;
+
+/// Tool for restoring a memo from the trash or to an earlier revision
+#[derive(Default)]
+pub struct RestoreMemoTool;
+
+impl RestoreMemoTool {
+    /// Creates a new instance of the RestoreMemoTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for RestoreMemoTool {
+    fn name(&self) -> &'static str {
+        "memo_restore"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "restore")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "ID of the memo to restore"
+                },
+                "revision": {
+                    "type": "integer",
+                    "description": "Revision whose content to restore; only takes the memo out of the trash when omitted",
+                    "minimum": 1
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: RestoreMemoRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        tracing::debug!(
+            "Restoring memo {} to revision {:?}",
+            request.id,
+            request.revision
+        );
+
+        let memo_id = MemoId::from_string(request.id.clone()).map_err(|_| {
+            McpError::invalid_params(format!("Invalid memo ID format: {}", request.id), None)
+        })?;
+
+        let memo_storage = context.memo_storage.write().await;
+        let memo = memo_storage
+            .restore_memo(&memo_id, request.revision)
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "restore memo"))?;
+        let revision = memo_storage
+            .memo_history(&memo.id)
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "get memo history"))?
+            .last()
+            .map_or(1, |entry| entry.revision);
+
+        tracing::info!("Restored memo {} as revision {}", memo.id, revision);
+        Ok(BaseToolImpl::create_success_response(format!(
+            "Successfully restored memo as revision {revision}:\n\nID: {}\nTitle: {}\nUpdated: {}\n\nContent:\n{}",
+            memo.id,
+            memo.title,
+            McpFormatter::format_timestamp(memo.updated_at),
+            memo.content
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[test]
+    fn test_restore_memo_tool_new() {
+        let tool = RestoreMemoTool::new();
+        assert_eq!(tool.name(), "memo_restore");
+        assert!(!tool.description().is_empty());
+        assert_eq!(tool.schema()["required"], serde_json::json!(["id"]));
+    }
+
+    #[tokio::test]
+    async fn test_restore_memo_tool_execute() {
+        let tool = RestoreMemoTool::new();
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        let memo = memo_storage
+            .create_memo("Plan".to_string(), "first".to_string())
+            .await
+            .unwrap();
+        memo_storage
+            .update_memo(&memo.id, "second".to_string())
+            .await
+            .unwrap();
+        memo_storage.delete_memo(&memo.id).await.unwrap();
+        drop(memo_storage); // Release the lock
+
+        // Out of the trash as it was deleted
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!(memo.id.to_string()));
+        let text = tool
+            .execute(arguments.clone(), &context)
+            .await
+            .unwrap()
+            .content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text.starts_with("Successfully restored memo as revision 4:"));
+        assert!(text.ends_with("Content:\nsecond"));
+
+        // A memo that is not in the trash needs a revision
+        assert!(tool.execute(arguments.clone(), &context).await.is_err());
+
+        arguments.insert("revision".to_string(), serde_json::json!(1));
+        let text = tool.execute(arguments, &context).await.unwrap().content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text.starts_with("Successfully restored memo as revision 5:"));
+        assert!(text.ends_with("Content:\nfirst"));
+    }
+
+    #[tokio::test]
+    async fn test_restore_memo_tool_unknown_revision() {
+        let tool = RestoreMemoTool::new();
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        let memo = memo_storage
+            .create_memo("Plan".to_string(), "first".to_string())
+            .await
+            .unwrap();
+        drop(memo_storage); // Release the lock
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("id".to_string(), serde_json::json!(memo.id.to_string()));
+        arguments.insert("revision".to_string(), serde_json::json!(7));
+        assert!(tool.execute(arguments, &context).await.is_err());
+    }
+}
@@ -1,12 +1,14 @@
 //! Memo search tool for MCP operations
 //!
 //! This module provides the SearchMemoTool for searching memos by query string through the MCP protocol.
+//! Besides plain keyword matching it can rank memos by meaning, or fuse both rankings.
 
 use crate::mcp::memo_types::SearchMemosRequest;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
+use swissarmyhammer::memoranda::{MemoSearchMode, SearchOptions, SearchResult};
 
 /// Tool for searching memos by query string
 #[derive(Default)]
@@ -20,6 +22,37 @@ impl SearchMemoTool {
     pub fn new() -> Self {
         Self
     }
+
+    /// Format ranked results of a semantic or hybrid search
+    fn format_ranked_results(
+        query: &str,
+        mode: MemoSearchMode,
+        results: &[SearchResult],
+    ) -> String {
+        let memo_list = results
+            .iter()
+            .map(|result| {
+                let mut entry = format!(
+                    "• {} ({})\n  Score: {:.1}",
+                    result.memo.title, result.memo.id, result.relevance_score
+                );
+                if let Some(highlight) = result.highlights.first() {
+                    entry.push_str(&format!("\n  Matched: {highlight}"));
+                }
+                entry
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        format!(
+            "Found {} memo{} matching '{}' ({} search):\n\n{}",
+            results.len(),
+            if results.len() == 1 { "" } else { "s" },
+            query,
+            mode,
+            memo_list
+        )
+    }
 }
 
 #[async_trait]
@@ -40,6 +73,12 @@ impl McpTool for SearchMemoTool {
                 "query": {
                     "type": "string",
                     "description": "Search query string to match against memo titles and content"
+                },
+                "mode": {
+                    "type": "string",
+                    "description": "How to rank memos: by keywords, by meaning using embeddings, or both fused",
+                    "enum": ["keyword", "semantic", "hybrid"],
+                    "default": "keyword"
                 }
             },
             "required": ["query"]
@@ -61,7 +100,38 @@ impl McpTool for SearchMemoTool {
                 crate::mcp::shared_utils::McpErrorHandler::handle_error(e, "validate search query")
             })?;
 
+        let mode = request
+            .mode
+            .as_deref()
+            .map(str::parse::<MemoSearchMode>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?
+            .unwrap_or_default();
+
         let memo_storage = context.memo_storage.read().await;
+        if mode != MemoSearchMode::Keyword {
+            // Storages without a vector index fall back to keyword search
+            let used = memo_storage.search_mode_used(mode);
+            let options = SearchOptions {
+                include_highlights: true,
+                ..Default::default()
+            };
+            let results = memo_storage
+                .search_memos_with_mode(&request.query, &options, mode)
+                .await
+                .map_err(|e| {
+                    crate::mcp::shared_utils::McpErrorHandler::handle_error(e, "search memos")
+                })?;
+            tracing::info!("{} search returned {} memos", used, results.len());
+            return Ok(BaseToolImpl::create_success_response(
+                if results.is_empty() {
+                    format!("No memos found matching query: '{}'", request.query)
+                } else {
+                    Self::format_ranked_results(&request.query, used, &results)
+                },
+            ));
+        }
+
         match memo_storage.search_memos(&request.query).await {
             Ok(memos) => {
                 tracing::info!("Search returned {} memos", memos.len());
@@ -118,9 +188,64 @@ mod tests {
 
         assert_eq!(schema["type"], "object");
         assert!(schema["properties"]["query"].is_object());
+        assert_eq!(
+            schema["properties"]["mode"]["enum"],
+            serde_json::json!(["keyword", "semantic", "hybrid"])
+        );
         assert_eq!(schema["required"], serde_json::json!(["query"]));
     }
 
+    #[test]
+    fn test_format_ranked_results() {
+        use swissarmyhammer::memoranda::{Memo, MemoId};
+
+        let mut memo = Memo::new(
+            "Deploy".to_string(),
+            "# Production\nTag a release".to_string(),
+        );
+        memo.id = MemoId::from_filename("Deploy");
+        let results = vec![SearchResult {
+            memo,
+            relevance_score: 87.3,
+            highlights: vec!["Production: Tag a release".to_string()],
+            match_count: 1,
+        }];
+
+        assert_eq!(
+            SearchMemoTool::format_ranked_results("shipping", MemoSearchMode::Hybrid, &results),
+            "Found 1 memo matching 'shipping' (hybrid search):\n\n• Deploy (Deploy)\n  Score: 87.3\n  Matched: Production: Tag a release"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_memo_tool_execute_modes() {
+        let tool = SearchMemoTool::new();
+        let context = create_test_context().await;
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("query".to_string(), serde_json::json!("content"));
+        arguments.insert("mode".to_string(), serde_json::json!("fuzzy"));
+        assert!(tool.execute(arguments.clone(), &context).await.is_err());
+
+        // The mock storage has no vector index, so keyword search answers
+        context
+            .memo_storage
+            .write()
+            .await
+            .create_memo("Notes".to_string(), "Some content".to_string())
+            .await
+            .unwrap();
+        arguments.insert("mode".to_string(), serde_json::json!("semantic"));
+        let result = tool.execute(arguments.clone(), &context).await.unwrap();
+        assert_eq!(result.is_error, Some(false));
+        let text = &result.content[0].as_text().unwrap().text;
+        assert!(text.contains("(keyword search)"), "{text}");
+
+        arguments.insert("mode".to_string(), serde_json::json!("keyword"));
+        let result = tool.execute(arguments, &context).await.unwrap();
+        assert_eq!(result.is_error, Some(false));
+    }
+
     #[test]
     fn test_format_memo_preview() {
         use chrono::Utc;
@@ -132,6 +257,8 @@ mod tests {
             content: "This is a long piece of content that should be truncated in the preview to show only the first part".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            tags: Vec::new(),
+            links: Vec::new(),
         };
 
         let preview = crate::mcp::shared_utils::McpFormatter::format_memo_preview(&memo, 50);
@@ -0,0 +1,147 @@
+//! Memo tags tool for MCP operations
+//!
+//! This module provides the TagsMemoTool for listing memo tags, or the memos
+//! carrying a tag, through the MCP protocol.
+
+use crate::mcp::memo_types::MemoTagsRequest;
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+
+/// Tool for listing memo tags
+#[derive(Default)]
+pub struct TagsMemoTool;
+
+impl TagsMemoTool {
+    /// Creates a new instance of the TagsMemoTool
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl McpTool for TagsMemoTool {
+    fn name(&self) -> &'static str {
+        "memo_tags"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("memoranda", "tags")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "tag": {
+                    "type": "string",
+                    "description": "Tag whose memos to list; every tag with its count when omitted"
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        context: &ToolContext,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request: MemoTagsRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let memo_storage = context.memo_storage.read().await;
+
+        if let Some(tag) = request.tag.filter(|tag| !tag.trim().is_empty()) {
+            tracing::debug!("Listing memos tagged {}", tag);
+            let memos = memo_storage
+                .memos_with_tag(&tag)
+                .await
+                .map_err(|e| McpErrorHandler::handle_error(e, "list tagged memos"))?;
+            if memos.is_empty() {
+                return Ok(BaseToolImpl::create_success_response(format!(
+                    "No memos tagged '{tag}'"
+                )));
+            }
+            let memo_list = memos
+                .iter()
+                .map(|memo| format!("- {} (ID: {})", memo.title, memo.id))
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Ok(BaseToolImpl::create_success_response(format!(
+                "Memos tagged '{tag}' ({}):\n{memo_list}",
+                memos.len()
+            )));
+        }
+
+        tracing::debug!("Listing memo tags");
+        let tags = memo_storage
+            .link_index()
+            .await
+            .map_err(|e| McpErrorHandler::handle_error(e, "list memo tags"))?
+            .tags();
+        if tags.is_empty() {
+            return Ok(BaseToolImpl::create_success_response(
+                "No memo tags found".to_string(),
+            ));
+        }
+        let tag_list = tags
+            .iter()
+            .map(|(tag, count)| format!("- #{tag} ({count})"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(BaseToolImpl::create_success_response(format!(
+            "Memo tags ({}):\n{tag_list}",
+            tags.len()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[tokio::test]
+    async fn test_tags_memo_tool_execute() {
+        let tool = TagsMemoTool::new();
+        assert_eq!(tool.name(), "memo_tags");
+        let context = create_test_context().await;
+
+        let memo_storage = context.memo_storage.write().await;
+        memo_storage
+            .create_memo("Auth Research".to_string(), "#auth #research".to_string())
+            .await
+            .unwrap();
+        memo_storage
+            .create_memo(
+                "Login Bug".to_string(),
+                "---\ntags: [auth]\n---\nDetails".to_string(),
+            )
+            .await
+            .unwrap();
+        drop(memo_storage); // Release the lock
+
+        let text = tool
+            .execute(serde_json::Map::new(), &context)
+            .await
+            .unwrap()
+            .content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert_eq!(text, "Memo tags (2):\n- #auth (2)\n- #research (1)");
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("tag".to_string(), serde_json::json!("#Research"));
+        let text = tool.execute(arguments, &context).await.unwrap().content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .clone();
+        assert!(text.starts_with("Memos tagged '#Research' (1):\n- Auth Research"));
+    }
+}
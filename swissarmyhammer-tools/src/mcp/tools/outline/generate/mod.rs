@@ -86,6 +86,8 @@ pub enum OutlineKind {
     Constant,
     /// Import/use statement
     Import,
+    /// Document section under a heading (Markdown)
+    Section,
     /// Other/unknown symbol type
     Other,
 }
@@ -346,6 +348,7 @@ fn convert_outline_node_type(
         OutlineNodeType::Trait => OutlineKind::Trait,
         OutlineNodeType::Constant => OutlineKind::Constant,
         OutlineNodeType::Import => OutlineKind::Import,
+        OutlineNodeType::Section => OutlineKind::Section,
         OutlineNodeType::Impl => OutlineKind::Other, // Map Impl to Other as no direct equivalent
     }
 }
@@ -450,6 +453,7 @@ mod tests {
             OutlineKind::Trait,
             OutlineKind::Constant,
             OutlineKind::Import,
+            OutlineKind::Section,
             OutlineKind::Other,
         ];
 
@@ -77,6 +77,12 @@
 //! - **Python**: Classes, functions, methods, properties, imports
 //! - **TypeScript/JavaScript**: Classes, interfaces, functions, methods, types
 //! - **Dart**: Classes, functions, methods, constructors, properties
+//! - **Go**: Structs, interfaces, functions, methods (attached to their receiver), constants
+//! - **Java**: Classes, records, interfaces, enums, methods, constructors, fields
+//! - **C/C++**: Structs, classes, namespaces, functions, methods, typedefs, macros
+//! - **C#**: Namespaces, classes, structs, interfaces, methods, properties, events
+//! - **Ruby**: Modules, classes, methods, constants, attribute accessors
+//! - **Markdown**: Heading sections, nested by level
 //!
 //! ## Output Formats
 //!
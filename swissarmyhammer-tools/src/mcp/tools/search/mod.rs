@@ -1,16 +1,18 @@
 //! Search tools for MCP operations
 //!
 //! This module provides search tools that expose semantic search functionality through the MCP protocol.
-//! It includes tools for indexing files and performing semantic search queries.
+//! It includes tools for indexing files, performing semantic search queries and reporting
+//! the state of the index.
 
 pub mod index;
 pub mod query;
+pub mod status;
 
 use crate::mcp::tool_registry::ToolRegistry;
 
 /// Register all search-related tools with the registry
 ///
-/// This function registers both the search indexing and query tools with the provided registry.
+/// This function registers the search indexing, query and status tools with the provided registry.
 /// These tools expose the semantic search functionality that uses vector embeddings and
 /// TreeSitter parsing for code understanding.
 ///
@@ -22,9 +24,11 @@ use crate::mcp::tool_registry::ToolRegistry;
 ///
 /// - `search_index`: Index files for semantic search using vector embeddings
 /// - `search_query`: Perform semantic search queries across indexed files
+/// - `search_status`: Report the index size and how far behind the files it is
 pub fn register_search_tools(registry: &mut ToolRegistry) {
     registry.register(index::SearchIndexTool::new());
     registry.register(query::SearchQueryTool::new());
+    registry.register(status::SearchStatusTool::new());
 }
 
 #[cfg(test)]
@@ -39,9 +43,10 @@ mod tests {
 
         register_search_tools(&mut registry);
 
-        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.len(), 3);
         assert!(registry.get_tool("search_index").is_some());
         assert!(registry.get_tool("search_query").is_some());
+        assert!(registry.get_tool("search_status").is_some());
     }
 
     #[test]
@@ -1,15 +1,21 @@
 //! Search query tool for MCP operations
 //!
-//! This module provides the SearchQueryTool for performing semantic search queries through the MCP protocol.
+//! This module provides the SearchQueryTool for performing semantic, lexical and hybrid
+//! search queries through the MCP protocol.
 
-use crate::mcp::search_types::{SearchQueryRequest, SearchQueryResponse, SearchResult};
+use crate::mcp::search_types::{
+    SearchQueryRequest, SearchQueryResponse, SearchResult, SearchScores,
+};
 use crate::mcp::shared_utils::McpErrorHandler;
 use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
 use async_trait::async_trait;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
 use std::time::Instant;
-use swissarmyhammer::search::{SearchQuery, SemanticConfig, SemanticSearcher, VectorStorage};
+use swissarmyhammer::search::{
+    CrossEncoderReranker, SearchMode, SearchQuery, SemanticConfig, SemanticSearcher, SymbolFilter,
+    VectorStorage,
+};
 
 /// Tool for performing semantic search queries
 #[derive(Default)]
@@ -86,9 +92,10 @@ impl McpTool for SearchQueryTool {
         let request: SearchQueryRequest = BaseToolImpl::parse_arguments(arguments)?;
 
         tracing::debug!(
-            "Starting search query: '{}', limit: {}",
+            "Starting search query: '{}', limit: {}, mode: {:?}",
             request.query,
-            request.limit
+            request.limit,
+            request.mode
         );
 
         if request.query.trim().is_empty() {
@@ -98,6 +105,16 @@ impl McpTool for SearchQueryTool {
             ));
         }
 
+        let mode = request
+            .mode
+            .as_deref()
+            .map(str::parse::<SearchMode>)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?
+            .unwrap_or_default();
+        let symbol_filter = SymbolFilter::parse(request.kind.as_deref(), request.path.as_deref())
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
         let start_time = Instant::now();
 
         // Initialize semantic search components
@@ -141,15 +158,62 @@ impl McpTool for SearchQueryTool {
             }
         };
 
+        let searcher = if request.rerank {
+            let reranker = CrossEncoderReranker::shared().await.map_err(|e| {
+                McpErrorHandler::handle_error(
+                    swissarmyhammer::SwissArmyHammerError::Semantic(e),
+                    "load reranker model",
+                )
+            })?;
+            searcher.with_reranker(reranker)
+        } else {
+            searcher
+        };
+
         // Perform search
         let search_query = SearchQuery {
             text: request.query.clone(),
             limit: request.limit,
             similarity_threshold: 0.5, // Use lower threshold for more results
             language_filter: None,
+            symbol_filter: (!symbol_filter.is_empty()).then_some(symbol_filter),
         };
 
-        let search_results = searcher.search(&search_query).await.map_err(|e| {
+        // Explained results come from the same ranking, so each signal runs once
+        let explained = if request.explain {
+            searcher
+                .search_with_mode_explained(&search_query, mode)
+                .await
+                .map(|results| {
+                    results
+                        .into_iter()
+                        .map(|(result, explanation)| {
+                            let scores = SearchScores {
+                                vector_score: explanation
+                                    .vector_rank
+                                    .map(|_| explanation.similarity_score),
+                                vector_rank: explanation.vector_rank,
+                                lexical_score: explanation.lexical_score,
+                                lexical_rank: explanation.lexical_rank,
+                                fused_score: explanation.fused_score,
+                                rerank_score: explanation.rerank_score,
+                            };
+                            (result, Some(scores))
+                        })
+                        .collect::<Vec<_>>()
+                })
+        } else {
+            searcher
+                .search_with_mode(&search_query, mode)
+                .await
+                .map(|results| {
+                    results
+                        .into_iter()
+                        .map(|result| (result, None))
+                        .collect::<Vec<_>>()
+                })
+        };
+        let search_results = explained.map_err(|e| {
             McpErrorHandler::handle_error(
                 swissarmyhammer::SwissArmyHammerError::Semantic(e),
                 &format!("search for '{}'", request.query),
@@ -161,15 +225,22 @@ impl McpTool for SearchQueryTool {
         // Convert search results to response format
         let results: Vec<SearchResult> = search_results
             .into_iter()
-            .map(|result| SearchResult {
-                file_path: result.chunk.file_path.to_string_lossy().to_string(),
-                chunk_text: result.chunk.content.clone(),
-                line_start: Some(result.chunk.start_line),
-                line_end: Some(result.chunk.end_line),
-                similarity_score: result.similarity_score,
-                language: Some(format!("{:?}", result.chunk.language).to_lowercase()),
-                chunk_type: Some(format!("{:?}", result.chunk.chunk_type)),
-                excerpt: result.excerpt,
+            .map(|(result, scores)| {
+                let symbol = result.chunk.symbol.as_ref();
+                SearchResult {
+                    file_path: result.chunk.file_path.to_string_lossy().to_string(),
+                    chunk_text: result.chunk.content.clone(),
+                    line_start: Some(result.chunk.start_line),
+                    line_end: Some(result.chunk.end_line),
+                    similarity_score: result.similarity_score,
+                    language: Some(format!("{:?}", result.chunk.language).to_lowercase()),
+                    chunk_type: Some(format!("{:?}", result.chunk.chunk_type)),
+                    symbol: symbol.map(|symbol| symbol.qualified_path.clone()),
+                    symbol_kind: symbol.map(|symbol| symbol.kind.to_string()),
+                    signature: symbol.and_then(|symbol| symbol.signature.clone()),
+                    excerpt: result.excerpt,
+                    scores,
+                }
             })
             .collect();
 
@@ -181,7 +252,8 @@ impl McpTool for SearchQueryTool {
         };
 
         tracing::info!(
-            "Search query completed: found {} results for '{}' in {:?}",
+            "{} search query completed: found {} results for '{}' in {:?}",
+            mode,
             response.total_results,
             response.query,
             duration
@@ -215,6 +287,11 @@ mod tests {
         assert_eq!(schema["type"], "object");
         assert!(schema["properties"]["query"].is_object());
         assert!(schema["properties"]["limit"].is_object());
+        assert!(schema["properties"]["mode"].is_object());
+        assert!(schema["properties"]["rerank"].is_object());
+        assert!(schema["properties"]["kind"].is_object());
+        assert!(schema["properties"]["path"].is_object());
+        assert!(schema["properties"]["explain"].is_object());
         assert_eq!(schema["required"], serde_json::json!(["query"]));
     }
 
@@ -305,6 +382,41 @@ mod tests {
         assert_eq!(request.limit, 10); // Default value
     }
 
+    #[tokio::test]
+    async fn test_search_query_tool_rejects_unknown_mode() {
+        let tool = SearchQueryTool::new();
+        let context = create_test_context().await;
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert(
+            "query".to_string(),
+            serde_json::json!("validate_branch_name"),
+        );
+        arguments.insert("mode".to_string(), serde_json::json!("fuzzy"));
+
+        let result = tool.execute(arguments, &context).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown search mode"));
+    }
+
+    #[tokio::test]
+    async fn test_search_query_tool_rejects_unknown_kind() {
+        let tool = SearchQueryTool::new();
+        let context = create_test_context().await;
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("query".to_string(), serde_json::json!("branch"));
+        arguments.insert("kind".to_string(), serde_json::json!("widget"));
+
+        let result = tool.execute(arguments, &context).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown symbol kind"));
+    }
+
     #[tokio::test]
     async fn test_search_query_tool_execute_missing_query() {
         let tool = SearchQueryTool::new();
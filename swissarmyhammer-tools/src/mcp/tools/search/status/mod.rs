@@ -0,0 +1,211 @@
+//! Search status tool for MCP operations
+//!
+//! This module provides the SearchStatusTool for reporting the size of the search index
+//! and how far behind the files on disk it is through the MCP protocol.
+
+use crate::mcp::search_types::{SearchStatusRequest, SearchStatusResponse};
+use crate::mcp::shared_utils::McpErrorHandler;
+use crate::mcp::tool_registry::{BaseToolImpl, McpTool, ToolContext};
+use async_trait::async_trait;
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer::search::{IndexWatchStatus, SemanticConfig, VectorStorage};
+
+/// Tool for reporting the state of the search index
+#[derive(Default)]
+pub struct SearchStatusTool;
+
+impl SearchStatusTool {
+    /// Creates a new instance of the SearchStatusTool
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[cfg(test)]
+    fn create_test_config() -> SemanticConfig {
+        // Create a unique temporary database path for each test execution
+        use std::thread;
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let thread_id = format!("{:?}", thread::current().id());
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let unique_id = format!(
+            "{}_{}",
+            thread_id.replace("ThreadId(", "").replace(")", ""),
+            timestamp
+        );
+
+        let persistent_path =
+            std::env::temp_dir().join(format!("swissarmyhammer_test_{unique_id}"));
+        std::fs::create_dir_all(&persistent_path).expect("Failed to create persistent test dir");
+
+        SemanticConfig {
+            database_path: persistent_path.join("semantic.db"),
+            ..Default::default()
+        }
+    }
+
+    /// Read the index size and the watcher status for the index in `config`
+    fn index_status(config: &SemanticConfig) -> Result<SearchStatusResponse, McpError> {
+        let storage = VectorStorage::new(config.clone())
+            .map_err(|e| McpErrorHandler::handle_error(e, "initialize vector storage"))?;
+        storage
+            .initialize()
+            .map_err(|e| McpErrorHandler::handle_error(e, "initialize storage database"))?;
+        let stats = storage
+            .get_index_stats()
+            .map_err(|e| McpErrorHandler::handle_error(e, "read index statistics"))?;
+        drop(storage);
+
+        let now = chrono::Utc::now();
+        let watch_status =
+            IndexWatchStatus::load(&IndexWatchStatus::path_for(&config.database_path))
+                .map_err(|e| {
+                    McpErrorHandler::handle_error(
+                        swissarmyhammer::SwissArmyHammerError::Semantic(e),
+                        "read index watch status",
+                    )
+                })?
+                .filter(|status| status.is_running(now));
+
+        let response = match watch_status {
+            Some(status) => SearchStatusResponse {
+                message: match status.queue_depth {
+                    0 => "Watching: the index is current".to_string(),
+                    queued => format!("Watching: {queued} changed files waiting to be indexed"),
+                },
+                indexed_files: stats.file_count,
+                total_chunks: stats.chunk_count,
+                watching: true,
+                queue_depth: status.queue_depth,
+                staleness_ms: status
+                    .staleness(now)
+                    .num_milliseconds()
+                    .max(0)
+                    .try_into()
+                    .unwrap_or(0),
+                last_indexed_at: status.last_indexed_at.map(|at| at.to_rfc3339()),
+                watch_errors: status.errors,
+                last_error: status.last_error,
+            },
+            None => SearchStatusResponse {
+                message: "Not watching: the index changes only when search_index runs".to_string(),
+                indexed_files: stats.file_count,
+                total_chunks: stats.chunk_count,
+                watching: false,
+                queue_depth: 0,
+                staleness_ms: 0,
+                last_indexed_at: None,
+                watch_errors: 0,
+                last_error: None,
+            },
+        };
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl McpTool for SearchStatusTool {
+    fn name(&self) -> &'static str {
+        "search_status"
+    }
+
+    fn description(&self) -> &'static str {
+        crate::mcp::tool_descriptions::get_tool_description("search", "status")
+            .expect("Tool description should be available")
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {},
+            "required": []
+        })
+    }
+
+    async fn execute(
+        &self,
+        arguments: serde_json::Map<String, serde_json::Value>,
+        _context: &ToolContext, // Search tools don't need shared context
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let _request: SearchStatusRequest = BaseToolImpl::parse_arguments(arguments)?;
+
+        let config = {
+            #[cfg(test)]
+            {
+                Self::create_test_config()
+            }
+            #[cfg(not(test))]
+            {
+                SemanticConfig::default()
+            }
+        };
+        let response = Self::index_status(&config)?;
+
+        tracing::debug!("Search index status: {}", response.message);
+        Ok(BaseToolImpl::create_success_response(
+            serde_json::to_string_pretty(&response).map_err(|e| {
+                McpError::internal_error(format!("Failed to serialize response: {e}"), None)
+            })?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_context;
+
+    #[test]
+    fn test_search_status_tool_new() {
+        let tool = SearchStatusTool::new();
+        assert_eq!(tool.name(), "search_status");
+        assert!(!tool.description().is_empty());
+        assert_eq!(tool.schema()["type"], "object");
+    }
+
+    #[tokio::test]
+    async fn test_search_status_tool_without_watcher() {
+        let tool = SearchStatusTool::new();
+        let context = create_test_context().await;
+
+        let result = tool
+            .execute(serde_json::Map::new(), &context)
+            .await
+            .unwrap();
+        let text = &result.content[0].as_text().unwrap().text;
+        let response: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(response["watching"], false);
+        assert_eq!(response["indexed_files"], 0);
+    }
+
+    #[test]
+    fn test_search_status_reports_running_watcher() {
+        let config = SearchStatusTool::create_test_config();
+        let mut status = IndexWatchStatus::new(".");
+        status.queue_depth = 2;
+        status.pending_since = Some(chrono::Utc::now() - chrono::Duration::seconds(1));
+        status
+            .save(&IndexWatchStatus::path_for(&config.database_path))
+            .unwrap();
+
+        let response = SearchStatusTool::index_status(&config).unwrap();
+        assert!(response.watching);
+        assert_eq!(response.queue_depth, 2);
+        assert!(response.staleness_ms >= 1000);
+        assert_eq!(
+            response.message,
+            "Watching: 2 changed files waiting to be indexed"
+        );
+
+        // A watcher that stopped writing its status is not running
+        status.updated_at -= chrono::Duration::minutes(5);
+        status
+            .save(&IndexWatchStatus::path_for(&config.database_path))
+            .unwrap();
+        assert!(!SearchStatusTool::index_status(&config).unwrap().watching);
+    }
+}
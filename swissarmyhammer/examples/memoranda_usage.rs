@@ -418,6 +418,8 @@ mod tests {
                 .to_string(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            tags: Vec::new(),
+            links: Vec::new(),
         };
 
         let stats = analyze_memo_content(&memo).await;
@@ -57,6 +57,14 @@ pub enum SwissArmyHammerError {
     #[error("Issue already exists: {0}")]
     IssueAlreadyExists(u32),
 
+    /// Issue was changed both locally and in the remote tracker
+    #[error("Issue conflict: {0}")]
+    IssueConflict(String),
+
+    /// Issues are pending but every one of them waits on another issue
+    #[error("All pending issues are blocked: {0}")]
+    IssuesBlocked(String),
+
     /// Git operation failed
     #[error("Git operation '{operation}' failed: {details}")]
     GitOperationFailed {
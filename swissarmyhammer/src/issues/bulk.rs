@@ -0,0 +1,182 @@
+//! Selecting many issues at once
+//!
+//! Bulk operations act on every issue matched by an [`IssueFilter`]: a glob
+//! over issue names, labels from the front matter, or both. Each matched issue
+//! is changed on its own, so one failure does not stop the others; the
+//! [`BulkResult`] lists what succeeded and what failed.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::filesystem::IssueInfo;
+use crate::issues::history::IssueStatus;
+use serde::{Deserialize, Serialize};
+
+/// Which issues a bulk operation applies to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueFilter {
+    /// Glob matched against issue names, such as `auth_*`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Labels an issue must all carry, ignoring case
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Only issues with this status; active and completed issues otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<IssueStatus>,
+}
+
+impl IssueFilter {
+    /// Filter matching issue names against `pattern`
+    pub fn by_name(pattern: impl Into<String>) -> Self {
+        Self {
+            name: Some(pattern.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Filter matching issues carrying `label`
+    pub fn by_label(label: impl Into<String>) -> Self {
+        Self {
+            labels: vec![label.into()],
+            ..Self::default()
+        }
+    }
+
+    /// Whether neither a name pattern nor a label is set
+    ///
+    /// Such a filter matches every issue, which bulk tools refuse.
+    pub fn is_unrestricted(&self) -> bool {
+        self.name.is_none() && self.labels.is_empty()
+    }
+
+    /// Issues from `infos` matched by the filter
+    pub fn select(&self, infos: Vec<IssueInfo>) -> Result<Vec<IssueInfo>> {
+        let pattern = self
+            .name
+            .as_deref()
+            .map(glob::Pattern::new)
+            .transpose()
+            .map_err(|e| SwissArmyHammerError::Other(format!("Invalid issue name pattern: {e}")))?;
+
+        Ok(infos
+            .into_iter()
+            .filter(|info| self.status.is_none_or(|status| info.status() == status))
+            .filter(|info| {
+                pattern
+                    .as_ref()
+                    .is_none_or(|pattern| pattern.matches(&info.issue.name))
+            })
+            .filter(|info| {
+                if self.labels.is_empty() {
+                    return true;
+                }
+                let metadata = info.issue.metadata();
+                self.labels.iter().all(|label| metadata.has_label(label))
+            })
+            .collect())
+    }
+}
+
+/// An issue a bulk operation could not change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkFailure {
+    /// Issue name
+    pub name: String,
+    /// Why it failed
+    pub error: String,
+}
+
+/// Outcome of a bulk operation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkResult {
+    /// Issues that were changed
+    pub succeeded: Vec<String>,
+    /// Issues that matched but could not be changed
+    pub failed: Vec<BulkFailure>,
+}
+
+impl BulkResult {
+    /// Record the outcome for one issue
+    pub fn record<T>(&mut self, name: String, result: Result<T>) {
+        match result {
+            Ok(_) => self.succeeded.push(name),
+            Err(e) => self.failed.push(BulkFailure {
+                name,
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    /// Number of issues the operation matched
+    pub fn matched(&self) -> usize {
+        self.succeeded.len() + self.failed.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::filesystem::Issue;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn info(name: &str, content: &str, completed: bool) -> IssueInfo {
+        IssueInfo {
+            issue: Issue {
+                name: name.to_string(),
+                content: content.to_string(),
+            },
+            completed,
+            archived: false,
+            file_path: PathBuf::from(format!("/issues/{name}.md")),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn names(infos: Vec<IssueInfo>) -> Vec<String> {
+        infos.into_iter().map(|info| info.issue.name).collect()
+    }
+
+    #[test]
+    fn test_select_by_name_label_and_status() {
+        let infos = vec![
+            info("auth_login", "---\nlabels: [bug]\n---\n", false),
+            info("auth_logout", "# Logout", true),
+            info("search_index", "---\nlabels: [Bug, perf]\n---\n", false),
+        ];
+
+        let by_name = IssueFilter::by_name("auth_*");
+        assert_eq!(
+            names(by_name.select(infos.clone()).unwrap()),
+            vec!["auth_login", "auth_logout"]
+        );
+
+        let by_label = IssueFilter::by_label("bug");
+        assert_eq!(
+            names(by_label.select(infos.clone()).unwrap()),
+            vec!["auth_login", "search_index"]
+        );
+
+        let both = IssueFilter {
+            name: Some("auth_*".to_string()),
+            labels: vec!["bug".to_string()],
+            status: Some(IssueStatus::Active),
+        };
+        assert_eq!(
+            names(both.select(infos.clone()).unwrap()),
+            vec!["auth_login"]
+        );
+
+        let completed = IssueFilter {
+            status: Some(IssueStatus::Completed),
+            ..IssueFilter::by_name("*")
+        };
+        assert_eq!(names(completed.select(infos).unwrap()), vec!["auth_logout"]);
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_an_error() {
+        assert!(IssueFilter::by_name("[").select(Vec::new()).is_err());
+        assert!(IssueFilter::default().is_unrestricted());
+        assert!(!IssueFilter::by_label("bug").is_unrestricted());
+    }
+}
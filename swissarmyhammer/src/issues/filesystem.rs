@@ -1,12 +1,22 @@
 use crate::common::generate_monotonic_ulid_string;
 use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::bulk::{BulkResult, IssueFilter};
+use crate::issues::history::{IssueChange, IssueHistoryEntry, IssueHistoryStore, IssueStatus};
+use crate::issues::metadata::{select_next_issue, set_labels};
+use crate::issues::remote::{ConflictStrategy, SyncReport};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tokio::sync::Mutex;
 use tracing::{debug, warn};
 
+/// Name of the directory holding completed issues, inside the issues directory
+pub const COMPLETED_DIR: &str = "complete";
+/// Name of the directory holding archived issues, inside the issues directory
+pub const ARCHIVED_DIR: &str = "archived";
+
 // IssueNumber type eliminated - we now use issue names (filename without .md) as the primary identifier
 
 /// Represents an issue in the tracking system
@@ -30,7 +40,7 @@ impl Issue {
     /// Get the file path for this issue based on its location (completed or active)
     pub fn get_file_path(&self, base_dir: &Path, completed: bool) -> PathBuf {
         let dir = if completed {
-            base_dir.join("complete")
+            base_dir.join(COMPLETED_DIR)
         } else {
             base_dir.to_path_buf()
         };
@@ -55,6 +65,8 @@ pub struct IssueInfo {
     pub issue: Issue,
     /// Whether this issue is completed (in completed directory)
     pub completed: bool,
+    /// Whether this issue is archived (in archived directory)
+    pub archived: bool,
     /// Full path to the issue file
     pub file_path: PathBuf,
     /// When this issue was created
@@ -65,15 +77,28 @@ impl IssueInfo {
     /// Create issue info from an issue and its file path
     pub fn from_issue_and_path(issue: Issue, file_path: PathBuf, completed_dir: &Path) -> Self {
         let completed = issue.is_completed(&file_path, completed_dir);
+        let archived = file_path.parent() == Some(&completed_dir.with_file_name(ARCHIVED_DIR));
         let created_at = Issue::get_created_at(&file_path);
 
         Self {
             issue,
             completed,
+            archived,
             file_path,
             created_at,
         }
     }
+
+    /// Where the issue currently lives
+    pub fn status(&self) -> IssueStatus {
+        if self.archived {
+            IssueStatus::Archived
+        } else if self.completed {
+            IssueStatus::Completed
+        } else {
+            IssueStatus::Active
+        }
+    }
 }
 
 /// Represents the current state of the issue system
@@ -83,17 +108,26 @@ pub struct IssueState {
     pub issues_dir: PathBuf,
     /// Path to the completed issues directory
     pub completed_dir: PathBuf,
+    /// Path to the archived issues directory
+    pub archived_dir: PathBuf,
 }
 
 /// Trait for issue storage operations
 #[async_trait::async_trait]
 pub trait IssueStorage: Send + Sync {
     /// List all issues (both pending and completed)
+    ///
+    /// Archived issues are not included.
     async fn list_issues(&self) -> Result<Vec<Issue>>;
 
     /// List all issues with extended information (includes completion status and file paths)
+    ///
+    /// Archived issues are not included.
     async fn list_issues_info(&self) -> Result<Vec<IssueInfo>>;
 
+    /// List archived issues with extended information
+    async fn list_archived_issues(&self) -> Result<Vec<IssueInfo>>;
+
     /// Get a specific issue by name
     async fn get_issue(&self, name: &str) -> Result<Issue>;
 
@@ -112,11 +146,125 @@ pub trait IssueStorage: Send + Sync {
     /// Mark an issue as completed by name
     async fn complete_issue(&self, name: &str) -> Result<Issue>;
 
-    /// Get the next available issue (first pending issue alphabetically)
+    /// Move a completed or archived issue back to the active issues
+    async fn reopen_issue(&self, name: &str) -> Result<Issue>;
+
+    /// Move an issue out of the way into the archived issues
+    ///
+    /// Archived issues are neither pending nor completed: they are skipped by
+    /// `next_issue`, `all_issues_completed` and the issue listings. Issues that
+    /// depend on an archived issue are no longer blocked by it.
+    async fn archive_issue(&self, name: &str) -> Result<Issue>;
+
+    /// Add and remove labels in an issue's front matter
+    async fn relabel_issue(&self, name: &str, add: &[String], remove: &[String]) -> Result<Issue> {
+        let issue = self.get_issue(name).await?;
+        let mut labels = issue.metadata().labels;
+        labels.retain(|label| !remove.iter().any(|r| r.eq_ignore_ascii_case(label)));
+        for label in add {
+            if !labels.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+                labels.push(label.clone());
+            }
+        }
+
+        let content = set_labels(&issue.content, &labels)?;
+        if content == issue.content {
+            return Ok(issue);
+        }
+        self.update_issue(name, content).await
+    }
+
+    /// Issues matched by `filter`
+    ///
+    /// Searches the archived issues when the filter asks for them, and the
+    /// active and completed issues otherwise.
+    async fn select_issues(&self, filter: &IssueFilter) -> Result<Vec<IssueInfo>> {
+        let infos = if filter.status == Some(IssueStatus::Archived) {
+            self.list_archived_issues().await?
+        } else {
+            self.list_issues_info().await?
+        };
+        filter.select(infos)
+    }
+
+    /// Complete every issue matched by `filter`
+    async fn bulk_complete(&self, filter: &IssueFilter) -> Result<BulkResult> {
+        let mut result = BulkResult::default();
+        for info in self.select_issues(filter).await? {
+            if info.completed {
+                continue;
+            }
+            let outcome = self.complete_issue(&info.issue.name).await;
+            result.record(info.issue.name, outcome);
+        }
+        Ok(result)
+    }
+
+    /// Archive every issue matched by `filter`
+    async fn bulk_archive(&self, filter: &IssueFilter) -> Result<BulkResult> {
+        let mut result = BulkResult::default();
+        for info in self.select_issues(filter).await? {
+            if info.archived {
+                continue;
+            }
+            let outcome = self.archive_issue(&info.issue.name).await;
+            result.record(info.issue.name, outcome);
+        }
+        Ok(result)
+    }
+
+    /// Delete every issue matched by `filter`
+    async fn bulk_delete(&self, filter: &IssueFilter) -> Result<BulkResult> {
+        let mut result = BulkResult::default();
+        for info in self.select_issues(filter).await? {
+            let outcome = self.delete_issue(&info.issue.name).await;
+            result.record(info.issue.name, outcome);
+        }
+        Ok(result)
+    }
+
+    /// Add and remove labels on every issue matched by `filter`
+    async fn bulk_relabel(
+        &self,
+        filter: &IssueFilter,
+        add: &[String],
+        remove: &[String],
+    ) -> Result<BulkResult> {
+        let mut result = BulkResult::default();
+        for info in self.select_issues(filter).await? {
+            let outcome = self.relabel_issue(&info.issue.name, add, remove).await;
+            result.record(info.issue.name, outcome);
+        }
+        Ok(result)
+    }
+
+    /// Get the next available issue
+    ///
+    /// This is the most urgent pending issue whose dependencies are all
+    /// completed or archived, with ties going to the first name alphabetically.
+    /// Fails with `IssuesBlocked` when issues are pending but none can start.
     async fn next_issue(&self) -> Result<Option<Issue>>;
 
     /// Check if all issues are completed
     async fn all_issues_completed(&self) -> Result<bool>;
+
+    /// Append-only history of an issue, oldest first
+    async fn issue_history(&self, name: &str) -> Result<Vec<IssueHistoryEntry>>;
+
+    /// Record a change made outside the storage, such as a work branch or a merge
+    async fn record_issue_change(&self, name: &str, change: IssueChange) -> Result<()>;
+
+    /// Restore the content an issue had at `revision` of its history
+    ///
+    /// The restored content is recorded as a new revision.
+    async fn revert_issue(&self, name: &str, revision: u32) -> Result<Issue>;
+
+    /// Synchronize with a remote issue tracker
+    ///
+    /// Returns `None` for storages that are not backed by a tracker.
+    async fn sync(&self, _strategy: ConflictStrategy) -> Result<Option<SyncReport>> {
+        Ok(None)
+    }
 }
 
 /// File-system based issue storage implementation
@@ -126,6 +274,10 @@ pub struct FileSystemIssueStorage {
     issues_dir: PathBuf,
     /// Directory for completed issues
     completed_dir: PathBuf,
+    /// Directory for archived issues
+    archived_dir: PathBuf,
+    /// Append-only change history
+    history: IssueHistoryStore,
     /// Locking mechanism for thread safety
     lock: Mutex<()>,
 }
@@ -133,15 +285,19 @@ pub struct FileSystemIssueStorage {
 impl FileSystemIssueStorage {
     /// Create a new FileSystemIssueStorage with a specific directory
     pub fn new(issues_dir: PathBuf) -> Result<Self> {
-        let completed_dir = issues_dir.join("complete");
+        let completed_dir = issues_dir.join(COMPLETED_DIR);
+        let archived_dir = issues_dir.join(ARCHIVED_DIR);
 
         // Create directories if they don't exist
         fs::create_dir_all(&issues_dir).map_err(SwissArmyHammerError::Io)?;
         fs::create_dir_all(&completed_dir).map_err(SwissArmyHammerError::Io)?;
+        fs::create_dir_all(&archived_dir).map_err(SwissArmyHammerError::Io)?;
 
         Ok(Self {
+            history: IssueHistoryStore::new(&issues_dir),
             issues_dir,
             completed_dir,
+            archived_dir,
             lock: Mutex::new(()),
         })
     }
@@ -180,6 +336,7 @@ impl FileSystemIssueStorage {
         IssueState {
             issues_dir: self.issues_dir.clone(),
             completed_dir: self.completed_dir.clone(),
+            archived_dir: self.archived_dir.clone(),
         }
     }
 
@@ -243,9 +400,52 @@ impl FileSystemIssueStorage {
             return Ok(Some(completed_path));
         }
 
+        // Check archived issues directory
+        let archived_path = self.archived_dir.join(format!("{}.md", name));
+        if archived_path.exists() {
+            return Ok(Some(archived_path));
+        }
+
         Ok(None)
     }
 
+    /// Status of the issue stored at `file_path`
+    fn status_of(&self, file_path: &Path) -> IssueStatus {
+        match file_path.parent() {
+            Some(parent) if parent == self.completed_dir => IssueStatus::Completed,
+            Some(parent) if parent == self.archived_dir => IssueStatus::Archived,
+            _ => IssueStatus::Active,
+        }
+    }
+
+    /// Move an issue into `dir`, recording the status change
+    ///
+    /// Issues already in `dir` are returned unchanged.
+    fn move_issue(&self, name: &str, dir: &Path, to: IssueStatus) -> Result<Issue> {
+        let file_path = self
+            .find_issue_file(name)?
+            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+        let issue = self.load_issue_from_path(&file_path)?;
+
+        let from = self.status_of(&file_path);
+        if from == to {
+            return Ok(issue);
+        }
+
+        let new_path = dir.join(format!("{}.md", name));
+        fs::rename(&file_path, &new_path).map_err(SwissArmyHammerError::Io)?;
+        self.history
+            .append(name, IssueChange::StatusChanged { from, to })?;
+
+        debug!(
+            "Moved issue '{}' from {} to {}",
+            name,
+            file_path.display(),
+            new_path.display()
+        );
+        Ok(issue)
+    }
+
     /// Generate a unique issue name using ULID
     fn generate_issue_name(&self) -> String {
         generate_monotonic_ulid_string()
@@ -340,6 +540,25 @@ impl IssueStorage for FileSystemIssueStorage {
         Ok(issues_info)
     }
 
+    async fn list_archived_issues(&self) -> Result<Vec<IssueInfo>> {
+        let _lock = self.lock.lock().await;
+
+        let mut issues_info = Vec::new();
+        for file_path in Self::list_markdown_files(&self.archived_dir)? {
+            match self.load_issue_from_path(&file_path) {
+                Ok(issue) => issues_info.push(IssueInfo::from_issue_and_path(
+                    issue,
+                    file_path,
+                    &self.completed_dir,
+                )),
+                Err(e) => {
+                    warn!("Failed to load issue from {}: {}", file_path.display(), e);
+                }
+            }
+        }
+        Ok(issues_info)
+    }
+
     async fn get_issue(&self, name: &str) -> Result<Issue> {
         let _lock = self.lock.lock().await;
 
@@ -387,6 +606,12 @@ impl IssueStorage for FileSystemIssueStorage {
 
         let file_path = self.issues_dir.join(format!("{}.md", issue_name));
         self.save_issue_to_file(&issue, &file_path)?;
+        self.history.append(
+            &issue_name,
+            IssueChange::Created {
+                content: issue.content.clone(),
+            },
+        )?;
 
         debug!("Created issue '{}' at {}", issue_name, file_path.display());
         Ok(issue)
@@ -399,12 +624,19 @@ impl IssueStorage for FileSystemIssueStorage {
             .find_issue_file(name)?
             .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
 
+        let previous = self.load_issue_from_path(&file_path)?;
         let issue = Issue {
             name: name.to_string(),
             content,
         };
 
         self.save_issue_to_file(&issue, &file_path)?;
+        if previous.content != issue.content {
+            self.history.append(
+                name,
+                IssueChange::edited(name, &previous.content, &issue.content),
+            )?;
+        }
         debug!("Updated issue '{}' at {}", name, file_path.display());
         Ok(issue)
     }
@@ -417,36 +649,24 @@ impl IssueStorage for FileSystemIssueStorage {
             .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
 
         fs::remove_file(&file_path).map_err(SwissArmyHammerError::Io)?;
+        self.history.append(name, IssueChange::Deleted)?;
         debug!("Deleted issue '{}' from {}", name, file_path.display());
         Ok(())
     }
 
     async fn complete_issue(&self, name: &str) -> Result<Issue> {
         let _lock = self.lock.lock().await;
+        self.move_issue(name, &self.completed_dir, IssueStatus::Completed)
+    }
 
-        let file_path = self
-            .find_issue_file(name)?
-            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
-
-        let issue = self.load_issue_from_path(&file_path)?;
-
-        // If already completed, just return it
-        if file_path.parent() == Some(&self.completed_dir) {
-            return Ok(issue);
-        }
-
-        // Move to completed directory
-        let completed_path = self.completed_dir.join(format!("{}.md", name));
-        fs::rename(&file_path, &completed_path).map_err(SwissArmyHammerError::Io)?;
-
-        debug!(
-            "Completed issue '{}': moved from {} to {}",
-            name,
-            file_path.display(),
-            completed_path.display()
-        );
+    async fn reopen_issue(&self, name: &str) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+        self.move_issue(name, &self.issues_dir, IssueStatus::Active)
+    }
 
-        Ok(issue)
+    async fn archive_issue(&self, name: &str) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+        self.move_issue(name, &self.archived_dir, IssueStatus::Archived)
     }
 
     async fn next_issue(&self) -> Result<Option<Issue>> {
@@ -457,9 +677,27 @@ impl IssueStorage for FileSystemIssueStorage {
             return Ok(None);
         }
 
-        // Return the first issue (files are sorted)
-        let issue = self.load_issue_from_path(&active_files[0])?;
-        Ok(Some(issue))
+        let mut pending = Vec::with_capacity(active_files.len());
+        for file_path in &active_files {
+            match self.load_issue_from_path(file_path) {
+                Ok(issue) => pending.push(issue),
+                Err(e) => {
+                    warn!("Failed to load issue from {}: {}", file_path.display(), e);
+                }
+            }
+        }
+
+        // Archived issues will never be completed, so depending on one does
+        // not keep an issue blocked
+        let mut satisfied = Self::list_markdown_files(&self.completed_dir)?;
+        satisfied.extend(Self::list_markdown_files(&self.archived_dir)?);
+        let satisfied: HashSet<String> = satisfied
+            .iter()
+            .filter_map(|path| path.file_stem().and_then(|s| s.to_str()))
+            .map(str::to_string)
+            .collect();
+
+        select_next_issue(pending, &satisfied)
     }
 
     async fn all_issues_completed(&self) -> Result<bool> {
@@ -468,6 +706,53 @@ impl IssueStorage for FileSystemIssueStorage {
         let active_files = Self::list_markdown_files(&self.issues_dir)?;
         Ok(active_files.is_empty())
     }
+
+    async fn issue_history(&self, name: &str) -> Result<Vec<IssueHistoryEntry>> {
+        let _lock = self.lock.lock().await;
+
+        let entries = self.history.entries(name)?;
+        if entries.is_empty() && self.find_issue_file(name)?.is_none() {
+            return Err(SwissArmyHammerError::IssueNotFound(name.to_string()));
+        }
+        Ok(entries)
+    }
+
+    async fn record_issue_change(&self, name: &str, change: IssueChange) -> Result<()> {
+        let _lock = self.lock.lock().await;
+
+        if self.find_issue_file(name)?.is_none() {
+            return Err(SwissArmyHammerError::IssueNotFound(name.to_string()));
+        }
+        self.history.append(name, change)?;
+        Ok(())
+    }
+
+    async fn revert_issue(&self, name: &str, revision: u32) -> Result<Issue> {
+        let _lock = self.lock.lock().await;
+
+        let file_path = self
+            .find_issue_file(name)?
+            .ok_or_else(|| SwissArmyHammerError::IssueNotFound(name.to_string()))?;
+        let previous = self.load_issue_from_path(&file_path)?;
+        let content = self.history.content_at(name, revision)?;
+
+        let issue = Issue {
+            name: name.to_string(),
+            content,
+        };
+        self.save_issue_to_file(&issue, &file_path)?;
+        self.history.append(
+            name,
+            IssueChange::Reverted {
+                revision,
+                diff: crate::issues::history::unified_diff(name, &previous.content, &issue.content),
+                content: issue.content.clone(),
+            },
+        )?;
+
+        debug!("Reverted issue '{}' to revision {}", name, revision);
+        Ok(issue)
+    }
 }
 
 #[cfg(test)]
@@ -564,6 +849,83 @@ mod tests {
         assert_eq!(next.unwrap().name, "a-issue");
     }
 
+    #[tokio::test]
+    async fn test_next_issue_uses_front_matter() {
+        let (storage, _temp_dir) = create_temp_storage();
+
+        storage
+            .create_issue("a-plain".to_string(), "# Plain issue".to_string())
+            .await
+            .unwrap();
+        storage
+            .create_issue(
+                "b-urgent".to_string(),
+                "---\npriority: critical\ndepends_on: [c-setup]\n---\n# Urgent".to_string(),
+            )
+            .await
+            .unwrap();
+        storage
+            .create_issue(
+                "c-setup".to_string(),
+                "---\npriority: high\n---\n# Setup".to_string(),
+            )
+            .await
+            .unwrap();
+
+        // The urgent issue waits for its dependency, which outranks the plain issue
+        let next = storage.next_issue().await.unwrap().unwrap();
+        assert_eq!(next.name, "c-setup");
+
+        storage.complete_issue("c-setup").await.unwrap();
+        let next = storage.next_issue().await.unwrap().unwrap();
+        assert_eq!(next.name, "b-urgent");
+        assert_eq!(
+            next.metadata().priority,
+            Some(crate::issues::IssuePriority::Critical)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_next_issue_reports_blocked_issues() {
+        let (storage, _temp_dir) = create_temp_storage();
+
+        storage
+            .create_issue(
+                "a-first".to_string(),
+                "---\ndepends_on: [b-second]\n---\n# First".to_string(),
+            )
+            .await
+            .unwrap();
+        storage
+            .create_issue(
+                "b-second".to_string(),
+                "---\ndepends_on: [a-first, c-dropped]\n---\n# Second".to_string(),
+            )
+            .await
+            .unwrap();
+        storage
+            .create_issue("c-dropped".to_string(), "# Dropped".to_string())
+            .await
+            .unwrap();
+        storage.archive_issue("c-dropped").await.unwrap();
+
+        // A cycle is reported instead of looking like there is nothing to do
+        let error = storage.next_issue().await.unwrap_err();
+        assert!(matches!(error, SwissArmyHammerError::IssuesBlocked(_)));
+        assert!(error
+            .to_string()
+            .contains("a-first waits on b-second (pending)"));
+        assert!(error
+            .to_string()
+            .contains("b-second waits on a-first (pending)"));
+        assert!(!storage.all_issues_completed().await.unwrap());
+
+        // The archived dependency no longer blocks anything
+        storage.archive_issue("a-first").await.unwrap();
+        let next = storage.next_issue().await.unwrap().unwrap();
+        assert_eq!(next.name, "b-second");
+    }
+
     #[tokio::test]
     async fn test_all_issues_completed() {
         let (storage, _temp_dir) = create_temp_storage();
@@ -601,4 +963,160 @@ mod tests {
         assert!(!issue.name.is_empty());
         assert!(issue.name.len() > 10); // ULIDs are longer than this
     }
+
+    #[tokio::test]
+    async fn test_history_and_revert() {
+        let (storage, _temp_dir) = create_temp_storage();
+
+        storage
+            .create_issue("parser".to_string(), "# Parser\n".to_string())
+            .await
+            .unwrap();
+        storage
+            .update_issue("parser", "# Parser\n\nFirst draft\n".to_string())
+            .await
+            .unwrap();
+        storage
+            .update_issue("parser", "# Parser\n\nRewritten\n".to_string())
+            .await
+            .unwrap();
+
+        let reverted = storage.revert_issue("parser", 2).await.unwrap();
+        assert_eq!(reverted.content, "# Parser\n\nFirst draft\n");
+        storage.complete_issue("parser").await.unwrap();
+
+        let history = storage.issue_history("parser").await.unwrap();
+        let summaries: Vec<String> = history.iter().map(|e| e.change.summary()).collect();
+        assert_eq!(
+            summaries,
+            vec![
+                "created",
+                "edited (+2 -0)",
+                "edited (+1 -1)",
+                "reverted to revision 2",
+                "active → completed",
+            ]
+        );
+        assert_eq!(history[4].revision, 5);
+
+        // Unchanged content is not recorded
+        storage
+            .update_issue("parser", reverted.content.clone())
+            .await
+            .unwrap();
+        assert_eq!(storage.issue_history("parser").await.unwrap().len(), 5);
+
+        assert!(storage.revert_issue("parser", 9).await.is_err());
+        assert!(matches!(
+            storage.issue_history("missing").await,
+            Err(SwissArmyHammerError::IssueNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reopen_and_archive_issue() {
+        let (storage, temp_dir) = create_temp_storage();
+        let issues_dir = temp_dir.path().join("issues");
+
+        storage
+            .create_issue("done".to_string(), "# Done".to_string())
+            .await
+            .unwrap();
+        storage
+            .create_issue("stale".to_string(), "# Stale".to_string())
+            .await
+            .unwrap();
+
+        storage.complete_issue("done").await.unwrap();
+        storage.reopen_issue("done").await.unwrap();
+        assert!(issues_dir.join("done.md").exists());
+        assert!(!storage.get_issue_info("done").await.unwrap().completed);
+
+        // Archived issues do not keep the project from being complete
+        storage.complete_issue("done").await.unwrap();
+        storage.archive_issue("stale").await.unwrap();
+        assert!(issues_dir.join("archived/stale.md").exists());
+        assert!(storage.all_issues_completed().await.unwrap());
+        assert!(storage.next_issue().await.unwrap().is_none());
+
+        let listed: Vec<String> = storage
+            .list_issues()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|issue| issue.name)
+            .collect();
+        assert_eq!(listed, vec!["done"]);
+        let archived = storage.list_archived_issues().await.unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].status(), IssueStatus::Archived);
+        assert_eq!(
+            storage.get_issue_info("stale").await.unwrap().status(),
+            IssueStatus::Archived
+        );
+
+        storage.reopen_issue("stale").await.unwrap();
+        assert!(!storage.all_issues_completed().await.unwrap());
+        let summaries: Vec<String> = storage
+            .issue_history("stale")
+            .await
+            .unwrap()
+            .iter()
+            .map(|e| e.change.summary())
+            .collect();
+        assert_eq!(
+            summaries,
+            vec!["created", "active → archived", "archived → active"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bulk_operations() {
+        let (storage, _temp_dir) = create_temp_storage();
+        for (name, content) in [
+            ("auth_login", "---\nlabels: [bug]\n---\n# Login"),
+            ("auth_logout", "# Logout"),
+            ("search_index", "---\nlabels: [bug, perf]\n---\n# Index"),
+        ] {
+            storage
+                .create_issue(name.to_string(), content.to_string())
+                .await
+                .unwrap();
+        }
+
+        let result = storage
+            .bulk_relabel(
+                &IssueFilter::by_name("auth_*"),
+                &["auth".to_string()],
+                &["bug".to_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.succeeded, vec!["auth_login", "auth_logout"]);
+        let metadata = storage.get_issue("auth_login").await.unwrap().metadata();
+        assert_eq!(metadata.labels, vec!["auth"]);
+
+        let result = storage
+            .bulk_complete(&IssueFilter::by_label("auth"))
+            .await
+            .unwrap();
+        assert_eq!(result.succeeded, vec!["auth_login", "auth_logout"]);
+        assert!(result.failed.is_empty());
+
+        let result = storage
+            .bulk_archive(&IssueFilter::by_label("perf"))
+            .await
+            .unwrap();
+        assert_eq!(result.succeeded, vec!["search_index"]);
+        assert!(storage.all_issues_completed().await.unwrap());
+
+        let archived = IssueFilter {
+            status: Some(IssueStatus::Archived),
+            ..IssueFilter::by_name("*")
+        };
+        let result = storage.bulk_delete(&archived).await.unwrap();
+        assert_eq!(result.succeeded, vec!["search_index"]);
+        assert!(storage.get_issue("search_index").await.is_err());
+        assert_eq!(storage.list_issues().await.unwrap().len(), 2);
+    }
 }
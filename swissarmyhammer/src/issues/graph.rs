@@ -0,0 +1,773 @@
+//! Dependency graph between issues
+//!
+//! Issues refer to each other in three ways, from most to least explicit:
+//!
+//! - `depends_on` and `blocked_by` in their front matter
+//! - markdown links to other issue files, such as `[parser](0003_parser.md)`
+//! - plain mentions of another issue's name, such as "after 0003_parser"
+//!
+//! Every reference is read as "this issue depends on the referenced one". The
+//! resulting graph can be rendered as Mermaid or DOT and analysed for cycles,
+//! references to issues that do not exist, the critical path through the
+//! remaining work and the issues that can be started now.
+
+use crate::frontmatter::parse_frontmatter;
+use crate::issues::filesystem::IssueInfo;
+use crate::issues::metadata::IssuePriority;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Minimum length of an issue name for plain mentions of it to count
+///
+/// Shorter names match too many ordinary words.
+pub const MIN_MENTION_NAME_LENGTH: usize = 4;
+
+/// How one issue refers to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueReferenceKind {
+    /// Listed in `depends_on` or `blocked_by` front matter
+    FrontMatter,
+    /// Markdown link to the other issue's file
+    Link,
+    /// The other issue's name appears in the text
+    Mention,
+}
+
+/// An edge of the graph: `issue` depends on `depends_on`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueDependency {
+    /// The dependent issue
+    pub issue: String,
+    /// The issue that must be completed first
+    pub depends_on: String,
+    /// How the reference was found
+    pub kind: IssueReferenceKind,
+}
+
+/// An issue in the graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueNode {
+    /// Issue name
+    pub name: String,
+    /// Whether the issue is completed
+    pub completed: bool,
+    /// Priority from the front matter
+    pub priority: Option<IssuePriority>,
+    /// Estimate from the front matter
+    pub estimate: Option<String>,
+}
+
+/// Longest chain of remaining work
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CriticalPath {
+    /// Issues in the order they have to be done
+    pub issues: Vec<String>,
+    /// Sum of the issues' estimates, counting issues without one as 1
+    pub total_estimate: f64,
+}
+
+/// Result of analysing an issue graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueGraphAnalysis {
+    /// Dependency cycles, each starting and ending with the same issue
+    pub cycles: Vec<Vec<String>>,
+    /// References to issues that do not exist
+    pub dangling: Vec<IssueDependency>,
+    /// Longest chain of active issues, if the active issues have no cycles
+    pub critical_path: Option<CriticalPath>,
+    /// Active issues whose dependencies are all completed
+    pub unblocked: Vec<String>,
+}
+
+/// Dependency graph built from issue files
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IssueGraph {
+    /// Issues by name
+    pub nodes: BTreeMap<String, IssueNode>,
+    /// Dependencies between existing issues, sorted and without duplicates
+    pub dependencies: Vec<IssueDependency>,
+    /// Front matter entries and links naming issues that do not exist
+    pub dangling: Vec<IssueDependency>,
+}
+
+fn link_regex() -> &'static Regex {
+    static LINK_REGEX: OnceLock<Regex> = OnceLock::new();
+    LINK_REGEX.get_or_init(|| Regex::new(r"\[[^\]]*\]\(<?([^)\s>]+)>?\)").unwrap())
+}
+
+/// Whether `c` can be part of an issue name for mention matching
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Whether `name` occurs in `text` as a whole word
+fn mentions(text: &str, name: &str) -> bool {
+    text.match_indices(name).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + name.len()..].chars().next();
+        !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char)
+    })
+}
+
+/// Issue name a link target points at, if it is a local markdown file
+///
+/// Only sibling files and files in an `issues` or `complete` directory count, so
+/// links to documentation are not mistaken for missing issues.
+fn linked_issue_name(target: &str) -> Option<String> {
+    if target.contains("://") || target.starts_with('#') {
+        return None;
+    }
+    let target = target.split('#').next().unwrap_or(target);
+    let path = Path::new(target);
+    if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+        return None;
+    }
+    let in_issue_dir = match path.parent().and_then(|parent| parent.file_name()) {
+        None => true,
+        Some(dir) => dir == "issues" || dir == "complete",
+    };
+    if !in_issue_dir {
+        return None;
+    }
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(str::to_string)
+}
+
+/// Weight of an estimate such as `3`, `2h`, `1.5d` or `1w`, in hours for units
+///
+/// Estimates that cannot be read count as 1.
+pub fn estimate_weight(estimate: Option<&str>) -> f64 {
+    let Some(estimate) = estimate.map(str::trim) else {
+        return 1.0;
+    };
+    let split = estimate
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(estimate.len());
+    let Ok(value) = estimate[..split].parse::<f64>() else {
+        return 1.0;
+    };
+    let multiplier = match estimate[split..].trim().to_lowercase().as_str() {
+        "" | "h" | "hr" | "hrs" | "hour" | "hours" | "pt" | "pts" | "points" => 1.0,
+        "m" | "min" | "mins" | "minutes" => 1.0 / 60.0,
+        "d" | "day" | "days" => 8.0,
+        "w" | "wk" | "week" | "weeks" => 40.0,
+        _ => 1.0,
+    };
+    value * multiplier
+}
+
+impl IssueGraph {
+    /// Build the graph from every issue, active and completed
+    pub fn from_issues(issues: &[IssueInfo]) -> Self {
+        let mut graph = Self::default();
+        for info in issues {
+            let metadata = info.issue.metadata();
+            graph.nodes.insert(
+                info.issue.name.clone(),
+                IssueNode {
+                    name: info.issue.name.clone(),
+                    completed: info.completed,
+                    priority: metadata.priority,
+                    estimate: metadata.estimate.clone(),
+                },
+            );
+        }
+
+        let mut edges: BTreeMap<(String, String), IssueReferenceKind> = BTreeMap::new();
+        let mut dangling: BTreeMap<(String, String), IssueReferenceKind> = BTreeMap::new();
+        let mut add = |issue: &str, target: String, kind: IssueReferenceKind, known: bool| {
+            if target == issue {
+                return;
+            }
+            let map = if known { &mut edges } else { &mut dangling };
+            let entry = map.entry((issue.to_string(), target)).or_insert(kind);
+            *entry = (*entry).min(kind);
+        };
+
+        for info in issues {
+            let name = info.issue.name.as_str();
+            let metadata = info.issue.metadata();
+            for target in metadata.dependencies() {
+                let known = graph.nodes.contains_key(target);
+                add(
+                    name,
+                    target.to_string(),
+                    IssueReferenceKind::FrontMatter,
+                    known,
+                );
+            }
+
+            let body = parse_frontmatter(&info.issue.content)
+                .map(|frontmatter| frontmatter.content)
+                .unwrap_or_else(|_| info.issue.content.clone());
+
+            for capture in link_regex().captures_iter(&body) {
+                if let Some(target) = linked_issue_name(&capture[1]) {
+                    let known = graph.nodes.contains_key(&target);
+                    add(name, target, IssueReferenceKind::Link, known);
+                }
+            }
+
+            for other in graph.nodes.keys() {
+                if other.chars().count() >= MIN_MENTION_NAME_LENGTH && mentions(&body, other) {
+                    add(name, other.clone(), IssueReferenceKind::Mention, true);
+                }
+            }
+        }
+
+        let to_dependencies = |map: BTreeMap<(String, String), IssueReferenceKind>| {
+            map.into_iter()
+                .map(|((issue, depends_on), kind)| IssueDependency {
+                    issue,
+                    depends_on,
+                    kind,
+                })
+                .collect()
+        };
+        graph.dependencies = to_dependencies(edges);
+        graph.dangling = to_dependencies(dangling);
+        graph
+    }
+
+    /// Copy of the graph without completed issues and the edges touching them
+    pub fn without_completed(&self) -> Self {
+        let nodes: BTreeMap<_, _> = self
+            .nodes
+            .iter()
+            .filter(|(_, node)| !node.completed)
+            .map(|(name, node)| (name.clone(), node.clone()))
+            .collect();
+        let dependencies = self
+            .dependencies
+            .iter()
+            .filter(|dep| nodes.contains_key(&dep.issue) && nodes.contains_key(&dep.depends_on))
+            .cloned()
+            .collect();
+        let dangling = self
+            .dangling
+            .iter()
+            .filter(|dep| nodes.contains_key(&dep.issue))
+            .cloned()
+            .collect();
+        Self {
+            nodes,
+            dependencies,
+            dangling,
+        }
+    }
+
+    /// Names of the issues `name` depends on
+    pub fn dependencies_of(&self, name: &str) -> Vec<&str> {
+        self.dependencies
+            .iter()
+            .filter(|dep| dep.issue == name)
+            .map(|dep| dep.depends_on.as_str())
+            .collect()
+    }
+
+    /// Adjacency list from each issue to the issues depending on it
+    fn dependents(&self) -> HashMap<&str, Vec<&str>> {
+        let mut adjacency: HashMap<&str, Vec<&str>> = self
+            .nodes
+            .keys()
+            .map(|name| (name.as_str(), Vec::new()))
+            .collect();
+        for dep in &self.dependencies {
+            adjacency
+                .entry(dep.depends_on.as_str())
+                .or_default()
+                .push(dep.issue.as_str());
+        }
+        adjacency
+    }
+
+    /// Detect every dependency cycle
+    ///
+    /// Each cycle lists its issues in dependency order and repeats the first
+    /// issue at the end.
+    pub fn detect_cycles(&self) -> Vec<Vec<String>> {
+        let adjacency = self.dependents();
+        let mut cycles = Vec::new();
+        let mut visited = BTreeSet::new();
+
+        for start in self.nodes.keys() {
+            if visited.contains(start.as_str()) {
+                continue;
+            }
+            let mut path = Vec::new();
+            Self::collect_cycles(start, &adjacency, &mut visited, &mut path, &mut cycles);
+        }
+
+        cycles
+    }
+
+    fn collect_cycles<'a>(
+        node: &'a str,
+        adjacency: &HashMap<&'a str, Vec<&'a str>>,
+        visited: &mut BTreeSet<&'a str>,
+        path: &mut Vec<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        path.push(node);
+        visited.insert(node);
+
+        for &next in adjacency.get(node).into_iter().flatten() {
+            if let Some(pos) = path.iter().position(|&n| n == next) {
+                let mut cycle: Vec<String> = path[pos..].iter().map(|n| n.to_string()).collect();
+                cycle.push(next.to_string());
+                cycles.push(cycle);
+            } else if !visited.contains(next) {
+                Self::collect_cycles(next, adjacency, visited, path, cycles);
+            }
+        }
+
+        path.pop();
+    }
+
+    /// Active issues whose dependencies are all completed
+    ///
+    /// Dangling front matter entries and links keep an issue blocked, the same
+    /// way `next_issue` treats unknown `depends_on` entries.
+    pub fn unblocked(&self) -> Vec<String> {
+        self.nodes
+            .values()
+            .filter(|node| !node.completed)
+            .filter(|node| {
+                !self.dangling.iter().any(|dep| dep.issue == node.name)
+                    && self
+                        .dependencies_of(&node.name)
+                        .iter()
+                        .all(|dep| self.nodes.get(*dep).is_some_and(|n| n.completed))
+            })
+            .map(|node| node.name.clone())
+            .collect()
+    }
+
+    /// Longest chain of active issues, weighted by their estimates
+    ///
+    /// Returns `None` when there are no active issues or they contain a cycle.
+    pub fn critical_path(&self) -> Option<CriticalPath> {
+        let active = self.without_completed();
+        if active.nodes.is_empty() {
+            return None;
+        }
+        let adjacency = active.dependents();
+
+        // Kahn's algorithm, visiting ready issues by name for a stable result
+        let mut in_degree: BTreeMap<&str, usize> =
+            active.nodes.keys().map(|name| (name.as_str(), 0)).collect();
+        for dep in &active.dependencies {
+            *in_degree.get_mut(dep.issue.as_str())? += 1;
+        }
+        let mut queue: VecDeque<&str> = in_degree
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(&name, _)| name)
+            .collect();
+
+        let mut best: HashMap<&str, (f64, Option<&str>)> = HashMap::new();
+        let mut processed = 0;
+        while let Some(name) = queue.pop_front() {
+            processed += 1;
+            let weight = estimate_weight(active.nodes[name].estimate.as_deref());
+            let (length, _) = *best.entry(name).or_insert((weight, None));
+
+            let mut dependents = adjacency.get(name).cloned().unwrap_or_default();
+            dependents.sort_unstable();
+            for next in dependents {
+                let next_weight = estimate_weight(active.nodes[next].estimate.as_deref());
+                let candidate = length + next_weight;
+                let entry = best.entry(next).or_insert((next_weight, None));
+                if entry.1.is_none() || candidate > entry.0 {
+                    *entry = (candidate, Some(name));
+                }
+                let degree = in_degree.get_mut(next)?;
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+        if processed != active.nodes.len() {
+            return None;
+        }
+
+        let (&end, &(total_estimate, _)) = best.iter().max_by(|a, b| {
+            a.1 .0
+                .partial_cmp(&b.1 .0)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.0.cmp(a.0))
+        })?;
+
+        let mut issues = vec![end.to_string()];
+        let mut current = end;
+        while let Some(&(_, Some(previous))) = best.get(current) {
+            issues.push(previous.to_string());
+            current = previous;
+        }
+        issues.reverse();
+
+        Some(CriticalPath {
+            issues,
+            total_estimate,
+        })
+    }
+
+    /// Run every analysis
+    pub fn analyze(&self) -> IssueGraphAnalysis {
+        IssueGraphAnalysis {
+            cycles: self.detect_cycles(),
+            dangling: self.dangling.clone(),
+            critical_path: self.critical_path(),
+            unblocked: self.unblocked(),
+        }
+    }
+
+    /// Render the graph as a Mermaid flowchart
+    ///
+    /// Arrows point from an issue to the issues depending on it. Completed,
+    /// unblocked, critical path and missing issues get their own classes.
+    pub fn to_mermaid(&self) -> String {
+        let analysis = self.analyze();
+        let ids: HashMap<&str, String> = self
+            .nodes
+            .keys()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), format!("i{i}")))
+            .collect();
+        let missing: BTreeSet<&str> = self
+            .dangling
+            .iter()
+            .map(|dep| dep.depends_on.as_str())
+            .collect();
+        let missing_ids: HashMap<&str, String> = missing
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (*name, format!("m{i}")))
+            .collect();
+        let escape = |label: &str| label.replace('"', "#quot;");
+
+        let mut diagram = String::from("graph TD\n");
+        for name in self.nodes.keys() {
+            diagram.push_str(&format!(
+                "    {}[\"{}\"]\n",
+                ids[name.as_str()],
+                escape(name)
+            ));
+        }
+        for name in &missing {
+            diagram.push_str(&format!(
+                "    {}[\"{} (missing)\"]\n",
+                missing_ids[name],
+                escape(name)
+            ));
+        }
+
+        for dep in &self.dependencies {
+            let arrow = match dep.kind {
+                IssueReferenceKind::Mention => "-.->",
+                _ => "-->",
+            };
+            diagram.push_str(&format!(
+                "    {} {} {}\n",
+                ids[dep.depends_on.as_str()],
+                arrow,
+                ids[dep.issue.as_str()]
+            ));
+        }
+        for dep in &self.dangling {
+            diagram.push_str(&format!(
+                "    {} -.-> {}\n",
+                missing_ids[dep.depends_on.as_str()],
+                ids[dep.issue.as_str()]
+            ));
+        }
+
+        diagram.push_str("    classDef completed fill:#d4edda,stroke:#28a745\n");
+        diagram.push_str("    classDef unblocked fill:#fff3cd,stroke:#ffc107\n");
+        diagram.push_str("    classDef critical stroke:#dc3545,stroke-width:3px\n");
+        diagram.push_str("    classDef missing fill:#f8d7da,stroke:#dc3545,stroke-dasharray:5 5\n");
+
+        let class_line = |class: &str, names: Vec<&String>| {
+            let members: Vec<&str> = names
+                .into_iter()
+                .filter_map(|name| ids.get(name.as_str()).map(String::as_str))
+                .collect();
+            if members.is_empty() {
+                String::new()
+            } else {
+                format!("    class {} {class}\n", members.join(","))
+            }
+        };
+        let completed: Vec<&String> = self
+            .nodes
+            .values()
+            .filter(|node| node.completed)
+            .map(|node| &node.name)
+            .collect();
+        diagram.push_str(&class_line("completed", completed));
+        diagram.push_str(&class_line(
+            "unblocked",
+            analysis.unblocked.iter().collect(),
+        ));
+        if let Some(path) = &analysis.critical_path {
+            diagram.push_str(&class_line("critical", path.issues.iter().collect()));
+        }
+        if !missing_ids.is_empty() {
+            let members: Vec<&str> = missing_ids.values().map(String::as_str).collect();
+            diagram.push_str(&format!("    class {} missing\n", members.join(",")));
+        }
+
+        diagram
+    }
+
+    /// Render the graph in Graphviz DOT format
+    pub fn to_dot(&self) -> String {
+        let analysis = self.analyze();
+        let critical: BTreeSet<&str> = analysis
+            .critical_path
+            .as_ref()
+            .map(|path| path.issues.iter().map(String::as_str).collect())
+            .unwrap_or_default();
+        let escape = |label: &str| label.replace('\\', "\\\\").replace('"', "\\\"");
+
+        let mut dot = String::from("digraph issues {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=box, style=rounded];\n");
+
+        for node in self.nodes.values() {
+            let mut attributes = Vec::new();
+            if node.completed {
+                attributes.push("style=\"rounded,filled\", fillcolor=\"#d4edda\"".to_string());
+            } else if analysis.unblocked.contains(&node.name) {
+                attributes.push("style=\"rounded,filled\", fillcolor=\"#fff3cd\"".to_string());
+            }
+            if critical.contains(node.name.as_str()) {
+                attributes.push("color=\"#dc3545\", penwidth=3".to_string());
+            }
+            if attributes.is_empty() {
+                dot.push_str(&format!("    \"{}\";\n", escape(&node.name)));
+            } else {
+                dot.push_str(&format!(
+                    "    \"{}\" [{}];\n",
+                    escape(&node.name),
+                    attributes.join(", ")
+                ));
+            }
+        }
+
+        let missing: BTreeSet<&str> = self
+            .dangling
+            .iter()
+            .map(|dep| dep.depends_on.as_str())
+            .collect();
+        for name in &missing {
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{} (missing)\", style=\"rounded,dashed\", color=\"#dc3545\"];\n",
+                escape(name),
+                escape(name)
+            ));
+        }
+
+        for dep in self.dependencies.iter().chain(self.dangling.iter()) {
+            let style = match dep.kind {
+                IssueReferenceKind::Mention => " [style=dashed]",
+                _ => "",
+            };
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\"{};\n",
+                escape(&dep.depends_on),
+                escape(&dep.issue),
+                style
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl IssueGraphAnalysis {
+    /// Human readable summary of the analysis
+    pub fn format_report(&self) -> String {
+        let mut report = String::new();
+
+        if self.cycles.is_empty() {
+            report.push_str("✅ No dependency cycles\n");
+        } else {
+            report.push_str(&format!("🔁 {} dependency cycle(s):\n", self.cycles.len()));
+            for cycle in &self.cycles {
+                report.push_str(&format!("  {}\n", cycle.join(" → ")));
+            }
+        }
+
+        if self.dangling.is_empty() {
+            report.push_str("✅ No dangling references\n");
+        } else {
+            report.push_str(&format!(
+                "❓ {} dangling reference(s):\n",
+                self.dangling.len()
+            ));
+            for dep in &self.dangling {
+                report.push_str(&format!("  {} → {} (missing)\n", dep.issue, dep.depends_on));
+            }
+        }
+
+        match &self.critical_path {
+            Some(path) => report.push_str(&format!(
+                "🛤️ Critical path ({} issue(s), estimate {}): {}\n",
+                path.issues.len(),
+                path.total_estimate,
+                path.issues.join(" → ")
+            )),
+            None => report.push_str("🛤️ Critical path: none\n"),
+        }
+
+        if self.unblocked.is_empty() {
+            report.push_str("🚧 No unblocked issues\n");
+        } else {
+            report.push_str(&format!(
+                "🟢 Unblocked ({}): {}\n",
+                self.unblocked.len(),
+                self.unblocked.join(", ")
+            ));
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issues::Issue;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn info(name: &str, content: &str, completed: bool) -> IssueInfo {
+        IssueInfo {
+            issue: Issue {
+                name: name.to_string(),
+                content: content.to_string(),
+            },
+            completed,
+            archived: false,
+            file_path: PathBuf::from(format!("issues/{name}.md")),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn sample_plan() -> Vec<IssueInfo> {
+        vec![
+            info("0001_setup", "# Setup\n\nCreate the project.", true),
+            info(
+                "0002_lexer",
+                "---\nestimate: 3h\n---\n# Lexer\n\nBuilds on 0001_setup.",
+                false,
+            ),
+            info(
+                "0003_parser",
+                "# Parser\n\nSee [the lexer](0002_lexer.md) first.",
+                false,
+            ),
+            info(
+                "0004_docs",
+                "---\ndepends_on: [0001_setup]\n---\n# Docs\n\nRead [guide](../docs/guide.md).",
+                false,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_builds_edges_from_every_reference_kind() {
+        let graph = IssueGraph::from_issues(&sample_plan());
+
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.dependencies_of("0002_lexer"), vec!["0001_setup"]);
+        assert_eq!(graph.dependencies_of("0003_parser"), vec!["0002_lexer"]);
+        assert_eq!(graph.dependencies_of("0004_docs"), vec!["0001_setup"]);
+        assert!(graph.dangling.is_empty());
+
+        let kinds: Vec<_> = graph.dependencies.iter().map(|dep| dep.kind).collect();
+        assert!(kinds.contains(&IssueReferenceKind::FrontMatter));
+        assert!(kinds.contains(&IssueReferenceKind::Link));
+        assert!(kinds.contains(&IssueReferenceKind::Mention));
+    }
+
+    #[test]
+    fn test_mentions_need_whole_words() {
+        assert!(mentions("after 0003_parser is done", "0003_parser"));
+        assert!(mentions("(0003_parser)", "0003_parser"));
+        assert!(!mentions("see 0003_parser_v2", "0003_parser"));
+        assert!(!mentions("x0003_parser", "0003_parser"));
+    }
+
+    #[test]
+    fn test_unblocked_and_critical_path() {
+        let graph = IssueGraph::from_issues(&sample_plan());
+
+        assert_eq!(graph.unblocked(), vec!["0002_lexer", "0004_docs"]);
+
+        let path = graph.critical_path().unwrap();
+        assert_eq!(path.issues, vec!["0002_lexer", "0003_parser"]);
+        assert_eq!(path.total_estimate, 4.0);
+    }
+
+    #[test]
+    fn test_detects_cycles_and_dangling_references() {
+        let issues = vec![
+            info("alpha", "---\ndepends_on: [gamma]\n---\n", false),
+            info("beta", "Needs alpha.", false),
+            info(
+                "gamma",
+                "Needs [beta](beta.md) and [delta](delta.md).",
+                false,
+            ),
+        ];
+        let analysis = IssueGraph::from_issues(&issues).analyze();
+
+        assert_eq!(analysis.cycles.len(), 1);
+        let cycle = &analysis.cycles[0];
+        assert_eq!(cycle.first(), cycle.last());
+        assert_eq!(cycle.len(), 4);
+
+        assert_eq!(analysis.dangling.len(), 1);
+        assert_eq!(analysis.dangling[0].issue, "gamma");
+        assert_eq!(analysis.dangling[0].depends_on, "delta");
+
+        assert!(analysis.critical_path.is_none());
+        assert!(analysis.unblocked.is_empty());
+    }
+
+    #[test]
+    fn test_render_mermaid_and_dot() {
+        let graph = IssueGraph::from_issues(&sample_plan());
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.starts_with("graph TD\n"));
+        assert!(mermaid.contains("i1[\"0002_lexer\"]"));
+        assert!(mermaid.contains("i1 --> i2"));
+        assert!(mermaid.contains("i0 -.-> i1"));
+        assert!(mermaid.contains("class i0 completed"));
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph issues {"));
+        assert!(dot.contains("\"0002_lexer\" -> \"0003_parser\";"));
+        assert!(dot.contains("\"0001_setup\" -> \"0002_lexer\" [style=dashed];"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_estimate_weight() {
+        assert_eq!(estimate_weight(None), 1.0);
+        assert_eq!(estimate_weight(Some("3")), 3.0);
+        assert_eq!(estimate_weight(Some("2h")), 2.0);
+        assert_eq!(estimate_weight(Some("1.5d")), 12.0);
+        assert_eq!(estimate_weight(Some("large")), 1.0);
+    }
+}
@@ -0,0 +1,452 @@
+//! Append-only history of issue changes
+//!
+//! Every change to an issue is appended as one JSON line to
+//! `.history/<name>.jsonl` inside the issues directory. Entries record when the
+//! change happened, who made it and what changed: content edits with a unified
+//! diff and the resulting content, status transitions, the work branch and the
+//! merge commit. Entries are never rewritten, so reverting an issue appends a
+//! new revision holding the restored content.
+
+use crate::error::{Result, SwissArmyHammerError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+/// Directory holding history files, inside the issues directory
+pub const HISTORY_DIR: &str = ".history";
+
+/// Who made a change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IssueActor {
+    /// A person using the command line
+    Cli {
+        /// Login name of the user
+        user: String,
+    },
+    /// An MCP client, such as an AI assistant
+    Mcp {
+        /// Name the client reported when connecting
+        client: String,
+    },
+}
+
+impl IssueActor {
+    /// The user running this process
+    pub fn current_user() -> Self {
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string());
+        IssueActor::Cli { user }
+    }
+}
+
+impl fmt::Display for IssueActor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueActor::Cli { user } => write!(f, "{user} (cli)"),
+            IssueActor::Mcp { client } => write!(f, "{client} (mcp)"),
+        }
+    }
+}
+
+fn actor_slot() -> &'static RwLock<IssueActor> {
+    static ACTOR: OnceLock<RwLock<IssueActor>> = OnceLock::new();
+    ACTOR.get_or_init(|| RwLock::new(IssueActor::current_user()))
+}
+
+tokio::task_local! {
+    /// Actor of the request being handled on the current task
+    static REQUEST_ACTOR: IssueActor;
+}
+
+/// Set the actor recorded for changes made by this process
+///
+/// Changes are attributed to the user running the command line unless this
+/// is called, or the change is made inside [`with_actor`].
+pub fn set_current_actor(actor: IssueActor) {
+    *actor_slot().write().unwrap_or_else(|e| e.into_inner()) = actor;
+}
+
+/// Run `future`, attributing the changes it makes to `actor`
+///
+/// The MCP server handles each request this way, so clients connected to the
+/// same process are told apart.
+pub async fn with_actor<F: Future>(actor: IssueActor, future: F) -> F::Output {
+    REQUEST_ACTOR.scope(actor, future).await
+}
+
+/// The actor recorded for changes made on the current task
+pub fn current_actor() -> IssueActor {
+    REQUEST_ACTOR
+        .try_with(IssueActor::clone)
+        .unwrap_or_else(|_| {
+            actor_slot()
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone()
+        })
+}
+
+/// Where an issue lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueStatus {
+    /// In the issues directory
+    Active,
+    /// In the completed directory
+    Completed,
+    /// In the archived directory, out of the way of the workflow
+    Archived,
+}
+
+impl fmt::Display for IssueStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            IssueStatus::Active => "active",
+            IssueStatus::Completed => "completed",
+            IssueStatus::Archived => "archived",
+        })
+    }
+}
+
+impl std::str::FromStr for IssueStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "active" => Ok(IssueStatus::Active),
+            "completed" | "complete" => Ok(IssueStatus::Completed),
+            "archived" => Ok(IssueStatus::Archived),
+            other => Err(format!(
+                "Unknown issue status '{other}', expected one of active, completed, archived"
+            )),
+        }
+    }
+}
+
+/// What changed in a history entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum IssueChange {
+    /// The issue was created
+    Created {
+        /// Initial content
+        content: String,
+    },
+    /// The content was edited
+    Edited {
+        /// Unified diff from the previous content
+        diff: String,
+        /// Content after the edit
+        content: String,
+    },
+    /// The issue moved between states
+    StatusChanged {
+        /// Previous status
+        from: IssueStatus,
+        /// New status
+        to: IssueStatus,
+    },
+    /// A work branch was created or checked out for the issue
+    BranchLinked {
+        /// Branch name
+        branch: String,
+    },
+    /// The work branch was merged
+    Merged {
+        /// Merged branch
+        branch: String,
+        /// Branch merged into
+        target: String,
+        /// Hash of the commit on the target branch after the merge
+        commit: String,
+    },
+    /// The content was restored from an earlier revision
+    Reverted {
+        /// Revision whose content was restored
+        revision: u32,
+        /// Unified diff from the previous content
+        diff: String,
+        /// Restored content
+        content: String,
+    },
+    /// The issue was deleted
+    Deleted,
+}
+
+impl IssueChange {
+    /// An edit from `old` to `new`
+    pub fn edited(name: &str, old: &str, new: &str) -> Self {
+        IssueChange::Edited {
+            diff: unified_diff(name, old, new),
+            content: new.to_string(),
+        }
+    }
+
+    /// Content of the issue after this change, if the change sets it
+    pub fn content(&self) -> Option<&str> {
+        match self {
+            IssueChange::Created { content }
+            | IssueChange::Edited { content, .. }
+            | IssueChange::Reverted { content, .. } => Some(content),
+            _ => None,
+        }
+    }
+
+    /// One-line description of the change
+    pub fn summary(&self) -> String {
+        match self {
+            IssueChange::Created { .. } => "created".to_string(),
+            IssueChange::Edited { diff, .. } => {
+                let (added, removed) = diff_stats(diff);
+                format!("edited (+{added} -{removed})")
+            }
+            IssueChange::StatusChanged { from, to } => format!("{from} → {to}"),
+            IssueChange::BranchLinked { branch } => format!("branch {branch}"),
+            IssueChange::Merged {
+                branch,
+                target,
+                commit,
+            } => format!(
+                "merged {branch} into {target} ({})",
+                &commit[..commit.len().min(8)]
+            ),
+            IssueChange::Reverted { revision, .. } => format!("reverted to revision {revision}"),
+            IssueChange::Deleted => "deleted".to_string(),
+        }
+    }
+}
+
+/// One revision in an issue's history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueHistoryEntry {
+    /// Revision number, starting at 1
+    pub revision: u32,
+    /// When the change was made
+    pub timestamp: DateTime<Utc>,
+    /// Who made the change
+    pub actor: IssueActor,
+    /// What changed
+    #[serde(flatten)]
+    pub change: IssueChange,
+}
+
+/// Unified diff between two versions of an issue
+pub fn unified_diff(name: &str, old: &str, new: &str) -> String {
+    similar::TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{name}.md"), &format!("b/{name}.md"))
+        .to_string()
+}
+
+/// Number of added and removed lines in a unified diff
+fn diff_stats(diff: &str) -> (usize, usize) {
+    diff.lines()
+        .filter(|line| !line.starts_with("+++") && !line.starts_with("---"))
+        .fold((0, 0), |(added, removed), line| {
+            if line.starts_with('+') {
+                (added + 1, removed)
+            } else if line.starts_with('-') {
+                (added, removed + 1)
+            } else {
+                (added, removed)
+            }
+        })
+}
+
+/// History files of an issues directory
+#[derive(Debug, Clone)]
+pub struct IssueHistoryStore {
+    dir: PathBuf,
+}
+
+impl IssueHistoryStore {
+    /// History store for the issues directory `issues_dir`
+    pub fn new(issues_dir: &Path) -> Self {
+        Self {
+            dir: issues_dir.join(HISTORY_DIR),
+        }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.jsonl"))
+    }
+
+    /// Append a change made by the current actor, returning the new entry
+    pub fn append(&self, name: &str, change: IssueChange) -> Result<IssueHistoryEntry> {
+        let revision = self.entries(name)?.len() as u32 + 1;
+        let entry = IssueHistoryEntry {
+            revision,
+            timestamp: Utc::now(),
+            actor: current_actor(),
+            change,
+        };
+
+        fs::create_dir_all(&self.dir)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(name))?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        Ok(entry)
+    }
+
+    /// Every entry of an issue, oldest first
+    ///
+    /// Issues without a history file have an empty history.
+    pub fn entries(&self, name: &str) -> Result<Vec<IssueHistoryEntry>> {
+        let path = self.path(name);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        fs::read_to_string(&path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line).map_err(|e| {
+                    SwissArmyHammerError::Storage(format!(
+                        "Invalid history entry in {}: {e}",
+                        path.display()
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Content of an issue as of `revision`
+    pub fn content_at(&self, name: &str, revision: u32) -> Result<String> {
+        let entries = self.entries(name)?;
+        if entries.is_empty() {
+            return Err(SwissArmyHammerError::IssueNotFound(name.to_string()));
+        }
+        if revision == 0 || revision as usize > entries.len() {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Issue '{name}' has no revision {revision}, expected 1 to {}",
+                entries.len()
+            )));
+        }
+
+        entries[..revision as usize]
+            .iter()
+            .rev()
+            .find_map(|entry| entry.change.content())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                SwissArmyHammerError::Other(format!(
+                    "History of issue '{name}' has no content at revision {revision}"
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_and_read_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = IssueHistoryStore::new(temp_dir.path());
+
+        store
+            .append(
+                "parser",
+                IssueChange::Created {
+                    content: "# Parser\n".to_string(),
+                },
+            )
+            .unwrap();
+        store
+            .append(
+                "parser",
+                IssueChange::edited("parser", "# Parser\n", "# Parser\n\nDone\n"),
+            )
+            .unwrap();
+        store
+            .append(
+                "parser",
+                IssueChange::StatusChanged {
+                    from: IssueStatus::Active,
+                    to: IssueStatus::Completed,
+                },
+            )
+            .unwrap();
+
+        let entries = store.entries("parser").unwrap();
+        assert_eq!(
+            entries.iter().map(|e| e.revision).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(entries[1].change.summary(), "edited (+2 -0)");
+        assert_eq!(entries[2].change.summary(), "active → completed");
+        assert!(store.entries("missing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_content_at_revision() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = IssueHistoryStore::new(temp_dir.path());
+        store
+            .append(
+                "a",
+                IssueChange::Created {
+                    content: "one".to_string(),
+                },
+            )
+            .unwrap();
+        store
+            .append("a", IssueChange::edited("a", "one", "two"))
+            .unwrap();
+        store
+            .append(
+                "a",
+                IssueChange::BranchLinked {
+                    branch: "issue/a".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(store.content_at("a", 1).unwrap(), "one");
+        assert_eq!(store.content_at("a", 3).unwrap(), "two");
+        assert!(store.content_at("a", 4).is_err());
+        assert!(matches!(
+            store.content_at("b", 1),
+            Err(SwissArmyHammerError::IssueNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_actor_serialization() {
+        let actor = IssueActor::Mcp {
+            client: "claude-code".to_string(),
+        };
+        let json = serde_json::to_value(&actor).unwrap();
+        assert_eq!(json["kind"], "mcp");
+        assert_eq!(json["client"], "claude-code");
+        assert_eq!(actor.to_string(), "claude-code (mcp)");
+    }
+
+    #[tokio::test]
+    async fn test_with_actor_scopes_the_actor_to_the_request() {
+        let client = |name: &str| IssueActor::Mcp {
+            client: name.to_string(),
+        };
+
+        let (first, second) = tokio::join!(
+            with_actor(client("first"), async { current_actor() }),
+            with_actor(client("second"), async { current_actor() }),
+        );
+        assert_eq!(first, client("first"));
+        assert_eq!(second, client("second"));
+        assert!(!matches!(current_actor(), IssueActor::Mcp { .. }));
+    }
+}
@@ -0,0 +1,450 @@
+//! Issue front matter
+//!
+//! Issues may start with a YAML front matter block describing how they are
+//! scheduled:
+//!
+//! ```markdown
+//! ---
+//! priority: high
+//! labels: [bug, auth]
+//! estimate: 2h
+//! depends_on: [setup_database]
+//! blocked_by: [api_review]
+//! ---
+//! # Fix login
+//! ```
+//!
+//! Every field is optional. Issues without front matter have default metadata
+//! and are scheduled by name, as before.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::frontmatter::parse_frontmatter;
+use crate::issues::filesystem::Issue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Priority of an issue, from most to least urgent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssuePriority {
+    /// Must be worked on before anything else
+    Critical,
+    /// Should be worked on soon
+    High,
+    /// Normal priority, also used for issues without a priority
+    Medium,
+    /// Can wait until other work is done
+    Low,
+}
+
+impl IssuePriority {
+    /// All priorities, most urgent first
+    pub const ALL: [IssuePriority; 4] = [
+        IssuePriority::Critical,
+        IssuePriority::High,
+        IssuePriority::Medium,
+        IssuePriority::Low,
+    ];
+
+    /// Lowercase name used in front matter
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssuePriority::Critical => "critical",
+            IssuePriority::High => "high",
+            IssuePriority::Medium => "medium",
+            IssuePriority::Low => "low",
+        }
+    }
+}
+
+impl fmt::Display for IssuePriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IssuePriority {
+    type Err = String;
+
+    /// Parse a priority name, or `p0` to `p3`
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "critical" | "p0" => Ok(IssuePriority::Critical),
+            "high" | "p1" => Ok(IssuePriority::High),
+            "medium" | "normal" | "p2" => Ok(IssuePriority::Medium),
+            "low" | "p3" => Ok(IssuePriority::Low),
+            other => Err(format!(
+                "Unknown priority '{other}', expected one of critical, high, medium, low"
+            )),
+        }
+    }
+}
+
+/// Scheduling metadata read from an issue's front matter
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueMetadata {
+    /// How urgent the issue is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<IssuePriority>,
+    /// Free-form labels
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Free-form size estimate, such as `2h` or `3`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<String>,
+    /// Issues that must be completed before this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Issues currently blocking this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_by: Vec<String>,
+}
+
+impl IssueMetadata {
+    /// Read the metadata from issue content
+    ///
+    /// Content without front matter, or whose front matter is not a mapping,
+    /// yields default metadata. Fields of the wrong type are an error.
+    pub fn from_content(content: &str) -> Result<Self> {
+        let frontmatter = parse_frontmatter(content)?;
+        let Some(serde_json::Value::Object(fields)) = frontmatter.metadata else {
+            return Ok(Self::default());
+        };
+
+        let priority = match fields.get("priority") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(serde_json::Value::String(s)) => {
+                Some(s.parse().map_err(Self::invalid_front_matter)?)
+            }
+            Some(serde_json::Value::Number(n)) => {
+                let index = n.as_u64().unwrap_or(u64::MAX) as usize;
+                Some(*IssuePriority::ALL.get(index).ok_or_else(|| {
+                    Self::invalid_front_matter(format!(
+                        "Priority {n} is out of range, expected 0 to 3"
+                    ))
+                })?)
+            }
+            Some(other) => {
+                return Err(Self::invalid_front_matter(format!(
+                    "priority must be a string, found {other}"
+                )))
+            }
+        };
+
+        let estimate = match fields.get("estimate") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(serde_json::Value::String(s)) => Some(s.clone()),
+            Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+            Some(other) => {
+                return Err(Self::invalid_front_matter(format!(
+                    "estimate must be a string or number, found {other}"
+                )))
+            }
+        };
+
+        Ok(Self {
+            priority,
+            labels: Self::string_list(&fields, "labels")?,
+            estimate,
+            depends_on: Self::string_list(&fields, "depends_on")?,
+            blocked_by: Self::string_list(&fields, "blocked_by")?,
+        })
+    }
+
+    /// Read a field holding a list of strings, or a single string
+    fn string_list(
+        fields: &serde_json::Map<String, serde_json::Value>,
+        key: &str,
+    ) -> Result<Vec<String>> {
+        let values = match fields.get(key) {
+            None | Some(serde_json::Value::Null) => return Ok(Vec::new()),
+            Some(serde_json::Value::String(s)) => vec![s.clone()],
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .map(|item| match item {
+                    serde_json::Value::String(s) => Ok(s.clone()),
+                    serde_json::Value::Number(n) => Ok(n.to_string()),
+                    other => Err(Self::invalid_front_matter(format!(
+                        "{key} must contain strings, found {other}"
+                    ))),
+                })
+                .collect::<Result<_>>()?,
+            Some(other) => {
+                return Err(Self::invalid_front_matter(format!(
+                    "{key} must be a list of strings, found {other}"
+                )))
+            }
+        };
+
+        Ok(values
+            .into_iter()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .collect())
+    }
+
+    fn invalid_front_matter(message: String) -> SwissArmyHammerError {
+        SwissArmyHammerError::Other(format!("Invalid issue front matter: {message}"))
+    }
+
+    /// Whether no field is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Priority used for scheduling, treating a missing priority as medium
+    pub fn effective_priority(&self) -> IssuePriority {
+        self.priority.unwrap_or(IssuePriority::Medium)
+    }
+
+    /// Names of every issue that must be completed first
+    pub fn dependencies(&self) -> impl Iterator<Item = &str> {
+        self.depends_on
+            .iter()
+            .chain(self.blocked_by.iter())
+            .map(String::as_str)
+    }
+
+    /// Whether the issue has `label`, ignoring case
+    pub fn has_label(&self, label: &str) -> bool {
+        self.labels
+            .iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(label))
+    }
+
+    /// Whether every dependency is in `completed`
+    pub fn is_ready(&self, completed: &HashSet<String>) -> bool {
+        self.dependencies().all(|name| completed.contains(name))
+    }
+}
+
+impl Issue {
+    /// Scheduling metadata from the issue's front matter
+    ///
+    /// Malformed front matter is logged and treated as absent so that a single
+    /// bad file cannot stop issues from being listed or scheduled.
+    pub fn metadata(&self) -> IssueMetadata {
+        IssueMetadata::from_content(&self.content).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring front matter of issue '{}': {}", self.name, e);
+            IssueMetadata::default()
+        })
+    }
+}
+
+/// Replace the labels in an issue's front matter
+///
+/// Other front matter fields and the body are kept. A front matter block is
+/// added when the content has none, and dropped when removing the last label
+/// leaves it empty.
+pub fn set_labels(content: &str, labels: &[String]) -> Result<String> {
+    let frontmatter = parse_frontmatter(content)?;
+    let mut fields = match frontmatter.metadata {
+        Some(serde_json::Value::Object(fields)) => fields,
+        None | Some(serde_json::Value::Null) => serde_json::Map::new(),
+        Some(other) => {
+            return Err(IssueMetadata::invalid_front_matter(format!(
+                "expected a mapping, found {other}"
+            )))
+        }
+    };
+
+    if labels.is_empty() {
+        fields.remove("labels");
+    } else {
+        fields.insert("labels".to_string(), serde_json::json!(labels));
+    }
+
+    if fields.is_empty() {
+        return Ok(frontmatter.content);
+    }
+    let yaml = serde_yaml::to_string(&fields)
+        .map_err(|e| SwissArmyHammerError::Other(format!("Failed to write front matter: {e}")))?;
+    Ok(format!("---\n{yaml}---\n{}", frontmatter.content))
+}
+
+/// Pick the issue to work on next
+///
+/// Only issues whose dependencies are all in `completed` are considered. Among
+/// those, the most urgent wins and ties go to the first name alphabetically.
+/// Returns `Ok(None)` when nothing is pending, and an
+/// [`SwissArmyHammerError::IssuesBlocked`] error naming every issue and its
+/// unmet dependencies when issues are pending but none of them can start.
+pub fn select_next_issue(
+    pending: impl IntoIterator<Item = Issue>,
+    completed: &HashSet<String>,
+) -> Result<Option<Issue>> {
+    let pending: Vec<(IssueMetadata, Issue)> = pending
+        .into_iter()
+        .map(|issue| (issue.metadata(), issue))
+        .collect();
+    if pending.is_empty() {
+        return Ok(None);
+    }
+
+    let pending_names: HashSet<&str> = pending
+        .iter()
+        .map(|(_, issue)| issue.name.as_str())
+        .collect();
+    let next = pending
+        .iter()
+        .filter(|(metadata, _)| metadata.is_ready(completed))
+        .min_by(|(a_meta, a), (b_meta, b)| {
+            a_meta
+                .effective_priority()
+                .cmp(&b_meta.effective_priority())
+                .then_with(|| a.name.cmp(&b.name))
+        })
+        .map(|(_, issue)| issue.clone());
+    if next.is_some() {
+        return Ok(next);
+    }
+
+    let mut blocked: Vec<String> = pending
+        .iter()
+        .map(|(metadata, issue)| {
+            let waiting_on: Vec<String> = metadata
+                .dependencies()
+                .filter(|dep| !completed.contains(*dep))
+                .map(|dep| {
+                    if pending_names.contains(dep) {
+                        format!("{dep} (pending)")
+                    } else {
+                        format!("{dep} (not found)")
+                    }
+                })
+                .collect();
+            format!("{} waits on {}", issue.name, waiting_on.join(", "))
+        })
+        .collect();
+    blocked.sort();
+    Err(SwissArmyHammerError::IssuesBlocked(blocked.join("; ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(name: &str, content: &str) -> Issue {
+        Issue {
+            name: name.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_plain_markdown_has_default_metadata() {
+        let metadata = IssueMetadata::from_content("# Title\n\nBody").unwrap();
+        assert!(metadata.is_empty());
+        assert_eq!(metadata.effective_priority(), IssuePriority::Medium);
+    }
+
+    #[test]
+    fn test_parse_all_fields() {
+        let content = r#"---
+priority: High
+labels: [bug, auth]
+estimate: 3
+depends_on: [setup_database]
+blocked_by: api_review
+---
+# Fix login
+"#;
+        let metadata = IssueMetadata::from_content(content).unwrap();
+        assert_eq!(metadata.priority, Some(IssuePriority::High));
+        assert_eq!(metadata.labels, vec!["bug", "auth"]);
+        assert_eq!(metadata.estimate.as_deref(), Some("3"));
+        assert_eq!(metadata.depends_on, vec!["setup_database"]);
+        assert_eq!(metadata.blocked_by, vec!["api_review"]);
+        assert!(metadata.has_label("BUG"));
+        assert_eq!(
+            metadata.dependencies().collect::<Vec<_>>(),
+            vec!["setup_database", "api_review"]
+        );
+    }
+
+    #[test]
+    fn test_numeric_priority() {
+        let metadata = IssueMetadata::from_content("---\npriority: 0\n---\nBody").unwrap();
+        assert_eq!(metadata.priority, Some(IssuePriority::Critical));
+        assert!(IssueMetadata::from_content("---\npriority: 9\n---\nBody").is_err());
+    }
+
+    #[test]
+    fn test_invalid_fields_are_errors() {
+        assert!(IssueMetadata::from_content("---\npriority: urgent\n---\nBody").is_err());
+        assert!(IssueMetadata::from_content("---\nlabels: {a: b}\n---\nBody").is_err());
+
+        // Issue::metadata tolerates them
+        let bad = issue("bad", "---\npriority: urgent\n---\nBody");
+        assert!(bad.metadata().is_empty());
+    }
+
+    #[test]
+    fn test_set_labels_keeps_other_fields() {
+        let content = "---\npriority: high\nlabels: [bug]\n---\n# Fix login\n";
+        let relabeled = set_labels(content, &["bug".to_string(), "auth".to_string()]).unwrap();
+        let metadata = IssueMetadata::from_content(&relabeled).unwrap();
+        assert_eq!(metadata.priority, Some(IssuePriority::High));
+        assert_eq!(metadata.labels, vec!["bug", "auth"]);
+        assert!(relabeled.ends_with("---\n# Fix login\n"));
+
+        let added = set_labels("# Plain\n", &["docs".to_string()]).unwrap();
+        assert_eq!(added, "---\nlabels:\n- docs\n---\n# Plain\n");
+        assert_eq!(set_labels(&added, &[]).unwrap(), "# Plain\n");
+    }
+
+    #[test]
+    fn test_select_next_issue_by_priority_then_name() {
+        let pending = vec![
+            issue("a_low", "---\npriority: low\n---\n"),
+            issue("b_plain", "# Plain"),
+            issue("c_high", "---\npriority: high\n---\n"),
+            issue("d_high", "---\npriority: high\n---\n"),
+        ];
+        let next = select_next_issue(pending, &HashSet::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.name, "c_high");
+    }
+
+    #[test]
+    fn test_select_next_issue_waits_for_dependencies() {
+        let pending = vec![
+            issue(
+                "a_blocked",
+                "---\npriority: critical\ndepends_on: [setup]\n---\n",
+            ),
+            issue("b_ready", "---\npriority: low\n---\n"),
+        ];
+        let next = select_next_issue(pending.clone(), &HashSet::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.name, "b_ready");
+
+        let completed = HashSet::from(["setup".to_string()]);
+        let next = select_next_issue(pending, &completed).unwrap().unwrap();
+        assert_eq!(next.name, "a_blocked");
+    }
+
+    #[test]
+    fn test_select_next_issue_with_only_blocked_issues() {
+        assert!(select_next_issue(Vec::new(), &HashSet::new())
+            .unwrap()
+            .is_none());
+
+        let pending = vec![
+            issue("a", "---\ndepends_on: b\n---\n"),
+            issue("b", "---\ndepends_on: a\n---\n"),
+            issue("c", "---\nblocked_by: missing\n---\n"),
+        ];
+        let error = select_next_issue(pending, &HashSet::new()).unwrap_err();
+        assert!(matches!(error, SwissArmyHammerError::IssuesBlocked(_)));
+        assert_eq!(
+            error.to_string(),
+            "All pending issues are blocked: a waits on b (pending); \
+             b waits on a (pending); c waits on missing (not found)"
+        );
+    }
+}
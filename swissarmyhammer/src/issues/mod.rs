@@ -10,6 +10,10 @@
 //! - **Git Integration**: Automatic branch creation and management for issue workflows
 //! - **Performance Monitoring**: Built-in metrics collection for performance analysis
 //! - **Batch Operations**: Efficient batch creation, retrieval, and updates for large projects
+//! - **Scheduling Metadata**: Optional front matter with priority, labels and dependencies
+//! - **History**: Append-only record of every change with its author, and reverting to a revision
+//! - **Remote Trackers**: Optional two-way sync with GitHub, Gitea, Jira or other REST trackers
+//! - **Lifecycle**: Reopening completed issues, archiving stale ones, and bulk operations by name or label
 //!
 //! ## Basic Usage
 //!
@@ -218,16 +222,49 @@ impl AsRef<str> for IssueName {
     }
 }
 
+/// Selecting many issues at once for bulk operations
+pub mod bulk;
 /// Filesystem-based issue storage implementation
 pub mod filesystem;
+/// Dependency graph between issues
+pub mod graph;
+/// Append-only history of issue changes
+pub mod history;
+/// Issue front matter: priority, labels, estimates and dependencies
+pub mod metadata;
 /// Performance metrics collection and analysis
 pub mod metrics;
+/// Issue storage synchronized with a remote issue tracker
+pub mod remote;
 /// Shared utilities for issue management
 pub mod utils;
 
 // Re-export main types from the filesystem module
 pub use filesystem::{FileSystemIssueStorage, Issue, IssueInfo, IssueState, IssueStorage};
 
+// Export bulk operation types
+pub use bulk::{BulkFailure, BulkResult, IssueFilter};
+
+// Export dependency graph types
+pub use graph::{
+    CriticalPath, IssueDependency, IssueGraph, IssueGraphAnalysis, IssueNode, IssueReferenceKind,
+};
+
+// Export history types
+pub use history::{
+    current_actor, set_current_actor, with_actor, IssueActor, IssueChange, IssueHistoryEntry,
+    IssueStatus,
+};
+
+// Export front matter types
+pub use metadata::{select_next_issue, set_labels, IssueMetadata, IssuePriority};
+
+// Export remote tracker types
+pub use remote::{
+    create_issue_storage_in, ConflictStrategy, HttpIssueStorage, RemoteIssueConfig, RestMapping,
+    SyncReport,
+};
+
 // Export metrics types
 pub use metrics::{MetricsSnapshot, Operation, PerformanceMetrics};
 
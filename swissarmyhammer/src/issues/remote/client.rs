@@ -0,0 +1,367 @@
+//! HTTP client for a REST issue tracker
+
+use super::mapping::{set_pointer, OffsetPaging, RemoteIssue, RequestTemplate, RestMapping};
+use reqwest::header::{HeaderMap, LINK};
+use serde_json::Value;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Maximum number of list pages followed before giving up
+const MAX_LIST_PAGES: usize = 100;
+
+/// Errors talking to the tracker
+#[derive(Debug, Error)]
+pub enum RemoteError {
+    /// The tracker could not be reached or is temporarily unavailable
+    ///
+    /// Operations failing this way are queued and retried on the next sync.
+    #[error("Issue tracker unavailable: {0}")]
+    Offline(String),
+
+    /// The tracker rejected the request
+    #[error("Issue tracker returned {status}: {message}")]
+    Http {
+        /// HTTP status code
+        status: u16,
+        /// Response body
+        message: String,
+    },
+
+    /// The tracker's response could not be understood
+    #[error("Invalid issue tracker response: {0}")]
+    Invalid(String),
+}
+
+impl RemoteError {
+    /// Whether the operation may succeed when retried later
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RemoteError::Offline(_) => true,
+            RemoteError::Http { status, .. } => *status == 429 || *status >= 500,
+            RemoteError::Invalid(_) => false,
+        }
+    }
+
+    /// Whether the tracker reported that the issue does not exist
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, RemoteError::Http { status, .. } if *status == 404 || *status == 410)
+    }
+}
+
+impl From<RemoteError> for crate::error::SwissArmyHammerError {
+    fn from(error: RemoteError) -> Self {
+        crate::error::SwissArmyHammerError::Storage(error.to_string())
+    }
+}
+
+/// Result of a tracker call
+pub type RemoteResult<T> = std::result::Result<T, RemoteError>;
+
+/// Client performing the requests described by a [`RestMapping`]
+#[derive(Debug, Clone)]
+pub struct RemoteIssueClient {
+    client: reqwest::Client,
+    base_url: String,
+    authorization: Option<String>,
+    mapping: RestMapping,
+}
+
+impl RemoteIssueClient {
+    /// Create a client for the tracker at `base_url`
+    ///
+    /// `authorization` is sent verbatim as the `Authorization` header, for
+    /// example `Bearer <token>`.
+    pub fn new(
+        base_url: impl Into<String>,
+        mapping: RestMapping,
+        authorization: Option<String>,
+        timeout: Duration,
+    ) -> RemoteResult<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .user_agent(concat!("swissarmyhammer/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| RemoteError::Invalid(format!("Failed to build HTTP client: {e}")))?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            authorization,
+            mapping,
+        })
+    }
+
+    /// The mapping used by this client
+    pub fn mapping(&self) -> &RestMapping {
+        &self.mapping
+    }
+
+    /// Fetch every remote issue, following pagination links or offsets
+    pub async fn list(&self) -> RemoteResult<Vec<RemoteIssue>> {
+        if let Some(paging) = &self.mapping.list_paging {
+            return self.list_by_offset(paging).await;
+        }
+
+        let mut url = self.url(&self.mapping.list.path);
+        let mut issues = Vec::new();
+
+        for _ in 0..MAX_LIST_PAGES {
+            let (value, headers) = self.send_url(&self.mapping.list, url.clone(), None).await?;
+            issues.extend(
+                self.mapping
+                    .parse_list(&value)
+                    .map_err(|e| RemoteError::Invalid(e.to_string()))?,
+            );
+
+            match next_page(&headers) {
+                Some(next) => url = next,
+                None => return Ok(issues),
+            }
+        }
+
+        Err(RemoteError::Invalid(format!(
+            "Issue list has more than {MAX_LIST_PAGES} pages"
+        )))
+    }
+
+    /// Fetch every remote issue page by page until the reported total is reached
+    ///
+    /// Trackers may return fewer items than asked for, so the offset advances
+    /// by the number of items received.
+    async fn list_by_offset(&self, paging: &OffsetPaging) -> RemoteResult<Vec<RemoteIssue>> {
+        let mut issues = Vec::new();
+        let mut offset = 0;
+
+        for _ in 0..MAX_LIST_PAGES {
+            let url = self.url(&paging.page_path(&self.mapping.list.path, offset));
+            let (value, _) = self.send_url(&self.mapping.list, url, None).await?;
+            let received = self
+                .mapping
+                .items_pointer
+                .as_deref()
+                .map_or(Some(&value), |pointer| value.pointer(pointer))
+                .and_then(Value::as_array)
+                .map_or(0, Vec::len);
+            issues.extend(
+                self.mapping
+                    .parse_list(&value)
+                    .map_err(|e| RemoteError::Invalid(e.to_string()))?,
+            );
+
+            offset += received;
+            let done = match paging.total(&value) {
+                Some(total) => offset >= total,
+                None => received < paging.page_size,
+            };
+            if received == 0 || done {
+                return Ok(issues);
+            }
+        }
+
+        Err(RemoteError::Invalid(format!(
+            "Issue list has more than {MAX_LIST_PAGES} pages"
+        )))
+    }
+
+    /// Fetch one remote issue
+    pub async fn get(&self, id: &str) -> RemoteResult<RemoteIssue> {
+        let value = self.send(&self.mapping.get, id, None).await?;
+        self.parse(&value)
+    }
+
+    /// Create a remote issue
+    ///
+    /// Returns the issue as stored by the tracker. Trackers answering with only
+    /// the new issue's id, as Jira does, are asked for the issue again.
+    pub async fn create(&self, title: &str, body: &str) -> RemoteResult<RemoteIssue> {
+        let request = self
+            .mapping
+            .issue_body(&self.mapping.create, title, body, None);
+        let value = self.send(&self.mapping.create, "", Some(request)).await?;
+        let created = self.parse(&value)?;
+        if value.pointer(&self.mapping.fields.title).is_some() {
+            Ok(created)
+        } else {
+            self.get(&created.id).await
+        }
+    }
+
+    /// Update a remote issue's title and body
+    ///
+    /// Returns the issue as stored by the tracker. Trackers answering with an
+    /// empty body are asked for the issue again.
+    pub async fn update(&self, id: &str, title: &str, body: &str) -> RemoteResult<RemoteIssue> {
+        let request = self
+            .mapping
+            .issue_body(&self.mapping.update, title, body, None);
+        let value = self.send(&self.mapping.update, id, Some(request)).await?;
+        match value {
+            Value::Null => self.get(id).await,
+            value => self.parse(&value),
+        }
+    }
+
+    /// Close or reopen a remote issue
+    pub async fn set_closed(&self, id: &str, closed: bool) -> RemoteResult<RemoteIssue> {
+        let dedicated = if closed {
+            self.mapping.close.as_ref()
+        } else {
+            self.mapping.reopen.as_ref()
+        };
+
+        match dedicated {
+            Some(template) => {
+                self.send(template, id, template.body.clone()).await?;
+            }
+            None => {
+                let state = if closed {
+                    &self.mapping.closed_state
+                } else {
+                    &self.mapping.open_state
+                };
+                let mut request = Value::Object(serde_json::Map::new());
+                set_pointer(
+                    &mut request,
+                    &self.mapping.fields.state,
+                    Value::String(state.clone()),
+                );
+                self.send(&self.mapping.update, id, Some(request)).await?;
+            }
+        }
+
+        self.get(id).await
+    }
+
+    fn parse(&self, value: &Value) -> RemoteResult<RemoteIssue> {
+        self.mapping
+            .parse_issue(value)
+            .map_err(|e| RemoteError::Invalid(e.to_string()))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    async fn send(
+        &self,
+        template: &RequestTemplate,
+        id: &str,
+        body: Option<Value>,
+    ) -> RemoteResult<Value> {
+        let url = self.url(&template.path_for(id));
+        self.send_url(template, url, body)
+            .await
+            .map(|(value, _)| value)
+    }
+
+    async fn send_url(
+        &self,
+        template: &RequestTemplate,
+        url: String,
+        body: Option<Value>,
+    ) -> RemoteResult<(Value, HeaderMap)> {
+        let mut request = self
+            .client
+            .request(template.method.to_reqwest(), &url)
+            .header(reqwest::header::ACCEPT, "application/json");
+        if let Some(authorization) = &self.authorization {
+            request = request.header(reqwest::header::AUTHORIZATION, authorization);
+        }
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            if e.is_connect() || e.is_timeout() {
+                RemoteError::Offline(format!("{url}: {e}"))
+            } else {
+                RemoteError::Invalid(format!("{url}: {e}"))
+            }
+        })?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| RemoteError::Offline(format!("Failed to read response: {e}")))?;
+
+        if !status.is_success() {
+            let error = RemoteError::Http {
+                status: status.as_u16(),
+                message: text,
+            };
+            return Err(if error.is_retryable() {
+                RemoteError::Offline(error.to_string())
+            } else {
+                error
+            });
+        }
+
+        if text.trim().is_empty() {
+            return Ok((Value::Null, headers));
+        }
+        let value = serde_json::from_str(&text)
+            .map_err(|e| RemoteError::Invalid(format!("{url} did not return JSON: {e}")))?;
+        Ok((value, headers))
+    }
+}
+
+/// URL of the next page from an RFC 8288 `Link` header
+fn next_page(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let mut parts = link.split(';');
+            let target = parts.next()?.trim();
+            let is_next = parts.any(|param| {
+                let param = param.trim().replace(' ', "");
+                param == "rel=\"next\"" || param == "rel=next"
+            });
+            is_next.then(|| {
+                target
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_next_page_from_link_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            LINK,
+            HeaderValue::from_static(
+                "<https://api.example.com/issues?page=1>; rel=\"prev\", <https://api.example.com/issues?page=3>; rel=\"next\"",
+            ),
+        );
+        assert_eq!(
+            next_page(&headers).as_deref(),
+            Some("https://api.example.com/issues?page=3")
+        );
+        assert_eq!(next_page(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_server_errors_are_retryable() {
+        let server_error = RemoteError::Http {
+            status: 503,
+            message: String::new(),
+        };
+        let not_found = RemoteError::Http {
+            status: 404,
+            message: String::new(),
+        };
+        assert!(server_error.is_retryable());
+        assert!(!not_found.is_retryable());
+    }
+}
@@ -0,0 +1,489 @@
+//! Mapping between issues and the JSON of a REST issue tracker
+//!
+//! Trackers differ in URL layout and field names but agree on the shape of the
+//! operations: list, fetch, create, update and close. A [`RestMapping`] names the
+//! request used for each operation and locates each issue field in the JSON with
+//! a JSON pointer, so a new tracker only needs configuration.
+
+use crate::error::{Result, SwissArmyHammerError};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// HTTP method of a mapped request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    /// GET
+    Get,
+    /// POST
+    Post,
+    /// PUT
+    Put,
+    /// PATCH
+    Patch,
+    /// DELETE
+    Delete,
+}
+
+impl HttpMethod {
+    pub(crate) fn to_reqwest(self) -> reqwest::Method {
+        match self {
+            HttpMethod::Get => reqwest::Method::GET,
+            HttpMethod::Post => reqwest::Method::POST,
+            HttpMethod::Put => reqwest::Method::PUT,
+            HttpMethod::Patch => reqwest::Method::PATCH,
+            HttpMethod::Delete => reqwest::Method::DELETE,
+        }
+    }
+}
+
+/// A request to the tracker
+///
+/// `path` is relative to the tracker's base URL and may contain an `{id}`
+/// placeholder for the remote issue id. `body`, when set, is the starting point
+/// of the request body; mapped fields are written into it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestTemplate {
+    /// HTTP method
+    pub method: HttpMethod,
+    /// Path relative to the base URL
+    pub path: String,
+    /// Fixed fields of the request body
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+impl RequestTemplate {
+    /// Create a template without a fixed body
+    pub fn new(method: HttpMethod, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            body: None,
+        }
+    }
+
+    /// Path with `{id}` replaced by the url-encoded remote id
+    pub fn path_for(&self, id: &str) -> String {
+        self.path.replace("{id}", &urlencoding::encode(id))
+    }
+}
+
+/// JSON pointers locating issue fields in the tracker's JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldMapping {
+    /// Remote id used in `{id}` placeholders
+    pub id: String,
+    /// Title, which holds the issue name
+    pub title: String,
+    /// Body, which holds the issue content
+    pub body: String,
+    /// Open or closed state
+    pub state: String,
+    /// Last modification time, used to detect remote changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+impl Default for FieldMapping {
+    fn default() -> Self {
+        Self {
+            id: "/number".to_string(),
+            title: "/title".to_string(),
+            body: "/body".to_string(),
+            state: "/state".to_string(),
+            updated_at: Some("/updated_at".to_string()),
+        }
+    }
+}
+
+/// An issue as stored by the tracker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteIssue {
+    /// Remote id
+    pub id: String,
+    /// Issue name
+    pub title: String,
+    /// Issue content
+    pub body: String,
+    /// Whether the remote issue is closed
+    pub closed: bool,
+    /// Last modification time reported by the tracker
+    pub updated_at: Option<String>,
+}
+
+impl RemoteIssue {
+    /// Value that changes whenever the remote issue changes
+    ///
+    /// Uses the modification time when the tracker reports one and a hash of
+    /// the issue otherwise.
+    pub fn fingerprint(&self) -> String {
+        match &self.updated_at {
+            Some(updated_at) => updated_at.clone(),
+            None => format!(
+                "{:x}",
+                md5::compute(format!("{}\0{}\0{}", self.title, self.body, self.closed))
+            ),
+        }
+    }
+}
+
+/// Offset paging of a list request
+///
+/// Used by trackers that page with query parameters instead of `Link` headers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OffsetPaging {
+    /// Query parameter holding the index of the first item
+    pub offset_param: String,
+    /// Query parameter holding the page size
+    pub limit_param: String,
+    /// Number of items asked for per page
+    pub page_size: usize,
+    /// Pointer to the total number of items in the list response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_pointer: Option<String>,
+}
+
+impl OffsetPaging {
+    /// Path of the page starting at `offset`
+    pub fn page_path(&self, path: &str, offset: usize) -> String {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        format!(
+            "{path}{separator}{}={offset}&{}={}",
+            self.offset_param, self.limit_param, self.page_size
+        )
+    }
+
+    /// Total number of items reported by a list response
+    pub fn total(&self, value: &Value) -> Option<usize> {
+        self.total_pointer
+            .as_deref()
+            .and_then(|pointer| value.pointer(pointer))
+            .and_then(Value::as_u64)
+            .map(|total| total as usize)
+    }
+}
+
+/// How issues map onto a REST issue tracker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestMapping {
+    /// Request listing every issue, followed through `Link: rel="next"` headers
+    pub list: RequestTemplate,
+    /// Pointer to the array of issues in the list response, the root if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items_pointer: Option<String>,
+    /// Offset paging of the list request, for trackers without `Link` headers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_paging: Option<OffsetPaging>,
+    /// Request fetching a single issue
+    pub get: RequestTemplate,
+    /// Request creating an issue
+    pub create: RequestTemplate,
+    /// Request updating an issue's title and body
+    pub update: RequestTemplate,
+    /// Request closing an issue; when unset, `update` sets the state field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close: Option<RequestTemplate>,
+    /// Request reopening an issue; when unset, `update` sets the state field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reopen: Option<RequestTemplate>,
+    /// Where issue fields live in the JSON
+    #[serde(default)]
+    pub fields: FieldMapping,
+    /// State value of open issues
+    pub open_state: String,
+    /// State value of closed issues
+    pub closed_state: String,
+}
+
+impl RestMapping {
+    /// Mapping for the GitHub REST API, with `https://api.github.com` as base URL
+    pub fn github(owner: &str, repo: &str) -> Self {
+        let issues = format!("/repos/{owner}/{repo}/issues");
+        Self {
+            list: RequestTemplate::new(HttpMethod::Get, format!("{issues}?state=all&per_page=100")),
+            items_pointer: None,
+            list_paging: None,
+            get: RequestTemplate::new(HttpMethod::Get, format!("{issues}/{{id}}")),
+            create: RequestTemplate::new(HttpMethod::Post, issues.clone()),
+            update: RequestTemplate::new(HttpMethod::Patch, format!("{issues}/{{id}}")),
+            close: None,
+            reopen: None,
+            fields: FieldMapping::default(),
+            open_state: "open".to_string(),
+            closed_state: "closed".to_string(),
+        }
+    }
+
+    /// Mapping for the Gitea API, with `https://<host>/api/v1` as base URL
+    pub fn gitea(owner: &str, repo: &str) -> Self {
+        let mut mapping = Self::github(owner, repo);
+        mapping.list.path = format!("/repos/{owner}/{repo}/issues?state=all&type=issues&limit=50");
+        mapping
+    }
+
+    /// Mapping for the Jira REST API v2
+    ///
+    /// Jira changes status through workflow transitions, so closing and
+    /// reopening use the given transition ids.
+    pub fn jira(project: &str, close_transition: &str, reopen_transition: &str) -> Self {
+        let transition = |id: &str| RequestTemplate {
+            method: HttpMethod::Post,
+            path: "/rest/api/2/issue/{id}/transitions".to_string(),
+            body: Some(serde_json::json!({ "transition": { "id": id } })),
+        };
+        Self {
+            list: RequestTemplate::new(
+                HttpMethod::Get,
+                format!(
+                    "/rest/api/2/search?jql={}",
+                    urlencoding::encode(&format!("project = \"{project}\""))
+                ),
+            ),
+            items_pointer: Some("/issues".to_string()),
+            list_paging: Some(OffsetPaging {
+                offset_param: "startAt".to_string(),
+                limit_param: "maxResults".to_string(),
+                page_size: 100,
+                total_pointer: Some("/total".to_string()),
+            }),
+            get: RequestTemplate::new(HttpMethod::Get, "/rest/api/2/issue/{id}"),
+            create: RequestTemplate {
+                method: HttpMethod::Post,
+                path: "/rest/api/2/issue".to_string(),
+                body: Some(serde_json::json!({
+                    "fields": { "project": { "key": project }, "issuetype": { "name": "Task" } }
+                })),
+            },
+            update: RequestTemplate::new(HttpMethod::Put, "/rest/api/2/issue/{id}"),
+            close: Some(transition(close_transition)),
+            reopen: Some(transition(reopen_transition)),
+            fields: FieldMapping {
+                id: "/key".to_string(),
+                title: "/fields/summary".to_string(),
+                body: "/fields/description".to_string(),
+                state: "/fields/status/name".to_string(),
+                updated_at: Some("/fields/updated".to_string()),
+            },
+            open_state: "To Do".to_string(),
+            closed_state: "Done".to_string(),
+        }
+    }
+
+    /// Read an issue from tracker JSON
+    pub fn parse_issue(&self, value: &Value) -> Result<RemoteIssue> {
+        let id = match value.pointer(&self.fields.id) {
+            Some(Value::String(id)) => id.clone(),
+            Some(Value::Number(id)) => id.to_string(),
+            _ => {
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Remote issue has no id at '{}'",
+                    self.fields.id
+                )))
+            }
+        };
+        let text = |pointer: &str| {
+            value
+                .pointer(pointer)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let state = text(&self.fields.state);
+
+        Ok(RemoteIssue {
+            id,
+            title: text(&self.fields.title),
+            body: text(&self.fields.body),
+            closed: state.eq_ignore_ascii_case(&self.closed_state),
+            updated_at: self
+                .fields
+                .updated_at
+                .as_deref()
+                .and_then(|pointer| value.pointer(pointer))
+                .map(|updated| match updated {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                }),
+        })
+    }
+
+    /// Read every issue from a list response
+    ///
+    /// Pull requests, which GitHub and Gitea list among the issues, are skipped.
+    pub fn parse_list(&self, value: &Value) -> Result<Vec<RemoteIssue>> {
+        let items = match self.items_pointer.as_deref() {
+            Some(pointer) => value.pointer(pointer),
+            None => Some(value),
+        };
+        let Some(Value::Array(items)) = items else {
+            return Err(SwissArmyHammerError::Other(
+                "Remote issue list response is not an array".to_string(),
+            ));
+        };
+        items
+            .iter()
+            .filter(|item| item.get("pull_request").map_or(true, Value::is_null))
+            .map(|item| self.parse_issue(item))
+            .collect()
+    }
+
+    /// Body of a create or update request
+    ///
+    /// The state field is only written when `closed` is given and the tracker
+    /// has no dedicated close and reopen requests.
+    pub fn issue_body(
+        &self,
+        template: &RequestTemplate,
+        title: &str,
+        body: &str,
+        closed: Option<bool>,
+    ) -> Value {
+        let mut value = template
+            .body
+            .clone()
+            .unwrap_or_else(|| Value::Object(Map::new()));
+        set_pointer(
+            &mut value,
+            &self.fields.title,
+            Value::String(title.to_string()),
+        );
+        set_pointer(
+            &mut value,
+            &self.fields.body,
+            Value::String(body.to_string()),
+        );
+        if let Some(closed) = closed {
+            let state = if closed {
+                &self.closed_state
+            } else {
+                &self.open_state
+            };
+            set_pointer(&mut value, &self.fields.state, Value::String(state.clone()));
+        }
+        value
+    }
+}
+
+/// Write `value` at a JSON pointer, creating intermediate objects
+pub(crate) fn set_pointer(target: &mut Value, pointer: &str, value: Value) {
+    let mut current = target;
+    let tokens: Vec<String> = pointer
+        .split('/')
+        .skip(1)
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    let Some((last, parents)) = tokens.split_last() else {
+        *current = value;
+        return;
+    };
+    for token in parents {
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        current = current
+            .as_object_mut()
+            .expect("value was just made an object")
+            .entry(token.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    if !current.is_object() {
+        *current = Value::Object(Map::new());
+    }
+    current
+        .as_object_mut()
+        .expect("value was just made an object")
+        .insert(last.clone(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_github_issue() {
+        let mapping = RestMapping::github("acme", "widgets");
+        let issue = mapping
+            .parse_issue(&json!({
+                "number": 42,
+                "title": "fix_login",
+                "body": "# Fix login",
+                "state": "closed",
+                "updated_at": "2024-01-01T00:00:00Z"
+            }))
+            .unwrap();
+
+        assert_eq!(issue.id, "42");
+        assert_eq!(issue.title, "fix_login");
+        assert!(issue.closed);
+        assert_eq!(issue.fingerprint(), "2024-01-01T00:00:00Z");
+        assert_eq!(
+            mapping.get.path_for(&issue.id),
+            "/repos/acme/widgets/issues/42"
+        );
+    }
+
+    #[test]
+    fn test_list_skips_pull_requests() {
+        let mapping = RestMapping::github("acme", "widgets");
+        let list = mapping
+            .parse_list(&json!([
+                { "number": 1, "title": "bug", "state": "open", "pull_request": null },
+                { "number": 2, "title": "fix bug", "state": "open",
+                  "pull_request": { "url": "https://api.github.com/repos/acme/widgets/pulls/2" } },
+                { "number": 3, "title": "feature", "state": "open" }
+            ]))
+            .unwrap();
+        let ids: Vec<&str> = list.iter().map(|issue| issue.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3"]);
+    }
+
+    #[test]
+    fn test_jira_nested_fields() {
+        let mapping = RestMapping::jira("OPS", "31", "11");
+        let list = mapping
+            .parse_list(&json!({
+                "issues": [{
+                    "key": "OPS-7",
+                    "fields": {
+                        "summary": "rotate_keys",
+                        "description": null,
+                        "status": { "name": "To Do" },
+                        "updated": "2024-02-02T10:00:00.000+0000"
+                    }
+                }]
+            }))
+            .unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].id, "OPS-7");
+        assert_eq!(list[0].body, "");
+        assert!(!list[0].closed);
+
+        let body = mapping.issue_body(&mapping.create, "rotate_keys", "Body", Some(true));
+        assert_eq!(body["fields"]["project"]["key"], "OPS");
+        assert_eq!(body["fields"]["summary"], "rotate_keys");
+        assert_eq!(body["fields"]["status"]["name"], "Done");
+    }
+
+    #[test]
+    fn test_set_pointer_creates_objects() {
+        let mut value = json!({"a": 1});
+        set_pointer(&mut value, "/b/c~1d", json!("x"));
+        assert_eq!(value, json!({"a": 1, "b": {"c/d": "x"}}));
+    }
+
+    #[test]
+    fn test_fingerprint_without_updated_at_tracks_content() {
+        let issue = RemoteIssue {
+            id: "1".to_string(),
+            title: "a".to_string(),
+            body: "b".to_string(),
+            closed: false,
+            updated_at: None,
+        };
+        let mut changed = issue.clone();
+        changed.closed = true;
+        assert_ne!(issue.fingerprint(), changed.fingerprint());
+    }
+}
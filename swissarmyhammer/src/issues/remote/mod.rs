@@ -0,0 +1,1040 @@
+//! Issue storage synchronized with a remote issue tracker
+//!
+//! [`HttpIssueStorage`] keeps the markdown files in the issues directory as the
+//! working copy, so everything reading issues, including `issue_work` and
+//! `issue_merge`, behaves exactly as with [`FileSystemIssueStorage`]. Every
+//! change is written locally first and then sent to the tracker:
+//!
+//! - creating an issue creates a remote issue titled with the issue name
+//! - updating an issue updates the remote body, unless the remote issue changed
+//!   since the last sync, which is reported as a conflict
+//! - completing, archiving or deleting an issue closes the remote issue, and
+//!   reopening it reopens the remote issue
+//!
+//! Changes that cannot reach the tracker are queued in the sync state file and
+//! sent by the next [`IssueStorage::sync`], which also pulls remote changes.
+//!
+//! The tracker is configured in `sah.toml`:
+//!
+//! ```toml
+//! [issues.remote]
+//! preset = "github"
+//! base_url = "https://api.github.com"
+//! owner = "acme"
+//! repo = "widgets"
+//! token_env = "GITHUB_TOKEN"
+//! ```
+//!
+//! Presets exist for GitHub, Gitea and Jira. Other trackers can be described
+//! with `preset = "custom"` and a [`RestMapping`] under `issues.remote.mapping`.
+
+mod client;
+mod mapping;
+mod state;
+
+pub use client::{RemoteError, RemoteIssueClient, RemoteResult};
+pub use mapping::{
+    FieldMapping, HttpMethod, OffsetPaging, RemoteIssue, RequestTemplate, RestMapping,
+};
+pub use state::{content_hash, QueuedOperation, SyncRecord, SyncState, SYNC_STATE_FILE};
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::issues::filesystem::{FileSystemIssueStorage, Issue, IssueInfo, IssueStorage};
+use crate::issues::history::{IssueChange, IssueHistoryEntry};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+/// Configuration key of the remote tracker settings
+pub const REMOTE_CONFIG_KEY: &str = "issues.remote";
+
+/// How `sync` resolves issues changed on both sides
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// Leave both sides untouched and report the conflict
+    #[default]
+    Report,
+    /// Overwrite the remote issue with the local one
+    Local,
+    /// Overwrite the local issue with the remote one
+    Remote,
+}
+
+impl FromStr for ConflictStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "report" => Ok(ConflictStrategy::Report),
+            "local" => Ok(ConflictStrategy::Local),
+            "remote" => Ok(ConflictStrategy::Remote),
+            other => Err(format!(
+                "Unknown conflict strategy '{other}', expected report, local or remote"
+            )),
+        }
+    }
+}
+
+/// An issue changed both locally and remotely since the last sync
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncConflict {
+    /// Local issue name
+    pub name: String,
+    /// Remote id
+    pub remote_id: String,
+    /// What changed on each side
+    pub reason: String,
+}
+
+/// An operation the tracker rejected
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncFailure {
+    /// Local issue name
+    pub name: String,
+    /// Error returned by the tracker
+    pub error: String,
+}
+
+/// Outcome of a sync
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncReport {
+    /// Local changes sent to existing remote issues
+    pub pushed: Vec<String>,
+    /// Remote changes applied to existing local issues
+    pub pulled: Vec<String>,
+    /// Local issues created from remote issues
+    pub created_local: Vec<String>,
+    /// Remote issues created from local issues
+    pub created_remote: Vec<String>,
+    /// Remote issues closed because the local issue was deleted
+    pub closed_remote: Vec<String>,
+    /// Issues left untouched because both sides changed
+    pub conflicts: Vec<SyncConflict>,
+    /// Operations the tracker rejected
+    pub failed: Vec<SyncFailure>,
+    /// Operations still waiting in the offline queue
+    pub queued: usize,
+    /// Whether the tracker could not be reached
+    pub offline: bool,
+}
+
+impl SyncReport {
+    /// Whether the sync neither changed nor found anything
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Human readable summary
+    pub fn summary(&self) -> String {
+        let mut lines = Vec::new();
+        if self.offline {
+            lines.push("Issue tracker unreachable, changes stay queued".to_string());
+        }
+        let sections: [(&str, &Vec<String>); 5] = [
+            ("Pushed", &self.pushed),
+            ("Pulled", &self.pulled),
+            ("Created locally", &self.created_local),
+            ("Created remotely", &self.created_remote),
+            ("Closed remotely", &self.closed_remote),
+        ];
+        for (label, names) in sections {
+            if !names.is_empty() {
+                lines.push(format!("{label}: {}", names.join(", ")));
+            }
+        }
+        for conflict in &self.conflicts {
+            lines.push(format!(
+                "Conflict: {} (remote {}): {}",
+                conflict.name, conflict.remote_id, conflict.reason
+            ));
+        }
+        for failure in &self.failed {
+            lines.push(format!("Failed: {}: {}", failure.name, failure.error));
+        }
+        if self.queued > 0 {
+            lines.push(format!("Queued operations: {}", self.queued));
+        }
+        if lines.is_empty() {
+            lines.push("Issues are in sync".to_string());
+        }
+        lines.join("\n")
+    }
+}
+
+/// Tracker presets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemotePreset {
+    /// GitHub REST API
+    #[default]
+    Github,
+    /// Gitea API
+    Gitea,
+    /// Jira REST API v2
+    Jira,
+    /// Mapping given in the configuration
+    Custom,
+}
+
+/// Settings of the remote tracker, read from `issues.remote`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteIssueConfig {
+    /// Base URL of the tracker API
+    pub base_url: String,
+    /// Tracker preset
+    #[serde(default)]
+    pub preset: RemotePreset,
+    /// Repository owner, for GitHub and Gitea
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Repository name, for GitHub and Gitea
+    #[serde(default)]
+    pub repo: Option<String>,
+    /// Project key, for Jira
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Transition closing an issue, for Jira
+    #[serde(default)]
+    pub close_transition: Option<String>,
+    /// Transition reopening an issue, for Jira
+    #[serde(default)]
+    pub reopen_transition: Option<String>,
+    /// Mapping for the custom preset
+    #[serde(default)]
+    pub mapping: Option<RestMapping>,
+    /// API token
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Environment variable holding the API token
+    #[serde(default)]
+    pub token_env: Option<String>,
+    /// Scheme of the `Authorization` header
+    #[serde(default = "RemoteIssueConfig::default_auth_scheme")]
+    pub auth_scheme: String,
+    /// Request timeout in seconds
+    #[serde(default = "RemoteIssueConfig::default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl RemoteIssueConfig {
+    fn default_auth_scheme() -> String {
+        "Bearer".to_string()
+    }
+
+    fn default_timeout_secs() -> u64 {
+        30
+    }
+
+    /// Read the settings from the loaded configuration, if present
+    pub fn from_configuration() -> Result<Option<Self>> {
+        let context = swissarmyhammer_config::load_configuration()
+            .map_err(|e| SwissArmyHammerError::Config(e.to_string()))?;
+        context
+            .get(REMOTE_CONFIG_KEY)
+            .map(|value| Self::from_value(value.clone()))
+            .transpose()
+    }
+
+    /// Read the settings from a configuration value
+    pub fn from_value(value: serde_json::Value) -> Result<Self> {
+        serde_json::from_value(value).map_err(|e| {
+            SwissArmyHammerError::Config(format!("Invalid {REMOTE_CONFIG_KEY} settings: {e}"))
+        })
+    }
+
+    /// The REST mapping for the configured preset
+    pub fn rest_mapping(&self) -> Result<RestMapping> {
+        let required = |value: &Option<String>, field: &str| {
+            value.clone().ok_or_else(|| {
+                SwissArmyHammerError::Config(format!(
+                    "{REMOTE_CONFIG_KEY}.{field} is required for this preset"
+                ))
+            })
+        };
+
+        Ok(match self.preset {
+            RemotePreset::Github => RestMapping::github(
+                &required(&self.owner, "owner")?,
+                &required(&self.repo, "repo")?,
+            ),
+            RemotePreset::Gitea => RestMapping::gitea(
+                &required(&self.owner, "owner")?,
+                &required(&self.repo, "repo")?,
+            ),
+            RemotePreset::Jira => RestMapping::jira(
+                &required(&self.project, "project")?,
+                &required(&self.close_transition, "close_transition")?,
+                &required(&self.reopen_transition, "reopen_transition")?,
+            ),
+            RemotePreset::Custom => self.mapping.clone().ok_or_else(|| {
+                SwissArmyHammerError::Config(format!(
+                    "{REMOTE_CONFIG_KEY}.mapping is required for the custom preset"
+                ))
+            })?,
+        })
+    }
+
+    /// Value of the `Authorization` header, if a token is configured
+    pub fn authorization(&self) -> Option<String> {
+        let token = self.token.clone().or_else(|| {
+            self.token_env
+                .as_ref()
+                .and_then(|name| std::env::var(name).ok())
+        })?;
+        Some(format!("{} {}", self.auth_scheme, token))
+    }
+
+    /// Create a client for the configured tracker
+    pub fn client(&self) -> Result<RemoteIssueClient> {
+        Ok(RemoteIssueClient::new(
+            self.base_url.clone(),
+            self.rest_mapping()?,
+            self.authorization(),
+            Duration::from_secs(self.timeout_secs),
+        )?)
+    }
+}
+
+/// Create the issue storage for a working directory
+///
+/// Uses [`HttpIssueStorage`] when `issues.remote` is configured and
+/// [`FileSystemIssueStorage`] otherwise.
+pub fn create_issue_storage_in(work_dir: &Path) -> Result<Box<dyn IssueStorage>> {
+    let issues_dir = FileSystemIssueStorage::default_directory_in(work_dir)?;
+    match RemoteIssueConfig::from_configuration()? {
+        Some(config) => {
+            debug!("Syncing issues with {}", config.base_url);
+            Ok(Box::new(HttpIssueStorage::new(
+                issues_dir,
+                config.client()?,
+            )?))
+        }
+        None => Ok(Box::new(FileSystemIssueStorage::new(issues_dir)?)),
+    }
+}
+
+/// Issue storage mirrored to a remote issue tracker
+#[derive(Debug)]
+pub struct HttpIssueStorage {
+    /// Local working copy
+    local: FileSystemIssueStorage,
+    /// Tracker client
+    client: RemoteIssueClient,
+    /// Location of the sync state
+    state_path: PathBuf,
+    /// Sync state, also serializing all writes
+    state: Mutex<SyncState>,
+}
+
+impl HttpIssueStorage {
+    /// Create a storage synchronizing `issues_dir` with the tracker
+    pub fn new(issues_dir: PathBuf, client: RemoteIssueClient) -> Result<Self> {
+        let local = FileSystemIssueStorage::new(issues_dir)?;
+        let state_path = local.get_state().issues_dir.join(SYNC_STATE_FILE);
+        let state = SyncState::load(&state_path)?;
+
+        Ok(Self {
+            local,
+            client,
+            state_path,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Snapshot of the sync state
+    pub async fn sync_state(&self) -> SyncState {
+        self.state.lock().await.clone()
+    }
+
+    /// Send a local issue to the tracker, creating the remote issue if needed
+    async fn push(&self, info: &IssueInfo, record: Option<SyncRecord>) -> RemoteResult<SyncRecord> {
+        let issue = &info.issue;
+        let (title, mut remote) = match record {
+            None => {
+                let remote = self.client.create(&issue.name, &issue.content).await?;
+                (issue.name.clone(), remote)
+            }
+            Some(record) if record.content_hash != content_hash(&issue.content) => {
+                let remote = self
+                    .client
+                    .update(&record.remote_id, &record.title, &issue.content)
+                    .await?;
+                (record.title, remote)
+            }
+            Some(record) => {
+                let remote = self.client.get(&record.remote_id).await?;
+                (record.title, remote)
+            }
+        };
+
+        if remote.closed != is_closed(info) {
+            remote = self.client.set_closed(&remote.id, is_closed(info)).await?;
+        }
+
+        Ok(SyncRecord {
+            remote_id: remote.id.clone(),
+            title,
+            content_hash: content_hash(&issue.content),
+            remote_fingerprint: remote.fingerprint(),
+            closed: is_closed(info),
+        })
+    }
+
+    /// Apply a remote issue to the local working copy
+    async fn pull(
+        &self,
+        name: &str,
+        remote: &RemoteIssue,
+        local: Option<&IssueInfo>,
+    ) -> Result<SyncRecord> {
+        match local {
+            Some(info) => {
+                if info.issue.content != remote.body {
+                    self.local.update_issue(name, remote.body.clone()).await?;
+                }
+                if remote.closed && !is_closed(info) {
+                    self.local.complete_issue(name).await?;
+                } else if !remote.closed && is_closed(info) {
+                    self.local.reopen_issue(name).await?;
+                    debug!("Reopened issue '{}' from the issue tracker", name);
+                }
+            }
+            None => {
+                self.local
+                    .create_issue(name.to_string(), remote.body.clone())
+                    .await?;
+                if remote.closed {
+                    self.local.complete_issue(name).await?;
+                }
+            }
+        }
+
+        Ok(SyncRecord {
+            remote_id: remote.id.clone(),
+            title: remote.title.clone(),
+            content_hash: content_hash(&remote.body),
+            remote_fingerprint: remote.fingerprint(),
+            closed: remote.closed,
+        })
+    }
+
+    /// Refuse to overwrite a remote edit made since the last sync
+    ///
+    /// An unreachable tracker is not an error; the change is queued and the
+    /// next sync detects the conflict instead.
+    async fn ensure_remote_unchanged(&self, name: &str, record: Option<&SyncRecord>) -> Result<()> {
+        let Some(record) = record else {
+            return Ok(());
+        };
+        match self.client.get(&record.remote_id).await {
+            Ok(remote) if remote.fingerprint() != record.remote_fingerprint => {
+                Err(SwissArmyHammerError::IssueConflict(format!(
+                    "'{name}' was changed in the issue tracker since the last sync, run issue_sync first"
+                )))
+            }
+            Ok(_) => Ok(()),
+            Err(e) if e.is_retryable() => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Send the local content of an issue after it was written
+    async fn push_content(
+        &self,
+        state: &mut SyncState,
+        name: &str,
+        record: Option<SyncRecord>,
+    ) -> Result<()> {
+        let queued_create = state.queue.contains(&QueuedOperation::Create {
+            name: name.to_string(),
+        });
+        if record.is_none() && queued_create {
+            // The queued create sends the current content
+            return Ok(());
+        }
+
+        let info = self.local.get_issue_info(name).await?;
+        match self.push(&info, record).await {
+            Ok(record) => {
+                state.issues.insert(name.to_string(), record);
+                self.save_state(state)
+            }
+            Err(e) => {
+                let operation = QueuedOperation::Update {
+                    name: name.to_string(),
+                };
+                self.queue_or_fail(state, operation, e)
+            }
+        }
+    }
+
+    /// Close or reopen the remote issue after the local issue moved
+    async fn push_status(&self, state: &mut SyncState, name: &str) -> Result<()> {
+        let Some(record) = state.issues.get(name).cloned() else {
+            return Ok(());
+        };
+        let info = self.local.get_issue_info(name).await?;
+        if record.closed == is_closed(&info) {
+            return Ok(());
+        }
+
+        match self.push(&info, Some(record)).await {
+            Ok(record) => {
+                state.issues.insert(name.to_string(), record);
+                self.save_state(state)
+            }
+            Err(e) => {
+                let operation = QueuedOperation::Close {
+                    name: name.to_string(),
+                };
+                self.queue_or_fail(state, operation, e)
+            }
+        }
+    }
+
+    fn save_state(&self, state: &SyncState) -> Result<()> {
+        state.save(&self.state_path)
+    }
+
+    /// Queue an operation after the tracker could not be reached, or fail
+    fn queue_or_fail(
+        &self,
+        state: &mut SyncState,
+        operation: QueuedOperation,
+        error: RemoteError,
+    ) -> Result<()> {
+        if error.is_retryable() {
+            warn!(
+                "Queued issue '{}' for the next sync: {}",
+                operation.name(),
+                error
+            );
+            state.enqueue(operation);
+            self.save_state(state)
+        } else {
+            self.save_state(state)?;
+            Err(SwissArmyHammerError::Storage(format!(
+                "Issue '{}' was saved locally but the tracker rejected it: {error}",
+                operation.name()
+            )))
+        }
+    }
+
+    /// Send queued operations in order, stopping when the tracker is unreachable
+    async fn replay_queue(&self, state: &mut SyncState, report: &mut SyncReport) {
+        let queue = std::mem::take(&mut state.queue);
+        let mut remaining = queue.into_iter();
+
+        while let Some(operation) = remaining.next() {
+            let result = match &operation {
+                QueuedOperation::Delete { name, remote_id } => {
+                    match self.client.set_closed(remote_id, true).await {
+                        Ok(_) => {
+                            state.issues.remove(name);
+                            report.closed_remote.push(name.clone());
+                            Ok(())
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                QueuedOperation::Create { name }
+                | QueuedOperation::Update { name }
+                | QueuedOperation::Close { name } => self.replay_push(name, state, report).await,
+            };
+
+            match result {
+                Ok(()) => {}
+                Err(e) if e.is_retryable() => {
+                    debug!("Issue tracker still unreachable: {}", e);
+                    report.offline = true;
+                    state.queue.push(operation);
+                    state.queue.extend(remaining);
+                    break;
+                }
+                Err(e) => report.failed.push(SyncFailure {
+                    name: operation.name().to_string(),
+                    error: e.to_string(),
+                }),
+            }
+        }
+    }
+
+    /// Push a queued change unless the remote issue changed in the meantime
+    ///
+    /// Issues whose remote side changed are left for the reconciliation pass,
+    /// which reports them as conflicts.
+    async fn replay_push(
+        &self,
+        name: &str,
+        state: &mut SyncState,
+        report: &mut SyncReport,
+    ) -> RemoteResult<()> {
+        let Ok(info) = self.local.get_issue_info(name).await else {
+            // Deleted locally after being queued; the delete has its own entry
+            return Ok(());
+        };
+
+        let record = state.issues.get(name).cloned();
+        if let Some(record) = &record {
+            let remote = self.client.get(&record.remote_id).await?;
+            if remote.fingerprint() != record.remote_fingerprint {
+                return Ok(());
+            }
+        }
+
+        let created = record.is_none();
+        let updated = self.push(&info, record).await?;
+        state.issues.insert(name.to_string(), updated);
+        if created {
+            report.created_remote.push(name.to_string());
+        } else {
+            report.pushed.push(name.to_string());
+        }
+        Ok(())
+    }
+
+    /// Compare both sides with the sync state and bring them together
+    async fn reconcile(
+        &self,
+        state: &mut SyncState,
+        strategy: ConflictStrategy,
+        report: &mut SyncReport,
+    ) -> Result<()> {
+        let remote_issues = match self.client.list().await {
+            Ok(issues) => issues,
+            Err(e) if e.is_retryable() => {
+                report.offline = true;
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut remote_by_id: BTreeMap<String, RemoteIssue> = remote_issues
+            .into_iter()
+            .map(|issue| (issue.id.clone(), issue))
+            .collect();
+        let mut local_issues = self.local.list_issues_info().await?;
+        local_issues.extend(self.local.list_archived_issues().await?);
+        let local: BTreeMap<String, IssueInfo> = local_issues
+            .into_iter()
+            .map(|info| (info.issue.name.clone(), info))
+            .collect();
+
+        // Linked issues
+        for (name, record) in state.issues.clone() {
+            let remote = remote_by_id.remove(&record.remote_id);
+            let result = match (local.get(&name), remote) {
+                (None, None) => {
+                    state.issues.remove(&name);
+                    Ok(())
+                }
+                (None, Some(remote)) => {
+                    // Deleted locally without going through the storage
+                    let closed = if remote.closed {
+                        Ok(())
+                    } else {
+                        self.client.set_closed(&remote.id, true).await.map(|_| {
+                            report.closed_remote.push(name.clone());
+                        })
+                    };
+                    closed.map(|()| {
+                        state.issues.remove(&name);
+                    })
+                }
+                (Some(info), None) => {
+                    // Missing from the list is not proof of deletion; ask for it
+                    match self.client.get(&record.remote_id).await {
+                        Ok(remote) => {
+                            self.reconcile_linked(
+                                &name, info, &remote, &record, state, strategy, report,
+                            )
+                            .await
+                        }
+                        Err(e) if e.is_not_found() => {
+                            self.resolve_missing_remote(
+                                &name, info, &record, state, strategy, report,
+                            )
+                            .await
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                (Some(info), Some(remote)) => {
+                    self.reconcile_linked(&name, info, &remote, &record, state, strategy, report)
+                        .await
+                }
+            };
+
+            if let Err(e) = result {
+                if e.is_retryable() {
+                    report.offline = true;
+                    return Ok(());
+                }
+                report.failed.push(SyncFailure {
+                    name,
+                    error: e.to_string(),
+                });
+            }
+        }
+
+        // Remote issues not linked to a local issue
+        let mut taken: HashSet<String> = local.keys().cloned().collect();
+        taken.extend(state.issues.keys().cloned());
+        for remote in remote_by_id.values() {
+            let name = local_name_for(remote);
+            match local.get(&name) {
+                Some(info) if !state.issues.contains_key(&name) => {
+                    // Same name on both sides: link them if they agree
+                    let record = SyncRecord {
+                        remote_id: remote.id.clone(),
+                        title: remote.title.clone(),
+                        content_hash: String::new(),
+                        remote_fingerprint: String::new(),
+                        closed: is_closed(info),
+                    };
+                    if info.issue.content == remote.body && is_closed(info) == remote.closed {
+                        state
+                            .issues
+                            .insert(name.clone(), self.linked_record(info, remote));
+                    } else if let Err(e) = self
+                        .reconcile_linked(&name, info, remote, &record, state, strategy, report)
+                        .await
+                    {
+                        if e.is_retryable() {
+                            report.offline = true;
+                            return Ok(());
+                        }
+                        report.failed.push(SyncFailure {
+                            name: name.clone(),
+                            error: e.to_string(),
+                        });
+                    }
+                }
+                _ => {
+                    let name = if taken.contains(&name) {
+                        format!("{name}_{}", sanitize_name(&remote.id))
+                    } else {
+                        name
+                    };
+                    let record = self.pull(&name, remote, None).await?;
+                    state.issues.insert(name.clone(), record);
+                    report.created_local.push(name.clone());
+                    taken.insert(name);
+                }
+            }
+        }
+
+        // Local issues never sent to the tracker
+        for (name, info) in &local {
+            if state.issues.contains_key(name) || report.conflicts.iter().any(|c| &c.name == name) {
+                continue;
+            }
+            match self.push(info, None).await {
+                Ok(record) => {
+                    state.issues.insert(name.clone(), record);
+                    report.created_remote.push(name.clone());
+                }
+                Err(e) if e.is_retryable() => {
+                    report.offline = true;
+                    state.enqueue(QueuedOperation::Create { name: name.clone() });
+                    return Ok(());
+                }
+                Err(e) => report.failed.push(SyncFailure {
+                    name: name.clone(),
+                    error: e.to_string(),
+                }),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record for a local and remote issue that are already identical
+    fn linked_record(&self, info: &IssueInfo, remote: &RemoteIssue) -> SyncRecord {
+        SyncRecord {
+            remote_id: remote.id.clone(),
+            title: remote.title.clone(),
+            content_hash: content_hash(&info.issue.content),
+            remote_fingerprint: remote.fingerprint(),
+            closed: is_closed(info),
+        }
+    }
+
+    /// Bring a linked issue up to date, or report a conflict
+    #[allow(clippy::too_many_arguments)]
+    async fn reconcile_linked(
+        &self,
+        name: &str,
+        info: &IssueInfo,
+        remote: &RemoteIssue,
+        record: &SyncRecord,
+        state: &mut SyncState,
+        strategy: ConflictStrategy,
+        report: &mut SyncReport,
+    ) -> RemoteResult<()> {
+        let local_changed = content_hash(&info.issue.content) != record.content_hash
+            || is_closed(info) != record.closed;
+        let remote_changed = remote.fingerprint() != record.remote_fingerprint;
+
+        let pull = match (local_changed, remote_changed) {
+            (false, false) => return Ok(()),
+            (true, false) => false,
+            (false, true) => true,
+            (true, true) => {
+                if info.issue.content == remote.body && is_closed(info) == remote.closed {
+                    state
+                        .issues
+                        .insert(name.to_string(), self.linked_record(info, remote));
+                    return Ok(());
+                }
+                match strategy {
+                    ConflictStrategy::Local => false,
+                    ConflictStrategy::Remote => true,
+                    ConflictStrategy::Report => {
+                        report.conflicts.push(SyncConflict {
+                            name: name.to_string(),
+                            remote_id: remote.id.clone(),
+                            reason: "changed locally and in the issue tracker since the last sync"
+                                .to_string(),
+                        });
+                        return Ok(());
+                    }
+                }
+            }
+        };
+
+        let updated = if pull {
+            let updated = self
+                .pull(name, remote, Some(info))
+                .await
+                .map_err(|e| RemoteError::Invalid(e.to_string()))?;
+            report.pulled.push(name.to_string());
+            updated
+        } else {
+            let linked = SyncRecord {
+                remote_id: remote.id.clone(),
+                title: record.title.clone(),
+                // Force the content to be sent
+                content_hash: String::new(),
+                remote_fingerprint: record.remote_fingerprint.clone(),
+                closed: record.closed,
+            };
+            let updated = self.push(info, Some(linked)).await?;
+            report.pushed.push(name.to_string());
+            updated
+        };
+        state.issues.insert(name.to_string(), updated);
+        Ok(())
+    }
+
+    /// Handle a linked issue whose remote issue no longer exists
+    async fn resolve_missing_remote(
+        &self,
+        name: &str,
+        info: &IssueInfo,
+        record: &SyncRecord,
+        state: &mut SyncState,
+        strategy: ConflictStrategy,
+        report: &mut SyncReport,
+    ) -> RemoteResult<()> {
+        match strategy {
+            ConflictStrategy::Report => {
+                report.conflicts.push(SyncConflict {
+                    name: name.to_string(),
+                    remote_id: record.remote_id.clone(),
+                    reason: "deleted in the issue tracker".to_string(),
+                });
+            }
+            ConflictStrategy::Local => {
+                let created = self.push(info, None).await?;
+                state.issues.insert(name.to_string(), created);
+                report.created_remote.push(name.to_string());
+            }
+            ConflictStrategy::Remote => {
+                self.local
+                    .delete_issue(name)
+                    .await
+                    .map_err(|e| RemoteError::Invalid(e.to_string()))?;
+                state.issues.remove(name);
+                report.pulled.push(name.to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether the remote issue of a local issue should be closed
+fn is_closed(info: &IssueInfo) -> bool {
+    info.completed || info.archived
+}
+
+/// Make a remote title usable as an issue file name
+fn sanitize_name(title: &str) -> String {
+    let name: String = title
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    name.trim_matches(|c| c == '_' || c == '.').to_string()
+}
+
+/// Local name for a remote issue
+fn local_name_for(remote: &RemoteIssue) -> String {
+    match sanitize_name(&remote.title) {
+        name if name.is_empty() => format!("remote_{}", sanitize_name(&remote.id)),
+        name => name,
+    }
+}
+
+#[async_trait::async_trait]
+impl IssueStorage for HttpIssueStorage {
+    async fn list_issues(&self) -> Result<Vec<Issue>> {
+        self.local.list_issues().await
+    }
+
+    async fn list_issues_info(&self) -> Result<Vec<IssueInfo>> {
+        self.local.list_issues_info().await
+    }
+
+    async fn list_archived_issues(&self) -> Result<Vec<IssueInfo>> {
+        self.local.list_archived_issues().await
+    }
+
+    async fn get_issue(&self, name: &str) -> Result<Issue> {
+        self.local.get_issue(name).await
+    }
+
+    async fn get_issue_info(&self, name: &str) -> Result<IssueInfo> {
+        self.local.get_issue_info(name).await
+    }
+
+    async fn create_issue(&self, name: String, content: String) -> Result<Issue> {
+        let mut state = self.state.lock().await;
+        let issue = self.local.create_issue(name, content).await?;
+        let info = self.local.get_issue_info(&issue.name).await?;
+
+        match self.push(&info, None).await {
+            Ok(record) => {
+                state.issues.insert(issue.name.clone(), record);
+                self.save_state(&state)?;
+            }
+            Err(e) => {
+                let operation = QueuedOperation::Create {
+                    name: issue.name.clone(),
+                };
+                self.queue_or_fail(&mut state, operation, e)?;
+            }
+        }
+        Ok(issue)
+    }
+
+    async fn update_issue(&self, name: &str, content: String) -> Result<Issue> {
+        let mut state = self.state.lock().await;
+        let record = state.issues.get(name).cloned();
+        self.ensure_remote_unchanged(name, record.as_ref()).await?;
+
+        let issue = self.local.update_issue(name, content).await?;
+        self.push_content(&mut state, name, record).await?;
+        Ok(issue)
+    }
+
+    async fn delete_issue(&self, name: &str) -> Result<()> {
+        let mut state = self.state.lock().await;
+        self.local.delete_issue(name).await?;
+
+        let Some(record) = state.issues.get(name).cloned() else {
+            // Never reached the tracker; drop anything still queued for it
+            state.queue.retain(|op| op.name() != name);
+            return self.save_state(&state);
+        };
+
+        match self.client.set_closed(&record.remote_id, true).await {
+            Ok(_) => {
+                state.issues.remove(name);
+                state.queue.retain(|op| op.name() != name);
+                self.save_state(&state)
+            }
+            Err(e) => {
+                let operation = QueuedOperation::Delete {
+                    name: name.to_string(),
+                    remote_id: record.remote_id,
+                };
+                self.queue_or_fail(&mut state, operation, e)
+            }
+        }
+    }
+
+    async fn complete_issue(&self, name: &str) -> Result<Issue> {
+        let mut state = self.state.lock().await;
+        let issue = self.local.complete_issue(name).await?;
+        self.push_status(&mut state, name).await?;
+        Ok(issue)
+    }
+
+    async fn reopen_issue(&self, name: &str) -> Result<Issue> {
+        let mut state = self.state.lock().await;
+        let issue = self.local.reopen_issue(name).await?;
+        self.push_status(&mut state, name).await?;
+        Ok(issue)
+    }
+
+    async fn archive_issue(&self, name: &str) -> Result<Issue> {
+        let mut state = self.state.lock().await;
+        let issue = self.local.archive_issue(name).await?;
+        self.push_status(&mut state, name).await?;
+        Ok(issue)
+    }
+
+    async fn next_issue(&self) -> Result<Option<Issue>> {
+        self.local.next_issue().await
+    }
+
+    async fn all_issues_completed(&self) -> Result<bool> {
+        self.local.all_issues_completed().await
+    }
+
+    async fn issue_history(&self, name: &str) -> Result<Vec<IssueHistoryEntry>> {
+        self.local.issue_history(name).await
+    }
+
+    async fn record_issue_change(&self, name: &str, change: IssueChange) -> Result<()> {
+        self.local.record_issue_change(name, change).await
+    }
+
+    async fn revert_issue(&self, name: &str, revision: u32) -> Result<Issue> {
+        let mut state = self.state.lock().await;
+        let record = state.issues.get(name).cloned();
+        self.ensure_remote_unchanged(name, record.as_ref()).await?;
+
+        let issue = self.local.revert_issue(name, revision).await?;
+        self.push_content(&mut state, name, record).await?;
+        Ok(issue)
+    }
+
+    async fn sync(&self, strategy: ConflictStrategy) -> Result<Option<SyncReport>> {
+        let mut state = self.state.lock().await;
+        let mut report = SyncReport::default();
+
+        self.replay_queue(&mut state, &mut report).await;
+        if !report.offline {
+            let result = self.reconcile(&mut state, strategy, &mut report).await;
+            self.save_state(&state)?;
+            result?;
+        } else {
+            self.save_state(&state)?;
+        }
+
+        report.queued = state.queue.len();
+        Ok(Some(report))
+    }
+}
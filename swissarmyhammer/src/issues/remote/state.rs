@@ -0,0 +1,212 @@
+//! Persistent sync state between local issue files and the tracker
+//!
+//! The state remembers, for every linked issue, what both sides looked like
+//! after the last successful sync. Comparing against it tells which side
+//! changed since, and a change on both sides is a conflict. It also holds the
+//! queue of operations that could not reach the tracker.
+
+use crate::error::{Result, SwissArmyHammerError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// File holding the sync state, inside the issues directory
+pub const SYNC_STATE_FILE: &str = ".sync-state.json";
+
+/// Link between a local issue and a remote issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncRecord {
+    /// Remote id of the issue
+    pub remote_id: String,
+    /// Remote title, kept when pushing because local names are sanitized
+    pub title: String,
+    /// Hash of the local content at the last sync
+    pub content_hash: String,
+    /// Remote fingerprint at the last sync
+    pub remote_fingerprint: String,
+    /// Whether the issue was closed at the last sync
+    pub closed: bool,
+}
+
+/// An operation waiting for the tracker to become reachable
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum QueuedOperation {
+    /// Create the remote issue for a local issue
+    Create {
+        /// Local issue name
+        name: String,
+    },
+    /// Push the local content of an issue
+    Update {
+        /// Local issue name
+        name: String,
+    },
+    /// Push the completion state of an issue
+    Close {
+        /// Local issue name
+        name: String,
+    },
+    /// Close the remote issue of a deleted local issue
+    Delete {
+        /// Local issue name
+        name: String,
+        /// Remote id of the deleted issue
+        remote_id: String,
+    },
+}
+
+impl QueuedOperation {
+    /// Name of the local issue the operation applies to
+    pub fn name(&self) -> &str {
+        match self {
+            QueuedOperation::Create { name }
+            | QueuedOperation::Update { name }
+            | QueuedOperation::Close { name }
+            | QueuedOperation::Delete { name, .. } => name,
+        }
+    }
+}
+
+/// Sync state of an issues directory
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncState {
+    /// Linked issues by local name
+    #[serde(default)]
+    pub issues: BTreeMap<String, SyncRecord>,
+    /// Operations waiting to be sent, oldest first
+    #[serde(default)]
+    pub queue: Vec<QueuedOperation>,
+}
+
+impl SyncState {
+    /// Load the state from `path`, or an empty state if the file is missing
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            SwissArmyHammerError::Storage(format!(
+                "Invalid issue sync state {}: {e}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Write the state to `path`
+    ///
+    /// The file is written next to its final location and renamed so that an
+    /// interrupted write never leaves a truncated state behind.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    /// Queue an operation, dropping ones it supersedes
+    ///
+    /// Repeated updates of the same issue only need to be sent once, and
+    /// nothing else needs to be sent for an issue that is going to be deleted.
+    pub fn enqueue(&mut self, operation: QueuedOperation) {
+        match &operation {
+            QueuedOperation::Delete { name, .. } => {
+                let pending_create = self
+                    .queue
+                    .iter()
+                    .any(|op| matches!(op, QueuedOperation::Create { name: n } if n == name));
+                self.queue.retain(|op| op.name() != name);
+                // The remote issue was never created, so there is nothing to close
+                if pending_create {
+                    return;
+                }
+            }
+            QueuedOperation::Update { name } => {
+                let already_queued = self.queue.iter().any(|op| {
+                    matches!(op, QueuedOperation::Create { name: n } | QueuedOperation::Update { name: n } if n == name)
+                });
+                if already_queued {
+                    return;
+                }
+            }
+            QueuedOperation::Create { .. } | QueuedOperation::Close { .. } => {
+                if self.queue.contains(&operation) {
+                    return;
+                }
+            }
+        }
+        self.queue.push(operation);
+    }
+}
+
+/// Hash of local issue content used to detect local changes
+pub fn content_hash(content: &str) -> String {
+    format!("{:x}", md5::compute(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn update(name: &str) -> QueuedOperation {
+        QueuedOperation::Update {
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SYNC_STATE_FILE);
+        assert_eq!(SyncState::load(&path).unwrap(), SyncState::default());
+
+        let mut state = SyncState::default();
+        state.issues.insert(
+            "fix_login".to_string(),
+            SyncRecord {
+                remote_id: "42".to_string(),
+                title: "fix_login".to_string(),
+                content_hash: content_hash("# Fix"),
+                remote_fingerprint: "2024-01-01T00:00:00Z".to_string(),
+                closed: false,
+            },
+        );
+        state.enqueue(update("fix_login"));
+        state.save(&path).unwrap();
+
+        assert_eq!(SyncState::load(&path).unwrap(), state);
+    }
+
+    #[test]
+    fn test_enqueue_coalesces_operations() {
+        let mut state = SyncState::default();
+        state.enqueue(update("a"));
+        state.enqueue(update("a"));
+        assert_eq!(state.queue, vec![update("a")]);
+
+        state.enqueue(QueuedOperation::Create {
+            name: "b".to_string(),
+        });
+        state.enqueue(update("b"));
+        state.enqueue(QueuedOperation::Delete {
+            name: "b".to_string(),
+            remote_id: String::new(),
+        });
+        assert_eq!(state.queue, vec![update("a")]);
+
+        state.enqueue(QueuedOperation::Delete {
+            name: "a".to_string(),
+            remote_id: "7".to_string(),
+        });
+        assert_eq!(
+            state.queue,
+            vec![QueuedOperation::Delete {
+                name: "a".to_string(),
+                remote_id: "7".to_string(),
+            }]
+        );
+    }
+}
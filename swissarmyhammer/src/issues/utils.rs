@@ -417,18 +417,21 @@ mod tests {
             IssueInfo {
                 issue: active_issue1.clone(),
                 completed: false,
+                archived: false,
                 file_path: PathBuf::from("/test/active1.md"),
                 created_at: Utc::now(),
             },
             IssueInfo {
                 issue: active_issue2.clone(),
                 completed: false,
+                archived: false,
                 file_path: PathBuf::from("/test/active2.md"),
                 created_at: Utc::now(),
             },
             IssueInfo {
                 issue: completed_issue.clone(),
                 completed: true,
+                archived: false,
                 file_path: PathBuf::from("/test/completed/completed1.md"),
                 created_at: Utc::now(),
             },
@@ -457,6 +460,7 @@ mod tests {
         let all_completed = vec![IssueInfo {
             issue: completed_issue.clone(),
             completed: true,
+            archived: false,
             file_path: PathBuf::from("/test/completed/completed1.md"),
             created_at: Utc::now(),
         }];
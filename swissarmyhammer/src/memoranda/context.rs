@@ -0,0 +1,546 @@
+//! Ranked, token-budgeted memo context
+//!
+//! [`MemoStorage::get_all_context`](crate::memoranda::MemoStorage::get_all_context)
+//! concatenates memos newest first. For agents working on a task it is more
+//! useful to rank memos by how well they match the task and how recently they
+//! changed, then pack the best ones into a fixed token budget. Tokens are
+//! counted with a real tokenizer from the `tokenizers` crate, loaded in the
+//! background the first time it is needed; memos that no longer fit whole are
+//! cut short with a marker, and the [`MemoContext`] lists what was included
+//! and what was dropped.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::memoranda::{Memo, MemoId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokenizers::Tokenizer;
+
+/// Hugging Face model whose tokenizer counts context tokens by default
+pub const DEFAULT_TOKENIZER_MODEL: &str = "Xenova/gpt-4o";
+
+/// Environment variable naming a `tokenizer.json` file or a Hugging Face model
+pub const TOKENIZER_ENV_VAR: &str = "SWISSARMYHAMMER_TOKENIZER";
+
+/// Characters per token assumed when no tokenizer is available
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Time before loading the shared tokenizer is tried again after a failure
+const TOKENIZER_RETRY_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Days after which a memo's recency score halves
+const RECENCY_HALF_LIFE_DAYS: f32 = 30.0;
+
+/// Words too common to say anything about relevance
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "from", "into", "are", "was", "were", "has",
+    "have", "not", "but", "all", "any", "can", "how", "what", "when", "where", "which", "who",
+    "why", "our", "its", "use", "using",
+];
+
+/// Separator written between memos
+const MEMO_SEPARATOR: &str =
+    "\n\n================================================================================\n\n";
+
+static SHARED_COUNTER: OnceLock<Arc<TokenCounter>> = OnceLock::new();
+static SHARED_COUNTER_LOADING: AtomicBool = AtomicBool::new(false);
+static SHARED_COUNTER_FAILED_AT: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Counts and cuts text in tokens
+pub enum TokenCounter {
+    /// A Hugging Face tokenizer
+    Tokenizer(Box<Tokenizer>),
+    /// Roughly four characters per token, for when no tokenizer can be loaded
+    Estimate,
+}
+
+impl TokenCounter {
+    /// Load a tokenizer from a `tokenizer.json` file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let tokenizer = Tokenizer::from_file(path).map_err(|e| {
+            SwissArmyHammerError::Other(format!(
+                "Failed to load tokenizer from {}: {e}",
+                path.display()
+            ))
+        })?;
+        Ok(Self::Tokenizer(Box::new(tokenizer)))
+    }
+
+    /// Load the tokenizer of a Hugging Face model, downloading it if not cached
+    pub async fn from_pretrained(model_id: &str) -> Result<Self> {
+        let api = hf_hub::api::tokio::Api::new().map_err(|e| {
+            SwissArmyHammerError::Other(format!("Failed to access Hugging Face hub: {e}"))
+        })?;
+        let path = api
+            .model(model_id.to_string())
+            .get("tokenizer.json")
+            .await
+            .map_err(|e| {
+                SwissArmyHammerError::Other(format!(
+                    "Failed to download tokenizer for {model_id}: {e}"
+                ))
+            })?;
+        Self::from_file(&path)
+    }
+
+    /// The tokenizer shared by the whole process
+    ///
+    /// Uses `SWISSARMYHAMMER_TOKENIZER` if set, either a path to a
+    /// `tokenizer.json` file or a Hugging Face model id, and
+    /// [`DEFAULT_TOKENIZER_MODEL`] otherwise. The tokenizer is loaded, and
+    /// downloaded if needed, by a background task started on first use; until
+    /// it is ready, or when it cannot be loaded, tokens are estimated. A failed
+    /// load is tried again after a while rather than remembered.
+    pub fn shared() -> Arc<TokenCounter> {
+        if let Some(counter) = SHARED_COUNTER.get() {
+            return counter.clone();
+        }
+        Self::load_shared();
+        Arc::new(Self::Estimate)
+    }
+
+    /// Start loading the shared tokenizer unless it is loading or recently failed
+    fn load_shared() {
+        let recently_failed = SHARED_COUNTER_FAILED_AT
+            .lock()
+            .map(|failed_at| failed_at.is_some_and(|at| at.elapsed() < TOKENIZER_RETRY_INTERVAL))
+            .unwrap_or(false);
+        if recently_failed {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        if SHARED_COUNTER_LOADING.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        runtime.spawn(async {
+            let source = std::env::var(TOKENIZER_ENV_VAR)
+                .unwrap_or_else(|_| DEFAULT_TOKENIZER_MODEL.to_string());
+            let loaded = if Path::new(&source).is_file() {
+                tokio::task::spawn_blocking(move || Self::from_file(Path::new(&source)))
+                    .await
+                    .unwrap_or_else(|e| {
+                        Err(SwissArmyHammerError::Other(format!(
+                            "Tokenizer loading panicked: {e}"
+                        )))
+                    })
+            } else {
+                Self::from_pretrained(&source).await
+            };
+            match loaded {
+                Ok(counter) => {
+                    let _ = SHARED_COUNTER.set(Arc::new(counter));
+                }
+                Err(e) => {
+                    tracing::warn!("{}, estimating tokens for now", e);
+                    if let Ok(mut failed_at) = SHARED_COUNTER_FAILED_AT.lock() {
+                        *failed_at = Some(Instant::now());
+                    }
+                }
+            }
+            SHARED_COUNTER_LOADING.store(false, Ordering::SeqCst);
+        });
+    }
+
+    /// Number of tokens in `text`
+    pub fn count(&self, text: &str) -> usize {
+        match self {
+            Self::Tokenizer(tokenizer) => match tokenizer.encode(text, false) {
+                Ok(encoding) => encoding.len(),
+                Err(e) => {
+                    tracing::debug!("Tokenizer failed, estimating tokens: {}", e);
+                    text.len().div_ceil(CHARS_PER_TOKEN)
+                }
+            },
+            Self::Estimate => text.len().div_ceil(CHARS_PER_TOKEN),
+        }
+    }
+
+    /// The longest start of `text` that has at most `max_tokens` tokens
+    pub fn truncate<'a>(&self, text: &'a str, max_tokens: usize) -> &'a str {
+        let end = match self {
+            Self::Tokenizer(tokenizer) => match tokenizer.encode(text, false) {
+                Ok(encoding) if encoding.len() <= max_tokens => text.len(),
+                Ok(_) if max_tokens == 0 => 0,
+                Ok(encoding) => encoding.get_offsets()[max_tokens - 1].1,
+                Err(_) => max_tokens * CHARS_PER_TOKEN,
+            },
+            Self::Estimate => max_tokens * CHARS_PER_TOKEN,
+        };
+        let mut end = end.min(text.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        &text[..end]
+    }
+}
+
+/// Options for [`MemoStorage::ranked_context`](crate::memoranda::MemoStorage::ranked_context)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankedContextOptions {
+    /// Query or task description memos are ranked against; newest first without
+    pub query: Option<String>,
+    /// Token budget for the whole context; unlimited without
+    pub max_tokens: Option<usize>,
+    /// Share of a memo's score that comes from recency rather than relevance (default: 0.3)
+    pub recency_weight: f32,
+    /// Memos are only cut short if at least this many of their tokens fit (default: 64)
+    pub min_excerpt_tokens: usize,
+}
+
+impl Default for RankedContextOptions {
+    fn default() -> Self {
+        Self {
+            query: None,
+            max_tokens: None,
+            recency_weight: 0.3,
+            min_excerpt_tokens: 64,
+        }
+    }
+}
+
+/// How much of a memo made it into the context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextInclusion {
+    /// The whole memo
+    Full,
+    /// The start of the memo, followed by a truncation marker
+    Truncated,
+    /// Nothing; the memo did not fit
+    Dropped,
+}
+
+/// A memo considered for the context
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextEntry {
+    /// Memo identifier
+    pub id: MemoId,
+    /// Memo title
+    pub title: String,
+    /// Combined relevance and recency score (0.0-1.0)
+    pub score: f32,
+    /// Tokens of the whole rendered memo
+    pub tokens: usize,
+    /// Tokens the memo used in the context
+    pub included_tokens: usize,
+    /// How much of the memo was included
+    pub inclusion: ContextInclusion,
+}
+
+/// Memo context packed into a token budget
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoContext {
+    /// The context text
+    pub text: String,
+    /// Every memo considered, best first
+    pub entries: Vec<ContextEntry>,
+    /// Tokens used by the context text
+    pub used_tokens: usize,
+    /// The budget the context was packed into
+    pub max_tokens: Option<usize>,
+}
+
+impl MemoContext {
+    /// Memos included whole or in part
+    pub fn included(&self) -> impl Iterator<Item = &ContextEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.inclusion != ContextInclusion::Dropped)
+    }
+
+    /// Memos left out
+    pub fn dropped(&self) -> impl Iterator<Item = &ContextEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.inclusion == ContextInclusion::Dropped)
+    }
+}
+
+/// Lowercase words of a query that carry meaning
+fn query_terms(query: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    query
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .map(str::to_lowercase)
+        .filter(|term| term.chars().count() > 2 && !STOP_WORDS.contains(&term.as_str()))
+        .filter(|term| seen.insert(term.clone()))
+        .collect()
+}
+
+/// Share of query terms found in a memo, counting title matches double (0.0-1.0)
+fn relevance(memo: &Memo, terms: &[String]) -> f32 {
+    if terms.is_empty() {
+        return 0.0;
+    }
+    let title = memo.title.to_lowercase();
+    let content = memo.content.to_lowercase();
+    let tags: Vec<&str> = memo.tags.iter().map(String::as_str).collect();
+    let matched: f32 = terms
+        .iter()
+        .map(|term| {
+            let mut score = 0.0;
+            if title.contains(term.as_str()) || tags.contains(&term.as_str()) {
+                score += 2.0;
+            }
+            if content.contains(term.as_str()) {
+                score += 1.0;
+            }
+            score
+        })
+        .sum();
+    matched / (3.0 * terms.len() as f32)
+}
+
+/// Recency of a memo, halving every [`RECENCY_HALF_LIFE_DAYS`] (0.0-1.0)
+fn recency(memo: &Memo, now: DateTime<Utc>) -> f32 {
+    let age_days = (now - memo.updated_at).num_seconds().max(0) as f32 / 86_400.0;
+    0.5_f32.powf(age_days / RECENCY_HALF_LIFE_DAYS)
+}
+
+/// Rank memos by relevance to the query and recency, best first
+///
+/// Without a query memos are ranked by recency alone, newest first.
+pub fn rank_memos(
+    memos: Vec<Memo>,
+    options: &RankedContextOptions,
+    now: DateTime<Utc>,
+) -> Vec<(Memo, f32)> {
+    let terms = options
+        .query
+        .as_deref()
+        .map(query_terms)
+        .unwrap_or_default();
+    let recency_weight = if terms.is_empty() {
+        1.0
+    } else {
+        options.recency_weight.clamp(0.0, 1.0)
+    };
+
+    let mut ranked: Vec<(Memo, f32)> = memos
+        .into_iter()
+        .map(|memo| {
+            let score = (1.0 - recency_weight) * relevance(&memo, &terms)
+                + recency_weight * recency(&memo, now);
+            (memo, score)
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.1.total_cmp(&a.1)
+            .then_with(|| b.0.updated_at.cmp(&a.0.updated_at))
+    });
+    ranked
+}
+
+/// Heading written above a memo's content
+fn memo_header(memo: &Memo) -> String {
+    format!(
+        "=== {} (ID: {}) ===\nCreated: {}\nUpdated: {}\n\n",
+        memo.title,
+        memo.id,
+        memo.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        memo.updated_at.format("%Y-%m-%d %H:%M:%S UTC")
+    )
+}
+
+/// Marker closing a memo that was cut short
+fn truncation_marker(shown: usize, total: usize) -> String {
+    format!("\n\n[... truncated: {shown} of {total} tokens shown ...]")
+}
+
+/// Pack ranked memos into the token budget, best first
+///
+/// Memos that fit are included whole. A memo that does not fit is cut short if
+/// at least `min_excerpt_tokens` of it still fit, and dropped otherwise; later,
+/// smaller memos may still fit after a memo was dropped.
+pub fn pack_context(
+    ranked: Vec<(Memo, f32)>,
+    options: &RankedContextOptions,
+    counter: &TokenCounter,
+) -> MemoContext {
+    let separator_tokens = counter.count(MEMO_SEPARATOR);
+    let mut remaining = options.max_tokens.unwrap_or(usize::MAX);
+    let mut parts: Vec<String> = Vec::new();
+    let mut entries = Vec::with_capacity(ranked.len());
+
+    for (memo, score) in ranked {
+        let header = memo_header(&memo);
+        let full = format!("{header}{}", memo.content);
+        let tokens = counter.count(&full);
+        let overhead = if parts.is_empty() {
+            0
+        } else {
+            separator_tokens
+        };
+
+        let mut entry = ContextEntry {
+            id: memo.id.clone(),
+            title: memo.title.clone(),
+            score,
+            tokens,
+            included_tokens: 0,
+            inclusion: ContextInclusion::Dropped,
+        };
+
+        if overhead + tokens <= remaining {
+            entry.included_tokens = overhead + tokens;
+            entry.inclusion = ContextInclusion::Full;
+            parts.push(full);
+        } else {
+            // Room for the header, the marker and enough content to be useful
+            let fixed = overhead
+                + counter.count(&header)
+                + counter.count(&truncation_marker(tokens, tokens));
+            let room = remaining.saturating_sub(fixed);
+            if room >= options.min_excerpt_tokens {
+                let excerpt = counter.truncate(&memo.content, room);
+                // Do not end in the middle of a word
+                let excerpt = match memo.content[excerpt.len()..].chars().next() {
+                    Some(next) if !next.is_whitespace() => excerpt
+                        .rfind(char::is_whitespace)
+                        .map_or(excerpt, |end| &excerpt[..end]),
+                    _ => excerpt,
+                };
+                let shown = counter.count(excerpt);
+                let part = format!(
+                    "{header}{}{}",
+                    excerpt.trim_end(),
+                    truncation_marker(shown, tokens)
+                );
+                entry.included_tokens = overhead + counter.count(&part);
+                entry.inclusion = ContextInclusion::Truncated;
+                parts.push(part);
+            }
+        }
+
+        remaining = remaining.saturating_sub(entry.included_tokens);
+        entries.push(entry);
+    }
+
+    let text = parts.join(MEMO_SEPARATOR);
+    MemoContext {
+        used_tokens: counter.count(&text),
+        text,
+        entries,
+        max_tokens: options.max_tokens,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use std::str::FromStr;
+
+    /// Tokenizer splitting on words and punctuation, every word its own token
+    fn word_tokenizer() -> TokenCounter {
+        let json = r#"{
+            "version": "1.0",
+            "truncation": null,
+            "padding": null,
+            "added_tokens": [],
+            "normalizer": null,
+            "pre_tokenizer": {"type": "Whitespace"},
+            "post_processor": null,
+            "decoder": null,
+            "model": {"type": "WordLevel", "vocab": {"[UNK]": 0}, "unk_token": "[UNK]"}
+        }"#;
+        TokenCounter::Tokenizer(Box::new(Tokenizer::from_str(json).unwrap()))
+    }
+
+    fn memo(title: &str, content: &str, age_days: i64) -> Memo {
+        let mut memo = Memo::new(title.to_string(), content.to_string());
+        memo.id = MemoId::from_filename(title);
+        memo.updated_at = Utc::now() - Duration::days(age_days);
+        memo
+    }
+
+    #[test]
+    fn test_token_counter_counts_and_truncates() {
+        let counter = word_tokenizer();
+        assert_eq!(counter.count("Tag the release, then push."), 7);
+        assert_eq!(
+            counter.truncate("Tag the release, then push.", 3),
+            "Tag the release"
+        );
+        assert_eq!(counter.truncate("Tag the release", 10), "Tag the release");
+
+        let estimate = TokenCounter::Estimate;
+        assert_eq!(estimate.count("12345678"), 2);
+        assert_eq!(estimate.truncate("héllo wörld", 1), "hél");
+    }
+
+    #[test]
+    fn test_rank_memos_by_relevance_and_recency() {
+        let memos = vec![
+            memo("Groceries", "Milk and bread", 0),
+            memo("Release process", "Tag the release and deploy", 60),
+            memo("Deploy notes", "Deploy with the release script", 5),
+        ];
+        let options = RankedContextOptions {
+            query: Some("How do we deploy a release?".to_string()),
+            ..Default::default()
+        };
+        let ranked = rank_memos(memos.clone(), &options, Utc::now());
+        let titles: Vec<&str> = ranked.iter().map(|(m, _)| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["Deploy notes", "Release process", "Groceries"]);
+
+        // Without a query the newest memo comes first
+        let ranked = rank_memos(memos, &RankedContextOptions::default(), Utc::now());
+        assert_eq!(ranked[0].0.title, "Groceries");
+    }
+
+    #[test]
+    fn test_pack_context_truncates_and_drops_tail() {
+        let counter = word_tokenizer();
+        let long = "word ".repeat(200);
+        let ranked = vec![
+            (memo("First", "short note", 0), 0.9),
+            (memo("Second", &long, 0), 0.8),
+            (memo("Third", &long, 0), 0.7),
+        ];
+        let options = RankedContextOptions {
+            max_tokens: Some(150),
+            min_excerpt_tokens: 20,
+            ..Default::default()
+        };
+
+        let context = pack_context(ranked, &options, &counter);
+        let inclusions: Vec<ContextInclusion> =
+            context.entries.iter().map(|e| e.inclusion).collect();
+        assert_eq!(
+            inclusions,
+            vec![
+                ContextInclusion::Full,
+                ContextInclusion::Truncated,
+                ContextInclusion::Dropped
+            ]
+        );
+        assert!(context.used_tokens <= 150);
+        assert!(context.text.contains("short note"));
+        assert!(context.text.contains("[... truncated:"));
+        assert_eq!(context.included().count(), 2);
+        assert_eq!(context.dropped().next().unwrap().title, "Third");
+    }
+
+    #[test]
+    fn test_pack_context_without_budget_includes_everything() {
+        let ranked = vec![
+            (memo("First", "one", 0), 1.0),
+            (memo("Second", "two", 0), 0.5),
+        ];
+        let context = pack_context(
+            ranked,
+            &RankedContextOptions::default(),
+            &TokenCounter::Estimate,
+        );
+        assert_eq!(context.dropped().count(), 0);
+        assert!(context.text.find("one").unwrap() < context.text.find("two").unwrap());
+    }
+}
@@ -0,0 +1,649 @@
+//! Memo export and import
+//!
+//! Memos can be written out as an Obsidian vault, a plain markdown tree or a
+//! single JSON bundle, and read back from any of them. Markdown exports carry
+//! each memo's ID, title and timestamps in YAML front matter, merged with any
+//! front matter the memo content already has, so an export imports back into
+//! the same memos.
+//!
+//! Import skips memos that are already present, matched by ID, by title or by
+//! a hash of their content, and can be run as a dry run that only reports what
+//! it would do.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::frontmatter::parse_frontmatter;
+use crate::memoranda::{Memo, MemoId, MemoStorage};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Version of the JSON bundle layout
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Front matter keys written by export and removed again on import
+const RESERVED_KEYS: &[&str] = &["id", "title", "aliases", "created", "updated"];
+
+/// Layout of a memo export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoExportFormat {
+    /// One note per memo, named after its title, with `aliases` front matter
+    Obsidian,
+    /// One markdown file per memo with front matter
+    #[default]
+    Markdown,
+    /// A single JSON file holding every memo
+    Json,
+}
+
+impl fmt::Display for MemoExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MemoExportFormat::Obsidian => "obsidian",
+            MemoExportFormat::Markdown => "markdown",
+            MemoExportFormat::Json => "json",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for MemoExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "obsidian" => Ok(MemoExportFormat::Obsidian),
+            "markdown" | "md" => Ok(MemoExportFormat::Markdown),
+            "json" => Ok(MemoExportFormat::Json),
+            other => Err(format!(
+                "Unknown export format '{other}', expected one of obsidian, markdown, json"
+            )),
+        }
+    }
+}
+
+/// Every memo of a storage in one JSON document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoBundle {
+    /// Layout version, see [`BUNDLE_VERSION`]
+    pub version: u32,
+    /// When the bundle was written
+    pub exported_at: DateTime<Utc>,
+    /// The memos, oldest first
+    pub memos: Vec<Memo>,
+}
+
+/// What an export wrote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportReport {
+    /// Layout written
+    pub format: MemoExportFormat,
+    /// Files written, one per memo except for JSON bundles
+    pub files: Vec<PathBuf>,
+    /// Number of memos exported
+    pub memos: usize,
+}
+
+/// Why an imported memo was taken for one that already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateReason {
+    /// A memo with the same ID exists
+    Id,
+    /// A memo with the same title exists
+    Title,
+    /// A memo with the same content exists
+    Content,
+}
+
+impl fmt::Display for DuplicateReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DuplicateReason::Id => "id",
+            DuplicateReason::Title => "title",
+            DuplicateReason::Content => "content",
+        };
+        f.write_str(name)
+    }
+}
+
+/// What import did, or would do, with one memo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportOutcome {
+    /// The memo was created
+    Created(MemoId),
+    /// The memo matches an existing one and was skipped
+    Duplicate {
+        /// The memo it matches
+        existing: MemoId,
+        /// How it matched
+        reason: DuplicateReason,
+    },
+    /// The memo could not be read or stored
+    Failed(String),
+}
+
+/// One memo found by import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportEntry {
+    /// File the memo was read from
+    pub source: PathBuf,
+    /// Memo title
+    pub title: String,
+    /// What happened to it
+    pub outcome: ImportOutcome,
+}
+
+/// Result of an import
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Whether nothing was written
+    pub dry_run: bool,
+    /// Every memo found, in source order
+    pub entries: Vec<ImportEntry>,
+}
+
+impl ImportReport {
+    /// Memos created, or that would be created in a dry run
+    pub fn created(&self) -> impl Iterator<Item = &ImportEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.outcome, ImportOutcome::Created(_)))
+    }
+
+    /// Memos skipped as duplicates
+    pub fn duplicates(&self) -> impl Iterator<Item = &ImportEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.outcome, ImportOutcome::Duplicate { .. }))
+    }
+
+    /// Memos that could not be imported
+    pub fn failed(&self) -> impl Iterator<Item = &ImportEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.outcome, ImportOutcome::Failed(_)))
+    }
+}
+
+fn format_timestamp(timestamp: &DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// File name for a memo title, without extension
+///
+/// Obsidian also refuses `#`, `^`, `[`, `]` and `|` in note names, since they
+/// have a meaning inside links.
+fn export_file_stem(title: &str, format: MemoExportFormat) -> String {
+    let stem: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            '#' | '^' | '[' | ']' if format == MemoExportFormat::Obsidian => '_',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let stem = stem.trim().trim_start_matches('.').to_string();
+    if stem.is_empty() {
+        "untitled".to_string()
+    } else {
+        stem
+    }
+}
+
+/// Render a memo as markdown with its metadata in the front matter
+pub fn render_markdown(memo: &Memo, format: MemoExportFormat) -> Result<String> {
+    let mut front_matter = serde_yaml::Mapping::new();
+    front_matter.insert("id".into(), memo.id.as_str().into());
+    front_matter.insert("title".into(), memo.title.clone().into());
+    if format == MemoExportFormat::Obsidian {
+        front_matter.insert(
+            "aliases".into(),
+            serde_yaml::Value::Sequence(vec![memo.title.clone().into()]),
+        );
+    }
+    front_matter.insert("created".into(), format_timestamp(&memo.created_at).into());
+    front_matter.insert("updated".into(), format_timestamp(&memo.updated_at).into());
+
+    // Keep the memo's own front matter, such as `tags`, in the same block
+    let mut body = memo.content.as_str();
+    let parsed = parse_frontmatter(&memo.content).ok();
+    if let Some(parsed) = &parsed {
+        if let Some(serde_json::Value::Object(fields)) = &parsed.metadata {
+            for (key, value) in fields {
+                if !RESERVED_KEYS.contains(&key.as_str()) {
+                    front_matter.insert(key.as_str().into(), serde_yaml::to_value(value)?);
+                }
+            }
+            body = &parsed.content;
+        }
+    }
+
+    Ok(format!(
+        "---\n{}---\n{body}",
+        serde_yaml::to_string(&front_matter)?
+    ))
+}
+
+/// Write `memos` to `destination` in the given format
+///
+/// Markdown and Obsidian exports write one file per memo into the
+/// `destination` directory; JSON exports write a single [`MemoBundle`] file.
+pub fn export_memos(
+    memos: &[Memo],
+    format: MemoExportFormat,
+    destination: &Path,
+) -> Result<ExportReport> {
+    let mut memos = memos.to_vec();
+    memos.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+
+    if format == MemoExportFormat::Json {
+        if let Some(parent) = destination.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let bundle = MemoBundle {
+            version: BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            memos,
+        };
+        std::fs::write(destination, serde_json::to_string_pretty(&bundle)?)?;
+        return Ok(ExportReport {
+            format,
+            files: vec![destination.to_path_buf()],
+            memos: bundle.memos.len(),
+        });
+    }
+
+    std::fs::create_dir_all(destination)?;
+    let mut used: HashMap<String, usize> = HashMap::new();
+    let mut files = Vec::with_capacity(memos.len());
+    for memo in &memos {
+        let stem = export_file_stem(&memo.title, format);
+        let count = used.entry(stem.to_lowercase()).or_insert(0);
+        *count += 1;
+        let name = match *count {
+            1 => format!("{stem}.md"),
+            n => format!("{stem} ({n}).md"),
+        };
+        let path = destination.join(name);
+        std::fs::write(&path, render_markdown(memo, format)?)?;
+        files.push(path);
+    }
+
+    Ok(ExportReport {
+        format,
+        memos: memos.len(),
+        files,
+    })
+}
+
+fn parse_timestamp(
+    fields: &serde_json::Map<String, serde_json::Value>,
+    key: &str,
+) -> Option<DateTime<Utc>> {
+    fields
+        .get(key)
+        .and_then(|value| value.as_str())
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+/// Read a memo from an exported, or hand-written, markdown file
+///
+/// The title comes from the `title` front matter, the first of `aliases`, or
+/// the file name. Front matter keys other than the ones export writes stay in
+/// the content.
+pub fn parse_markdown(path: &Path, text: &str) -> Result<Memo> {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "untitled".to_string());
+    let modified = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now());
+
+    let parsed = parse_frontmatter(text)?;
+    let Some(serde_json::Value::Object(fields)) = parsed.metadata else {
+        let mut memo = Memo::new(stem, text.to_string());
+        memo.created_at = modified;
+        memo.updated_at = modified;
+        return Ok(memo);
+    };
+
+    let title = fields
+        .get("title")
+        .and_then(|value| value.as_str())
+        .or_else(|| {
+            fields
+                .get("aliases")
+                .and_then(|value| value.as_array())
+                .and_then(|aliases| aliases.first())
+                .and_then(|alias| alias.as_str())
+        })
+        .map(str::to_string)
+        .unwrap_or(stem);
+
+    let rest: serde_yaml::Mapping = fields
+        .iter()
+        .filter(|(key, _)| !RESERVED_KEYS.contains(&key.as_str()))
+        .map(|(key, value)| Ok((key.as_str().into(), serde_yaml::to_value(value)?)))
+        .collect::<Result<_>>()?;
+    let content = if rest.is_empty() {
+        parsed.content
+    } else {
+        format!(
+            "---\n{}---\n{}",
+            serde_yaml::to_string(&rest)?,
+            parsed.content
+        )
+    };
+
+    let mut memo = Memo::new(title, content);
+    if let Some(id) = fields
+        .get("id")
+        .and_then(|value| value.as_str())
+        .and_then(|id| MemoId::from_string(id.to_string()).ok())
+    {
+        memo.id = id;
+    }
+    memo.updated_at = parse_timestamp(&fields, "updated").unwrap_or(modified);
+    memo.created_at = parse_timestamp(&fields, "created").unwrap_or(memo.updated_at);
+    Ok(memo)
+}
+
+/// Memos found at `source`, with the file each came from
+///
+/// `source` is either a JSON bundle or a directory of markdown files, such as
+/// an Obsidian vault. Hidden directories like `.obsidian` are skipped. A file
+/// that cannot be read is returned as an error in its place.
+pub fn read_import_source(source: &Path) -> Result<Vec<(PathBuf, Result<Memo>)>> {
+    if source.is_file() {
+        let text = std::fs::read_to_string(source)?;
+        if source.extension().is_some_and(|ext| ext == "json") {
+            let bundle: MemoBundle = serde_json::from_str(&text).map_err(|e| {
+                SwissArmyHammerError::Other(format!(
+                    "Invalid memo bundle {}: {e}",
+                    source.display()
+                ))
+            })?;
+            if bundle.version > BUNDLE_VERSION {
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Memo bundle {} has version {}, newer than the supported {BUNDLE_VERSION}",
+                    source.display(),
+                    bundle.version
+                )));
+            }
+            return Ok(bundle
+                .memos
+                .into_iter()
+                .map(|mut memo| {
+                    memo.refresh_links();
+                    (source.to_path_buf(), Ok(memo))
+                })
+                .collect());
+        }
+        return Ok(vec![(source.to_path_buf(), parse_markdown(source, &text))]);
+    }
+
+    if !source.is_dir() {
+        return Err(SwissArmyHammerError::Other(format!(
+            "Import source {} does not exist",
+            source.display()
+        )));
+    }
+
+    let mut paths: Vec<PathBuf> = walkdir::WalkDir::new(source)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    paths.sort();
+
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let memo = std::fs::read_to_string(&path)
+                .map_err(SwissArmyHammerError::from)
+                .and_then(|text| parse_markdown(&path, &text));
+            (path, memo)
+        })
+        .collect())
+}
+
+fn content_hash(content: &str) -> String {
+    format!("{:x}", md5::compute(content.trim()))
+}
+
+/// Known memos by ID, title and content hash
+#[derive(Default)]
+struct DuplicateIndex {
+    ids: HashMap<MemoId, MemoId>,
+    titles: HashMap<String, MemoId>,
+    hashes: HashMap<String, MemoId>,
+}
+
+impl DuplicateIndex {
+    fn insert(&mut self, memo: &Memo) {
+        self.ids.insert(memo.id.clone(), memo.id.clone());
+        self.titles
+            .entry(memo.title.trim().to_lowercase())
+            .or_insert_with(|| memo.id.clone());
+        self.hashes
+            .entry(content_hash(&memo.content))
+            .or_insert_with(|| memo.id.clone());
+    }
+
+    fn find(&self, memo: &Memo) -> Option<(MemoId, DuplicateReason)> {
+        if let Some(existing) = self.ids.get(&memo.id) {
+            return Some((existing.clone(), DuplicateReason::Id));
+        }
+        if let Some(existing) = self.titles.get(&memo.title.trim().to_lowercase()) {
+            return Some((existing.clone(), DuplicateReason::Title));
+        }
+        self.hashes
+            .get(&content_hash(&memo.content))
+            .map(|existing| (existing.clone(), DuplicateReason::Content))
+    }
+}
+
+/// Import the memos at `source` into `storage`
+///
+/// Memos matching an existing memo, or one imported earlier in the same run,
+/// by ID, title (ignoring case) or content are skipped. With `dry_run` nothing
+/// is written and the report says what would have happened.
+pub async fn import_memos(
+    storage: &dyn MemoStorage,
+    source: &Path,
+    dry_run: bool,
+) -> Result<ImportReport> {
+    let mut known = DuplicateIndex::default();
+    for memo in storage.list_memos().await? {
+        known.insert(&memo);
+    }
+
+    let mut report = ImportReport {
+        dry_run,
+        entries: Vec::new(),
+    };
+    for (path, memo) in read_import_source(source)? {
+        let memo = match memo {
+            Ok(memo) => memo,
+            Err(e) => {
+                let title = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                report.entries.push(ImportEntry {
+                    source: path,
+                    title,
+                    outcome: ImportOutcome::Failed(e.to_string()),
+                });
+                continue;
+            }
+        };
+
+        let outcome = if let Some((existing, reason)) = known.find(&memo) {
+            ImportOutcome::Duplicate { existing, reason }
+        } else if dry_run {
+            known.insert(&memo);
+            ImportOutcome::Created(memo.id.clone())
+        } else {
+            match storage.import_memo(memo.clone()).await {
+                Ok(imported) => {
+                    known.insert(&memo);
+                    known.insert(&imported);
+                    ImportOutcome::Created(imported.id)
+                }
+                Err(e) => ImportOutcome::Failed(e.to_string()),
+            }
+        };
+        tracing::debug!("Import of memo '{}': {:?}", memo.title, outcome);
+        report.entries.push(ImportEntry {
+            source: path,
+            title: memo.title,
+            outcome,
+        });
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memoranda::mock_storage::MockMemoStorage;
+    use tempfile::TempDir;
+
+    async fn storage_with_memos() -> MockMemoStorage {
+        let storage = MockMemoStorage::new();
+        storage
+            .create_memo(
+                "Auth: Research".to_string(),
+                "---\ntags:\n- auth\n---\nSee [[Plan]]\n".to_string(),
+            )
+            .await
+            .unwrap();
+        storage
+            .create_memo("Plan".to_string(), "Ship it #release\n".to_string())
+            .await
+            .unwrap();
+        storage
+    }
+
+    #[test]
+    fn test_export_format_from_str() {
+        assert_eq!(
+            "Obsidian".parse::<MemoExportFormat>().unwrap(),
+            MemoExportFormat::Obsidian
+        );
+        assert_eq!(
+            "md".parse::<MemoExportFormat>().unwrap(),
+            MemoExportFormat::Markdown
+        );
+        assert!("html".parse::<MemoExportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_render_and_parse_markdown() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut memo = Memo::new(
+            "Auth: Research".to_string(),
+            "---\ntags:\n- auth\n---\nBody\n".to_string(),
+        );
+        memo.refresh_links();
+
+        let text = render_markdown(&memo, MemoExportFormat::Obsidian).unwrap();
+        assert!(text.starts_with(&format!("---\nid: {}\n", memo.id)));
+        assert!(text.contains("\naliases:\n- "));
+        assert!(text.contains("tags:\n- auth\n---\nBody\n"));
+
+        let path = temp_dir.path().join("Auth_ Research.md");
+        std::fs::write(&path, &text).unwrap();
+        assert_eq!(parse_markdown(&path, &text).unwrap(), memo);
+
+        // Plain markdown without front matter is titled after its file
+        let plain = parse_markdown(&temp_dir.path().join("Notes.md"), "Hello").unwrap();
+        assert_eq!(plain.title, "Notes");
+        assert_eq!(plain.content, "Hello");
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let source = storage_with_memos().await;
+        let memos = source.list_memos().await.unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        for format in [
+            MemoExportFormat::Obsidian,
+            MemoExportFormat::Markdown,
+            MemoExportFormat::Json,
+        ] {
+            let destination = match format {
+                MemoExportFormat::Json => temp_dir.path().join("memos.json"),
+                _ => temp_dir.path().join(format.to_string()),
+            };
+            let report = export_memos(&memos, format, &destination).unwrap();
+            assert_eq!(report.memos, 2);
+
+            let target = MockMemoStorage::new();
+            let report = import_memos(&target, &destination, false).await.unwrap();
+            assert_eq!(report.created().count(), 2, "{format}");
+
+            let mut imported = target.list_memos().await.unwrap();
+            let mut expected = memos.clone();
+            imported.sort_by(|a, b| a.id.cmp(&b.id));
+            expected.sort_by(|a, b| a.id.cmp(&b.id));
+            assert_eq!(imported, expected, "{format}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_detects_duplicates_and_dry_run() {
+        let storage = storage_with_memos().await;
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("plan.md"), "Something else").unwrap();
+        std::fs::write(temp_dir.path().join("Copy.md"), "Ship it #release\n").unwrap();
+        std::fs::write(temp_dir.path().join("New.md"), "Fresh notes").unwrap();
+        std::fs::write(temp_dir.path().join("Again.md"), "Fresh notes").unwrap();
+        std::fs::create_dir(temp_dir.path().join(".obsidian")).unwrap();
+        std::fs::write(temp_dir.path().join(".obsidian/skip.md"), "x").unwrap();
+
+        let report = import_memos(&storage, temp_dir.path(), true).await.unwrap();
+        assert!(report.dry_run);
+        let outcomes: Vec<(&str, Option<DuplicateReason>)> = report
+            .entries
+            .iter()
+            .map(|entry| {
+                let reason = match &entry.outcome {
+                    ImportOutcome::Duplicate { reason, .. } => Some(*reason),
+                    _ => None,
+                };
+                (entry.title.as_str(), reason)
+            })
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("Again", None),
+                ("Copy", Some(DuplicateReason::Content)),
+                ("New", Some(DuplicateReason::Content)),
+                ("plan", Some(DuplicateReason::Title)),
+            ]
+        );
+        assert_eq!(storage.memo_count().await, 2);
+
+        let report = import_memos(&storage, temp_dir.path(), false)
+            .await
+            .unwrap();
+        assert_eq!(report.created().count(), 1);
+        assert_eq!(report.duplicates().count(), 3);
+        assert_eq!(storage.memo_count().await, 3);
+    }
+}
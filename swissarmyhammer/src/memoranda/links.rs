@@ -0,0 +1,593 @@
+//! Tags and wiki-links between memos
+//!
+//! Memo content can reference other memos, issues and code with `[[wiki-links]]`:
+//!
+//! - `[[Meeting Notes]]` or `[[memo:Meeting Notes]]` links to a memo by title or ID
+//! - `[[issue:fix_login]]` links to an issue by name
+//! - `[[file:src/auth.rs]]` (or `[[code:src/auth.rs:42]]`) links to a path in the repository
+//! - `[[memo:Meeting Notes|the kickoff]]` gives the link a label
+//!
+//! Tags come from a `tags` list in the memo's front matter and from inline
+//! `#hashtags`. Links and tags inside code blocks and inline code are ignored.
+//!
+//! Links are parsed whenever a memo is saved and collected in a
+//! [`MemoLinkIndex`], which answers backlink queries such as "which memos
+//! reference memo X" or "which memos reference file Y".
+
+use crate::frontmatter::parse_frontmatter;
+use crate::memoranda::{Memo, MemoId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fmt;
+
+/// What a wiki-link points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    /// Another memo, by title or ID
+    Memo,
+    /// An issue, by name
+    Issue,
+    /// A file or directory in the repository
+    File,
+}
+
+impl fmt::Display for LinkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LinkKind::Memo => "memo",
+            LinkKind::Issue => "issue",
+            LinkKind::File => "file",
+        })
+    }
+}
+
+impl std::str::FromStr for LinkKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "memo" => Ok(LinkKind::Memo),
+            "issue" => Ok(LinkKind::Issue),
+            "file" | "code" => Ok(LinkKind::File),
+            other => Err(format!(
+                "Unknown link kind '{other}', expected one of memo, issue, file"
+            )),
+        }
+    }
+}
+
+/// A `[[wiki-link]]` found in memo content
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MemoLink {
+    /// What the link points at
+    pub kind: LinkKind,
+    /// Memo title or ID, issue name, or repository path
+    pub target: String,
+    /// Text shown instead of the target
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl MemoLink {
+    /// Parse the text between `[[` and `]]`
+    ///
+    /// A prefix other than `memo:`, `issue:`, `file:` or `code:` is part of
+    /// the target, so `[[Notes: week 1]]` links to a memo of that title.
+    fn parse(inner: &str) -> Option<Self> {
+        let (target, label) = match inner.split_once('|') {
+            Some((target, label)) => (target, Some(label.trim().to_string())),
+            None => (inner, None),
+        };
+
+        let (kind, target) = match target.split_once(':') {
+            Some((prefix, rest)) => match prefix.parse::<LinkKind>() {
+                Ok(kind) => (kind, rest),
+                Err(_) => (LinkKind::Memo, target),
+            },
+            None => (LinkKind::Memo, target),
+        };
+
+        let target = match kind {
+            LinkKind::File => normalize_path(target),
+            _ => target.trim().to_string(),
+        };
+        if target.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            kind,
+            target,
+            label: label.filter(|label| !label.is_empty()),
+        })
+    }
+}
+
+impl fmt::Display for MemoLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[[{}:{}", self.kind, self.target)?;
+        if let Some(label) = &self.label {
+            write!(f, "|{label}")?;
+        }
+        f.write_str("]]")
+    }
+}
+
+/// Normalize a repository path so equivalent spellings compare equal
+fn normalize_path(path: &str) -> String {
+    let path = path.trim().replace('\\', "/");
+    let mut path = path.as_str();
+    while let Some(rest) = path.strip_prefix("./") {
+        path = rest;
+    }
+    path.trim_end_matches('/').to_string()
+}
+
+/// Lines of `content` outside fenced code blocks, with inline code removed
+fn prose_lines(content: &str) -> Vec<String> {
+    let mut in_fence = false;
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        // Every other segment between backticks is inline code
+        lines.push(line.split('`').step_by(2).collect::<Vec<_>>().join(" "));
+    }
+    lines
+}
+
+/// The `[[wiki-links]]` in memo content, in order of first appearance
+pub fn parse_links(content: &str) -> Vec<MemoLink> {
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+    for line in prose_lines(content) {
+        let mut rest = line.as_str();
+        while let Some(start) = rest.find("[[") {
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("]]") else {
+                break;
+            };
+            if let Some(link) = MemoLink::parse(&after[..end]) {
+                if seen.insert((link.kind, link.target.clone())) {
+                    links.push(link);
+                }
+            }
+            rest = &after[end + 2..];
+        }
+    }
+    links
+}
+
+/// The tags of memo content: front matter `tags` followed by inline `#hashtags`
+///
+/// Tags are lowercased and listed once each, in order of first appearance.
+/// A hashtag starts at the beginning of a line or after whitespace and must
+/// not start with a digit, so markdown headings and `#123` references are
+/// not tags.
+pub fn parse_tags(content: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut push = |tag: &str| {
+        let tag = tag.trim().trim_start_matches('#').to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    };
+
+    let body = match parse_frontmatter(content) {
+        Ok(frontmatter) => {
+            if let Some(serde_json::Value::Object(fields)) = &frontmatter.metadata {
+                match fields.get("tags") {
+                    Some(serde_json::Value::String(tag)) => tag.split(',').for_each(&mut push),
+                    Some(serde_json::Value::Array(items)) => items
+                        .iter()
+                        .filter_map(|item| item.as_str())
+                        .for_each(&mut push),
+                    _ => {}
+                }
+            }
+            frontmatter.content
+        }
+        Err(_) => content.to_string(),
+    };
+
+    let is_tag_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '/');
+    for line in prose_lines(&body) {
+        let mut previous = None;
+        for (i, c) in line.char_indices() {
+            if c == '#' && previous.is_none_or(char::is_whitespace) {
+                let tag: String = line[i + 1..]
+                    .chars()
+                    .take_while(|&c| is_tag_char(c))
+                    .collect();
+                if tag.chars().next().is_some_and(char::is_alphabetic) {
+                    push(tag.trim_end_matches(['/', '-']));
+                }
+            }
+            previous = Some(c);
+        }
+    }
+
+    tags
+}
+
+/// Link and tag data the index keeps for one memo
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedMemo {
+    /// Memo title
+    pub title: String,
+    /// When the memo was last modified, to detect changes made elsewhere
+    pub updated_at: DateTime<Utc>,
+    /// Tags of the memo
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Links out of the memo
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<MemoLink>,
+}
+
+/// A memo linking to a target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Backlink {
+    /// The memo holding the link
+    pub memo_id: MemoId,
+    /// Title of that memo
+    pub title: String,
+    /// The link itself
+    pub link: MemoLink,
+}
+
+/// Links in both directions for one memo
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoLinks {
+    /// Links out of the memo
+    pub outgoing: Vec<MemoLink>,
+    /// Links from other memos to this one
+    pub backlinks: Vec<Backlink>,
+}
+
+/// Tags and links of every memo, for backlink and neighbourhood queries
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoLinkIndex {
+    memos: BTreeMap<MemoId, IndexedMemo>,
+}
+
+impl MemoLinkIndex {
+    /// Index of the given memos
+    pub fn from_memos(memos: &[Memo]) -> Self {
+        let mut index = Self::default();
+        for memo in memos {
+            index.update(memo);
+        }
+        index
+    }
+
+    /// Add or replace the entry for `memo`
+    pub fn update(&mut self, memo: &Memo) {
+        self.memos.insert(
+            memo.id.clone(),
+            IndexedMemo {
+                title: memo.title.clone(),
+                updated_at: memo.updated_at,
+                tags: memo.tags.clone(),
+                links: memo.links.clone(),
+            },
+        );
+    }
+
+    /// Remove the entry for a deleted memo
+    pub fn remove(&mut self, id: &MemoId) {
+        self.memos.remove(id);
+    }
+
+    /// Bring the index in line with the current memos
+    ///
+    /// Entries of memos that changed since they were indexed are replaced and
+    /// entries of memos that no longer exist are removed. Returns whether
+    /// anything changed.
+    pub fn reconcile(&mut self, memos: &[Memo]) -> bool {
+        let mut changed = false;
+        let current: HashSet<&MemoId> = memos.iter().map(|memo| &memo.id).collect();
+        self.memos.retain(|id, _| {
+            let keep = current.contains(id);
+            changed |= !keep;
+            keep
+        });
+        for memo in memos {
+            let stale = self.memos.get(&memo.id).is_none_or(|entry| {
+                entry.updated_at != memo.updated_at || entry.title != memo.title
+            });
+            if stale {
+                self.update(memo);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Number of indexed memos
+    pub fn len(&self) -> usize {
+        self.memos.len()
+    }
+
+    /// Whether no memos are indexed
+    pub fn is_empty(&self) -> bool {
+        self.memos.is_empty()
+    }
+
+    /// The entry for a memo
+    pub fn get(&self, id: &MemoId) -> Option<&IndexedMemo> {
+        self.memos.get(id)
+    }
+
+    /// The memo a memo link target refers to
+    ///
+    /// Targets match a memo ID exactly, or a title ignoring case.
+    pub fn resolve_memo(&self, target: &str) -> Option<MemoId> {
+        let target = target.trim();
+        if let Some((id, _)) = self.memos.get_key_value(&MemoId::from_filename(target)) {
+            return Some(id.clone());
+        }
+        let lower = target.to_lowercase();
+        self.memos
+            .iter()
+            .find(|(_, entry)| entry.title.to_lowercase() == lower)
+            .map(|(id, _)| id.clone())
+    }
+
+    /// Every link pointing at `target`
+    ///
+    /// Memo targets are resolved by ID or title, so `[[Meeting Notes]]` and a
+    /// link by ID are both backlinks of that memo. File targets also match
+    /// links to a line in the file, such as `[[file:src/auth.rs:42]]`.
+    pub fn backlinks(&self, kind: LinkKind, target: &str) -> Vec<Backlink> {
+        let matches: Box<dyn Fn(&str) -> bool + '_> = match kind {
+            LinkKind::Memo => {
+                let resolved = self.resolve_memo(target);
+                let lower = target.trim().to_lowercase();
+                Box::new(move |candidate: &str| match &resolved {
+                    Some(id) => self.resolve_memo(candidate).as_ref() == Some(id),
+                    None => candidate.to_lowercase() == lower,
+                })
+            }
+            LinkKind::Issue => {
+                let name = target.trim().trim_end_matches(".md").to_string();
+                Box::new(move |candidate: &str| candidate.trim_end_matches(".md") == name)
+            }
+            LinkKind::File => {
+                let path = normalize_path(target);
+                Box::new(move |candidate: &str| {
+                    candidate == path
+                        || candidate
+                            .strip_prefix(path.as_str())
+                            .is_some_and(|rest| rest.starts_with([':', '#']))
+                })
+            }
+        };
+
+        self.memos
+            .iter()
+            .flat_map(|(id, entry)| {
+                entry
+                    .links
+                    .iter()
+                    .filter(|link| link.kind == kind && matches(&link.target))
+                    .map(|link| Backlink {
+                        memo_id: id.clone(),
+                        title: entry.title.clone(),
+                        link: link.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Outgoing links and backlinks of a memo
+    pub fn links_of(&self, id: &MemoId) -> Option<MemoLinks> {
+        let entry = self.memos.get(id)?;
+        Some(MemoLinks {
+            outgoing: entry.links.clone(),
+            backlinks: self
+                .backlinks(LinkKind::Memo, id.as_str())
+                .into_iter()
+                .filter(|backlink| &backlink.memo_id != id)
+                .collect(),
+        })
+    }
+
+    /// Every tag with the number of memos carrying it
+    pub fn tags(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for entry in self.memos.values() {
+            for tag in &entry.tags {
+                *counts.entry(tag.clone()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Memos carrying `tag`, ignoring case and a leading `#`
+    pub fn memos_with_tag(&self, tag: &str) -> Vec<MemoId> {
+        let tag = tag.trim().trim_start_matches('#').to_lowercase();
+        self.memos
+            .iter()
+            .filter(|(_, entry)| entry.tags.contains(&tag))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// A memo and the memos within `depth` links of it, nearest first
+    ///
+    /// Links are followed in both directions, so the neighbourhood holds the
+    /// memos a memo links to as well as the memos linking to it.
+    pub fn neighbourhood(&self, id: &MemoId, depth: usize) -> Vec<MemoId> {
+        if !self.memos.contains_key(id) {
+            return Vec::new();
+        }
+
+        let mut visited: HashSet<MemoId> = HashSet::from([id.clone()]);
+        let mut order = vec![id.clone()];
+        let mut queue = VecDeque::from([(id.clone(), 0)]);
+        while let Some((current, distance)) = queue.pop_front() {
+            if distance == depth {
+                continue;
+            }
+            let outgoing = self.memos[&current]
+                .links
+                .iter()
+                .filter(|link| link.kind == LinkKind::Memo)
+                .filter_map(|link| self.resolve_memo(&link.target));
+            let incoming = self
+                .backlinks(LinkKind::Memo, current.as_str())
+                .into_iter()
+                .map(|backlink| backlink.memo_id);
+            let neighbours: Vec<MemoId> = outgoing.chain(incoming).collect();
+            for neighbour in neighbours {
+                if visited.insert(neighbour.clone()) {
+                    order.push(neighbour.clone());
+                    queue.push_back((neighbour, distance + 1));
+                }
+            }
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memo(title: &str, content: &str) -> Memo {
+        let mut memo = Memo::new(title.to_string(), content.to_string());
+        memo.id = MemoId::from_filename(title);
+        memo
+    }
+
+    #[test]
+    fn test_parse_links() {
+        let links = parse_links(
+            "See [[Auth Research]] and [[issue:fix_login|the bug]].\n\
+             Code in [[file:./src/auth.rs]] and [[code:src/auth.rs:42]], notes in [[Notes: week 1]].\n\
+             ```\n[[ignored]]\n```\n\
+             Inline `[[also ignored]]` and a repeat [[memo:Auth Research]].",
+        );
+        assert_eq!(
+            links,
+            vec![
+                MemoLink {
+                    kind: LinkKind::Memo,
+                    target: "Auth Research".to_string(),
+                    label: None,
+                },
+                MemoLink {
+                    kind: LinkKind::Issue,
+                    target: "fix_login".to_string(),
+                    label: Some("the bug".to_string()),
+                },
+                MemoLink {
+                    kind: LinkKind::File,
+                    target: "src/auth.rs".to_string(),
+                    label: None,
+                },
+                MemoLink {
+                    kind: LinkKind::File,
+                    target: "src/auth.rs:42".to_string(),
+                    label: None,
+                },
+                MemoLink {
+                    kind: LinkKind::Memo,
+                    target: "Notes: week 1".to_string(),
+                    label: None,
+                },
+            ]
+        );
+        assert!(parse_links("[[]] and [[issue:]]").is_empty());
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        let tags = parse_tags(
+            "---\ntags: [Research, auth]\n---\n# Heading\n\n#auth flow for #OAuth2, see issue #42.\n\
+             ```\n#include <stdio.h>\n```\nmid#word and `#code` are not tags #todo/later",
+        );
+        assert_eq!(tags, vec!["research", "auth", "oauth2", "todo/later"]);
+        assert_eq!(parse_tags("tags: not front matter #x"), vec!["x"]);
+    }
+
+    #[test]
+    fn test_backlinks() {
+        let index = MemoLinkIndex::from_memos(&[
+            memo("Auth Research", "OAuth notes in [[file:src/auth.rs]]"),
+            memo(
+                "Plan",
+                "Based on [[auth research]], fixes [[issue:fix_login]]",
+            ),
+            memo(
+                "Review",
+                "[[memo:Auth Research|research]] and [[file:src/auth.rs:10]]",
+            ),
+        ]);
+
+        let memo_backlinks: Vec<String> = index
+            .backlinks(LinkKind::Memo, "Auth Research")
+            .into_iter()
+            .map(|backlink| backlink.title)
+            .collect();
+        assert_eq!(memo_backlinks, vec!["Plan", "Review"]);
+
+        let file_backlinks: Vec<String> = index
+            .backlinks(LinkKind::File, "./src/auth.rs")
+            .into_iter()
+            .map(|backlink| backlink.title)
+            .collect();
+        assert_eq!(file_backlinks, vec!["Auth Research", "Review"]);
+        assert!(index.backlinks(LinkKind::File, "src/auth").is_empty());
+
+        assert_eq!(index.backlinks(LinkKind::Issue, "fix_login.md").len(), 1);
+
+        let links = index
+            .links_of(&MemoId::from_filename("Auth Research"))
+            .unwrap();
+        assert_eq!(links.outgoing.len(), 1);
+        assert_eq!(links.backlinks.len(), 2);
+    }
+
+    #[test]
+    fn test_tags_and_neighbourhood() {
+        let mut index = MemoLinkIndex::from_memos(&[
+            memo("A", "#design links to [[B]]"),
+            memo("B", "#design links to [[C]]"),
+            memo("C", "leaf"),
+            memo("D", "links to [[A]] #misc"),
+            memo("E", "unrelated"),
+        ]);
+
+        assert_eq!(index.tags()["design"], 2);
+        assert_eq!(
+            index.memos_with_tag("#Design"),
+            vec![MemoId::from_filename("A"), MemoId::from_filename("B")]
+        );
+
+        let ids = |ids: Vec<MemoId>| ids.into_iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        let a = MemoId::from_filename("A");
+        assert_eq!(ids(index.neighbourhood(&a, 0)), vec!["A"]);
+        assert_eq!(ids(index.neighbourhood(&a, 1)), vec!["A", "B", "D"]);
+        assert_eq!(ids(index.neighbourhood(&a, 2)), vec!["A", "B", "D", "C"]);
+        assert!(index
+            .neighbourhood(&MemoId::from_filename("missing"), 2)
+            .is_empty());
+
+        // Deleting a memo drops it and its links
+        let remaining = vec![memo("A", "#design links to [[B]]"), memo("B", "")];
+        assert!(index.reconcile(&remaining));
+        assert_eq!(index.len(), 2);
+        assert!(index.backlinks(LinkKind::Memo, "A").is_empty());
+    }
+}
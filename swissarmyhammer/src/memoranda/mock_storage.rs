@@ -36,9 +36,10 @@
 //! ```
 
 use crate::error::{Result, SwissArmyHammerError};
+use crate::memoranda::versions::{self, DEFAULT_TRASH_RETENTION_DAYS};
 use crate::memoranda::{
-    AdvancedMemoSearchEngine, ContextOptions, Memo, MemoId, MemoStorage, SearchOptions,
-    SearchResult,
+    AdvancedMemoSearchEngine, ContextOptions, Memo, MemoChange, MemoId, MemoRevision, MemoStorage,
+    SearchOptions, SearchResult, TrashedMemo,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -54,6 +55,12 @@ pub struct MockMemoStorage {
     /// In-memory storage for memos, keyed by memo ID
     storage: Arc<RwLock<HashMap<MemoId, Memo>>>,
 
+    /// Revisions of every memo, oldest first
+    history: Arc<RwLock<HashMap<MemoId, Vec<MemoRevision>>>>,
+
+    /// Deleted memos, keyed by memo ID
+    trash: Arc<RwLock<HashMap<MemoId, Memo>>>,
+
     /// Configuration for controlling mock behavior during tests
     config: Arc<RwLock<MockStorageConfig>>,
 }
@@ -96,6 +103,8 @@ impl MockMemoStorage {
     pub fn new() -> Self {
         Self {
             storage: Arc::new(RwLock::new(HashMap::new())),
+            history: Arc::new(RwLock::new(HashMap::new())),
+            trash: Arc::new(RwLock::new(HashMap::new())),
             config: Arc::new(RwLock::new(MockStorageConfig::default())),
         }
     }
@@ -110,6 +119,8 @@ impl MockMemoStorage {
     pub fn new_with_config(config: MockStorageConfig) -> Self {
         Self {
             storage: Arc::new(RwLock::new(HashMap::new())),
+            history: Arc::new(RwLock::new(HashMap::new())),
+            trash: Arc::new(RwLock::new(HashMap::new())),
             config: Arc::new(RwLock::new(config)),
         }
     }
@@ -139,6 +150,8 @@ impl MockMemoStorage {
     /// This is useful for test cleanup and resetting state between tests.
     pub async fn clear(&self) {
         self.storage.write().await.clear();
+        self.history.write().await.clear();
+        self.trash.write().await.clear();
     }
 
     /// Check if the storage is empty
@@ -161,6 +174,14 @@ impl MockMemoStorage {
         }
     }
 
+    /// Append a revision recording `memo` after `change`
+    async fn record(&self, memo: &Memo, change: MemoChange) {
+        let mut history = self.history.write().await;
+        let revisions = history.entry(memo.id.clone()).or_default();
+        let entry = MemoRevision::next(revisions, memo, change);
+        revisions.push(entry);
+    }
+
     /// Check if storage is at capacity limit
     async fn check_capacity_limit(&self) -> Result<()> {
         let config = self.config.read().await;
@@ -206,6 +227,7 @@ impl MemoStorage for MockMemoStorage {
         let mut storage = self.storage.write().await;
         storage.insert(memo.id.clone(), memo.clone());
         drop(storage);
+        self.record(&memo, MemoChange::Created).await;
 
         Ok(memo)
     }
@@ -244,14 +266,17 @@ impl MemoStorage for MockMemoStorage {
 
         // Update in storage
         let mut storage = self.storage.write().await;
-        match storage.get_mut(id) {
+        let memo = match storage.get_mut(id) {
             Some(memo) => {
-                memo.content = content;
-                memo.updated_at = chrono::Utc::now();
-                Ok(memo.clone())
+                memo.update_content(content);
+                memo.clone()
             }
-            None => Err(SwissArmyHammerError::MemoNotFound(id.as_str().to_string())),
-        }
+            None => return Err(SwissArmyHammerError::MemoNotFound(id.as_str().to_string())),
+        };
+        drop(storage);
+        self.record(&memo, MemoChange::Updated).await;
+
+        Ok(memo)
     }
 
     async fn delete_memo(&self, id: &MemoId) -> Result<()> {
@@ -268,10 +293,15 @@ impl MemoStorage for MockMemoStorage {
 
         // Remove from storage
         let mut storage = self.storage.write().await;
-        match storage.remove(id) {
-            Some(_) => Ok(()),
-            None => Err(SwissArmyHammerError::MemoNotFound(id.as_str().to_string())),
-        }
+        let memo = match storage.remove(id) {
+            Some(memo) => memo,
+            None => return Err(SwissArmyHammerError::MemoNotFound(id.as_str().to_string())),
+        };
+        drop(storage);
+        self.record(&memo, MemoChange::Deleted).await;
+        self.trash.write().await.insert(id.clone(), memo);
+
+        Ok(())
     }
 
     async fn list_memos(&self) -> Result<Vec<Memo>> {
@@ -416,6 +446,110 @@ impl MemoStorage for MockMemoStorage {
 
         Ok(context_parts.join(&format!("\n{}\n", options.delimiter)))
     }
+
+    async fn memo_history(&self, id: &MemoId) -> Result<Vec<MemoRevision>> {
+        self.simulate_delay().await;
+
+        match self.history.read().await.get(id) {
+            Some(revisions) => Ok(revisions.clone()),
+            None => Err(SwissArmyHammerError::MemoNotFound(id.as_str().to_string())),
+        }
+    }
+
+    async fn restore_memo(&self, id: &MemoId, revision: Option<u32>) -> Result<Memo> {
+        self.simulate_delay().await;
+
+        let trashed = self.trash.write().await.remove(id);
+        let mut memo = match trashed {
+            Some(memo) => memo,
+            None if revision.is_none() => {
+                self.get_memo(id).await?;
+                return Err(SwissArmyHammerError::Other(format!(
+                    "Memo '{id}' is not in the trash; give a revision to restore"
+                )));
+            }
+            None => self.get_memo(id).await?,
+        };
+
+        if let Some(revision) = revision {
+            let history = self.memo_history(id).await?;
+            let content = versions::find_revision(id, &history, revision)?
+                .content
+                .clone();
+            memo.update_content(content);
+        }
+        self.storage
+            .write()
+            .await
+            .insert(memo.id.clone(), memo.clone());
+        self.record(
+            &memo,
+            MemoChange::Restored {
+                from_revision: revision,
+            },
+        )
+        .await;
+
+        Ok(memo)
+    }
+
+    async fn import_memo(&self, mut memo: Memo) -> Result<Memo> {
+        self.simulate_delay().await;
+
+        let config = self.config.read().await;
+        if config.fail_create {
+            return Err(SwissArmyHammerError::Storage(
+                "Simulated create failure".to_string(),
+            ));
+        }
+        drop(config);
+
+        self.check_capacity_limit().await?;
+
+        memo.refresh_links();
+        let mut storage = self.storage.write().await;
+        if storage.contains_key(&memo.id) {
+            return Err(SwissArmyHammerError::MemoAlreadyExists(
+                memo.id.as_str().to_string(),
+            ));
+        }
+        storage.insert(memo.id.clone(), memo.clone());
+        drop(storage);
+        self.record(&memo, MemoChange::Created).await;
+
+        Ok(memo)
+    }
+
+    async fn list_trash(&self) -> Result<Vec<TrashedMemo>> {
+        self.simulate_delay().await;
+
+        let history = self.history.read().await;
+        let mut trashed: Vec<TrashedMemo> = self
+            .trash
+            .read()
+            .await
+            .values()
+            .map(|memo| {
+                let deleted_at = history
+                    .get(&memo.id)
+                    .and_then(|revisions| {
+                        revisions
+                            .iter()
+                            .rev()
+                            .find(|entry| entry.change == MemoChange::Deleted)
+                    })
+                    .map_or(memo.updated_at, |entry| entry.timestamp);
+                TrashedMemo {
+                    id: memo.id.clone(),
+                    title: memo.title.clone(),
+                    deleted_at,
+                    expires_at: deleted_at + chrono::Duration::days(DEFAULT_TRASH_RETENTION_DAYS),
+                }
+            })
+            .collect();
+        trashed.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        Ok(trashed)
+    }
 }
 
 #[cfg(test)]
@@ -11,6 +11,11 @@
 //! - **Pure Markdown Storage**: Files stored as `.md` with content only, no metadata wrapper
 //! - **Automatic Timestamps**: Creation and update times derived from filesystem metadata
 //! - **Full-text Search**: Search across memo titles and content
+//! - **Semantic Search**: Rank memos by meaning over embedded markdown sections, alone or fused with keyword search
+//! - **Ranked Context**: Memos ranked against a task and packed into a token budget for agents
+//! - **Revisions and Trash**: Every change keeps the prior revision; deleted memos wait in a trash before they are removed
+//! - **Export and Import**: Obsidian vaults, markdown trees and JSON bundles, with duplicate detection on import
+//! - **Tags and Links**: `#tags` and `[[wiki-links]]` to memos, issues and code, indexed for backlinks
 //! - **Type-safe API**: Strong typing for memo identifiers and validation
 //!
 //! ## Basic Usage
@@ -448,6 +453,28 @@ pub mod mock_storage;
 pub mod advanced_search;
 pub use advanced_search::AdvancedMemoSearchEngine;
 
+/// Tags, wiki-links and the backlinks index
+pub mod links;
+pub use links::{Backlink, LinkKind, MemoLink, MemoLinkIndex, MemoLinks};
+
+/// Semantic and hybrid search over heading-sized memo sections
+pub mod semantic;
+pub use semantic::{MemoSearchMode, MemoVectorIndex};
+
+/// Relevance-ranked memo context packed into a token budget
+pub mod context;
+pub use context::{
+    ContextEntry, ContextInclusion, MemoContext, RankedContextOptions, TokenCounter,
+};
+
+/// Memo revisions and the trash
+pub mod versions;
+pub use versions::{MemoChange, MemoRevision, MemoVersionStore, TrashedMemo};
+
+/// Export and import of memos as Obsidian vaults, markdown trees and JSON bundles
+pub mod exchange;
+pub use exchange::{ImportReport, MemoExportFormat};
+
 /// A unique identifier for memos using ULID (Universally Unique Lexicographically Sortable Identifier)
 ///
 /// ULIDs provide both uniqueness and natural ordering, making them ideal for memo identification
@@ -665,6 +692,12 @@ pub struct Memo {
     pub created_at: DateTime<Utc>,
     /// When this memo was last modified
     pub updated_at: DateTime<Utc>,
+    /// Tags from the front matter and inline `#hashtags`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// `[[wiki-links]]` to other memos, issues and code paths
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<MemoLink>,
 }
 
 impl Memo {
@@ -698,13 +731,26 @@ impl Memo {
     /// ```
     pub fn new(title: String, content: String) -> Self {
         let now = Utc::now();
-        Self {
+        let mut memo = Self {
             id: MemoId::new(),
             title,
             content,
             created_at: now,
             updated_at: now,
-        }
+            tags: Vec::new(),
+            links: Vec::new(),
+        };
+        memo.refresh_links();
+        memo
+    }
+
+    /// Parse the tags and links of the current content
+    ///
+    /// Called whenever the content changes, so `tags` and `links` always
+    /// describe the content.
+    pub fn refresh_links(&mut self) {
+        self.tags = links::parse_tags(&self.content);
+        self.links = links::parse_links(&self.content);
     }
 
     /// Update the content of this memo and refresh the updated timestamp
@@ -733,6 +779,7 @@ impl Memo {
     pub fn update_content(&mut self, content: String) {
         self.content = content;
         self.updated_at = Utc::now();
+        self.refresh_links();
     }
 
     /// Update the title of this memo and refresh the updated timestamp
@@ -874,7 +921,7 @@ pub struct GetMemoRequest {
 
 /// Request to delete a specific memo by its ID
 ///
-/// Used to permanently remove a memo from storage using its unique ULID identifier.
+/// Used to remove a memo from storage using its unique ULID identifier.
 ///
 /// # Examples
 ///
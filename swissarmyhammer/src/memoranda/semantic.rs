@@ -0,0 +1,610 @@
+//! Semantic and hybrid memo search
+//!
+//! Memo content is split into sections at markdown headings and each section is
+//! embedded with the [`EmbeddingEngine`] used for code search. The vectors live
+//! in a DuckDB [`VectorStorage`] next to the memo files, keyed by memo id, so a
+//! memo can be re-embedded or dropped on its own when it changes.
+//!
+//! Hybrid search merges the keyword ranking of
+//! [`MemoStorage::search_memos_advanced`](crate::memoranda::MemoStorage::search_memos_advanced)
+//! with the semantic ranking using reciprocal rank fusion, so neither signal's
+//! raw scores need to be comparable.
+
+use crate::error::Result;
+use crate::memoranda::{Memo, MemoId, SearchOptions, SearchResult};
+use crate::search::{
+    ChunkType, CodeChunk, Embedding, EmbeddingEngine, FileHasher, FileId, IndexedFile, Language,
+    SemanticConfig, SemanticUtils, VectorStorage,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Damping constant for reciprocal rank fusion
+pub const RRF_K: f32 = 60.0;
+
+/// Sections longer than this many characters are split at blank lines
+const MAX_SECTION_CHARS: usize = 2000;
+
+/// Candidate chunks fetched per requested memo, since one memo may own several hits
+const CANDIDATES_PER_RESULT: usize = 4;
+
+/// Characters of a matching section shown as its excerpt
+const EXCERPT_CHARS: usize = 200;
+
+/// Prefix of the keys memos are stored under in the vector storage
+const MEMO_KEY_PREFIX: &str = "memo:";
+
+/// How memo search ranks memos
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoSearchMode {
+    /// Match the words of the query
+    #[default]
+    Keyword,
+    /// Match the meaning of the query using embeddings
+    Semantic,
+    /// Fuse the keyword and semantic rankings
+    Hybrid,
+}
+
+impl fmt::Display for MemoSearchMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MemoSearchMode::Keyword => "keyword",
+            MemoSearchMode::Semantic => "semantic",
+            MemoSearchMode::Hybrid => "hybrid",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for MemoSearchMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "keyword" => Ok(MemoSearchMode::Keyword),
+            "semantic" => Ok(MemoSearchMode::Semantic),
+            "hybrid" => Ok(MemoSearchMode::Hybrid),
+            other => Err(format!(
+                "Unknown search mode '{other}', expected one of keyword, semantic, hybrid"
+            )),
+        }
+    }
+}
+
+/// A section of a memo, from one heading to the next
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoSection {
+    /// Heading text without the leading `#`s; `None` before the first heading
+    pub heading: Option<String>,
+    /// Section text, including its heading line
+    pub content: String,
+    /// First line of the section, starting at 1
+    pub start_line: usize,
+    /// Last line of the section
+    pub end_line: usize,
+}
+
+/// Heading text of a markdown ATX heading line
+fn heading_text(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some(rest.trim().trim_end_matches('#').trim())
+}
+
+/// Split memo content into sections at markdown headings
+///
+/// Headings inside fenced code blocks are ignored, blank sections are dropped,
+/// and sections longer than a couple of thousand characters are split further
+/// at blank lines so every section stays a useful embedding unit.
+pub fn split_sections(content: &str) -> Vec<MemoSection> {
+    let mut sections = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut lines: Vec<&str> = Vec::new();
+    let mut start_line = 1;
+    let mut in_fence = false;
+
+    for (index, line) in content.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let new_heading = if in_fence { None } else { heading_text(line) };
+        if let Some(text) = new_heading {
+            push_section(&mut sections, heading.take(), &lines, start_line);
+            heading = Some(text.to_string());
+            lines.clear();
+            start_line = index + 1;
+        }
+        lines.push(line);
+    }
+    push_section(&mut sections, heading, &lines, start_line);
+    sections
+}
+
+/// Add a section, split at blank lines when it is too long
+fn push_section(
+    sections: &mut Vec<MemoSection>,
+    heading: Option<String>,
+    lines: &[&str],
+    start_line: usize,
+) {
+    let mut part_start = 0;
+    let mut part_chars = 0;
+    for (offset, line) in lines.iter().enumerate() {
+        if part_chars >= MAX_SECTION_CHARS && line.trim().is_empty() {
+            push_part(
+                sections,
+                &heading,
+                &lines[part_start..offset],
+                start_line + part_start,
+            );
+            part_start = offset;
+            part_chars = 0;
+        }
+        part_chars += line.len() + 1;
+    }
+    push_part(
+        sections,
+        &heading,
+        &lines[part_start..],
+        start_line + part_start,
+    );
+}
+
+fn push_part(
+    sections: &mut Vec<MemoSection>,
+    heading: &Option<String>,
+    lines: &[&str],
+    start_line: usize,
+) {
+    let content = lines.join("\n").trim().to_string();
+    if content.is_empty() {
+        return;
+    }
+    sections.push(MemoSection {
+        heading: heading.clone(),
+        content,
+        start_line,
+        end_line: start_line + lines.len().saturating_sub(1),
+    });
+}
+
+/// Text embedded for a section, prefixed with where it sits in the memo
+fn embedding_text(title: &str, section: &MemoSection) -> String {
+    match &section.heading {
+        Some(heading) => format!("{title} > {heading}\n\n{}", section.content),
+        None => format!("{title}\n\n{}", section.content),
+    }
+}
+
+/// The best matching section of a memo
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoMatch {
+    /// The matching memo
+    pub memo_id: MemoId,
+    /// Cosine similarity of the best matching section
+    pub similarity: f32,
+    /// Heading of the best matching section
+    pub heading: Option<String>,
+    /// Start of the best matching section
+    pub excerpt: String,
+    /// Number of the memo's sections among the candidates
+    pub matching_sections: usize,
+}
+
+impl MemoMatch {
+    /// Highlight line naming the matched section
+    pub fn highlight(&self) -> String {
+        match &self.heading {
+            Some(heading) => format!("{heading}: {}", self.excerpt),
+            None => self.excerpt.clone(),
+        }
+    }
+}
+
+/// Vector index over memo sections
+pub struct MemoVectorIndex {
+    storage: VectorStorage,
+    engine: EmbeddingEngine,
+}
+
+impl MemoVectorIndex {
+    /// Database file in the memo directory holding the memo vectors
+    pub const DATABASE_FILE: &'static str = ".semantic.db";
+
+    /// Open or create the index stored at `database_path`
+    ///
+    /// An index built by a different embedding model is emptied, to be filled
+    /// again by the next [`Self::sync`].
+    pub fn new(database_path: PathBuf, engine: EmbeddingEngine) -> Result<Self> {
+        let storage = VectorStorage::new(SemanticConfig {
+            database_path,
+            ..SemanticConfig::default()
+        })?;
+        storage.initialize()?;
+
+        let model = engine.model_info();
+        if let Err(e) = storage.check_embedding_model(&model) {
+            tracing::info!("Re-embedding memos for model {}: {}", model.model_id, e);
+            storage.clear()?;
+        }
+        storage.record_embedding_model(&model)?;
+        Ok(Self { storage, engine })
+    }
+
+    /// Key a memo is stored under in the vector storage
+    fn memo_key(id: &MemoId) -> PathBuf {
+        PathBuf::from(format!("{MEMO_KEY_PREFIX}{}", id.as_str()))
+    }
+
+    /// Memo a vector storage key belongs to
+    fn memo_id_from_key(path: &Path) -> Option<MemoId> {
+        path.to_str()?
+            .strip_prefix(MEMO_KEY_PREFIX)
+            .map(MemoId::from_filename)
+    }
+
+    /// Embed the sections of a memo, unless it is indexed already
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool>` - Whether the memo had to be (re-)embedded
+    pub async fn index_memo(&self, memo: &Memo) -> Result<bool> {
+        let key = Self::memo_key(&memo.id);
+        let hash = FileHasher::hash_string(&memo.content);
+        if !self.storage.needs_reindexing(&key, &hash)? {
+            return Ok(false);
+        }
+
+        let sections = split_sections(&memo.content);
+        let texts: Vec<String> = sections
+            .iter()
+            .map(|section| embedding_text(&memo.title, section))
+            .collect();
+        let text_refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+        let vectors = self.engine.embed_batch(&text_refs).await?;
+
+        let chunks: Vec<CodeChunk> = sections
+            .into_iter()
+            .enumerate()
+            .map(|(n, section)| CodeChunk {
+                id: format!("{}#{n}", key.display()),
+                file_path: key.clone(),
+                language: Language::Unknown,
+                content_hash: FileHasher::hash_string(&section.content),
+                content: section.content,
+                start_line: section.start_line,
+                end_line: section.end_line,
+                chunk_type: ChunkType::PlainText,
+                symbol: None,
+            })
+            .collect();
+        let embeddings: Vec<Embedding> = chunks
+            .iter()
+            .zip(vectors)
+            .map(|(chunk, vector)| Embedding {
+                chunk_id: chunk.id.clone(),
+                vector,
+            })
+            .collect();
+
+        self.storage.remove_file_transaction(&key)?;
+        self.storage.store_indexed_file(&IndexedFile {
+            file_id: FileId(key.display().to_string()),
+            path: key,
+            language: Language::Unknown,
+            content_hash: hash,
+            chunk_count: chunks.len(),
+            indexed_at: Utc::now(),
+        })?;
+        self.storage
+            .store_chunks_and_embeddings_transaction(&chunks, &embeddings)?;
+        Ok(true)
+    }
+
+    /// Drop the sections of a memo from the index
+    pub fn remove_memo(&self, id: &MemoId) -> Result<()> {
+        self.storage.remove_file_transaction(&Self::memo_key(id))
+    }
+
+    /// Bring the index in line with `memos`
+    ///
+    /// New and changed memos are embedded and memos that no longer exist are
+    /// dropped; unchanged memos are left alone.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize>` - Number of memos embedded or dropped
+    pub async fn sync(&self, memos: &[Memo]) -> Result<usize> {
+        let mut changed = 0;
+        for memo in memos {
+            if self.index_memo(memo).await? {
+                changed += 1;
+            }
+        }
+
+        let current: HashSet<&MemoId> = memos.iter().map(|m| &m.id).collect();
+        for path in self.storage.indexed_paths()? {
+            let Some(id) = Self::memo_id_from_key(&path) else {
+                continue;
+            };
+            if !current.contains(&id) {
+                self.storage.remove_file_transaction(&path)?;
+                changed += 1;
+            }
+        }
+
+        if changed > 0 {
+            tracing::debug!("Updated {} memos in the semantic memo index", changed);
+        }
+        Ok(changed)
+    }
+
+    /// Memos whose sections are closest in meaning to `query`, best first
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<MemoMatch>> {
+        let query_vector = self.engine.embed_text(query).await?;
+        let hits = self.storage.similarity_search(
+            &query_vector,
+            limit.saturating_mul(CANDIDATES_PER_RESULT),
+            0.0,
+        )?;
+
+        let mut matches: Vec<MemoMatch> = Vec::new();
+        let mut positions: HashMap<MemoId, usize> = HashMap::new();
+        for hit in hits {
+            let Some(memo_id) = Self::memo_id_from_key(&hit.chunk.file_path) else {
+                continue;
+            };
+            if let Some(&position) = positions.get(&memo_id) {
+                matches[position].matching_sections += 1;
+                continue;
+            }
+            let section = split_sections(&hit.chunk.content).into_iter().next();
+            let heading = section.as_ref().and_then(|s| s.heading.clone());
+            positions.insert(memo_id.clone(), matches.len());
+            matches.push(MemoMatch {
+                memo_id,
+                similarity: hit.similarity_score,
+                heading,
+                excerpt: excerpt(&hit.chunk.content),
+                matching_sections: 1,
+            });
+        }
+
+        matches.truncate(limit);
+        Ok(matches)
+    }
+
+    /// Semantic search results for `query` among `memos`
+    ///
+    /// The relevance score is the similarity of the best matching section
+    /// scaled to 0-100. Memos missing from `memos` are skipped.
+    pub async fn search_memos(
+        &self,
+        query: &str,
+        memos: &[Memo],
+        limit: usize,
+        options: &SearchOptions,
+    ) -> Result<Vec<SearchResult>> {
+        let by_id: HashMap<&MemoId, &Memo> = memos.iter().map(|memo| (&memo.id, memo)).collect();
+        Ok(self
+            .search(query, limit)
+            .await?
+            .into_iter()
+            .filter_map(|found| {
+                let memo = by_id.get(&found.memo_id)?;
+                Some(SearchResult {
+                    memo: (*memo).clone(),
+                    relevance_score: (found.similarity * 100.0).clamp(0.0, 100.0),
+                    highlights: if options.include_highlights {
+                        vec![found.highlight()]
+                    } else {
+                        Vec::new()
+                    },
+                    match_count: found.matching_sections,
+                })
+            })
+            .collect())
+    }
+}
+
+/// Start of a section with its heading line removed
+fn excerpt(content: &str) -> String {
+    let body = match content.lines().next().and_then(heading_text) {
+        Some(_) => content.split_once('\n').map_or("", |(_, rest)| rest),
+        None => content,
+    };
+    let flat = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() > EXCERPT_CHARS {
+        format!(
+            "{}...",
+            flat.chars().take(EXCERPT_CHARS).collect::<String>()
+        )
+    } else {
+        flat
+    }
+}
+
+/// Merge ranked memo search results with reciprocal rank fusion
+///
+/// Each list must be ordered best first. A memo's fused relevance score is its
+/// fusion score scaled so that ranking first in every list scores 100;
+/// highlights and match counts from all lists are combined.
+pub fn fuse_results(rankings: Vec<Vec<SearchResult>>) -> Vec<SearchResult> {
+    let list_count = rankings.len().max(1) as f32;
+    let mut merged: HashMap<MemoId, SearchResult> = HashMap::new();
+    let mut id_rankings = Vec::with_capacity(rankings.len());
+
+    for ranking in rankings {
+        let mut ids = Vec::with_capacity(ranking.len());
+        for result in ranking {
+            ids.push(result.memo.id.clone());
+            match merged.get_mut(&result.memo.id) {
+                Some(existing) => {
+                    existing.match_count += result.match_count;
+                    for highlight in result.highlights {
+                        if !existing.highlights.contains(&highlight) {
+                            existing.highlights.push(highlight);
+                        }
+                    }
+                }
+                None => {
+                    merged.insert(result.memo.id.clone(), result);
+                }
+            }
+        }
+        id_rankings.push(ids);
+    }
+
+    let best_possible = list_count / (RRF_K + 1.0);
+    SemanticUtils::reciprocal_rank_fusion(&id_rankings, RRF_K)
+        .into_iter()
+        .filter_map(|(id, score)| {
+            let mut result = merged.remove(&id)?;
+            result.relevance_score = (score / best_possible * 100.0).min(100.0);
+            Some(result)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::IsolatedTestHome;
+    use tempfile::TempDir;
+
+    fn result(title: &str, score: f32) -> SearchResult {
+        SearchResult {
+            memo: memo(title, &format!("{title} content")),
+            relevance_score: score,
+            highlights: Vec::new(),
+            match_count: 1,
+        }
+    }
+
+    fn memo(title: &str, content: &str) -> Memo {
+        let mut memo = Memo::new(title.to_string(), content.to_string());
+        memo.id = MemoId::from_filename(title);
+        memo
+    }
+
+    #[test]
+    fn test_search_mode_parsing() {
+        assert_eq!("Hybrid".parse(), Ok(MemoSearchMode::Hybrid));
+        assert_eq!(MemoSearchMode::default(), MemoSearchMode::Keyword);
+        assert_eq!(MemoSearchMode::Semantic.to_string(), "semantic");
+        assert!("fuzzy".parse::<MemoSearchMode>().is_err());
+    }
+
+    #[test]
+    fn test_split_sections_at_headings() {
+        let content = "Intro line\n\n# Setup\nInstall it\n```sh\n# not a heading\n```\n\n## Usage ##\nRun it\n#hashtag";
+        let sections = split_sections(content);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].heading, None);
+        assert_eq!(sections[0].content, "Intro line");
+        assert_eq!(sections[1].heading.as_deref(), Some("Setup"));
+        assert!(sections[1].content.contains("# not a heading"));
+        assert_eq!((sections[1].start_line, sections[1].end_line), (3, 8));
+        assert_eq!(sections[2].heading.as_deref(), Some("Usage"));
+        assert_eq!(sections[2].content, "## Usage ##\nRun it\n#hashtag");
+    }
+
+    #[test]
+    fn test_split_long_sections_at_blank_lines() {
+        let paragraph = "word ".repeat(300);
+        let content = format!("# Long\n{paragraph}\n\n{paragraph}\n\n{paragraph}");
+        let sections = split_sections(&content);
+        assert!(sections.len() > 1);
+        assert!(sections
+            .iter()
+            .all(|section| section.heading.as_deref() == Some("Long")));
+    }
+
+    #[test]
+    fn test_fuse_results_prefers_memos_in_both_lists() {
+        let keyword = vec![result("a", 90.0), result("b", 80.0)];
+        let semantic = vec![result("c", 70.0), result("b", 60.0)];
+        let fused = fuse_results(vec![keyword, semantic]);
+
+        let ids: Vec<&str> = fused.iter().map(|r| r.memo.title.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a", "c"]);
+        assert_eq!(fused[0].match_count, 2);
+        assert!(fused[0].relevance_score > fused[1].relevance_score);
+        assert!(fused.iter().all(|r| r.relevance_score <= 100.0));
+    }
+
+    #[tokio::test]
+    async fn test_index_search_and_sync() {
+        let _guard = IsolatedTestHome::new();
+        let temp_dir = TempDir::new().unwrap();
+        let engine = EmbeddingEngine::new_for_testing().await.unwrap();
+        let index =
+            MemoVectorIndex::new(temp_dir.path().join(MemoVectorIndex::DATABASE_FILE), engine)
+                .unwrap();
+
+        let deploy = memo(
+            "Deploy",
+            "# Staging\nPush the branch\n\n# Production\nTag a release",
+        );
+        let recipes = memo("Recipes", "Bake bread at 220 degrees");
+        assert_eq!(
+            index
+                .sync(&[deploy.clone(), recipes.clone()])
+                .await
+                .unwrap(),
+            2
+        );
+
+        // Unchanged memos are not embedded again
+        assert!(!index.index_memo(&deploy).await.unwrap());
+
+        // The mock engine embeds identical text identically
+        let matches = index
+            .search("Deploy > Production\n\n# Production\nTag a release", 5)
+            .await
+            .unwrap();
+        assert_eq!(matches[0].memo_id, deploy.id);
+        assert_eq!(matches[0].heading.as_deref(), Some("Production"));
+        assert_eq!(matches[0].excerpt, "Tag a release");
+
+        // Removed memos disappear from the index
+        assert_eq!(index.sync(std::slice::from_ref(&deploy)).await.unwrap(), 1);
+        let matches = index
+            .search("Recipes\n\nBake bread at 220 degrees", 5)
+            .await
+            .unwrap();
+        assert!(matches.iter().all(|m| m.memo_id != recipes.id));
+
+        // Opened with another embedding model, every memo is embedded again
+        drop(index);
+        let engine = EmbeddingEngine::new_for_testing_with_config(crate::search::EmbeddingConfig {
+            model_id: "other-mock-model".to_string(),
+            dimensions: Some(384),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let index =
+            MemoVectorIndex::new(temp_dir.path().join(MemoVectorIndex::DATABASE_FILE), engine)
+                .unwrap();
+        assert_eq!(index.sync(std::slice::from_ref(&deploy)).await.unwrap(), 1);
+        let matches = index
+            .search("Deploy > Staging\n\n# Staging\nPush the branch", 5)
+            .await
+            .unwrap();
+        assert_eq!(matches[0].heading.as_deref(), Some("Staging"));
+    }
+}
@@ -47,13 +47,17 @@
 
 use crate::directory_utils::get_or_create_swissarmyhammer_directory;
 use crate::error::{Result, SwissArmyHammerError};
-use crate::memoranda::{AdvancedMemoSearchEngine, Memo, MemoId, SearchOptions};
+use crate::memoranda::{
+    AdvancedMemoSearchEngine, Backlink, LinkKind, Memo, MemoChange, MemoContext, MemoId,
+    MemoLinkIndex, MemoLinks, MemoRevision, MemoSearchMode, MemoVectorIndex, MemoVersionStore,
+    RankedContextOptions, SearchOptions, TokenCounter, TrashedMemo,
+};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use std::path::PathBuf;
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OnceCell};
 
 /// State configuration for memo storage
 ///
@@ -171,7 +175,8 @@ pub trait MemoStorage: Send + Sync {
 
     /// Delete a memo by its unique identifier
     ///
-    /// Permanently removes the memo from storage. This operation cannot be undone.
+    /// Removes the memo from storage. Storages that keep revisions move it to
+    /// the trash instead, from where `restore_memo` can bring it back.
     ///
     /// # Arguments
     ///
@@ -265,6 +270,58 @@ pub trait MemoStorage: Send + Sync {
         options: &crate::memoranda::SearchOptions,
     ) -> Result<Vec<crate::memoranda::SearchResult>>;
 
+    /// Mode [`MemoStorage::search_memos_with_mode`] actually uses for `mode`
+    ///
+    /// Storages without a vector index answer every search with keywords.
+    fn search_mode_used(&self, mode: MemoSearchMode) -> MemoSearchMode {
+        let _ = mode;
+        MemoSearchMode::Keyword
+    }
+
+    /// Search memos by keywords, by meaning, or both
+    ///
+    /// Keyword mode is [`MemoStorage::search_memos_advanced`]. Semantic mode
+    /// ranks memos by the embedding similarity of their best matching section,
+    /// and hybrid mode fuses both rankings with reciprocal rank fusion. Storages
+    /// without a vector index log a warning and fall back to keyword search.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The search query
+    /// * `options` - Search configuration; `max_results` caps every mode
+    /// * `mode` - How to rank memos
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<SearchResult>>` - Search results, best first
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # async fn hybrid_search_example(storage: &impl swissarmyhammer::memoranda::MemoStorage) -> swissarmyhammer::error::Result<()> {
+    /// use swissarmyhammer::memoranda::{MemoSearchMode, SearchOptions};
+    ///
+    /// let results = storage
+    ///     .search_memos_with_mode("how do we deploy", &SearchOptions::default(), MemoSearchMode::Hybrid)
+    ///     .await?;
+    /// println!("Found {} relevant memos", results.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn search_memos_with_mode(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+        mode: MemoSearchMode,
+    ) -> Result<Vec<crate::memoranda::SearchResult>> {
+        if self.search_mode_used(mode) != mode {
+            tracing::warn!(
+                "{mode} memo search is not supported by this storage, using keyword search"
+            );
+        }
+        self.search_memos_advanced(query, options).await
+    }
+
     /// Get all memo content formatted for AI consumption
     ///
     /// Concatenates all memos with metadata and delimiters optimized for
@@ -297,6 +354,249 @@ pub trait MemoStorage: Send + Sync {
     /// # }
     /// ```
     async fn get_all_context(&self, options: &crate::memoranda::ContextOptions) -> Result<String>;
+
+    /// Get the memos most useful for a task, packed into a token budget
+    ///
+    /// Memos are ranked by how well they match `options.query` and how
+    /// recently they were updated, then included best first until the token
+    /// budget runs out. A memo that no longer fits whole is cut short with a
+    /// marker; the rest are dropped. The result lists every memo and how much
+    /// of it was included.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Query, token budget and ranking weights
+    /// * `counter` - Tokenizer measuring the budget
+    ///
+    /// # Returns
+    ///
+    /// * `Result<MemoContext>` - The context text and what went into it
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # async fn ranked_context_example(storage: &impl swissarmyhammer::memoranda::MemoStorage) -> swissarmyhammer::error::Result<()> {
+    /// use swissarmyhammer::memoranda::{RankedContextOptions, TokenCounter};
+    ///
+    /// let options = RankedContextOptions {
+    ///     query: Some("Fix the login redirect loop".to_string()),
+    ///     max_tokens: Some(4000),
+    ///     ..Default::default()
+    /// };
+    /// let counter = TokenCounter::shared();
+    /// let context = storage.ranked_context(&options, &counter).await?;
+    /// println!("{} memos dropped", context.dropped().count());
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn ranked_context(
+        &self,
+        options: &RankedContextOptions,
+        counter: &TokenCounter,
+    ) -> Result<MemoContext> {
+        let memos = self.list_memos().await?;
+        let ranked = crate::memoranda::context::rank_memos(memos, options, Utc::now());
+        Ok(crate::memoranda::context::pack_context(
+            ranked, options, counter,
+        ))
+    }
+
+    /// Get every revision of a memo, oldest first
+    ///
+    /// Each revision holds the memo's content after the change it records, so
+    /// any of them can be compared or restored. The history outlives deletion
+    /// while the memo is in the trash.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The memo, existing or in the trash
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<MemoRevision>>` - The revisions, or `MemoNotFound`
+    async fn memo_history(&self, _id: &MemoId) -> Result<Vec<MemoRevision>> {
+        Err(SwissArmyHammerError::Other(
+            "Memo history is not supported by this storage".to_string(),
+        ))
+    }
+
+    /// Restore a memo from the trash, or set it back to an earlier revision
+    ///
+    /// A trashed memo is moved back first; with `revision` its content is then
+    /// replaced by that revision's. The restore is recorded as a new revision,
+    /// so it can be undone in turn.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The memo to restore
+    /// * `revision` - Revision whose content to restore; required for memos not in the trash
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Memo>` - The restored memo
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # async fn restore_example(storage: &impl swissarmyhammer::memoranda::MemoStorage) -> swissarmyhammer::error::Result<()> {
+    /// use swissarmyhammer::memoranda::MemoId;
+    ///
+    /// let id = MemoId::from_filename("Meeting_Notes");
+    /// storage.delete_memo(&id).await?;
+    /// let memo = storage.restore_memo(&id, None).await?;
+    /// println!("{}", storage.diff_memo(&memo.id, Some(1), None).await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn restore_memo(&self, _id: &MemoId, _revision: Option<u32>) -> Result<Memo> {
+        Err(SwissArmyHammerError::Other(
+            "Restoring memos is not supported by this storage".to_string(),
+        ))
+    }
+
+    /// Get the memos in the trash, most recently deleted first
+    ///
+    /// Memos past the trash retention period are removed for good first.
+    async fn list_trash(&self) -> Result<Vec<TrashedMemo>> {
+        Err(SwissArmyHammerError::Other(
+            "The memo trash is not supported by this storage".to_string(),
+        ))
+    }
+
+    /// Store a memo read from an export, keeping its ID and timestamps
+    ///
+    /// Storages that derive IDs from titles keep the title instead of the ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `memo` - The memo to store
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Memo>` - The memo as stored
+    ///
+    /// # Errors
+    ///
+    /// Returns `MemoAlreadyExists` if a memo with the same ID exists.
+    async fn import_memo(&self, _memo: Memo) -> Result<Memo> {
+        Err(SwissArmyHammerError::Other(
+            "Importing memos is not supported by this storage".to_string(),
+        ))
+    }
+
+    /// Get a unified diff between two revisions of a memo
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The memo
+    /// * `from` - Older revision; the one before `to` if not given
+    /// * `to` - Newer revision; the latest if not given
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String>` - The unified diff, empty if the contents are equal
+    async fn diff_memo(&self, id: &MemoId, from: Option<u32>, to: Option<u32>) -> Result<String> {
+        let history = self.memo_history(id).await?;
+        crate::memoranda::versions::diff_revisions(id, &history, from, to)
+    }
+
+    /// Index of the tags and links of every memo
+    ///
+    /// The default implementation parses every memo on each call; storages
+    /// that keep the index on disk override it.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<MemoLinkIndex>` - Tags and links keyed by memo ID
+    async fn link_index(&self) -> Result<MemoLinkIndex> {
+        Ok(MemoLinkIndex::from_memos(&self.list_memos().await?))
+    }
+
+    /// Outgoing links of a memo and the memos linking to it
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The unique identifier of the memo
+    ///
+    /// # Returns
+    ///
+    /// * `Result<MemoLinks>` - Links in both directions, or `MemoNotFound`
+    async fn memo_links(&self, id: &MemoId) -> Result<MemoLinks> {
+        self.link_index()
+            .await?
+            .links_of(id)
+            .ok_or_else(|| SwissArmyHammerError::MemoNotFound(id.as_str().to_string()))
+    }
+
+    /// Every memo link pointing at a memo, an issue or a file
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - What the target is
+    /// * `target` - Memo title or ID, issue name, or repository path
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Backlink>>` - The linking memos with the links themselves
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # async fn backlinks_example(storage: &impl swissarmyhammer::memoranda::MemoStorage) -> swissarmyhammer::error::Result<()> {
+    /// use swissarmyhammer::memoranda::LinkKind;
+    ///
+    /// for backlink in storage.backlinks(LinkKind::File, "src/auth.rs").await? {
+    ///     println!("{} references {}", backlink.title, backlink.link);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn backlinks(&self, kind: LinkKind, target: &str) -> Result<Vec<Backlink>> {
+        Ok(self.link_index().await?.backlinks(kind, target))
+    }
+
+    /// Memos carrying a tag, ignoring case and a leading `#`
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag to look for
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Memo>>` - The tagged memos
+    async fn memos_with_tag(&self, tag: &str) -> Result<Vec<Memo>> {
+        let mut memos = Vec::new();
+        for id in self.link_index().await?.memos_with_tag(tag) {
+            memos.push(self.get_memo(&id).await?);
+        }
+        Ok(memos)
+    }
+
+    /// A memo together with the memos within `depth` links of it
+    ///
+    /// Links are followed in both directions. The memo itself comes first,
+    /// followed by its neighbours nearest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The memo at the centre of the neighbourhood
+    /// * `depth` - How many links to follow; 0 returns the memo alone
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Memo>>` - The memos of the neighbourhood, or `MemoNotFound`
+    async fn memo_neighbourhood(&self, id: &MemoId, depth: usize) -> Result<Vec<Memo>> {
+        let ids = self.link_index().await?.neighbourhood(id, depth);
+        if ids.is_empty() {
+            return Err(SwissArmyHammerError::MemoNotFound(id.as_str().to_string()));
+        }
+
+        let mut memos = Vec::new();
+        for id in ids {
+            memos.push(self.get_memo(&id).await?);
+        }
+        Ok(memos)
+    }
 }
 
 /// Filesystem-based implementation of memo storage
@@ -336,6 +636,8 @@ pub struct FileSystemMemoStorage {
     creation_lock: Mutex<()>,
     /// Advanced search engine for full-text search capabilities
     search_engine: Option<AdvancedMemoSearchEngine>,
+    /// Revision history and trash
+    versions: MemoVersionStore,
 }
 
 /// Generate highlighted text snippets showing where search matches were found
@@ -557,12 +859,19 @@ impl FileSystemMemoStorage {
     /// ```
     pub fn new(memos_dir: PathBuf) -> Self {
         Self {
+            versions: MemoVersionStore::new(&memos_dir),
             state: MemoState { memos_dir },
             creation_lock: Mutex::new(()),
             search_engine: None,
         }
     }
 
+    /// Keep deleted memos in the trash for `retention` instead of the default 30 days
+    pub fn with_trash_retention(mut self, retention: chrono::Duration) -> Self {
+        self.versions = self.versions.with_retention(retention);
+        self
+    }
+
     /// Create a new filesystem storage with advanced search enabled
     ///
     /// # Arguments
@@ -578,6 +887,7 @@ impl FileSystemMemoStorage {
         let search_engine = AdvancedMemoSearchEngine::new_persistent(index_path).await?;
 
         Ok(Self {
+            versions: MemoVersionStore::new(&memos_dir),
             state: MemoState { memos_dir },
             creation_lock: Mutex::new(()),
             search_engine: Some(search_engine),
@@ -679,7 +989,9 @@ impl FileSystemMemoStorage {
     /// * `Result<Memo>` - The deserialized memo or error if file cannot be read/parsed
     async fn load_memo_from_file(&self, path: &PathBuf) -> Result<Memo> {
         let content = tokio::fs::read_to_string(path).await?;
-        let memo: Memo = serde_json::from_str(&content)?;
+        let mut memo: Memo = serde_json::from_str(&content)?;
+        // Files written before memos carried links have none recorded
+        memo.refresh_links();
         Ok(memo)
     }
 
@@ -754,6 +1066,7 @@ impl MemoStorage for FileSystemMemoStorage {
 
         let memo = Memo::new(title, content);
         self.create_memo_file_atomically(&memo).await?;
+        self.versions.record(&memo, MemoChange::Created)?;
 
         // Index the memo in the search engine if available
         self.index_memo_if_available(&memo).await?;
@@ -772,8 +1085,10 @@ impl MemoStorage for FileSystemMemoStorage {
 
     async fn update_memo(&self, id: &MemoId, content: String) -> Result<Memo> {
         let mut memo = self.get_memo(id).await?;
+        self.versions.record_baseline(&memo)?;
         memo.update_content(content);
         self.save_memo_to_file(&memo).await?;
+        self.versions.record(&memo, MemoChange::Updated)?;
 
         // Update the memo in the search engine if available
         self.index_memo_if_available(&memo).await?;
@@ -787,7 +1102,9 @@ impl MemoStorage for FileSystemMemoStorage {
             return Err(SwissArmyHammerError::MemoNotFound(id.as_str().to_string()));
         }
 
-        tokio::fs::remove_file(path).await?;
+        let memo = self.load_memo_from_file(&path).await?;
+        self.versions.trash(&memo, &path)?;
+        self.versions.purge_expired()?;
 
         // Remove the memo from the search engine if available
         self.remove_memo_from_index_if_available(id).await?;
@@ -953,6 +1270,59 @@ impl MemoStorage for FileSystemMemoStorage {
 
         Ok(context)
     }
+
+    async fn memo_history(&self, id: &MemoId) -> Result<Vec<MemoRevision>> {
+        let current = self.get_memo(id).await.ok();
+        self.versions.revisions(id, current.as_ref())
+    }
+
+    async fn restore_memo(&self, id: &MemoId, revision: Option<u32>) -> Result<Memo> {
+        let path = self.get_memo_path(id);
+        if !path.exists() || (revision.is_none() && self.versions.is_trashed(id)?) {
+            // Fails if a memo was created again in the deleted one's place
+            self.versions.untrash(id, &path)?;
+        } else if revision.is_none() {
+            self.get_memo(id).await?;
+            return Err(SwissArmyHammerError::Other(format!(
+                "Memo '{id}' is not in the trash; give a revision to restore"
+            )));
+        }
+
+        let mut memo = self.get_memo(id).await?;
+        if let Some(revision) = revision {
+            let history = self.versions.revisions(id, Some(&memo))?;
+            let content = crate::memoranda::versions::find_revision(id, &history, revision)?
+                .content
+                .clone();
+            self.versions.record_baseline(&memo)?;
+            memo.update_content(content);
+            self.save_memo_to_file(&memo).await?;
+        }
+        self.versions.record(
+            &memo,
+            MemoChange::Restored {
+                from_revision: revision,
+            },
+        )?;
+
+        self.index_memo_if_available(&memo).await?;
+        Ok(memo)
+    }
+
+    async fn list_trash(&self) -> Result<Vec<TrashedMemo>> {
+        self.versions.trashed()
+    }
+
+    async fn import_memo(&self, mut memo: Memo) -> Result<Memo> {
+        let _lock = self.creation_lock.lock().await;
+
+        memo.refresh_links();
+        self.create_memo_file_atomically(&memo).await?;
+        self.versions.record(&memo, MemoChange::Created)?;
+
+        self.index_memo_if_available(&memo).await?;
+        Ok(memo)
+    }
 }
 
 /// Markdown-based implementation of memo storage
@@ -988,9 +1358,21 @@ pub struct MarkdownMemoStorage {
     creation_lock: Mutex<()>,
     /// Advanced search engine for full-text search capabilities
     search_engine: Option<AdvancedMemoSearchEngine>,
+    /// Mutex serializing reads and writes of the persisted link index
+    link_index_lock: Mutex<()>,
+    /// Vector index for semantic search, opened by the first semantic search
+    semantic_index: OnceCell<MemoVectorIndex>,
+    /// Revision history and trash
+    versions: MemoVersionStore,
 }
 
 impl MarkdownMemoStorage {
+    /// File in the memo directory holding the tags and links of every memo
+    pub const LINK_INDEX_FILE: &'static str = ".links.json";
+
+    /// Results returned by semantic and hybrid search without `max_results`
+    pub const DEFAULT_SEMANTIC_RESULTS: usize = 20;
+
     /// Create a new markdown storage with the default memo directory
     ///
     /// Uses the `SWISSARMYHAMMER_MEMOS_DIR` environment variable if set,
@@ -1023,12 +1405,31 @@ impl MarkdownMemoStorage {
     /// * `Self` - New storage instance
     pub fn new(memos_dir: PathBuf) -> Self {
         Self {
+            versions: MemoVersionStore::new(&memos_dir),
             state: MemoState { memos_dir },
             creation_lock: Mutex::new(()),
             search_engine: None,
+            link_index_lock: Mutex::new(()),
+            semantic_index: OnceCell::new(),
         }
     }
 
+    /// Keep deleted memos in the trash for `retention` instead of the default 30 days
+    pub fn with_trash_retention(mut self, retention: chrono::Duration) -> Self {
+        self.versions = self.versions.with_retention(retention);
+        self
+    }
+
+    /// Use `index` for semantic search instead of opening the default one
+    ///
+    /// The default index lives in the memo directory and embeds with the
+    /// default [`EmbeddingEngine`](crate::search::EmbeddingEngine), which
+    /// downloads its model on first use.
+    pub fn with_semantic_index(mut self, index: MemoVectorIndex) -> Self {
+        self.semantic_index = OnceCell::new_with(Some(index));
+        self
+    }
+
     /// Sanitize a title to make it safe for use as a filename
     ///
     /// Removes or replaces characters that are not safe for filenames
@@ -1126,13 +1527,17 @@ impl MarkdownMemoStorage {
             .map(DateTime::<Utc>::from)
             .unwrap_or_else(|_| created_at);
 
-        Ok(Memo {
+        let mut memo = Memo {
             id,
             title,
             content,
             created_at,
             updated_at,
-        })
+            tags: Vec::new(),
+            links: Vec::new(),
+        };
+        memo.refresh_links();
+        Ok(memo)
     }
 
     /// Save a memo to a markdown file
@@ -1178,6 +1583,80 @@ impl MarkdownMemoStorage {
         }
         Ok(())
     }
+
+    /// Read the persisted link index
+    ///
+    /// A missing or unreadable index yields an empty one, which
+    /// [`MemoLinkIndex::reconcile`] then rebuilds from the memo files.
+    async fn load_link_index(&self) -> MemoLinkIndex {
+        let path = self.state.memos_dir.join(Self::LINK_INDEX_FILE);
+        let Ok(content) = tokio::fs::read_to_string(&path).await else {
+            return MemoLinkIndex::default();
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!(
+                path = %path.display(),
+                error = %e,
+                "Invalid memo link index, rebuilding"
+            );
+            MemoLinkIndex::default()
+        })
+    }
+
+    /// Write the link index next to the memo files
+    async fn save_link_index(&self, index: &MemoLinkIndex) -> Result<()> {
+        self.ensure_directory_exists().await?;
+        let path = self.state.memos_dir.join(Self::LINK_INDEX_FILE);
+        tokio::fs::write(path, serde_json::to_string_pretty(index)?).await?;
+        Ok(())
+    }
+
+    /// Apply a change to the persisted link index
+    async fn update_link_index<F>(&self, change: F) -> Result<()>
+    where
+        F: FnOnce(&mut MemoLinkIndex) + Send,
+    {
+        let _lock = self.link_index_lock.lock().await;
+        let mut index = self.load_link_index().await;
+        change(&mut index);
+        self.save_link_index(&index).await
+    }
+
+    /// The semantic index, opened on first use
+    async fn semantic_index(&self) -> Result<&MemoVectorIndex> {
+        self.semantic_index
+            .get_or_try_init(|| async {
+                let engine = crate::search::EmbeddingEngine::from_configuration().await?;
+                MemoVectorIndex::new(
+                    self.state.memos_dir.join(MemoVectorIndex::DATABASE_FILE),
+                    engine,
+                )
+            })
+            .await
+    }
+
+    /// Re-embed a memo that was just written if the semantic index is open
+    ///
+    /// Until a semantic search opens the index, changes are picked up by the
+    /// sync at the start of every semantic search instead. Failures are only
+    /// logged for the same reason.
+    async fn index_semantic_if_open(&self, memo: &Memo) {
+        if let Some(index) = self.semantic_index.get() {
+            if let Err(e) = index.index_memo(memo).await {
+                tracing::warn!(memo = %memo.id, error = %e, "Failed to update semantic memo index");
+            }
+        }
+    }
+
+    /// Record the links of a memo that was just written
+    ///
+    /// The memo is read back so the indexed timestamp matches the file and
+    /// the next [`MemoStorage::link_index`] call does not see it as changed.
+    async fn index_links(&self, memo: &Memo) -> Result<()> {
+        let path = self.get_memo_path_from_title(&memo.title);
+        let saved = self.load_memo_from_markdown_file(&path).await?;
+        self.update_link_index(|index| index.update(&saved)).await
+    }
 }
 
 #[async_trait]
@@ -1196,15 +1675,21 @@ impl MemoStorage for MarkdownMemoStorage {
         let id = MemoId::from_filename(&sanitized_title);
         let now = Utc::now();
 
-        let memo = Memo {
+        let mut memo = Memo {
             id,
             title,
             content,
             created_at: now,
             updated_at: now,
+            tags: Vec::new(),
+            links: Vec::new(),
         };
+        memo.refresh_links();
 
         self.save_memo_to_markdown_file(&memo).await?;
+        self.versions.record(&memo, MemoChange::Created)?;
+        self.index_links(&memo).await?;
+        self.index_semantic_if_open(&memo).await;
 
         // Index the memo in the search engine if available
         self.index_memo_if_available(&memo).await?;
@@ -1225,10 +1710,14 @@ impl MemoStorage for MarkdownMemoStorage {
 
     async fn update_memo(&self, id: &MemoId, content: String) -> Result<Memo> {
         let mut memo = self.get_memo(id).await?;
+        self.versions.record_baseline(&memo)?;
         memo.update_content(content);
 
         // Since we're updating content only, the filename stays the same
         self.save_memo_to_markdown_file(&memo).await?;
+        self.versions.record(&memo, MemoChange::Updated)?;
+        self.index_links(&memo).await?;
+        self.index_semantic_if_open(&memo).await;
 
         // Update the memo in the search engine if available
         self.index_memo_if_available(&memo).await?;
@@ -1244,7 +1733,14 @@ impl MemoStorage for MarkdownMemoStorage {
             return Err(SwissArmyHammerError::MemoNotFound(id.as_str().to_string()));
         }
 
-        tokio::fs::remove_file(path).await?;
+        self.versions.trash(&memo, &path)?;
+        self.versions.purge_expired()?;
+        self.update_link_index(|index| index.remove(id)).await?;
+        if let Some(index) = self.semantic_index.get() {
+            if let Err(e) = index.remove_memo(id) {
+                tracing::warn!(memo = %id, error = %e, "Failed to update semantic memo index");
+            }
+        }
 
         // Remove the memo from the search engine if available
         self.remove_memo_from_index_if_available(id).await?;
@@ -1410,6 +1906,124 @@ impl MemoStorage for MarkdownMemoStorage {
 
         Ok(context)
     }
+
+    fn search_mode_used(&self, mode: MemoSearchMode) -> MemoSearchMode {
+        mode
+    }
+
+    async fn search_memos_with_mode(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+        mode: MemoSearchMode,
+    ) -> Result<Vec<crate::memoranda::SearchResult>> {
+        if mode == MemoSearchMode::Keyword {
+            return self.search_memos_advanced(query, options).await;
+        }
+
+        // Memo files may have been edited by hand or changed by another process
+        let memos = self.list_memos().await?;
+        let index = self.semantic_index().await?;
+        index.sync(&memos).await?;
+
+        let limit = options
+            .max_results
+            .unwrap_or(Self::DEFAULT_SEMANTIC_RESULTS);
+        let semantic = index.search_memos(query, &memos, limit, options).await?;
+        let mut results = if mode == MemoSearchMode::Hybrid {
+            let keyword_options = SearchOptions {
+                max_results: None,
+                ..options.clone()
+            };
+            let keyword = self.search_memos_advanced(query, &keyword_options).await?;
+            crate::memoranda::semantic::fuse_results(vec![keyword, semantic])
+        } else {
+            semantic
+        };
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn memo_history(&self, id: &MemoId) -> Result<Vec<MemoRevision>> {
+        let current = self.get_memo(id).await.ok();
+        self.versions.revisions(id, current.as_ref())
+    }
+
+    async fn restore_memo(&self, id: &MemoId, revision: Option<u32>) -> Result<Memo> {
+        let path = self.get_memo_path_from_title(id.as_str());
+        if !path.exists() || (revision.is_none() && self.versions.is_trashed(id)?) {
+            // Fails if a memo was created again in the deleted one's place
+            self.versions.untrash(id, &path)?;
+        } else if revision.is_none() {
+            self.get_memo(id).await?;
+            return Err(SwissArmyHammerError::Other(format!(
+                "Memo '{id}' is not in the trash; give a revision to restore"
+            )));
+        }
+
+        let mut memo = self.get_memo(id).await?;
+        if let Some(revision) = revision {
+            let history = self.versions.revisions(id, Some(&memo))?;
+            let content = crate::memoranda::versions::find_revision(id, &history, revision)?
+                .content
+                .clone();
+            self.versions.record_baseline(&memo)?;
+            memo.update_content(content);
+            self.save_memo_to_markdown_file(&memo).await?;
+        }
+        self.versions.record(
+            &memo,
+            MemoChange::Restored {
+                from_revision: revision,
+            },
+        )?;
+
+        self.index_links(&memo).await?;
+        self.index_semantic_if_open(&memo).await;
+        self.index_memo_if_available(&memo).await?;
+        Ok(memo)
+    }
+
+    async fn list_trash(&self) -> Result<Vec<TrashedMemo>> {
+        self.versions.trashed()
+    }
+
+    async fn import_memo(&self, mut memo: Memo) -> Result<Memo> {
+        let _lock = self.creation_lock.lock().await;
+
+        let path = self.get_memo_path_from_title(&memo.title);
+        if path.exists() {
+            return Err(SwissArmyHammerError::MemoAlreadyExists(memo.title));
+        }
+        memo.id = MemoId::from_filename(&Self::sanitize_title_for_filename(&memo.title));
+        memo.refresh_links();
+
+        self.save_memo_to_markdown_file(&memo).await?;
+        // Timestamps come from the file, so carry the modification time over
+        std::fs::File::options()
+            .write(true)
+            .open(&path)?
+            .set_modified(memo.updated_at.into())?;
+
+        self.versions.record(&memo, MemoChange::Created)?;
+        self.index_links(&memo).await?;
+        self.index_semantic_if_open(&memo).await;
+        self.index_memo_if_available(&memo).await?;
+        Ok(memo)
+    }
+
+    async fn link_index(&self) -> Result<MemoLinkIndex> {
+        let memos = self.list_memos().await?;
+
+        // Memo files may have been edited, added or removed by hand since the
+        // index was written
+        let _lock = self.link_index_lock.lock().await;
+        let mut index = self.load_link_index().await;
+        if index.reconcile(&memos) {
+            self.save_link_index(&index).await?;
+        }
+        Ok(index)
+    }
 }
 
 #[cfg(test)]
@@ -2662,4 +3276,348 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert!(!results[0].highlights.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_markdown_links_and_backlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = MarkdownMemoStorage::new(temp_dir.path().join("memos"));
+
+        let research = storage
+            .create_memo(
+                "Auth Research".to_string(),
+                "#auth notes on [[file:src/auth.rs]]".to_string(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(research.tags, vec!["auth"]);
+        assert_eq!(research.links.len(), 1);
+
+        let plan = storage
+            .create_memo(
+                "Plan".to_string(),
+                "Builds on [[Auth Research]]".to_string(),
+            )
+            .await
+            .unwrap();
+        assert!(temp_dir
+            .path()
+            .join("memos")
+            .join(MarkdownMemoStorage::LINK_INDEX_FILE)
+            .exists());
+
+        let links = storage.memo_links(&research.id).await.unwrap();
+        assert_eq!(links.outgoing.len(), 1);
+        assert_eq!(links.backlinks.len(), 1);
+        assert_eq!(links.backlinks[0].memo_id, plan.id);
+
+        let file_backlinks = storage
+            .backlinks(LinkKind::File, "src/auth.rs")
+            .await
+            .unwrap();
+        assert_eq!(file_backlinks.len(), 1);
+        assert_eq!(file_backlinks[0].memo_id, research.id);
+
+        // Updating the plan drops its link
+        storage
+            .update_memo(&plan.id, "Unrelated now #misc".to_string())
+            .await
+            .unwrap();
+        assert!(storage
+            .backlinks(LinkKind::Memo, "Auth Research")
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(storage.memos_with_tag("misc").await.unwrap().len(), 1);
+
+        storage.delete_memo(&research.id).await.unwrap();
+        assert!(storage
+            .backlinks(LinkKind::File, "src/auth.rs")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_markdown_link_index_picks_up_external_edits() {
+        let temp_dir = TempDir::new().unwrap();
+        let memos_dir = temp_dir.path().join("memos");
+        let storage = MarkdownMemoStorage::new(memos_dir.clone());
+
+        storage
+            .create_memo("Design".to_string(), "The design".to_string())
+            .await
+            .unwrap();
+        // Written by hand, without going through the storage
+        std::fs::write(memos_dir.join("Review.md"), "Reviewed [[design]]").unwrap();
+
+        let neighbourhood = storage
+            .memo_neighbourhood(&MemoId::from_filename("Design"), 1)
+            .await
+            .unwrap();
+        let titles: Vec<&str> = neighbourhood.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["Design", "Review"]);
+
+        let index = storage.link_index().await.unwrap();
+        assert_eq!(index.len(), 2);
+        let persisted: MemoLinkIndex = serde_json::from_str(
+            &std::fs::read_to_string(memos_dir.join(MarkdownMemoStorage::LINK_INDEX_FILE)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(persisted, index);
+
+        assert!(matches!(
+            storage
+                .memo_neighbourhood(&MemoId::from_filename("Missing"), 1)
+                .await,
+            Err(SwissArmyHammerError::MemoNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_markdown_semantic_and_hybrid_search() {
+        let _guard = IsolatedTestHome::new();
+        let temp_dir = TempDir::new().unwrap();
+        let memos_dir = temp_dir.path().join("memos");
+        let engine = crate::search::EmbeddingEngine::new_for_testing()
+            .await
+            .unwrap();
+        let index =
+            MemoVectorIndex::new(memos_dir.join(MemoVectorIndex::DATABASE_FILE), engine).unwrap();
+        let storage = MarkdownMemoStorage::new(memos_dir).with_semantic_index(index);
+
+        storage
+            .create_memo(
+                "Deploy".to_string(),
+                "# Staging\nPush the branch\n\n# Production\nTag a release".to_string(),
+            )
+            .await
+            .unwrap();
+        storage
+            .create_memo(
+                "Recipes".to_string(),
+                "Bake bread at 220 degrees".to_string(),
+            )
+            .await
+            .unwrap();
+
+        // The mock engine embeds identical text identically
+        let options = SearchOptions {
+            include_highlights: true,
+            ..Default::default()
+        };
+        let results = storage
+            .search_memos_with_mode(
+                "Deploy > Production\n\n# Production\nTag a release",
+                &options,
+                MemoSearchMode::Semantic,
+            )
+            .await
+            .unwrap();
+        assert_eq!(results[0].memo.title, "Deploy");
+        assert_eq!(results[0].highlights, vec!["Production: Tag a release"]);
+
+        let results = storage
+            .search_memos_with_mode("bread", &options, MemoSearchMode::Hybrid)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].memo.title, "Recipes");
+
+        // Updates and deletes reach the open index
+        storage
+            .update_memo(
+                &MemoId::from_filename("Deploy"),
+                "# Rollback\nRevert the tag".to_string(),
+            )
+            .await
+            .unwrap();
+        storage
+            .delete_memo(&MemoId::from_filename("Recipes"))
+            .await
+            .unwrap();
+        let results = storage
+            .search_memos_with_mode(
+                "Deploy > Rollback\n\n# Rollback\nRevert the tag",
+                &options,
+                MemoSearchMode::Semantic,
+            )
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].highlights, vec!["Rollback: Revert the tag"]);
+
+        // Storages without a vector index fall back to keyword search
+        let (legacy, _temp_dir) = create_test_storage();
+        legacy
+            .create_memo("Recipes".to_string(), "Bake bread".to_string())
+            .await
+            .unwrap();
+        for mode in [MemoSearchMode::Semantic, MemoSearchMode::Hybrid] {
+            let results = legacy
+                .search_memos_with_mode("bread", &options, mode)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].memo.title, "Recipes");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_filesystem_memo_revisions_and_trash() {
+        let _guard = IsolatedTestHome::new();
+        let (storage, _temp_dir) = create_test_storage();
+
+        let memo = storage
+            .create_memo("Plan".to_string(), "first draft\n".to_string())
+            .await
+            .unwrap();
+        storage
+            .update_memo(&memo.id, "second draft\n".to_string())
+            .await
+            .unwrap();
+
+        let history = storage.memo_history(&memo.id).await.unwrap();
+        assert_eq!(
+            history.iter().map(|r| r.change).collect::<Vec<_>>(),
+            vec![MemoChange::Created, MemoChange::Updated]
+        );
+        assert_eq!(history[0].content, "first draft\n");
+
+        let diff = storage.diff_memo(&memo.id, None, None).await.unwrap();
+        assert!(diff.contains("-first draft"));
+        assert!(diff.contains("+second draft"));
+
+        // Going back to an earlier revision is itself a new revision
+        let restored = storage.restore_memo(&memo.id, Some(1)).await.unwrap();
+        assert_eq!(restored.content, "first draft\n");
+        assert_eq!(storage.memo_history(&memo.id).await.unwrap().len(), 3);
+
+        storage.delete_memo(&memo.id).await.unwrap();
+        assert!(storage.get_memo(&memo.id).await.is_err());
+        let trash = storage.list_trash().await.unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].id, memo.id);
+        assert_eq!(trash[0].title, "Plan");
+
+        let restored = storage.restore_memo(&memo.id, None).await.unwrap();
+        assert_eq!(restored.content, "first draft\n");
+        assert!(storage.list_trash().await.unwrap().is_empty());
+        assert!(storage.restore_memo(&memo.id, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_markdown_memo_created_again_after_delete() {
+        let _guard = IsolatedTestHome::new();
+        let temp_dir = TempDir::new().unwrap();
+        let storage = MarkdownMemoStorage::new(temp_dir.path().join("memos"));
+        let id = MemoId::from_filename("Notes");
+
+        for content in ["first", "second"] {
+            storage
+                .create_memo("Notes".to_string(), content.to_string())
+                .await
+                .unwrap();
+            storage.delete_memo(&id).await.unwrap();
+        }
+        // Each deletion is kept on its own
+        assert_eq!(storage.list_trash().await.unwrap().len(), 2);
+
+        storage
+            .create_memo("Notes".to_string(), "third".to_string())
+            .await
+            .unwrap();
+        assert_eq!(storage.memo_history(&id).await.unwrap().len(), 1);
+        assert!(matches!(
+            storage.restore_memo(&id, None).await,
+            Err(SwissArmyHammerError::MemoAlreadyExists(_))
+        ));
+
+        // Expired deletions take only their own history with them
+        let storage = storage.with_trash_retention(chrono::Duration::zero());
+        assert!(storage.list_trash().await.unwrap().is_empty());
+        let history = storage.memo_history(&id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].content, "third");
+
+        // The latest deletion is the one restored
+        let storage = storage.with_trash_retention(chrono::Duration::days(1));
+        storage.delete_memo(&id).await.unwrap();
+        let restored = storage.restore_memo(&id, None).await.unwrap();
+        assert_eq!(restored.content, "third");
+    }
+
+    #[tokio::test]
+    async fn test_import_memo_keeps_id_and_timestamps() {
+        let _guard = IsolatedTestHome::new();
+        let (storage, temp_dir) = create_test_storage();
+        let mut memo = Memo::new("Imported".to_string(), "From elsewhere".to_string());
+        memo.created_at = Utc::now() - chrono::Duration::days(10);
+        memo.updated_at = Utc::now() - chrono::Duration::days(2);
+
+        let imported = storage.import_memo(memo.clone()).await.unwrap();
+        assert_eq!(imported, memo);
+        assert_eq!(storage.get_memo(&memo.id).await.unwrap(), memo);
+        assert!(matches!(
+            storage.import_memo(memo.clone()).await,
+            Err(SwissArmyHammerError::MemoAlreadyExists(_))
+        ));
+
+        // Markdown memos are keyed by title and dated by their file
+        let markdown = MarkdownMemoStorage::new(temp_dir.path().join("markdown"));
+        let imported = markdown.import_memo(memo.clone()).await.unwrap();
+        assert_eq!(imported.id, MemoId::from_filename("Imported"));
+        let stored = markdown.get_memo(&imported.id).await.unwrap();
+        assert_eq!(stored.content, "From elsewhere");
+        assert_eq!(stored.updated_at.timestamp(), memo.updated_at.timestamp());
+    }
+
+    #[tokio::test]
+    async fn test_markdown_memo_revisions_and_trash_retention() {
+        let _guard = IsolatedTestHome::new();
+        let temp_dir = TempDir::new().unwrap();
+        let memos_dir = temp_dir.path().join("memos");
+        let storage = MarkdownMemoStorage::new(memos_dir.clone());
+
+        // Memos written before history was kept start with their current content
+        std::fs::create_dir_all(&memos_dir).unwrap();
+        std::fs::write(memos_dir.join("Notes.md"), "old notes\n").unwrap();
+        let id = MemoId::from_filename("Notes");
+        assert_eq!(storage.memo_history(&id).await.unwrap().len(), 1);
+
+        storage
+            .update_memo(&id, "new notes\n".to_string())
+            .await
+            .unwrap();
+        let history = storage.memo_history(&id).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].content, "old notes\n");
+
+        storage.delete_memo(&id).await.unwrap();
+        assert!(!memos_dir.join("Notes.md").exists());
+        assert!(storage.list_memos().await.unwrap().is_empty());
+        // History stays available while the memo is in the trash
+        assert_eq!(
+            storage
+                .memo_history(&id)
+                .await
+                .unwrap()
+                .last()
+                .unwrap()
+                .change,
+            MemoChange::Deleted
+        );
+
+        let restored = storage.restore_memo(&id, Some(1)).await.unwrap();
+        assert_eq!(restored.content, "old notes\n");
+        assert!(memos_dir.join("Notes.md").exists());
+
+        // Nothing can be restored once the retention period has passed
+        let storage = storage.with_trash_retention(chrono::Duration::zero());
+        storage.delete_memo(&id).await.unwrap();
+        assert!(storage.list_trash().await.unwrap().is_empty());
+        assert!(matches!(
+            storage.restore_memo(&id, None).await,
+            Err(SwissArmyHammerError::MemoNotFound(_))
+        ));
+    }
 }
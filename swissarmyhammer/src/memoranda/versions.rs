@@ -0,0 +1,586 @@
+//! Memo revisions and the trash
+//!
+//! Every change to a memo is appended as one JSON line to
+//! `.history/<id>.jsonl` inside the memo directory, together with the memo's
+//! content after the change, so any earlier revision can be shown, compared or
+//! restored. Memos written before history was kept get their current content
+//! recorded as revision 1 the first time they change.
+//!
+//! Deleting a memo moves its file to `.trash/` instead of removing it. Trashed
+//! memos can be restored until their retention period runs out, after which
+//! the file and its history are removed for good. Memo IDs may be derived from
+//! titles, so a memo can be deleted, created again and deleted again; each
+//! deletion is therefore kept under its own key, `<id>@<deleted at>`, which
+//! names both the trashed file and the history moved along with it.
+
+use crate::error::{Result, SwissArmyHammerError};
+use crate::memoranda::{Memo, MemoId};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Directory holding history files, inside the memo directory
+pub const HISTORY_DIR: &str = ".history";
+
+/// Directory holding deleted memo files, inside the memo directory
+pub const TRASH_DIR: &str = ".trash";
+
+/// Days a deleted memo stays in the trash by default
+pub const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+
+/// Separates the memo ID from the deletion time in trash keys
+const TRASH_KEY_SEPARATOR: char = '@';
+
+/// Format of the deletion time in trash keys
+const TRASH_KEY_TIME_FORMAT: &str = "%Y%m%dT%H%M%S%6fZ";
+
+/// What happened to a memo in a revision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum MemoChange {
+    /// The memo was created, or first recorded
+    Created,
+    /// The content was replaced
+    Updated,
+    /// The memo was moved to the trash
+    Deleted,
+    /// The memo was taken out of the trash or set back to an earlier revision
+    Restored {
+        /// Revision whose content was restored, if any
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from_revision: Option<u32>,
+    },
+}
+
+impl MemoChange {
+    /// One-line description of the change
+    pub fn summary(&self) -> String {
+        match self {
+            MemoChange::Created => "created".to_string(),
+            MemoChange::Updated => "updated".to_string(),
+            MemoChange::Deleted => "deleted".to_string(),
+            MemoChange::Restored {
+                from_revision: Some(revision),
+            } => format!("restored revision {revision}"),
+            MemoChange::Restored {
+                from_revision: None,
+            } => "restored from trash".to_string(),
+        }
+    }
+}
+
+/// One revision of a memo
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoRevision {
+    /// Revision number, starting at 1
+    pub revision: u32,
+    /// When the change was made
+    pub timestamp: DateTime<Utc>,
+    /// What changed
+    #[serde(flatten)]
+    pub change: MemoChange,
+    /// Memo title at this revision
+    pub title: String,
+    /// Memo content after the change
+    pub content: String,
+}
+
+impl MemoRevision {
+    /// The revision following `history` that records `memo`
+    pub fn next(history: &[MemoRevision], memo: &Memo, change: MemoChange) -> Self {
+        Self {
+            revision: history.last().map_or(1, |last| last.revision + 1),
+            timestamp: Utc::now(),
+            change,
+            title: memo.title.clone(),
+            content: memo.content.clone(),
+        }
+    }
+
+    /// Revision 1 of a memo that has no recorded history
+    pub fn baseline(memo: &Memo) -> Self {
+        Self {
+            revision: 1,
+            timestamp: memo.created_at,
+            change: MemoChange::Created,
+            title: memo.title.clone(),
+            content: memo.content.clone(),
+        }
+    }
+}
+
+/// Revision `revision` of a memo's history
+pub fn find_revision<'a>(
+    id: &MemoId,
+    history: &'a [MemoRevision],
+    revision: u32,
+) -> Result<&'a MemoRevision> {
+    history
+        .iter()
+        .find(|entry| entry.revision == revision)
+        .ok_or_else(|| {
+            SwissArmyHammerError::Other(format!(
+                "Memo '{id}' has no revision {revision}, expected 1 to {}",
+                history.len()
+            ))
+        })
+}
+
+/// Unified diff between two revisions of a memo
+///
+/// `from` defaults to the revision before `to`, and `to` to the latest one.
+pub fn diff_revisions(
+    id: &MemoId,
+    history: &[MemoRevision],
+    from: Option<u32>,
+    to: Option<u32>,
+) -> Result<String> {
+    let latest = history
+        .last()
+        .ok_or_else(|| SwissArmyHammerError::MemoNotFound(id.as_str().to_string()))?;
+    let to = find_revision(id, history, to.unwrap_or(latest.revision))?;
+    let from = match from {
+        Some(from) => find_revision(id, history, from)?,
+        None => history
+            .iter()
+            .rev()
+            .find(|entry| entry.revision < to.revision)
+            .unwrap_or(to),
+    };
+
+    Ok(similar::TextDiff::from_lines(&from.content, &to.content)
+        .unified_diff()
+        .context_radius(3)
+        .header(
+            &format!("a/{id}@{}", from.revision),
+            &format!("b/{id}@{}", to.revision),
+        )
+        .to_string())
+}
+
+/// A memo waiting in the trash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashedMemo {
+    /// Memo identifier
+    pub id: MemoId,
+    /// Memo title when it was deleted
+    pub title: String,
+    /// When the memo was deleted
+    pub deleted_at: DateTime<Utc>,
+    /// When the memo will be removed for good
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A file in the trash and where its history is kept
+#[derive(Debug, Clone)]
+struct TrashEntry {
+    memo: TrashedMemo,
+    file: PathBuf,
+    /// Trash key; `None` for files put in the trash by hand
+    key: Option<String>,
+}
+
+/// Trash key of a memo deleted at `deleted_at`
+fn trash_key(id: &MemoId, deleted_at: DateTime<Utc>) -> String {
+    format!(
+        "{id}{TRASH_KEY_SEPARATOR}{}",
+        deleted_at.format(TRASH_KEY_TIME_FORMAT)
+    )
+}
+
+/// Memo ID and deletion time of a trash key
+fn parse_trash_key(key: &str) -> Option<(MemoId, DateTime<Utc>)> {
+    let (id, deleted_at) = key.rsplit_once(TRASH_KEY_SEPARATOR)?;
+    let deleted_at =
+        chrono::NaiveDateTime::parse_from_str(deleted_at, TRASH_KEY_TIME_FORMAT).ok()?;
+    Some((MemoId::from_filename(id), deleted_at.and_utc()))
+}
+
+/// History files and trash of a memo directory
+#[derive(Debug, Clone)]
+pub struct MemoVersionStore {
+    memos_dir: PathBuf,
+    retention: Duration,
+}
+
+impl MemoVersionStore {
+    /// Version store for the memo directory `memos_dir`
+    pub fn new(memos_dir: &Path) -> Self {
+        Self {
+            memos_dir: memos_dir.to_path_buf(),
+            retention: Duration::days(DEFAULT_TRASH_RETENTION_DAYS),
+        }
+    }
+
+    /// Keep deleted memos in the trash for `retention`
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    fn history_path(&self, id: &MemoId) -> PathBuf {
+        self.keyed_history_path(id.as_str())
+    }
+
+    /// History file named `key`, a memo ID or a trash key
+    fn keyed_history_path(&self, key: &str) -> PathBuf {
+        self.memos_dir
+            .join(HISTORY_DIR)
+            .join(format!("{key}.jsonl"))
+    }
+
+    fn trash_dir(&self) -> PathBuf {
+        self.memos_dir.join(TRASH_DIR)
+    }
+
+    /// Recorded revisions of a memo, oldest first
+    pub fn history(&self, id: &MemoId) -> Result<Vec<MemoRevision>> {
+        self.read_history(&self.history_path(id))
+    }
+
+    fn read_history(&self, path: &Path) -> Result<Vec<MemoRevision>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line).map_err(|e| {
+                    SwissArmyHammerError::Storage(format!(
+                        "Invalid memo revision in {}: {e}",
+                        path.display()
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Revisions of a memo, with its current content as revision 1 if none were recorded
+    ///
+    /// A memo that no longer exists shows the history of its latest deletion
+    /// still in the trash. Returns `MemoNotFound` for a memo that neither
+    /// exists nor has a history.
+    pub fn revisions(&self, id: &MemoId, current: Option<&Memo>) -> Result<Vec<MemoRevision>> {
+        let mut history = self.history(id)?;
+        if history.is_empty() && current.is_none() {
+            if let Some(key) = self.latest_trash_entry(id)?.and_then(|entry| entry.key) {
+                history = self.read_history(&self.keyed_history_path(&key))?;
+            }
+        }
+        match (history.is_empty(), current) {
+            (false, _) => Ok(history),
+            (true, Some(memo)) => Ok(vec![MemoRevision::baseline(memo)]),
+            (true, None) => Err(SwissArmyHammerError::MemoNotFound(id.as_str().to_string())),
+        }
+    }
+
+    /// Append a revision recording `memo` after `change`
+    pub fn record(&self, memo: &Memo, change: MemoChange) -> Result<MemoRevision> {
+        let history = self.history(&memo.id)?;
+        let entry = MemoRevision::next(&history, memo, change);
+
+        let path = self.history_path(&memo.id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        Ok(entry)
+    }
+
+    /// Record the current state of a memo about to change, if it has no history yet
+    pub fn record_baseline(&self, memo: &Memo) -> Result<()> {
+        if !self.history_path(&memo.id).exists() {
+            self.record(memo, MemoChange::Created)?;
+        }
+        Ok(())
+    }
+
+    /// Move the file of `memo` to the trash and record the deletion
+    ///
+    /// The memo's history goes along, so a memo created with the same ID
+    /// afterwards starts a history of its own.
+    pub fn trash(&self, memo: &Memo, file: &Path) -> Result<()> {
+        self.record_baseline(memo)?;
+        let deleted = self.record(memo, MemoChange::Deleted)?;
+        let key = trash_key(&memo.id, deleted.timestamp);
+
+        // Keys may contain dots, so the extension is appended rather than set
+        let trashed = match file.extension() {
+            Some(extension) => format!("{key}.{}", extension.to_string_lossy()),
+            None => key.clone(),
+        };
+        let trashed = self.trash_dir().join(trashed);
+        fs::create_dir_all(self.trash_dir())?;
+        fs::rename(file, trashed)?;
+        fs::rename(self.history_path(&memo.id), self.keyed_history_path(&key))?;
+        Ok(())
+    }
+
+    /// Whether a deleted copy of a memo is in the trash
+    pub fn is_trashed(&self, id: &MemoId) -> Result<bool> {
+        Ok(self.latest_trash_entry(id)?.is_some())
+    }
+
+    /// Move the most recently deleted copy of a memo back to `file`
+    ///
+    /// Fails with `MemoAlreadyExists` if a memo has taken its place meanwhile.
+    pub fn untrash(&self, id: &MemoId, file: &Path) -> Result<()> {
+        if file.exists() {
+            return Err(SwissArmyHammerError::MemoAlreadyExists(
+                id.as_str().to_string(),
+            ));
+        }
+        let entry = self
+            .latest_trash_entry(id)?
+            .ok_or_else(|| SwissArmyHammerError::MemoNotFound(id.as_str().to_string()))?;
+        fs::rename(&entry.file, file)?;
+        if let Some(key) = &entry.key {
+            let history = self.keyed_history_path(key);
+            if history.exists() {
+                fs::rename(history, self.history_path(id))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The most recently deleted copy of a memo still in the trash
+    fn latest_trash_entry(&self, id: &MemoId) -> Result<Option<TrashEntry>> {
+        Ok(self
+            .trash_entries()?
+            .into_iter()
+            .filter(|entry| entry.memo.id == *id)
+            .max_by_key(|entry| entry.memo.deleted_at))
+    }
+
+    /// Memos in the trash, most recently deleted first
+    ///
+    /// Memos past their retention period are removed first.
+    pub fn trashed(&self) -> Result<Vec<TrashedMemo>> {
+        self.purge_expired()?;
+        let mut trashed: Vec<TrashedMemo> = self
+            .trash_entries()?
+            .into_iter()
+            .map(|entry| entry.memo)
+            .collect();
+        trashed.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        Ok(trashed)
+    }
+
+    fn trash_entries(&self) -> Result<Vec<TrashEntry>> {
+        let dir = self.trash_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut trashed = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(stem) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+                continue;
+            };
+            let entry = match parse_trash_key(&stem) {
+                Some((id, deleted_at)) => {
+                    let title = self
+                        .read_history(&self.keyed_history_path(&stem))?
+                        .into_iter()
+                        .rev()
+                        .find(|entry| entry.change == MemoChange::Deleted)
+                        .map_or_else(|| id.as_str().to_string(), |entry| entry.title);
+                    TrashEntry {
+                        memo: TrashedMemo {
+                            id,
+                            title,
+                            deleted_at,
+                            expires_at: deleted_at + self.retention,
+                        },
+                        file: path,
+                        key: Some(stem),
+                    }
+                }
+                // Trashed by hand; fall back to the file itself
+                None => {
+                    let deleted_at = fs::metadata(&path)?
+                        .modified()
+                        .map(DateTime::<Utc>::from)
+                        .unwrap_or_else(|_| Utc::now());
+                    TrashEntry {
+                        memo: TrashedMemo {
+                            id: MemoId::from_filename(&stem),
+                            title: stem,
+                            deleted_at,
+                            expires_at: deleted_at + self.retention,
+                        },
+                        file: path,
+                        key: None,
+                    }
+                }
+            };
+            trashed.push(entry);
+        }
+        Ok(trashed)
+    }
+
+    /// Remove memos whose retention period has run out, with their history
+    ///
+    /// Only the history of the expired deletion is removed, never that of a
+    /// memo created again under the same ID.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<MemoId>>` - The memos removed for good
+    pub fn purge_expired(&self) -> Result<Vec<MemoId>> {
+        let now = Utc::now();
+        let mut purged = Vec::new();
+        for entry in self.trash_entries()? {
+            if entry.memo.expires_at > now {
+                continue;
+            }
+            fs::remove_file(&entry.file)?;
+            if let Some(key) = &entry.key {
+                let history = self.keyed_history_path(key);
+                if history.exists() {
+                    fs::remove_file(history)?;
+                }
+            }
+            tracing::debug!("Purged memo {} from the trash", entry.memo.id);
+            purged.push(entry.memo.id);
+        }
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn memo(content: &str) -> Memo {
+        let mut memo = Memo::new("Notes".to_string(), content.to_string());
+        memo.id = MemoId::from_filename("Notes");
+        memo
+    }
+
+    #[test]
+    fn test_record_and_diff_revisions() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = MemoVersionStore::new(temp_dir.path());
+        let id = MemoId::from_filename("Notes");
+
+        // Without history the current memo is revision 1
+        let current = memo("one\ntwo\n");
+        let revisions = store.revisions(&id, Some(&current)).unwrap();
+        assert_eq!(revisions, vec![MemoRevision::baseline(&current)]);
+        assert!(store.revisions(&id, None).is_err());
+
+        store.record_baseline(&current).unwrap();
+        store.record_baseline(&current).unwrap();
+        store
+            .record(&memo("one\nthree\n"), MemoChange::Updated)
+            .unwrap();
+        let history = store.history(&id).unwrap();
+        assert_eq!(
+            history.iter().map(|r| r.change).collect::<Vec<_>>(),
+            vec![MemoChange::Created, MemoChange::Updated]
+        );
+
+        let diff = diff_revisions(&id, &history, None, None).unwrap();
+        assert!(diff.contains("--- a/Notes@1"));
+        assert!(diff.contains("+++ b/Notes@2"));
+        assert!(diff.contains("-two"));
+        assert!(diff.contains("+three"));
+        assert!(diff_revisions(&id, &history, Some(1), Some(5)).is_err());
+    }
+
+    #[test]
+    fn test_trash_untrash_and_purge() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = MemoVersionStore::new(temp_dir.path());
+        let file = temp_dir.path().join("Notes.md");
+        fs::write(&file, "content").unwrap();
+        let notes = memo("content");
+
+        store.trash(&notes, &file).unwrap();
+        assert!(!file.exists());
+        assert!(store.is_trashed(&notes.id).unwrap());
+        let trashed = store.trashed().unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].title, "Notes");
+        assert_eq!(
+            trashed[0].expires_at - trashed[0].deleted_at,
+            Duration::days(DEFAULT_TRASH_RETENTION_DAYS)
+        );
+
+        store.untrash(&notes.id, &file).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "content");
+        assert!(store.trashed().unwrap().is_empty());
+
+        // Nothing is kept once the retention period has passed
+        let store = store.with_retention(Duration::zero());
+        store.trash(&notes, &file).unwrap();
+        assert_eq!(store.purge_expired().unwrap(), vec![notes.id.clone()]);
+        assert!(!store.is_trashed(&notes.id).unwrap());
+        assert!(store.history(&notes.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_memo_created_again_keeps_history_apart_from_trash() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = MemoVersionStore::new(temp_dir.path());
+        let file = temp_dir.path().join("Notes.md");
+
+        // Deleted twice under the same ID
+        for content in ["first", "second"] {
+            fs::write(&file, content).unwrap();
+            store.record(&memo(content), MemoChange::Created).unwrap();
+            store.trash(&memo(content), &file).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        let trashed = store.trashed().unwrap();
+        assert_eq!(trashed.len(), 2);
+        assert!(trashed.iter().all(|entry| entry.id.as_str() == "Notes"));
+
+        // Created again while both deletions are in the trash
+        fs::write(&file, "third").unwrap();
+        store.record(&memo("third"), MemoChange::Created).unwrap();
+        assert!(matches!(
+            store.untrash(&memo("third").id, &file),
+            Err(SwissArmyHammerError::MemoAlreadyExists(_))
+        ));
+
+        // Purging the deletions leaves the live memo's history alone
+        let store = store.with_retention(Duration::zero());
+        assert_eq!(store.purge_expired().unwrap().len(), 2);
+        let history = store.history(&memo("third").id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].content, "third");
+
+        // The deletion comes back with its history
+        store.trash(&memo("third"), &file).unwrap();
+        store.untrash(&memo("third").id, &file).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "third");
+        assert_eq!(
+            store
+                .history(&memo("third").id)
+                .unwrap()
+                .last()
+                .unwrap()
+                .change,
+            MemoChange::Deleted
+        );
+    }
+
+    #[test]
+    fn test_trash_key_round_trip() {
+        let id = MemoId::from_filename("Notes v1.2");
+        let deleted_at = Utc::now();
+        let (parsed_id, parsed_at) = parse_trash_key(&trash_key(&id, deleted_at)).unwrap();
+        assert_eq!(parsed_id, id);
+        assert_eq!(parsed_at.timestamp_micros(), deleted_at.timestamp_micros());
+        assert!(parse_trash_key("Notes").is_none());
+    }
+}
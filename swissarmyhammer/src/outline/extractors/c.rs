@@ -0,0 +1,967 @@
+//! C language symbol extractor for outline generation
+//!
+//! This module implements Tree-sitter based symbol extraction for C code,
+//! supporting function definitions and prototypes, structs, unions, enums,
+//! typedefs, macros, global variables and includes, along with their
+//! comments, signatures and linkage-based visibility.
+//!
+//! The declarator helpers here are shared with the C++ extractor, whose
+//! grammar extends the C one, and the comment helpers with the C# extractor.
+
+use crate::outline::parser::SymbolExtractor;
+use crate::outline::signature::{
+    GenericParameter, Modifier, Parameter, Signature, SignatureExtractor, TypeInfo,
+};
+use crate::outline::types::{OutlineNode, OutlineNodeType, Visibility};
+use crate::outline::{OutlineError, Result};
+use crate::search::types::Language;
+use tree_sitter::{Node, Query, QueryCursor, StreamingIterator, Tree};
+
+/// C symbol extractor using Tree-sitter
+pub struct CExtractor {
+    /// Tree-sitter queries with their associated node types
+    queries: Vec<(OutlineNodeType, Query)>,
+}
+
+/// Collapse runs of whitespace, including newlines, into single spaces
+pub(crate) fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Line range of a node (1-based)
+///
+/// Preprocessor directives end at the start of the following line, which is
+/// not part of the symbol.
+pub(crate) fn line_range(node: &Node) -> (usize, usize) {
+    let start = node.start_position();
+    let end = node.end_position();
+    let end_line = if end.column == 0 && end.row > start.row {
+        end.row
+    } else {
+        end.row + 1
+    };
+    (start.row + 1, end_line)
+}
+
+/// The function declarator inside a declarator, if it declares a function
+///
+/// `int *make(void)` nests the function declarator in a pointer declarator,
+/// while `int (*fp)(int)` is a function pointer variable, not a function.
+pub(crate) fn find_function_declarator<'a>(declarator: Node<'a>) -> Option<Node<'a>> {
+    let mut current = declarator;
+    loop {
+        match current.kind() {
+            "function_declarator" => {
+                let inner = current.child_by_field_name("declarator")?;
+                return (inner.kind() != "parenthesized_declarator").then_some(current);
+            }
+            "pointer_declarator" | "reference_declarator" | "attributed_declarator" => {
+                current = current
+                    .child_by_field_name("declarator")
+                    .or_else(|| current.named_child(current.named_child_count().checked_sub(1)?))?;
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// The identifier a declarator ultimately names
+///
+/// Walks through pointer, array, init, function and parenthesized
+/// declarators, so `*names[4] = {0}` yields `names`.
+pub(crate) fn innermost_declarator<'a>(declarator: Node<'a>) -> Node<'a> {
+    let mut current = declarator;
+    loop {
+        let next = match current.kind() {
+            "parenthesized_declarator" => current.named_child(0),
+            "reference_declarator" => {
+                current.named_child(current.named_child_count().saturating_sub(1))
+            }
+            _ => current.child_by_field_name("declarator"),
+        };
+        match next {
+            Some(next) => current = next,
+            None => return current,
+        }
+    }
+}
+
+/// Whether a node is declared inside a function body
+pub(crate) fn is_local(node: &Node) -> bool {
+    let mut parent = node.parent();
+    while let Some(ancestor) = parent {
+        if ancestor.kind() == "compound_statement" {
+            return true;
+        }
+        parent = ancestor.parent();
+    }
+    false
+}
+
+/// Text of a declaration with the body of its type replaced by nothing
+///
+/// `typedef struct { int a; } point_t;` becomes `typedef struct point_t`.
+pub(crate) fn text_without_body(node: &Node, body: Option<Node>, source: &str) -> String {
+    let text = match body {
+        Some(body) => format!(
+            "{} {}",
+            &source[node.start_byte()..body.start_byte()],
+            &source[body.end_byte()..node.end_byte()]
+        ),
+        None => source[node.start_byte()..node.end_byte()].to_string(),
+    };
+    collapse_whitespace(text.trim_end().trim_end_matches(';'))
+}
+
+/// Extract the comments directly above a node, without comment markers
+pub(crate) fn extract_preceding_comments(node: &Node, source: &str) -> Option<String> {
+    let mut doc_lines = Vec::new();
+    let mut next_row = node.start_position().row;
+    let mut sibling = node.prev_sibling();
+
+    while let Some(comment) = sibling {
+        // Stop at anything that is not a comment or is separated by a blank line
+        if comment.kind() != "comment" || comment.end_position().row + 1 < next_row {
+            break;
+        }
+        let text = &source[comment.start_byte()..comment.end_byte()];
+        let cleaned = if let Some(line) = text.strip_prefix("//") {
+            line.trim_start_matches(['/', '!']).trim().to_string()
+        } else {
+            let inner = text
+                .trim_start_matches("/*")
+                .trim_start_matches(['*', '!'])
+                .trim_end_matches("*/");
+            inner
+                .lines()
+                .map(|line| line.trim().trim_start_matches('*').trim())
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        if !cleaned.is_empty() {
+            doc_lines.insert(0, cleaned);
+        }
+        next_row = comment.start_position().row;
+        sibling = comment.prev_sibling();
+    }
+
+    if doc_lines.is_empty() {
+        None
+    } else {
+        Some(doc_lines.join(" "))
+    }
+}
+
+impl CExtractor {
+    /// Create a new C extractor with compiled queries
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_c::LANGUAGE.into();
+        let mut queries = Vec::new();
+
+        // Define Tree-sitter queries for each C construct
+        let query_definitions = vec![
+            (
+                OutlineNodeType::Function,
+                r#"(function_definition) @function"#,
+            ),
+            // Prototypes and function-like macros; declarations are filtered in extract_symbols
+            (
+                OutlineNodeType::Function,
+                r#"[(declaration declarator: (_) @prototype) (preproc_function_def) @macro]"#,
+            ),
+            (
+                OutlineNodeType::Struct,
+                r#"[(struct_specifier name: (_) body: (_)) (union_specifier name: (_) body: (_))] @struct"#,
+            ),
+            (
+                OutlineNodeType::Enum,
+                r#"(enum_specifier name: (_) body: (_)) @enum"#,
+            ),
+            (
+                OutlineNodeType::TypeAlias,
+                r#"(type_definition declarator: (_) @typedef)"#,
+            ),
+            (
+                OutlineNodeType::Property,
+                r#"(field_declaration declarator: (_) @field)"#,
+            ),
+            (
+                OutlineNodeType::Variable,
+                r#"(declaration declarator: (_) @variable)"#,
+            ),
+            (
+                OutlineNodeType::Constant,
+                r#"(preproc_def value: (_)) @constant"#,
+            ),
+            (OutlineNodeType::Import, r#"(preproc_include) @include"#),
+        ];
+
+        // Compile all queries
+        for (node_type, query_str) in query_definitions {
+            let query = Query::new(&language, query_str).map_err(|e| {
+                OutlineError::TreeSitter(format!("Failed to compile {node_type:?} query: {e}"))
+            })?;
+            queries.push((node_type, query));
+        }
+
+        Ok(Self { queries })
+    }
+
+    /// Extract the text content of a node
+    fn get_node_text(&self, node: &Node, source: &str) -> String {
+        source[node.start_byte()..node.end_byte()].to_string()
+    }
+
+    /// Extract the name from a Tree-sitter node
+    fn extract_name_from_node(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "function_definition" => {
+                let declarator = node.child_by_field_name("declarator")?;
+                let function = find_function_declarator(declarator)?;
+                let name = function.child_by_field_name("declarator")?;
+                Some(self.get_node_text(&name, source))
+            }
+            "preproc_include" => {
+                let path = node.child_by_field_name("path")?;
+                Some(
+                    self.get_node_text(&path, source)
+                        .trim_matches(['"', '<', '>'])
+                        .to_string(),
+                )
+            }
+            "struct_specifier"
+            | "union_specifier"
+            | "enum_specifier"
+            | "preproc_def"
+            | "preproc_function_def" => node
+                .child_by_field_name("name")
+                .map(|name| self.get_node_text(&name, source)),
+            // Declarators of declarations, fields and typedefs
+            _ => {
+                let name = innermost_declarator(*node);
+                matches!(
+                    name.kind(),
+                    "identifier" | "field_identifier" | "type_identifier"
+                )
+                .then(|| self.get_node_text(&name, source))
+            }
+        }
+    }
+
+    /// The declaration a declarator belongs to, which holds its type, range and comments
+    fn declaration_node<'a>(&self, node: &Node<'a>) -> Node<'a> {
+        match node.parent() {
+            // A struct defined in a declaration's type keeps its own range
+            Some(parent)
+                if matches!(
+                    parent.kind(),
+                    "declaration" | "field_declaration" | "type_definition"
+                ) && parent.child_by_field_name("type") != Some(*node) =>
+            {
+                parent
+            }
+            _ => *node,
+        }
+    }
+
+    /// Whether a captured node belongs to the query's node type
+    ///
+    /// Declarations are captured by both the prototype and variable queries,
+    /// and are told apart by whether they declare a function.
+    fn matches_node_type(&self, node_type: &OutlineNodeType, node: &Node) -> bool {
+        match (node_type, node.kind()) {
+            (OutlineNodeType::Function, "preproc_function_def") => true,
+            (OutlineNodeType::Function, "function_definition") => true,
+            (OutlineNodeType::Function, _) => {
+                !is_local(node) && find_function_declarator(*node).is_some()
+            }
+            (OutlineNodeType::Variable, _) => {
+                !is_local(node) && find_function_declarator(*node).is_none()
+            }
+            (OutlineNodeType::Property, _) => find_function_declarator(*node).is_none(),
+            _ => true,
+        }
+    }
+
+    /// Whether a declaration has a `const` qualifier on a non-pointer value
+    fn is_constant(&self, declaration: &Node, declarator: &Node, source: &str) -> bool {
+        let has_const = declaration.children(&mut declaration.walk()).any(|child| {
+            child.kind() == "type_qualifier" && self.get_node_text(&child, source) == "const"
+        });
+        let value = match declarator.kind() {
+            "init_declarator" => declarator.child_by_field_name("declarator"),
+            _ => Some(*declarator),
+        };
+        has_const && value.is_some_and(|value| value.kind() != "pointer_declarator")
+    }
+
+    /// Whether a declaration has the given storage class, such as `static`
+    fn has_storage_class(&self, declaration: &Node, storage: &str, source: &str) -> bool {
+        declaration.children(&mut declaration.walk()).any(|child| {
+            child.kind() == "storage_class_specifier"
+                && self.get_node_text(&child, source) == storage
+        })
+    }
+
+    /// Functions and globals are public unless declared `static`
+    fn get_visibility(&self, declaration: &Node, source: &str) -> Visibility {
+        if self.has_storage_class(declaration, "static", source) {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        }
+    }
+
+    /// Build function signature, such as `static int add(int a, int b)`
+    fn build_function_signature(&self, node: &Node, source: &str) -> String {
+        match node.kind() {
+            "function_definition" => {
+                let end = node
+                    .child_by_field_name("body")
+                    .map_or(node.end_byte(), |body| body.start_byte());
+                collapse_whitespace(&source[node.start_byte()..end])
+            }
+            "preproc_function_def" => {
+                let end = node
+                    .child_by_field_name("value")
+                    .map_or(node.end_byte(), |value| value.start_byte());
+                collapse_whitespace(&source[node.start_byte()..end])
+            }
+            // A prototype's declarator
+            _ => self.build_declarator_signature(node, source),
+        }
+    }
+
+    /// Build the signature of one declarator of a declaration, without its initializer
+    ///
+    /// `static const int limit = 5, other;` gives `static const int limit`
+    /// for its first declarator.
+    fn build_declarator_signature(&self, node: &Node, source: &str) -> String {
+        let declaration = self.declaration_node(node);
+        let prefix_end = declaration
+            .child_by_field_name("declarator")
+            .map_or(node.start_byte(), |first| first.start_byte());
+        let declarator = match node.kind() {
+            "init_declarator" => node.child_by_field_name("declarator").unwrap_or(*node),
+            _ => *node,
+        };
+        // `struct inner { int depth; } nested;` gives `struct inner nested`
+        let prefix = match declaration
+            .child_by_field_name("type")
+            .and_then(|ty| ty.child_by_field_name("body"))
+        {
+            Some(body) => format!(
+                "{} {}",
+                &source[declaration.start_byte()..body.start_byte()],
+                &source[body.end_byte()..prefix_end]
+            ),
+            None => source[declaration.start_byte()..prefix_end].to_string(),
+        };
+        collapse_whitespace(&format!(
+            "{prefix} {}",
+            self.get_node_text(&declarator, source)
+        ))
+    }
+
+    /// Build type signature, such as `struct point` or `typedef struct point point_t`
+    fn build_type_signature(&self, node: &Node, source: &str) -> String {
+        match node.kind() {
+            "struct_specifier" | "union_specifier" | "enum_specifier" => {
+                let end = node
+                    .child_by_field_name("body")
+                    .map_or(node.end_byte(), |body| body.start_byte());
+                collapse_whitespace(&source[node.start_byte()..end])
+            }
+            // A typedef's declarator
+            _ => {
+                let declaration = self.declaration_node(node);
+                let body = declaration
+                    .child_by_field_name("type")
+                    .and_then(|ty| ty.child_by_field_name("body"));
+                text_without_body(&declaration, body, source)
+            }
+        }
+    }
+
+    /// Build macro signature, such as `#define MAX 10`
+    fn build_macro_signature(&self, node: &Node, source: &str) -> String {
+        collapse_whitespace(&self.get_node_text(node, source))
+    }
+
+    /// Build include signature, such as `#include <stdio.h>`
+    fn build_include_signature(&self, node: &Node, source: &str) -> String {
+        collapse_whitespace(&self.get_node_text(node, source))
+    }
+
+    /// Check if an inner symbol is within the range of an outer symbol
+    fn is_symbol_within_range(inner: &OutlineNode, outer: &OutlineNode) -> bool {
+        inner.source_range.0 >= outer.source_range.0 && inner.source_range.1 <= outer.source_range.1
+    }
+}
+
+impl SignatureExtractor for CExtractor {
+    fn extract_function_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        let (declaration, declarator) = match node.kind() {
+            "function_definition" => (*node, node.child_by_field_name("declarator")?),
+            "preproc_function_def" => {
+                let name = self.extract_name_from_node(node, source)?;
+                let mut signature = Signature::new(name, Language::C);
+                if let Some(params) = node.child_by_field_name("parameters") {
+                    for param in params.named_children(&mut params.walk()) {
+                        signature = signature
+                            .with_parameter(Parameter::new(self.get_node_text(&param, source)));
+                    }
+                }
+                return Some(
+                    signature.with_raw_signature(self.build_function_signature(node, source)),
+                );
+            }
+            _ => (self.declaration_node(node), *node),
+        };
+        let function = find_function_declarator(declarator)?;
+        let name = function.child_by_field_name("declarator")?;
+
+        let mut signature = Signature::new(self.get_node_text(&name, source), Language::C);
+        signature = signature.with_modifiers(self.parse_modifiers(&declaration, source));
+
+        if let Some(params_node) = function.child_by_field_name("parameters") {
+            for param_node in params_node.named_children(&mut params_node.walk()) {
+                if let Some(param) = self.parse_parameter(&param_node, source) {
+                    signature = signature.with_parameter(param);
+                }
+            }
+        }
+
+        if let Some(return_node) = declaration.child_by_field_name("type") {
+            if let Some(mut return_type) = self.parse_type_info(&return_node, source) {
+                // `char *name(...)` returns a pointer
+                let mut current = declarator;
+                while current.kind() == "pointer_declarator" {
+                    return_type.name.push('*');
+                    match current.child_by_field_name("declarator") {
+                        Some(inner) => current = inner,
+                        None => break,
+                    }
+                }
+                signature = signature.with_return_type(return_type);
+            }
+        }
+
+        signature = signature.with_raw_signature(self.build_function_signature(node, source));
+        Some(signature)
+    }
+
+    fn extract_method_signature(&self, _node: &Node, _source: &str) -> Option<Signature> {
+        // C has no methods
+        None
+    }
+
+    fn extract_constructor_signature(&self, _node: &Node, _source: &str) -> Option<Signature> {
+        // C has no constructors
+        None
+    }
+
+    fn extract_type_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        let name = self.extract_name_from_node(node, source)?;
+        let signature = Signature::new(name, Language::C)
+            .with_raw_signature(self.build_type_signature(node, source));
+        Some(signature)
+    }
+
+    fn parse_type_info(&self, node: &Node, source: &str) -> Option<TypeInfo> {
+        let text = collapse_whitespace(&self.get_node_text(node, source));
+        (!text.is_empty()).then(|| TypeInfo::new(text))
+    }
+
+    fn parse_parameter(&self, node: &Node, source: &str) -> Option<Parameter> {
+        match node.kind() {
+            "parameter_declaration" => {
+                let type_node = node.child_by_field_name("type")?;
+                let Some(declarator) = node.child_by_field_name("declarator") else {
+                    // `(void)` declares no parameters; `(int)` an unnamed one
+                    let type_text = self.get_node_text(&type_node, source);
+                    if type_text == "void" {
+                        return None;
+                    }
+                    return Some(
+                        Parameter::new("_".to_string()).with_type(TypeInfo::new(type_text)),
+                    );
+                };
+
+                let name = innermost_declarator(declarator);
+                let mut type_info = self.parse_type_info(&type_node, source)?;
+                let mut current = declarator;
+                while current.kind() == "pointer_declarator" {
+                    type_info.name.push('*');
+                    match current.child_by_field_name("declarator") {
+                        Some(inner) => current = inner,
+                        None => break,
+                    }
+                }
+                if current.kind() == "array_declarator" {
+                    type_info = TypeInfo::array(type_info, 1);
+                }
+                Some(Parameter::new(self.get_node_text(&name, source)).with_type(type_info))
+            }
+            "variadic_parameter" => Some(Parameter::new("...".to_string()).variadic()),
+            _ => None,
+        }
+    }
+
+    fn parse_generic_parameters(&self, _node: &Node, _source: &str) -> Vec<GenericParameter> {
+        // C has no generics
+        Vec::new()
+    }
+
+    fn parse_modifiers(&self, node: &Node, source: &str) -> Vec<Modifier> {
+        node.children(&mut node.walk())
+            .filter_map(|child| match child.kind() {
+                "storage_class_specifier" | "type_qualifier" => {
+                    match self.get_node_text(&child, source).as_str() {
+                        "static" => Some(Modifier::Static),
+                        "extern" => Some(Modifier::Extern),
+                        "inline" => Some(Modifier::Inline),
+                        "const" => Some(Modifier::Const),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl SymbolExtractor for CExtractor {
+    fn extract_symbols(&self, tree: &Tree, source: &str) -> Result<Vec<OutlineNode>> {
+        let mut symbols = Vec::new();
+        let root_node = tree.root_node();
+
+        // Process each query
+        for (node_type, query) in &self.queries {
+            let mut cursor = QueryCursor::new();
+            let mut matches = cursor.matches(query, root_node, source.as_bytes());
+
+            while let Some(query_match) = matches.next() {
+                // Get the main captured node (should be the only capture)
+                if let Some(capture) = query_match.captures.first() {
+                    let node = &capture.node;
+
+                    if !self.matches_node_type(node_type, node) {
+                        continue;
+                    }
+
+                    if let Some(name) = self.extract_name_from_node(node, source) {
+                        let declaration = self.declaration_node(node);
+                        let mut symbol_type = node_type.clone();
+                        if *node_type == OutlineNodeType::Variable
+                            && self.is_constant(&declaration, node, source)
+                        {
+                            symbol_type = OutlineNodeType::Constant;
+                        }
+
+                        let (start_line, end_line) = line_range(&declaration);
+                        let mut outline_node = OutlineNode::new(
+                            name,
+                            symbol_type,
+                            start_line,
+                            end_line,
+                            (declaration.start_byte(), declaration.end_byte()),
+                        );
+
+                        // Add signature based on node type
+                        let signature = match node_type {
+                            OutlineNodeType::Function => self
+                                .extract_function_signature(node, source)
+                                .map(|sig| sig.format_for_language(Language::C)),
+                            OutlineNodeType::Struct
+                            | OutlineNodeType::Enum
+                            | OutlineNodeType::TypeAlias => {
+                                Some(self.build_type_signature(node, source))
+                            }
+                            OutlineNodeType::Variable | OutlineNodeType::Property => {
+                                Some(self.build_declarator_signature(node, source))
+                            }
+                            OutlineNodeType::Constant => {
+                                Some(self.build_macro_signature(node, source))
+                            }
+                            OutlineNodeType::Import => {
+                                Some(self.build_include_signature(node, source))
+                            }
+                            _ => None,
+                        };
+
+                        if let Some(sig) = signature {
+                            outline_node = outline_node.with_signature(sig);
+                        }
+
+                        // Add visibility for symbols with linkage
+                        if matches!(
+                            node_type,
+                            OutlineNodeType::Function | OutlineNodeType::Variable
+                        ) && declaration.kind() != "preproc_function_def"
+                        {
+                            outline_node = outline_node
+                                .with_visibility(self.get_visibility(&declaration, source));
+                        }
+
+                        // Add documentation
+                        if let Some(docs) = extract_preceding_comments(&declaration, source) {
+                            outline_node = outline_node.with_documentation(docs);
+                        }
+
+                        symbols.push(outline_node);
+                    }
+                }
+            }
+        }
+
+        // Sort symbols by line number
+        symbols.sort_by_key(|s| s.start_line);
+
+        Ok(symbols)
+    }
+
+    fn extract_documentation(&self, node: &Node, source: &str) -> Option<String> {
+        extract_preceding_comments(&self.declaration_node(node), source)
+    }
+
+    fn extract_signature(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "function_definition" | "preproc_function_def" => {
+                Some(self.build_function_signature(node, source))
+            }
+            "struct_specifier" | "union_specifier" | "enum_specifier" => {
+                Some(self.build_type_signature(node, source))
+            }
+            "preproc_def" => Some(self.build_macro_signature(node, source)),
+            "preproc_include" => Some(self.build_include_signature(node, source)),
+            _ => match self.declaration_node(node).kind() {
+                "type_definition" => Some(self.build_type_signature(node, source)),
+                "declaration" | "field_declaration" => {
+                    Some(self.build_declarator_signature(node, source))
+                }
+                _ => None,
+            },
+        }
+    }
+
+    fn extract_visibility(&self, node: &Node, source: &str) -> Option<Visibility> {
+        Some(self.get_visibility(&self.declaration_node(node), source))
+    }
+
+    fn build_hierarchy(&self, symbols: Vec<OutlineNode>) -> Vec<OutlineNode> {
+        // Nest each symbol under the innermost symbol that contains it
+        let mut symbols = symbols;
+        symbols.sort_by(|a, b| {
+            a.source_range
+                .0
+                .cmp(&b.source_range.0)
+                .then(b.source_range.1.cmp(&a.source_range.1))
+        });
+
+        let mut roots: Vec<OutlineNode> = Vec::new();
+        let mut stack: Vec<OutlineNode> = Vec::new();
+
+        for symbol in symbols {
+            while let Some(top) = stack.last() {
+                if Self::is_symbol_within_range(&symbol, top) {
+                    break;
+                }
+                let finished = stack.pop().expect("stack is not empty");
+                match stack.last_mut() {
+                    Some(parent) => parent.add_child(finished),
+                    None => roots.push(finished),
+                }
+            }
+            stack.push(symbol);
+        }
+
+        while let Some(finished) = stack.pop() {
+            match stack.last_mut() {
+                Some(parent) => parent.add_child(finished),
+                None => roots.push(finished),
+            }
+        }
+
+        roots
+    }
+
+    fn get_queries(&self) -> Vec<(&'static str, OutlineNodeType)> {
+        vec![
+            // Function definitions
+            ("(function_definition) @function", OutlineNodeType::Function),
+            // Prototypes and function-like macros
+            (
+                "[(declaration declarator: (_) @prototype) (preproc_function_def) @macro]",
+                OutlineNodeType::Function,
+            ),
+            // Structs and unions
+            (
+                "[(struct_specifier name: (_) body: (_)) (union_specifier name: (_) body: (_))] @struct",
+                OutlineNodeType::Struct,
+            ),
+            // Enums
+            (
+                "(enum_specifier name: (_) body: (_)) @enum",
+                OutlineNodeType::Enum,
+            ),
+            // Typedefs
+            (
+                "(type_definition declarator: (_) @typedef)",
+                OutlineNodeType::TypeAlias,
+            ),
+            // Struct fields
+            (
+                "(field_declaration declarator: (_) @field)",
+                OutlineNodeType::Property,
+            ),
+            // Global variables
+            (
+                "(declaration declarator: (_) @variable)",
+                OutlineNodeType::Variable,
+            ),
+            // Object-like macros
+            (
+                "(preproc_def value: (_)) @constant",
+                OutlineNodeType::Constant,
+            ),
+            // Includes
+            ("(preproc_include) @include", OutlineNodeType::Import),
+        ]
+    }
+}
+
+impl Default for CExtractor {
+    fn default() -> Self {
+        Self::new().expect("Failed to create CExtractor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_c::LANGUAGE.into())
+            .unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_c_extractor_creation() {
+        let extractor = CExtractor::new();
+        assert!(extractor.is_ok());
+    }
+
+    #[test]
+    fn test_extract_simple_function() {
+        let extractor = CExtractor::new().unwrap();
+        let source = r#"
+/**
+ * Adds two numbers.
+ */
+static int add(int a, int b) {
+    int sum = a + b;
+    return sum;
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        let func = &symbols[0];
+        assert_eq!(func.name, "add");
+        assert_eq!(func.node_type, OutlineNodeType::Function);
+        assert_eq!(func.visibility, Some(Visibility::Private));
+        assert_eq!(
+            func.signature.as_deref(),
+            Some("static int add(int a, int b)")
+        );
+        assert_eq!(func.documentation.as_deref(), Some("Adds two numbers."));
+    }
+
+    #[test]
+    fn test_extract_struct() {
+        let extractor = CExtractor::new().unwrap();
+        let source = r#"
+/* A point in the plane. */
+struct point {
+    int x;
+    int y;
+};
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("point", &OutlineNodeType::Struct),
+                ("x", &OutlineNodeType::Property),
+                ("y", &OutlineNodeType::Property),
+            ]
+        );
+        assert_eq!(symbols[0].signature.as_deref(), Some("struct point"));
+        assert_eq!(
+            symbols[0].documentation.as_deref(),
+            Some("A point in the plane.")
+        );
+        assert_eq!(symbols[1].signature.as_deref(), Some("int x"));
+    }
+
+    #[test]
+    fn test_extract_prototypes_and_pointers() {
+        let extractor = CExtractor::new().unwrap();
+        let source = r#"
+int *make(void);
+int sum(int count, ...);
+char *name(const char *s) { return 0; }
+int (*callback)(int);
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("make", &OutlineNodeType::Function),
+                ("sum", &OutlineNodeType::Function),
+                ("name", &OutlineNodeType::Function),
+                // A function pointer is a variable
+                ("callback", &OutlineNodeType::Variable),
+            ]
+        );
+        assert_eq!(symbols[0].signature.as_deref(), Some("int *make(void)"));
+        assert_eq!(symbols[3].visibility, Some(Visibility::Public));
+    }
+
+    #[test]
+    fn test_extract_multiple_symbols() {
+        let extractor = CExtractor::new().unwrap();
+        let source = r#"
+#ifndef UTIL_H
+#define UTIL_H
+
+#include <stdio.h>
+#include "util.h"
+
+#define MAX_SIZE 10
+#define SQUARE(x) ((x) * (x))
+
+typedef struct { int a; } anon_t;
+
+enum color { RED, GREEN };
+
+static const int limit = 5;
+extern int counter;
+
+#endif
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("stdio.h", &OutlineNodeType::Import),
+                ("util.h", &OutlineNodeType::Import),
+                ("MAX_SIZE", &OutlineNodeType::Constant),
+                ("SQUARE", &OutlineNodeType::Function),
+                ("anon_t", &OutlineNodeType::TypeAlias),
+                ("a", &OutlineNodeType::Property),
+                ("color", &OutlineNodeType::Enum),
+                ("limit", &OutlineNodeType::Constant),
+                ("counter", &OutlineNodeType::Variable),
+            ]
+        );
+
+        let max_size = &symbols[2];
+        assert_eq!(max_size.signature.as_deref(), Some("#define MAX_SIZE 10"));
+        assert_eq!(max_size.start_line, max_size.end_line);
+        assert_eq!(symbols[3].signature.as_deref(), Some("#define SQUARE(x)"));
+        assert_eq!(
+            symbols[4].signature.as_deref(),
+            Some("typedef struct anon_t")
+        );
+        assert_eq!(
+            symbols[7].signature.as_deref(),
+            Some("static const int limit")
+        );
+        assert_eq!(symbols[7].visibility, Some(Visibility::Private));
+    }
+
+    #[test]
+    fn test_extract_function_signature_details() {
+        let extractor = CExtractor::new().unwrap();
+        let source = "const char *join(const char **parts, int count, ...) { return 0; }";
+
+        let tree = parse(source);
+        let function = tree.root_node().named_child(0).unwrap();
+        let signature = extractor
+            .extract_function_signature(&function, source)
+            .unwrap();
+
+        assert_eq!(signature.name, "join");
+        assert_eq!(signature.parameters.len(), 3);
+        assert_eq!(signature.parameters[0].name, "parts");
+        assert!(signature.parameters[2].is_variadic);
+        assert_eq!(signature.return_type.unwrap().name, "char*");
+        assert!(signature.modifiers.contains(&Modifier::Const));
+    }
+
+    #[test]
+    fn test_hierarchical_relationships() {
+        let extractor = CExtractor::new().unwrap();
+        let source = r#"
+struct outer {
+    struct inner {
+        int depth;
+    } nested;
+    int size;
+};
+
+int main(void) {
+    int local = 0;
+    return local;
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+        let hierarchical_symbols = extractor.build_hierarchy(symbols);
+
+        assert_eq!(hierarchical_symbols.len(), 2);
+        let outer = &hierarchical_symbols[0];
+        assert_eq!(outer.name, "outer");
+        let child_names: Vec<&str> = outer.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(child_names, vec!["nested", "size"]);
+        assert_eq!(outer.children[0].children[0].name, "inner");
+        assert_eq!(
+            outer.children[0].signature.as_deref(),
+            Some("struct inner nested")
+        );
+
+        // Locals are not symbols
+        assert_eq!(hierarchical_symbols[1].name, "main");
+        assert!(hierarchical_symbols[1].children.is_empty());
+    }
+}
@@ -0,0 +1,1123 @@
+//! C++ language symbol extractor for outline generation
+//!
+//! This module implements Tree-sitter based symbol extraction for C++ code,
+//! supporting namespaces, classes, structs, unions, enums, templates, methods
+//! declared in and out of their class, fields, type aliases, global variables,
+//! macros and includes, along with their comments, signatures and access
+//! specifiers.
+
+use super::c::{
+    collapse_whitespace, extract_preceding_comments, find_function_declarator,
+    innermost_declarator, is_local, line_range, text_without_body,
+};
+use crate::outline::parser::SymbolExtractor;
+use crate::outline::signature::{
+    GenericParameter, Modifier, Parameter, Signature, SignatureExtractor, TypeInfo,
+};
+use crate::outline::types::{OutlineNode, OutlineNodeType, Visibility};
+use crate::outline::{OutlineError, Result};
+use crate::search::types::Language;
+use tree_sitter::{Node, Query, QueryCursor, StreamingIterator, Tree};
+
+/// C++ symbol extractor using Tree-sitter
+pub struct CppExtractor {
+    /// Tree-sitter queries with their associated node types
+    queries: Vec<(OutlineNodeType, Query)>,
+}
+
+impl CppExtractor {
+    /// Create a new C++ extractor with compiled queries
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_cpp::LANGUAGE.into();
+        let mut queries = Vec::new();
+
+        // Define Tree-sitter queries for each C++ construct
+        let query_definitions = vec![
+            (
+                OutlineNodeType::Module,
+                r#"(namespace_definition name: (_)) @namespace"#,
+            ),
+            (
+                OutlineNodeType::Class,
+                r#"(class_specifier name: (_) body: (_)) @class"#,
+            ),
+            (
+                OutlineNodeType::Struct,
+                r#"[(struct_specifier name: (_) body: (_)) (union_specifier name: (_) body: (_))] @struct"#,
+            ),
+            (
+                OutlineNodeType::Enum,
+                r#"(enum_specifier name: (_) body: (_)) @enum"#,
+            ),
+            // Definitions, prototypes and in-class declarations; members become methods
+            (
+                OutlineNodeType::Function,
+                r#"[(function_definition) @function (declaration declarator: (_) @prototype) (field_declaration declarator: (_) @method) (preproc_function_def) @macro]"#,
+            ),
+            (
+                OutlineNodeType::Property,
+                r#"(field_declaration declarator: (_) @field)"#,
+            ),
+            (
+                OutlineNodeType::Variable,
+                r#"(declaration declarator: (_) @variable)"#,
+            ),
+            (
+                OutlineNodeType::TypeAlias,
+                r#"[(type_definition declarator: (_) @typedef) (alias_declaration) @alias]"#,
+            ),
+            (
+                OutlineNodeType::Constant,
+                r#"(preproc_def value: (_)) @constant"#,
+            ),
+            (OutlineNodeType::Import, r#"(preproc_include) @include"#),
+        ];
+
+        // Compile all queries
+        for (node_type, query_str) in query_definitions {
+            let query = Query::new(&language, query_str).map_err(|e| {
+                OutlineError::TreeSitter(format!("Failed to compile {node_type:?} query: {e}"))
+            })?;
+            queries.push((node_type, query));
+        }
+
+        Ok(Self { queries })
+    }
+
+    /// Extract the text content of a node
+    fn get_node_text(&self, node: &Node, source: &str) -> String {
+        source[node.start_byte()..node.end_byte()].to_string()
+    }
+
+    /// Extract the name from a Tree-sitter node
+    ///
+    /// Methods defined outside their class keep their qualified name, such
+    /// as `Shape::area`.
+    fn extract_name_from_node(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "function_definition" => {
+                let declarator = node.child_by_field_name("declarator")?;
+                let function = find_function_declarator(declarator)?;
+                let name = function.child_by_field_name("declarator")?;
+                Some(self.get_node_text(&name, source))
+            }
+            "preproc_include" => {
+                let path = node.child_by_field_name("path")?;
+                Some(
+                    self.get_node_text(&path, source)
+                        .trim_matches(['"', '<', '>'])
+                        .to_string(),
+                )
+            }
+            "namespace_definition"
+            | "class_specifier"
+            | "struct_specifier"
+            | "union_specifier"
+            | "enum_specifier"
+            | "alias_declaration"
+            | "preproc_def"
+            | "preproc_function_def" => node
+                .child_by_field_name("name")
+                .map(|name| collapse_whitespace(&self.get_node_text(&name, source))),
+            // Declarators of declarations, fields and typedefs
+            _ => {
+                if let Some(function) = find_function_declarator(*node) {
+                    let name = function.child_by_field_name("declarator")?;
+                    return Some(self.get_node_text(&name, source));
+                }
+                let name = innermost_declarator(*node);
+                matches!(
+                    name.kind(),
+                    "identifier" | "field_identifier" | "type_identifier"
+                )
+                .then(|| self.get_node_text(&name, source))
+            }
+        }
+    }
+
+    /// The node holding a symbol's range and comments
+    ///
+    /// Declarators belong to their declaration, and templates include their
+    /// `template <...>` prefix.
+    fn declaration_node<'a>(&self, node: &Node<'a>) -> Node<'a> {
+        let mut declaration = *node;
+        if let Some(parent) = node.parent() {
+            // A class defined in a declaration's type keeps its own range
+            if matches!(
+                parent.kind(),
+                "declaration" | "field_declaration" | "type_definition"
+            ) && parent.child_by_field_name("type") != Some(*node)
+            {
+                declaration = parent;
+            }
+        }
+        match declaration.parent() {
+            Some(parent) if parent.kind() == "template_declaration" => parent,
+            _ => declaration,
+        }
+    }
+
+    /// The direct child of a class body that holds a declaration
+    ///
+    /// `struct Config { ... };` inside a class is a field declaration
+    /// without a declarator, whose type is the nested struct.
+    fn member_node<'a>(&self, declaration: &Node<'a>) -> Option<Node<'a>> {
+        let mut member = *declaration;
+        let mut body = member.parent()?;
+        if body.kind() == "field_declaration" {
+            member = body;
+            body = body.parent()?;
+        }
+        (body.kind() == "field_declaration_list").then_some(member)
+    }
+
+    /// The class, struct or union whose body directly holds a declaration
+    fn enclosing_class<'a>(&self, declaration: &Node<'a>) -> Option<Node<'a>> {
+        self.member_node(declaration)?.parent()?.parent()
+    }
+
+    /// Whether a captured node belongs to the query's node type
+    ///
+    /// Declarations are captured by both the function and variable queries,
+    /// and are told apart by whether they declare a function.
+    fn matches_node_type(&self, node_type: &OutlineNodeType, node: &Node) -> bool {
+        match (node_type, node.kind()) {
+            (OutlineNodeType::Function, "function_definition" | "preproc_function_def") => true,
+            (OutlineNodeType::Function, _) => {
+                !is_local(node) && find_function_declarator(*node).is_some()
+            }
+            (OutlineNodeType::Variable, _) => {
+                !is_local(node) && find_function_declarator(*node).is_none()
+            }
+            (OutlineNodeType::Property, _) => find_function_declarator(*node).is_none(),
+            _ => true,
+        }
+    }
+
+    /// Whether a declaration has a `const` or `constexpr` qualifier on a non-pointer value
+    fn is_constant(&self, declaration: &Node, declarator: &Node, source: &str) -> bool {
+        let has_const = declaration.children(&mut declaration.walk()).any(|child| {
+            child.kind() == "type_qualifier"
+                && matches!(
+                    self.get_node_text(&child, source).as_str(),
+                    "const" | "constexpr"
+                )
+        });
+        let value = match declarator.kind() {
+            "init_declarator" => declarator.child_by_field_name("declarator"),
+            _ => Some(*declarator),
+        };
+        has_const && value.is_some_and(|value| value.kind() != "pointer_declarator")
+    }
+
+    /// Get the visibility of a symbol
+    ///
+    /// Class members take the closest preceding access specifier, defaulting
+    /// to private in classes and public in structs and unions. Other functions
+    /// and variables are public unless declared `static`.
+    fn get_visibility(&self, declaration: &Node, source: &str) -> Option<Visibility> {
+        if let (Some(member), Some(class)) = (
+            self.member_node(declaration),
+            self.enclosing_class(declaration),
+        ) {
+            let mut sibling = member.prev_named_sibling();
+            while let Some(node) = sibling {
+                if node.kind() == "access_specifier" {
+                    return match self.get_node_text(&node, source).as_str() {
+                        "public" => Some(Visibility::Public),
+                        "protected" => Some(Visibility::Protected),
+                        _ => Some(Visibility::Private),
+                    };
+                }
+                sibling = node.prev_named_sibling();
+            }
+            return if class.kind() == "class_specifier" {
+                Some(Visibility::Private)
+            } else {
+                Some(Visibility::Public)
+            };
+        }
+
+        let has_static = declaration.children(&mut declaration.walk()).any(|child| {
+            child.kind() == "storage_class_specifier"
+                && self.get_node_text(&child, source) == "static"
+        });
+        if has_static {
+            Some(Visibility::Private)
+        } else {
+            Some(Visibility::Public)
+        }
+    }
+
+    /// Text of the `template <...>` prefix of a templated declaration
+    fn template_prefix(&self, node: &Node, source: &str) -> String {
+        match node.parent() {
+            Some(parent) if parent.kind() == "template_declaration" => parent
+                .child_by_field_name("parameters")
+                .map(|params| format!("template {} ", self.get_node_text(&params, source)))
+                .unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+
+    /// Build function signature, such as `virtual double area() const`
+    fn build_function_signature(&self, node: &Node, source: &str) -> String {
+        match node.kind() {
+            "function_definition" => {
+                // Stop before the body or a constructor's initializer list
+                let end = node
+                    .children(&mut node.walk())
+                    .find(|child| {
+                        matches!(
+                            child.kind(),
+                            "compound_statement" | "field_initializer_list" | "try_statement"
+                        )
+                    })
+                    .map_or(node.end_byte(), |body| body.start_byte());
+                collapse_whitespace(&format!(
+                    "{}{}",
+                    self.template_prefix(node, source),
+                    source[node.start_byte()..end].trim_end_matches(';')
+                ))
+            }
+            "preproc_function_def" => {
+                let end = node
+                    .child_by_field_name("value")
+                    .map_or(node.end_byte(), |value| value.start_byte());
+                collapse_whitespace(&source[node.start_byte()..end])
+            }
+            // A prototype's or member declaration's declarator
+            _ => {
+                let declaration = self.declaration_node(node);
+                let declaration = match declaration.kind() {
+                    "template_declaration" => node.parent().unwrap_or(declaration),
+                    _ => declaration,
+                };
+                collapse_whitespace(&format!(
+                    "{}{}",
+                    self.template_prefix(&declaration, source),
+                    self.build_declarator_signature(node, source)
+                ))
+            }
+        }
+    }
+
+    /// Build the signature of one declarator of a declaration, without its initializer
+    fn build_declarator_signature(&self, node: &Node, source: &str) -> String {
+        let Some(declaration) = node.parent() else {
+            return collapse_whitespace(&self.get_node_text(node, source));
+        };
+        let prefix_end = declaration
+            .child_by_field_name("declarator")
+            .map_or(node.start_byte(), |first| first.start_byte());
+        let declarator = match node.kind() {
+            "init_declarator" => node.child_by_field_name("declarator").unwrap_or(*node),
+            _ => *node,
+        };
+        // `struct inner { int depth; } nested;` gives `struct inner nested`
+        let prefix = match declaration
+            .child_by_field_name("type")
+            .and_then(|ty| ty.child_by_field_name("body"))
+        {
+            Some(body) => format!(
+                "{} {}",
+                &source[declaration.start_byte()..body.start_byte()],
+                &source[body.end_byte()..prefix_end]
+            ),
+            None => source[declaration.start_byte()..prefix_end].to_string(),
+        };
+        collapse_whitespace(&format!(
+            "{prefix} {}",
+            self.get_node_text(&declarator, source)
+        ))
+    }
+
+    /// Build type signature, such as `template <typename T> class Stack : public Base<T>`
+    fn build_type_signature(&self, node: &Node, source: &str) -> String {
+        match node.kind() {
+            "namespace_definition"
+            | "class_specifier"
+            | "struct_specifier"
+            | "union_specifier"
+            | "enum_specifier" => {
+                let end = node
+                    .child_by_field_name("body")
+                    .map_or(node.end_byte(), |body| body.start_byte());
+                collapse_whitespace(&format!(
+                    "{}{}",
+                    self.template_prefix(node, source),
+                    &source[node.start_byte()..end]
+                ))
+            }
+            "alias_declaration" => collapse_whitespace(&format!(
+                "{}{}",
+                self.template_prefix(node, source),
+                self.get_node_text(node, source).trim_end_matches(';')
+            )),
+            // A typedef's declarator
+            _ => match node.parent() {
+                Some(declaration) => {
+                    let body = declaration
+                        .child_by_field_name("type")
+                        .and_then(|ty| ty.child_by_field_name("body"));
+                    text_without_body(&declaration, body, source)
+                }
+                None => collapse_whitespace(&self.get_node_text(node, source)),
+            },
+        }
+    }
+
+    /// Build macro or include signature, such as `#include <string>`
+    fn build_directive_signature(&self, node: &Node, source: &str) -> String {
+        collapse_whitespace(&self.get_node_text(node, source))
+    }
+
+    /// Whether a function is a constructor of the class it is declared in or qualified by
+    fn is_constructor(&self, name: &str, declaration: &Node, source: &str) -> bool {
+        // `Shape::Shape` defined outside its class
+        let mut segments = name.rsplit("::");
+        let last = segments.next().unwrap_or(name);
+        if let Some(scope) = segments.next() {
+            return scope == last;
+        }
+        self.enclosing_class(declaration)
+            .and_then(|class| class.child_by_field_name("name"))
+            .is_some_and(|class_name| self.get_node_text(&class_name, source) == last)
+    }
+
+    /// Check if an inner symbol is within the range of an outer symbol
+    fn is_symbol_within_range(inner: &OutlineNode, outer: &OutlineNode) -> bool {
+        inner.source_range.0 >= outer.source_range.0 && inner.source_range.1 <= outer.source_range.1
+    }
+}
+
+impl SignatureExtractor for CppExtractor {
+    fn extract_function_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        let (declaration, declarator) = match node.kind() {
+            "function_definition" => (*node, node.child_by_field_name("declarator")?),
+            "preproc_function_def" => {
+                let name = self.extract_name_from_node(node, source)?;
+                let mut signature = Signature::new(name, Language::Cpp);
+                if let Some(params) = node.child_by_field_name("parameters") {
+                    for param in params.named_children(&mut params.walk()) {
+                        signature = signature
+                            .with_parameter(Parameter::new(self.get_node_text(&param, source)));
+                    }
+                }
+                return Some(
+                    signature.with_raw_signature(self.build_function_signature(node, source)),
+                );
+            }
+            _ => (node.parent()?, *node),
+        };
+        let function = find_function_declarator(declarator)?;
+        let name = self.get_node_text(&function.child_by_field_name("declarator")?, source);
+
+        let mut signature = Signature::new(name, Language::Cpp);
+
+        let mut modifiers = self.parse_modifiers(&declaration, source);
+        modifiers.extend(self.parse_modifiers(&function, source));
+        signature = signature.with_modifiers(modifiers);
+
+        for generic in self.parse_generic_parameters(&declaration, source) {
+            signature = signature.with_generic(generic);
+        }
+
+        if let Some(params_node) = function.child_by_field_name("parameters") {
+            for param_node in params_node.named_children(&mut params_node.walk()) {
+                if let Some(param) = self.parse_parameter(&param_node, source) {
+                    signature = signature.with_parameter(param);
+                }
+            }
+        }
+
+        if let Some(return_node) = declaration.child_by_field_name("type") {
+            if let Some(mut return_type) = self.parse_type_info(&return_node, source) {
+                // `char *name(...)` returns a pointer, `T& get()` a reference
+                let mut current = declarator;
+                loop {
+                    match current.kind() {
+                        "pointer_declarator" => return_type.name.push('*'),
+                        "reference_declarator" => return_type.name.push('&'),
+                        _ => break,
+                    }
+                    match current
+                        .child_by_field_name("declarator")
+                        .or_else(|| current.named_child(current.named_child_count() - 1))
+                    {
+                        Some(inner) => current = inner,
+                        None => break,
+                    }
+                }
+                signature = signature.with_return_type(return_type);
+            }
+        }
+
+        signature = signature.with_raw_signature(self.build_function_signature(node, source));
+        Some(signature)
+    }
+
+    fn extract_method_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        let signature = self.extract_function_signature(node, source)?;
+        let declaration = self.declaration_node(node);
+        if self.is_constructor(&signature.name, &declaration, source) {
+            Some(signature.constructor())
+        } else {
+            Some(signature)
+        }
+    }
+
+    fn extract_constructor_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        let signature = self.extract_function_signature(node, source)?;
+        let declaration = self.declaration_node(node);
+        self.is_constructor(&signature.name, &declaration, source)
+            .then(|| signature.constructor())
+    }
+
+    fn extract_type_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        let name = self.extract_name_from_node(node, source)?;
+        let mut signature = Signature::new(name, Language::Cpp);
+        for generic in self.parse_generic_parameters(node, source) {
+            signature = signature.with_generic(generic);
+        }
+        signature = signature.with_raw_signature(self.build_type_signature(node, source));
+        Some(signature)
+    }
+
+    fn parse_type_info(&self, node: &Node, source: &str) -> Option<TypeInfo> {
+        match node.kind() {
+            "template_type" => {
+                let base = node.child_by_field_name("name")?;
+                let args = node
+                    .child_by_field_name("arguments")
+                    .map(|args| {
+                        args.named_children(&mut args.walk())
+                            .filter_map(|arg| {
+                                let arg = arg.child_by_field_name("type").unwrap_or(arg);
+                                self.parse_type_info(&arg, source)
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Some(TypeInfo::generic(self.get_node_text(&base, source), args))
+            }
+            _ => {
+                let text = collapse_whitespace(&self.get_node_text(node, source));
+                (!text.is_empty()).then(|| TypeInfo::new(text))
+            }
+        }
+    }
+
+    fn parse_parameter(&self, node: &Node, source: &str) -> Option<Parameter> {
+        match node.kind() {
+            "parameter_declaration"
+            | "optional_parameter_declaration"
+            | "variadic_parameter_declaration" => {
+                let type_node = node.child_by_field_name("type")?;
+                let mut type_info = self.parse_type_info(&type_node, source)?;
+                let Some(declarator) = node.child_by_field_name("declarator") else {
+                    // `(void)` declares no parameters; `(int)` an unnamed one
+                    if type_info.name == "void" {
+                        return None;
+                    }
+                    return Some(Parameter::new("_".to_string()).with_type(type_info));
+                };
+
+                let mut current = declarator;
+                loop {
+                    match current.kind() {
+                        "pointer_declarator" => type_info.name.push('*'),
+                        "reference_declarator" => {
+                            let reference = current.child(0).map_or("&".to_string(), |token| {
+                                self.get_node_text(&token, source)
+                            });
+                            type_info.name.push_str(&reference);
+                        }
+                        _ => break,
+                    }
+                    match current
+                        .child_by_field_name("declarator")
+                        .or_else(|| current.named_child(current.named_child_count() - 1))
+                    {
+                        Some(inner) => current = inner,
+                        None => break,
+                    }
+                }
+
+                let name = innermost_declarator(declarator);
+                let mut param =
+                    Parameter::new(self.get_node_text(&name, source)).with_type(type_info);
+                if let Some(default) = node.child_by_field_name("default_value") {
+                    param = param.with_default(self.get_node_text(&default, source));
+                }
+                if node.kind() == "variadic_parameter_declaration" {
+                    param = param.variadic();
+                }
+                Some(param)
+            }
+            "variadic_parameter" => Some(Parameter::new("...".to_string()).variadic()),
+            _ => None,
+        }
+    }
+
+    fn parse_generic_parameters(&self, node: &Node, source: &str) -> Vec<GenericParameter> {
+        let mut generics = Vec::new();
+
+        let Some(template) = node
+            .parent()
+            .filter(|parent| parent.kind() == "template_declaration")
+        else {
+            return generics;
+        };
+        let Some(params) = template.child_by_field_name("parameters") else {
+            return generics;
+        };
+
+        for param in params.named_children(&mut params.walk()) {
+            let name = match param.kind() {
+                "type_parameter_declaration" | "variadic_type_parameter_declaration" => param
+                    .named_children(&mut param.walk())
+                    .find(|child| child.kind() == "type_identifier")
+                    .map(|name| self.get_node_text(&name, source)),
+                "optional_type_parameter_declaration" => param
+                    .child_by_field_name("name")
+                    .map(|name| self.get_node_text(&name, source)),
+                // Non-type parameters such as `int N = 4`
+                "parameter_declaration" | "optional_parameter_declaration" => param
+                    .child_by_field_name("declarator")
+                    .map(|name| self.get_node_text(&innermost_declarator(name), source)),
+                _ => None,
+            };
+            if let Some(name) = name {
+                generics.push(GenericParameter::new(name));
+            }
+        }
+
+        generics
+    }
+
+    fn parse_modifiers(&self, node: &Node, source: &str) -> Vec<Modifier> {
+        node.children(&mut node.walk())
+            .filter_map(|child| {
+                let text = self.get_node_text(&child, source);
+                match (child.kind(), text.as_str()) {
+                    ("virtual", _) => Some(Modifier::Virtual),
+                    ("storage_class_specifier", "static") => Some(Modifier::Static),
+                    ("storage_class_specifier", "extern") => Some(Modifier::Extern),
+                    ("storage_class_specifier", "inline") => Some(Modifier::Inline),
+                    ("type_qualifier", "const" | "constexpr") => Some(Modifier::Const),
+                    ("virtual_specifier", "override") => Some(Modifier::Override),
+                    ("virtual_specifier", "final") => Some(Modifier::Final),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+impl SymbolExtractor for CppExtractor {
+    fn extract_symbols(&self, tree: &Tree, source: &str) -> Result<Vec<OutlineNode>> {
+        let mut symbols = Vec::new();
+        let root_node = tree.root_node();
+
+        // Process each query
+        for (node_type, query) in &self.queries {
+            let mut cursor = QueryCursor::new();
+            let mut matches = cursor.matches(query, root_node, source.as_bytes());
+
+            while let Some(query_match) = matches.next() {
+                // Get the main captured node (should be the only capture)
+                if let Some(capture) = query_match.captures.first() {
+                    let node = &capture.node;
+
+                    if !self.matches_node_type(node_type, node) {
+                        continue;
+                    }
+
+                    if let Some(name) = self.extract_name_from_node(node, source) {
+                        let declaration = self.declaration_node(node);
+                        let in_class = self.enclosing_class(&declaration).is_some();
+
+                        let symbol_type = match node_type {
+                            OutlineNodeType::Function if in_class => OutlineNodeType::Method,
+                            OutlineNodeType::Variable | OutlineNodeType::Property
+                                if self.is_constant(
+                                    &node.parent().unwrap_or(declaration),
+                                    node,
+                                    source,
+                                ) =>
+                            {
+                                OutlineNodeType::Constant
+                            }
+                            _ => node_type.clone(),
+                        };
+
+                        let (start_line, end_line) = line_range(&declaration);
+                        let mut outline_node = OutlineNode::new(
+                            name,
+                            symbol_type.clone(),
+                            start_line,
+                            end_line,
+                            (declaration.start_byte(), declaration.end_byte()),
+                        );
+
+                        // Add signature based on node type
+                        let signature = match node_type {
+                            OutlineNodeType::Function => {
+                                let signature = if in_class {
+                                    self.extract_method_signature(node, source)
+                                } else {
+                                    self.extract_function_signature(node, source)
+                                };
+                                signature.map(|sig| sig.format_for_language(Language::Cpp))
+                            }
+                            OutlineNodeType::Module
+                            | OutlineNodeType::Class
+                            | OutlineNodeType::Struct
+                            | OutlineNodeType::Enum
+                            | OutlineNodeType::TypeAlias => {
+                                Some(self.build_type_signature(node, source))
+                            }
+                            OutlineNodeType::Variable | OutlineNodeType::Property => {
+                                Some(self.build_declarator_signature(node, source))
+                            }
+                            OutlineNodeType::Constant | OutlineNodeType::Import => {
+                                Some(self.build_directive_signature(node, source))
+                            }
+                            _ => None,
+                        };
+
+                        if let Some(sig) = signature {
+                            outline_node = outline_node.with_signature(sig);
+                        }
+
+                        // Add visibility to class members and symbols with linkage
+                        let has_visibility = in_class
+                            || (matches!(
+                                node_type,
+                                OutlineNodeType::Function | OutlineNodeType::Variable
+                            ) && node.kind() != "preproc_function_def");
+                        if has_visibility {
+                            if let Some(visibility) = self.get_visibility(&declaration, source) {
+                                outline_node = outline_node.with_visibility(visibility);
+                            }
+                        }
+
+                        // Add documentation
+                        if let Some(docs) = extract_preceding_comments(&declaration, source) {
+                            outline_node = outline_node.with_documentation(docs);
+                        }
+
+                        symbols.push(outline_node);
+                    }
+                }
+            }
+        }
+
+        // Sort symbols by line number
+        symbols.sort_by_key(|s| s.start_line);
+
+        Ok(symbols)
+    }
+
+    fn extract_documentation(&self, node: &Node, source: &str) -> Option<String> {
+        extract_preceding_comments(&self.declaration_node(node), source)
+    }
+
+    fn extract_signature(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "function_definition" | "preproc_function_def" => {
+                Some(self.build_function_signature(node, source))
+            }
+            "namespace_definition"
+            | "class_specifier"
+            | "struct_specifier"
+            | "union_specifier"
+            | "enum_specifier"
+            | "alias_declaration" => Some(self.build_type_signature(node, source)),
+            "preproc_def" | "preproc_include" => Some(self.build_directive_signature(node, source)),
+            _ => match node.parent()?.kind() {
+                "type_definition" => Some(self.build_type_signature(node, source)),
+                "declaration" | "field_declaration" => {
+                    if find_function_declarator(*node).is_some() {
+                        Some(self.build_function_signature(node, source))
+                    } else {
+                        Some(self.build_declarator_signature(node, source))
+                    }
+                }
+                _ => None,
+            },
+        }
+    }
+
+    fn extract_visibility(&self, node: &Node, source: &str) -> Option<Visibility> {
+        self.get_visibility(&self.declaration_node(node), source)
+    }
+
+    fn build_hierarchy(&self, symbols: Vec<OutlineNode>) -> Vec<OutlineNode> {
+        // Nest each symbol under the innermost symbol that contains it
+        let mut symbols = symbols;
+        symbols.sort_by(|a, b| {
+            a.source_range
+                .0
+                .cmp(&b.source_range.0)
+                .then(b.source_range.1.cmp(&a.source_range.1))
+        });
+
+        let mut roots: Vec<OutlineNode> = Vec::new();
+        let mut stack: Vec<OutlineNode> = Vec::new();
+
+        for symbol in symbols {
+            while let Some(top) = stack.last() {
+                if Self::is_symbol_within_range(&symbol, top) {
+                    break;
+                }
+                let finished = stack.pop().expect("stack is not empty");
+                match stack.last_mut() {
+                    Some(parent) => parent.add_child(finished),
+                    None => roots.push(finished),
+                }
+            }
+            stack.push(symbol);
+        }
+
+        while let Some(finished) = stack.pop() {
+            match stack.last_mut() {
+                Some(parent) => parent.add_child(finished),
+                None => roots.push(finished),
+            }
+        }
+
+        roots
+    }
+
+    fn get_queries(&self) -> Vec<(&'static str, OutlineNodeType)> {
+        vec![
+            // Namespaces
+            (
+                "(namespace_definition name: (_)) @namespace",
+                OutlineNodeType::Module,
+            ),
+            // Classes
+            (
+                "(class_specifier name: (_) body: (_)) @class",
+                OutlineNodeType::Class,
+            ),
+            // Structs and unions
+            (
+                "[(struct_specifier name: (_) body: (_)) (union_specifier name: (_) body: (_))] @struct",
+                OutlineNodeType::Struct,
+            ),
+            // Enums
+            (
+                "(enum_specifier name: (_) body: (_)) @enum",
+                OutlineNodeType::Enum,
+            ),
+            // Functions and methods
+            (
+                "[(function_definition) @function (declaration declarator: (_) @prototype) (field_declaration declarator: (_) @method) (preproc_function_def) @macro]",
+                OutlineNodeType::Function,
+            ),
+            // Fields
+            (
+                "(field_declaration declarator: (_) @field)",
+                OutlineNodeType::Property,
+            ),
+            // Global variables
+            (
+                "(declaration declarator: (_) @variable)",
+                OutlineNodeType::Variable,
+            ),
+            // Typedefs and aliases
+            (
+                "[(type_definition declarator: (_) @typedef) (alias_declaration) @alias]",
+                OutlineNodeType::TypeAlias,
+            ),
+            // Object-like macros
+            (
+                "(preproc_def value: (_)) @constant",
+                OutlineNodeType::Constant,
+            ),
+            // Includes
+            ("(preproc_include) @include", OutlineNodeType::Import),
+        ]
+    }
+}
+
+impl Default for CppExtractor {
+    fn default() -> Self {
+        Self::new().expect("Failed to create CppExtractor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_cpp::LANGUAGE.into())
+            .unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_cpp_extractor_creation() {
+        let extractor = CppExtractor::new();
+        assert!(extractor.is_ok());
+    }
+
+    #[test]
+    fn test_extract_simple_function() {
+        let extractor = CppExtractor::new().unwrap();
+        let source = r#"
+/// Returns the larger value.
+template <typename T>
+T max_of(const T& a, const T& b) {
+    return a > b ? a : b;
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        let func = &symbols[0];
+        assert_eq!(func.name, "max_of");
+        assert_eq!(func.node_type, OutlineNodeType::Function);
+        assert_eq!(func.visibility, Some(Visibility::Public));
+        assert_eq!(
+            func.signature.as_deref(),
+            Some("template <typename T> T max_of(const T& a, const T& b)")
+        );
+        assert_eq!(
+            func.documentation.as_deref(),
+            Some("Returns the larger value.")
+        );
+        // The range includes the template prefix
+        assert_eq!(func.start_line, 3);
+    }
+
+    #[test]
+    fn test_extract_class() {
+        let extractor = CppExtractor::new().unwrap();
+        let source = r#"
+/// A shape.
+class Shape : public Base {
+    int sides_;
+public:
+    /// Creates a shape.
+    explicit Shape(int sides);
+    virtual ~Shape() = default;
+    virtual double area() const { return 0; }
+protected:
+    static constexpr int kMax = 10;
+};
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Shape", &OutlineNodeType::Class),
+                ("sides_", &OutlineNodeType::Property),
+                ("Shape", &OutlineNodeType::Method),
+                ("~Shape", &OutlineNodeType::Method),
+                ("area", &OutlineNodeType::Method),
+                ("kMax", &OutlineNodeType::Constant),
+            ]
+        );
+
+        let class = &symbols[0];
+        assert_eq!(
+            class.signature.as_deref(),
+            Some("class Shape : public Base")
+        );
+        assert_eq!(class.documentation.as_deref(), Some("A shape."));
+
+        // Class members are private until an access specifier says otherwise
+        assert_eq!(symbols[1].visibility, Some(Visibility::Private));
+        assert_eq!(symbols[2].visibility, Some(Visibility::Public));
+        assert_eq!(
+            symbols[2].signature.as_deref(),
+            Some("explicit Shape(int sides)")
+        );
+        assert_eq!(
+            symbols[2].documentation.as_deref(),
+            Some("Creates a shape.")
+        );
+        assert_eq!(
+            symbols[4].signature.as_deref(),
+            Some("virtual double area() const")
+        );
+        assert_eq!(symbols[5].visibility, Some(Visibility::Protected));
+    }
+
+    #[test]
+    fn test_extract_struct() {
+        let extractor = CppExtractor::new().unwrap();
+        let source = r#"
+struct Point {
+    int x;
+    double length() const;
+};
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 3);
+        assert_eq!(symbols[0].name, "Point");
+        assert_eq!(symbols[0].node_type, OutlineNodeType::Struct);
+        // Struct members are public by default
+        assert_eq!(symbols[1].visibility, Some(Visibility::Public));
+        assert_eq!(symbols[2].name, "length");
+        assert_eq!(symbols[2].node_type, OutlineNodeType::Method);
+        assert_eq!(
+            symbols[2].signature.as_deref(),
+            Some("double length() const")
+        );
+    }
+
+    #[test]
+    fn test_extract_multiple_symbols() {
+        let extractor = CppExtractor::new().unwrap();
+        let source = r#"
+#include <string>
+#include "shape.h"
+
+namespace geo {
+
+using Id = int;
+
+enum class Color : int { Red, Green };
+
+constexpr double kPi = 3.14;
+
+static int counter = 0;
+
+}  // namespace geo
+
+double geo::Shape::perimeter() const { return 1.0; }
+
+Shape::Shape(int s) : sides_(s) {}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("string", &OutlineNodeType::Import),
+                ("shape.h", &OutlineNodeType::Import),
+                ("geo", &OutlineNodeType::Module),
+                ("Id", &OutlineNodeType::TypeAlias),
+                ("Color", &OutlineNodeType::Enum),
+                ("kPi", &OutlineNodeType::Constant),
+                ("counter", &OutlineNodeType::Variable),
+                ("geo::Shape::perimeter", &OutlineNodeType::Function),
+                ("Shape::Shape", &OutlineNodeType::Function),
+            ]
+        );
+
+        assert_eq!(symbols[2].signature.as_deref(), Some("namespace geo"));
+        assert_eq!(symbols[3].signature.as_deref(), Some("using Id = int"));
+        assert_eq!(
+            symbols[4].signature.as_deref(),
+            Some("enum class Color : int")
+        );
+        assert_eq!(symbols[6].visibility, Some(Visibility::Private));
+        assert_eq!(symbols[8].signature.as_deref(), Some("Shape::Shape(int s)"));
+    }
+
+    #[test]
+    fn test_extract_method_signature_details() {
+        let extractor = CppExtractor::new().unwrap();
+        let source = r#"
+class Stack {
+    bool push(std::vector<int>&& values, int count = 1) override;
+};
+        "#;
+
+        let tree = parse(source);
+        let body = tree
+            .root_node()
+            .named_child(0)
+            .unwrap()
+            .child_by_field_name("body")
+            .unwrap();
+        let declarator = body
+            .named_child(0)
+            .unwrap()
+            .child_by_field_name("declarator")
+            .unwrap();
+        let signature = extractor
+            .extract_method_signature(&declarator, source)
+            .unwrap();
+
+        assert_eq!(signature.name, "push");
+        assert_eq!(signature.parameters.len(), 2);
+        assert_eq!(signature.parameters[0].name, "values");
+        assert_eq!(
+            signature.parameters[0].type_info.as_ref().unwrap().name,
+            "std::vector<int>&&"
+        );
+        assert_eq!(signature.parameters[1].default_value.as_deref(), Some("1"));
+        assert!(signature.modifiers.contains(&Modifier::Override));
+        assert!(!signature.is_constructor);
+    }
+
+    #[test]
+    fn test_hierarchical_relationships() {
+        let extractor = CppExtractor::new().unwrap();
+        let source = r#"
+namespace app {
+
+class Server {
+public:
+    void start();
+
+    struct Config {
+        int port;
+    };
+};
+
+void run() {
+    int local = 0;
+}
+
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+        let hierarchical_symbols = extractor.build_hierarchy(symbols);
+
+        assert_eq!(hierarchical_symbols.len(), 1);
+        let namespace = &hierarchical_symbols[0];
+        assert_eq!(namespace.name, "app");
+        let child_names: Vec<&str> = namespace.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(child_names, vec!["Server", "run"]);
+
+        let server = &namespace.children[0];
+        let member_names: Vec<&str> = server.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(member_names, vec!["start", "Config"]);
+        assert_eq!(server.children[1].visibility, Some(Visibility::Public));
+        assert_eq!(server.children[1].children[0].name, "port");
+
+        // Locals are not symbols
+        assert!(namespace.children[1].children.is_empty());
+    }
+}
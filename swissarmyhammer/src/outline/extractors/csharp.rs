@@ -0,0 +1,1009 @@
+//! C# language symbol extractor for outline generation
+//!
+//! This module implements Tree-sitter based symbol extraction for C# code,
+//! supporting namespaces, classes, records, structs, interfaces, enums,
+//! delegates, methods, constructors, properties, fields, events and using
+//! directives, along with their XML doc comments, signatures and access
+//! modifiers.
+
+use super::c::{collapse_whitespace, extract_preceding_comments};
+use crate::outline::parser::SymbolExtractor;
+use crate::outline::signature::{
+    GenericParameter, Modifier, Parameter, Signature, SignatureExtractor, TypeInfo,
+};
+use crate::outline::types::{OutlineNode, OutlineNodeType, Visibility};
+use crate::outline::{OutlineError, Result};
+use crate::search::types::Language;
+use tree_sitter::{Node, Query, QueryCursor, StreamingIterator, Tree};
+
+/// C# symbol extractor using Tree-sitter
+pub struct CSharpExtractor {
+    /// Tree-sitter queries with their associated node types
+    queries: Vec<(OutlineNodeType, Query)>,
+}
+
+impl CSharpExtractor {
+    /// Create a new C# extractor with compiled queries
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_c_sharp::LANGUAGE.into();
+        let mut queries = Vec::new();
+
+        // Define Tree-sitter queries for each C# construct
+        let query_definitions = vec![
+            (
+                OutlineNodeType::Module,
+                r#"[(namespace_declaration) (file_scoped_namespace_declaration)] @namespace"#,
+            ),
+            (
+                OutlineNodeType::Class,
+                r#"[(class_declaration) (record_declaration)] @class"#,
+            ),
+            (OutlineNodeType::Struct, r#"(struct_declaration) @struct"#),
+            (
+                OutlineNodeType::Interface,
+                r#"(interface_declaration) @interface"#,
+            ),
+            (OutlineNodeType::Enum, r#"(enum_declaration) @enum"#),
+            (
+                OutlineNodeType::TypeAlias,
+                r#"(delegate_declaration) @delegate"#,
+            ),
+            (
+                OutlineNodeType::Method,
+                r#"[(method_declaration) (constructor_declaration) (destructor_declaration)] @method"#,
+            ),
+            // Fields and events; constant fields are reclassified in extract_symbols
+            (
+                OutlineNodeType::Property,
+                r#"[(property_declaration) @property (field_declaration (variable_declaration (variable_declarator) @field)) (event_field_declaration (variable_declaration (variable_declarator) @event))]"#,
+            ),
+            (OutlineNodeType::Import, r#"(using_directive) @using"#),
+        ];
+
+        // Compile all queries
+        for (node_type, query_str) in query_definitions {
+            let query = Query::new(&language, query_str).map_err(|e| {
+                OutlineError::TreeSitter(format!("Failed to compile {node_type:?} query: {e}"))
+            })?;
+            queries.push((node_type, query));
+        }
+
+        Ok(Self { queries })
+    }
+
+    /// Extract the text content of a node
+    fn get_node_text(&self, node: &Node, source: &str) -> String {
+        source[node.start_byte()..node.end_byte()].to_string()
+    }
+
+    /// Extract line numbers for a node (1-based)
+    fn get_line_range(&self, node: &Node) -> (usize, usize) {
+        let start_line = node.start_position().row + 1;
+        let end_line = node.end_position().row + 1;
+        (start_line, end_line)
+    }
+
+    /// Extract the name from a Tree-sitter node
+    fn extract_name_from_node(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "using_directive" => {
+                // `using Gen = System.Collections.Generic;` is named by its alias
+                if let Some(alias) = node.child_by_field_name("name") {
+                    return Some(self.get_node_text(&alias, source));
+                }
+                let name = node.named_children(&mut node.walk()).last()?;
+                Some(self.get_node_text(&name, source))
+            }
+            "destructor_declaration" => {
+                let name = node.child_by_field_name("name")?;
+                Some(format!("~{}", self.get_node_text(&name, source)))
+            }
+            _ => node
+                .child_by_field_name("name")
+                .map(|name| collapse_whitespace(&self.get_node_text(&name, source))),
+        }
+    }
+
+    /// The declaration a field's variable declarator belongs to
+    ///
+    /// `private string _name, _alias;` declares two fields that share
+    /// modifiers, type and doc comment.
+    fn declaration_node<'a>(&self, node: &Node<'a>) -> Node<'a> {
+        if node.kind() == "variable_declarator" {
+            if let Some(declaration) = node.parent().and_then(|variables| variables.parent()) {
+                return declaration;
+            }
+        }
+        *node
+    }
+
+    /// Byte range of a symbol
+    ///
+    /// A file-scoped namespace declaration covers the rest of the file.
+    fn source_range(&self, node: &Node) -> (usize, usize) {
+        if node.kind() == "file_scoped_namespace_declaration" {
+            let mut root = *node;
+            while let Some(parent) = root.parent() {
+                root = parent;
+            }
+            return (node.start_byte(), root.end_byte());
+        }
+        (node.start_byte(), node.end_byte())
+    }
+
+    /// Keyword modifiers of a declaration, such as `public` or `static`
+    fn modifier_keywords(&self, node: &Node, source: &str) -> Vec<String> {
+        node.children(&mut node.walk())
+            .filter(|child| child.kind() == "modifier")
+            .map(|child| self.get_node_text(&child, source))
+            .collect()
+    }
+
+    /// Parse the access modifier of a declaration
+    ///
+    /// Types in a namespace default to internal, interface members to public
+    /// and other members to private.
+    fn get_visibility(&self, node: &Node, source: &str) -> Visibility {
+        let keywords = self.modifier_keywords(node, source);
+        if keywords.iter().any(|k| k == "public") {
+            return Visibility::Public;
+        }
+        if keywords.iter().any(|k| k == "protected") {
+            return Visibility::Protected;
+        }
+        if keywords.iter().any(|k| k == "internal") {
+            return Visibility::Package;
+        }
+        if keywords.iter().any(|k| k == "private") {
+            return Visibility::Private;
+        }
+
+        let container = node
+            .parent()
+            .map(|parent| match parent.kind() {
+                "declaration_list" => parent.parent().map_or("", |owner| owner.kind()),
+                kind => kind,
+            })
+            .unwrap_or("");
+        match container {
+            "compilation_unit" | "namespace_declaration" => Visibility::Package,
+            "interface_declaration" => Visibility::Public,
+            _ => Visibility::Private,
+        }
+    }
+
+    /// Extract the `///` XML doc comment directly above a node, without its tags
+    fn extract_doc_comments(&self, node: &Node, source: &str) -> Option<String> {
+        let comments = extract_preceding_comments(node, source)?;
+
+        let mut text = String::new();
+        let mut in_tag = false;
+        for c in comments.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' if in_tag => {
+                    in_tag = false;
+                    text.push(' ');
+                }
+                _ if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+
+        let text = collapse_whitespace(&text);
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// Collapse a declaration's text up to its body into one line, skipping attributes
+    fn build_header(&self, node: &Node, source: &str) -> String {
+        let start = node
+            .children(&mut node.walk())
+            .find(|child| child.kind() != "attribute_list")
+            .map_or(node.start_byte(), |child| child.start_byte());
+        let end = node
+            .children(&mut node.walk())
+            .find(|child| {
+                matches!(
+                    child.kind(),
+                    "declaration_list"
+                        | "block"
+                        | "arrow_expression_clause"
+                        | "accessor_list"
+                        | "constructor_initializer"
+                        | "enum_member_declaration_list"
+                        | ";"
+                        | "="
+                )
+            })
+            .map_or(node.end_byte(), |body| body.start_byte());
+        collapse_whitespace(&source[start..end])
+    }
+
+    /// Build method signature, such as `public static async Task<int> RunAsync(int x)`
+    fn build_method_signature(&self, node: &Node, source: &str) -> String {
+        self.build_header(node, source)
+    }
+
+    /// Build type signature, such as `public sealed class User<T> : Base where T : class`
+    fn build_type_signature(&self, node: &Node, source: &str) -> String {
+        self.build_header(node, source)
+    }
+
+    /// Build property signature, such as `public string Name { get; set; }`
+    fn build_property_signature(&self, node: &Node, source: &str) -> String {
+        let header = self.build_header(node, source);
+        let accessors: Vec<String> = match node.child_by_field_name("accessors") {
+            Some(list) => list
+                .named_children(&mut list.walk())
+                .filter_map(|accessor| accessor.child_by_field_name("name"))
+                .map(|name| format!("{}; ", self.get_node_text(&name, source)))
+                .collect(),
+            // Expression-bodied properties are read-only
+            None => vec!["get; ".to_string()],
+        };
+        format!("{header} {{ {}}}", accessors.concat())
+    }
+
+    /// Build field signature, such as `private readonly string _name`
+    fn build_field_signature(&self, node: &Node, source: &str) -> Option<String> {
+        let declaration = self.declaration_node(node);
+        let variables = node.parent()?;
+        let field_type = variables.child_by_field_name("type")?;
+        let name = self.extract_name_from_node(node, source)?;
+
+        let start = declaration
+            .children(&mut declaration.walk())
+            .find(|child| child.kind() != "attribute_list")
+            .map_or(declaration.start_byte(), |child| child.start_byte());
+        Some(collapse_whitespace(&format!(
+            "{} {} {name}",
+            &source[start..variables.start_byte()],
+            self.get_node_text(&field_type, source)
+        )))
+    }
+
+    /// Build using signature, such as `using System.Linq`
+    fn build_using_signature(&self, node: &Node, source: &str) -> String {
+        collapse_whitespace(self.get_node_text(node, source).trim_end_matches(';'))
+    }
+
+    /// Check if an inner symbol is within the range of an outer symbol
+    fn is_symbol_within_range(inner: &OutlineNode, outer: &OutlineNode) -> bool {
+        inner.source_range.0 >= outer.source_range.0 && inner.source_range.1 <= outer.source_range.1
+    }
+}
+
+impl SignatureExtractor for CSharpExtractor {
+    fn extract_function_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        if !matches!(node.kind(), "method_declaration" | "delegate_declaration") {
+            return None;
+        }
+
+        let name = self.extract_name_from_node(node, source)?;
+        let mut signature = Signature::new(name, Language::CSharp);
+
+        signature = signature.with_modifiers(self.parse_modifiers(node, source));
+        if self
+            .modifier_keywords(node, source)
+            .iter()
+            .any(|k| k == "async")
+        {
+            signature = signature.async_function();
+        }
+
+        for generic in self.parse_generic_parameters(node, source) {
+            signature = signature.with_generic(generic);
+        }
+
+        for param in self.parse_parameter_list(node, source) {
+            signature = signature.with_parameter(param);
+        }
+
+        let return_node = node
+            .child_by_field_name("returns")
+            .or_else(|| node.child_by_field_name("type"));
+        if let Some(return_type) = return_node.and_then(|ty| self.parse_type_info(&ty, source)) {
+            signature = signature.with_return_type(return_type);
+        }
+
+        signature = signature.with_raw_signature(self.build_method_signature(node, source));
+        Some(signature)
+    }
+
+    fn extract_method_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        match node.kind() {
+            "constructor_declaration" | "destructor_declaration" => {
+                self.extract_constructor_signature(node, source)
+            }
+            _ => self.extract_function_signature(node, source),
+        }
+    }
+
+    fn extract_constructor_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        if !matches!(
+            node.kind(),
+            "constructor_declaration" | "destructor_declaration"
+        ) {
+            return None;
+        }
+
+        let name = self.extract_name_from_node(node, source)?;
+        let mut signature = Signature::new(name, Language::CSharp);
+        if node.kind() == "constructor_declaration" {
+            signature = signature.constructor();
+        }
+        signature = signature.with_modifiers(self.parse_modifiers(node, source));
+
+        for param in self.parse_parameter_list(node, source) {
+            signature = signature.with_parameter(param);
+        }
+
+        signature = signature.with_raw_signature(self.build_method_signature(node, source));
+        Some(signature)
+    }
+
+    fn extract_type_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        let name = self.extract_name_from_node(node, source)?;
+        let mut signature = Signature::new(name, Language::CSharp);
+        signature = signature.with_modifiers(self.parse_modifiers(node, source));
+        for generic in self.parse_generic_parameters(node, source) {
+            signature = signature.with_generic(generic);
+        }
+        signature = signature.with_raw_signature(self.build_type_signature(node, source));
+        Some(signature)
+    }
+
+    fn parse_type_info(&self, node: &Node, source: &str) -> Option<TypeInfo> {
+        match node.kind() {
+            "generic_name" => {
+                let base = node.named_child(0)?;
+                let args = node
+                    .named_children(&mut node.walk())
+                    .find(|child| child.kind() == "type_argument_list")
+                    .map(|args| {
+                        args.named_children(&mut args.walk())
+                            .filter_map(|arg| self.parse_type_info(&arg, source))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Some(TypeInfo::generic(self.get_node_text(&base, source), args))
+            }
+            "array_type" => {
+                let element = node.child_by_field_name("type")?;
+                let element_type = self.parse_type_info(&element, source)?;
+                Some(TypeInfo::array(element_type, 1))
+            }
+            "nullable_type" => {
+                let inner = node.named_child(0)?;
+                let mut type_info = self.parse_type_info(&inner, source)?;
+                type_info.is_nullable = true;
+                Some(type_info)
+            }
+            _ => {
+                let text = self.get_node_text(node, source);
+                (!text.is_empty()).then(|| TypeInfo::new(text))
+            }
+        }
+    }
+
+    fn parse_parameter(&self, node: &Node, source: &str) -> Option<Parameter> {
+        if node.kind() != "parameter" {
+            return None;
+        }
+
+        let name = node.child_by_field_name("name")?;
+        let mut param = Parameter::new(self.get_node_text(&name, source));
+        if let Some(type_info) = node
+            .child_by_field_name("type")
+            .and_then(|ty| self.parse_type_info(&ty, source))
+        {
+            param = param.with_type(type_info);
+        }
+        // `int count = 1`
+        if let Some(default) = node
+            .named_children(&mut node.walk())
+            .find(|child| child.kind() == "equals_value_clause")
+            .and_then(|clause| clause.named_child(0))
+        {
+            param = param.with_default(self.get_node_text(&default, source));
+        }
+        Some(param)
+    }
+
+    fn parse_generic_parameters(&self, node: &Node, source: &str) -> Vec<GenericParameter> {
+        let mut generics = Vec::new();
+
+        let Some(type_params) = node
+            .named_children(&mut node.walk())
+            .find(|child| child.kind() == "type_parameter_list")
+        else {
+            return generics;
+        };
+
+        // Constraints come from `where T : class, new()` clauses
+        let constraints: Vec<(String, Vec<String>)> = node
+            .named_children(&mut node.walk())
+            .filter(|child| child.kind() == "type_parameter_constraints_clause")
+            .filter_map(|clause| {
+                let mut cursor = clause.walk();
+                let mut children = clause.named_children(&mut cursor);
+                let target = children.next()?;
+                let bounds = children
+                    .map(|bound| self.get_node_text(&bound, source))
+                    .collect();
+                Some((self.get_node_text(&target, source), bounds))
+            })
+            .collect();
+
+        for param in type_params.named_children(&mut type_params.walk()) {
+            if let Some(name) = param.child_by_field_name("name") {
+                let name = self.get_node_text(&name, source);
+                let bounds = constraints
+                    .iter()
+                    .find(|(target, _)| *target == name)
+                    .map(|(_, bounds)| bounds.clone())
+                    .unwrap_or_default();
+                generics.push(GenericParameter::new(name).with_bounds(bounds));
+            }
+        }
+
+        generics
+    }
+
+    fn parse_modifiers(&self, node: &Node, source: &str) -> Vec<Modifier> {
+        self.modifier_keywords(node, source)
+            .iter()
+            .filter_map(|keyword| match keyword.as_str() {
+                "public" => Some(Modifier::Public),
+                "private" => Some(Modifier::Private),
+                "protected" => Some(Modifier::Protected),
+                "static" => Some(Modifier::Static),
+                "abstract" => Some(Modifier::Abstract),
+                "sealed" => Some(Modifier::Final),
+                "async" => Some(Modifier::Async),
+                "const" => Some(Modifier::Const),
+                "readonly" => Some(Modifier::Readonly),
+                "override" => Some(Modifier::Override),
+                "virtual" => Some(Modifier::Virtual),
+                "unsafe" => Some(Modifier::Unsafe),
+                "extern" => Some(Modifier::Extern),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl CSharpExtractor {
+    /// Parse the parameters of a method, constructor or delegate
+    ///
+    /// A `params string[] rest` parameter is not wrapped in a parameter node,
+    /// so its type and name are read from the tokens after `params`.
+    fn parse_parameter_list(&self, node: &Node, source: &str) -> Vec<Parameter> {
+        let Some(params_node) = node.child_by_field_name("parameters") else {
+            return Vec::new();
+        };
+
+        let mut parameters = Vec::new();
+        let mut cursor = params_node.walk();
+        let mut children = params_node.children(&mut cursor);
+        while let Some(child) = children.next() {
+            match child.kind() {
+                "parameter" => parameters.extend(self.parse_parameter(&child, source)),
+                "params" => {
+                    let type_node = children.next();
+                    let name_node = children.next();
+                    if let Some(name_node) = name_node {
+                        let mut param =
+                            Parameter::new(self.get_node_text(&name_node, source)).variadic();
+                        if let Some(type_info) =
+                            type_node.and_then(|ty| self.parse_type_info(&ty, source))
+                        {
+                            param = param.with_type(type_info);
+                        }
+                        parameters.push(param);
+                    }
+                }
+                _ => {}
+            }
+        }
+        parameters
+    }
+}
+
+impl SymbolExtractor for CSharpExtractor {
+    fn extract_symbols(&self, tree: &Tree, source: &str) -> Result<Vec<OutlineNode>> {
+        let mut symbols = Vec::new();
+        let root_node = tree.root_node();
+
+        // Process each query
+        for (node_type, query) in &self.queries {
+            let mut cursor = QueryCursor::new();
+            let mut matches = cursor.matches(query, root_node, source.as_bytes());
+
+            while let Some(query_match) = matches.next() {
+                // Get the main captured node (should be the only capture)
+                if let Some(capture) = query_match.captures.first() {
+                    let node = &capture.node;
+
+                    if let Some(name) = self.extract_name_from_node(node, source) {
+                        // Fields are ranged and documented by their whole declaration
+                        let declaration = self.declaration_node(node);
+                        let is_const = self
+                            .modifier_keywords(&declaration, source)
+                            .iter()
+                            .any(|k| k == "const");
+                        let symbol_type = match node_type {
+                            OutlineNodeType::Property if is_const => OutlineNodeType::Constant,
+                            _ => node_type.clone(),
+                        };
+
+                        let range = self.source_range(&declaration);
+                        let (start_line, _) = self.get_line_range(&declaration);
+                        let end_line = source[..range.1].lines().count().max(start_line);
+                        let mut outline_node =
+                            OutlineNode::new(name, symbol_type, start_line, end_line, range);
+
+                        // Add signature based on node type
+                        let signature = match node_type {
+                            OutlineNodeType::Method => self
+                                .extract_method_signature(node, source)
+                                .map(|sig| sig.format_for_language(Language::CSharp)),
+                            OutlineNodeType::Property => match node.kind() {
+                                "property_declaration" => {
+                                    Some(self.build_property_signature(node, source))
+                                }
+                                _ => self.build_field_signature(node, source),
+                            },
+                            OutlineNodeType::Import => {
+                                Some(self.build_using_signature(node, source))
+                            }
+                            _ => self
+                                .extract_type_signature(node, source)
+                                .map(|sig| sig.format_for_language(Language::CSharp)),
+                        };
+
+                        if let Some(sig) = signature {
+                            outline_node = outline_node.with_signature(sig);
+                        }
+
+                        // Add visibility
+                        if !matches!(node_type, OutlineNodeType::Import | OutlineNodeType::Module) {
+                            outline_node = outline_node
+                                .with_visibility(self.get_visibility(&declaration, source));
+                        }
+
+                        // Add documentation
+                        if let Some(docs) = self.extract_doc_comments(&declaration, source) {
+                            outline_node = outline_node.with_documentation(docs);
+                        }
+
+                        symbols.push(outline_node);
+                    }
+                }
+            }
+        }
+
+        // Sort symbols by line number
+        symbols.sort_by_key(|s| s.start_line);
+
+        Ok(symbols)
+    }
+
+    fn extract_documentation(&self, node: &Node, source: &str) -> Option<String> {
+        self.extract_doc_comments(&self.declaration_node(node), source)
+    }
+
+    fn extract_signature(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "method_declaration" | "constructor_declaration" | "destructor_declaration" => {
+                Some(self.build_method_signature(node, source))
+            }
+            "property_declaration" => Some(self.build_property_signature(node, source)),
+            "variable_declarator" => self.build_field_signature(node, source),
+            "using_directive" => Some(self.build_using_signature(node, source)),
+            "namespace_declaration"
+            | "file_scoped_namespace_declaration"
+            | "class_declaration"
+            | "record_declaration"
+            | "struct_declaration"
+            | "interface_declaration"
+            | "enum_declaration"
+            | "delegate_declaration" => Some(self.build_type_signature(node, source)),
+            _ => None,
+        }
+    }
+
+    fn extract_visibility(&self, node: &Node, source: &str) -> Option<Visibility> {
+        Some(self.get_visibility(&self.declaration_node(node), source))
+    }
+
+    fn build_hierarchy(&self, symbols: Vec<OutlineNode>) -> Vec<OutlineNode> {
+        // Nest each symbol under the innermost symbol that contains it
+        let mut symbols = symbols;
+        symbols.sort_by(|a, b| {
+            a.source_range
+                .0
+                .cmp(&b.source_range.0)
+                .then(b.source_range.1.cmp(&a.source_range.1))
+        });
+
+        let mut roots: Vec<OutlineNode> = Vec::new();
+        let mut stack: Vec<OutlineNode> = Vec::new();
+
+        for symbol in symbols {
+            while let Some(top) = stack.last() {
+                if Self::is_symbol_within_range(&symbol, top) {
+                    break;
+                }
+                let finished = stack.pop().expect("stack is not empty");
+                match stack.last_mut() {
+                    Some(parent) => parent.add_child(finished),
+                    None => roots.push(finished),
+                }
+            }
+            stack.push(symbol);
+        }
+
+        while let Some(finished) = stack.pop() {
+            match stack.last_mut() {
+                Some(parent) => parent.add_child(finished),
+                None => roots.push(finished),
+            }
+        }
+
+        roots
+    }
+
+    fn get_queries(&self) -> Vec<(&'static str, OutlineNodeType)> {
+        vec![
+            // Namespaces
+            (
+                "[(namespace_declaration) (file_scoped_namespace_declaration)] @namespace",
+                OutlineNodeType::Module,
+            ),
+            // Classes and records
+            (
+                "[(class_declaration) (record_declaration)] @class",
+                OutlineNodeType::Class,
+            ),
+            // Structs
+            ("(struct_declaration) @struct", OutlineNodeType::Struct),
+            // Interfaces
+            (
+                "(interface_declaration) @interface",
+                OutlineNodeType::Interface,
+            ),
+            // Enums
+            ("(enum_declaration) @enum", OutlineNodeType::Enum),
+            // Delegates
+            (
+                "(delegate_declaration) @delegate",
+                OutlineNodeType::TypeAlias,
+            ),
+            // Methods, constructors and destructors
+            (
+                "[(method_declaration) (constructor_declaration) (destructor_declaration)] @method",
+                OutlineNodeType::Method,
+            ),
+            // Properties, fields and events
+            (
+                "[(property_declaration) @property (field_declaration (variable_declaration (variable_declarator) @field)) (event_field_declaration (variable_declaration (variable_declarator) @event))]",
+                OutlineNodeType::Property,
+            ),
+            // Using directives
+            ("(using_directive) @using", OutlineNodeType::Import),
+        ]
+    }
+}
+
+impl Default for CSharpExtractor {
+    fn default() -> Self {
+        Self::new().expect("Failed to create CSharpExtractor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_c_sharp::LANGUAGE.into())
+            .unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_csharp_extractor_creation() {
+        let extractor = CSharpExtractor::new();
+        assert!(extractor.is_ok());
+    }
+
+    #[test]
+    fn test_extract_simple_method() {
+        let extractor = CSharpExtractor::new().unwrap();
+        let source = r#"
+class Greeter
+{
+    /// <summary>
+    /// Greets someone.
+    /// </summary>
+    [Obsolete]
+    public static async Task<string> GreetAsync(string name, int times = 1)
+    {
+        return "Hello, " + name;
+    }
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let method = symbols
+            .iter()
+            .find(|s| s.name == "GreetAsync")
+            .expect("Should find GreetAsync method");
+        assert_eq!(method.node_type, OutlineNodeType::Method);
+        assert_eq!(method.visibility, Some(Visibility::Public));
+        assert_eq!(
+            method.signature.as_deref(),
+            Some("public static async Task<string> GreetAsync(string name, int times = 1)")
+        );
+        assert_eq!(method.documentation.as_deref(), Some("Greets someone."));
+    }
+
+    #[test]
+    fn test_extract_class() {
+        let extractor = CSharpExtractor::new().unwrap();
+        let source = r#"
+/// <summary>A user.</summary>
+[Serializable]
+public sealed class User<T> : Base, IUser where T : class
+{
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        let class = &symbols[0];
+        assert_eq!(class.name, "User");
+        assert_eq!(class.node_type, OutlineNodeType::Class);
+        assert_eq!(class.visibility, Some(Visibility::Public));
+        assert_eq!(
+            class.signature.as_deref(),
+            Some("public sealed class User<T> : Base, IUser where T : class")
+        );
+        assert_eq!(class.documentation.as_deref(), Some("A user."));
+    }
+
+    #[test]
+    fn test_extract_members() {
+        let extractor = CSharpExtractor::new().unwrap();
+        let source = r#"
+public class User
+{
+    public const int Max = 5;
+    private readonly string _name, _alias;
+    public string Name { get; set; } = "x";
+    public int Count => 1;
+    public event EventHandler Changed;
+    public User(string name) : base(name) { }
+    ~User() { }
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("User", &OutlineNodeType::Class),
+                ("Max", &OutlineNodeType::Constant),
+                ("_name", &OutlineNodeType::Property),
+                ("_alias", &OutlineNodeType::Property),
+                ("Name", &OutlineNodeType::Property),
+                ("Count", &OutlineNodeType::Property),
+                ("Changed", &OutlineNodeType::Property),
+                ("User", &OutlineNodeType::Method),
+                ("~User", &OutlineNodeType::Method),
+            ]
+        );
+
+        assert_eq!(
+            symbols[1].signature.as_deref(),
+            Some("public const int Max")
+        );
+        assert_eq!(
+            symbols[3].signature.as_deref(),
+            Some("private readonly string _alias")
+        );
+        assert_eq!(
+            symbols[4].signature.as_deref(),
+            Some("public string Name { get; set; }")
+        );
+        assert_eq!(
+            symbols[5].signature.as_deref(),
+            Some("public int Count { get; }")
+        );
+        assert_eq!(
+            symbols[6].signature.as_deref(),
+            Some("public event EventHandler Changed")
+        );
+        assert_eq!(
+            symbols[7].signature.as_deref(),
+            Some("public User(string name)")
+        );
+        // Members without an access modifier are private
+        assert_eq!(symbols[8].visibility, Some(Visibility::Private));
+    }
+
+    #[test]
+    fn test_extract_multiple_symbols() {
+        let extractor = CSharpExtractor::new().unwrap();
+        let source = r#"
+using System;
+using Gen = System.Collections.Generic;
+
+namespace App.Core;
+
+interface IUser { void Save(); }
+
+public record Point(int X, int Y);
+
+struct Size { }
+
+enum Color { Red, Green }
+
+delegate void Handler(int x);
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("System", &OutlineNodeType::Import),
+                ("Gen", &OutlineNodeType::Import),
+                ("App.Core", &OutlineNodeType::Module),
+                ("IUser", &OutlineNodeType::Interface),
+                ("Save", &OutlineNodeType::Method),
+                ("Point", &OutlineNodeType::Class),
+                ("Size", &OutlineNodeType::Struct),
+                ("Color", &OutlineNodeType::Enum),
+                ("Handler", &OutlineNodeType::TypeAlias),
+            ]
+        );
+
+        assert_eq!(
+            symbols[1].signature.as_deref(),
+            Some("using Gen = System.Collections.Generic")
+        );
+        // Types default to internal, interface members to public
+        assert_eq!(symbols[3].visibility, Some(Visibility::Package));
+        assert_eq!(symbols[4].visibility, Some(Visibility::Public));
+        assert_eq!(
+            symbols[5].signature.as_deref(),
+            Some("public record Point(int X, int Y)")
+        );
+        assert_eq!(
+            symbols[8].signature.as_deref(),
+            Some("delegate void Handler(int x)")
+        );
+    }
+
+    #[test]
+    fn test_extract_method_signature_details() {
+        let extractor = CSharpExtractor::new().unwrap();
+        let source = r#"
+class Repo
+{
+    public T Find<T>(int? id, params string[] names) where T : class, new() { return null; }
+}
+        "#;
+
+        let tree = parse(source);
+        let body = tree
+            .root_node()
+            .named_child(0)
+            .unwrap()
+            .child_by_field_name("body")
+            .unwrap();
+        let method = body.named_child(0).unwrap();
+        let signature = extractor
+            .extract_function_signature(&method, source)
+            .unwrap();
+
+        assert_eq!(signature.name, "Find");
+        assert_eq!(signature.generic_parameters.len(), 1);
+        assert_eq!(
+            signature.generic_parameters[0].bounds,
+            vec!["class".to_string(), "new()".to_string()]
+        );
+        assert_eq!(signature.parameters.len(), 2);
+        assert!(
+            signature.parameters[0]
+                .type_info
+                .as_ref()
+                .unwrap()
+                .is_nullable
+        );
+        assert_eq!(signature.parameters[1].name, "names");
+        assert!(signature.parameters[1].is_variadic);
+        assert_eq!(signature.return_type.unwrap().name, "T");
+    }
+
+    #[test]
+    fn test_hierarchical_relationships() {
+        let extractor = CSharpExtractor::new().unwrap();
+        let source = r#"
+namespace App
+{
+    public class Server
+    {
+        public void Start() { }
+
+        private class Config
+        {
+            public int Port { get; set; }
+        }
+    }
+}
+
+namespace Other
+{
+    class Helper { }
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+        let hierarchical_symbols = extractor.build_hierarchy(symbols);
+
+        assert_eq!(hierarchical_symbols.len(), 2);
+        let app = &hierarchical_symbols[0];
+        assert_eq!(app.name, "App");
+        assert_eq!(app.children.len(), 1);
+
+        let server = &app.children[0];
+        let member_names: Vec<&str> = server.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(member_names, vec!["Start", "Config"]);
+        assert_eq!(server.children[1].visibility, Some(Visibility::Private));
+        assert_eq!(server.children[1].children[0].name, "Port");
+
+        assert_eq!(hierarchical_symbols[1].children[0].name, "Helper");
+    }
+
+    #[test]
+    fn test_file_scoped_namespace_contains_file() {
+        let extractor = CSharpExtractor::new().unwrap();
+        let source = r#"namespace App.Core;
+
+public class A { }
+
+public class B { }
+"#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+        let hierarchical_symbols = extractor.build_hierarchy(symbols);
+
+        assert_eq!(hierarchical_symbols.len(), 1);
+        let namespace = &hierarchical_symbols[0];
+        assert_eq!(namespace.end_line, 5);
+        let names: Vec<&str> = namespace.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["A", "B"]);
+    }
+}
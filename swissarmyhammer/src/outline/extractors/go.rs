@@ -0,0 +1,896 @@
+//! Go language symbol extractor for outline generation
+//!
+//! This module implements Tree-sitter based symbol extraction for Go code,
+//! supporting functions, methods, struct and interface types, type definitions,
+//! package-level constants and variables, and imports, along with their doc
+//! comments, signatures and exported-name visibility.
+
+use crate::outline::parser::SymbolExtractor;
+use crate::outline::signature::{
+    GenericParameter, Modifier, Parameter, Signature, SignatureExtractor, TypeInfo,
+};
+use crate::outline::types::{OutlineNode, OutlineNodeType, Visibility};
+use crate::outline::{OutlineError, Result};
+use crate::search::types::Language;
+use tree_sitter::{Node, Query, QueryCursor, StreamingIterator, Tree};
+
+/// Go symbol extractor using Tree-sitter
+pub struct GoExtractor {
+    /// Tree-sitter queries with their associated node types
+    queries: Vec<(OutlineNodeType, Query)>,
+}
+
+impl GoExtractor {
+    /// Create a new Go extractor with compiled queries
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_go::LANGUAGE.into();
+        let mut queries = Vec::new();
+
+        // Define Tree-sitter queries for each Go construct
+        let query_definitions = vec![
+            (
+                OutlineNodeType::Function,
+                r#"(function_declaration) @function"#,
+            ),
+            (OutlineNodeType::Method, r#"(method_declaration) @method"#),
+            (
+                OutlineNodeType::Struct,
+                r#"(type_spec type: (struct_type)) @struct"#,
+            ),
+            (
+                OutlineNodeType::Interface,
+                r#"(type_spec type: (interface_type)) @interface"#,
+            ),
+            // Other type specs are matched here and filtered in extract_symbols
+            (
+                OutlineNodeType::TypeAlias,
+                r#"[(type_alias) (type_spec)] @type_alias"#,
+            ),
+            // Only package-level constants and variables, not locals
+            (
+                OutlineNodeType::Constant,
+                r#"(source_file (const_declaration (const_spec) @const))"#,
+            ),
+            (
+                OutlineNodeType::Variable,
+                r#"(source_file (var_declaration [(var_spec) @var (var_spec_list (var_spec) @var)]))"#,
+            ),
+            (OutlineNodeType::Import, r#"(import_spec) @import"#),
+        ];
+
+        // Compile all queries
+        for (node_type, query_str) in query_definitions {
+            let query = Query::new(&language, query_str).map_err(|e| {
+                OutlineError::TreeSitter(format!("Failed to compile {node_type:?} query: {e}"))
+            })?;
+            queries.push((node_type, query));
+        }
+
+        Ok(Self { queries })
+    }
+
+    /// Extract the text content of a node
+    fn get_node_text(&self, node: &Node, source: &str) -> String {
+        source[node.start_byte()..node.end_byte()].to_string()
+    }
+
+    /// Extract line numbers for a node (1-based)
+    fn get_line_range(&self, node: &Node) -> (usize, usize) {
+        let start_line = node.start_position().row + 1;
+        let end_line = node.end_position().row + 1;
+        (start_line, end_line)
+    }
+
+    /// Extract the name from a Tree-sitter node
+    fn extract_name_from_node(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            // `const a, b = 1, 2` declares several names in one spec
+            "const_spec" | "var_spec" => {
+                let names: Vec<String> = node
+                    .children_by_field_name("name", &mut node.walk())
+                    .map(|name| self.get_node_text(&name, source))
+                    .collect();
+                (!names.is_empty()).then(|| names.join(", "))
+            }
+            "import_spec" => {
+                if let Some(alias) = node.child_by_field_name("name") {
+                    return Some(self.get_node_text(&alias, source));
+                }
+                let path = node.child_by_field_name("path")?;
+                Some(
+                    self.get_node_text(&path, source)
+                        .trim_matches('"')
+                        .to_string(),
+                )
+            }
+            _ => node
+                .child_by_field_name("name")
+                .map(|name| self.get_node_text(&name, source)),
+        }
+    }
+
+    /// The node a declaration's doc comment and source range belong to
+    ///
+    /// `type Server struct {}` is a type declaration holding a single type
+    /// spec, and its doc comment precedes the declaration. Specs inside a
+    /// grouped `type (...)` block carry their own comments.
+    fn declaration_node<'a>(&self, node: &Node<'a>) -> Node<'a> {
+        match node.parent() {
+            Some(parent)
+                if matches!(
+                    parent.kind(),
+                    "type_declaration" | "const_declaration" | "var_declaration"
+                ) && parent.named_child_count() == 1 =>
+            {
+                parent
+            }
+            _ => *node,
+        }
+    }
+
+    /// Whether a type spec declares a struct or interface, which have their own queries
+    fn is_struct_or_interface(&self, node: &Node) -> bool {
+        node.kind() == "type_spec"
+            && node
+                .child_by_field_name("type")
+                .is_some_and(|ty| matches!(ty.kind(), "struct_type" | "interface_type"))
+    }
+
+    /// Exported names start with an upper case letter
+    fn get_visibility(&self, name: &str) -> Option<Visibility> {
+        let first = name.chars().next()?;
+        if first.is_uppercase() {
+            Some(Visibility::Public)
+        } else {
+            Some(Visibility::Package)
+        }
+    }
+
+    /// Extract the `//` or `/* */` comments directly above a node
+    fn extract_doc_comments(&self, node: &Node, source: &str) -> Option<String> {
+        let mut doc_lines = Vec::new();
+        let mut next_row = node.start_position().row;
+        let mut sibling = node.prev_sibling();
+
+        while let Some(comment) = sibling {
+            // Stop at anything that is not a comment or is separated by a blank line
+            if comment.kind() != "comment" || comment.end_position().row + 1 < next_row {
+                break;
+            }
+            let text = self.get_node_text(&comment, source);
+            let text = text
+                .strip_prefix("//")
+                .or_else(|| {
+                    text.strip_prefix("/*")
+                        .map(|inner| inner.strip_suffix("*/").unwrap_or(inner))
+                })
+                .unwrap_or(&text)
+                .trim()
+                .to_string();
+            doc_lines.insert(0, text);
+            next_row = comment.start_position().row;
+            sibling = comment.prev_sibling();
+        }
+
+        if doc_lines.is_empty() {
+            None
+        } else {
+            Some(doc_lines.join(" "))
+        }
+    }
+
+    /// Collapse a declaration's text up to its body into one line
+    fn build_header(&self, node: &Node, source: &str) -> String {
+        let end = node
+            .child_by_field_name("body")
+            .map_or(node.end_byte(), |body| body.start_byte());
+        source[node.start_byte()..end]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Build function or method signature, such as `func (s *Server) Start(port int) error`
+    fn build_function_signature(&self, node: &Node, source: &str) -> String {
+        self.build_header(node, source)
+    }
+
+    /// Build type signature, such as `type Server struct` or `type ID = string`
+    fn build_type_signature(&self, name: &str, node: &Node, source: &str) -> String {
+        let type_parameters = node
+            .child_by_field_name("type_parameters")
+            .map(|params| self.get_node_text(&params, source))
+            .unwrap_or_default();
+        match node.child_by_field_name("type").map(|ty| ty.kind()) {
+            Some("struct_type") => format!("type {name}{type_parameters} struct"),
+            Some("interface_type") => format!("type {name}{type_parameters} interface"),
+            _ => format!("type {}", self.build_header(node, source)),
+        }
+    }
+
+    /// Build constant or variable signature, such as `const MaxConnections = 100`
+    fn build_value_signature(&self, node: &Node, source: &str) -> String {
+        let keyword = if node.kind() == "const_spec" {
+            "const"
+        } else {
+            "var"
+        };
+        format!("{keyword} {}", self.build_header(node, source))
+    }
+
+    /// Build import signature, such as `import "net/http"`
+    fn build_import_signature(&self, node: &Node, source: &str) -> String {
+        format!("import {}", self.get_node_text(node, source))
+    }
+
+    /// Check if an inner symbol is within the range of an outer symbol
+    fn is_symbol_within_range(inner: &OutlineNode, outer: &OutlineNode) -> bool {
+        inner.source_range.0 >= outer.source_range.0 && inner.source_range.1 <= outer.source_range.1
+    }
+}
+
+/// The type a Go method signature's receiver names
+///
+/// Returns `Server` for `func (s *Server) Start()` and `Stack` for
+/// `func (s Stack[T]) Push(v T)`.
+pub fn receiver_type(signature: &str) -> Option<&str> {
+    let receiver = signature.strip_prefix("func (")?;
+    let receiver = &receiver[..receiver.find(')')?];
+    let type_name = receiver.split_whitespace().last()?.trim_start_matches('*');
+    let type_name = type_name.split('[').next()?;
+    (!type_name.is_empty()).then_some(type_name)
+}
+
+impl SignatureExtractor for GoExtractor {
+    fn extract_function_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        if !matches!(node.kind(), "function_declaration" | "method_declaration") {
+            return None;
+        }
+
+        let name = self.extract_name_from_node(node, source)?;
+        let mut signature = Signature::new(name, Language::Go);
+
+        signature = signature.with_modifiers(self.parse_modifiers(node, source));
+
+        for generic in self.parse_generic_parameters(node, source) {
+            signature = signature.with_generic(generic);
+        }
+
+        if let Some(params_node) = node.child_by_field_name("parameters") {
+            for param_node in params_node.named_children(&mut params_node.walk()) {
+                for parameter in self.parse_parameters(&param_node, source) {
+                    signature = signature.with_parameter(parameter);
+                }
+            }
+        }
+
+        if let Some(result_node) = node.child_by_field_name("result") {
+            if let Some(return_type) = self.parse_type_info(&result_node, source) {
+                signature = signature.with_return_type(return_type);
+            }
+        }
+
+        signature = signature.with_raw_signature(self.build_function_signature(node, source));
+        Some(signature)
+    }
+
+    fn extract_method_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        // Methods are functions with a receiver, which the raw signature keeps
+        self.extract_function_signature(node, source)
+    }
+
+    fn extract_constructor_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        // Go has no constructors, but `NewServer` style functions serve as them
+        let signature = self.extract_function_signature(node, source)?;
+        if node.kind() == "function_declaration" && signature.name.starts_with("New") {
+            Some(signature.constructor())
+        } else {
+            Some(signature)
+        }
+    }
+
+    fn extract_type_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        if !matches!(node.kind(), "type_spec" | "type_alias") {
+            return None;
+        }
+
+        let name = self.extract_name_from_node(node, source)?;
+        let mut signature = Signature::new(name.clone(), Language::Go);
+        signature = signature.with_modifiers(self.parse_modifiers(node, source));
+        for generic in self.parse_generic_parameters(node, source) {
+            signature = signature.with_generic(generic);
+        }
+        signature = signature.with_raw_signature(self.build_type_signature(&name, node, source));
+        Some(signature)
+    }
+
+    fn parse_type_info(&self, node: &Node, source: &str) -> Option<TypeInfo> {
+        match node.kind() {
+            "pointer_type" => {
+                let inner = node.named_child(0)?;
+                let inner_type = self.parse_type_info(&inner, source)?;
+                Some(TypeInfo::new(format!("*{}", inner_type.name)))
+            }
+            "slice_type" | "array_type" => {
+                let element = node.child_by_field_name("element")?;
+                let element_type = self.parse_type_info(&element, source)?;
+                Some(TypeInfo::array(element_type, 1))
+            }
+            "generic_type" => {
+                let base = node.child_by_field_name("type")?;
+                let args = node
+                    .child_by_field_name("type_arguments")
+                    .map(|args| {
+                        args.named_children(&mut args.walk())
+                            .filter_map(|arg| self.parse_type_info(&arg, source))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Some(TypeInfo::generic(self.get_node_text(&base, source), args))
+            }
+            "parameter_list" => {
+                // Multiple return values, such as `(bool, error)`
+                let text = self.get_node_text(node, source);
+                Some(TypeInfo::new(
+                    text.split_whitespace().collect::<Vec<_>>().join(" "),
+                ))
+            }
+            _ => {
+                let text = self.get_node_text(node, source);
+                (!text.is_empty()).then(|| TypeInfo::new(text))
+            }
+        }
+    }
+
+    fn parse_parameter(&self, node: &Node, source: &str) -> Option<Parameter> {
+        self.parse_parameters(node, source).into_iter().next()
+    }
+
+    fn parse_generic_parameters(&self, node: &Node, source: &str) -> Vec<GenericParameter> {
+        let mut generics = Vec::new();
+
+        if let Some(type_params) = node.child_by_field_name("type_parameters") {
+            for declaration in type_params.named_children(&mut type_params.walk()) {
+                let bounds: Vec<String> = declaration
+                    .child_by_field_name("type")
+                    .map(|constraint| vec![self.get_node_text(&constraint, source)])
+                    .unwrap_or_default();
+                for name in declaration.children_by_field_name("name", &mut declaration.walk()) {
+                    generics.push(
+                        GenericParameter::new(self.get_node_text(&name, source))
+                            .with_bounds(bounds.clone()),
+                    );
+                }
+            }
+        }
+
+        generics
+    }
+
+    fn parse_modifiers(&self, node: &Node, source: &str) -> Vec<Modifier> {
+        // Exported names are Go's only modifier
+        match self
+            .extract_name_from_node(node, source)
+            .and_then(|name| self.get_visibility(&name))
+        {
+            Some(Visibility::Public) => vec![Modifier::Public],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl GoExtractor {
+    /// Parse a parameter declaration, which may name several parameters of one type
+    fn parse_parameters(&self, node: &Node, source: &str) -> Vec<Parameter> {
+        let variadic = match node.kind() {
+            "parameter_declaration" => false,
+            "variadic_parameter_declaration" => true,
+            _ => return Vec::new(),
+        };
+        let type_info = node
+            .child_by_field_name("type")
+            .and_then(|ty| self.parse_type_info(&ty, source));
+
+        let mut parameters: Vec<Parameter> = node
+            .children_by_field_name("name", &mut node.walk())
+            .map(|name| Parameter::new(self.get_node_text(&name, source)))
+            .collect();
+        if parameters.is_empty() {
+            // Unnamed parameters such as `func(int, string)`
+            parameters.push(Parameter::new("_".to_string()));
+        }
+
+        parameters
+            .into_iter()
+            .map(|mut parameter| {
+                if let Some(type_info) = &type_info {
+                    parameter = parameter.with_type(type_info.clone());
+                }
+                if variadic {
+                    parameter = parameter.variadic();
+                }
+                parameter
+            })
+            .collect()
+    }
+}
+
+impl SymbolExtractor for GoExtractor {
+    fn extract_symbols(&self, tree: &Tree, source: &str) -> Result<Vec<OutlineNode>> {
+        let mut symbols = Vec::new();
+        let root_node = tree.root_node();
+
+        // Process each query
+        for (node_type, query) in &self.queries {
+            let mut cursor = QueryCursor::new();
+            let mut matches = cursor.matches(query, root_node, source.as_bytes());
+
+            while let Some(query_match) = matches.next() {
+                // Get the main captured node (should be the only capture)
+                if let Some(capture) = query_match.captures.first() {
+                    let node = &capture.node;
+
+                    // Struct and interface specs are captured by their own queries
+                    if *node_type == OutlineNodeType::TypeAlias && self.is_struct_or_interface(node)
+                    {
+                        continue;
+                    }
+
+                    if let Some(name) = self.extract_name_from_node(node, source) {
+                        let declaration = self.declaration_node(node);
+                        let (start_line, end_line) = self.get_line_range(&declaration);
+                        let mut outline_node = OutlineNode::new(
+                            name.clone(),
+                            node_type.clone(),
+                            start_line,
+                            end_line,
+                            (declaration.start_byte(), declaration.end_byte()),
+                        );
+
+                        // Add signature based on node type
+                        let signature = match node_type {
+                            OutlineNodeType::Function | OutlineNodeType::Method => self
+                                .extract_function_signature(node, source)
+                                .map(|sig| sig.format_for_language(Language::Go)),
+                            OutlineNodeType::Struct
+                            | OutlineNodeType::Interface
+                            | OutlineNodeType::TypeAlias => self
+                                .extract_type_signature(node, source)
+                                .map(|sig| sig.format_for_language(Language::Go)),
+                            OutlineNodeType::Constant | OutlineNodeType::Variable => {
+                                Some(self.build_value_signature(node, source))
+                            }
+                            OutlineNodeType::Import => {
+                                Some(self.build_import_signature(node, source))
+                            }
+                            _ => None,
+                        };
+
+                        if let Some(sig) = signature {
+                            outline_node = outline_node.with_signature(sig);
+                        }
+
+                        // Add visibility
+                        if *node_type != OutlineNodeType::Import {
+                            if let Some(visibility) = self.get_visibility(&name) {
+                                outline_node = outline_node.with_visibility(visibility);
+                            }
+                        }
+
+                        // Add documentation
+                        if let Some(docs) = self.extract_doc_comments(&declaration, source) {
+                            outline_node = outline_node.with_documentation(docs);
+                        }
+
+                        symbols.push(outline_node);
+                    }
+                }
+            }
+        }
+
+        // Sort symbols by line number
+        symbols.sort_by_key(|s| s.start_line);
+
+        Ok(symbols)
+    }
+
+    fn extract_documentation(&self, node: &Node, source: &str) -> Option<String> {
+        self.extract_doc_comments(&self.declaration_node(node), source)
+    }
+
+    fn extract_signature(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "function_declaration" | "method_declaration" => {
+                Some(self.build_function_signature(node, source))
+            }
+            "type_spec" | "type_alias" => {
+                let name = self.extract_name_from_node(node, source)?;
+                Some(self.build_type_signature(&name, node, source))
+            }
+            "const_spec" | "var_spec" => Some(self.build_value_signature(node, source)),
+            "import_spec" => Some(self.build_import_signature(node, source)),
+            _ => None,
+        }
+    }
+
+    fn extract_visibility(&self, node: &Node, source: &str) -> Option<Visibility> {
+        let name = self.extract_name_from_node(node, source)?;
+        self.get_visibility(&name)
+    }
+
+    fn build_hierarchy(&self, symbols: Vec<OutlineNode>) -> Vec<OutlineNode> {
+        let mut hierarchical_symbols = Vec::new();
+        let mut methods = Vec::new();
+
+        // Methods are declared outside their type, so they are grouped by receiver
+        for symbol in symbols {
+            if symbol.node_type == OutlineNodeType::Method {
+                methods.push(symbol);
+            } else {
+                hierarchical_symbols.push(symbol);
+            }
+        }
+
+        for method in methods {
+            let receiver = method
+                .signature
+                .as_deref()
+                .and_then(receiver_type)
+                .map(str::to_string);
+            let owner = hierarchical_symbols.iter_mut().find(|symbol| {
+                matches!(
+                    symbol.node_type,
+                    OutlineNodeType::Struct
+                        | OutlineNodeType::Interface
+                        | OutlineNodeType::TypeAlias
+                ) && receiver.as_deref() == Some(symbol.name.as_str())
+            });
+            match owner {
+                Some(owner) => owner.add_child(method),
+                None => hierarchical_symbols.push(method),
+            }
+        }
+
+        // Nothing else nests in Go, except types declared inside functions
+        let mut nested = Vec::new();
+        for (i, symbol) in hierarchical_symbols.iter().enumerate() {
+            if hierarchical_symbols.iter().enumerate().any(|(j, outer)| {
+                i != j
+                    && outer.node_type == OutlineNodeType::Function
+                    && Self::is_symbol_within_range(symbol, outer)
+            }) {
+                nested.push(i);
+            }
+        }
+        for i in nested.into_iter().rev() {
+            let symbol = hierarchical_symbols.remove(i);
+            if let Some(function) = hierarchical_symbols.iter_mut().find(|outer| {
+                outer.node_type == OutlineNodeType::Function
+                    && Self::is_symbol_within_range(&symbol, outer)
+            }) {
+                function.add_child(symbol);
+            }
+        }
+
+        hierarchical_symbols.sort_by_key(|s| s.start_line);
+        hierarchical_symbols
+    }
+
+    fn get_queries(&self) -> Vec<(&'static str, OutlineNodeType)> {
+        vec![
+            // Functions
+            (
+                "(function_declaration) @function",
+                OutlineNodeType::Function,
+            ),
+            // Methods
+            ("(method_declaration) @method", OutlineNodeType::Method),
+            // Structs
+            (
+                "(type_spec type: (struct_type)) @struct",
+                OutlineNodeType::Struct,
+            ),
+            // Interfaces
+            (
+                "(type_spec type: (interface_type)) @interface",
+                OutlineNodeType::Interface,
+            ),
+            // Type definitions and aliases
+            (
+                "[(type_alias) (type_spec)] @type_alias",
+                OutlineNodeType::TypeAlias,
+            ),
+            // Constants
+            (
+                "(source_file (const_declaration (const_spec) @const))",
+                OutlineNodeType::Constant,
+            ),
+            // Variables
+            (
+                "(source_file (var_declaration [(var_spec) @var (var_spec_list (var_spec) @var)]))",
+                OutlineNodeType::Variable,
+            ),
+            // Imports
+            ("(import_spec) @import", OutlineNodeType::Import),
+        ]
+    }
+}
+
+impl Default for GoExtractor {
+    fn default() -> Self {
+        Self::new().expect("Failed to create GoExtractor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_go::LANGUAGE.into())
+            .unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_go_extractor_creation() {
+        let extractor = GoExtractor::new();
+        assert!(extractor.is_ok());
+    }
+
+    #[test]
+    fn test_extract_simple_function() {
+        let extractor = GoExtractor::new().unwrap();
+        let source = r#"
+package main
+
+// Greet returns a greeting
+// for the given name.
+func Greet(name string) string {
+	return "Hello, " + name
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        let func = &symbols[0];
+        assert_eq!(func.name, "Greet");
+        assert_eq!(func.node_type, OutlineNodeType::Function);
+        assert_eq!(func.visibility, Some(Visibility::Public));
+        assert_eq!(
+            func.signature.as_deref(),
+            Some("func Greet(name string) string")
+        );
+        assert_eq!(
+            func.documentation.as_deref(),
+            Some("Greet returns a greeting for the given name.")
+        );
+    }
+
+    #[test]
+    fn test_extract_struct() {
+        let extractor = GoExtractor::new().unwrap();
+        let source = r#"
+package main
+
+// Person is a person.
+type Person struct {
+	Name string
+	age  int
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        let struct_node = &symbols[0];
+        assert_eq!(struct_node.name, "Person");
+        assert_eq!(struct_node.node_type, OutlineNodeType::Struct);
+        assert_eq!(struct_node.visibility, Some(Visibility::Public));
+        assert_eq!(struct_node.signature.as_deref(), Some("type Person struct"));
+        assert_eq!(
+            struct_node.documentation.as_deref(),
+            Some("Person is a person.")
+        );
+        // The symbol covers the whole declaration, including the `type` keyword
+        assert_eq!(struct_node.start_line, 5);
+    }
+
+    #[test]
+    fn test_extract_interface() {
+        let extractor = GoExtractor::new().unwrap();
+        let source = r#"
+package main
+
+// Store persists values.
+type Store interface {
+	Get(key string) (string, error)
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        let interface = &symbols[0];
+        assert_eq!(interface.name, "Store");
+        assert_eq!(interface.node_type, OutlineNodeType::Interface);
+        assert_eq!(interface.signature.as_deref(), Some("type Store interface"));
+    }
+
+    #[test]
+    fn test_extract_method() {
+        let extractor = GoExtractor::new().unwrap();
+        let source = r#"
+package main
+
+// Start runs the server.
+func (s *Server) start(port int) (bool, error) {
+	return true, nil
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        let method = &symbols[0];
+        assert_eq!(method.name, "start");
+        assert_eq!(method.node_type, OutlineNodeType::Method);
+        assert_eq!(method.visibility, Some(Visibility::Package));
+        assert_eq!(
+            method.signature.as_deref(),
+            Some("func (s *Server) start(port int) (bool, error)")
+        );
+        assert_eq!(
+            receiver_type(method.signature.as_ref().unwrap()),
+            Some("Server")
+        );
+    }
+
+    #[test]
+    fn test_extract_multiple_symbols() {
+        let extractor = GoExtractor::new().unwrap();
+        let source = r#"
+package main
+
+import (
+	"fmt"
+	log "github.com/sirupsen/logrus"
+)
+
+// MaxSize is the largest size.
+const MaxSize = 100
+
+var (
+	defaultName = "app"
+)
+
+type ID = string
+
+type Celsius float64
+
+func main() {
+	local := 1
+	fmt.Println(local)
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("fmt", &OutlineNodeType::Import),
+                ("log", &OutlineNodeType::Import),
+                ("MaxSize", &OutlineNodeType::Constant),
+                ("defaultName", &OutlineNodeType::Variable),
+                ("ID", &OutlineNodeType::TypeAlias),
+                ("Celsius", &OutlineNodeType::TypeAlias),
+                ("main", &OutlineNodeType::Function),
+            ]
+        );
+
+        let max_size = &symbols[2];
+        assert_eq!(max_size.signature.as_deref(), Some("const MaxSize = 100"));
+        assert_eq!(
+            max_size.documentation.as_deref(),
+            Some("MaxSize is the largest size.")
+        );
+        assert_eq!(symbols[4].signature.as_deref(), Some("type ID = string"));
+    }
+
+    #[test]
+    fn test_extract_generic_function_signature() {
+        let extractor = GoExtractor::new().unwrap();
+        let source = r#"
+package main
+
+func Map[T any, U any](items []T, f func(T) U, rest ...int) []U {
+	return nil
+}
+        "#;
+
+        let tree = parse(source);
+        let root = tree.root_node();
+        let function = root.named_child(1).unwrap();
+        let signature = extractor
+            .extract_function_signature(&function, source)
+            .unwrap();
+
+        assert_eq!(signature.name, "Map");
+        assert_eq!(signature.generic_parameters.len(), 2);
+        assert_eq!(signature.parameters.len(), 3);
+        assert_eq!(signature.parameters[0].name, "items");
+        assert!(signature.parameters[2].is_variadic);
+        assert_eq!(
+            signature.raw_signature,
+            "func Map[T any, U any](items []T, f func(T) U, rest ...int) []U"
+        );
+    }
+
+    #[test]
+    fn test_hierarchical_relationships() {
+        let extractor = GoExtractor::new().unwrap();
+        let source = r#"
+package main
+
+// Server serves requests.
+type Server struct {
+	name string
+}
+
+// NewServer creates a server.
+func NewServer(name string) *Server {
+	return &Server{name: name}
+}
+
+// Start runs the server.
+func (s *Server) Start() error {
+	return nil
+}
+
+// Name returns the name.
+func (s Server) Name() string {
+	return s.name
+}
+
+func (o *Other) Run() {}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+        let hierarchical_symbols = extractor.build_hierarchy(symbols);
+
+        let server = hierarchical_symbols
+            .iter()
+            .find(|s| s.name == "Server")
+            .expect("Should find Server struct");
+        let method_names: Vec<&str> = server.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(method_names, vec!["Start", "Name"]);
+
+        // Constructors and methods of unknown types stay at the top level
+        let top_level: Vec<&str> = hierarchical_symbols
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(top_level, vec!["Server", "NewServer", "Run"]);
+    }
+
+    #[test]
+    fn test_receiver_type() {
+        assert_eq!(receiver_type("func (s *Server) Start()"), Some("Server"));
+        assert_eq!(receiver_type("func (Stack[T]) Push(v T)"), Some("Stack"));
+        assert_eq!(receiver_type("func main()"), None);
+    }
+}
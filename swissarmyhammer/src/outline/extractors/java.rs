@@ -0,0 +1,865 @@
+//! Java language symbol extractor for outline generation
+//!
+//! This module implements Tree-sitter based symbol extraction for Java code,
+//! supporting classes, records, interfaces, enums, annotation types, methods,
+//! constructors, fields and imports, along with their Javadoc comments,
+//! signatures and access modifiers.
+
+use crate::outline::parser::SymbolExtractor;
+use crate::outline::signature::{
+    GenericParameter, Modifier, Parameter, Signature, SignatureExtractor, TypeInfo,
+};
+use crate::outline::types::{OutlineNode, OutlineNodeType, Visibility};
+use crate::outline::{OutlineError, Result};
+use crate::search::types::Language;
+use tree_sitter::{Node, Query, QueryCursor, StreamingIterator, Tree};
+
+/// Java symbol extractor using Tree-sitter
+pub struct JavaExtractor {
+    /// Tree-sitter queries with their associated node types
+    queries: Vec<(OutlineNodeType, Query)>,
+}
+
+impl JavaExtractor {
+    /// Create a new Java extractor with compiled queries
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_java::LANGUAGE.into();
+        let mut queries = Vec::new();
+
+        // Define Tree-sitter queries for each Java construct
+        let query_definitions = vec![
+            (
+                OutlineNodeType::Class,
+                r#"[(class_declaration) (record_declaration)] @class"#,
+            ),
+            (
+                OutlineNodeType::Interface,
+                r#"[(interface_declaration) (annotation_type_declaration)] @interface"#,
+            ),
+            (OutlineNodeType::Enum, r#"(enum_declaration) @enum"#),
+            (
+                OutlineNodeType::Method,
+                r#"[(method_declaration) (constructor_declaration) (compact_constructor_declaration) (annotation_type_element_declaration)] @method"#,
+            ),
+            (
+                OutlineNodeType::Property,
+                r#"(field_declaration declarator: (variable_declarator) @field)"#,
+            ),
+            (
+                OutlineNodeType::Constant,
+                r#"(constant_declaration declarator: (variable_declarator) @constant)"#,
+            ),
+            (OutlineNodeType::Import, r#"(import_declaration) @import"#),
+        ];
+
+        // Compile all queries
+        for (node_type, query_str) in query_definitions {
+            let query = Query::new(&language, query_str).map_err(|e| {
+                OutlineError::TreeSitter(format!("Failed to compile {node_type:?} query: {e}"))
+            })?;
+            queries.push((node_type, query));
+        }
+
+        Ok(Self { queries })
+    }
+
+    /// Extract the text content of a node
+    fn get_node_text(&self, node: &Node, source: &str) -> String {
+        source[node.start_byte()..node.end_byte()].to_string()
+    }
+
+    /// Extract line numbers for a node (1-based)
+    fn get_line_range(&self, node: &Node) -> (usize, usize) {
+        let start_line = node.start_position().row + 1;
+        let end_line = node.end_position().row + 1;
+        (start_line, end_line)
+    }
+
+    /// Extract the name from a Tree-sitter node
+    fn extract_name_from_node(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "import_declaration" => {
+                // `import static java.util.Collections.*;` keeps the whole path
+                let path = node
+                    .named_children(&mut node.walk())
+                    .find(|child| matches!(child.kind(), "scoped_identifier" | "identifier"))?;
+                let mut name = self.get_node_text(&path, source);
+                if node
+                    .children(&mut node.walk())
+                    .any(|child| child.kind() == "asterisk")
+                {
+                    name.push_str(".*");
+                }
+                Some(name)
+            }
+            _ => node
+                .child_by_field_name("name")
+                .map(|name| self.get_node_text(&name, source)),
+        }
+    }
+
+    /// The declaration a field's variable declarator belongs to
+    ///
+    /// `int x, y;` declares two fields that share modifiers, type and Javadoc.
+    fn declaration_node<'a>(&self, node: &Node<'a>) -> Node<'a> {
+        match node.parent() {
+            Some(parent)
+                if node.kind() == "variable_declarator"
+                    && matches!(parent.kind(), "field_declaration" | "constant_declaration") =>
+            {
+                parent
+            }
+            _ => *node,
+        }
+    }
+
+    /// The `modifiers` child of a declaration, if it has one
+    fn modifiers_node<'a>(&self, node: &Node<'a>) -> Option<Node<'a>> {
+        node.children(&mut node.walk())
+            .find(|child| child.kind() == "modifiers")
+    }
+
+    /// Keyword modifiers of a declaration, without its annotations
+    fn modifier_keywords(&self, node: &Node, source: &str) -> Vec<String> {
+        self.modifiers_node(node)
+            .map(|modifiers| {
+                modifiers
+                    .children(&mut modifiers.walk())
+                    .filter(|child| !child.kind().ends_with("annotation"))
+                    .map(|child| self.get_node_text(&child, source))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Parse the access modifier of a declaration
+    ///
+    /// Members without one are package-private, except in interfaces and
+    /// annotation types, where they are public.
+    fn get_visibility(&self, node: &Node, source: &str) -> Visibility {
+        let keywords = self.modifier_keywords(node, source);
+        if keywords.iter().any(|k| k == "public") {
+            Visibility::Public
+        } else if keywords.iter().any(|k| k == "protected") {
+            Visibility::Protected
+        } else if keywords.iter().any(|k| k == "private") {
+            Visibility::Private
+        } else if node.parent().is_some_and(|parent| {
+            matches!(parent.kind(), "interface_body" | "annotation_type_body")
+        }) {
+            Visibility::Public
+        } else {
+            Visibility::Package
+        }
+    }
+
+    /// Extract the Javadoc comment directly above a node
+    ///
+    /// Only the description is kept; block tags such as `@param` are dropped.
+    fn extract_javadoc(&self, node: &Node, source: &str) -> Option<String> {
+        let comment = node.prev_sibling()?;
+        if comment.kind() != "block_comment" {
+            return None;
+        }
+        let text = self.get_node_text(&comment, source);
+        let inner = text.strip_prefix("/**")?.strip_suffix("*/")?;
+
+        let doc_lines: Vec<&str> = inner
+            .lines()
+            .map(|line| line.trim().trim_start_matches('*').trim())
+            .take_while(|line| !line.starts_with('@'))
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        if doc_lines.is_empty() {
+            None
+        } else {
+            Some(doc_lines.join(" "))
+        }
+    }
+
+    /// Collapse a declaration's text up to its body into one line, skipping annotations
+    fn build_header(&self, node: &Node, source: &str) -> String {
+        let end = node
+            .child_by_field_name("body")
+            .map_or(node.end_byte(), |body| body.start_byte());
+
+        let mut parts = self.modifier_keywords(node, source);
+        let start = self
+            .modifiers_node(node)
+            .map_or(node.start_byte(), |modifiers| modifiers.end_byte());
+        parts.push(source[start..end].trim_end_matches(';').to_string());
+
+        parts
+            .join(" ")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Build method or constructor signature, such as `public Optional<User> findUser(long id) throws NotFound`
+    fn build_method_signature(&self, node: &Node, source: &str) -> String {
+        self.build_header(node, source)
+    }
+
+    /// Build type signature, such as `public class UserService<T> extends Base implements Service`
+    fn build_type_signature(&self, node: &Node, source: &str) -> String {
+        self.build_header(node, source)
+    }
+
+    /// Build field signature, such as `public static final int MAX_USERS`
+    fn build_field_signature(&self, node: &Node, source: &str) -> Option<String> {
+        let declaration = self.declaration_node(node);
+        let name = self.extract_name_from_node(node, source)?;
+        let field_type = declaration.child_by_field_name("type")?;
+
+        let mut parts = self.modifier_keywords(&declaration, source);
+        parts.push(self.get_node_text(&field_type, source));
+        parts.push(name);
+        Some(parts.join(" "))
+    }
+
+    /// Build import signature, such as `import java.util.List`
+    fn build_import_signature(&self, node: &Node, source: &str) -> String {
+        self.get_node_text(node, source)
+            .trim_end_matches(';')
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Check if an inner symbol is within the range of an outer symbol
+    fn is_symbol_within_range(inner: &OutlineNode, outer: &OutlineNode) -> bool {
+        inner.source_range.0 >= outer.source_range.0 && inner.source_range.1 <= outer.source_range.1
+    }
+}
+
+impl SignatureExtractor for JavaExtractor {
+    fn extract_function_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        if !matches!(
+            node.kind(),
+            "method_declaration" | "annotation_type_element_declaration"
+        ) {
+            return None;
+        }
+
+        let name = self.extract_name_from_node(node, source)?;
+        let mut signature = Signature::new(name, Language::Java);
+
+        signature = signature.with_modifiers(self.parse_modifiers(node, source));
+
+        for generic in self.parse_generic_parameters(node, source) {
+            signature = signature.with_generic(generic);
+        }
+
+        if let Some(params_node) = node.child_by_field_name("parameters") {
+            for param_node in params_node.named_children(&mut params_node.walk()) {
+                if let Some(param) = self.parse_parameter(&param_node, source) {
+                    signature = signature.with_parameter(param);
+                }
+            }
+        }
+
+        if let Some(return_node) = node.child_by_field_name("type") {
+            if let Some(return_type) = self.parse_type_info(&return_node, source) {
+                signature = signature.with_return_type(return_type);
+            }
+        }
+
+        signature = signature.with_raw_signature(self.build_method_signature(node, source));
+        Some(signature)
+    }
+
+    fn extract_method_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        match node.kind() {
+            "constructor_declaration" | "compact_constructor_declaration" => {
+                self.extract_constructor_signature(node, source)
+            }
+            _ => self.extract_function_signature(node, source),
+        }
+    }
+
+    fn extract_constructor_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        if !matches!(
+            node.kind(),
+            "constructor_declaration" | "compact_constructor_declaration"
+        ) {
+            return None;
+        }
+
+        let name = self.extract_name_from_node(node, source)?;
+        let mut signature = Signature::new(name, Language::Java).constructor();
+
+        signature = signature.with_modifiers(self.parse_modifiers(node, source));
+
+        for generic in self.parse_generic_parameters(node, source) {
+            signature = signature.with_generic(generic);
+        }
+
+        if let Some(params_node) = node.child_by_field_name("parameters") {
+            for param_node in params_node.named_children(&mut params_node.walk()) {
+                if let Some(param) = self.parse_parameter(&param_node, source) {
+                    signature = signature.with_parameter(param);
+                }
+            }
+        }
+
+        signature = signature.with_raw_signature(self.build_method_signature(node, source));
+        Some(signature)
+    }
+
+    fn extract_type_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        if !matches!(
+            node.kind(),
+            "class_declaration"
+                | "record_declaration"
+                | "interface_declaration"
+                | "annotation_type_declaration"
+                | "enum_declaration"
+        ) {
+            return None;
+        }
+
+        let name = self.extract_name_from_node(node, source)?;
+        let mut signature = Signature::new(name, Language::Java);
+        signature = signature.with_modifiers(self.parse_modifiers(node, source));
+        for generic in self.parse_generic_parameters(node, source) {
+            signature = signature.with_generic(generic);
+        }
+        signature = signature.with_raw_signature(self.build_type_signature(node, source));
+        Some(signature)
+    }
+
+    fn parse_type_info(&self, node: &Node, source: &str) -> Option<TypeInfo> {
+        match node.kind() {
+            "generic_type" => {
+                let base = node.named_child(0)?;
+                let args = node
+                    .named_children(&mut node.walk())
+                    .find(|child| child.kind() == "type_arguments")
+                    .map(|args| {
+                        args.named_children(&mut args.walk())
+                            .filter_map(|arg| self.parse_type_info(&arg, source))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Some(TypeInfo::generic(self.get_node_text(&base, source), args))
+            }
+            "array_type" => {
+                let element = node.child_by_field_name("element")?;
+                let element_type = self.parse_type_info(&element, source)?;
+                let dimensions = node
+                    .child_by_field_name("dimensions")
+                    .map(|dims| self.get_node_text(&dims, source).matches('[').count())
+                    .unwrap_or(1);
+                Some(TypeInfo::array(element_type, dimensions))
+            }
+            _ => {
+                let text = self.get_node_text(node, source);
+                (!text.is_empty()).then(|| TypeInfo::new(text))
+            }
+        }
+    }
+
+    fn parse_parameter(&self, node: &Node, source: &str) -> Option<Parameter> {
+        match node.kind() {
+            "formal_parameter" => {
+                let name = node.child_by_field_name("name")?;
+                let mut param = Parameter::new(self.get_node_text(&name, source));
+                if let Some(type_info) = node
+                    .child_by_field_name("type")
+                    .and_then(|ty| self.parse_type_info(&ty, source))
+                {
+                    param = param.with_type(type_info);
+                }
+                Some(param)
+            }
+            "spread_parameter" => {
+                // `String... names`
+                let declarator = node
+                    .named_children(&mut node.walk())
+                    .find(|child| child.kind() == "variable_declarator")?;
+                let name = declarator.child_by_field_name("name")?;
+                let mut param = Parameter::new(self.get_node_text(&name, source)).variadic();
+                if let Some(type_info) = node
+                    .named_children(&mut node.walk())
+                    .find(|child| !matches!(child.kind(), "modifiers" | "variable_declarator"))
+                    .and_then(|ty| self.parse_type_info(&ty, source))
+                {
+                    param = param.with_type(type_info);
+                }
+                Some(param)
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_generic_parameters(&self, node: &Node, source: &str) -> Vec<GenericParameter> {
+        let mut generics = Vec::new();
+
+        if let Some(type_params) = node.child_by_field_name("type_parameters") {
+            for param in type_params.named_children(&mut type_params.walk()) {
+                if param.kind() != "type_parameter" {
+                    continue;
+                }
+                let mut name = None;
+                let mut bounds = Vec::new();
+                for child in param.named_children(&mut param.walk()) {
+                    match child.kind() {
+                        "type_identifier" => name = Some(self.get_node_text(&child, source)),
+                        "type_bound" => {
+                            bounds = child
+                                .named_children(&mut child.walk())
+                                .map(|bound| self.get_node_text(&bound, source))
+                                .collect();
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(name) = name {
+                    generics.push(GenericParameter::new(name).with_bounds(bounds));
+                }
+            }
+        }
+
+        generics
+    }
+
+    fn parse_modifiers(&self, node: &Node, source: &str) -> Vec<Modifier> {
+        self.modifier_keywords(node, source)
+            .iter()
+            .filter_map(|keyword| match keyword.as_str() {
+                "public" => Some(Modifier::Public),
+                "private" => Some(Modifier::Private),
+                "protected" => Some(Modifier::Protected),
+                "static" => Some(Modifier::Static),
+                "abstract" => Some(Modifier::Abstract),
+                "final" => Some(Modifier::Final),
+                "native" => Some(Modifier::Extern),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl SymbolExtractor for JavaExtractor {
+    fn extract_symbols(&self, tree: &Tree, source: &str) -> Result<Vec<OutlineNode>> {
+        let mut symbols = Vec::new();
+        let root_node = tree.root_node();
+
+        // Process each query
+        for (node_type, query) in &self.queries {
+            let mut cursor = QueryCursor::new();
+            let mut matches = cursor.matches(query, root_node, source.as_bytes());
+
+            while let Some(query_match) = matches.next() {
+                // Get the main captured node (should be the only capture)
+                if let Some(capture) = query_match.captures.first() {
+                    let node = &capture.node;
+
+                    if let Some(name) = self.extract_name_from_node(node, source) {
+                        // Fields are ranged and documented by their whole declaration
+                        let declaration = self.declaration_node(node);
+                        let (start_line, end_line) = self.get_line_range(&declaration);
+                        let mut outline_node = OutlineNode::new(
+                            name,
+                            node_type.clone(),
+                            start_line,
+                            end_line,
+                            (declaration.start_byte(), declaration.end_byte()),
+                        );
+
+                        // Add signature based on node type
+                        let signature = match node_type {
+                            OutlineNodeType::Method => self
+                                .extract_method_signature(node, source)
+                                .map(|sig| sig.format_for_language(Language::Java)),
+                            OutlineNodeType::Class
+                            | OutlineNodeType::Interface
+                            | OutlineNodeType::Enum => self
+                                .extract_type_signature(node, source)
+                                .map(|sig| sig.format_for_language(Language::Java)),
+                            OutlineNodeType::Property | OutlineNodeType::Constant => {
+                                self.build_field_signature(node, source)
+                            }
+                            OutlineNodeType::Import => {
+                                Some(self.build_import_signature(node, source))
+                            }
+                            _ => None,
+                        };
+
+                        if let Some(sig) = signature {
+                            outline_node = outline_node.with_signature(sig);
+                        }
+
+                        // Add visibility
+                        if *node_type != OutlineNodeType::Import {
+                            outline_node = outline_node
+                                .with_visibility(self.get_visibility(&declaration, source));
+                        }
+
+                        // Add documentation
+                        if let Some(docs) = self.extract_javadoc(&declaration, source) {
+                            outline_node = outline_node.with_documentation(docs);
+                        }
+
+                        symbols.push(outline_node);
+                    }
+                }
+            }
+        }
+
+        // Sort symbols by line number
+        symbols.sort_by_key(|s| s.start_line);
+
+        Ok(symbols)
+    }
+
+    fn extract_documentation(&self, node: &Node, source: &str) -> Option<String> {
+        self.extract_javadoc(&self.declaration_node(node), source)
+    }
+
+    fn extract_signature(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "method_declaration"
+            | "constructor_declaration"
+            | "compact_constructor_declaration"
+            | "annotation_type_element_declaration" => {
+                Some(self.build_method_signature(node, source))
+            }
+            "class_declaration"
+            | "record_declaration"
+            | "interface_declaration"
+            | "annotation_type_declaration"
+            | "enum_declaration" => Some(self.build_type_signature(node, source)),
+            "variable_declarator" => self.build_field_signature(node, source),
+            "import_declaration" => Some(self.build_import_signature(node, source)),
+            _ => None,
+        }
+    }
+
+    fn extract_visibility(&self, node: &Node, source: &str) -> Option<Visibility> {
+        Some(self.get_visibility(&self.declaration_node(node), source))
+    }
+
+    fn build_hierarchy(&self, symbols: Vec<OutlineNode>) -> Vec<OutlineNode> {
+        // Nest each symbol under the innermost symbol that contains it
+        let mut symbols = symbols;
+        symbols.sort_by(|a, b| {
+            a.source_range
+                .0
+                .cmp(&b.source_range.0)
+                .then(b.source_range.1.cmp(&a.source_range.1))
+        });
+
+        let mut roots: Vec<OutlineNode> = Vec::new();
+        let mut stack: Vec<OutlineNode> = Vec::new();
+
+        for symbol in symbols {
+            while let Some(top) = stack.last() {
+                if Self::is_symbol_within_range(&symbol, top) {
+                    break;
+                }
+                let finished = stack.pop().expect("stack is not empty");
+                match stack.last_mut() {
+                    Some(parent) => parent.add_child(finished),
+                    None => roots.push(finished),
+                }
+            }
+            stack.push(symbol);
+        }
+
+        while let Some(finished) = stack.pop() {
+            match stack.last_mut() {
+                Some(parent) => parent.add_child(finished),
+                None => roots.push(finished),
+            }
+        }
+
+        roots
+    }
+
+    fn get_queries(&self) -> Vec<(&'static str, OutlineNodeType)> {
+        vec![
+            // Classes and records
+            (
+                "[(class_declaration) (record_declaration)] @class",
+                OutlineNodeType::Class,
+            ),
+            // Interfaces and annotation types
+            (
+                "[(interface_declaration) (annotation_type_declaration)] @interface",
+                OutlineNodeType::Interface,
+            ),
+            // Enums
+            ("(enum_declaration) @enum", OutlineNodeType::Enum),
+            // Methods and constructors
+            (
+                "[(method_declaration) (constructor_declaration) (compact_constructor_declaration) (annotation_type_element_declaration)] @method",
+                OutlineNodeType::Method,
+            ),
+            // Fields
+            (
+                "(field_declaration declarator: (variable_declarator) @field)",
+                OutlineNodeType::Property,
+            ),
+            // Interface constants
+            (
+                "(constant_declaration declarator: (variable_declarator) @constant)",
+                OutlineNodeType::Constant,
+            ),
+            // Imports
+            ("(import_declaration) @import", OutlineNodeType::Import),
+        ]
+    }
+}
+
+impl Default for JavaExtractor {
+    fn default() -> Self {
+        Self::new().expect("Failed to create JavaExtractor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_java::LANGUAGE.into())
+            .unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_java_extractor_creation() {
+        let extractor = JavaExtractor::new();
+        assert!(extractor.is_ok());
+    }
+
+    #[test]
+    fn test_extract_simple_method() {
+        let extractor = JavaExtractor::new().unwrap();
+        let source = r#"
+class Greeter {
+    /**
+     * Greets someone.
+     * @param name who to greet
+     */
+    @Override
+    public static String greet(String name) {
+        return "Hello, " + name;
+    }
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let method = symbols
+            .iter()
+            .find(|s| s.name == "greet")
+            .expect("Should find greet method");
+        assert_eq!(method.node_type, OutlineNodeType::Method);
+        assert_eq!(method.visibility, Some(Visibility::Public));
+        assert_eq!(
+            method.signature.as_deref(),
+            Some("public static String greet(String name)")
+        );
+        assert_eq!(method.documentation.as_deref(), Some("Greets someone."));
+    }
+
+    #[test]
+    fn test_extract_class() {
+        let extractor = JavaExtractor::new().unwrap();
+        let source = r#"
+/**
+ * Manages users.
+ */
+public class UserService<T extends User> extends Base implements Service {
+}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        let class = &symbols[0];
+        assert_eq!(class.name, "UserService");
+        assert_eq!(class.node_type, OutlineNodeType::Class);
+        assert_eq!(class.visibility, Some(Visibility::Public));
+        assert_eq!(
+            class.signature.as_deref(),
+            Some("public class UserService<T extends User> extends Base implements Service")
+        );
+        assert_eq!(class.documentation.as_deref(), Some("Manages users."));
+    }
+
+    #[test]
+    fn test_extract_interface_and_enum() {
+        let extractor = JavaExtractor::new().unwrap();
+        let source = r#"
+interface Service {
+    int TIMEOUT = 30;
+    void run();
+}
+
+enum Color { RED, GREEN }
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Service", &OutlineNodeType::Interface),
+                ("TIMEOUT", &OutlineNodeType::Constant),
+                ("run", &OutlineNodeType::Method),
+                ("Color", &OutlineNodeType::Enum),
+            ]
+        );
+
+        // Interface members are implicitly public, top-level types are package-private
+        assert_eq!(symbols[0].visibility, Some(Visibility::Package));
+        assert_eq!(symbols[2].visibility, Some(Visibility::Public));
+        assert_eq!(symbols[2].signature.as_deref(), Some("void run()"));
+    }
+
+    #[test]
+    fn test_extract_multiple_symbols() {
+        let extractor = JavaExtractor::new().unwrap();
+        let source = r#"
+package com.example;
+
+import java.util.List;
+import static java.util.Collections.*;
+
+public class Users {
+    /** Maximum users. */
+    public static final int MAX_USERS = 10;
+
+    private List<String> names, aliases;
+
+    protected Users(String name) {
+        int local = 1;
+    }
+}
+
+record Point(int x, int y) {}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("java.util.List", &OutlineNodeType::Import),
+                ("java.util.Collections.*", &OutlineNodeType::Import),
+                ("Users", &OutlineNodeType::Class),
+                ("MAX_USERS", &OutlineNodeType::Property),
+                ("names", &OutlineNodeType::Property),
+                ("aliases", &OutlineNodeType::Property),
+                ("Users", &OutlineNodeType::Method),
+                ("Point", &OutlineNodeType::Class),
+            ]
+        );
+
+        let max_users = &symbols[3];
+        assert_eq!(
+            max_users.signature.as_deref(),
+            Some("public static final int MAX_USERS")
+        );
+        assert_eq!(max_users.documentation.as_deref(), Some("Maximum users."));
+        assert_eq!(
+            symbols[5].signature.as_deref(),
+            Some("private List<String> aliases")
+        );
+
+        let constructor = &symbols[6];
+        assert_eq!(constructor.visibility, Some(Visibility::Protected));
+        assert_eq!(
+            constructor.signature.as_deref(),
+            Some("protected Users(String name)")
+        );
+    }
+
+    #[test]
+    fn test_extract_method_signature_details() {
+        let extractor = JavaExtractor::new().unwrap();
+        let source = r#"
+class Repo {
+    public <T extends Comparable<T>> List<T> find(long id, String... names) throws NotFound {
+        return null;
+    }
+}
+        "#;
+
+        let tree = parse(source);
+        let class_body = tree
+            .root_node()
+            .named_child(0)
+            .unwrap()
+            .child_by_field_name("body")
+            .unwrap();
+        let method = class_body.named_child(0).unwrap();
+        let signature = extractor
+            .extract_function_signature(&method, source)
+            .unwrap();
+
+        assert_eq!(signature.name, "find");
+        assert_eq!(signature.generic_parameters.len(), 1);
+        assert_eq!(signature.generic_parameters[0].name, "T");
+        assert_eq!(signature.parameters.len(), 2);
+        assert!(signature.parameters[1].is_variadic);
+        assert_eq!(signature.return_type.unwrap().name, "List");
+        assert_eq!(
+            signature.raw_signature,
+            "public <T extends Comparable<T>> List<T> find(long id, String... names) throws NotFound"
+        );
+    }
+
+    #[test]
+    fn test_hierarchical_relationships() {
+        let extractor = JavaExtractor::new().unwrap();
+        let source = r#"
+public class Outer {
+    private int count;
+
+    public void run() {}
+
+    private static class Inner {
+        void step() {}
+    }
+}
+
+class Other {}
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+        let hierarchical_symbols = extractor.build_hierarchy(symbols);
+
+        assert_eq!(hierarchical_symbols.len(), 2);
+        let outer = &hierarchical_symbols[0];
+        assert_eq!(outer.name, "Outer");
+        let child_names: Vec<&str> = outer.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(child_names, vec!["count", "run", "Inner"]);
+
+        let inner = &outer.children[2];
+        assert_eq!(inner.visibility, Some(Visibility::Private));
+        assert_eq!(inner.children.len(), 1);
+        assert_eq!(inner.children[0].name, "step");
+        assert_eq!(inner.children[0].visibility, Some(Visibility::Package));
+    }
+}
@@ -0,0 +1,379 @@
+//! Markdown symbol extractor for outline generation
+//!
+//! This module implements Tree-sitter based extraction of Markdown heading
+//! sections. Each ATX heading (`#` to `######`) opens a section that runs
+//! until the next heading of the same or a higher level, so sections nest
+//! the way the document's table of contents does.
+
+use crate::outline::parser::SymbolExtractor;
+use crate::outline::signature::{
+    GenericParameter, Modifier, Parameter, Signature, SignatureExtractor, TypeInfo,
+};
+use crate::outline::types::{OutlineNode, OutlineNodeType, Visibility};
+use crate::outline::{OutlineError, Result};
+use crate::search::types::Language;
+use tree_sitter::{Node, Query, QueryCursor, StreamingIterator, Tree};
+
+/// Markdown symbol extractor using Tree-sitter
+pub struct MarkdownExtractor {
+    /// Tree-sitter queries with their associated node types
+    queries: Vec<(OutlineNodeType, Query)>,
+}
+
+impl MarkdownExtractor {
+    /// Create a new Markdown extractor with compiled queries
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_md::LANGUAGE.into();
+        let mut queries = Vec::new();
+
+        // Sections are the only Markdown construct in an outline
+        let query_definitions = vec![(
+            OutlineNodeType::Section,
+            r#"(section (atx_heading)) @section"#,
+        )];
+
+        // Compile all queries
+        for (node_type, query_str) in query_definitions {
+            let query = Query::new(&language, query_str).map_err(|e| {
+                OutlineError::TreeSitter(format!("Failed to compile {node_type:?} query: {e}"))
+            })?;
+            queries.push((node_type, query));
+        }
+
+        Ok(Self { queries })
+    }
+
+    /// Extract the text content of a node
+    fn get_node_text(&self, node: &Node, source: &str) -> String {
+        source[node.start_byte()..node.end_byte()].to_string()
+    }
+
+    /// Extract line numbers for a node (1-based)
+    ///
+    /// A section ends at the start of the next heading's line, which belongs
+    /// to the next section.
+    fn get_line_range(&self, node: &Node) -> (usize, usize) {
+        let start = node.start_position();
+        let end = node.end_position();
+        let end_line = if end.column == 0 && end.row > start.row {
+            end.row
+        } else {
+            end.row + 1
+        };
+        (start.row + 1, end_line)
+    }
+
+    /// The heading that opens a section
+    fn heading<'a>(&self, section: &Node<'a>) -> Option<Node<'a>> {
+        section
+            .named_children(&mut section.walk())
+            .find(|child| child.kind() == "atx_heading")
+    }
+
+    /// Extract the heading text of a section, such as `Install`
+    fn extract_name_from_node(&self, node: &Node, source: &str) -> Option<String> {
+        let heading = self.heading(node)?;
+        let content = heading.child_by_field_name("heading_content")?;
+        let name = self
+            .get_node_text(&content, source)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// Build the heading signature, such as `## Install`
+    fn build_heading_signature(&self, node: &Node, source: &str) -> Option<String> {
+        let heading = self.heading(node)?;
+        let marker = heading.named_child(0)?;
+        let name = self.extract_name_from_node(node, source)?;
+        Some(format!("{} {name}", self.get_node_text(&marker, source)))
+    }
+
+    /// Extract the first paragraph of a section as its summary
+    fn extract_summary(&self, node: &Node, source: &str) -> Option<String> {
+        let paragraph = node
+            .named_children(&mut node.walk())
+            .take_while(|child| child.kind() != "section")
+            .find(|child| child.kind() == "paragraph")?;
+        let text = self
+            .get_node_text(&paragraph, source)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// Check if an inner symbol is within the range of an outer symbol
+    fn is_symbol_within_range(inner: &OutlineNode, outer: &OutlineNode) -> bool {
+        inner.source_range.0 >= outer.source_range.0 && inner.source_range.1 <= outer.source_range.1
+    }
+}
+
+impl SignatureExtractor for MarkdownExtractor {
+    fn extract_function_signature(&self, _node: &Node, _source: &str) -> Option<Signature> {
+        // Markdown has no functions
+        None
+    }
+
+    fn extract_method_signature(&self, _node: &Node, _source: &str) -> Option<Signature> {
+        // Markdown has no methods
+        None
+    }
+
+    fn extract_constructor_signature(&self, _node: &Node, _source: &str) -> Option<Signature> {
+        // Markdown has no constructors
+        None
+    }
+
+    fn extract_type_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        // A section's heading is the closest thing to a declaration
+        let name = self.extract_name_from_node(node, source)?;
+        let raw = self.build_heading_signature(node, source)?;
+        Some(Signature::new(name, Language::Markdown).with_raw_signature(raw))
+    }
+
+    fn parse_type_info(&self, _node: &Node, _source: &str) -> Option<TypeInfo> {
+        None
+    }
+
+    fn parse_parameter(&self, _node: &Node, _source: &str) -> Option<Parameter> {
+        None
+    }
+
+    fn parse_generic_parameters(&self, _node: &Node, _source: &str) -> Vec<GenericParameter> {
+        Vec::new()
+    }
+
+    fn parse_modifiers(&self, _node: &Node, _source: &str) -> Vec<Modifier> {
+        Vec::new()
+    }
+}
+
+impl SymbolExtractor for MarkdownExtractor {
+    fn extract_symbols(&self, tree: &Tree, source: &str) -> Result<Vec<OutlineNode>> {
+        let mut symbols = Vec::new();
+        let root_node = tree.root_node();
+
+        // Process each query
+        for (node_type, query) in &self.queries {
+            let mut cursor = QueryCursor::new();
+            let mut matches = cursor.matches(query, root_node, source.as_bytes());
+
+            while let Some(query_match) = matches.next() {
+                // Get the main captured node (should be the only capture)
+                if let Some(capture) = query_match.captures.first() {
+                    let node = &capture.node;
+
+                    if let Some(name) = self.extract_name_from_node(node, source) {
+                        let (start_line, end_line) = self.get_line_range(node);
+                        let mut outline_node = OutlineNode::new(
+                            name,
+                            node_type.clone(),
+                            start_line,
+                            end_line,
+                            (node.start_byte(), node.end_byte()),
+                        );
+
+                        if let Some(sig) = self.build_heading_signature(node, source) {
+                            outline_node = outline_node.with_signature(sig);
+                        }
+
+                        if let Some(summary) = self.extract_summary(node, source) {
+                            outline_node = outline_node.with_documentation(summary);
+                        }
+
+                        symbols.push(outline_node);
+                    }
+                }
+            }
+        }
+
+        // Sort symbols by line number
+        symbols.sort_by_key(|s| s.start_line);
+
+        Ok(symbols)
+    }
+
+    fn extract_documentation(&self, node: &Node, source: &str) -> Option<String> {
+        self.extract_summary(node, source)
+    }
+
+    fn extract_signature(&self, node: &Node, source: &str) -> Option<String> {
+        self.build_heading_signature(node, source)
+    }
+
+    fn extract_visibility(&self, _node: &Node, _source: &str) -> Option<Visibility> {
+        // Documents have no visibility
+        None
+    }
+
+    fn build_hierarchy(&self, symbols: Vec<OutlineNode>) -> Vec<OutlineNode> {
+        // Nest each section under the section whose heading it follows
+        let mut symbols = symbols;
+        symbols.sort_by(|a, b| {
+            a.source_range
+                .0
+                .cmp(&b.source_range.0)
+                .then(b.source_range.1.cmp(&a.source_range.1))
+        });
+
+        let mut roots: Vec<OutlineNode> = Vec::new();
+        let mut stack: Vec<OutlineNode> = Vec::new();
+
+        for symbol in symbols {
+            while let Some(top) = stack.last() {
+                if Self::is_symbol_within_range(&symbol, top) {
+                    break;
+                }
+                let finished = stack.pop().expect("stack is not empty");
+                match stack.last_mut() {
+                    Some(parent) => parent.add_child(finished),
+                    None => roots.push(finished),
+                }
+            }
+            stack.push(symbol);
+        }
+
+        while let Some(finished) = stack.pop() {
+            match stack.last_mut() {
+                Some(parent) => parent.add_child(finished),
+                None => roots.push(finished),
+            }
+        }
+
+        roots
+    }
+
+    fn get_queries(&self) -> Vec<(&'static str, OutlineNodeType)> {
+        vec![
+            // Heading sections
+            ("(section (atx_heading)) @section", OutlineNodeType::Section),
+        ]
+    }
+}
+
+impl Default for MarkdownExtractor {
+    fn default() -> Self {
+        Self::new().expect("Failed to create MarkdownExtractor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_md::LANGUAGE.into())
+            .unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_markdown_extractor_creation() {
+        let extractor = MarkdownExtractor::new();
+        assert!(extractor.is_ok());
+    }
+
+    #[test]
+    fn test_extract_simple_section() {
+        let extractor = MarkdownExtractor::new().unwrap();
+        let source = "# Getting Started\n\nInstall the tool\nwith cargo.\n\nThen run it.\n";
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        let section = &symbols[0];
+        assert_eq!(section.name, "Getting Started");
+        assert_eq!(section.node_type, OutlineNodeType::Section);
+        assert_eq!(section.visibility, None);
+        assert_eq!(section.signature.as_deref(), Some("# Getting Started"));
+        assert_eq!(
+            section.documentation.as_deref(),
+            Some("Install the tool with cargo.")
+        );
+        assert_eq!(section.start_line, 1);
+        assert_eq!(section.end_line, 6);
+    }
+
+    #[test]
+    fn test_extract_multiple_sections() {
+        let extractor = MarkdownExtractor::new().unwrap();
+        let source = r#"# Guide
+
+Intro text.
+
+## Install
+
+```sh
+# not a heading
+cargo install tool
+```
+
+### From source
+
+Clone it.
+
+## Usage
+
+Run it.
+"#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, Option<&str>)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.signature.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Guide", Some("# Guide")),
+                ("Install", Some("## Install")),
+                ("From source", Some("### From source")),
+                ("Usage", Some("## Usage")),
+            ]
+        );
+
+        // A section ends on the line before the next heading at its level
+        let install = &symbols[1];
+        assert_eq!(install.start_line, 5);
+        assert_eq!(install.end_line, 15);
+        assert_eq!(install.documentation, None);
+        assert_eq!(symbols[3].end_line, 18);
+    }
+
+    #[test]
+    fn test_hierarchical_relationships() {
+        let extractor = MarkdownExtractor::new().unwrap();
+        let source = r#"# Guide
+
+## Install
+
+### From source
+
+## Usage
+
+# Reference
+"#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+        let hierarchical_symbols = extractor.build_hierarchy(symbols);
+
+        let top_level: Vec<&str> = hierarchical_symbols
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(top_level, vec!["Guide", "Reference"]);
+
+        let guide = &hierarchical_symbols[0];
+        let child_names: Vec<&str> = guide.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(child_names, vec!["Install", "Usage"]);
+        assert_eq!(guide.children[0].children[0].name, "From source");
+    }
+}
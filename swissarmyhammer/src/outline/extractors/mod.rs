@@ -4,14 +4,51 @@
 //! languages, implementing the SymbolExtractor trait to generate structured
 //! code outlines.
 
+pub mod c;
+pub mod cpp;
+pub mod csharp;
 pub mod dart;
+pub mod go;
+pub mod java;
 pub mod javascript;
+pub mod markdown;
 pub mod python;
+pub mod ruby;
 pub mod rust;
 pub mod typescript;
 
+pub use c::CExtractor;
+pub use cpp::CppExtractor;
+pub use csharp::CSharpExtractor;
 pub use dart::DartExtractor;
+pub use go::GoExtractor;
+pub use java::JavaExtractor;
 pub use javascript::JavaScriptExtractor;
+pub use markdown::MarkdownExtractor;
 pub use python::PythonExtractor;
+pub use ruby::RubyExtractor;
 pub use rust::RustExtractor;
 pub use typescript::TypeScriptExtractor;
+
+use crate::outline::parser::SymbolExtractor;
+use crate::outline::Result;
+use crate::search::types::Language;
+use std::collections::HashMap;
+
+/// Create the symbol extractor of every supported language
+pub fn create_extractors() -> Result<HashMap<Language, Box<dyn SymbolExtractor>>> {
+    let mut extractors: HashMap<Language, Box<dyn SymbolExtractor>> = HashMap::new();
+    extractors.insert(Language::Rust, Box::new(RustExtractor::new()?));
+    extractors.insert(Language::TypeScript, Box::new(TypeScriptExtractor::new()?));
+    extractors.insert(Language::JavaScript, Box::new(JavaScriptExtractor::new()?));
+    extractors.insert(Language::Python, Box::new(PythonExtractor::new()?));
+    extractors.insert(Language::Dart, Box::new(DartExtractor::new()?));
+    extractors.insert(Language::Go, Box::new(GoExtractor::new()?));
+    extractors.insert(Language::Java, Box::new(JavaExtractor::new()?));
+    extractors.insert(Language::C, Box::new(CExtractor::new()?));
+    extractors.insert(Language::Cpp, Box::new(CppExtractor::new()?));
+    extractors.insert(Language::CSharp, Box::new(CSharpExtractor::new()?));
+    extractors.insert(Language::Ruby, Box::new(RubyExtractor::new()?));
+    extractors.insert(Language::Markdown, Box::new(MarkdownExtractor::new()?));
+    Ok(extractors)
+}
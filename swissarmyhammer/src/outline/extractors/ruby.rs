@@ -0,0 +1,846 @@
+//! Ruby language symbol extractor for outline generation
+//!
+//! This module implements Tree-sitter based symbol extraction for Ruby code,
+//! supporting modules, classes, instance and singleton methods, constants,
+//! attribute accessors and requires, along with their comments, signatures
+//! and `private`/`protected` sections.
+
+use crate::outline::parser::SymbolExtractor;
+use crate::outline::signature::{
+    GenericParameter, Modifier, Parameter, Signature, SignatureExtractor, TypeInfo,
+};
+use crate::outline::types::{OutlineNode, OutlineNodeType, Visibility};
+use crate::outline::{OutlineError, Result};
+use crate::search::types::Language;
+use tree_sitter::{Node, Query, QueryCursor, StreamingIterator, Tree};
+
+/// Ruby symbol extractor using Tree-sitter
+pub struct RubyExtractor {
+    /// Tree-sitter queries with their associated node types
+    queries: Vec<(OutlineNodeType, Query)>,
+}
+
+impl RubyExtractor {
+    /// Create a new Ruby extractor with compiled queries
+    pub fn new() -> Result<Self> {
+        let language = tree_sitter_ruby::LANGUAGE.into();
+        let mut queries = Vec::new();
+
+        // Define Tree-sitter queries for each Ruby construct
+        let query_definitions = vec![
+            (OutlineNodeType::Module, r#"(module) @module"#),
+            (OutlineNodeType::Class, r#"(class) @class"#),
+            // Methods outside a class or module are reclassified as functions
+            (
+                OutlineNodeType::Method,
+                r#"[(method) (singleton_method)] @method"#,
+            ),
+            (
+                OutlineNodeType::Constant,
+                r#"(assignment left: (constant)) @constant"#,
+            ),
+            // Arguments of attr_reader and friends, filtered in extract_symbols
+            (
+                OutlineNodeType::Property,
+                r#"(call arguments: (argument_list (simple_symbol) @attribute))"#,
+            ),
+            // Top-level calls, filtered to require and require_relative
+            (OutlineNodeType::Import, r#"(program (call) @require)"#),
+        ];
+
+        // Compile all queries
+        for (node_type, query_str) in query_definitions {
+            let query = Query::new(&language, query_str).map_err(|e| {
+                OutlineError::TreeSitter(format!("Failed to compile {node_type:?} query: {e}"))
+            })?;
+            queries.push((node_type, query));
+        }
+
+        Ok(Self { queries })
+    }
+
+    /// Extract the text content of a node
+    fn get_node_text(&self, node: &Node, source: &str) -> String {
+        source[node.start_byte()..node.end_byte()].to_string()
+    }
+
+    /// Extract line numbers for a node (1-based)
+    fn get_line_range(&self, node: &Node) -> (usize, usize) {
+        let start_line = node.start_position().row + 1;
+        let end_line = node.end_position().row + 1;
+        (start_line, end_line)
+    }
+
+    /// The name of the method a call invokes, such as `attr_reader`
+    fn call_method_name(&self, call: &Node, source: &str) -> Option<String> {
+        if call.kind() != "call" || call.child_by_field_name("receiver").is_some() {
+            return None;
+        }
+        let method = call.child_by_field_name("method")?;
+        Some(self.get_node_text(&method, source))
+    }
+
+    /// The call a symbol argument is passed to
+    fn argument_call<'a>(&self, node: &Node<'a>) -> Option<Node<'a>> {
+        let arguments = node.parent()?;
+        if arguments.kind() != "argument_list" {
+            return None;
+        }
+        arguments.parent()
+    }
+
+    /// Whether a captured node belongs to the query's node type
+    fn matches_node_type(&self, node_type: &OutlineNodeType, node: &Node, source: &str) -> bool {
+        match node_type {
+            OutlineNodeType::Property => self
+                .argument_call(node)
+                .and_then(|call| self.call_method_name(&call, source))
+                .is_some_and(|method| {
+                    matches!(
+                        method.as_str(),
+                        "attr_reader" | "attr_writer" | "attr_accessor"
+                    )
+                }),
+            OutlineNodeType::Import => self
+                .call_method_name(node, source)
+                .is_some_and(|method| matches!(method.as_str(), "require" | "require_relative")),
+            // Constants assigned inside methods are not declarations
+            OutlineNodeType::Constant => self.enclosing_method(node).is_none(),
+            _ => true,
+        }
+    }
+
+    /// Extract the name from a Tree-sitter node
+    fn extract_name_from_node(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "assignment" => node
+                .child_by_field_name("left")
+                .map(|name| self.get_node_text(&name, source)),
+            "simple_symbol" => Some(
+                self.get_node_text(node, source)
+                    .trim_start_matches(':')
+                    .to_string(),
+            ),
+            "call" => {
+                let arguments = node.child_by_field_name("arguments")?;
+                let path = arguments.named_child(0)?;
+                let content = path
+                    .named_children(&mut path.walk())
+                    .find(|child| child.kind() == "string_content")?;
+                Some(self.get_node_text(&content, source))
+            }
+            _ => node
+                .child_by_field_name("name")
+                .map(|name| self.get_node_text(&name, source)),
+        }
+    }
+
+    /// The node a symbol's comments precede
+    ///
+    /// `private def secret; end` wraps the method in a call, and attribute
+    /// symbols share their `attr_reader` call.
+    fn declaration_node<'a>(&self, node: &Node<'a>) -> Node<'a> {
+        match node.kind() {
+            "method" | "singleton_method" | "simple_symbol" => {
+                self.argument_call(node).unwrap_or(*node)
+            }
+            _ => *node,
+        }
+    }
+
+    /// The innermost method containing a node, if any
+    fn enclosing_method<'a>(&self, node: &Node<'a>) -> Option<Node<'a>> {
+        let mut parent = node.parent();
+        while let Some(ancestor) = parent {
+            if matches!(ancestor.kind(), "method" | "singleton_method") {
+                return Some(ancestor);
+            }
+            parent = ancestor.parent();
+        }
+        None
+    }
+
+    /// Whether a node is defined in the body of a class or module
+    fn is_in_namespace(&self, node: &Node) -> bool {
+        let mut parent = node.parent();
+        while let Some(ancestor) = parent {
+            match ancestor.kind() {
+                "class" | "module" | "singleton_class" => return true,
+                "method" | "singleton_method" => return false,
+                _ => parent = ancestor.parent(),
+            }
+        }
+        false
+    }
+
+    /// Whether a method is defined on the class itself rather than its instances
+    fn is_class_method(&self, node: &Node) -> bool {
+        node.kind() == "singleton_method"
+            || node
+                .parent()
+                .and_then(|body| body.parent())
+                .is_some_and(|owner| owner.kind() == "singleton_class")
+    }
+
+    /// Get the visibility of a method or attribute
+    ///
+    /// `private def secret` sets the visibility of one method, while a bare
+    /// `private` applies to the definitions that follow it in the same body.
+    fn get_visibility(&self, declaration: &Node, source: &str) -> Visibility {
+        let keyword_visibility = |keyword: &str| match keyword {
+            "private" => Some(Visibility::Private),
+            "protected" => Some(Visibility::Protected),
+            "public" => Some(Visibility::Public),
+            _ => None,
+        };
+
+        if let Some(visibility) = self
+            .call_method_name(declaration, source)
+            .and_then(|method| keyword_visibility(&method))
+        {
+            return visibility;
+        }
+
+        let mut sibling = declaration.prev_named_sibling();
+        while let Some(node) = sibling {
+            if node.kind() == "identifier" {
+                if let Some(visibility) = keyword_visibility(&self.get_node_text(&node, source)) {
+                    return visibility;
+                }
+            }
+            sibling = node.prev_named_sibling();
+        }
+        Visibility::Public
+    }
+
+    /// Extract the `#` comments directly above a node
+    fn extract_comments(&self, node: &Node, source: &str) -> Option<String> {
+        let mut doc_lines = Vec::new();
+        let mut next_row = node.start_position().row;
+
+        // The first statement of a body follows comments placed before the body
+        let mut sibling = node.prev_sibling().or_else(|| {
+            node.parent()
+                .filter(|parent| parent.kind() == "body_statement")
+                .and_then(|body| body.prev_sibling())
+        });
+
+        while let Some(comment) = sibling {
+            // Stop at anything that is not a comment or is separated by a blank line
+            if comment.kind() != "comment" || comment.end_position().row + 1 < next_row {
+                break;
+            }
+            let text = self.get_node_text(&comment, source);
+            doc_lines.insert(0, text.trim_start_matches('#').trim().to_string());
+            next_row = comment.start_position().row;
+            sibling = comment.prev_sibling();
+        }
+
+        if doc_lines.is_empty() {
+            None
+        } else {
+            Some(doc_lines.join(" "))
+        }
+    }
+
+    /// Build method signature, such as `def initialize(total, currency: "USD")`
+    fn build_method_signature(&self, node: &Node, source: &str) -> Option<String> {
+        let end = node
+            .child_by_field_name("parameters")
+            .or_else(|| node.child_by_field_name("name"))?
+            .end_byte();
+        Some(
+            source[node.start_byte()..end]
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+
+    /// Build class or module signature, such as `class Invoice < Base`
+    fn build_type_signature(&self, node: &Node, source: &str) -> Option<String> {
+        let end = node
+            .child_by_field_name("superclass")
+            .or_else(|| node.child_by_field_name("name"))?
+            .end_byte();
+        Some(
+            source[node.start_byte()..end]
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+
+    /// Build constant signature from the first line of its assignment, such as `MAX = 3`
+    fn build_constant_signature(&self, node: &Node, source: &str) -> String {
+        self.get_node_text(node, source)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    }
+
+    /// Build attribute signature, such as `attr_reader :total`
+    fn build_attribute_signature(&self, node: &Node, source: &str) -> Option<String> {
+        let call = self.argument_call(node)?;
+        let method = self.call_method_name(&call, source)?;
+        Some(format!("{method} {}", self.get_node_text(node, source)))
+    }
+
+    /// Build require signature, such as `require "json"`
+    fn build_require_signature(&self, node: &Node, source: &str) -> String {
+        self.get_node_text(node, source)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Check if an inner symbol is within the range of an outer symbol
+    fn is_symbol_within_range(inner: &OutlineNode, outer: &OutlineNode) -> bool {
+        inner.source_range.0 >= outer.source_range.0 && inner.source_range.1 <= outer.source_range.1
+    }
+}
+
+impl SignatureExtractor for RubyExtractor {
+    fn extract_function_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        if !matches!(node.kind(), "method" | "singleton_method") {
+            return None;
+        }
+
+        let name = self.extract_name_from_node(node, source)?;
+        let mut signature = Signature::new(name, Language::Ruby);
+
+        signature = signature.with_modifiers(self.parse_modifiers(node, source));
+
+        if let Some(params_node) = node.child_by_field_name("parameters") {
+            for param_node in params_node.named_children(&mut params_node.walk()) {
+                if let Some(param) = self.parse_parameter(&param_node, source) {
+                    signature = signature.with_parameter(param);
+                }
+            }
+        }
+
+        if let Some(raw) = self.build_method_signature(node, source) {
+            signature = signature.with_raw_signature(raw);
+        }
+        Some(signature)
+    }
+
+    fn extract_method_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        let signature = self.extract_function_signature(node, source)?;
+        if node.kind() == "method" && signature.name == "initialize" {
+            Some(signature.constructor())
+        } else {
+            Some(signature)
+        }
+    }
+
+    fn extract_constructor_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        let signature = self.extract_function_signature(node, source)?;
+        (node.kind() == "method" && signature.name == "initialize").then(|| signature.constructor())
+    }
+
+    fn extract_type_signature(&self, node: &Node, source: &str) -> Option<Signature> {
+        if !matches!(node.kind(), "class" | "module") {
+            return None;
+        }
+
+        let name = self.extract_name_from_node(node, source)?;
+        let raw = self.build_type_signature(node, source)?;
+        Some(Signature::new(name, Language::Ruby).with_raw_signature(raw))
+    }
+
+    fn parse_type_info(&self, node: &Node, source: &str) -> Option<TypeInfo> {
+        // Ruby has no type annotations; constants name classes
+        let text = self.get_node_text(node, source);
+        (!text.is_empty()).then(|| TypeInfo::new(text))
+    }
+
+    fn parse_parameter(&self, node: &Node, source: &str) -> Option<Parameter> {
+        let name_of = |node: &Node| {
+            node.child_by_field_name("name")
+                .map(|name| self.get_node_text(&name, source))
+        };
+
+        match node.kind() {
+            "identifier" => Some(Parameter::new(self.get_node_text(node, source))),
+            "optional_parameter" => {
+                let mut param = Parameter::new(name_of(node)?).optional();
+                if let Some(value) = node.child_by_field_name("value") {
+                    param = param.with_default(self.get_node_text(&value, source));
+                }
+                Some(param)
+            }
+            "keyword_parameter" => {
+                let mut param = Parameter::new(format!("{}:", name_of(node)?));
+                if let Some(value) = node.child_by_field_name("value") {
+                    param = param
+                        .optional()
+                        .with_default(self.get_node_text(&value, source));
+                }
+                Some(param)
+            }
+            "splat_parameter" => {
+                Some(Parameter::new(format!("*{}", name_of(node).unwrap_or_default())).variadic())
+            }
+            "hash_splat_parameter" => {
+                Some(Parameter::new(format!("**{}", name_of(node).unwrap_or_default())).variadic())
+            }
+            "block_parameter" => Some(Parameter::new(format!(
+                "&{}",
+                name_of(node).unwrap_or_default()
+            ))),
+            _ => None,
+        }
+    }
+
+    fn parse_generic_parameters(&self, _node: &Node, _source: &str) -> Vec<GenericParameter> {
+        // Ruby has no generics
+        Vec::new()
+    }
+
+    fn parse_modifiers(&self, node: &Node, source: &str) -> Vec<Modifier> {
+        let mut modifiers = Vec::new();
+        if self.is_class_method(node) {
+            modifiers.push(Modifier::ClassMethod);
+        }
+        match self.get_visibility(&self.declaration_node(node), source) {
+            Visibility::Private => modifiers.push(Modifier::Private),
+            Visibility::Protected => modifiers.push(Modifier::Protected),
+            _ => modifiers.push(Modifier::Public),
+        }
+        modifiers
+    }
+}
+
+impl SymbolExtractor for RubyExtractor {
+    fn extract_symbols(&self, tree: &Tree, source: &str) -> Result<Vec<OutlineNode>> {
+        let mut symbols = Vec::new();
+        let root_node = tree.root_node();
+
+        // Process each query
+        for (node_type, query) in &self.queries {
+            let mut cursor = QueryCursor::new();
+            let mut matches = cursor.matches(query, root_node, source.as_bytes());
+
+            while let Some(query_match) = matches.next() {
+                // Get the main captured node (should be the only capture)
+                if let Some(capture) = query_match.captures.first() {
+                    let node = &capture.node;
+
+                    if !self.matches_node_type(node_type, node, source) {
+                        continue;
+                    }
+
+                    if let Some(name) = self.extract_name_from_node(node, source) {
+                        let symbol_type = match node_type {
+                            OutlineNodeType::Method if !self.is_in_namespace(node) => {
+                                OutlineNodeType::Function
+                            }
+                            _ => node_type.clone(),
+                        };
+
+                        let declaration = self.declaration_node(node);
+                        let (start_line, end_line) = self.get_line_range(node);
+                        let mut outline_node = OutlineNode::new(
+                            name,
+                            symbol_type,
+                            start_line,
+                            end_line,
+                            (node.start_byte(), node.end_byte()),
+                        );
+
+                        // Add signature based on node type
+                        let signature = match node_type {
+                            OutlineNodeType::Method => self
+                                .extract_method_signature(node, source)
+                                .map(|sig| sig.format_for_language(Language::Ruby)),
+                            OutlineNodeType::Module | OutlineNodeType::Class => self
+                                .extract_type_signature(node, source)
+                                .map(|sig| sig.format_for_language(Language::Ruby)),
+                            OutlineNodeType::Constant => {
+                                Some(self.build_constant_signature(node, source))
+                            }
+                            OutlineNodeType::Property => {
+                                self.build_attribute_signature(node, source)
+                            }
+                            OutlineNodeType::Import => {
+                                Some(self.build_require_signature(node, source))
+                            }
+                            _ => None,
+                        };
+
+                        if let Some(sig) = signature {
+                            outline_node = outline_node.with_signature(sig);
+                        }
+
+                        // Add visibility
+                        let visibility = match node_type {
+                            OutlineNodeType::Method | OutlineNodeType::Property => {
+                                Some(self.get_visibility(&declaration, source))
+                            }
+                            OutlineNodeType::Import => None,
+                            _ => Some(Visibility::Public),
+                        };
+                        if let Some(visibility) = visibility {
+                            outline_node = outline_node.with_visibility(visibility);
+                        }
+
+                        // Add documentation
+                        if let Some(docs) = self.extract_comments(&declaration, source) {
+                            outline_node = outline_node.with_documentation(docs);
+                        }
+
+                        symbols.push(outline_node);
+                    }
+                }
+            }
+        }
+
+        // Sort symbols by line number
+        symbols.sort_by_key(|s| s.start_line);
+
+        Ok(symbols)
+    }
+
+    fn extract_documentation(&self, node: &Node, source: &str) -> Option<String> {
+        self.extract_comments(&self.declaration_node(node), source)
+    }
+
+    fn extract_signature(&self, node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "method" | "singleton_method" => self.build_method_signature(node, source),
+            "class" | "module" => self.build_type_signature(node, source),
+            "assignment" => Some(self.build_constant_signature(node, source)),
+            "simple_symbol" => self.build_attribute_signature(node, source),
+            "call" => Some(self.build_require_signature(node, source)),
+            _ => None,
+        }
+    }
+
+    fn extract_visibility(&self, node: &Node, source: &str) -> Option<Visibility> {
+        Some(self.get_visibility(&self.declaration_node(node), source))
+    }
+
+    fn build_hierarchy(&self, symbols: Vec<OutlineNode>) -> Vec<OutlineNode> {
+        // Nest each symbol under the innermost symbol that contains it
+        let mut symbols = symbols;
+        symbols.sort_by(|a, b| {
+            a.source_range
+                .0
+                .cmp(&b.source_range.0)
+                .then(b.source_range.1.cmp(&a.source_range.1))
+        });
+
+        let mut roots: Vec<OutlineNode> = Vec::new();
+        let mut stack: Vec<OutlineNode> = Vec::new();
+
+        for symbol in symbols {
+            while let Some(top) = stack.last() {
+                if Self::is_symbol_within_range(&symbol, top) {
+                    break;
+                }
+                let finished = stack.pop().expect("stack is not empty");
+                match stack.last_mut() {
+                    Some(parent) => parent.add_child(finished),
+                    None => roots.push(finished),
+                }
+            }
+            stack.push(symbol);
+        }
+
+        while let Some(finished) = stack.pop() {
+            match stack.last_mut() {
+                Some(parent) => parent.add_child(finished),
+                None => roots.push(finished),
+            }
+        }
+
+        roots
+    }
+
+    fn get_queries(&self) -> Vec<(&'static str, OutlineNodeType)> {
+        vec![
+            // Modules
+            ("(module) @module", OutlineNodeType::Module),
+            // Classes
+            ("(class) @class", OutlineNodeType::Class),
+            // Instance and singleton methods
+            (
+                "[(method) (singleton_method)] @method",
+                OutlineNodeType::Method,
+            ),
+            // Constants
+            (
+                "(assignment left: (constant)) @constant",
+                OutlineNodeType::Constant,
+            ),
+            // Attribute accessors
+            (
+                "(call arguments: (argument_list (simple_symbol) @attribute))",
+                OutlineNodeType::Property,
+            ),
+            // Requires
+            ("(program (call) @require)", OutlineNodeType::Import),
+        ]
+    }
+}
+
+impl Default for RubyExtractor {
+    fn default() -> Self {
+        Self::new().expect("Failed to create RubyExtractor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_ruby::LANGUAGE.into())
+            .unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_ruby_extractor_creation() {
+        let extractor = RubyExtractor::new();
+        assert!(extractor.is_ok());
+    }
+
+    #[test]
+    fn test_extract_simple_function() {
+        let extractor = RubyExtractor::new().unwrap();
+        let source = r#"
+# Greets someone
+# by name.
+def greet(name, greeting = "Hello")
+  greeting + ", " + name
+end
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        let func = &symbols[0];
+        assert_eq!(func.name, "greet");
+        assert_eq!(func.node_type, OutlineNodeType::Function);
+        assert_eq!(func.visibility, Some(Visibility::Public));
+        assert_eq!(
+            func.signature.as_deref(),
+            Some(r#"def greet(name, greeting = "Hello")"#)
+        );
+        assert_eq!(
+            func.documentation.as_deref(),
+            Some("Greets someone by name.")
+        );
+    }
+
+    #[test]
+    fn test_extract_class() {
+        let extractor = RubyExtractor::new().unwrap();
+        let source = r#"
+# An invoice.
+class Invoice < Base
+  attr_reader :total, :lines
+
+  # Creates an invoice.
+  def initialize(total)
+    @total = total
+  end
+end
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Invoice", &OutlineNodeType::Class),
+                ("total", &OutlineNodeType::Property),
+                ("lines", &OutlineNodeType::Property),
+                ("initialize", &OutlineNodeType::Method),
+            ]
+        );
+
+        let class = &symbols[0];
+        assert_eq!(class.signature.as_deref(), Some("class Invoice < Base"));
+        assert_eq!(class.documentation.as_deref(), Some("An invoice."));
+        assert_eq!(symbols[1].signature.as_deref(), Some("attr_reader :total"));
+        assert_eq!(
+            symbols[3].documentation.as_deref(),
+            Some("Creates an invoice.")
+        );
+    }
+
+    #[test]
+    fn test_extract_visibility_sections() {
+        let extractor = RubyExtractor::new().unwrap();
+        let source = r#"
+class Account
+  def balance; end
+
+  protected def guarded; end
+
+  private
+
+  def secret; end
+
+  public
+
+  def visible; end
+end
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let visibilities: Vec<(&str, Option<Visibility>)> = symbols
+            .iter()
+            .skip(1)
+            .map(|s| (s.name.as_str(), s.visibility.clone()))
+            .collect();
+        assert_eq!(
+            visibilities,
+            vec![
+                ("balance", Some(Visibility::Public)),
+                ("guarded", Some(Visibility::Protected)),
+                ("secret", Some(Visibility::Private)),
+                ("visible", Some(Visibility::Public)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_multiple_symbols() {
+        let extractor = RubyExtractor::new().unwrap();
+        let source = r#"
+require "json"
+require_relative "lib/helper"
+
+module Billing
+  # Max retries.
+  MAX_RETRIES = 3
+
+  def self.configure(**options, &block)
+    LOCAL = 1
+  end
+
+  class << self
+    def load(path) = path
+  end
+end
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+
+        let names: Vec<(&str, &OutlineNodeType)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), &s.node_type))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("json", &OutlineNodeType::Import),
+                ("lib/helper", &OutlineNodeType::Import),
+                ("Billing", &OutlineNodeType::Module),
+                ("MAX_RETRIES", &OutlineNodeType::Constant),
+                ("configure", &OutlineNodeType::Method),
+                ("load", &OutlineNodeType::Method),
+            ]
+        );
+
+        assert_eq!(symbols[0].signature.as_deref(), Some(r#"require "json""#));
+        assert_eq!(symbols[3].signature.as_deref(), Some("MAX_RETRIES = 3"));
+        assert_eq!(symbols[3].documentation.as_deref(), Some("Max retries."));
+        assert_eq!(
+            symbols[4].signature.as_deref(),
+            Some("def self.configure(**options, &block)")
+        );
+        assert_eq!(symbols[5].signature.as_deref(), Some("def load(path)"));
+    }
+
+    #[test]
+    fn test_extract_method_signature_details() {
+        let extractor = RubyExtractor::new().unwrap();
+        let source = r#"
+class Invoice
+  def initialize(total, *items, currency: "USD", rate:, **opts, &block)
+  end
+end
+        "#;
+
+        let tree = parse(source);
+        let body = tree
+            .root_node()
+            .named_child(0)
+            .unwrap()
+            .child_by_field_name("body")
+            .unwrap();
+        let method = body.named_child(0).unwrap();
+        let signature = extractor.extract_method_signature(&method, source).unwrap();
+
+        assert_eq!(signature.name, "initialize");
+        assert!(signature.is_constructor);
+        let params: Vec<&str> = signature
+            .parameters
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(
+            params,
+            vec!["total", "*items", "currency:", "rate:", "**opts", "&block"]
+        );
+        assert!(signature.parameters[1].is_variadic);
+        assert!(signature.parameters[2].is_optional);
+        assert!(!signature.parameters[3].is_optional);
+    }
+
+    #[test]
+    fn test_hierarchical_relationships() {
+        let extractor = RubyExtractor::new().unwrap();
+        let source = r#"
+module Billing
+  class Invoice
+    def total; end
+
+    class Line
+      def amount; end
+    end
+  end
+
+  def self.helper; end
+end
+
+def main; end
+        "#;
+
+        let tree = parse(source);
+        let symbols = extractor.extract_symbols(&tree, source).unwrap();
+        let hierarchical_symbols = extractor.build_hierarchy(symbols);
+
+        assert_eq!(hierarchical_symbols.len(), 2);
+        let billing = &hierarchical_symbols[0];
+        assert_eq!(billing.name, "Billing");
+        let child_names: Vec<&str> = billing.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(child_names, vec!["Invoice", "helper"]);
+
+        let invoice = &billing.children[0];
+        let member_names: Vec<&str> = invoice.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(member_names, vec!["total", "Line"]);
+        assert_eq!(invoice.children[1].children[0].name, "amount");
+
+        assert_eq!(hierarchical_symbols[1].name, "main");
+        assert_eq!(hierarchical_symbols[1].node_type, OutlineNodeType::Function);
+    }
+}
@@ -235,13 +235,11 @@ impl FileDiscovery {
 
     /// Get supported file patterns based on language registry
     pub fn supported_patterns() -> Vec<String> {
-        vec![
-            "**/*.rs".to_string(),   // Rust
-            "**/*.py".to_string(),   // Python
-            "**/*.ts".to_string(),   // TypeScript
-            "**/*.js".to_string(),   // JavaScript
-            "**/*.dart".to_string(), // Dart
-        ]
+        LanguageRegistry::with_defaults()
+            .supported_extensions()
+            .into_iter()
+            .map(|extension| format!("**/*.{extension}"))
+            .collect()
     }
 
     /// Filter discovered files to only include supported languages
@@ -462,6 +460,15 @@ mod tests {
         assert!(patterns.iter().any(|p| p.contains("*.ts")));
         assert!(patterns.iter().any(|p| p.contains("*.js")));
         assert!(patterns.iter().any(|p| p.contains("*.dart")));
+        assert!(patterns.iter().any(|p| p.contains("*.go")));
+        assert!(patterns.iter().any(|p| p.contains("*.java")));
+        assert!(patterns.iter().any(|p| p.contains("*.md")));
+        for extension in ["hpp", "cc", "cs", "rb", "markdown"] {
+            assert!(
+                patterns.contains(&format!("**/*.{extension}")),
+                "missing {extension}"
+            );
+        }
     }
 
     #[test]
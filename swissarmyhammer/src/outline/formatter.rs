@@ -240,22 +240,7 @@ impl YamlFormatter {
 
     /// Convert OutlineNodeType to string representation
     fn node_type_to_string(&self, node_type: &OutlineNodeType) -> &'static str {
-        match node_type {
-            OutlineNodeType::Function => "function",
-            OutlineNodeType::Method => "method",
-            OutlineNodeType::Class => "class",
-            OutlineNodeType::Struct => "struct",
-            OutlineNodeType::Enum => "enum",
-            OutlineNodeType::Interface => "interface",
-            OutlineNodeType::Trait => "trait",
-            OutlineNodeType::Impl => "impl",
-            OutlineNodeType::Module => "module",
-            OutlineNodeType::Property => "property",
-            OutlineNodeType::Constant => "constant",
-            OutlineNodeType::Variable => "variable",
-            OutlineNodeType::TypeAlias => "type_alias",
-            OutlineNodeType::Import => "import",
-        }
+        node_type.as_str()
     }
 
     /// Format a signature string, applying length limits
@@ -369,6 +354,7 @@ impl YamlFormatter {
             OutlineNodeType::Method => 11,
             OutlineNodeType::Variable => 12,
             OutlineNodeType::Property => 13,
+            OutlineNodeType::Section => 14,
         }
     }
 }
@@ -383,6 +383,7 @@ fn symbol_kind_order(node_type: &crate::outline::OutlineNodeType) -> u8 {
         OutlineNodeType::Method => 11,
         OutlineNodeType::Variable => 12,
         OutlineNodeType::Property => 13,
+        OutlineNodeType::Section => 14,
     }
 }
 
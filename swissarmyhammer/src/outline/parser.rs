@@ -4,9 +4,7 @@
 //! the existing search parser infrastructure. It focuses on extracting structured
 //! symbol information rather than creating search indexes.
 
-use crate::outline::extractors::{
-    DartExtractor, JavaScriptExtractor, PythonExtractor, RustExtractor, TypeScriptExtractor,
-};
+use crate::outline::extractors::create_extractors;
 use crate::outline::{OutlineNode, OutlineNodeType, OutlineTree, Result, Visibility};
 use crate::search::parser::{CodeParser, ParserConfig};
 use crate::search::types::Language;
@@ -86,14 +84,8 @@ impl OutlineParser {
         let code_parser = CodeParser::new(parser_config)
             .map_err(|e| crate::outline::OutlineError::TreeSitter(e.to_string()))?;
 
-        let mut extractors: HashMap<Language, Box<dyn SymbolExtractor>> = HashMap::new();
-
         // Register language-specific extractors
-        extractors.insert(Language::Rust, Box::new(RustExtractor::new()?));
-        extractors.insert(Language::TypeScript, Box::new(TypeScriptExtractor::new()?));
-        extractors.insert(Language::JavaScript, Box::new(JavaScriptExtractor::new()?));
-        extractors.insert(Language::Python, Box::new(PythonExtractor::new()?));
-        extractors.insert(Language::Dart, Box::new(DartExtractor::new()?));
+        let extractors = create_extractors()?;
 
         Ok(Self {
             code_parser,
@@ -139,6 +131,13 @@ impl OutlineParser {
             Language::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
             Language::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
             Language::Dart => tree_sitter_dart::language(),
+            Language::Go => tree_sitter_go::LANGUAGE.into(),
+            Language::Java => tree_sitter_java::LANGUAGE.into(),
+            Language::C => tree_sitter_c::LANGUAGE.into(),
+            Language::Cpp => tree_sitter_cpp::LANGUAGE.into(),
+            Language::CSharp => tree_sitter_c_sharp::LANGUAGE.into(),
+            Language::Ruby => tree_sitter_ruby::LANGUAGE.into(),
+            Language::Markdown => tree_sitter_md::LANGUAGE.into(),
             Language::Unknown => {
                 return Err(crate::outline::OutlineError::LanguageDetection(
                     "Cannot parse unknown language".to_string(),
@@ -378,5 +377,12 @@ pub mod inner {
         assert!(supported.contains(&Language::JavaScript));
         assert!(supported.contains(&Language::Python));
         assert!(supported.contains(&Language::Dart));
+        assert!(supported.contains(&Language::Go));
+        assert!(supported.contains(&Language::Java));
+        assert!(supported.contains(&Language::C));
+        assert!(supported.contains(&Language::Cpp));
+        assert!(supported.contains(&Language::CSharp));
+        assert!(supported.contains(&Language::Ruby));
+        assert!(supported.contains(&Language::Markdown));
     }
 }
@@ -732,7 +732,14 @@ impl Signature {
             Language::JavaScript => self.format_javascript_style(),
             Language::Dart => self.format_dart_style(),
             Language::Python => self.format_python_style(),
-            Language::Unknown => self.raw_signature.clone(),
+            Language::Go
+            | Language::Java
+            | Language::C
+            | Language::Cpp
+            | Language::CSharp
+            | Language::Ruby
+            | Language::Markdown
+            | Language::Unknown => self.raw_signature.clone(),
         }
     }
 
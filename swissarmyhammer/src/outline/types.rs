@@ -3,7 +3,9 @@
 use crate::search::types::Language;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 /// A discovered file ready for outline processing
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -189,6 +191,74 @@ pub enum OutlineNodeType {
     TypeAlias,
     /// Import or use statement
     Import,
+    /// Heading section of a document (Markdown)
+    Section,
+}
+
+impl OutlineNodeType {
+    /// Every node type, in declaration order
+    pub const ALL: [OutlineNodeType; 15] = [
+        OutlineNodeType::Function,
+        OutlineNodeType::Method,
+        OutlineNodeType::Class,
+        OutlineNodeType::Struct,
+        OutlineNodeType::Enum,
+        OutlineNodeType::Interface,
+        OutlineNodeType::Trait,
+        OutlineNodeType::Impl,
+        OutlineNodeType::Module,
+        OutlineNodeType::Property,
+        OutlineNodeType::Constant,
+        OutlineNodeType::Variable,
+        OutlineNodeType::TypeAlias,
+        OutlineNodeType::Import,
+        OutlineNodeType::Section,
+    ];
+
+    /// Lowercase name of the node type, as used in outlines and search filters
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutlineNodeType::Function => "function",
+            OutlineNodeType::Method => "method",
+            OutlineNodeType::Class => "class",
+            OutlineNodeType::Struct => "struct",
+            OutlineNodeType::Enum => "enum",
+            OutlineNodeType::Interface => "interface",
+            OutlineNodeType::Trait => "trait",
+            OutlineNodeType::Impl => "impl",
+            OutlineNodeType::Module => "module",
+            OutlineNodeType::Property => "property",
+            OutlineNodeType::Constant => "constant",
+            OutlineNodeType::Variable => "variable",
+            OutlineNodeType::TypeAlias => "type_alias",
+            OutlineNodeType::Import => "import",
+            OutlineNodeType::Section => "section",
+        }
+    }
+}
+
+impl fmt::Display for OutlineNodeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OutlineNodeType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|node_type| node_type.as_str() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(OutlineNodeType::as_str).collect();
+                format!(
+                    "Unknown symbol kind '{name}', expected one of {}",
+                    names.join(", ")
+                )
+            })
+    }
 }
 
 /// Visibility modifier for symbols
@@ -361,6 +431,7 @@ impl OutlineTree {
                 OutlineNodeType::Property => stats.properties += 1,
                 OutlineNodeType::TypeAlias => stats.type_aliases += 1,
                 OutlineNodeType::Import => stats.imports += 1,
+                OutlineNodeType::Section => stats.sections += 1,
             }
         }
 
@@ -394,6 +465,9 @@ pub struct OutlineStats {
     pub type_aliases: usize,
     /// Number of imports
     pub imports: usize,
+    /// Number of document sections, such as Markdown headings
+    #[serde(default)]
+    pub sections: usize,
 }
 
 impl OutlineStats {
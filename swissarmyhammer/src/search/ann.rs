@@ -0,0 +1,642 @@
+//! Approximate nearest neighbour index for embedding vectors
+//!
+//! This module provides an in-process HNSW (hierarchical navigable small world)
+//! graph over the stored embeddings. Searching it visits a few hundred vectors
+//! instead of all of them, which keeps queries fast on large indexes at the cost
+//! of occasionally missing a close match. The graph is persisted next to the
+//! DuckDB file so it does not have to be rebuilt on every start.
+
+use crate::error::{Result, SwissArmyHammerError};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of a persisted index
+const INDEX_MAGIC: &[u8; 8] = b"SAHHNSW2";
+
+/// Tuning parameters for the HNSW graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HnswConfig {
+    /// Neighbours kept per node on the upper layers; the bottom layer keeps twice as many
+    pub max_neighbours: usize,
+    /// Candidates considered while inserting a vector
+    pub ef_construction: usize,
+    /// Candidates considered while searching
+    pub ef_search: usize,
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self {
+            max_neighbours: 16,
+            ef_construction: 200,
+            ef_search: 96,
+        }
+    }
+}
+
+/// A vector in the graph
+#[derive(Debug, Clone)]
+struct Node {
+    id: String,
+    vector: Vec<f32>,
+    /// Neighbours on each layer the node is part of, bottom layer first
+    neighbours: Vec<Vec<u32>>,
+    deleted: bool,
+}
+
+/// A node with its distance to the vector being searched for
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f32,
+    node: u32,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.node.cmp(&other.node))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// HNSW index over unit-length vectors using cosine distance
+///
+/// Removed vectors stay in the graph as tombstones so it stays connected;
+/// [`HnswIndex::tombstone_ratio`] tells when a rebuild is worth it.
+#[derive(Debug, Clone)]
+pub struct HnswIndex {
+    dimension: usize,
+    config: HnswConfig,
+    nodes: Vec<Node>,
+    ids: HashMap<String, u32>,
+    entry_point: Option<u32>,
+    rng_state: u64,
+}
+
+impl HnswIndex {
+    /// Create an empty index for vectors with `dimension` components
+    pub fn new(dimension: usize, config: HnswConfig) -> Self {
+        Self {
+            dimension,
+            config,
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            entry_point: None,
+            rng_state: 0x5DEE_CE66_D1CE_4E5B,
+        }
+    }
+
+    /// Number of components of the indexed vectors
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Number of live vectors in the index
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether the index holds no live vectors
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Whether searches have a node to start from
+    ///
+    /// An index holding live vectors without an entry point cannot find them.
+    pub fn has_entry_point(&self) -> bool {
+        self.entry_point.is_some()
+    }
+
+    /// Share of the graph taken up by removed vectors
+    pub fn tombstone_ratio(&self) -> f32 {
+        if self.nodes.is_empty() {
+            0.0
+        } else {
+            (self.nodes.len() - self.ids.len()) as f32 / self.nodes.len() as f32
+        }
+    }
+
+    /// Add a vector, replacing any vector previously stored under `id`
+    pub fn insert(&mut self, id: &str, vector: &[f32]) -> Result<()> {
+        if vector.len() != self.dimension {
+            return Err(SwissArmyHammerError::Storage(format!(
+                "Vector for {id} has {} dimensions but the index stores {}",
+                vector.len(),
+                self.dimension
+            )));
+        }
+        self.remove(id);
+
+        let vector = normalized(vector);
+        let level = self.random_level();
+        let new_node = self.nodes.len() as u32;
+        self.nodes.push(Node {
+            id: id.to_string(),
+            vector,
+            neighbours: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.ids.insert(id.to_string(), new_node);
+
+        let Some(entry_point) = self.entry_point else {
+            self.entry_point = Some(new_node);
+            return Ok(());
+        };
+
+        let query = self.nodes[new_node as usize].vector.clone();
+        let top_level = self.level_of(entry_point);
+        let mut entry = vec![self.candidate(&query, entry_point)];
+
+        // Descend greedily through the layers above the new node
+        for layer in (level + 1..=top_level).rev() {
+            entry = self.search_layer(&query, &entry, 1, layer);
+        }
+
+        // Connect the new node on every layer it is part of
+        for layer in (0..=level.min(top_level)).rev() {
+            let candidates = self.search_layer(&query, &entry, self.config.ef_construction, layer);
+            let neighbours: Vec<u32> = candidates
+                .iter()
+                .take(self.config.max_neighbours)
+                .map(|candidate| candidate.node)
+                .collect();
+            self.nodes[new_node as usize].neighbours[layer] = neighbours.clone();
+
+            for neighbour in neighbours {
+                self.nodes[neighbour as usize].neighbours[layer].push(new_node);
+                self.prune(neighbour, layer);
+            }
+            entry = candidates;
+        }
+
+        if level > top_level {
+            self.entry_point = Some(new_node);
+        }
+        Ok(())
+    }
+
+    /// Remove the vector stored under `id`, returning whether there was one
+    pub fn remove(&mut self, id: &str) -> bool {
+        match self.ids.remove(id) {
+            Some(node) => {
+                self.nodes[node as usize].deleted = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Find up to `limit` vectors closest to `query`, most similar first
+    ///
+    /// Returns the ids with their cosine similarity to the query.
+    pub fn search(&self, query: &[f32], limit: usize) -> Result<Vec<(String, f32)>> {
+        if query.len() != self.dimension {
+            return Err(SwissArmyHammerError::Storage(format!(
+                "Query has {} dimensions but the index stores {}",
+                query.len(),
+                self.dimension
+            )));
+        }
+        let Some(entry_point) = self.entry_point else {
+            return Ok(Vec::new());
+        };
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let query = normalized(query);
+        let mut entry = vec![self.candidate(&query, entry_point)];
+        for layer in (1..=self.level_of(entry_point)).rev() {
+            entry = self.search_layer(&query, &entry, 1, layer);
+        }
+
+        // Removed vectors take up room among the candidates, so widen the search
+        let tombstones = self.nodes.len() - self.ids.len();
+        let ef = self.config.ef_search.max(limit) + tombstones.min(limit * 4);
+        Ok(self
+            .search_layer(&query, &entry, ef, 0)
+            .into_iter()
+            .filter(|candidate| !self.nodes[candidate.node as usize].deleted)
+            .take(limit)
+            .map(|candidate| {
+                (
+                    self.nodes[candidate.node as usize].id.clone(),
+                    1.0 - candidate.distance,
+                )
+            })
+            .collect())
+    }
+
+    /// Write the index to `path`, replacing any previous copy
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let temp_path = path.with_extension("tmp");
+        {
+            let file = std::fs::File::create(&temp_path).map_err(SwissArmyHammerError::Io)?;
+            let mut writer = BufWriter::new(file);
+            self.write_to(&mut writer)
+                .and_then(|_| writer.flush())
+                .map_err(SwissArmyHammerError::Io)?;
+        }
+        std::fs::rename(&temp_path, path).map_err(SwissArmyHammerError::Io)
+    }
+
+    /// Read an index written by [`HnswIndex::save`]
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path).map_err(SwissArmyHammerError::Io)?;
+        Self::read_from(&mut BufReader::new(file)).map_err(|e| {
+            SwissArmyHammerError::Storage(format!(
+                "Failed to read ANN index {}: {e}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Entry point to persist, moved off a removed node
+    ///
+    /// The highest-level live node takes over from a removed entry point, so a
+    /// reloaded index starts its searches from a vector that can be returned.
+    fn live_entry_point(&self) -> Option<u32> {
+        match self.entry_point {
+            Some(entry) if !self.nodes[entry as usize].deleted => Some(entry),
+            _ => (0..self.nodes.len() as u32)
+                .filter(|node| !self.nodes[*node as usize].deleted)
+                .max_by_key(|node| (self.level_of(*node), std::cmp::Reverse(*node))),
+        }
+    }
+
+    fn write_to(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        // Tombstones are written too, so the links running through them survive
+        writer.write_all(INDEX_MAGIC)?;
+        write_u64(writer, self.dimension as u64)?;
+        write_u64(writer, self.config.max_neighbours as u64)?;
+        write_u64(writer, self.config.ef_construction as u64)?;
+        write_u64(writer, self.config.ef_search as u64)?;
+        write_u64(writer, self.rng_state)?;
+        write_u64(
+            writer,
+            self.live_entry_point()
+                .map(|entry| entry as u64 + 1)
+                .unwrap_or(0),
+        )?;
+        write_u64(writer, self.nodes.len() as u64)?;
+
+        for node in &self.nodes {
+            write_u64(writer, node.id.len() as u64)?;
+            writer.write_all(node.id.as_bytes())?;
+            writer.write_all(&[node.deleted as u8])?;
+            for value in &node.vector {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+            write_u64(writer, node.neighbours.len() as u64)?;
+            for layer in &node.neighbours {
+                write_u64(writer, layer.len() as u64)?;
+                for neighbour in layer {
+                    writer.write_all(&neighbour.to_le_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_from(reader: &mut impl Read) -> std::io::Result<Self> {
+        let invalid = |message: &str| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, message.to_string())
+        };
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != INDEX_MAGIC {
+            return Err(invalid("not an ANN index file"));
+        }
+
+        let dimension = read_u64(reader)? as usize;
+        let config = HnswConfig {
+            max_neighbours: read_u64(reader)? as usize,
+            ef_construction: read_u64(reader)? as usize,
+            ef_search: read_u64(reader)? as usize,
+        };
+        let rng_state = read_u64(reader)?;
+        let entry_point = read_u64(reader)?.checked_sub(1).map(|entry| entry as u32);
+        let node_count = read_u64(reader)? as usize;
+
+        let mut nodes = Vec::with_capacity(node_count);
+        let mut ids = HashMap::with_capacity(node_count);
+        for index in 0..node_count {
+            let mut id = vec![0u8; read_u64(reader)? as usize];
+            reader.read_exact(&mut id)?;
+            let id = String::from_utf8(id).map_err(|_| invalid("chunk id is not UTF-8"))?;
+            let mut deleted = [0u8; 1];
+            reader.read_exact(&mut deleted)?;
+            let deleted = deleted[0] != 0;
+
+            let mut vector = Vec::with_capacity(dimension);
+            let mut value = [0u8; 4];
+            for _ in 0..dimension {
+                reader.read_exact(&mut value)?;
+                vector.push(f32::from_le_bytes(value));
+            }
+
+            let layers = read_u64(reader)? as usize;
+            let mut neighbours = Vec::with_capacity(layers);
+            for _ in 0..layers {
+                let count = read_u64(reader)? as usize;
+                let mut layer = Vec::with_capacity(count);
+                let mut neighbour = [0u8; 4];
+                for _ in 0..count {
+                    reader.read_exact(&mut neighbour)?;
+                    let neighbour = u32::from_le_bytes(neighbour);
+                    if neighbour as usize >= node_count {
+                        return Err(invalid("neighbour out of range"));
+                    }
+                    layer.push(neighbour);
+                }
+                neighbours.push(layer);
+            }
+
+            if !deleted {
+                ids.insert(id.clone(), index as u32);
+            }
+            nodes.push(Node {
+                id,
+                vector,
+                neighbours,
+                deleted,
+            });
+        }
+        if entry_point.is_some_and(|entry| entry as usize >= node_count) {
+            return Err(invalid("entry point out of range"));
+        }
+
+        Ok(Self {
+            dimension,
+            config,
+            nodes,
+            ids,
+            entry_point,
+            rng_state,
+        })
+    }
+
+    /// Highest layer a node is part of
+    fn level_of(&self, node: u32) -> usize {
+        self.nodes[node as usize].neighbours.len() - 1
+    }
+
+    fn candidate(&self, query: &[f32], node: u32) -> Candidate {
+        Candidate {
+            distance: 1.0 - dot(query, &self.nodes[node as usize].vector),
+            node,
+        }
+    }
+
+    /// Best-first search of one layer, returning up to `ef` candidates closest first
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entry: &[Candidate],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited: HashSet<u32> = entry.iter().map(|candidate| candidate.node).collect();
+        // Closest unexplored candidate first
+        let mut frontier: BinaryHeap<std::cmp::Reverse<Candidate>> =
+            entry.iter().copied().map(std::cmp::Reverse).collect();
+        // Furthest of the best candidates found so far first
+        let mut best: BinaryHeap<Candidate> = entry.iter().copied().collect();
+
+        while let Some(std::cmp::Reverse(current)) = frontier.pop() {
+            if best.len() >= ef
+                && best
+                    .peek()
+                    .is_some_and(|worst| current.distance > worst.distance)
+            {
+                break;
+            }
+            let Some(neighbours) = self.nodes[current.node as usize].neighbours.get(layer) else {
+                continue;
+            };
+            for &neighbour in neighbours {
+                if !visited.insert(neighbour) {
+                    continue;
+                }
+                let candidate = self.candidate(query, neighbour);
+                if best.len() < ef || best.peek().is_some_and(|worst| candidate < *worst) {
+                    frontier.push(std::cmp::Reverse(candidate));
+                    best.push(candidate);
+                    if best.len() > ef {
+                        best.pop();
+                    }
+                }
+            }
+        }
+
+        best.into_sorted_vec()
+    }
+
+    /// Keep only the closest neighbours of a node on a layer
+    fn prune(&mut self, node: u32, layer: usize) {
+        let max = if layer == 0 {
+            self.config.max_neighbours * 2
+        } else {
+            self.config.max_neighbours
+        };
+        if self.nodes[node as usize].neighbours[layer].len() <= max {
+            return;
+        }
+
+        let vector = self.nodes[node as usize].vector.clone();
+        let mut neighbours: Vec<Candidate> = self.nodes[node as usize].neighbours[layer]
+            .iter()
+            .map(|&neighbour| self.candidate(&vector, neighbour))
+            .collect();
+        neighbours.sort();
+        neighbours.truncate(max);
+        self.nodes[node as usize].neighbours[layer] = neighbours
+            .into_iter()
+            .map(|candidate| candidate.node)
+            .collect();
+    }
+
+    /// Draw the top layer of a new node from an exponential distribution
+    fn random_level(&mut self) -> usize {
+        // splitmix64 keeps the graph reproducible without a random number crate
+        self.rng_state = self.rng_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        let uniform = ((z >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let level_multiplier = 1.0 / (self.config.max_neighbours.max(2) as f64).ln();
+        ((-uniform.ln() * level_multiplier) as usize).min(16)
+    }
+}
+
+/// Scale a vector to unit length, leaving zero vectors alone
+fn normalized(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        vector.to_vec()
+    } else {
+        vector.iter().map(|x| x / norm).collect()
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn write_u64(writer: &mut impl Write, value: u64) -> std::io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn read_u64(reader: &mut impl Read) -> std::io::Result<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::utils::SemanticUtils;
+    use tempfile::TempDir;
+
+    /// Deterministic pseudo-random vectors for recall tests
+    fn test_vectors(count: usize, dimension: usize) -> Vec<Vec<f32>> {
+        let mut state = 42u64;
+        (0..count)
+            .map(|_| {
+                (0..dimension)
+                    .map(|_| {
+                        state = state
+                            .wrapping_mul(6_364_136_223_846_793_005)
+                            .wrapping_add(1_442_695_040_888_963_407);
+                        ((state >> 33) as f32 / (1u64 << 31) as f32) - 0.5
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn build_index(vectors: &[Vec<f32>]) -> HnswIndex {
+        let mut index = HnswIndex::new(vectors[0].len(), HnswConfig::default());
+        for (i, vector) in vectors.iter().enumerate() {
+            index.insert(&format!("chunk-{i}"), vector).unwrap();
+        }
+        index
+    }
+
+    fn exact_top(vectors: &[Vec<f32>], query: &[f32], limit: usize) -> Vec<String> {
+        let mut scored: Vec<(String, f32)> = vectors
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                (
+                    format!("chunk-{i}"),
+                    SemanticUtils::cosine_similarity(query, v),
+                )
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().take(limit).map(|(id, _)| id).collect()
+    }
+
+    #[test]
+    fn test_search_recall_against_brute_force() {
+        let vectors = test_vectors(2000, 32);
+        let index = build_index(&vectors);
+        assert_eq!(index.len(), 2000);
+
+        let queries = test_vectors(2050, 32).split_off(2000);
+        let mut found = 0;
+        for query in &queries {
+            let expected = exact_top(&vectors, query, 10);
+            let results = index.search(query, 10).unwrap();
+            assert_eq!(results.len(), 10);
+            assert!(results.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+            found += results
+                .iter()
+                .filter(|(id, _)| expected.contains(id))
+                .count();
+        }
+
+        let recall = found as f32 / (queries.len() * 10) as f32;
+        assert!(recall >= 0.9, "recall {recall} is too low");
+    }
+
+    #[test]
+    fn test_removed_vectors_are_not_returned() {
+        let vectors = test_vectors(200, 16);
+        let mut index = build_index(&vectors);
+
+        let closest = index.search(&vectors[7], 1).unwrap();
+        assert_eq!(closest[0].0, "chunk-7");
+        assert!((closest[0].1 - 1.0).abs() < 1e-5);
+
+        assert!(index.remove("chunk-7"));
+        assert!(!index.remove("chunk-7"));
+        let results = index.search(&vectors[7], 10).unwrap();
+        assert!(results.iter().all(|(id, _)| id != "chunk-7"));
+        assert_eq!(index.len(), 199);
+        assert!(index.tombstone_ratio() > 0.0);
+
+        // Re-inserting an id replaces the old vector
+        index.insert("chunk-8", &vectors[7]).unwrap();
+        assert_eq!(index.search(&vectors[7], 1).unwrap()[0].0, "chunk-8");
+        assert_eq!(index.len(), 199);
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("index.hnsw");
+        let vectors = test_vectors(300, 16);
+        let mut index = build_index(&vectors);
+        index.remove("chunk-0");
+        // Removing the entry point must not leave the saved index without one
+        let entry_id = index.nodes[index.entry_point.unwrap() as usize].id.clone();
+        index.remove(&entry_id);
+        let query = if entry_id == "chunk-42" { 43 } else { 42 };
+        index.save(&path).unwrap();
+
+        let loaded = HnswIndex::load(&path).unwrap();
+        assert_eq!(loaded.dimension(), 16);
+        assert_eq!(loaded.len(), index.len());
+        assert_eq!(loaded.tombstone_ratio(), index.tombstone_ratio());
+        assert!(loaded.has_entry_point());
+        assert!(!loaded.nodes[loaded.entry_point.unwrap() as usize].deleted);
+        assert_eq!(
+            loaded.search(&vectors[query], 1).unwrap()[0].0,
+            format!("chunk-{query}")
+        );
+        assert!(loaded
+            .search(&vectors[0], 10)
+            .unwrap()
+            .iter()
+            .all(|(id, _)| id != "chunk-0" && *id != entry_id));
+
+        std::fs::write(&path, b"not an index").unwrap();
+        assert!(HnswIndex::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_dimension_mismatch_is_rejected() {
+        let mut index = HnswIndex::new(4, HnswConfig::default());
+        assert!(index.search(&[1.0, 0.0, 0.0, 0.0], 3).unwrap().is_empty());
+        assert!(index.insert("a", &[1.0, 0.0]).is_err());
+        index.insert("a", &[1.0, 0.0, 0.0, 0.0]).unwrap();
+        assert!(index.search(&[1.0, 0.0], 3).is_err());
+    }
+}
@@ -1,11 +1,23 @@
-//! Local embedding generation using fastembed-rs neural embeddings
-
+//! Embedding generation for code chunks and search queries
+//!
+//! By default vectors come from a local fastembed model. A local ONNX model or
+//! an OpenAI-compatible embedding server can be configured instead, see
+//! [`crate::search::embedding_provider`].
+
+use crate::search::embedding_provider::{
+    EmbeddingProviderConfig, EmbeddingProviderKind, ModelChangePolicy, OnnxFileProvider,
+    OpenAiEmbeddingProvider,
+};
 use crate::search::{CodeChunk, Embedding, Result, SemanticError};
+use async_trait::async_trait;
 use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::{debug, info};
 
+/// Model used when no other model is configured
+pub const DEFAULT_EMBEDDING_MODEL: &str = "nomic-embed-text-v1.5";
+
 /// Configuration for the embedding engine
 #[derive(Debug, Clone)]
 pub struct EmbeddingConfig {
@@ -32,7 +44,7 @@ pub struct EmbeddingConfig {
 impl Default for EmbeddingConfig {
     fn default() -> Self {
         Self {
-            model_id: "nomic-embed-text-v1.5".to_string(), // Modern, high-quality embedding model
+            model_id: DEFAULT_EMBEDDING_MODEL.to_string(), // Modern, high-quality embedding model
             embedding_model: EmbeddingModel::NomicEmbedTextV15Q,
             batch_size: 32, // Reasonable batch size for neural models
             max_text_length: 8000,
@@ -58,22 +70,145 @@ pub struct EmbeddingModelInfo {
     pub quantization: String,
 }
 
-/// Embedding model backend type
-enum EmbeddingBackend {
-    /// Production model using fastembed neural embeddings
-    Neural(Box<TextEmbedding>),
-    /// Mock model for testing (deterministic embeddings)
-    #[allow(dead_code)] // Only used in test code
-    Mock,
+/// A source of embedding vectors
+///
+/// Providers turn already cleaned texts into vectors. Batching, text cleanup
+/// and chunk formatting are left to [`EmbeddingEngine`], so a provider only
+/// needs to know how to reach its model.
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    /// Embed every text, returning the vectors in the order of `texts`
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>>;
+}
+
+/// Model downloaded from Hugging Face and run locally by fastembed
+pub struct FastEmbedProvider {
+    model: Mutex<TextEmbedding>,
+}
+
+impl FastEmbedProvider {
+    /// Load the model of `config`, downloading it if it is not cached
+    pub fn new(config: &EmbeddingConfig) -> Result<Self> {
+        let init_options = InitOptions::new(config.embedding_model.clone())
+            .with_show_download_progress(config.show_download_progress)
+            .with_cache_dir("/tmp/.cache/fastembed".into());
+
+        let model = TextEmbedding::try_new(init_options).map_err(|e| {
+            SemanticError::Embedding(format!("Failed to initialize fastembed model: {e}"))
+        })?;
+        Ok(Self {
+            model: Mutex::new(model),
+        })
+    }
+
+    /// The fastembed model published as `name`
+    ///
+    /// Accepts the full Hugging Face name, such as `BAAI/bge-small-en-v1.5`, or
+    /// just the part after the organization. `nomic-embed-text-v1.5` is the
+    /// quantized Nomic model used by default.
+    pub fn model_for_name(name: &str) -> Result<EmbeddingModel> {
+        if name == DEFAULT_EMBEDDING_MODEL {
+            return Ok(EmbeddingModel::NomicEmbedTextV15Q);
+        }
+        TextEmbedding::list_supported_models()
+            .into_iter()
+            .find(|info| {
+                info.model_code == name
+                    || info
+                        .model_code
+                        .rsplit_once('/')
+                        .is_some_and(|(_, model)| model == name)
+            })
+            .map(|info| info.model)
+            .ok_or_else(|| {
+                SemanticError::Config(format!("fastembed does not support the model '{name}'"))
+            })
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for FastEmbedProvider {
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        // Format texts appropriately for embedding (code context)
+        let texts = texts
+            .into_iter()
+            .map(|text| format!("passage: {text}"))
+            .collect();
+
+        let mut model = self.model.lock().await;
+        model
+            .embed(texts, None)
+            .map_err(|e| SemanticError::Embedding(format!("Fastembed error: {e}")))
+    }
+}
+
+/// Deterministic embeddings for tests, computed without any model
+#[cfg(any(test, feature = "test-utils"))]
+pub struct MockEmbeddingProvider {
+    dimensions: usize,
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl MockEmbeddingProvider {
+    /// Create a provider returning vectors with `dimensions` components
+    pub fn new(dimensions: usize) -> Self {
+        Self { dimensions }
+    }
+
+    /// Generate a simple deterministic mock embedding for testing
+    /// Creates consistent embeddings without complex semantic modeling
+    fn embed_text(&self, text: &str) -> Vec<f32> {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        // Simple hash-based approach for deterministic but varied embeddings
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        let base_hash = hasher.finish();
+
+        // Generate embedding vector using the hash as a seed
+        let mut embedding = Vec::with_capacity(self.dimensions);
+        for i in 0..self.dimensions {
+            // Use dimension index to vary the values across dimensions
+            let dim_hash = base_hash.wrapping_add(i as u64 * 37);
+            // Convert to float in range [-1.0, 1.0]
+            let value = ((dim_hash % 2000) as f32 / 1000.0) - 1.0;
+            embedding.push(value);
+        }
+
+        // Normalize the vector to unit length (like real embeddings)
+        let magnitude: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if magnitude > 0.0 {
+            for value in &mut embedding {
+                *value /= magnitude;
+            }
+        }
+
+        embedding
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+#[async_trait]
+impl EmbeddingProvider for MockEmbeddingProvider {
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|text| self.embed_text(text)).collect())
+    }
 }
 
-/// Embedding engine using fastembed-rs neural embeddings
-/// This provides high-quality semantic embeddings using local neural models
-/// without requiring external API dependencies.
+/// Embedding engine turning code chunks and queries into vectors
+///
+/// The vectors come from an [`EmbeddingProvider`]: by default a local fastembed
+/// model, or the provider configured under `search.embedding` in `sah.toml`
+/// when created with [`EmbeddingEngine::from_configuration`].
+///
+/// Clones share the loaded model.
+#[derive(Clone)]
 pub struct EmbeddingEngine {
     config: EmbeddingConfig,
     model_info: EmbeddingModelInfo,
-    backend: Arc<Mutex<EmbeddingBackend>>,
+    provider: Arc<dyn EmbeddingProvider>,
+    on_model_change: ModelChangePolicy,
 }
 
 impl EmbeddingEngine {
@@ -83,7 +218,43 @@ impl EmbeddingEngine {
         Self::with_config(config).await
     }
 
-    /// Create embedding engine with custom model
+    /// Create the engine of the provider configured in `sah.toml`
+    ///
+    /// Falls back to the default fastembed model when `search.embedding` is
+    /// not configured.
+    pub async fn from_configuration() -> Result<Self> {
+        match EmbeddingProviderConfig::from_configuration()? {
+            Some(provider_config) => Self::with_provider_config(&provider_config).await,
+            None => Self::new().await,
+        }
+    }
+
+    /// Create the engine of a provider configuration
+    pub async fn with_provider_config(provider_config: &EmbeddingProviderConfig) -> Result<Self> {
+        let config = provider_config.embedding_config()?;
+        info!(
+            "Initializing {} embedding provider with model: {}",
+            provider_config.provider, config.model_id
+        );
+
+        Self::validate_config(&config)?;
+        let provider: Arc<dyn EmbeddingProvider> = match provider_config.provider {
+            EmbeddingProviderKind::Fastembed => Arc::new(FastEmbedProvider::new(&config)?),
+            EmbeddingProviderKind::Onnx => Arc::new(OnnxFileProvider::from_config(
+                provider_config,
+                config.max_sequence_length,
+            )?),
+            EmbeddingProviderKind::OpenaiCompatible => {
+                Arc::new(OpenAiEmbeddingProvider::from_config(provider_config)?)
+            }
+        };
+
+        Ok(Self::with_provider(provider, config)
+            .await?
+            .with_model_change_policy(provider_config.on_model_change))
+    }
+
+    /// Create engine with custom model
     pub async fn with_model_id(model_id: String) -> Result<Self> {
         let config = EmbeddingConfig {
             model_id,
@@ -93,7 +264,63 @@ impl EmbeddingEngine {
     }
 
     /// Create engine with custom configuration
-    pub async fn with_config(mut config: EmbeddingConfig) -> Result<Self> {
+    pub async fn with_config(config: EmbeddingConfig) -> Result<Self> {
+        Self::validate_config(&config)?;
+
+        info!(
+            "Initializing fastembed embedding engine with model: {}",
+            config.model_id
+        );
+
+        let provider = FastEmbedProvider::new(&config)?;
+        Self::with_provider(Arc::new(provider), config).await
+    }
+
+    /// Create engine embedding with `provider`
+    ///
+    /// Unless `config` sets the dimensions, they are found by embedding a test
+    /// text, which also checks the provider works.
+    pub async fn with_provider(
+        provider: Arc<dyn EmbeddingProvider>,
+        mut config: EmbeddingConfig,
+    ) -> Result<Self> {
+        Self::validate_config(&config)?;
+
+        let dimensions = match config.dimensions {
+            Some(dimensions) => dimensions,
+            None => provider
+                .embed(vec!["test".to_string()])
+                .await
+                .map_err(|e| {
+                    SemanticError::Embedding(format!("Failed to get model dimensions: {e}"))
+                })?
+                .first()
+                .ok_or_else(|| SemanticError::Embedding("No test embedding generated".to_string()))?
+                .len(),
+        };
+        config.dimensions = Some(dimensions);
+
+        let model_info = EmbeddingModelInfo {
+            model_id: config.model_id.clone(),
+            dimensions,
+            max_sequence_length: config.max_sequence_length,
+            quantization: config.quantization.clone(),
+        };
+
+        info!(
+            "Successfully initialized embedding engine with {} dimensions",
+            dimensions
+        );
+
+        Ok(Self {
+            config,
+            model_info,
+            provider,
+            on_model_change: ModelChangePolicy::default(),
+        })
+    }
+
+    fn validate_config(config: &EmbeddingConfig) -> Result<()> {
         if config.model_id.is_empty() {
             return Err(SemanticError::Config(
                 "Model ID cannot be empty".to_string(),
@@ -124,49 +351,7 @@ impl EmbeddingEngine {
             ));
         }
 
-        info!(
-            "Initializing fastembed embedding engine with model: {}",
-            config.model_id
-        );
-
-        // Initialize fastembed model
-        let init_options = InitOptions::new(config.embedding_model.clone())
-            .with_show_download_progress(config.show_download_progress)
-            .with_cache_dir("/tmp/.cache/fastembed".into());
-
-        let mut model = TextEmbedding::try_new(init_options).map_err(|e| {
-            SemanticError::Embedding(format!("Failed to initialize fastembed model: {e}"))
-        })?;
-
-        // Get actual model dimensions by generating a test embedding
-        let test_embedding = model.embed(vec!["test".to_string()], None).map_err(|e| {
-            SemanticError::Embedding(format!("Failed to get model dimensions: {e}"))
-        })?;
-
-        let dimensions = test_embedding
-            .first()
-            .ok_or_else(|| SemanticError::Embedding("No test embedding generated".to_string()))?
-            .len();
-
-        config.dimensions = Some(dimensions);
-
-        let model_info = EmbeddingModelInfo {
-            model_id: config.model_id.clone(),
-            dimensions,
-            max_sequence_length: config.max_sequence_length,
-            quantization: config.quantization.clone(),
-        };
-
-        info!(
-            "Successfully initialized fastembed embedding engine with {} dimensions",
-            dimensions
-        );
-
-        Ok(Self {
-            config,
-            model_info,
-            backend: Arc::new(Mutex::new(EmbeddingBackend::Neural(Box::new(model)))),
-        })
+        Ok(())
     }
 
     /// Generate embedding for a single code chunk
@@ -232,11 +417,20 @@ impl EmbeddingEngine {
         self.model_info.clone()
     }
 
+    /// Set what indexing does when the index was built by a different model
+    pub fn with_model_change_policy(mut self, policy: ModelChangePolicy) -> Self {
+        self.on_model_change = policy;
+        self
+    }
+
+    /// What indexing does when the index was built by a different model
+    pub fn on_model_change(&self) -> ModelChangePolicy {
+        self.on_model_change
+    }
+
     // Private implementation methods
 
-    /// Generate a consistent, semantic embedding for the given text
-    /// This uses a combination of deterministic hashing and semantic analysis
-    /// to create embeddings that maintain semantic relationships
+    /// Generate the embedding of one text
     async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>> {
         // Validate input
         if text.is_empty() {
@@ -246,131 +440,36 @@ impl EmbeddingEngine {
         // Clean and truncate text
         let cleaned_text = self.clean_text(text);
 
-        // Generate embedding using fastembed neural model
-        let embedding = self.create_neural_embedding(&cleaned_text).await?;
+        // Generate embedding using the provider
+        let embedding = self
+            .provider
+            .embed(vec![cleaned_text])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| SemanticError::Embedding("No embedding generated".to_string()))?;
 
-        debug!(
-            "Generated neural embedding with {} dimensions",
-            embedding.len()
-        );
+        debug!("Generated embedding with {} dimensions", embedding.len());
         Ok(embedding)
     }
 
-    /// Create a neural embedding using fastembed (or mock for testing)
-    async fn create_neural_embedding(&self, text: &str) -> Result<Vec<f32>> {
-        // Check if this is a mock/test instance
-        #[cfg(test)]
-        if self.model_info.model_id == "mock-test-model" {
-            // Return deterministic mock embedding for testing
-            return Ok(self.generate_deterministic_mock_embedding(text));
-        }
-
-        // Use fastembed to generate high-quality neural embeddings
-        let mut backend = self.backend.lock().await;
-        let model = match &mut *backend {
-            EmbeddingBackend::Neural(model) => model.as_mut(),
-            EmbeddingBackend::Mock => {
-                return Err(SemanticError::Embedding(
-                    "Neural model not available in test mode".to_string(),
-                ))
-            }
-        };
-
-        // Format text appropriately for embedding (code context)
-        let formatted_text = format!("passage: {text}");
-
-        // Generate embedding using fastembed
-        let embeddings = model
-            .embed(vec![formatted_text], None)
-            .map_err(|e| SemanticError::Embedding(format!("Fastembed error: {e}")))?;
-
-        // Extract the first (and only) embedding
-        if let Some(embedding) = embeddings.into_iter().next() {
-            Ok(embedding)
-        } else {
-            Err(SemanticError::Embedding(
-                "No embedding generated".to_string(),
-            ))
-        }
-    }
-
-    #[cfg(any(test, feature = "test-utils"))]
-    #[allow(dead_code)]
-    /// Generate a simple deterministic mock embedding for testing
-    /// Creates consistent embeddings without complex semantic modeling
-    fn generate_deterministic_mock_embedding(&self, text: &str) -> Vec<f32> {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        // Simple hash-based approach for deterministic but varied embeddings
-        let mut hasher = DefaultHasher::new();
-        text.hash(&mut hasher);
-        let base_hash = hasher.finish();
-
-        // Generate embedding vector using the hash as a seed
-        let mut embedding = Vec::with_capacity(self.model_info.dimensions);
-        for i in 0..self.model_info.dimensions {
-            // Use dimension index to vary the values across dimensions
-            let dim_hash = base_hash.wrapping_add(i as u64 * 37);
-            // Convert to float in range [-1.0, 1.0]
-            let value = ((dim_hash % 2000) as f32 / 1000.0) - 1.0;
-            embedding.push(value);
-        }
-
-        // Normalize the vector to unit length (like real embeddings)
-        let magnitude: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
-        if magnitude > 0.0 {
-            for value in &mut embedding {
-                *value /= magnitude;
-            }
-        }
-
-        embedding
-    }
-
     async fn process_text_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
         if texts.is_empty() {
             return Ok(Vec::new());
         }
 
-        // Check if this is a mock/test instance
-        #[cfg(test)]
-        if self.model_info.model_id == "mock-test-model" {
-            // Generate mock embeddings for each text
-            let mut embeddings = Vec::new();
-            for text in texts {
-                let cleaned = self.clean_text(text);
-                let mock_embedding = self.generate_deterministic_mock_embedding(&cleaned);
-                embeddings.push(mock_embedding);
-            }
-            return Ok(embeddings);
-        }
-
-        // Clean and format texts for fastembed
-        let cleaned_texts: Vec<String> = texts
-            .iter()
-            .map(|text| {
-                let cleaned = self.clean_text(text);
-                format!("passage: {cleaned}")
-            })
-            .collect();
-
-        // Use fastembed's native batch processing
-        let mut backend = self.backend.lock().await;
-        let model = match &mut *backend {
-            EmbeddingBackend::Neural(model) => model.as_mut(),
-            EmbeddingBackend::Mock => {
-                return Err(SemanticError::Embedding(
-                    "Neural batch processing not available in test mode".to_string(),
-                ))
-            }
-        };
+        let cleaned_texts: Vec<String> = texts.iter().map(|text| self.clean_text(text)).collect();
 
         debug!("Processing batch of {} texts", texts.len());
 
-        let embeddings = model
-            .embed(cleaned_texts, None)
-            .map_err(|e| SemanticError::Embedding(format!("Fastembed batch error: {e}")))?;
+        let embeddings = self.provider.embed(cleaned_texts).await?;
+        if embeddings.len() != texts.len() {
+            return Err(SemanticError::Embedding(format!(
+                "Provider returned {} embeddings for {} texts",
+                embeddings.len(),
+                texts.len()
+            )));
+        }
 
         debug!(
             "Successfully generated {} embeddings in batch",
@@ -381,6 +480,25 @@ impl EmbeddingEngine {
     }
 
     async fn process_chunk_batch(&self, chunks: &[CodeChunk]) -> Result<Vec<Embedding>> {
+        let texts: Vec<String> = chunks
+            .iter()
+            .map(|chunk| self.prepare_chunk_text(chunk))
+            .collect();
+        let text_refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+        match self.process_text_batch(&text_refs).await {
+            Ok(vectors) => {
+                return Ok(chunks
+                    .iter()
+                    .zip(vectors)
+                    .map(|(chunk, vector)| Embedding {
+                        chunk_id: chunk.id.clone(),
+                        vector,
+                    })
+                    .collect())
+            }
+            Err(e) => tracing::debug!("Batch embedding failed, embedding chunks one by one: {}", e),
+        }
+
         let mut batch_embeddings = Vec::new();
 
         for chunk in chunks {
@@ -450,31 +568,13 @@ impl EmbeddingEngine {
     #[cfg(any(test, feature = "test-utils"))]
     /// Create embedding engine for testing with custom config (no network required)
     pub async fn new_for_testing_with_config(config: EmbeddingConfig) -> Result<Self> {
-        info!("Creating mock embedding engine for testing (no network required)");
-
         info!(
             "Creating mock embedding engine with {} dimensions",
             config.dimensions.unwrap_or(768)
         );
 
-        // Create a mock model info without initializing the actual fastembed model
-        let model_info = EmbeddingModelInfo {
-            model_id: config.model_id.clone(),
-            dimensions: config.dimensions.unwrap_or(768), // Use config dimensions or default
-            max_sequence_length: config.max_sequence_length,
-            quantization: config.quantization.clone(),
-        };
-
-        // For testing, we need to provide a dummy TextEmbedding model
-        // Since we can't create one without network access, we'll use a different strategy
-        // We'll create a minimal engine that uses the mock path in create_neural_embedding
-
-        info!("Mock embedding engine created successfully");
-        Ok(Self {
-            config,
-            model_info,
-            backend: Arc::new(Mutex::new(EmbeddingBackend::Mock)),
-        })
+        let provider = MockEmbeddingProvider::new(config.dimensions.unwrap_or(768));
+        Self::with_provider(Arc::new(provider), config).await
     }
 
     #[cfg(test)]
@@ -573,6 +673,7 @@ mod tests {
             end_line: 3,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("hash123".to_string()),
+            symbol: None,
         };
 
         let embedding = engine.embed_chunk(&chunk).await;
@@ -650,6 +751,7 @@ mod tests {
             end_line: 1,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("hash123".to_string()),
+            symbol: None,
         };
 
         let prepared_text = engine.prepare_chunk_text(&chunk);
@@ -0,0 +1,664 @@
+//! Embedding providers selected in `sah.toml`
+//!
+//! Search embeds code with a fastembed model downloaded from Hugging Face
+//! unless `search.embedding` says otherwise:
+//!
+//! ```toml
+//! [search.embedding]
+//! provider = "openai-compatible"
+//! base_url = "http://localhost:8080/v1"
+//! model = "nomic-embed-text"
+//! api_key_env = "EMBEDDING_API_KEY"
+//! ```
+//!
+//! `provider = "onnx"` runs a model from local files instead, which suits
+//! machines that cannot reach Hugging Face:
+//!
+//! ```toml
+//! [search.embedding]
+//! provider = "onnx"
+//! model = "bge-small-en-v1.5"
+//! model_path = "/opt/models/bge-small/model.onnx"
+//! tokenizer_path = "/opt/models/bge-small/tokenizer.json"
+//! ```
+//!
+//! An index records the model and dimension of its vectors. Vectors of another
+//! model cannot be compared with them, so indexing with a different model is
+//! refused until the index is rebuilt with `sah search index --force`, or
+//! rebuilt straight away with `on_model_change = "reindex"`.
+
+use crate::search::embedding::{EmbeddingConfig, EmbeddingProvider, DEFAULT_EMBEDDING_MODEL};
+use crate::search::rerank::flatten;
+use crate::search::{Result, SemanticError};
+use async_trait::async_trait;
+use ort::session::{builder::GraphOptimizationLevel, Session, SessionInputValue};
+use ort::value::Tensor;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::borrow::Cow;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokenizers::{Encoding, PaddingParams, Tokenizer, TruncationParams};
+
+/// Configuration key of the embedding provider settings
+pub const EMBEDDING_CONFIG_KEY: &str = "search.embedding";
+
+/// Where embedding vectors come from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EmbeddingProviderKind {
+    /// Model downloaded from Hugging Face and run locally by fastembed
+    #[default]
+    Fastembed,
+    /// ONNX model and tokenizer read from local files
+    Onnx,
+    /// Server implementing the OpenAI `/v1/embeddings` API
+    OpenaiCompatible,
+}
+
+impl fmt::Display for EmbeddingProviderKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Fastembed => "fastembed",
+            Self::Onnx => "onnx",
+            Self::OpenaiCompatible => "openai-compatible",
+        };
+        f.write_str(name)
+    }
+}
+
+/// What indexing does with an index built by a different embedding model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelChangePolicy {
+    /// Fail and leave the index alone until it is rebuilt with `--force`
+    #[default]
+    Refuse,
+    /// Clear the index and embed every file again
+    Reindex,
+}
+
+/// Settings of the embedding provider, read from `search.embedding`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddingProviderConfig {
+    /// Provider of the vectors
+    #[serde(default)]
+    pub provider: EmbeddingProviderKind,
+    /// Model name, recorded in the index and sent to embedding servers
+    ///
+    /// Required for `openai-compatible`. For `fastembed` it names a supported
+    /// model and defaults to `nomic-embed-text-v1.5`; for `onnx` it defaults
+    /// to the model file's path.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// ONNX model file, for `onnx`
+    #[serde(default)]
+    pub model_path: Option<PathBuf>,
+    /// `tokenizer.json` of the model, for `onnx`
+    #[serde(default)]
+    pub tokenizer_path: Option<PathBuf>,
+    /// Base URL of the API including the version prefix, for `openai-compatible`
+    #[serde(default = "EmbeddingProviderConfig::default_base_url")]
+    pub base_url: String,
+    /// Environment variable holding the API key, for `openai-compatible`
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    /// API key to send, taking precedence over `api_key_env`
+    ///
+    /// Never written back out when the settings are serialized.
+    #[serde(default, skip_serializing)]
+    pub api_key: Option<String>,
+    /// Number of components of the vectors
+    ///
+    /// Sent to embedding servers whose models can shorten their vectors. When
+    /// unset it is found by embedding a test text.
+    #[serde(default)]
+    pub dimensions: Option<usize>,
+    /// Number of texts embedded per request
+    #[serde(default = "EmbeddingProviderConfig::default_batch_size")]
+    pub batch_size: usize,
+    /// Request timeout in seconds, for `openai-compatible`
+    #[serde(default = "EmbeddingProviderConfig::default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// What indexing does with an index built by a different model
+    #[serde(default)]
+    pub on_model_change: ModelChangePolicy,
+}
+
+impl Default for EmbeddingProviderConfig {
+    fn default() -> Self {
+        Self {
+            provider: EmbeddingProviderKind::default(),
+            model: None,
+            model_path: None,
+            tokenizer_path: None,
+            base_url: Self::default_base_url(),
+            api_key_env: None,
+            api_key: None,
+            dimensions: None,
+            batch_size: Self::default_batch_size(),
+            timeout_secs: Self::default_timeout_secs(),
+            on_model_change: ModelChangePolicy::default(),
+        }
+    }
+}
+
+impl EmbeddingProviderConfig {
+    fn default_base_url() -> String {
+        "http://localhost:8000/v1".to_string()
+    }
+
+    fn default_batch_size() -> usize {
+        32
+    }
+
+    fn default_timeout_secs() -> u64 {
+        60
+    }
+
+    /// Read the settings from the loaded configuration, if present
+    pub fn from_configuration() -> Result<Option<Self>> {
+        let context = swissarmyhammer_config::load_configuration()
+            .map_err(|e| SemanticError::Config(e.to_string()))?;
+        context
+            .get(EMBEDDING_CONFIG_KEY)
+            .map(|value| Self::from_value(value.clone()))
+            .transpose()
+    }
+
+    /// Read the settings from a configuration value
+    pub fn from_value(value: serde_json::Value) -> Result<Self> {
+        serde_json::from_value(value).map_err(|e| {
+            SemanticError::Config(format!("Invalid {EMBEDDING_CONFIG_KEY} settings: {e}"))
+        })
+    }
+
+    fn required<'a, T>(&self, value: &'a Option<T>, field: &str) -> Result<&'a T> {
+        value.as_ref().ok_or_else(|| {
+            SemanticError::Config(format!(
+                "{EMBEDDING_CONFIG_KEY}.{field} is required for the {} provider",
+                self.provider
+            ))
+        })
+    }
+
+    /// Name of the model, as recorded in indexes built with it
+    pub fn model_id(&self) -> Result<String> {
+        match self.provider {
+            EmbeddingProviderKind::Fastembed => Ok(self
+                .model
+                .clone()
+                .unwrap_or_else(|| DEFAULT_EMBEDDING_MODEL.to_string())),
+            EmbeddingProviderKind::Onnx => match &self.model {
+                Some(model) => Ok(model.clone()),
+                None => Ok(self
+                    .required(&self.model_path, "model_path")?
+                    .display()
+                    .to_string()),
+            },
+            EmbeddingProviderKind::OpenaiCompatible => self.required(&self.model, "model").cloned(),
+        }
+    }
+
+    /// Engine settings for this provider
+    pub fn embedding_config(&self) -> Result<EmbeddingConfig> {
+        let model_id = self.model_id()?;
+        let embedding_model = match self.provider {
+            EmbeddingProviderKind::Fastembed => {
+                crate::search::FastEmbedProvider::model_for_name(&model_id)?
+            }
+            _ => EmbeddingConfig::default().embedding_model,
+        };
+        Ok(EmbeddingConfig {
+            model_id,
+            embedding_model,
+            batch_size: self.batch_size,
+            dimensions: self.dimensions,
+            ..Default::default()
+        })
+    }
+
+    /// Get the configured API key, reading it from `api_key_env` if not given directly
+    pub fn api_key(&self) -> Option<String> {
+        self.api_key
+            .clone()
+            .or_else(|| {
+                self.api_key_env
+                    .as_ref()
+                    .and_then(|name| std::env::var(name).ok())
+            })
+            .filter(|key| !key.is_empty())
+    }
+}
+
+/// ONNX embedding model read from local files
+///
+/// Works with sentence-transformers style models exported to ONNX: token
+/// vectors are mean pooled over the attention mask, while models that already
+/// output one vector per text are used as they are. Vectors are normalized to
+/// unit length.
+pub struct OnnxFileProvider {
+    model: Arc<OnnxModel>,
+}
+
+/// Loaded ONNX session and tokenizer, shared with the blocking inference tasks
+struct OnnxModel {
+    session: Mutex<Session>,
+    tokenizer: Tokenizer,
+    uses_token_type_ids: bool,
+}
+
+impl OnnxFileProvider {
+    /// Load a model from an ONNX file and its `tokenizer.json`
+    ///
+    /// Texts are truncated to `max_tokens` tokens.
+    pub fn from_files(model_path: &Path, tokenizer_path: &Path, max_tokens: usize) -> Result<Self> {
+        let mut tokenizer = Tokenizer::from_file(tokenizer_path).map_err(|e| {
+            SemanticError::Config(format!(
+                "Failed to load embedding tokenizer from {}: {e}",
+                tokenizer_path.display()
+            ))
+        })?;
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: max_tokens,
+                ..Default::default()
+            }))
+            .map_err(|e| SemanticError::Config(format!("Invalid embedding truncation: {e}")))?;
+        tokenizer.with_padding(Some(PaddingParams::default()));
+
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .commit_from_file(model_path)?;
+        let uses_token_type_ids = session
+            .inputs
+            .iter()
+            .any(|input| input.name == "token_type_ids");
+
+        tracing::info!("Loaded embedding model from {}", model_path.display());
+        Ok(Self {
+            model: Arc::new(OnnxModel {
+                session: Mutex::new(session),
+                tokenizer,
+                uses_token_type_ids,
+            }),
+        })
+    }
+
+    /// Load the files named by `model_path` and `tokenizer_path`
+    pub fn from_config(config: &EmbeddingProviderConfig, max_tokens: usize) -> Result<Self> {
+        Self::from_files(
+            config.required(&config.model_path, "model_path")?,
+            config.required(&config.tokenizer_path, "tokenizer_path")?,
+            max_tokens,
+        )
+    }
+}
+
+impl OnnxModel {
+    fn embed_batch(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let count = texts.len();
+        let encodings = self.tokenizer.encode_batch(texts, true).map_err(|e| {
+            SemanticError::Embedding(format!("Failed to tokenize for embedding: {e}"))
+        })?;
+        let width = encodings.first().map_or(0, |encoding| encoding.len());
+        if width == 0 {
+            return Err(SemanticError::Embedding(
+                "Tokenizer produced no tokens".to_string(),
+            ));
+        }
+
+        let shape = [count, width];
+        let mut inputs: Vec<(Cow<'_, str>, SessionInputValue<'_>)> = vec![
+            (
+                "input_ids".into(),
+                Tensor::from_array((shape, flatten(&encodings, Encoding::get_ids)))?.into(),
+            ),
+            (
+                "attention_mask".into(),
+                Tensor::from_array((shape, flatten(&encodings, Encoding::get_attention_mask)))?
+                    .into(),
+            ),
+        ];
+        if self.uses_token_type_ids {
+            inputs.push((
+                "token_type_ids".into(),
+                Tensor::from_array((shape, flatten(&encodings, Encoding::get_type_ids)))?.into(),
+            ));
+        }
+
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|e| SemanticError::Embedding(format!("Embedding model lock poisoned: {e}")))?;
+        let outputs = session.run(inputs)?;
+        let (output_shape, values) = outputs[0].try_extract_tensor::<f32>()?;
+
+        let vectors: Vec<Vec<f32>> = match **output_shape {
+            // One vector per text
+            [_, dimensions] if dimensions > 0 => values
+                .chunks(dimensions as usize)
+                .map(<[f32]>::to_vec)
+                .collect(),
+            // One vector per token, averaged over the real tokens of each text
+            [_, tokens, dimensions] if dimensions > 0 => {
+                let (tokens, dimensions) = (tokens as usize, dimensions as usize);
+                encodings
+                    .iter()
+                    .zip(values.chunks(tokens * dimensions))
+                    .map(|(encoding, token_vectors)| {
+                        mean_pool(token_vectors, encoding.get_attention_mask(), dimensions)
+                    })
+                    .collect()
+            }
+            _ => {
+                return Err(SemanticError::Embedding(format!(
+                    "Unexpected embedding model output shape {:?}",
+                    &**output_shape
+                )))
+            }
+        };
+
+        Ok(vectors.into_iter().map(normalize).collect())
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OnnxFileProvider {
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        // Inference takes long enough to stall other tasks on this worker
+        let model = Arc::clone(&self.model);
+        tokio::task::spawn_blocking(move || model.embed_batch(texts))
+            .await
+            .map_err(|e| SemanticError::Embedding(format!("Embedding task failed: {e}")))?
+    }
+}
+
+/// Average the vectors of the tokens the attention mask keeps
+fn mean_pool(token_vectors: &[f32], attention_mask: &[u32], dimensions: usize) -> Vec<f32> {
+    let mut sum = vec![0.0; dimensions];
+    let mut kept = 0.0;
+    for (token_vector, _) in token_vectors
+        .chunks(dimensions)
+        .zip(attention_mask)
+        .filter(|(_, mask)| **mask != 0)
+    {
+        for (total, value) in sum.iter_mut().zip(token_vector) {
+            *total += value;
+        }
+        kept += 1.0;
+    }
+    if kept > 0.0 {
+        for total in &mut sum {
+            *total /= kept;
+        }
+    }
+    sum
+}
+
+/// Scale a vector to unit length
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let magnitude = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if magnitude > 0.0 {
+        for value in &mut vector {
+            *value /= magnitude;
+        }
+    }
+    vector
+}
+
+/// Response body of `/v1/embeddings`
+#[derive(Debug, Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    #[serde(default)]
+    index: usize,
+}
+
+/// Embedding server implementing the OpenAI `/v1/embeddings` API
+///
+/// Works with the OpenAI API itself and with servers such as Ollama, vLLM,
+/// LM Studio or text-embeddings-inference.
+pub struct OpenAiEmbeddingProvider {
+    client: reqwest::Client,
+    url: String,
+    model: String,
+    api_key: Option<String>,
+    dimensions: Option<usize>,
+}
+
+impl OpenAiEmbeddingProvider {
+    /// Create a provider for the server and model of `config`
+    pub fn from_config(config: &EmbeddingProviderConfig) -> Result<Self> {
+        let model = config.required(&config.model, "model")?.clone();
+        if model.trim().is_empty() {
+            return Err(SemanticError::Config(format!(
+                "{EMBEDDING_CONFIG_KEY}.model cannot be empty"
+            )));
+        }
+        reqwest::Url::parse(&config.base_url).map_err(|e| {
+            SemanticError::Config(format!(
+                "Invalid embedding server URL '{}': {e}",
+                config.base_url
+            ))
+        })?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| SemanticError::Config(format!("Failed to create HTTP client: {e}")))?;
+
+        Ok(Self {
+            client,
+            url: format!("{}/embeddings", config.base_url.trim_end_matches('/')),
+            model,
+            api_key: config.api_key(),
+            dimensions: config.dimensions,
+        })
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAiEmbeddingProvider {
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let count = texts.len();
+        let mut body = json!({
+            "model": self.model,
+            "input": texts,
+        });
+        if let Some(dimensions) = self.dimensions {
+            body["dimensions"] = json!(dimensions);
+        }
+
+        let mut request = self.client.post(&self.url).json(&body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request.send().await.map_err(|e| {
+            SemanticError::Embedding(format!("Request to {} failed: {e}", self.url))
+        })?;
+
+        let status = response.status();
+        let text = response.text().await.map_err(|e| {
+            SemanticError::Embedding(format!("Failed to read embedding response: {e}"))
+        })?;
+        if !status.is_success() {
+            return Err(SemanticError::Embedding(format!(
+                "Embedding server returned {status}: {text}"
+            )));
+        }
+
+        let mut parsed: EmbeddingsResponse = serde_json::from_str(&text)?;
+        if parsed.data.len() != count {
+            return Err(SemanticError::Embedding(format!(
+                "Embedding server returned {} embeddings for {count} texts",
+                parsed.data.len()
+            )));
+        }
+        parsed.data.sort_by_key(|data| data.index);
+        Ok(parsed.data.into_iter().map(|data| data.embedding).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::EmbeddingEngine;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_provider_config_defaults() {
+        let config = EmbeddingProviderConfig::from_value(json!({})).unwrap();
+        assert_eq!(config, EmbeddingProviderConfig::default());
+        assert_eq!(config.provider, EmbeddingProviderKind::Fastembed);
+        assert_eq!(config.on_model_change, ModelChangePolicy::Refuse);
+        assert_eq!(config.model_id().unwrap(), DEFAULT_EMBEDDING_MODEL);
+    }
+
+    #[test]
+    fn test_provider_config_from_value() {
+        let config = EmbeddingProviderConfig::from_value(json!({
+            "provider": "openai-compatible",
+            "base_url": "http://embeddings.internal/v1",
+            "model": "text-embedding-3-small",
+            "dimensions": 512,
+            "on_model_change": "reindex"
+        }))
+        .unwrap();
+        assert_eq!(config.provider, EmbeddingProviderKind::OpenaiCompatible);
+        assert_eq!(config.model_id().unwrap(), "text-embedding-3-small");
+        assert_eq!(config.on_model_change, ModelChangePolicy::Reindex);
+
+        let engine_config = config.embedding_config().unwrap();
+        assert_eq!(engine_config.model_id, "text-embedding-3-small");
+        assert_eq!(engine_config.dimensions, Some(512));
+    }
+
+    #[test]
+    fn test_provider_config_requires_provider_fields() {
+        let openai = EmbeddingProviderConfig::from_value(json!({
+            "provider": "openai-compatible"
+        }))
+        .unwrap();
+        let error = openai.model_id().unwrap_err().to_string();
+        assert!(error.contains("search.embedding.model"), "{error}");
+
+        let onnx = EmbeddingProviderConfig::from_value(json!({
+            "provider": "onnx",
+            "model_path": "/models/model.onnx"
+        }))
+        .unwrap();
+        assert_eq!(onnx.model_id().unwrap(), "/models/model.onnx");
+        assert!(OnnxFileProvider::from_config(&onnx, 512).is_err());
+
+        assert!(EmbeddingProviderConfig::from_value(json!({ "provider": "word2vec" })).is_err());
+    }
+
+    #[test]
+    fn test_mean_pool_skips_padding() {
+        let token_vectors = [1.0, 3.0, 3.0, 5.0, 100.0, 100.0];
+        let pooled = mean_pool(&token_vectors, &[1, 1, 0], 2);
+        assert_eq!(pooled, vec![2.0, 4.0]);
+
+        let normalized = normalize(vec![3.0, 4.0]);
+        assert_eq!(normalized, vec![0.6, 0.8]);
+    }
+
+    #[tokio::test]
+    async fn test_openai_provider_embeds_in_request_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .and(header("authorization", "Bearer secret-key"))
+            .and(body_partial_json(json!({
+                "model": "test-embedder",
+                "input": ["first", "second"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "object": "list",
+                "data": [
+                    {"object": "embedding", "index": 1, "embedding": [0.0, 1.0, 0.0]},
+                    {"object": "embedding", "index": 0, "embedding": [1.0, 0.0, 0.0]}
+                ],
+                "model": "test-embedder"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = EmbeddingProviderConfig {
+            provider: EmbeddingProviderKind::OpenaiCompatible,
+            base_url: format!("{}/v1", server.uri()),
+            model: Some("test-embedder".to_string()),
+            api_key: Some("secret-key".to_string()),
+            ..Default::default()
+        };
+        let provider = OpenAiEmbeddingProvider::from_config(&config).unwrap();
+
+        let vectors = provider
+            .embed(vec!["first".to_string(), "second".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(vectors, vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]]);
+    }
+
+    #[tokio::test]
+    async fn test_openai_provider_reports_server_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("model not found"))
+            .mount(&server)
+            .await;
+
+        let config = EmbeddingProviderConfig {
+            provider: EmbeddingProviderKind::OpenaiCompatible,
+            base_url: format!("{}/v1", server.uri()),
+            model: Some("missing".to_string()),
+            ..Default::default()
+        };
+        let error = EmbeddingEngine::with_provider_config(&config)
+            .await
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("model not found"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_engine_learns_dimensions_from_openai_provider() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{"index": 0, "embedding": [0.6, 0.8]}]
+            })))
+            .mount(&server)
+            .await;
+
+        let config = EmbeddingProviderConfig {
+            provider: EmbeddingProviderKind::OpenaiCompatible,
+            base_url: format!("{}/v1", server.uri()),
+            model: Some("tiny".to_string()),
+            on_model_change: ModelChangePolicy::Reindex,
+            ..Default::default()
+        };
+        let engine = EmbeddingEngine::with_provider_config(&config)
+            .await
+            .unwrap();
+        assert_eq!(engine.model_info().model_id, "tiny");
+        assert_eq!(engine.model_info().dimensions, 2);
+        assert_eq!(engine.on_model_change(), ModelChangePolicy::Reindex);
+        assert_eq!(
+            engine.embed_text("fn main() {}").await.unwrap(),
+            vec![0.6, 0.8]
+        );
+    }
+}
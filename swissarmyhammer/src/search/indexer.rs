@@ -1,13 +1,15 @@
 //! File indexing logic for semantic search
 
+use crate::error::SwissArmyHammerError;
 use crate::search::{
-    CodeChunk, CodeParser, EmbeddingEngine, FileChangeTracker, FileHasher, FileId, IndexedFile,
-    ParserConfig, Result, SemanticError, VectorStorage,
+    CodeChunk, CodeParser, Embedding, EmbeddingEngine, FileChangeTracker, FileHasher, FileId,
+    IndexedFile, ModelChangePolicy, ParserConfig, Result, SemanticError, VectorStorage,
 };
 use chrono::Utc;
 use ignore::WalkBuilder;
 use indicatif::{ProgressBar, ProgressStyle};
-use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
 
 /// File indexer that processes source files for semantic search
 pub struct FileIndexer {
@@ -35,9 +37,10 @@ impl FileIndexer {
     /// * `storage` - Vector storage backend for persisting chunks and embeddings
     ///
     /// # Returns
-    /// A new FileIndexer instance with default embedding engine and parser configuration
+    /// A new FileIndexer instance with the embedding provider configured in
+    /// `sah.toml` and the default parser configuration
     pub async fn new(storage: VectorStorage) -> Result<Self> {
-        let embedding_engine = EmbeddingEngine::new().await?;
+        let embedding_engine = EmbeddingEngine::from_configuration().await?;
         let parser = CodeParser::new(Default::default())?;
         let change_tracker = FileChangeTracker::new(storage.clone());
 
@@ -115,6 +118,11 @@ impl FileIndexer {
 
         tracing::info!("Found {} files matching pattern", file_paths.len());
 
+        // An index built by another embedding model is rebuilt when forced or configured to
+        let may_clear =
+            force_reindex || self.embedding_engine.on_model_change() == ModelChangePolicy::Reindex;
+        let force_reindex = self.prepare_index(may_clear)? || force_reindex;
+
         // Filter files based on change detection unless forced
         let files_to_process = if force_reindex {
             file_paths
@@ -131,24 +139,64 @@ impl FileIndexer {
         self.index_files(files_to_process, force_reindex).await
     }
 
-    /// Expand glob pattern to list of file paths while respecting .gitignore
-    fn expand_glob_pattern(&self, pattern: &str) -> Result<Vec<PathBuf>> {
-        let mut paths = Vec::new();
-
-        // Parse the pattern to extract base directory and file pattern
-        let (base_dir, file_pattern) = self.parse_glob_pattern(pattern)?;
+    /// Make sure the index holds vectors of the configured embedding model
+    ///
+    /// An index built by a different model is cleared when `may_clear` is
+    /// set, and refused otherwise. Returns whether the index was cleared, in
+    /// which case every file needs indexing again.
+    fn prepare_index(&self, may_clear: bool) -> Result<bool> {
+        let model = self.embedding_engine.model_info();
+        let cleared = match self.storage.check_embedding_model(&model) {
+            Ok(()) => false,
+            Err(e) if may_clear => {
+                tracing::warn!(
+                    "Rebuilding the search index for embedding model {}: {}",
+                    model.model_id,
+                    e
+                );
+                self.storage.clear()?;
+                true
+            }
+            Err(e) => return Err(e.into()),
+        };
+        self.storage.record_embedding_model(&model)?;
+        Ok(cleared)
+    }
 
-        // Create a walker that respects .gitignore files
-        let walker = WalkBuilder::new(&base_dir)
+    /// Walk a directory, skipping whatever `.gitignore` files exclude
+    fn walk(dir: &Path) -> ignore::Walk {
+        WalkBuilder::new(dir)
             .git_ignore(true) // Respect .gitignore files
             .git_global(true) // Respect global gitignore
             .git_exclude(true) // Respect .git/info/exclude
             .hidden(false) // Include hidden files (let gitignore decide)
             .parents(true) // Check parent directories for .gitignore
-            .build();
+            .build()
+    }
+
+    /// Supported files under a directory that `.gitignore` does not exclude
+    fn supported_files_in(&self, dir: &Path) -> Vec<PathBuf> {
+        Self::walk(dir)
+            .filter_map(|entry| match entry {
+                Ok(entry) => Some(entry.into_path()),
+                Err(e) => {
+                    tracing::warn!("Error processing directory entry: {}", e);
+                    None
+                }
+            })
+            .filter(|path| path.is_file() && self.is_supported_file(path))
+            .collect()
+    }
+
+    /// Expand glob pattern to list of file paths while respecting .gitignore
+    fn expand_glob_pattern(&self, pattern: &str) -> Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+
+        // Parse the pattern to extract base directory and file pattern
+        let (base_dir, file_pattern) = self.parse_glob_pattern(pattern)?;
 
         // Walk the directory structure and collect matching files
-        for entry in walker {
+        for entry in Self::walk(&base_dir) {
             match entry {
                 Ok(dir_entry) => {
                     let path = dir_entry.path();
@@ -258,6 +306,7 @@ impl FileIndexer {
     ) -> Result<IndexingReport> {
         let mut report = IndexingReport::new();
         let start_time = std::time::Instant::now();
+        self.prepare_index(false)?;
 
         // Setup progress bar
         let progress = ProgressBar::new(file_paths.len() as u64);
@@ -409,6 +458,12 @@ impl FileIndexer {
                     file_path.display()
                 ))
             })?;
+        self.storage.store_lexical_chunks(&chunks).map_err(|e| {
+            SemanticError::Index(format!(
+                "Failed to add chunks from file {} to the lexical index: {e}",
+                file_path.display()
+            ))
+        })?;
         let storage_duration = storage_start.elapsed();
 
         let total_duration = start_time.elapsed();
@@ -508,6 +563,160 @@ impl FileIndexer {
         Ok(overall_report)
     }
 
+    /// Bring the index up to date with paths that changed on disk
+    ///
+    /// Unlike [`Self::incremental_index`], only the given paths are hashed.
+    /// Files whose content changed are re-chunked and replace their old chunks,
+    /// with the chunks of all files embedded together in one batch. Paths that
+    /// no longer exist, such as deleted or renamed files and removed
+    /// directories, have their chunks and vectors removed. Directories that
+    /// exist, such as one created, moved in or renamed, have their supported
+    /// files indexed, since watchers only report the directory itself.
+    pub async fn apply_changes(&mut self, paths: &[PathBuf]) -> Result<IndexingReport> {
+        let start_time = std::time::Instant::now();
+        let mut report = IndexingReport::new();
+
+        // Paths are stored as they were given when indexing, `./src/lib.rs`
+        // and `src/lib.rs` being the same file
+        let indexed: HashMap<PathBuf, PathBuf> = self
+            .storage
+            .indexed_paths()?
+            .into_iter()
+            .map(|path| (normalize_path(&path), path))
+            .collect();
+
+        let mut changed: Vec<(PathBuf, Vec<CodeChunk>)> = Vec::new();
+        let mut seen = HashSet::new();
+        let mut pending: Vec<PathBuf> = paths.iter().rev().cloned().collect();
+        while let Some(path) = pending.pop() {
+            let path = &path;
+            let key = normalize_path(path);
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            if path.is_dir() {
+                pending.extend(self.supported_files_in(path).into_iter().rev());
+                continue;
+            }
+            if !path.exists() {
+                for stored in indexed
+                    .iter()
+                    .filter(|(indexed_key, _)| indexed_key.starts_with(&key))
+                    .map(|(_, stored)| stored)
+                {
+                    match self.storage.remove_file_transaction(stored) {
+                        Ok(()) => report.files_removed += 1,
+                        Err(e) => report.add_error(stored.clone(), e),
+                    }
+                }
+                continue;
+            }
+            if !path.is_file() || !self.is_supported_file(path) {
+                continue;
+            }
+
+            let file_path = indexed.get(&key).unwrap_or(path);
+            match self.read_changed_chunks(file_path) {
+                Ok(Some(chunks)) => changed.push((file_path.clone(), chunks)),
+                Ok(None) => tracing::debug!("Unchanged: {}", file_path.display()),
+                Err(e) => report.add_error(file_path.clone(), SwissArmyHammerError::Semantic(e)),
+            }
+        }
+
+        let all_chunks: Vec<CodeChunk> = changed
+            .iter()
+            .flat_map(|(_, chunks)| chunks.iter().cloned())
+            .collect();
+        if !all_chunks.is_empty() {
+            self.prepare_index(false)?;
+        }
+        let mut embeddings = match self.embedding_engine.embed_chunks_batch(&all_chunks).await {
+            Ok(embeddings) => embeddings.into_iter(),
+            Err(e) => {
+                for (file_path, _) in changed {
+                    report.add_error(
+                        file_path,
+                        SwissArmyHammerError::Semantic(SemanticError::Embedding(e.to_string())),
+                    );
+                }
+                report.duration = start_time.elapsed();
+                return Ok(report);
+            }
+        };
+
+        for (file_path, chunks) in changed {
+            let file_embeddings: Vec<_> = embeddings.by_ref().take(chunks.len()).collect();
+            match self.replace_file_chunks(&file_path, &chunks, &file_embeddings) {
+                Ok(()) => report.merge(SingleFileReport {
+                    file_path,
+                    success: true,
+                    chunks_parsed: chunks.len(),
+                    embeddings_generated: file_embeddings.len(),
+                }),
+                Err(e) => report.add_error(file_path, SwissArmyHammerError::Semantic(e)),
+            }
+        }
+
+        report.duration = start_time.elapsed();
+        tracing::info!(
+            "Applied changes: {}, {} files removed",
+            report.summary(),
+            report.files_removed
+        );
+        Ok(report)
+    }
+
+    /// Chunks of a file whose content differs from the indexed version
+    ///
+    /// Returns `None` if the file is indexed with the same content.
+    fn read_changed_chunks(&self, file_path: &Path) -> Result<Option<Vec<CodeChunk>>> {
+        let content_hash = FileHasher::hash_file(file_path)?;
+        if !self.storage.needs_reindexing(file_path, &content_hash)? {
+            return Ok(None);
+        }
+
+        use crate::search::parser::DEFAULT_MAX_FILE_SIZE_BYTES;
+        let file_size = std::fs::metadata(file_path)?.len();
+        if file_size > DEFAULT_MAX_FILE_SIZE_BYTES as u64 {
+            return Err(SemanticError::Index(format!(
+                "File {} is too large ({} bytes > {} bytes limit). Skipping to prevent OOM.",
+                file_path.display(),
+                file_size,
+                DEFAULT_MAX_FILE_SIZE_BYTES
+            )));
+        }
+
+        let content = std::fs::read_to_string(file_path)?;
+        Ok(Some(self.parser.parse_file(file_path, &content)?))
+    }
+
+    /// Replace the stored chunks, vectors and metadata of a file
+    fn replace_file_chunks(
+        &self,
+        file_path: &Path,
+        chunks: &[CodeChunk],
+        embeddings: &[Embedding],
+    ) -> Result<()> {
+        let store_error = |e: SwissArmyHammerError| {
+            SemanticError::Index(format!(
+                "Failed to store changes to file {}: {e}",
+                file_path.display()
+            ))
+        };
+        self.storage
+            .remove_file_transaction(file_path)
+            .map_err(store_error)?;
+        self.storage
+            .store_chunks_and_embeddings_transaction(chunks, embeddings)
+            .map_err(store_error)?;
+        self.storage
+            .store_indexed_file(&self.create_file_metadata(file_path, chunks)?)
+            .map_err(store_error)?;
+        self.storage
+            .store_lexical_chunks(chunks)
+            .map_err(store_error)
+    }
+
     /// Re-index only files that have changed
     pub async fn incremental_index(&mut self, pattern: &str) -> Result<IndexingReport> {
         self.index_glob(pattern, false).await
@@ -548,6 +757,8 @@ pub struct IndexingReport {
     pub files_successful: usize,
     /// Number of files that failed to index
     pub files_failed: usize,
+    /// Number of deleted or renamed files removed from the index
+    pub files_removed: usize,
     /// Total number of code chunks generated
     pub total_chunks: usize,
     /// Total number of embeddings generated
@@ -565,6 +776,7 @@ impl IndexingReport {
             files_processed: 0,
             files_successful: 0,
             files_failed: 0,
+            files_removed: 0,
             total_chunks: 0,
             total_embeddings: 0,
             errors: Vec::new(),
@@ -601,6 +813,7 @@ impl IndexingReport {
         self.files_processed += other.files_processed;
         self.files_successful += other.files_successful;
         self.files_failed += other.files_failed;
+        self.files_removed += other.files_removed;
         self.total_chunks += other.total_chunks;
         self.total_embeddings += other.total_embeddings;
         self.errors.extend(other.errors);
@@ -626,6 +839,13 @@ impl Default for IndexingReport {
     }
 }
 
+/// A path without `.` components, for comparing paths given in different forms
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
 /// Report for a single file indexing operation
 #[derive(Debug)]
 pub struct SingleFileReport {
@@ -735,6 +955,100 @@ mod tests {
         let report = report.unwrap();
         assert_eq!(report.files_processed, 1);
         assert_eq!(report.total_chunks, 1);
+
+        // Chunks go into the lexical index alongside the embeddings
+        let lexical = indexer.storage.lexical_search("println", 5).unwrap();
+        assert_eq!(lexical.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_apply_changes() {
+        let (mut indexer, temp_dir) = create_test_indexer()
+            .await
+            .expect("Failed to create test indexer");
+
+        let kept = temp_dir.path().join("kept.rs");
+        let deleted = temp_dir.path().join("deleted.rs");
+        fs::write(&kept, "fn alpha() {}").unwrap();
+        fs::write(&deleted, "fn beta() {}").unwrap();
+
+        let report = indexer
+            .apply_changes(&[kept.clone(), deleted.clone()])
+            .await
+            .unwrap();
+        assert_eq!(report.files_successful, 2);
+
+        // Unchanged files are skipped
+        let report = indexer.apply_changes(&[kept.clone()]).await.unwrap();
+        assert_eq!(report.files_processed, 0);
+
+        fs::write(&kept, "fn gamma() {}").unwrap();
+        fs::remove_file(&deleted).unwrap();
+        let report = indexer
+            .apply_changes(&[kept.clone(), deleted.clone()])
+            .await
+            .unwrap();
+        assert_eq!(report.files_successful, 1);
+        assert_eq!(report.files_removed, 1);
+
+        assert_eq!(indexer.storage.indexed_paths().unwrap(), vec![kept.clone()]);
+        assert!(indexer
+            .storage
+            .lexical_search("alpha", 5)
+            .unwrap()
+            .is_empty());
+        assert!(indexer
+            .storage
+            .lexical_search("beta", 5)
+            .unwrap()
+            .is_empty());
+        assert_eq!(indexer.storage.lexical_search("gamma", 5).unwrap().len(), 1);
+        assert_eq!(
+            indexer.storage.get_index_stats().unwrap().embedding_count,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_changes_to_renamed_directory() {
+        let (mut indexer, temp_dir) = create_test_indexer()
+            .await
+            .expect("Failed to create test indexer");
+
+        let old_dir = temp_dir.path().join("old");
+        fs::create_dir_all(old_dir.join("nested")).unwrap();
+        fs::write(old_dir.join("a.rs"), "fn alpha() {}").unwrap();
+        fs::write(old_dir.join("nested/b.rs"), "fn beta() {}").unwrap();
+        fs::write(old_dir.join("notes.bin"), "not code").unwrap();
+        fs::create_dir_all(temp_dir.path().join(".git")).unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "generated.rs\n").unwrap();
+        fs::write(old_dir.join("generated.rs"), "fn generated() {}").unwrap();
+
+        // A created directory is reported as just the directory
+        let report = indexer.apply_changes(&[old_dir.clone()]).await.unwrap();
+        assert_eq!(report.files_successful, 2);
+
+        // A rename is reported as the old and the new directory
+        let new_dir = temp_dir.path().join("new");
+        fs::rename(&old_dir, &new_dir).unwrap();
+        let report = indexer
+            .apply_changes(&[old_dir.clone(), new_dir.clone()])
+            .await
+            .unwrap();
+        assert_eq!(report.files_removed, 2);
+        assert_eq!(report.files_successful, 2);
+
+        let mut indexed = indexer.storage.indexed_paths().unwrap();
+        indexed.sort();
+        assert_eq!(
+            indexed,
+            vec![new_dir.join("a.rs"), new_dir.join("nested/b.rs")]
+        );
+        assert!(indexer
+            .storage
+            .lexical_search("generated", 5)
+            .unwrap()
+            .is_empty());
     }
 
     #[tokio::test]
@@ -756,6 +1070,64 @@ mod tests {
         assert!(report.total_chunks > 0);
     }
 
+    #[tokio::test]
+    async fn test_embedding_model_change() {
+        let (mut indexer, temp_dir) = create_test_indexer()
+            .await
+            .expect("Failed to create test indexer");
+        std::fs::write(temp_dir.path().join("lib.rs"), "pub fn hello() {}").unwrap();
+        let pattern = format!("{}/*.rs", temp_dir.path().display());
+        indexer.index_glob(&pattern, false).await.unwrap();
+
+        let other_engine = || async {
+            EmbeddingEngine::new_for_testing_with_config(crate::search::EmbeddingConfig {
+                model_id: "other-mock-model".to_string(),
+                dimensions: Some(384),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+        };
+
+        // Another model is refused rather than mixing incomparable vectors
+        let mut other = FileIndexer::with_custom_embedding_engine(
+            indexer.storage.clone(),
+            other_engine().await,
+        )
+        .await
+        .unwrap();
+        let error = other.index_glob(&pattern, false).await.unwrap_err();
+        assert!(error.to_string().contains("mock-test-model"), "{error}");
+        std::fs::write(temp_dir.path().join("lib.rs"), "pub fn changed() {}").unwrap();
+        assert!(other
+            .apply_changes(&[temp_dir.path().join("lib.rs")])
+            .await
+            .is_err());
+
+        // Configured to reindex, the index is rebuilt with the new model
+        let mut other = FileIndexer::with_custom_embedding_engine(
+            indexer.storage.clone(),
+            other_engine()
+                .await
+                .with_model_change_policy(ModelChangePolicy::Reindex),
+        )
+        .await
+        .unwrap();
+        let report = other.index_glob(&pattern, false).await.unwrap();
+        assert_eq!(report.files_successful, 1);
+        let model = other.storage.embedding_model().unwrap();
+        assert_eq!(model.model_id.as_deref(), Some("other-mock-model"));
+        assert_eq!(model.dimensions, Some(384));
+
+        // Forcing a reindex also switches models
+        let report = indexer.index_glob(&pattern, true).await.unwrap();
+        assert_eq!(report.files_successful, 1);
+        assert_eq!(
+            indexer.storage.embedding_model().unwrap().dimensions,
+            Some(768)
+        );
+    }
+
     #[tokio::test]
     async fn test_incremental_vs_full_reindex() {
         let (mut indexer, temp_dir) = create_test_indexer()
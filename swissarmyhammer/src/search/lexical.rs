@@ -0,0 +1,318 @@
+//! BM25 lexical index over code chunks
+//!
+//! Embeddings match code by meaning but are poor at exact identifiers: a query
+//! for `validate_branch_name` may rank a dozen vaguely related validators above
+//! the function itself. The [`LexicalIndex`] keeps a tantivy index next to the
+//! DuckDB database with two views of every chunk: its text, split into words,
+//! and the identifiers it contains, kept whole. Identifier matches are boosted
+//! so exact names rank first, and hybrid search fuses these rankings with the
+//! vector ones.
+
+use crate::common::mcp_errors::McpResultExt;
+use crate::error::{Result, SwissArmyHammerError};
+use crate::search::CodeChunk;
+use std::collections::HashSet;
+use std::path::Path;
+use tantivy::{
+    collector::TopDocs,
+    directory::MmapDirectory,
+    query::{BooleanQuery, BoostQuery, Occur, Query, QueryParser, TermQuery},
+    schema::{
+        Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, STORED, STRING,
+        TEXT,
+    },
+    tokenizer::{LowerCaser, RawTokenizer, TextAnalyzer},
+    Index, IndexReader, IndexWriter, TantivyDocument, Term,
+};
+
+/// Name the identifier tokenizer is registered under
+const IDENTIFIER_TOKENIZER: &str = "code_identifier";
+
+/// Memory budget of the index writer, the minimum tantivy accepts with headroom
+const WRITER_BUFFER_SIZE: usize = 20_000_000;
+
+/// Weight of an exact identifier match relative to a word match
+const IDENTIFIER_BOOST: f32 = 3.0;
+
+/// Identifiers shorter than this are too common to be worth indexing
+const MIN_IDENTIFIER_LENGTH: usize = 3;
+
+/// Fields of the lexical index schema
+#[derive(Clone, Copy)]
+struct LexicalFields {
+    chunk_id: Field,
+    file_path: Field,
+    content: Field,
+    symbols: Field,
+}
+
+/// Tantivy index of chunk text and identifiers
+///
+/// A writer is opened for each change and released once it is committed, so
+/// several processes can share the index as long as they do not write at the
+/// same moment.
+pub struct LexicalIndex {
+    index: Index,
+    reader: IndexReader,
+    fields: LexicalFields,
+}
+
+impl LexicalIndex {
+    /// Open the index in `path`, creating it if it does not exist
+    pub fn open(path: &Path) -> Result<Self> {
+        std::fs::create_dir_all(path)?;
+        let directory = MmapDirectory::open(path).with_tantivy_context()?;
+        let index =
+            Index::open_or_create(directory, Self::build_schema()).with_tantivy_context()?;
+        Self::from_index(index)
+    }
+
+    /// Create an index that lives in memory only
+    pub fn in_memory() -> Result<Self> {
+        Self::from_index(Index::create_in_ram(Self::build_schema()))
+    }
+
+    fn from_index(index: Index) -> Result<Self> {
+        index.tokenizers().register(
+            IDENTIFIER_TOKENIZER,
+            TextAnalyzer::builder(RawTokenizer::default())
+                .filter(LowerCaser)
+                .build(),
+        );
+
+        let schema = index.schema();
+        let field = |name: &str| {
+            schema.get_field(name).map_err(|_| {
+                SwissArmyHammerError::Storage(format!("Missing {name} field in lexical index"))
+            })
+        };
+        let fields = LexicalFields {
+            chunk_id: field("chunk_id")?,
+            file_path: field("file_path")?,
+            content: field("content")?,
+            symbols: field("symbols")?,
+        };
+        let reader = index.reader().with_tantivy_context()?;
+
+        Ok(Self {
+            index,
+            reader,
+            fields,
+        })
+    }
+
+    fn build_schema() -> Schema {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_text_field("chunk_id", STRING | STORED);
+        schema_builder.add_text_field("file_path", STRING | STORED);
+        schema_builder.add_text_field("content", TEXT);
+        schema_builder.add_text_field(
+            "symbols",
+            TextOptions::default().set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_tokenizer(IDENTIFIER_TOKENIZER)
+                    .set_index_option(IndexRecordOption::WithFreqs),
+            ),
+        );
+        schema_builder.build()
+    }
+
+    fn writer(&self) -> Result<IndexWriter> {
+        self.index
+            .writer_with_num_threads(1, WRITER_BUFFER_SIZE)
+            .with_tantivy_context()
+    }
+
+    /// Commit a writer's changes and make them visible to searches
+    fn commit(&self, mut writer: IndexWriter) -> Result<()> {
+        writer.commit().with_tantivy_context()?;
+        self.reader.reload().with_tantivy_context()
+    }
+
+    /// Number of chunks in the index
+    pub fn len(&self) -> usize {
+        self.reader.searcher().num_docs() as usize
+    }
+
+    /// Whether the index holds no chunks
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add chunks to the index, replacing any chunks with the same ids
+    pub fn index_chunks(&self, chunks: &[CodeChunk]) -> Result<()> {
+        if chunks.is_empty() {
+            return Ok(());
+        }
+
+        let writer = self.writer()?;
+        for chunk in chunks {
+            writer.delete_term(Term::from_field_text(self.fields.chunk_id, &chunk.id));
+
+            let mut doc = TantivyDocument::default();
+            doc.add_text(self.fields.chunk_id, &chunk.id);
+            doc.add_text(self.fields.file_path, chunk.file_path.to_string_lossy());
+            doc.add_text(self.fields.content, &chunk.content);
+            for identifier in extract_identifiers(&chunk.content) {
+                doc.add_text(self.fields.symbols, identifier);
+            }
+            writer.add_document(doc).with_tantivy_context()?;
+        }
+        self.commit(writer)
+    }
+
+    /// Remove every chunk of a file
+    pub fn remove_file(&self, file_path: &Path) -> Result<()> {
+        let writer = self.writer()?;
+        writer.delete_term(Term::from_field_text(
+            self.fields.file_path,
+            &file_path.to_string_lossy(),
+        ));
+        self.commit(writer)
+    }
+
+    /// Remove every chunk
+    pub fn clear(&self) -> Result<()> {
+        let mut writer = self.writer()?;
+        writer.delete_all_documents().with_tantivy_context()?;
+        self.commit(writer)
+    }
+
+    /// Rank chunks by BM25 against the query, best first
+    ///
+    /// Returns chunk ids with their scores. Query syntax errors are ignored
+    /// rather than reported, since queries are usually plain text or code.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<(String, f32)>> {
+        if query.trim().is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+
+        let parser = QueryParser::for_index(&self.index, vec![self.fields.content]);
+        let (content_query, _errors) = parser.parse_query_lenient(query);
+        clauses.push((Occur::Should, content_query));
+
+        for identifier in extract_identifiers(query) {
+            let term = Term::from_field_text(self.fields.symbols, &identifier.to_lowercase());
+            clauses.push((
+                Occur::Should,
+                Box::new(BoostQuery::new(
+                    Box::new(TermQuery::new(term, IndexRecordOption::WithFreqs)),
+                    IDENTIFIER_BOOST,
+                )),
+            ));
+        }
+
+        let searcher = self.reader.searcher();
+        let top_docs = searcher
+            .search(&BooleanQuery::new(clauses), &TopDocs::with_limit(limit))
+            .with_tantivy_context()?;
+
+        let mut results = Vec::with_capacity(top_docs.len());
+        for (score, doc_address) in top_docs {
+            let doc = searcher
+                .doc::<TantivyDocument>(doc_address)
+                .with_tantivy_context()?;
+            if let Some(chunk_id) = doc
+                .get_first(self.fields.chunk_id)
+                .and_then(|value| value.as_str())
+            {
+                results.push((chunk_id.to_string(), score));
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Distinct identifiers in `text`, in order of first appearance
+///
+/// An identifier is a run of letters, digits and underscores that does not
+/// start with a digit, such as `validate_branch_name` or `SemanticSearcher`.
+pub fn extract_identifiers(text: &str) -> Vec<&str> {
+    let mut seen = HashSet::new();
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| {
+            word.len() >= MIN_IDENTIFIER_LENGTH
+                && !word.starts_with(|c: char| c.is_ascii_digit())
+                && seen.insert(*word)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{ChunkType, ContentHash, Language};
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn chunk(id: &str, file: &str, content: &str) -> CodeChunk {
+        CodeChunk {
+            id: id.to_string(),
+            file_path: PathBuf::from(file),
+            language: Language::Rust,
+            content: content.to_string(),
+            start_line: 1,
+            end_line: 3,
+            chunk_type: ChunkType::Function,
+            content_hash: ContentHash(format!("{id}-hash")),
+            symbol: None,
+        }
+    }
+
+    #[test]
+    fn test_extract_identifiers() {
+        assert_eq!(
+            extract_identifiers("fn validate_branch_name(name: &str) -> Result<()> { name }"),
+            vec!["validate_branch_name", "name", "str", "Result"]
+        );
+        assert!(extract_identifiers("1st 2 go").is_empty());
+    }
+
+    #[test]
+    fn test_exact_identifier_ranks_first() {
+        let index = LexicalIndex::in_memory().unwrap();
+        index
+            .index_chunks(&[
+                chunk(
+                    "validators",
+                    "src/validate.rs",
+                    "fn validate_name(name: &str) -> bool { validate(name) && branch(name) }",
+                ),
+                chunk(
+                    "branch",
+                    "src/git.rs",
+                    "fn validate_branch_name(name: &str) -> Result<()> { check(name) }",
+                ),
+                chunk("other", "src/main.rs", "fn main() { run() }"),
+            ])
+            .unwrap();
+        assert_eq!(index.len(), 3);
+
+        let results = index.search("validate_branch_name", 10).unwrap();
+        assert_eq!(results[0].0, "branch");
+        assert!(!results.iter().any(|(id, _)| id == "other"));
+    }
+
+    #[test]
+    fn test_reindex_and_remove_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = LexicalIndex::open(&temp_dir.path().join("lexical")).unwrap();
+        index
+            .index_chunks(&[chunk("a", "src/a.rs", "fn alpha() {}")])
+            .unwrap();
+        index
+            .index_chunks(&[chunk("a", "src/a.rs", "fn beta() {}")])
+            .unwrap();
+        assert_eq!(index.len(), 1);
+        assert!(index.search("alpha", 5).unwrap().is_empty());
+        assert_eq!(index.search("beta", 5).unwrap()[0].0, "a");
+
+        index.remove_file(Path::new("src/a.rs")).unwrap();
+        assert!(index.is_empty());
+
+        // Query syntax the parser rejects still searches
+        assert!(index.search("fn beta(", 5).unwrap().is_empty());
+    }
+}
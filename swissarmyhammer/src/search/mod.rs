@@ -1,18 +1,23 @@
 //! Semantic search functionality using vector embeddings and TreeSitter parsing
 //!
 //! This module provides semantic search capabilities for source code files.
-//! It uses mistral.rs for embeddings, DuckDB for vector storage, and TreeSitter
-//! for parsing various programming languages.
+//! It uses a configurable embedding provider for embeddings, DuckDB for vector
+//! storage, and TreeSitter for parsing various programming languages.
 
 use thiserror::Error;
 
+pub mod ann;
 pub mod embedding;
+pub mod embedding_provider;
 pub mod indexer;
+pub mod lexical;
 pub mod parser;
+pub mod rerank;
 pub mod searcher;
 pub mod storage;
 pub mod types;
 pub mod utils;
+pub mod watcher;
 
 // Integration tests
 #[cfg(test)]
@@ -93,13 +98,21 @@ impl From<crate::error::SwissArmyHammerError> for SemanticError {
     }
 }
 
+pub use ann::{HnswConfig, HnswIndex};
 pub use embedding::*;
+pub use embedding_provider::{
+    EmbeddingProviderConfig, EmbeddingProviderKind, ModelChangePolicy, OnnxFileProvider,
+    OpenAiEmbeddingProvider, EMBEDDING_CONFIG_KEY,
+};
 pub use indexer::*;
+pub use lexical::LexicalIndex;
 pub use parser::*;
+pub use rerank::CrossEncoderReranker;
 pub use searcher::*;
 pub use storage::*;
 pub use types::*;
 pub use utils::*;
+pub use watcher::{watch_index, IndexWatchOptions, IndexWatchStatus};
 
 // Re-export for convenience
 pub use SemanticError as Error;
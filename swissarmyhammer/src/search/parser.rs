@@ -1,6 +1,7 @@
 //! TreeSitter integration for parsing source code
 
-use crate::search::types::{ChunkType, CodeChunk, ContentHash, Language};
+use crate::outline::{create_extractors, OutlineNode, OutlineNodeType, SymbolExtractor};
+use crate::search::types::{ChunkSymbol, ChunkType, CodeChunk, ContentHash, Language};
 use crate::search::utils::FileHasher;
 use crate::search::{Result, SemanticError};
 use dashmap::DashMap;
@@ -8,7 +9,7 @@ use std::collections::HashMap;
 use std::path::Path;
 use tracing;
 use tree_sitter::{
-    Language as TreeSitterLanguage, Node, Parser, Query, QueryCursor, StreamingIterator,
+    Language as TreeSitterLanguage, Node, Parser, Query, QueryCursor, StreamingIterator, Tree,
 };
 
 /// Default minimum chunk size in characters
@@ -54,6 +55,13 @@ impl LanguageRegistry {
         registry.register(create_typescript_definition());
         registry.register(create_javascript_definition());
         registry.register(create_dart_definition());
+        registry.register(create_go_definition());
+        registry.register(create_java_definition());
+        registry.register(create_c_definition());
+        registry.register(create_cpp_definition());
+        registry.register(create_csharp_definition());
+        registry.register(create_ruby_definition());
+        registry.register(create_markdown_definition());
 
         registry
     }
@@ -89,6 +97,13 @@ impl LanguageRegistry {
     pub fn supported_languages(&self) -> Vec<Language> {
         self.definitions.keys().cloned().collect()
     }
+
+    /// Get the file extensions of all supported languages, sorted
+    pub fn supported_extensions(&self) -> Vec<&str> {
+        let mut extensions: Vec<&str> = self.extension_map.keys().map(String::as_str).collect();
+        extensions.sort_unstable();
+        extensions
+    }
 }
 
 /// Create Rust language definition
@@ -252,11 +267,167 @@ fn create_dart_definition() -> LanguageDefinition {
     }
 }
 
+/// Create Go language definition
+fn create_go_definition() -> LanguageDefinition {
+    LanguageDefinition {
+        language: Language::Go,
+        extensions: vec!["go"],
+        tree_sitter_language: || tree_sitter_go::LANGUAGE.into(),
+        queries: vec![
+            // Functions
+            ("(function_declaration) @function", ChunkType::Function),
+            // Methods with a receiver
+            ("(method_declaration) @method", ChunkType::Function),
+            // Struct and interface types
+            (
+                "(type_declaration (type_spec type: (struct_type))) @struct",
+                ChunkType::Class,
+            ),
+            (
+                "(type_declaration (type_spec type: (interface_type))) @interface",
+                ChunkType::Class,
+            ),
+            // Import declarations
+            ("(import_declaration) @import", ChunkType::Import),
+        ],
+    }
+}
+
+/// Create Java language definition
+fn create_java_definition() -> LanguageDefinition {
+    LanguageDefinition {
+        language: Language::Java,
+        extensions: vec!["java"],
+        tree_sitter_language: || tree_sitter_java::LANGUAGE.into(),
+        queries: vec![
+            // Methods and constructors
+            ("(method_declaration) @method", ChunkType::Function),
+            (
+                "(constructor_declaration) @constructor",
+                ChunkType::Function,
+            ),
+            // Classes and records
+            (
+                "[(class_declaration) (record_declaration)] @class",
+                ChunkType::Class,
+            ),
+            // Interfaces and enums
+            ("(interface_declaration) @interface", ChunkType::Class),
+            ("(enum_declaration) @enum", ChunkType::Class),
+            // Import statements
+            ("(import_declaration) @import", ChunkType::Import),
+        ],
+    }
+}
+
+/// Create C language definition
+fn create_c_definition() -> LanguageDefinition {
+    LanguageDefinition {
+        language: Language::C,
+        extensions: vec!["c", "h"],
+        tree_sitter_language: || tree_sitter_c::LANGUAGE.into(),
+        queries: vec![
+            // Functions
+            ("(function_definition) @function", ChunkType::Function),
+            // Structs and enums with a body
+            (
+                "(struct_specifier body: (field_declaration_list)) @struct",
+                ChunkType::Class,
+            ),
+            (
+                "(enum_specifier body: (enumerator_list)) @enum",
+                ChunkType::Class,
+            ),
+            // Includes
+            ("(preproc_include) @include", ChunkType::Import),
+        ],
+    }
+}
+
+/// Create C++ language definition
+fn create_cpp_definition() -> LanguageDefinition {
+    LanguageDefinition {
+        language: Language::Cpp,
+        extensions: vec!["cpp", "cc", "cxx", "hpp", "hh", "hxx"],
+        tree_sitter_language: || tree_sitter_cpp::LANGUAGE.into(),
+        queries: vec![
+            // Functions and methods
+            ("(function_definition) @function", ChunkType::Function),
+            // Classes and structs with a body
+            (
+                "[(class_specifier body: (field_declaration_list)) (struct_specifier body: (field_declaration_list))] @class",
+                ChunkType::Class,
+            ),
+            // Namespaces
+            ("(namespace_definition) @namespace", ChunkType::Module),
+            // Includes
+            ("(preproc_include) @include", ChunkType::Import),
+        ],
+    }
+}
+
+/// Create C# language definition
+fn create_csharp_definition() -> LanguageDefinition {
+    LanguageDefinition {
+        language: Language::CSharp,
+        extensions: vec!["cs"],
+        tree_sitter_language: || tree_sitter_c_sharp::LANGUAGE.into(),
+        queries: vec![
+            // Methods and constructors
+            (
+                "[(method_declaration) (constructor_declaration)] @method",
+                ChunkType::Function,
+            ),
+            // Classes, structs and records
+            (
+                "[(class_declaration) (struct_declaration) (record_declaration)] @class",
+                ChunkType::Class,
+            ),
+            // Interfaces and enums
+            ("(interface_declaration) @interface", ChunkType::Class),
+            ("(enum_declaration) @enum", ChunkType::Class),
+            // Using directives
+            ("(using_directive) @import", ChunkType::Import),
+        ],
+    }
+}
+
+/// Create Ruby language definition
+fn create_ruby_definition() -> LanguageDefinition {
+    LanguageDefinition {
+        language: Language::Ruby,
+        extensions: vec!["rb", "rake"],
+        tree_sitter_language: || tree_sitter_ruby::LANGUAGE.into(),
+        queries: vec![
+            // Instance and singleton methods
+            ("[(method) (singleton_method)] @method", ChunkType::Function),
+            // Classes
+            ("(class) @class", ChunkType::Class),
+            // Modules
+            ("(module) @module", ChunkType::Module),
+        ],
+    }
+}
+
+/// Create Markdown language definition
+fn create_markdown_definition() -> LanguageDefinition {
+    LanguageDefinition {
+        language: Language::Markdown,
+        extensions: vec!["md", "markdown"],
+        tree_sitter_language: || tree_sitter_md::LANGUAGE.into(),
+        queries: vec![
+            // Heading sections
+            ("(section) @section", ChunkType::Section),
+        ],
+    }
+}
+
 /// TreeSitter-based code parser with extensible language support
 /// Uses DashMap for thread-safe concurrent access to parsers
 pub struct CodeParser {
     parsers: DashMap<Language, Parser>,
     language_registry: LanguageRegistry,
+    symbol_extractors: HashMap<Language, Box<dyn SymbolExtractor>>,
     config: ParserConfig,
 }
 
@@ -396,9 +567,14 @@ impl CodeParser {
             }
         }
 
+        let symbol_extractors = create_extractors().map_err(|e| {
+            SemanticError::TreeSitter(format!("Failed to create symbol extractors: {e}"))
+        })?;
+
         let parser = Self {
             parsers,
             language_registry,
+            symbol_extractors,
             config,
         };
 
@@ -666,58 +842,81 @@ impl CodeParser {
             tracing::warn!("{}", error_details);
         }
 
-        let mut chunks = Vec::new();
         let content_hash = FileHasher::hash_string(content);
-        let queries = self.get_queries_for_language(language);
         let mut total_matches = 0;
 
         let query_start = std::time::Instant::now();
-        // Extract semantic chunks using queries
-        for (query_str, chunk_type) in queries {
-            let query = Query::new(&tree.language(), query_str).map_err(|e| {
-                let error_msg = format!(
-                    "Invalid TreeSitter query for {} (language: {:?}, chunk type: {:?}): {}\
-                         \nQuery pattern: {}\n\
-                         This indicates the query pattern doesn't match the language grammar.",
-                    file_path.display(),
+        // Cut chunks along the code outline where the language has a symbol extractor
+        let mut chunks = match self.symbol_extractors.get(language) {
+            Some(extractor) => {
+                match self.create_symbol_chunks(
+                    file_path,
+                    content,
+                    &tree,
                     language,
-                    chunk_type,
-                    e,
-                    query_str
-                );
-                tracing::error!("{}", error_msg);
-                SemanticError::TreeSitter(error_msg)
-            })?;
-
-            let mut cursor = QueryCursor::new();
-            let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
-
-            let mut query_matches = 0;
-            while let Some(query_match) = matches.next() {
-                for capture in query_match.captures {
-                    let node = capture.node;
-                    let chunk = self.create_chunk_from_node(
-                        file_path,
-                        content,
-                        node,
-                        language.clone(),
-                        chunk_type.clone(),
-                        content_hash.clone(),
-                    )?;
-                    chunks.push(chunk);
-                    query_matches += 1;
+                    extractor.as_ref(),
+                    &content_hash,
+                ) {
+                    Ok(chunks) => chunks,
+                    Err(e) => {
+                        tracing::warn!("{}. Falling back to chunking queries.", e);
+                        Vec::new()
+                    }
                 }
             }
+            None => Vec::new(),
+        };
+        total_matches += chunks.len();
 
-            if query_matches > 0 {
-                tracing::debug!(
-                    "Query extracted {} chunks for {:?} type from {}",
-                    query_matches,
-                    chunk_type,
-                    file_path.display()
-                );
+        // Otherwise extract semantic chunks using queries
+        if chunks.is_empty() {
+            let queries = self.get_queries_for_language(language);
+            for (query_str, chunk_type) in queries {
+                let query = Query::new(&tree.language(), query_str).map_err(|e| {
+                    let error_msg = format!(
+                        "Invalid TreeSitter query for {} (language: {:?}, chunk type: {:?}): {}\
+                             \nQuery pattern: {}\n\
+                             This indicates the query pattern doesn't match the language grammar.",
+                        file_path.display(),
+                        language,
+                        chunk_type,
+                        e,
+                        query_str
+                    );
+                    tracing::error!("{}", error_msg);
+                    SemanticError::TreeSitter(error_msg)
+                })?;
+
+                let mut cursor = QueryCursor::new();
+                let mut matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+
+                let mut query_matches = 0;
+                while let Some(query_match) = matches.next() {
+                    for capture in query_match.captures {
+                        let node = capture.node;
+                        let chunk = self.create_chunk_from_node(
+                            file_path,
+                            content,
+                            node,
+                            language.clone(),
+                            chunk_type.clone(),
+                            content_hash.clone(),
+                        )?;
+                        chunks.push(chunk);
+                        query_matches += 1;
+                    }
+                }
+
+                if query_matches > 0 {
+                    tracing::debug!(
+                        "Query extracted {} chunks for {:?} type from {}",
+                        query_matches,
+                        chunk_type,
+                        file_path.display()
+                    );
+                }
+                total_matches += query_matches;
             }
-            total_matches += query_matches;
         }
         let query_duration = query_start.elapsed();
 
@@ -796,9 +995,161 @@ impl CodeParser {
             end_line: end_pos.row + 1,
             chunk_type,
             content_hash,
+            symbol: None,
         })
     }
 
+    /// Create one chunk per symbol of the code outline
+    ///
+    /// Each chunk records the symbol's name, kind, signature and docstring, and
+    /// its path from the module root, such as `git::GitOperations::new` for the
+    /// `new` method of an `impl GitOperations` block in `src/git/mod.rs`.
+    /// Functions inside impls, traits and classes are recorded as methods.
+    fn create_symbol_chunks(
+        &self,
+        file_path: &Path,
+        content: &str,
+        tree: &Tree,
+        language: &Language,
+        extractor: &dyn SymbolExtractor,
+        content_hash: &ContentHash,
+    ) -> Result<Vec<CodeChunk>> {
+        let symbols = extractor.extract_symbols(tree, content).map_err(|e| {
+            SemanticError::TreeSitter(format!(
+                "Failed to extract symbols from {}: {e}",
+                file_path.display()
+            ))
+        })?;
+
+        // Extractors may nest symbols; containment is worked out from byte ranges instead
+        let mut nodes = Vec::new();
+        flatten_symbols(symbols, &mut nodes);
+        nodes.sort_by_key(|node| (node.source_range.0, std::cmp::Reverse(node.source_range.1)));
+        nodes.dedup_by(|node, previous| node.source_range == previous.source_range);
+
+        let separator = symbol_separator(language);
+        let module = module_path(file_path, language);
+        // Enclosing symbols of the current node: end byte, path segment and kind
+        let mut enclosing: Vec<(usize, Option<String>, OutlineNodeType)> = Vec::new();
+        let mut chunks = Vec::with_capacity(nodes.len());
+
+        let mut nodes = nodes.into_iter().peekable();
+        while let Some(node) = nodes.next() {
+            let (start_byte, end_byte) = node.source_range;
+            while enclosing
+                .last()
+                .is_some_and(|(enclosing_end, _, _)| end_byte > *enclosing_end)
+            {
+                enclosing.pop();
+            }
+
+            // Locals and imports inside function bodies are not worth a chunk of their own
+            let in_function = enclosing.last().is_some_and(|(_, _, enclosing_kind)| {
+                matches!(
+                    enclosing_kind,
+                    OutlineNodeType::Function | OutlineNodeType::Method
+                )
+            });
+            if in_function
+                && matches!(
+                    node.node_type,
+                    OutlineNodeType::Variable
+                        | OutlineNodeType::Constant
+                        | OutlineNodeType::Property
+                        | OutlineNodeType::Import
+                )
+            {
+                continue;
+            }
+
+            let kind = match (&node.node_type, enclosing.last()) {
+                (
+                    OutlineNodeType::Function,
+                    Some((
+                        _,
+                        _,
+                        OutlineNodeType::Impl
+                        | OutlineNodeType::Trait
+                        | OutlineNodeType::Class
+                        | OutlineNodeType::Struct
+                        | OutlineNodeType::Enum
+                        | OutlineNodeType::Interface,
+                    )),
+                ) => OutlineNodeType::Method,
+                (node_type, _) => node_type.clone(),
+            };
+            let segment = match kind {
+                OutlineNodeType::Impl => impl_target(&node.name).map(str::to_string),
+                // Go methods are declared beside their receiver type rather than inside it
+                OutlineNodeType::Method if *language == Language::Go => Some(
+                    node.signature
+                        .as_deref()
+                        .and_then(crate::outline::extractors::go::receiver_type)
+                        .map_or_else(
+                            || node.name.clone(),
+                            |receiver| format!("{receiver}.{}", node.name),
+                        ),
+                ),
+                _ => Some(node.name.clone()),
+            };
+            let qualified_path = module
+                .iter()
+                .cloned()
+                .chain(
+                    enclosing
+                        .iter()
+                        .filter_map(|(_, segment, _)| segment.clone()),
+                )
+                .chain(segment.clone())
+                .collect::<Vec<_>>()
+                .join(separator);
+
+            // A heading section's own text stops at its first subsection, which is chunked
+            // separately, so long documents do not end up as one oversized chunk
+            let (chunk_end, end_line) = match nodes.peek() {
+                Some(next)
+                    if node.node_type == OutlineNodeType::Section
+                        && next.source_range.0 < end_byte =>
+                {
+                    let own_text = content.get(start_byte..next.source_range.0).unwrap_or("");
+                    // The subsection's heading starts a line, so the own text ends with a newline
+                    let end_line =
+                        node.start_line + own_text.matches('\n').count().saturating_sub(1);
+                    (next.source_range.0, end_line)
+                }
+                _ => (end_byte, node.end_line),
+            };
+
+            if let Some(chunk_content) = content.get(start_byte..chunk_end) {
+                chunks.push(CodeChunk {
+                    id: format!(
+                        "{}:{}:{}",
+                        file_path.display(),
+                        node.start_line,
+                        qualified_path
+                    ),
+                    file_path: file_path.to_path_buf(),
+                    language: language.clone(),
+                    content: chunk_content.to_string(),
+                    start_line: node.start_line,
+                    end_line,
+                    chunk_type: ChunkType::from(&kind),
+                    content_hash: content_hash.clone(),
+                    symbol: Some(ChunkSymbol {
+                        name: node.name,
+                        qualified_path,
+                        kind: kind.clone(),
+                        signature: node.signature,
+                        docstring: node.documentation,
+                    }),
+                });
+            }
+            enclosing.push((end_byte, segment, kind));
+        }
+
+        Ok(chunks)
+    }
+
     fn parse_as_plain_text(&self, file_path: &Path, content: &str) -> Result<Vec<CodeChunk>> {
         let content_hash = FileHasher::hash_string(content);
         let language = self.detect_language(file_path);
@@ -830,6 +1181,7 @@ impl CodeParser {
             end_line: line_count.max(1),
             chunk_type: ChunkType::PlainText,
             content_hash: content_hash.clone(),
+            symbol: None,
         })
     }
 
@@ -958,6 +1310,113 @@ impl CodeParser {
     }
 }
 
+/// Move symbols and their children, at any depth, into a flat list
+fn flatten_symbols(symbols: Vec<OutlineNode>, flat: &mut Vec<OutlineNode>) {
+    for mut symbol in symbols {
+        let children = std::mem::take(&mut symbol.children);
+        flat.push(symbol);
+        flatten_symbols(children.into_iter().map(|child| *child).collect(), flat);
+    }
+}
+
+/// Separator between the segments of a qualified symbol path
+fn symbol_separator(language: &Language) -> &'static str {
+    match language {
+        Language::Rust | Language::Cpp | Language::Ruby => "::",
+        Language::Markdown => " > ",
+        _ => ".",
+    }
+}
+
+/// Module path of a source file, the first segments of its symbols' paths
+///
+/// For Rust this is the path of the file below `src`, so `src/git/mod.rs` is
+/// `git` and `src/lib.rs` is the crate root. Other languages qualify symbols
+/// within their file only, like Python's `__qualname__`.
+fn module_path(file_path: &Path, language: &Language) -> Vec<String> {
+    if *language != Language::Rust {
+        return Vec::new();
+    }
+
+    let components: Vec<String> = file_path
+        .components()
+        .filter_map(|component| match component {
+            std::path::Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    let Some(src) = components.iter().rposition(|component| component == "src") else {
+        return Vec::new();
+    };
+
+    let mut module: Vec<String> = components[src + 1..].to_vec();
+    if let Some(file_name) = module.pop() {
+        match file_name.as_str() {
+            "mod.rs" | "lib.rs" | "main.rs" => {}
+            _ => module.push(file_name.trim_end_matches(".rs").to_string()),
+        }
+    }
+    module
+}
+
+/// Type an impl block implements, `Foo` for both `impl Foo<T>` and `impl Display for Foo`
+///
+/// The impl's own generic parameters come first and may nest brackets or
+/// contain `for<'a>` bounds, so only a `for` outside all brackets separates
+/// the trait from the target.
+fn impl_target(impl_name: &str) -> Option<&str> {
+    let rest = impl_name.trim_start();
+    let rest = rest.strip_prefix("impl").unwrap_or(rest).trim_start();
+    let rest = match rest.strip_prefix('<') {
+        Some(params) => &params[closing_bracket(params)? + 1..],
+        None => rest,
+    };
+
+    let mut depth = 0usize;
+    let mut target_start = 0;
+    let mut target_end = rest.len();
+    for (index, c) in rest.char_indices() {
+        match c {
+            '>' if rest[..index].ends_with('-') => {}
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => {
+                let tail = &rest[index..];
+                if tail.starts_with(" for ") {
+                    target_start = index + " for ".len();
+                } else if tail.starts_with(" where ") || tail.starts_with(" where\n") {
+                    target_end = index;
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let target = &rest[target_start..target_end];
+    let target = target.split('<').next().unwrap_or(target).trim();
+    (!target.is_empty()).then_some(target)
+}
+
+/// Byte index of the `>` closing a generic list whose `<` precedes `text`
+fn closing_bracket(text: &str) -> Option<usize> {
+    let mut depth = 1usize;
+    for (index, c) in text.char_indices() {
+        match c {
+            '<' => depth += 1,
+            // `->` in a closure bound is not a bracket
+            '>' if !text[..index].ends_with('-') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -991,6 +1450,24 @@ mod tests {
             parser.detect_language(Path::new("test.dart")),
             Language::Dart
         );
+        assert_eq!(parser.detect_language(Path::new("test.go")), Language::Go);
+        assert_eq!(
+            parser.detect_language(Path::new("Test.java")),
+            Language::Java
+        );
+        assert_eq!(parser.detect_language(Path::new("test.c")), Language::C);
+        assert_eq!(parser.detect_language(Path::new("test.h")), Language::C);
+        assert_eq!(parser.detect_language(Path::new("test.cpp")), Language::Cpp);
+        assert_eq!(parser.detect_language(Path::new("test.hpp")), Language::Cpp);
+        assert_eq!(
+            parser.detect_language(Path::new("Test.cs")),
+            Language::CSharp
+        );
+        assert_eq!(parser.detect_language(Path::new("test.rb")), Language::Ruby);
+        assert_eq!(
+            parser.detect_language(Path::new("README.md")),
+            Language::Markdown
+        );
         assert_eq!(
             parser.detect_language(Path::new("test.txt")),
             Language::Unknown
@@ -1004,6 +1481,9 @@ mod tests {
 
         assert!(parser.is_supported_file(Path::new("main.rs")));
         assert!(parser.is_supported_file(Path::new("script.py")));
+        assert!(parser.is_supported_file(Path::new("server.go")));
+        assert!(parser.is_supported_file(Path::new("App.java")));
+        assert!(parser.is_supported_file(Path::new("docs/guide.md")));
         assert!(!parser.is_supported_file(Path::new("readme.txt")));
     }
 
@@ -1082,6 +1562,178 @@ mod tests {
         assert_eq!(chunks[0].content, content);
     }
 
+    #[test]
+    fn test_symbol_chunks_carry_outline_information() {
+        let config = ParserConfig {
+            min_chunk_size: 1,
+            max_chunk_size: 2000,
+            max_chunks_per_file: 100,
+            max_file_size_bytes: 10 * 1024 * 1024,
+        };
+        let parser = CodeParser::new(config).unwrap();
+
+        let content = r#"/// Runs git commands
+pub struct GitOperations {
+    root: String,
+}
+
+impl GitOperations {
+    /// Create operations for a repository
+    pub fn new(root: String) -> Self {
+        Self { root }
+    }
+}
+
+pub trait Branching {
+    fn current_branch(&self) -> String {
+        String::new()
+    }
+}
+"#;
+        let chunks = parser
+            .parse_file(Path::new("crate/src/git/mod.rs"), content)
+            .unwrap();
+        let symbol = |path: &str| {
+            chunks
+                .iter()
+                .find_map(|chunk| chunk.symbol.as_ref().filter(|s| s.qualified_path == path))
+                .unwrap_or_else(|| panic!("no chunk for {path}"))
+        };
+
+        let new = symbol("git::GitOperations::new");
+        assert_eq!(new.name, "new");
+        assert_eq!(new.kind, OutlineNodeType::Method);
+        assert!(new.signature.as_deref().unwrap().contains("new("));
+        assert!(new
+            .docstring
+            .as_deref()
+            .unwrap()
+            .contains("Create operations for a repository"));
+
+        assert_eq!(symbol("git::GitOperations").kind, OutlineNodeType::Struct);
+        assert_eq!(symbol("git::Branching").kind, OutlineNodeType::Trait);
+        assert_eq!(
+            symbol("git::Branching::current_branch").kind,
+            OutlineNodeType::Method
+        );
+
+        let method_chunk = chunks
+            .iter()
+            .find(|chunk| chunk.id.ends_with(":git::GitOperations::new"))
+            .unwrap();
+        assert_eq!(method_chunk.chunk_type, ChunkType::Function);
+        assert!(method_chunk.content.starts_with("pub fn new"));
+        assert_eq!(method_chunk.start_line, 8);
+    }
+
+    #[test]
+    fn test_python_symbol_paths() {
+        let parser =
+            CodeParser::new(ParserConfig::new(1, 2000, 100, 1024 * 1024).unwrap()).unwrap();
+        let content = "class Greeter:\n    def greet(self, name):\n        message = 'hi ' + name\n        return message\n";
+
+        let chunks = parser.parse_file(Path::new("greeter.py"), content).unwrap();
+        let paths: Vec<(&str, &OutlineNodeType)> = chunks
+            .iter()
+            .filter_map(|chunk| chunk.symbol.as_ref())
+            .map(|symbol| (symbol.qualified_path.as_str(), &symbol.kind))
+            .collect();
+
+        assert!(paths.contains(&("Greeter", &OutlineNodeType::Class)));
+        assert!(paths.contains(&("Greeter.greet", &OutlineNodeType::Method)));
+        // Locals of a function are part of its chunk
+        assert!(!paths.iter().any(|(path, _)| path.ends_with("message")));
+    }
+
+    #[test]
+    fn test_go_method_paths() {
+        let parser =
+            CodeParser::new(ParserConfig::new(1, 2000, 100, 1024 * 1024).unwrap()).unwrap();
+        let content = "package server\n\ntype Server struct {\n\tAddr string\n}\n\nfunc (s *Server) Start() error {\n\treturn nil\n}\n\nfunc New() *Server {\n\treturn &Server{}\n}\n";
+
+        let chunks = parser.parse_file(Path::new("server.go"), content).unwrap();
+        let paths: Vec<(&str, &OutlineNodeType)> = chunks
+            .iter()
+            .filter_map(|chunk| chunk.symbol.as_ref())
+            .map(|symbol| (symbol.qualified_path.as_str(), &symbol.kind))
+            .collect();
+
+        assert!(paths.contains(&("Server", &OutlineNodeType::Struct)));
+        assert!(paths.contains(&("Server.Start", &OutlineNodeType::Method)));
+        assert!(paths.contains(&("New", &OutlineNodeType::Function)));
+    }
+
+    #[test]
+    fn test_markdown_section_chunks() {
+        let parser =
+            CodeParser::new(ParserConfig::new(1, 2000, 100, 1024 * 1024).unwrap()).unwrap();
+        let content =
+            "# Guide\n\nIntro text.\n\n## Install\n\nRun cargo install.\n\n## Usage\n\nRun it.\n";
+
+        let chunks = parser
+            .parse_file(Path::new("docs/guide.md"), content)
+            .unwrap();
+        let sections: Vec<(&str, usize, usize)> = chunks
+            .iter()
+            .filter(|chunk| chunk.chunk_type == ChunkType::Section)
+            .filter_map(|chunk| {
+                let symbol = chunk.symbol.as_ref()?;
+                Some((
+                    symbol.qualified_path.as_str(),
+                    chunk.start_line,
+                    chunk.end_line,
+                ))
+            })
+            .collect();
+
+        assert_eq!(
+            sections,
+            vec![
+                ("Guide", 1, 4),
+                ("Guide > Install", 5, 8),
+                ("Guide > Usage", 9, 11),
+            ]
+        );
+
+        // A section's chunk holds its own text, not that of its subsections
+        let guide = chunks.iter().find(|chunk| chunk.start_line == 1).unwrap();
+        assert!(guide.content.contains("Intro text."));
+        assert!(!guide.content.contains("## Install"));
+    }
+
+    #[test]
+    fn test_module_path_and_impl_target() {
+        assert_eq!(
+            module_path(
+                Path::new("swissarmyhammer/src/git/operations.rs"),
+                &Language::Rust
+            ),
+            vec!["git", "operations"]
+        );
+        assert_eq!(
+            module_path(Path::new("./src/search/mod.rs"), &Language::Rust),
+            vec!["search"]
+        );
+        assert!(module_path(Path::new("src/lib.rs"), &Language::Rust).is_empty());
+        assert!(module_path(Path::new("tests/cli.rs"), &Language::Rust).is_empty());
+        assert!(module_path(Path::new("src/app/main.py"), &Language::Python).is_empty());
+
+        assert_eq!(impl_target("impl GitOperations"), Some("GitOperations"));
+        assert_eq!(impl_target("impl Display for Chunk"), Some("Chunk"));
+        assert_eq!(impl_target("impl Cache<K, V>"), Some("Cache"));
+        assert_eq!(impl_target("impl<T> Foo<T>"), Some("Foo"));
+        assert_eq!(impl_target("impl<T> Display for Foo<T>"), Some("Foo"));
+        assert_eq!(
+            impl_target("impl<K: Ord, V: Into<Vec<u8>>> Cache<K, V>"),
+            Some("Cache")
+        );
+        assert_eq!(
+            impl_target("impl<F: for<'a> Fn(&'a str) -> bool> Filter<F> where F: Send"),
+            Some("Filter")
+        );
+        assert_eq!(impl_target("impl"), None);
+    }
+
     #[test]
     fn test_chunk_ids_are_unique() {
         let config = ParserConfig::default();
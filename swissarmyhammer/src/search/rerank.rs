@@ -0,0 +1,182 @@
+//! Cross-encoder reranking of search results
+//!
+//! Vector and lexical search score the query and each chunk separately. A
+//! cross-encoder reads the query and a chunk together and judges how well the
+//! chunk answers the query, which is slower but noticeably more precise. It
+//! runs locally through ONNX Runtime, so hybrid search can rerank its top
+//! candidates without calling out to a service.
+
+use crate::search::{Result, SemanticError};
+use ort::session::{builder::GraphOptimizationLevel, Session, SessionInputValue};
+use ort::value::Tensor;
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tokenizers::{Encoding, PaddingParams, Tokenizer, TruncationParams};
+use tokio::sync::OnceCell;
+
+/// Hugging Face model used for reranking by default
+pub const DEFAULT_RERANK_MODEL: &str = "cross-encoder/ms-marco-MiniLM-L6-v2";
+
+/// Environment variable naming a model directory or Hugging Face model id
+///
+/// A directory must hold `model.onnx` and `tokenizer.json`; a model id must
+/// publish `onnx/model.onnx` and `tokenizer.json`.
+pub const RERANK_MODEL_ENV_VAR: &str = "SWISSARMYHAMMER_RERANK_MODEL";
+
+/// Longest query and chunk pair passed to the model, in tokens
+const MAX_PAIR_TOKENS: usize = 512;
+
+/// Pairs scored in one model run
+const RERANK_BATCH_SIZE: usize = 16;
+
+static SHARED_RERANKER: OnceCell<Arc<CrossEncoderReranker>> = OnceCell::const_new();
+
+/// A cross-encoder model scoring how well passages answer a query
+pub struct CrossEncoderReranker {
+    session: Mutex<Session>,
+    tokenizer: Tokenizer,
+    uses_token_type_ids: bool,
+}
+
+impl CrossEncoderReranker {
+    /// Load a model from an ONNX file and its `tokenizer.json`
+    pub fn from_files(model_path: &Path, tokenizer_path: &Path) -> Result<Self> {
+        let mut tokenizer = Tokenizer::from_file(tokenizer_path).map_err(|e| {
+            SemanticError::Config(format!(
+                "Failed to load reranker tokenizer from {}: {e}",
+                tokenizer_path.display()
+            ))
+        })?;
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: MAX_PAIR_TOKENS,
+                ..Default::default()
+            }))
+            .map_err(|e| SemanticError::Config(format!("Invalid reranker truncation: {e}")))?;
+        tokenizer.with_padding(Some(PaddingParams::default()));
+
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .commit_from_file(model_path)?;
+        let uses_token_type_ids = session
+            .inputs
+            .iter()
+            .any(|input| input.name == "token_type_ids");
+
+        tracing::info!("Loaded reranker model from {}", model_path.display());
+        Ok(Self {
+            session: Mutex::new(session),
+            tokenizer,
+            uses_token_type_ids,
+        })
+    }
+
+    /// Load a model published on Hugging Face, downloading it if not cached
+    pub async fn from_pretrained(model_id: &str) -> Result<Self> {
+        let api = hf_hub::api::tokio::Api::new().map_err(|e| {
+            SemanticError::Config(format!("Failed to access Hugging Face hub: {e}"))
+        })?;
+        let repo = api.model(model_id.to_string());
+        let model_path = repo.get("onnx/model.onnx").await.map_err(|e| {
+            SemanticError::Config(format!("Failed to download reranker {model_id}: {e}"))
+        })?;
+        let tokenizer_path = repo.get("tokenizer.json").await.map_err(|e| {
+            SemanticError::Config(format!(
+                "Failed to download tokenizer for reranker {model_id}: {e}"
+            ))
+        })?;
+        Self::from_files(&model_path, &tokenizer_path)
+    }
+
+    /// The reranker shared by the whole process
+    ///
+    /// Uses `SWISSARMYHAMMER_RERANK_MODEL` if set and [`DEFAULT_RERANK_MODEL`]
+    /// otherwise. A failed load is not cached, so a later call can retry.
+    pub async fn shared() -> Result<Arc<Self>> {
+        SHARED_RERANKER
+            .get_or_try_init(|| async {
+                let source = std::env::var(RERANK_MODEL_ENV_VAR)
+                    .unwrap_or_else(|_| DEFAULT_RERANK_MODEL.to_string());
+                let directory = Path::new(&source);
+                let reranker = if directory.is_dir() {
+                    Self::from_files(
+                        &directory.join("model.onnx"),
+                        &directory.join("tokenizer.json"),
+                    )?
+                } else {
+                    Self::from_pretrained(&source).await?
+                };
+                Ok::<_, SemanticError>(Arc::new(reranker))
+            })
+            .await
+            .cloned()
+    }
+
+    /// Relevance of each passage to the query, between 0.0 and 1.0
+    ///
+    /// Scores are returned in the order of `passages`.
+    pub fn score(&self, query: &str, passages: &[&str]) -> Result<Vec<f32>> {
+        let mut scores = Vec::with_capacity(passages.len());
+        for batch in passages.chunks(RERANK_BATCH_SIZE) {
+            scores.extend(self.score_batch(query, batch)?);
+        }
+        Ok(scores)
+    }
+
+    fn score_batch(&self, query: &str, passages: &[&str]) -> Result<Vec<f32>> {
+        let pairs: Vec<(&str, &str)> = passages.iter().map(|passage| (query, *passage)).collect();
+        let encodings = self.tokenizer.encode_batch(pairs, true).map_err(|e| {
+            SemanticError::Embedding(format!("Failed to tokenize for reranking: {e}"))
+        })?;
+        let width = encodings.first().map_or(0, |encoding| encoding.len());
+        if width == 0 {
+            return Ok(vec![0.0; passages.len()]);
+        }
+
+        let shape = [encodings.len(), width];
+        let mut inputs: Vec<(Cow<'_, str>, SessionInputValue<'_>)> = vec![
+            (
+                "input_ids".into(),
+                Tensor::from_array((shape, flatten(&encodings, Encoding::get_ids)))?.into(),
+            ),
+            (
+                "attention_mask".into(),
+                Tensor::from_array((shape, flatten(&encodings, Encoding::get_attention_mask)))?
+                    .into(),
+            ),
+        ];
+        if self.uses_token_type_ids {
+            inputs.push((
+                "token_type_ids".into(),
+                Tensor::from_array((shape, flatten(&encodings, Encoding::get_type_ids)))?.into(),
+            ));
+        }
+
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|e| SemanticError::Embedding(format!("Reranker lock poisoned: {e}")))?;
+        let outputs = session.run(inputs)?;
+        let (_, logits) = outputs[0].try_extract_tensor::<f32>()?;
+
+        // One logit per pair; models with several labels put relevance last
+        let labels = logits.len() / passages.len().max(1);
+        Ok(logits
+            .chunks(labels.max(1))
+            .map(|row| sigmoid(row[row.len() - 1]))
+            .collect())
+    }
+}
+
+/// One row per encoding of `values`, padded encodings being the same length
+pub(crate) fn flatten(encodings: &[Encoding], values: fn(&Encoding) -> &[u32]) -> Vec<i64> {
+    encodings
+        .iter()
+        .flat_map(|encoding| values(encoding).iter().map(|&value| value as i64))
+        .collect()
+}
+
+fn sigmoid(logit: f32) -> f32 {
+    1.0 / (1.0 + (-logit).exp())
+}
@@ -40,17 +40,103 @@
 //!     limit: 20,
 //!     similarity_threshold: 0.8,
 //!     language_filter: None,
+//!     symbol_filter: None,
 //! };
 //! let results = searcher.search(&query).await?;
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! ## Hybrid Search
+//!
+//! Embeddings miss exact identifiers, so [`SemanticSearcher::search_with_mode`]
+//! can also rank chunks with the BM25 lexical index, or fuse both rankings with
+//! reciprocal rank fusion. A [`CrossEncoderReranker`] can rescore the fused
+//! candidates before they are cut to the requested limit.
+//!
+//! ```rust,no_run
+//! use swissarmyhammer::search::{CrossEncoderReranker, SearchMode, SearchQuery, SemanticSearcher};
+//!
+//! # async fn example(searcher: SemanticSearcher) -> Result<(), Box<dyn std::error::Error>> {
+//! let searcher = searcher.with_reranker(CrossEncoderReranker::shared().await?);
+//! let query = SearchQuery {
+//!     text: "validate_branch_name".to_string(),
+//!     limit: 10,
+//!     similarity_threshold: 0.5,
+//!     language_filter: None,
+//!     symbol_filter: None,
+//! };
+//! let results = searcher.search_with_mode(&query, SearchMode::Hybrid).await?;
+//! # Ok(())
+//! # }
+//! ```
 
 use crate::search::{
-    CodeChunk, EmbeddingEngine, Language, Result, ResultExplanation, SearchExplanation,
-    SearchQuery, SearchStats, SemanticConfig, SemanticSearchResult, VectorStorage,
+    CodeChunk, CrossEncoderReranker, EmbeddingEngine, Language, Result, ResultExplanation,
+    SearchExplanation, SearchMode, SearchQuery, SearchStats, SemanticConfig, SemanticSearchResult,
+    SemanticUtils, VectorStorage,
 };
 use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Damping constant for reciprocal rank fusion of the search signals
+const HYBRID_RRF_K: f32 = 60.0;
+
+/// Candidates taken from each signal, and reranked, per requested result
+const CANDIDATES_PER_RESULT: usize = 4;
+
+/// A chunk with the scores each search signal gave it
+struct RankedChunk {
+    chunk: CodeChunk,
+    vector_score: Option<f32>,
+    vector_rank: Option<usize>,
+    lexical_score: Option<f32>,
+    lexical_rank: Option<usize>,
+    /// Reciprocal rank fusion score, scaled so first place in every signal is 1.0
+    fused_score: f32,
+    rerank_score: Option<f32>,
+}
+
+impl RankedChunk {
+    /// The score the chunk is finally ranked by
+    fn score(&self) -> f32 {
+        self.rerank_score.unwrap_or(self.fused_score)
+    }
+}
+
+/// Take the best `limit` candidates of a signal that pass the query's filters
+///
+/// Chunks of other languages, or without a matching symbol, would otherwise
+/// use up the candidates, so the fetch is widened until enough match or the
+/// signal has no more to give.
+fn matching_candidates<T>(
+    limit: usize,
+    filtered: bool,
+    mut fetch: impl FnMut(usize) -> Result<Vec<T>>,
+    mut accepts: impl FnMut(&T) -> Result<bool>,
+) -> Result<Vec<T>> {
+    if !filtered {
+        return fetch(limit);
+    }
+    let mut fetch_limit = limit;
+    loop {
+        let fetched = fetch(fetch_limit)?;
+        let exhausted = fetched.len() < fetch_limit;
+        let mut kept = Vec::with_capacity(limit);
+        for candidate in fetched {
+            if kept.len() == limit {
+                break;
+            }
+            if accepts(&candidate)? {
+                kept.push(candidate);
+            }
+        }
+        if kept.len() == limit || exhausted {
+            return Ok(kept);
+        }
+        fetch_limit *= CANDIDATES_PER_RESULT;
+    }
+}
 
 /// Semantic searcher for querying indexed code using vector embeddings.
 ///
@@ -90,10 +176,11 @@ pub struct SemanticSearcher {
     storage: VectorStorage,
     embedding_engine: EmbeddingEngine,
     config: SemanticConfig,
+    reranker: Option<Arc<CrossEncoderReranker>>,
 }
 
 impl SemanticSearcher {
-    /// Create a new semantic searcher with default embedding engine.
+    /// Create a new semantic searcher with the embedding engine configured in `sah.toml`.
     ///
     /// This constructor initializes a new embedding engine, which may take some time
     /// as it needs to load the model. For better performance when creating multiple
@@ -124,12 +211,13 @@ impl SemanticSearcher {
     ///
     /// [`with_embedding_engine`]: Self::with_embedding_engine
     pub async fn new(storage: VectorStorage, config: SemanticConfig) -> Result<Self> {
-        let embedding_engine = EmbeddingEngine::new().await?;
+        let embedding_engine = EmbeddingEngine::from_configuration().await?;
 
         Ok(Self {
             storage,
             embedding_engine,
             config,
+            reranker: None,
         })
     }
 
@@ -173,9 +261,22 @@ impl SemanticSearcher {
             storage,
             embedding_engine,
             config,
+            reranker: None,
         })
     }
 
+    /// Rerank the results of [`search_with_mode`] with a cross-encoder.
+    ///
+    /// The top candidates of every mode, including plain semantic search, are
+    /// rescored by the model before they are cut to the requested limit, and
+    /// their scores become the cross-encoder's relevance.
+    ///
+    /// [`search_with_mode`]: Self::search_with_mode
+    pub fn with_reranker(mut self, reranker: Arc<CrossEncoderReranker>) -> Self {
+        self.reranker = Some(reranker);
+        self
+    }
+
     /// Perform semantic search with a detailed query specification.
     ///
     /// This is the main search method that accepts a [`SearchQuery`] with full control
@@ -209,6 +310,7 @@ impl SemanticSearcher {
     ///     limit: 15,
     ///     similarity_threshold: 0.75,
     ///     language_filter: Some(Language::Rust),
+    ///     symbol_filter: None,
     /// };
     ///
     /// let results = searcher.search(&query).await?;
@@ -226,44 +328,308 @@ impl SemanticSearcher {
         tracing::debug!("Performing semantic search for: '{}'", query.text);
 
         // Generate embedding for the query
-        let query_embedding = self.embedding_engine.embed_text(&query.text).await?;
+        let query_embedding = self.embed_query(&query.text).await?;
 
         // Find similar embeddings in the database
-        let similar_chunk_ids = self
-            .storage
-            .similarity_search(&query_embedding, query.limit, query.similarity_threshold)
-            .map_err(|e| crate::search::SemanticError::VectorStorage {
-                operation: "similarity search".to_string(),
-                source: Box::new(e),
-            })?;
+        let similar_chunk_ids = matching_candidates(
+            query.limit,
+            query.is_filtered(),
+            |limit| self.similarity_search(&query_embedding, limit, query.similarity_threshold),
+            |result| Ok(query.accepts(&result.chunk)),
+        )?;
 
         if similar_chunk_ids.is_empty() {
             tracing::info!("No results found for query: '{}'", query.text);
             return Ok(Vec::new());
         }
 
-        // Apply language filter and create excerpts
+        // Create excerpts
         let mut results = Vec::new();
         for mut result in similar_chunk_ids {
-            // Apply language filter if specified
-            if let Some(ref language_filter) = query.language_filter {
-                if result.chunk.language != *language_filter {
-                    continue;
-                }
-            }
-
-            // Create excerpt for this result
             result.excerpt = self.create_excerpt(&result.chunk, &query.text);
             results.push(result);
         }
 
         // Sort by similarity score (highest first) - already sorted by storage, but ensure consistency
         results.sort_by(|a, b| b.similarity_score.partial_cmp(&a.similarity_score).unwrap());
+        results.truncate(query.limit);
 
         tracing::info!("Found {} results for query", results.len());
         Ok(results)
     }
 
+    /// Search using embeddings, the lexical index, or both.
+    ///
+    /// [`SearchMode::Semantic`] is the same as [`search`](Self::search).
+    /// [`SearchMode::Lexical`] ranks chunks by BM25 over their text and
+    /// identifiers, so exact names like `validate_branch_name` come first.
+    /// [`SearchMode::Hybrid`] takes candidates from both and merges the two
+    /// rankings with reciprocal rank fusion.
+    ///
+    /// Lexical and hybrid results carry the fused rank score as their
+    /// `similarity_score`: 1.0 for a chunk every signal ranked first. When a
+    /// reranker is set, results carry its relevance score instead.
+    pub async fn search_with_mode(
+        &self,
+        query: &SearchQuery,
+        mode: SearchMode,
+    ) -> Result<Vec<SemanticSearchResult>> {
+        if mode == SearchMode::Semantic && self.reranker.is_none() {
+            return self.search(query).await;
+        }
+
+        tracing::debug!("Performing {} search for: '{}'", mode, query.text);
+        let (ranked, _) = self.rank(query, mode).await?;
+        let results: Vec<SemanticSearchResult> = ranked
+            .into_iter()
+            .map(|ranked| self.ranked_result(ranked, query, mode))
+            .collect();
+
+        tracing::info!("Found {} results for {} query", results.len(), mode);
+        Ok(results)
+    }
+
+    /// Search like [`search_with_mode`] and explain each result, signal by signal.
+    ///
+    /// The explanations come from the same ranking as the results, so their
+    /// scores always belong to the results returned, and every signal is only
+    /// computed once.
+    ///
+    /// [`search_with_mode`]: Self::search_with_mode
+    pub async fn search_with_mode_explained(
+        &self,
+        query: &SearchQuery,
+        mode: SearchMode,
+    ) -> Result<Vec<(SemanticSearchResult, ResultExplanation)>> {
+        tracing::debug!("Performing explained {} search for: '{}'", mode, query.text);
+        let (ranked, _) = self.rank(query, mode).await?;
+        let results: Vec<(SemanticSearchResult, ResultExplanation)> = ranked
+            .into_iter()
+            .map(|ranked| {
+                let explanation = self.explain_ranked(&ranked, query);
+                (self.ranked_result(ranked, query, mode), explanation)
+            })
+            .collect();
+
+        tracing::info!("Found {} results for {} query", results.len(), mode);
+        Ok(results)
+    }
+
+    /// Turn a ranked chunk into a search result
+    ///
+    /// Plain semantic results keep their vector similarity as score, like
+    /// [`search`](Self::search); the other modes carry the fused or reranked score.
+    fn ranked_result(
+        &self,
+        ranked: RankedChunk,
+        query: &SearchQuery,
+        mode: SearchMode,
+    ) -> SemanticSearchResult {
+        let similarity_score = if mode == SearchMode::Semantic && self.reranker.is_none() {
+            ranked.vector_score.unwrap_or(0.0)
+        } else {
+            ranked.score()
+        };
+        SemanticSearchResult {
+            similarity_score,
+            excerpt: self.create_excerpt(&ranked.chunk, &query.text),
+            chunk: ranked.chunk,
+        }
+    }
+
+    /// The scores each signal gave a ranked chunk
+    fn explain_ranked(&self, ranked: &RankedChunk, query: &SearchQuery) -> ResultExplanation {
+        ResultExplanation {
+            chunk_id: ranked.chunk.id.clone(),
+            similarity_score: ranked.vector_score.unwrap_or(0.0),
+            vector_rank: ranked.vector_rank,
+            lexical_score: ranked.lexical_score,
+            lexical_rank: ranked.lexical_rank,
+            fused_score: Some(ranked.fused_score),
+            rerank_score: ranked.rerank_score,
+            language: ranked.chunk.language.clone(),
+            chunk_type: ranked.chunk.chunk_type.clone(),
+            content_preview: ranked
+                .chunk
+                .content
+                .chars()
+                .take(self.config.content_preview_length)
+                .collect(),
+            above_threshold: ranked.lexical_score.is_some()
+                || ranked
+                    .vector_score
+                    .is_some_and(|score| score >= query.similarity_threshold),
+        }
+    }
+
+    /// Embed the query text, if the index holds vectors of the same model
+    ///
+    /// Vectors of different models cannot be compared, so searching an index
+    /// built by another model fails instead of returning meaningless results.
+    async fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+        self.storage
+            .check_embedding_model(&self.embedding_engine.model_info())?;
+        self.embedding_engine.embed_text(text).await
+    }
+
+    /// Find chunks similar to the query embedding in the vector storage
+    fn similarity_search(
+        &self,
+        embedding: &[f32],
+        limit: usize,
+        threshold: f32,
+    ) -> Result<Vec<SemanticSearchResult>> {
+        self.storage
+            .similarity_search(embedding, limit, threshold)
+            .map_err(|e| crate::search::SemanticError::VectorStorage {
+                operation: "similarity search".to_string(),
+                source: Box::new(e),
+            })
+    }
+
+    /// Load a chunk found by the lexical index from the vector storage
+    fn load_chunk(&self, chunk_id: &str) -> Result<Option<CodeChunk>> {
+        self.storage.get_chunk(chunk_id).map_err(|e| {
+            crate::search::SemanticError::SearchOperation {
+                operation: "chunk retrieval for lexical match".to_string(),
+                message: format!("Failed to retrieve chunk {chunk_id}"),
+                source: Some(Box::new(e)),
+            }
+        })
+    }
+
+    /// Rank chunks by the signals of `mode`, fused and optionally reranked
+    ///
+    /// Returns at most `query.limit` chunks, best first, and the query
+    /// embedding if one was computed.
+    async fn rank(
+        &self,
+        query: &SearchQuery,
+        mode: SearchMode,
+    ) -> Result<(Vec<RankedChunk>, Option<Vec<f32>>)> {
+        let candidates = query.limit.max(1) * CANDIDATES_PER_RESULT;
+        let mut rankings: Vec<Vec<String>> = Vec::new();
+        let mut chunks = HashMap::new();
+
+        let mut query_embedding = None;
+        let mut vector_scores = HashMap::new();
+        if mode != SearchMode::Lexical {
+            let embedding = self.embed_query(&query.text).await?;
+            let results = matching_candidates(
+                candidates,
+                query.is_filtered(),
+                |limit| self.similarity_search(&embedding, limit, query.similarity_threshold),
+                |result| Ok(query.accepts(&result.chunk)),
+            )?;
+            let mut ranking = Vec::with_capacity(results.len());
+            for result in results {
+                ranking.push(result.chunk.id.clone());
+                vector_scores.insert(
+                    result.chunk.id.clone(),
+                    (result.similarity_score, ranking.len()),
+                );
+                chunks.insert(result.chunk.id.clone(), result.chunk);
+            }
+            rankings.push(ranking);
+            query_embedding = Some(embedding);
+        }
+
+        let mut lexical_scores = HashMap::new();
+        if mode != SearchMode::Semantic {
+            let results = matching_candidates(
+                candidates,
+                query.is_filtered(),
+                |limit| {
+                    self.storage
+                        .lexical_search(&query.text, limit)
+                        .map_err(|e| crate::search::SemanticError::VectorStorage {
+                            operation: "lexical search".to_string(),
+                            source: Box::new(e),
+                        })
+                },
+                |(chunk_id, _)| {
+                    if !chunks.contains_key(chunk_id) {
+                        match self.load_chunk(chunk_id)? {
+                            Some(chunk) => {
+                                chunks.insert(chunk_id.clone(), chunk);
+                            }
+                            None => return Ok(false),
+                        }
+                    }
+                    Ok(query.accepts(&chunks[chunk_id]))
+                },
+            )?;
+            rankings.push(
+                results
+                    .iter()
+                    .map(|(chunk_id, _)| chunk_id.clone())
+                    .collect(),
+            );
+            lexical_scores.extend(
+                results
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (chunk_id, score))| (chunk_id, (score, index + 1))),
+            );
+        }
+
+        // Only the top candidates are reranked, or returned without a reranker
+        let keep = if self.reranker.is_some() {
+            candidates
+        } else {
+            query.limit
+        };
+        let best_possible = rankings.len() as f32 / (HYBRID_RRF_K + 1.0);
+        let mut ranked = Vec::new();
+        for (chunk_id, score) in SemanticUtils::reciprocal_rank_fusion(&rankings, HYBRID_RRF_K) {
+            if ranked.len() >= keep {
+                break;
+            }
+            let chunk = match chunks.remove(&chunk_id) {
+                Some(chunk) => chunk,
+                // Chunks only the lexical index found are loaded from storage
+                None => match self.load_chunk(&chunk_id)? {
+                    Some(chunk) => chunk,
+                    None => continue,
+                },
+            };
+            ranked.push(RankedChunk {
+                vector_score: vector_scores.get(&chunk_id).map(|(score, _)| *score),
+                vector_rank: vector_scores.get(&chunk_id).map(|(_, rank)| *rank),
+                lexical_score: lexical_scores.get(&chunk_id).map(|(score, _)| *score),
+                lexical_rank: lexical_scores.get(&chunk_id).map(|(_, rank)| *rank),
+                fused_score: score / best_possible,
+                rerank_score: None,
+                chunk,
+            });
+        }
+
+        if let Some(reranker) = &self.reranker {
+            // Inference takes long enough to stall other tasks on this worker
+            let reranker = Arc::clone(reranker);
+            let query_text = query.text.clone();
+            let passages: Vec<String> = ranked
+                .iter()
+                .map(|ranked| ranked.chunk.content.clone())
+                .collect();
+            let scores = tokio::task::spawn_blocking(move || {
+                let passages: Vec<&str> = passages.iter().map(String::as_str).collect();
+                reranker.score(&query_text, &passages)
+            })
+            .await
+            .map_err(|e| {
+                crate::search::SemanticError::Embedding(format!("Reranking task failed: {e}"))
+            })??;
+            for (ranked, score) in ranked.iter_mut().zip(scores) {
+                ranked.rerank_score = Some(score);
+            }
+            ranked.sort_by(|a, b| b.score().total_cmp(&a.score()));
+            ranked.truncate(query.limit);
+        }
+
+        Ok((ranked, query_embedding))
+    }
+
     /// Search with simple text query using default parameters.
     ///
     /// This is a convenience method for basic searches that uses the configured
@@ -304,6 +670,7 @@ impl SemanticSearcher {
             limit,
             similarity_threshold: self.config.simple_search_threshold,
             language_filter: None,
+            symbol_filter: None,
         };
 
         self.search(&query).await
@@ -363,6 +730,7 @@ impl SemanticSearcher {
             limit,
             similarity_threshold: self.config.simple_search_threshold,
             language_filter: Some(language),
+            symbol_filter: None,
         };
 
         self.search(&query).await
@@ -409,6 +777,7 @@ impl SemanticSearcher {
     ///     end_line: 50,
     ///     chunk_type: ChunkType::Function,
     ///     content_hash: ContentHash("hash123".to_string()),
+    ///     symbol: None,
     /// };
     ///
     /// let similar = searcher.find_similar_code(&reference_chunk, 5).await?;
@@ -431,6 +800,7 @@ impl SemanticSearcher {
             limit: limit + 1, // +1 because the original chunk might be included
             similarity_threshold: self.config.code_similarity_threshold,
             language_filter: None, // Don't filter by language for broader results
+            symbol_filter: None,
         };
 
         let mut results = self.search(&query).await?;
@@ -736,6 +1106,7 @@ impl SemanticSearcher {
     ///     limit: 10,
     ///     similarity_threshold: 0.7,
     ///     language_filter: None,
+    ///     symbol_filter: None,
     /// };
     ///
     /// let explanation = searcher.explain_search(&query).await?;
@@ -762,43 +1133,60 @@ impl SemanticSearcher {
     /// # }
     /// ```
     pub async fn explain_search(&self, query: &SearchQuery) -> Result<SearchExplanation> {
-        let query_embedding = self.embedding_engine.embed_text(&query.text).await?;
+        let query_embedding = self.embed_query(&query.text).await?;
 
         // Get detailed similarity results
-        let similar_results = self
-            .storage
-            .similarity_search_with_details(
-                &query_embedding,
-                query.limit,
-                0.0, // Get all results for explanation
-            )
-            .map_err(|e| crate::search::SemanticError::VectorStorage {
-                operation: "detailed similarity search".to_string(),
-                source: Box::new(e),
-            })?;
+        let similar_results = matching_candidates(
+            query.limit,
+            query.is_filtered(),
+            |limit| {
+                let results = self
+                    .storage
+                    .similarity_search_with_details(
+                        &query_embedding,
+                        limit,
+                        0.0, // Get all results for explanation
+                    )
+                    .map_err(|e| crate::search::SemanticError::VectorStorage {
+                        operation: "detailed similarity search".to_string(),
+                        source: Box::new(e),
+                    })?;
+                let mut chunks = Vec::with_capacity(results.len());
+                for (chunk_id, similarity_score, _embedding) in results {
+                    if let Some(chunk) = self.storage.get_chunk(&chunk_id).map_err(|e| {
+                        crate::search::SemanticError::SearchOperation {
+                            operation: "chunk retrieval for explanation".to_string(),
+                            message: format!("Failed to retrieve chunk {chunk_id}"),
+                            source: Some(Box::new(e)),
+                        }
+                    })? {
+                        chunks.push((chunk, similarity_score));
+                    }
+                }
+                Ok(chunks)
+            },
+            |(chunk, _)| Ok(query.accepts(chunk)),
+        )?;
 
         let mut explanations = Vec::new();
-        for (chunk_id, similarity_score, _embedding) in similar_results {
-            if let Some(chunk) = self.storage.get_chunk(&chunk_id).map_err(|e| {
-                crate::search::SemanticError::SearchOperation {
-                    operation: "chunk retrieval for explanation".to_string(),
-                    message: format!("Failed to retrieve chunk {chunk_id}"),
-                    source: Some(Box::new(e)),
-                }
-            })? {
-                explanations.push(ResultExplanation {
-                    chunk_id: chunk_id.clone(),
-                    similarity_score,
-                    language: chunk.language.clone(),
-                    chunk_type: chunk.chunk_type.clone(),
-                    content_preview: chunk
-                        .content
-                        .chars()
-                        .take(self.config.content_preview_length)
-                        .collect(),
-                    above_threshold: similarity_score >= query.similarity_threshold,
-                });
-            }
+        for (index, (chunk, similarity_score)) in similar_results.into_iter().enumerate() {
+            explanations.push(ResultExplanation {
+                chunk_id: chunk.id.clone(),
+                similarity_score,
+                vector_rank: Some(index + 1),
+                lexical_score: None,
+                lexical_rank: None,
+                fused_score: None,
+                rerank_score: None,
+                language: chunk.language.clone(),
+                chunk_type: chunk.chunk_type.clone(),
+                content_preview: chunk
+                    .content
+                    .chars()
+                    .take(self.config.content_preview_length)
+                    .collect(),
+                above_threshold: similarity_score >= query.similarity_threshold,
+            });
         }
 
         Ok(SearchExplanation {
@@ -810,6 +1198,40 @@ impl SemanticSearcher {
         })
     }
 
+    /// Explain how [`search_with_mode`] ranks results, signal by signal.
+    ///
+    /// Semantic searches without a reranker are explained by
+    /// [`explain_search`](Self::explain_search). Otherwise each result shows
+    /// its vector similarity and BM25 score with its place in each ranking, the
+    /// fused rank score and, when reranked, the cross-encoder score, in the
+    /// order the search returns them.
+    ///
+    /// [`search_with_mode`]: Self::search_with_mode
+    pub async fn explain_search_with_mode(
+        &self,
+        query: &SearchQuery,
+        mode: SearchMode,
+    ) -> Result<SearchExplanation> {
+        if mode == SearchMode::Semantic && self.reranker.is_none() {
+            return self.explain_search(query).await;
+        }
+
+        let (ranked, query_embedding) = self.rank(query, mode).await?;
+        let results: Vec<ResultExplanation> = ranked
+            .iter()
+            .map(|ranked| self.explain_ranked(ranked, query))
+            .collect();
+
+        Ok(SearchExplanation {
+            query_text: query.text.clone(),
+            query_embedding_norm: query_embedding
+                .map_or(0.0, |embedding| self.calculate_vector_norm(&embedding)),
+            threshold: query.similarity_threshold,
+            total_candidates: results.len(),
+            results,
+        })
+    }
+
     fn calculate_vector_norm(&self, vector: &[f32]) -> f32 {
         vector.iter().map(|x| x * x).sum::<f32>().sqrt()
     }
@@ -825,7 +1247,10 @@ impl SemanticSearcher {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::search::{ChunkType, ContentHash, Language, SemanticConfig};
+    use crate::outline::OutlineNodeType;
+    use crate::search::{
+        ChunkSymbol, ChunkType, ContentHash, Language, SemanticConfig, SymbolFilter,
+    };
     use std::path::PathBuf;
 
     async fn create_test_searcher() -> Result<SemanticSearcher> {
@@ -876,6 +1301,7 @@ mod tests {
             end_line: 1,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("test-hash".to_string()),
+            symbol: None,
         }
     }
 
@@ -893,6 +1319,7 @@ mod tests {
             limit: 10,
             similarity_threshold: 0.5,
             language_filter: None,
+            symbol_filter: None,
         };
 
         let results = searcher.search(&query).await;
@@ -1008,6 +1435,7 @@ mod tests {
             limit: 10,
             similarity_threshold: 0.5,
             language_filter: None,
+            symbol_filter: None,
         };
 
         let explanation = searcher.explain_search(&query).await;
@@ -1078,6 +1506,7 @@ mod tests {
             end_line: 1,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("rust-main-hash".to_string()),
+            symbol: None,
         };
 
         let chunk2 = CodeChunk {
@@ -1089,6 +1518,13 @@ mod tests {
             end_line: 7,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("rust-error-hash".to_string()),
+            symbol: Some(ChunkSymbol {
+                name: "handle_error".to_string(),
+                qualified_path: "error::handle_error".to_string(),
+                kind: OutlineNodeType::Function,
+                signature: Some("fn handle_error(result: Result<String, Error>) -> String".to_string()),
+                docstring: Some("Describe a failed result".to_string()),
+            }),
         };
 
         let chunk3 = CodeChunk {
@@ -1100,6 +1536,7 @@ mod tests {
             end_line: 1,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("python-hello-hash".to_string()),
+            symbol: None,
         };
 
         // Store chunks
@@ -1113,9 +1550,11 @@ mod tests {
         let embedding3 = embedding_engine.embed_chunk(&chunk3).await?;
 
         // Store embeddings
+        storage.record_embedding_model(&embedding_engine.model_info())?;
         storage.store_embedding(&embedding1)?;
         storage.store_embedding(&embedding2)?;
         storage.store_embedding(&embedding3)?;
+        storage.store_lexical_chunks(&[chunk1, chunk2, chunk3])?;
 
         SemanticSearcher::with_embedding_engine(storage, embedding_engine, config).await
     }
@@ -1130,6 +1569,7 @@ mod tests {
             limit: 10,
             similarity_threshold: -1.0, // Allow negative similarities for mock embeddings
             language_filter: None,
+            symbol_filter: None,
         };
 
         let results = searcher.search(&query).await.unwrap();
@@ -1222,6 +1662,7 @@ mod tests {
             end_line: 1,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("reference-hash".to_string()),
+            symbol: None,
         };
 
         let results = searcher
@@ -1296,6 +1737,7 @@ mod tests {
             limit: 5,
             similarity_threshold: 0.3,
             language_filter: None,
+            symbol_filter: None,
         };
 
         let explanation = searcher.explain_search(&query).await.unwrap();
@@ -1319,6 +1761,190 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_hybrid_search_finds_exact_identifier() {
+        let searcher = create_test_searcher_with_data().await.unwrap();
+        let query = SearchQuery {
+            text: "handle_error".to_string(),
+            limit: 3,
+            similarity_threshold: -1.0,
+            language_filter: None,
+            symbol_filter: None,
+        };
+
+        let lexical = searcher
+            .search_with_mode(&query, SearchMode::Lexical)
+            .await
+            .unwrap();
+        assert_eq!(lexical.len(), 1);
+        assert_eq!(lexical[0].chunk.id, "test-chunk-rust-error");
+        assert!((lexical[0].similarity_score - 1.0).abs() < 1e-6);
+
+        // The lexical match outranks whatever the embeddings put first
+        let hybrid = searcher
+            .search_with_mode(&query, SearchMode::Hybrid)
+            .await
+            .unwrap();
+        assert_eq!(hybrid.len(), 3);
+        assert_eq!(hybrid[0].chunk.id, "test-chunk-rust-error");
+
+        let explanation = searcher
+            .explain_search_with_mode(&query, SearchMode::Hybrid)
+            .await
+            .unwrap();
+        let top = &explanation.results[0];
+        assert_eq!(top.chunk_id, "test-chunk-rust-error");
+        assert!(top.lexical_score.is_some());
+        assert_eq!(top.lexical_rank, Some(1));
+        assert!(top.vector_rank.is_some());
+        assert!(top.fused_score.unwrap() > 0.5);
+        assert!(top.rerank_score.is_none());
+        assert!(explanation.results[1..]
+            .iter()
+            .all(|result| result.lexical_score.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_explained_search_matches_results() {
+        let searcher = create_test_searcher_with_data().await.unwrap();
+        let query = SearchQuery {
+            text: "handle_error".to_string(),
+            limit: 3,
+            similarity_threshold: -1.0,
+            language_filter: None,
+            symbol_filter: None,
+        };
+
+        for mode in [
+            SearchMode::Semantic,
+            SearchMode::Lexical,
+            SearchMode::Hybrid,
+        ] {
+            let results = searcher.search_with_mode(&query, mode).await.unwrap();
+            let explained = searcher
+                .search_with_mode_explained(&query, mode)
+                .await
+                .unwrap();
+            assert_eq!(explained.len(), results.len(), "{mode}");
+            for (result, (explained_result, explanation)) in results.iter().zip(&explained) {
+                assert_eq!(explained_result.chunk.id, result.chunk.id, "{mode}");
+                assert_eq!(explanation.chunk_id, result.chunk.id, "{mode}");
+                assert!(
+                    (explained_result.similarity_score - result.similarity_score).abs() < 1e-6,
+                    "{mode}"
+                );
+            }
+        }
+
+        // Plain semantic results are scored by vector similarity
+        let explained = searcher
+            .search_with_mode_explained(&query, SearchMode::Semantic)
+            .await
+            .unwrap();
+        for (result, explanation) in &explained {
+            assert_eq!(result.similarity_score, explanation.similarity_score);
+            assert!(explanation.lexical_score.is_none());
+        }
+    }
+
+    #[test]
+    fn test_filters_apply_before_candidate_cutoff() {
+        let languages = [
+            Language::Rust,
+            Language::Rust,
+            Language::Rust,
+            Language::Python,
+            Language::Rust,
+            Language::Python,
+            Language::Python,
+        ];
+        let mut fetch_limits = Vec::new();
+        let candidates = matching_candidates(
+            2,
+            true,
+            |limit| {
+                fetch_limits.push(limit);
+                Ok((0..languages.len().min(limit)).collect())
+            },
+            |index: &usize| Ok(languages[*index] == Language::Python),
+        )
+        .unwrap();
+        assert_eq!(candidates, vec![3, 5]);
+        assert_eq!(fetch_limits, vec![2, 8]);
+
+        // A signal that runs out returns what it has
+        let candidates = matching_candidates(
+            4,
+            true,
+            |limit| Ok((0..languages.len().min(limit)).collect()),
+            |index: &usize| Ok(languages[*index] == Language::Python),
+        )
+        .unwrap();
+        assert_eq!(candidates, vec![3, 5, 6]);
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_filters_by_language() {
+        let searcher = create_test_searcher_with_data().await.unwrap();
+        let query = SearchQuery {
+            text: "fn handle_error hello world".to_string(),
+            limit: 1,
+            similarity_threshold: -1.0,
+            language_filter: Some(Language::Python),
+            symbol_filter: None,
+        };
+
+        for mode in [
+            SearchMode::Semantic,
+            SearchMode::Lexical,
+            SearchMode::Hybrid,
+        ] {
+            let explanation = searcher
+                .explain_search_with_mode(&query, mode)
+                .await
+                .unwrap();
+            assert_eq!(explanation.results.len(), 1, "{mode} search");
+            assert_eq!(explanation.results[0].chunk_id, "test-chunk-python-fn");
+        }
+        let results = searcher.search(&query).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].chunk.language, Language::Python);
+    }
+
+    #[tokio::test]
+    async fn test_search_filters_by_symbol() {
+        let searcher = create_test_searcher_with_data().await.unwrap();
+        let query = |kind: Option<&str>, path: Option<&str>| SearchQuery {
+            text: "hello world".to_string(),
+            limit: 2,
+            similarity_threshold: -1.0,
+            language_filter: None,
+            symbol_filter: Some(SymbolFilter::parse(kind, path).unwrap()),
+        };
+
+        for mode in [SearchMode::Semantic, SearchMode::Hybrid] {
+            let results = searcher
+                .search_with_mode(&query(Some("function"), Some("error::*")), mode)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 1, "{mode} search");
+            let symbol = results[0].chunk.symbol.as_ref().unwrap();
+            assert_eq!(symbol.qualified_path, "error::handle_error");
+            assert_eq!(
+                symbol.docstring.as_deref(),
+                Some("Describe a failed result")
+            );
+
+            let results = searcher
+                .search_with_mode(&query(Some("trait"), None), mode)
+                .await
+                .unwrap();
+            assert!(results.is_empty(), "{mode} search");
+        }
+
+        assert!(SymbolFilter::parse(Some("widget"), None).is_err());
+    }
+
     #[test]
     fn test_excerpt_generation_with_match() {
         let searcher = futures::executor::block_on(create_test_searcher()).unwrap();
@@ -1332,6 +1958,7 @@ mod tests {
             end_line: 1,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("test".to_string()),
+            symbol: None,
         };
 
         let excerpt = searcher.create_excerpt(&chunk, "error handling");
@@ -1359,6 +1986,7 @@ mod tests {
             end_line: 5,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("test".to_string()),
+            symbol: None,
         };
 
         let excerpt = searcher.create_excerpt(&chunk, "database connection");
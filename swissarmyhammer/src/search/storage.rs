@@ -3,14 +3,25 @@
 //! This module provides a DuckDB-based vector storage implementation for code chunks
 //! and their embeddings. It supports efficient vector similarity search using cosine
 //! similarity and manages the database schema for semantic search operations.
+//!
+//! Embeddings are stored in a fixed-size `FLOAT[n]` column whose size is fixed by
+//! the first embedding stored. Once the index holds enough embeddings, searches go
+//! through an [`HnswIndex`] kept next to the database file; smaller indexes, and
+//! indexes whose ANN graph is unavailable, are searched exactly inside DuckDB.
+//!
+//! A [`LexicalIndex`] over the chunk text is kept next to the database too, for
+//! keyword and hybrid search. It is filled by [`VectorStorage::store_lexical_chunks`],
+//! and built from the stored chunks the first time an older index is searched.
 
 use crate::error::{Result, SwissArmyHammerError};
 use crate::search::{
+    ann::{HnswConfig, HnswIndex},
+    lexical::LexicalIndex,
     types::{
-        CodeChunk, ContentHash, Embedding, IndexStats, IndexedFile, Language, SemanticSearchResult,
+        ChunkSymbol, CodeChunk, ContentHash, Embedding, IndexStats, IndexedEmbeddingModel,
+        IndexedFile, Language, SemanticSearchResult,
     },
-    utils::SemanticUtils,
-    SemanticConfig,
+    EmbeddingModelInfo, SemanticConfig,
 };
 use duckdb::{Connection, ToSql};
 use serde_json;
@@ -18,6 +29,30 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use tracing;
 
+/// Minimum number of embeddings before searches use the ANN index
+///
+/// Below this an exact search inside DuckDB is fast enough and always finds the
+/// best matches.
+pub const ANN_MIN_EMBEDDINGS: usize = 1024;
+
+/// Share of removed vectors at which the ANN index is rebuilt
+const ANN_REBUILD_TOMBSTONE_RATIO: f32 = 0.25;
+
+/// Index metadata key recording the number of components of stored embeddings
+const EMBEDDING_DIMENSION_KEY: &str = "embedding_dimension";
+
+/// Index metadata key recording the model that made the stored embeddings
+const EMBEDDING_MODEL_KEY: &str = "embedding_model";
+
+/// In-memory state of the ANN index
+#[derive(Default)]
+struct AnnState {
+    /// The index, once loaded from disk or built from the stored embeddings
+    index: Option<HnswIndex>,
+    /// Whether the index changed since it was last saved
+    dirty: bool,
+}
+
 /// Vector storage for code chunks and embeddings using DuckDB
 ///
 /// This implementation provides persistent storage operations using DuckDB for
@@ -27,6 +62,14 @@ pub struct VectorStorage {
     _config: SemanticConfig,
     /// DuckDB connection for persistent storage
     connection: Arc<Mutex<Connection>>,
+    /// Approximate nearest neighbour index over the stored embeddings
+    ///
+    /// Always locked after `connection` when both are needed.
+    ann: Arc<Mutex<AnnState>>,
+    /// Lexical index over the stored chunks, opened on first use
+    ///
+    /// Never locked while `connection` is held.
+    lexical: Arc<Mutex<Option<LexicalIndex>>>,
 }
 
 impl Clone for VectorStorage {
@@ -35,6 +78,8 @@ impl Clone for VectorStorage {
             db_path: self.db_path.clone(),
             _config: self._config.clone(),
             connection: Arc::clone(&self.connection),
+            ann: Arc::clone(&self.ann),
+            lexical: Arc::clone(&self.lexical),
         }
     }
 }
@@ -61,15 +106,31 @@ impl VectorStorage {
             start_line INTEGER NOT NULL,
             end_line INTEGER NOT NULL,
             chunk_type TEXT NOT NULL,
-            content_hash TEXT NOT NULL
+            content_hash TEXT NOT NULL,
+            symbol_name TEXT,
+            symbol_path TEXT,
+            symbol_kind TEXT,
+            signature TEXT,
+            docstring TEXT
         )
     "#;
 
-    const CREATE_EMBEDDINGS_TABLE: &'static str = r#"
-        CREATE TABLE IF NOT EXISTS embeddings (
-            chunk_id TEXT PRIMARY KEY,
-            vector TEXT NOT NULL,
-            FOREIGN KEY (chunk_id) REFERENCES code_chunks(chunk_id)
+    /// Symbol columns added to `code_chunks` after its first release
+    const SYMBOL_COLUMNS: [&'static str; 5] = [
+        "symbol_name",
+        "symbol_path",
+        "symbol_kind",
+        "signature",
+        "docstring",
+    ];
+
+    /// Columns read into a [`CodeChunk`] by [`Self::chunk_from_row`], in order
+    const CHUNK_COLUMNS: &'static str = "chunk_id, file_path, language, content, start_line, end_line, chunk_type, content_hash, symbol_name, symbol_path, symbol_kind, signature, docstring";
+
+    const CREATE_INDEX_METADATA_TABLE: &'static str = r#"
+        CREATE TABLE IF NOT EXISTS index_metadata (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
         )
     "#;
 
@@ -100,9 +161,41 @@ impl VectorStorage {
             db_path,
             _config: config,
             connection: Arc::new(Mutex::new(connection)),
+            ann: Arc::new(Mutex::new(AnnState::default())),
+            lexical: Arc::new(Mutex::new(None)),
         })
     }
 
+    /// SQL creating the embeddings table for vectors with `dimension` components
+    ///
+    /// Until the first embedding is stored the dimension is unknown, and the
+    /// table is created with a variable-length list column.
+    fn create_embeddings_table_sql(dimension: Option<usize>) -> String {
+        let vector_type = match dimension {
+            Some(dimension) => format!("FLOAT[{dimension}]"),
+            None => "FLOAT[]".to_string(),
+        };
+        format!(
+            r#"
+        CREATE TABLE IF NOT EXISTS embeddings (
+            chunk_id TEXT PRIMARY KEY,
+            vector {vector_type} NOT NULL,
+            FOREIGN KEY (chunk_id) REFERENCES code_chunks(chunk_id)
+        )
+    "#
+        )
+    }
+
+    /// Path of the persisted ANN index, next to the database file
+    pub fn ann_index_path(&self) -> PathBuf {
+        self.db_path.with_extension("hnsw")
+    }
+
+    /// Path of the lexical index directory, next to the database file
+    pub fn lexical_index_path(&self) -> PathBuf {
+        self.db_path.with_extension("lexical")
+    }
+
     /// Initialize the database schema
     pub fn initialize(&self) -> Result<()> {
         tracing::info!(
@@ -126,12 +219,28 @@ impl VectorStorage {
                 SwissArmyHammerError::Storage(format!("Failed to create code_chunks table: {e}"))
             })?;
 
-        // Create embeddings table
-        conn.execute(Self::CREATE_EMBEDDINGS_TABLE, [])
+        // Add the symbol columns to tables created before they existed
+        for column in Self::SYMBOL_COLUMNS {
+            conn.execute(
+                &format!("ALTER TABLE code_chunks ADD COLUMN IF NOT EXISTS {column} TEXT"),
+                [],
+            )
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!(
+                    "Failed to add {column} column to code_chunks table: {e}"
+                ))
+            })?;
+        }
+
+        // Create index metadata table
+        conn.execute(Self::CREATE_INDEX_METADATA_TABLE, [])
             .map_err(|e| {
-                SwissArmyHammerError::Storage(format!("Failed to create embeddings table: {e}"))
+                SwissArmyHammerError::Storage(format!("Failed to create index_metadata table: {e}"))
             })?;
 
+        // Create the embeddings table, converting vectors stored as JSON text
+        Self::prepare_embeddings_table(&conn)?;
+
         // Create indexes for better performance
         conn.execute(Self::CREATE_FILE_PATH_INDEX, [])
             .map_err(|e| {
@@ -146,10 +255,616 @@ impl VectorStorage {
             SwissArmyHammerError::Storage(format!("Failed to create path index: {e}"))
         })?;
 
+        self.load_ann_index(&conn)?;
+
         tracing::info!("Database schema initialized successfully");
         Ok(())
     }
 
+    /// Create the embeddings table, or migrate one that stores vectors as JSON text
+    fn prepare_embeddings_table(conn: &Connection) -> Result<()> {
+        let vector_type: Option<String> = conn
+            .query_row(
+                "SELECT data_type FROM information_schema.columns WHERE table_name = 'embeddings' AND column_name = 'vector'",
+                [],
+                |row| row.get(0),
+            )
+            .map(Some)
+            .or_else(|e| match e {
+                duckdb::Error::QueryReturnedNoRows => Ok(None),
+                e => Err(e),
+            })
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to inspect embeddings table: {e}"))
+            })?;
+
+        match vector_type.as_deref() {
+            Some("VARCHAR") => Self::migrate_json_embeddings(conn),
+            Some(_) => Ok(()),
+            None => {
+                let dimension = Self::stored_dimension(conn)?;
+                conn.execute(&Self::create_embeddings_table_sql(dimension), [])
+                    .map_err(|e| {
+                        SwissArmyHammerError::Storage(format!(
+                            "Failed to create embeddings table: {e}"
+                        ))
+                    })?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Move vectors stored as JSON text into a `FLOAT[n]` column
+    ///
+    /// Vectors that cannot be parsed, or whose dimension differs from the first
+    /// vector, are dropped; their files are re-embedded on the next indexing run
+    /// because their chunks no longer have embeddings.
+    fn migrate_json_embeddings(conn: &Connection) -> Result<()> {
+        tracing::info!("Migrating embeddings from JSON text to a native float array column");
+
+        let mut stmt = conn
+            .prepare("SELECT chunk_id, vector FROM embeddings")
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!(
+                    "Failed to prepare embeddings migration query: {e}"
+                ))
+            })?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to read embeddings to migrate: {e}"))
+            })?;
+
+        let mut vectors = Vec::new();
+        for row in rows {
+            let (chunk_id, vector_str) = row.map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to read embedding to migrate: {e}"))
+            })?;
+            match serde_json::from_str::<Vec<f32>>(&vector_str) {
+                Ok(vector) if !vector.is_empty() => vectors.push((chunk_id, vector)),
+                _ => tracing::warn!(
+                    "Dropping corrupted embedding for chunk {} during migration",
+                    chunk_id
+                ),
+            }
+        }
+        drop(stmt);
+
+        let dimension = vectors.first().map(|(_, vector)| vector.len());
+        vectors.retain(|(chunk_id, vector)| {
+            let matches = Some(vector.len()) == dimension;
+            if !matches {
+                tracing::warn!(
+                    "Dropping embedding for chunk {} with {} dimensions during migration",
+                    chunk_id,
+                    vector.len()
+                );
+            }
+            matches
+        });
+
+        conn.execute("BEGIN TRANSACTION", []).map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to begin transaction: {e}"))
+        })?;
+        let migrated = (|| -> Result<()> {
+            conn.execute("DROP TABLE embeddings", []).map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to drop JSON embeddings table: {e}"))
+            })?;
+            conn.execute(&Self::create_embeddings_table_sql(dimension), [])
+                .map_err(|e| {
+                    SwissArmyHammerError::Storage(format!("Failed to create embeddings table: {e}"))
+                })?;
+            if let Some(dimension) = dimension {
+                Self::set_metadata(conn, EMBEDDING_DIMENSION_KEY, &dimension.to_string())?;
+                for (chunk_id, vector) in &vectors {
+                    Self::insert_embedding(conn, chunk_id, vector, dimension)?;
+                }
+            }
+            Ok(())
+        })();
+        if let Err(e) = migrated {
+            let _ = conn.execute("ROLLBACK", []);
+            return Err(e);
+        }
+        conn.execute("COMMIT", []).map_err(|e| {
+            let _ = conn.execute("ROLLBACK", []);
+            SwissArmyHammerError::Storage(format!("Failed to commit transaction: {e}"))
+        })?;
+
+        tracing::info!("Migrated {} embeddings", vectors.len());
+        Ok(())
+    }
+
+    /// Read a value from the index metadata
+    fn get_metadata(conn: &Connection, key: &str) -> Result<Option<String>> {
+        conn.query_row(
+            "SELECT value FROM index_metadata WHERE key = ?",
+            [key],
+            |row| row.get(0),
+        )
+        .map(Some)
+        .or_else(|e| match e {
+            duckdb::Error::QueryReturnedNoRows => Ok(None),
+            e => Err(e),
+        })
+        .map_err(|e| SwissArmyHammerError::Storage(format!("Failed to read index metadata: {e}")))
+    }
+
+    /// Write a value to the index metadata
+    fn set_metadata(conn: &Connection, key: &str, value: &str) -> Result<()> {
+        conn.execute(
+            "INSERT OR REPLACE INTO index_metadata (key, value) VALUES (?, ?)",
+            [key, value],
+        )
+        .map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to write index metadata: {e}"))
+        })?;
+        Ok(())
+    }
+
+    /// Number of components of the stored embeddings, if any have been stored
+    fn stored_dimension(conn: &Connection) -> Result<Option<usize>> {
+        Ok(Self::get_metadata(conn, EMBEDDING_DIMENSION_KEY)?
+            .and_then(|dimension| dimension.parse().ok()))
+    }
+
+    /// Check an embedding fits the embeddings column, fixing its size on first use
+    fn ensure_dimension(conn: &Connection, dimension: usize) -> Result<usize> {
+        if dimension == 0 {
+            return Err(SwissArmyHammerError::Storage(
+                "Cannot store an empty embedding vector".to_string(),
+            ));
+        }
+        match Self::stored_dimension(conn)? {
+            Some(stored) if stored == dimension => Ok(stored),
+            Some(stored) => Err(SwissArmyHammerError::Storage(format!(
+                "Embedding has {dimension} dimensions but the index stores {stored}; \
+                 delete the index and re-index after changing embedding models"
+            ))),
+            None => {
+                // Nothing has been stored yet, so the table can be recreated with a fixed size
+                conn.execute("DROP TABLE IF EXISTS embeddings", [])
+                    .map_err(|e| {
+                        SwissArmyHammerError::Storage(format!(
+                            "Failed to recreate embeddings table: {e}"
+                        ))
+                    })?;
+                conn.execute(&Self::create_embeddings_table_sql(Some(dimension)), [])
+                    .map_err(|e| {
+                        SwissArmyHammerError::Storage(format!(
+                            "Failed to create embeddings table: {e}"
+                        ))
+                    })?;
+                Self::set_metadata(conn, EMBEDDING_DIMENSION_KEY, &dimension.to_string())?;
+                Ok(dimension)
+            }
+        }
+    }
+
+    /// Embedding model recorded for the stored vectors
+    pub fn embedding_model(&self) -> Result<IndexedEmbeddingModel> {
+        let conn = self.connection.lock().map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
+        })?;
+        Ok(IndexedEmbeddingModel {
+            model_id: Self::get_metadata(&conn, EMBEDDING_MODEL_KEY)?,
+            dimensions: Self::stored_dimension(&conn)?,
+        })
+    }
+
+    /// Check vectors of `model` can be stored and searched in this index
+    ///
+    /// Fails when the index was built by a different model or with a
+    /// different dimension.
+    pub fn check_embedding_model(&self, model: &EmbeddingModelInfo) -> Result<()> {
+        let indexed = self.embedding_model()?;
+        if indexed.matches(model) {
+            return Ok(());
+        }
+        Err(SwissArmyHammerError::Storage(format!(
+            "The search index was built with {indexed} but the configured embedding model is \
+             {} ({} dimensions); run `sah search index --force` to rebuild it, or set \
+             search.embedding.on_model_change = \"reindex\"",
+            model.model_id, model.dimensions
+        )))
+    }
+
+    /// Record `model` as the model making the stored vectors
+    ///
+    /// Fails when the index stores vectors of another dimension.
+    pub fn record_embedding_model(&self, model: &EmbeddingModelInfo) -> Result<()> {
+        let conn = self.connection.lock().map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
+        })?;
+        Self::ensure_dimension(&conn, model.dimensions)?;
+        Self::set_metadata(&conn, EMBEDDING_MODEL_KEY, &model.model_id)
+    }
+
+    /// Remove every indexed file, chunk and embedding
+    ///
+    /// The recorded embedding model is forgotten too, so the next index can
+    /// use any model.
+    pub fn clear(&self) -> Result<()> {
+        tracing::info!("Clearing search index at: {}", self.db_path.display());
+
+        let conn = self.connection.lock().map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
+        })?;
+        conn.execute("BEGIN TRANSACTION", []).map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to begin transaction: {e}"))
+        })?;
+        let cleared = (|| -> Result<()> {
+            conn.execute("DROP TABLE IF EXISTS embeddings", [])
+                .map_err(|e| {
+                    SwissArmyHammerError::Storage(format!("Failed to drop embeddings table: {e}"))
+                })?;
+            conn.execute(&Self::create_embeddings_table_sql(None), [])
+                .map_err(|e| {
+                    SwissArmyHammerError::Storage(format!("Failed to create embeddings table: {e}"))
+                })?;
+            for table in ["code_chunks", "indexed_files"] {
+                conn.execute(&format!("DELETE FROM {table}"), [])
+                    .map_err(|e| {
+                        SwissArmyHammerError::Storage(format!("Failed to clear {table}: {e}"))
+                    })?;
+            }
+            conn.execute(
+                "DELETE FROM index_metadata WHERE key IN (?, ?)",
+                [EMBEDDING_DIMENSION_KEY, EMBEDDING_MODEL_KEY],
+            )
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to clear index metadata: {e}"))
+            })?;
+            Ok(())
+        })();
+        if let Err(e) = cleared {
+            let _ = conn.execute("ROLLBACK", []);
+            return Err(e);
+        }
+        conn.execute("COMMIT", []).map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to commit transaction: {e}"))
+        })?;
+
+        let mut ann = self.lock_ann()?;
+        *ann = AnnState::default();
+        let _ = std::fs::remove_file(self.ann_index_path());
+        drop(ann);
+        drop(conn);
+        self.with_lexical_index(false, |index| index.clear())?;
+        Ok(())
+    }
+
+    /// Format a vector so DuckDB can cast it to `FLOAT[n]`
+    fn vector_literal(vector: &[f32]) -> Result<String> {
+        if vector.iter().any(|value| !value.is_finite()) {
+            return Err(SwissArmyHammerError::Storage(
+                "Embedding vector contains non-finite values".to_string(),
+            ));
+        }
+        serde_json::to_string(vector)
+            .map_err(|e| SwissArmyHammerError::Storage(format!("Failed to serialize vector: {e}")))
+    }
+
+    /// Insert or replace one embedding
+    fn insert_embedding(
+        conn: &Connection,
+        chunk_id: &str,
+        vector: &[f32],
+        dimension: usize,
+    ) -> Result<()> {
+        let vector_str = Self::vector_literal(vector)?;
+        conn.execute(
+            &format!(
+                "INSERT OR REPLACE INTO embeddings (chunk_id, vector) VALUES (?, CAST(? AS FLOAT[{dimension}]))"
+            ),
+            [&chunk_id as &dyn ToSql, &vector_str],
+        )
+        .map_err(|e| SwissArmyHammerError::Storage(format!("Failed to store embedding: {e}")))?;
+        Ok(())
+    }
+
+    /// Build a code chunk from the [`Self::CHUNK_COLUMNS`] starting at `offset`
+    fn chunk_from_row(row: &duckdb::Row<'_>, offset: usize) -> duckdb::Result<CodeChunk> {
+        let chunk_id: String = row.get(offset)?;
+        let file_path: String = row.get(offset + 1)?;
+        let language_str: String = row.get(offset + 2)?;
+        let content: String = row.get(offset + 3)?;
+        let start_line: i64 = row.get(offset + 4)?;
+        let end_line: i64 = row.get(offset + 5)?;
+        let chunk_type_str: String = row.get(offset + 6)?;
+        let content_hash: String = row.get(offset + 7)?;
+        let symbol_name: Option<String> = row.get(offset + 8)?;
+        let symbol_path: Option<String> = row.get(offset + 9)?;
+        let symbol_kind: Option<String> = row.get(offset + 10)?;
+
+        // Chunks cut without an outline, or indexed before symbols were stored, have none
+        let symbol = match (symbol_name, symbol_path, symbol_kind) {
+            (Some(name), Some(qualified_path), Some(kind)) => match kind.parse() {
+                Ok(kind) => Some(ChunkSymbol {
+                    name,
+                    qualified_path,
+                    kind,
+                    signature: row.get(offset + 11)?,
+                    docstring: row.get(offset + 12)?,
+                }),
+                Err(_) => None,
+            },
+            _ => None,
+        };
+
+        Ok(CodeChunk {
+            id: chunk_id,
+            file_path: PathBuf::from(file_path),
+            language: Self::parse_language(&language_str),
+            content,
+            start_line: start_line as usize,
+            end_line: end_line as usize,
+            chunk_type: Self::parse_chunk_type(&chunk_type_str),
+            content_hash: ContentHash(content_hash),
+            symbol,
+        })
+    }
+
+    /// Insert or replace one code chunk
+    fn insert_chunk(conn: &Connection, chunk: &CodeChunk) -> duckdb::Result<usize> {
+        let symbol = chunk.symbol.as_ref();
+        conn.execute(
+            &format!(
+                "INSERT OR REPLACE INTO code_chunks ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                Self::CHUNK_COLUMNS
+            ),
+            [
+                &chunk.id as &dyn ToSql,
+                &chunk.file_path.to_string_lossy(),
+                &format!("{:?}", chunk.language),
+                &chunk.content,
+                &chunk.start_line as &dyn ToSql,
+                &chunk.end_line as &dyn ToSql,
+                &format!("{:?}", chunk.chunk_type),
+                &chunk.content_hash.0,
+                &symbol.map(|symbol| symbol.name.as_str()),
+                &symbol.map(|symbol| symbol.qualified_path.as_str()),
+                &symbol.map(|symbol| symbol.kind.as_str()),
+                &symbol.and_then(|symbol| symbol.signature.as_deref()),
+                &symbol.and_then(|symbol| symbol.docstring.as_deref()),
+            ],
+        )
+    }
+
+    fn lock_ann(&self) -> Result<std::sync::MutexGuard<'_, AnnState>> {
+        self.ann.lock().map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to acquire ANN index lock: {e}"))
+        })
+    }
+
+    /// Load the persisted ANN index if it matches the stored embeddings
+    fn load_ann_index(&self, conn: &Connection) -> Result<()> {
+        let path = self.ann_index_path();
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let index = match HnswIndex::load(&path) {
+            Ok(index) => index,
+            Err(e) => {
+                tracing::warn!("Discarding unreadable ANN index: {}", e);
+                let _ = std::fs::remove_file(&path);
+                return Ok(());
+            }
+        };
+        let embedding_count = Self::count_embeddings(conn)?;
+        if Some(index.dimension()) != Self::stored_dimension(conn)?
+            || index.len() != embedding_count
+        {
+            tracing::info!("ANN index is out of date and will be rebuilt when needed");
+            let _ = std::fs::remove_file(&path);
+            return Ok(());
+        }
+
+        let mut ann = self.lock_ann()?;
+        if !index.is_empty() && !index.has_entry_point() {
+            // Searches could not reach any of its vectors
+            tracing::warn!("ANN index has no entry point, rebuilding it");
+            ann.index = Some(Self::build_ann_index(conn, index.dimension())?);
+            self.mark_ann_dirty(&mut ann);
+            return Ok(());
+        }
+
+        tracing::debug!("Loaded ANN index with {} vectors", index.len());
+        ann.index = Some(index);
+        ann.dirty = false;
+        Ok(())
+    }
+
+    /// Record that the in-memory ANN index changed
+    ///
+    /// The persisted copy is deleted on the first change so a crash before the
+    /// next save cannot leave a stale index behind.
+    fn mark_ann_dirty(&self, ann: &mut AnnState) {
+        if !ann.dirty {
+            let _ = std::fs::remove_file(self.ann_index_path());
+            ann.dirty = true;
+        }
+    }
+
+    /// Apply stored embeddings to the ANN index, if it is loaded
+    fn add_to_ann_index(&self, embeddings: &[Embedding]) -> Result<()> {
+        let mut ann = self.lock_ann()?;
+        let Some(index) = ann.index.as_mut() else {
+            return Ok(());
+        };
+        for embedding in embeddings {
+            index.insert(&embedding.chunk_id, &embedding.vector)?;
+        }
+        if !embeddings.is_empty() {
+            self.mark_ann_dirty(&mut ann);
+        }
+        Ok(())
+    }
+
+    /// Remove chunks from the ANN index, if it is loaded
+    fn remove_from_ann_index(&self, chunk_ids: &[String]) -> Result<()> {
+        let mut ann = self.lock_ann()?;
+        let mut removed = false;
+        if let Some(index) = ann.index.as_mut() {
+            for chunk_id in chunk_ids {
+                removed |= index.remove(chunk_id);
+            }
+        }
+        if removed {
+            self.mark_ann_dirty(&mut ann);
+        }
+        Ok(())
+    }
+
+    /// Build the ANN index from every stored embedding
+    fn build_ann_index(conn: &Connection, dimension: usize) -> Result<HnswIndex> {
+        let started = std::time::Instant::now();
+        let mut stmt = conn
+            .prepare("SELECT chunk_id, CAST(vector AS VARCHAR) FROM embeddings")
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to prepare ANN build query: {e}"))
+            })?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to read embeddings: {e}"))
+            })?;
+
+        let mut index = HnswIndex::new(dimension, HnswConfig::default());
+        for row in rows {
+            let (chunk_id, vector_str) = row.map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to read embedding: {e}"))
+            })?;
+            match serde_json::from_str::<Vec<f32>>(&vector_str) {
+                Ok(vector) => index.insert(&chunk_id, &vector)?,
+                Err(e) => tracing::warn!("Leaving chunk {} out of the ANN index: {}", chunk_id, e),
+            }
+        }
+
+        tracing::info!(
+            "Built ANN index over {} embeddings in {:?}",
+            index.len(),
+            started.elapsed()
+        );
+        Ok(index)
+    }
+
+    /// Count the stored embeddings
+    fn count_embeddings(conn: &Connection) -> Result<usize> {
+        conn.query_row("SELECT COUNT(*) FROM embeddings", [], |row| {
+            let count: i64 = row.get(0)?;
+            Ok(count as usize)
+        })
+        .map_err(|e| SwissArmyHammerError::Storage(format!("Failed to get embedding count: {e}")))
+    }
+
+    /// Chunk ids of a file, so they can be removed from the ANN index
+    fn file_chunk_ids(conn: &Connection, file_path: &str) -> Result<Vec<String>> {
+        let mut stmt = conn
+            .prepare("SELECT chunk_id FROM code_chunks WHERE file_path = ?")
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to prepare chunk id query: {e}"))
+            })?;
+        let rows = stmt
+            .query_map([file_path], |row| row.get::<_, String>(0))
+            .map_err(|e| SwissArmyHammerError::Storage(format!("Failed to read chunk ids: {e}")))?;
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| SwissArmyHammerError::Storage(format!("Failed to read chunk id: {e}")))
+    }
+
+    /// Write the ANN index next to the database file if it changed
+    pub fn save_ann_index(&self) -> Result<()> {
+        let mut ann = self.lock_ann()?;
+        if !ann.dirty {
+            return Ok(());
+        }
+        if let Some(index) = &ann.index {
+            index.save(&self.ann_index_path())?;
+            tracing::debug!("Saved ANN index with {} vectors", index.len());
+        }
+        ann.dirty = false;
+        Ok(())
+    }
+
+    /// Run `f` on the lexical index, opening it first if needed
+    ///
+    /// Returns `None` without creating anything when `create` is false and
+    /// there is no index on disk yet. A new or empty index is filled from the
+    /// chunks already stored, so indexes built before the lexical index existed
+    /// can be searched without re-indexing.
+    fn with_lexical_index<T>(
+        &self,
+        create: bool,
+        f: impl FnOnce(&LexicalIndex) -> Result<T>,
+    ) -> Result<Option<T>> {
+        let mut lexical = self.lexical.lock().map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to acquire lexical index lock: {e}"))
+        })?;
+        if lexical.is_none() {
+            let path = self.lexical_index_path();
+            if !create && !path.exists() {
+                return Ok(None);
+            }
+            let index = LexicalIndex::open(&path)?;
+            if index.is_empty() {
+                self.fill_lexical_index(&index)?;
+            }
+            *lexical = Some(index);
+        }
+        match lexical.as_ref() {
+            Some(index) => f(index).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Add every stored chunk to an empty lexical index
+    fn fill_lexical_index(&self, index: &LexicalIndex) -> Result<()> {
+        let paths = self.indexed_paths()?;
+        if paths.is_empty() {
+            return Ok(());
+        }
+
+        let started = std::time::Instant::now();
+        for path in &paths {
+            index.index_chunks(&self.get_file_chunks(path)?)?;
+        }
+        tracing::info!(
+            "Built lexical index over {} files in {:?}",
+            paths.len(),
+            started.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Add chunks to the lexical index, replacing earlier versions of them
+    ///
+    /// Call this once per file after its chunks are stored, so keyword and
+    /// hybrid searches can find them.
+    pub fn store_lexical_chunks(&self, chunks: &[CodeChunk]) -> Result<()> {
+        self.with_lexical_index(true, |index| index.index_chunks(chunks))?;
+        Ok(())
+    }
+
+    /// Rank stored chunks by BM25 against the query text, best first
+    ///
+    /// Returns chunk ids with their BM25 scores.
+    pub fn lexical_search(&self, query: &str, limit: usize) -> Result<Vec<(String, f32)>> {
+        Ok(self
+            .with_lexical_index(true, |index| index.search(query, limit))?
+            .unwrap_or_default())
+    }
+
+    /// Remove a file's chunks from the lexical index, if there is one
+    fn remove_from_lexical_index(&self, file_path: &Path) -> Result<()> {
+        self.with_lexical_index(false, |index| index.remove_file(file_path))?;
+        Ok(())
+    }
+
     /// Store indexed file metadata
     pub fn store_indexed_file(&self, file: &IndexedFile) -> Result<()> {
         tracing::debug!("Storing indexed file: {}", file.path.display());
@@ -192,24 +907,8 @@ impl VectorStorage {
             SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
         })?;
 
-        conn.execute(
-            r#"
-            INSERT OR REPLACE INTO code_chunks 
-            (chunk_id, file_path, language, content, start_line, end_line, chunk_type, content_hash)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
-            [
-                &chunk.id as &dyn ToSql,
-                &chunk.file_path.to_string_lossy(),
-                &format!("{:?}", chunk.language),
-                &chunk.content,
-                &chunk.start_line as &dyn ToSql,
-                &chunk.end_line as &dyn ToSql,
-                &format!("{:?}", chunk.chunk_type),
-                &chunk.content_hash.0,
-            ],
-        )
-        .map_err(|e| SwissArmyHammerError::Storage(format!("Failed to store chunk: {e}")))?;
+        Self::insert_chunk(&conn, chunk)
+            .map_err(|e| SwissArmyHammerError::Storage(format!("Failed to store chunk: {e}")))?;
 
         tracing::debug!("Successfully stored chunk: {}", chunk.id);
         Ok(())
@@ -223,16 +922,9 @@ impl VectorStorage {
             SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
         })?;
 
-        // Convert vector to JSON for storage
-        let vector_str = serde_json::to_string(&embedding.vector).map_err(|e| {
-            SwissArmyHammerError::Storage(format!("Failed to serialize vector: {e}"))
-        })?;
-
-        conn.execute(
-            "INSERT OR REPLACE INTO embeddings (chunk_id, vector) VALUES (?, ?)",
-            [&embedding.chunk_id as &dyn ToSql, &vector_str],
-        )
-        .map_err(|e| SwissArmyHammerError::Storage(format!("Failed to store embedding: {e}")))?;
+        let dimension = Self::ensure_dimension(&conn, embedding.vector.len())?;
+        Self::insert_embedding(&conn, &embedding.chunk_id, &embedding.vector, dimension)?;
+        self.add_to_ann_index(std::slice::from_ref(embedding))?;
 
         tracing::debug!(
             "Successfully stored embedding for chunk: {}",
@@ -242,6 +934,9 @@ impl VectorStorage {
     }
 
     /// Search for similar chunks using vector similarity
+    ///
+    /// Uses the ANN index once the index holds at least [`ANN_MIN_EMBEDDINGS`]
+    /// embeddings, and an exact search otherwise.
     pub fn similarity_search(
         &self,
         query_embedding: &[f32],
@@ -255,147 +950,184 @@ impl VectorStorage {
             threshold
         );
 
+        if let Some(results) = self.ann_search(query_embedding, limit, threshold)? {
+            tracing::debug!("Found {} similar chunks using ANN index", results.len());
+            return Ok(results);
+        }
+
+        self.similarity_search_exact(query_embedding, limit, threshold)
+    }
+
+    /// Search for similar chunks by comparing the query with every stored embedding
+    ///
+    /// This is the fallback for small indexes and the reference the ANN index is
+    /// measured against.
+    pub fn similarity_search_exact(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+        threshold: f32,
+    ) -> Result<Vec<SemanticSearchResult>> {
         let conn = self.connection.lock().map_err(|e| {
             SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
         })?;
 
-        // Query to get all embeddings with their corresponding chunks
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT 
-                e.chunk_id, e.vector,
-                c.file_path, c.language, c.content, c.start_line, c.end_line, c.chunk_type, c.content_hash
+        let Some(dimension) = Self::stored_dimension(&conn)? else {
+            return Ok(Vec::new());
+        };
+        Self::check_query_dimension(query_embedding, dimension)?;
+        let query_str = Self::vector_literal(query_embedding)?;
+
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+            SELECT
+                c.chunk_id, c.file_path, c.language, c.content, c.start_line, c.end_line, c.chunk_type, c.content_hash,
+                c.symbol_name, c.symbol_path, c.symbol_kind, c.signature, c.docstring,
+                array_cosine_similarity(e.vector, CAST(? AS FLOAT[{dimension}])) AS similarity
             FROM embeddings e
             JOIN code_chunks c ON e.chunk_id = c.chunk_id
+            WHERE similarity >= ?
+            ORDER BY similarity DESC
+            LIMIT ?
             "#
-        ).map_err(|e| {
-            SwissArmyHammerError::Storage(format!("Failed to prepare similarity search query: {e}"))
-        })?;
+            ))
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!(
+                    "Failed to prepare similarity search query: {e}"
+                ))
+            })?;
 
         let rows = stmt
-            .query_map([], |row| {
-                let chunk_id: String = row.get(0)?;
-                let vector_str: String = row.get(1)?;
-                let file_path: String = row.get(2)?;
-                let language_str: String = row.get(3)?;
-                let content: String = row.get(4)?;
-                let start_line: i64 = row.get(5)?;
-                let end_line: i64 = row.get(6)?;
-                let chunk_type_str: String = row.get(7)?;
-                let content_hash: String = row.get(8)?;
-
-                // Parse vector from JSON with robust handling for corrupted data
-                let vector = match serde_json::from_str::<Vec<f32>>(&vector_str) {
-                    Ok(vec) => Some(vec),
-                    Err(_e) => {
-                        // Try to parse as a single float and convert to a vector
-                        match serde_json::from_str::<f32>(&vector_str) {
-                            Ok(single_float) => {
-                                tracing::warn!(
-                                    "Found corrupted vector data stored as single float instead of array for chunk {}: {}. Skipping this chunk.",
-                                    chunk_id,
-                                    single_float
-                                );
-                                // Return None to skip this corrupted record
-                                None
-                            }
-                            Err(_) => {
-                                // Neither array nor single float, this is truly corrupted
-                                tracing::error!(
-                                    "Corrupted vector data for chunk {}: {}. Skipping this chunk.",
-                                    chunk_id,
-                                    vector_str
-                                );
-                                None
-                            }
-                        }
-                    }
-                };
-
-                // If vector parsing failed, skip this record
-                let vector = match vector {
-                    Some(v) => v,
-                    None => return Ok(None), // Skip this record
-                };
-
-                // Parse language and chunk type
-                let language = Self::parse_language(&language_str);
-                let chunk_type = Self::parse_chunk_type(&chunk_type_str);
-
-                Ok(Some((
-                    chunk_id,
-                    vector,
-                    file_path,
-                    language,
-                    content,
-                    start_line,
-                    end_line,
-                    chunk_type,
-                    content_hash,
-                )))
-            })
+            .query_map(
+                [
+                    &query_str as &dyn ToSql,
+                    &threshold as &dyn ToSql,
+                    &(limit as i64) as &dyn ToSql,
+                ],
+                |row| {
+                    let chunk = Self::chunk_from_row(row, 0)?;
+                    let similarity: f32 = row.get(13)?;
+                    Ok(SemanticSearchResult {
+                        excerpt: chunk.content.clone(),
+                        chunk,
+                        similarity_score: similarity,
+                    })
+                },
+            )
             .map_err(|e| {
                 SwissArmyHammerError::Storage(format!(
                     "Failed to execute similarity search query: {e}"
                 ))
             })?;
 
-        let mut results = Vec::new();
+        let results = rows
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| SwissArmyHammerError::Storage(format!("Failed to process row: {e}")))?;
 
-        // Calculate similarity for each embedding
-        for row_result in rows {
-            let row_data = row_result.map_err(|e| {
-                SwissArmyHammerError::Storage(format!("Failed to process row: {e}"))
-            })?;
+        tracing::debug!("Found {} similar chunks", results.len());
+        Ok(results)
+    }
 
-            // Skip corrupted records that returned None
-            let (
-                chunk_id,
-                vector,
-                file_path,
-                language,
-                content,
-                start_line,
-                end_line,
-                chunk_type,
-                content_hash,
-            ) = match row_data {
-                Some(data) => data,
-                None => continue, // Skip corrupted records
-            };
-
-            let similarity = SemanticUtils::cosine_similarity(query_embedding, &vector);
-
-            if similarity >= threshold {
-                let chunk = CodeChunk {
-                    id: chunk_id,
-                    file_path: PathBuf::from(file_path),
-                    language,
-                    content: content.clone(),
-                    start_line: start_line as usize,
-                    end_line: end_line as usize,
-                    chunk_type,
-                    content_hash: ContentHash(content_hash),
-                };
+    /// Search the ANN index, building it first if needed
+    ///
+    /// Returns `None` when the index is too small for the ANN index to pay off.
+    fn ann_search(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+        threshold: f32,
+    ) -> Result<Option<Vec<SemanticSearchResult>>> {
+        let conn = self.connection.lock().map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
+        })?;
 
-                results.push(SemanticSearchResult {
-                    chunk,
-                    similarity_score: similarity,
-                    excerpt: content,
-                });
+        let Some(dimension) = Self::stored_dimension(&conn)? else {
+            return Ok(None);
+        };
+        if Self::count_embeddings(&conn)? < ANN_MIN_EMBEDDINGS {
+            return Ok(None);
+        }
+        Self::check_query_dimension(query_embedding, dimension)?;
+
+        let hits = {
+            let mut ann = self.lock_ann()?;
+            let stale = ann.index.as_ref().is_none_or(|index| {
+                index.dimension() != dimension
+                    || index.tombstone_ratio() > ANN_REBUILD_TOMBSTONE_RATIO
+            });
+            if stale {
+                ann.index = Some(Self::build_ann_index(&conn, dimension)?);
+                self.mark_ann_dirty(&mut ann);
+            }
+            match &ann.index {
+                Some(index) => index.search(query_embedding, limit)?,
+                None => return Ok(None),
             }
+        };
+
+        let hits: Vec<(String, f32)> = hits
+            .into_iter()
+            .filter(|(_, similarity)| *similarity >= threshold)
+            .collect();
+        if hits.is_empty() {
+            return Ok(Some(Vec::new()));
         }
 
-        // Sort by similarity (descending) and limit results
-        results.sort_by(|a, b| {
-            b.similarity_score
-                .partial_cmp(&a.similarity_score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-        results.truncate(limit);
+        let placeholders = vec!["?"; hits.len()].join(", ");
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+            SELECT {columns}
+            FROM code_chunks
+            WHERE chunk_id IN ({placeholders})
+            "#,
+                columns = Self::CHUNK_COLUMNS
+            ))
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to prepare ANN chunk query: {e}"))
+            })?;
+        let rows = stmt
+            .query_map(
+                duckdb::params_from_iter(hits.iter().map(|(chunk_id, _)| chunk_id)),
+                |row| Self::chunk_from_row(row, 0),
+            )
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to execute ANN chunk query: {e}"))
+            })?;
 
-        tracing::debug!("Found {} similar chunks", results.len());
-        Ok(results)
+        let mut chunks = std::collections::HashMap::new();
+        for row in rows {
+            let chunk = row.map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to process row: {e}"))
+            })?;
+            chunks.insert(chunk.id.clone(), chunk);
+        }
+
+        // Keep the ranking of the ANN index
+        let results = hits
+            .into_iter()
+            .filter_map(|(chunk_id, similarity)| {
+                chunks.remove(&chunk_id).map(|chunk| SemanticSearchResult {
+                    excerpt: chunk.content.clone(),
+                    chunk,
+                    similarity_score: similarity,
+                })
+            })
+            .collect();
+        Ok(Some(results))
+    }
+
+    /// Check a vector has the dimension of the stored embeddings
+    fn check_query_dimension(query_embedding: &[f32], dimension: usize) -> Result<()> {
+        if query_embedding.len() != dimension {
+            return Err(SwissArmyHammerError::Storage(format!(
+                "Embedding has {} dimensions but the index stores {dimension}; \
+                 re-index after changing embedding models",
+                query_embedding.len()
+            )));
+        }
+        Ok(())
     }
 
     /// Search for similar chunks with detailed embedding information for debugging
@@ -416,8 +1148,25 @@ impl VectorStorage {
             SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
         })?;
 
+        let Some(dimension) = Self::stored_dimension(&conn)? else {
+            return Ok(Vec::new());
+        };
+        Self::check_query_dimension(query_embedding, dimension)?;
+        let query_str = Self::vector_literal(query_embedding)?;
+
         let mut stmt = conn
-            .prepare("SELECT chunk_id, vector FROM embeddings")
+            .prepare(&format!(
+                r#"
+            SELECT
+                chunk_id,
+                array_cosine_similarity(vector, CAST(? AS FLOAT[{dimension}])) AS similarity,
+                CAST(vector AS VARCHAR)
+            FROM embeddings
+            WHERE similarity >= ?
+            ORDER BY similarity DESC
+            LIMIT ?
+            "#
+            ))
             .map_err(|e| {
                 SwissArmyHammerError::Storage(format!(
                     "Failed to prepare detailed search query: {e}"
@@ -425,46 +1174,20 @@ impl VectorStorage {
             })?;
 
         let rows = stmt
-            .query_map([], |row| {
-                let chunk_id: String = row.get(0)?;
-                let vector_str: String = row.get(1)?;
-
-                // Parse vector from JSON with robust handling for corrupted data
-                let vector = match serde_json::from_str::<Vec<f32>>(&vector_str) {
-                    Ok(vec) => Some(vec),
-                    Err(_e) => {
-                        // Try to parse as a single float and convert to a vector
-                        match serde_json::from_str::<f32>(&vector_str) {
-                            Ok(single_float) => {
-                                tracing::warn!(
-                                    "Found corrupted vector data stored as single float instead of array for chunk {}: {}. Skipping this chunk.",
-                                    chunk_id,
-                                    single_float
-                                );
-                                // Return None to skip this corrupted record
-                                None
-                            }
-                            Err(_) => {
-                                // Neither array nor single float, this is truly corrupted
-                                tracing::error!(
-                                    "Corrupted vector data for chunk {}: {}. Skipping this chunk.",
-                                    chunk_id,
-                                    vector_str
-                                );
-                                None
-                            }
-                        }
-                    }
-                };
-
-                // If vector parsing failed, skip this record
-                let vector = match vector {
-                    Some(v) => v,
-                    None => return Ok(None), // Skip this record
-                };
-
-                Ok(Some((chunk_id, vector)))
-            })
+            .query_map(
+                [
+                    &query_str as &dyn ToSql,
+                    &threshold as &dyn ToSql,
+                    &(limit as i64) as &dyn ToSql,
+                ],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, f32>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                },
+            )
             .map_err(|e| {
                 SwissArmyHammerError::Storage(format!(
                     "Failed to execute detailed search query: {e}"
@@ -472,30 +1195,18 @@ impl VectorStorage {
             })?;
 
         let mut results = Vec::new();
-
-        // Calculate similarity for each embedding
         for row_result in rows {
-            let row_data = row_result.map_err(|e| {
+            let (chunk_id, similarity, vector_str) = row_result.map_err(|e| {
                 SwissArmyHammerError::Storage(format!("Failed to process detailed search row: {e}"))
             })?;
-
-            // Skip corrupted records that returned None
-            let (chunk_id, vector) = match row_data {
-                Some(data) => data,
-                None => continue, // Skip corrupted records
-            };
-
-            let similarity = SemanticUtils::cosine_similarity(query_embedding, &vector);
-
-            if similarity >= threshold {
-                results.push((chunk_id, similarity, vector));
-            }
+            let vector = serde_json::from_str::<Vec<f32>>(&vector_str).map_err(|e| {
+                SwissArmyHammerError::Storage(format!(
+                    "Failed to read vector for chunk {chunk_id}: {e}"
+                ))
+            })?;
+            results.push((chunk_id, similarity, vector));
         }
 
-        // Sort by similarity (descending) and limit results
-        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-        results.truncate(limit);
-
         tracing::debug!("Found {} similar chunks with details", results.len());
         Ok(results)
     }
@@ -508,38 +1219,17 @@ impl VectorStorage {
             SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
         })?;
 
-        let mut stmt = conn.prepare(
-            "SELECT chunk_id, file_path, language, content, start_line, end_line, chunk_type, content_hash FROM code_chunks WHERE chunk_id = ?"
-        ).map_err(|e| {
-            SwissArmyHammerError::Storage(format!("Failed to prepare get_chunk query: {e}"))
-        })?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM code_chunks WHERE chunk_id = ?",
+                Self::CHUNK_COLUMNS
+            ))
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to prepare get_chunk query: {e}"))
+            })?;
 
         let mut rows = stmt
-            .query_map([chunk_id], |row| {
-                let chunk_id: String = row.get(0)?;
-                let file_path: String = row.get(1)?;
-                let language_str: String = row.get(2)?;
-                let content: String = row.get(3)?;
-                let start_line: i64 = row.get(4)?;
-                let end_line: i64 = row.get(5)?;
-                let chunk_type_str: String = row.get(6)?;
-                let content_hash: String = row.get(7)?;
-
-                // Parse language and chunk type
-                let language = Self::parse_language(&language_str);
-                let chunk_type = Self::parse_chunk_type(&chunk_type_str);
-
-                Ok(CodeChunk {
-                    id: chunk_id,
-                    file_path: PathBuf::from(file_path),
-                    language,
-                    content,
-                    start_line: start_line as usize,
-                    end_line: end_line as usize,
-                    chunk_type,
-                    content_hash: ContentHash(content_hash),
-                })
-            })
+            .query_map([chunk_id], |row| Self::chunk_from_row(row, 0))
             .map_err(|e| {
                 SwissArmyHammerError::Storage(format!("Failed to execute get_chunk query: {e}"))
             })?;
@@ -560,40 +1250,18 @@ impl VectorStorage {
             SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
         })?;
 
-        let mut stmt = conn.prepare(
-            "SELECT chunk_id, file_path, language, content, start_line, end_line, chunk_type, content_hash FROM code_chunks"
-        ).map_err(|e| {
-            SwissArmyHammerError::Storage(format!("Failed to prepare get_all_chunks query: {e}"))
-        })?;
+        let mut stmt = conn
+            .prepare(&format!("SELECT {} FROM code_chunks", Self::CHUNK_COLUMNS))
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!(
+                    "Failed to prepare get_all_chunks query: {e}"
+                ))
+            })?;
 
         let rows = stmt
             .query_map([], |row| {
-                let chunk_id: String = row.get(0)?;
-                let file_path: String = row.get(1)?;
-                let language_str: String = row.get(2)?;
-                let content: String = row.get(3)?;
-                let start_line: i64 = row.get(4)?;
-                let end_line: i64 = row.get(5)?;
-                let chunk_type_str: String = row.get(6)?;
-                let content_hash: String = row.get(7)?;
-
-                // Parse language and chunk type
-                let language = Self::parse_language(&language_str);
-                let chunk_type = Self::parse_chunk_type(&chunk_type_str);
-
-                Ok((
-                    chunk_id.clone(),
-                    CodeChunk {
-                        id: chunk_id,
-                        file_path: PathBuf::from(file_path),
-                        language,
-                        content,
-                        start_line: start_line as usize,
-                        end_line: end_line as usize,
-                        chunk_type,
-                        content_hash: ContentHash(content_hash),
-                    },
-                ))
+                let chunk = Self::chunk_from_row(row, 0)?;
+                Ok((chunk.id.clone(), chunk))
             })
             .map_err(|e| {
                 SwissArmyHammerError::Storage(format!(
@@ -618,38 +1286,21 @@ impl VectorStorage {
             SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
         })?;
 
-        let mut stmt = conn.prepare(
-            "SELECT chunk_id, file_path, language, content, start_line, end_line, chunk_type, content_hash FROM code_chunks WHERE file_path = ?"
-        ).map_err(|e| {
-            SwissArmyHammerError::Storage(format!("Failed to prepare get_file_chunks query: {e}"))
-        })?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM code_chunks WHERE file_path = ?",
+                Self::CHUNK_COLUMNS
+            ))
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!(
+                    "Failed to prepare get_file_chunks query: {e}"
+                ))
+            })?;
 
         let file_path_str = file_path.to_string_lossy();
         let rows = stmt
             .query_map([&file_path_str as &dyn ToSql], |row| {
-                let chunk_id: String = row.get(0)?;
-                let file_path: String = row.get(1)?;
-                let language_str: String = row.get(2)?;
-                let content: String = row.get(3)?;
-                let start_line: i64 = row.get(4)?;
-                let end_line: i64 = row.get(5)?;
-                let chunk_type_str: String = row.get(6)?;
-                let content_hash: String = row.get(7)?;
-
-                // Parse language and chunk type
-                let language = Self::parse_language(&language_str);
-                let chunk_type = Self::parse_chunk_type(&chunk_type_str);
-
-                Ok(CodeChunk {
-                    id: chunk_id,
-                    file_path: PathBuf::from(file_path),
-                    language,
-                    content,
-                    start_line: start_line as usize,
-                    end_line: end_line as usize,
-                    chunk_type,
-                    content_hash: ContentHash(content_hash),
-                })
+                Self::chunk_from_row(row, 0)
             })
             .map_err(|e| {
                 SwissArmyHammerError::Storage(format!(
@@ -734,6 +1385,7 @@ impl VectorStorage {
         })?;
 
         let file_path_str = file_path.to_string_lossy();
+        let chunk_ids = Self::file_chunk_ids(&conn, &file_path_str)?;
 
         // Remove embeddings for chunks in this file (CASCADE should handle this, but let's be explicit)
         conn.execute(
@@ -761,6 +1413,9 @@ impl VectorStorage {
         .map_err(|e| {
             SwissArmyHammerError::Storage(format!("Failed to remove indexed file metadata: {e}"))
         })?;
+        self.remove_from_ann_index(&chunk_ids)?;
+        drop(conn);
+        self.remove_from_lexical_index(file_path)?;
 
         tracing::debug!(
             "Removed {} chunks for file: {}",
@@ -836,37 +1491,20 @@ impl VectorStorage {
         })?;
 
         let language_str = format!("{language:?}");
-        let mut stmt = conn.prepare(
-            "SELECT chunk_id, file_path, language, content, start_line, end_line, chunk_type, content_hash FROM code_chunks WHERE language = ?"
-        ).map_err(|e| {
-            SwissArmyHammerError::Storage(format!("Failed to prepare get_chunks_by_language query: {e}"))
-        })?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM code_chunks WHERE language = ?",
+                Self::CHUNK_COLUMNS
+            ))
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!(
+                    "Failed to prepare get_chunks_by_language query: {e}"
+                ))
+            })?;
 
         let rows = stmt
             .query_map([&language_str as &dyn ToSql], |row| {
-                let chunk_id: String = row.get(0)?;
-                let file_path: String = row.get(1)?;
-                let language_str: String = row.get(2)?;
-                let content: String = row.get(3)?;
-                let start_line: i64 = row.get(4)?;
-                let end_line: i64 = row.get(5)?;
-                let chunk_type_str: String = row.get(6)?;
-                let content_hash: String = row.get(7)?;
-
-                // Parse language and chunk type
-                let language = Self::parse_language(&language_str);
-                let chunk_type = Self::parse_chunk_type(&chunk_type_str);
-
-                Ok(CodeChunk {
-                    id: chunk_id,
-                    file_path: PathBuf::from(file_path),
-                    language,
-                    content,
-                    start_line: start_line as usize,
-                    end_line: end_line as usize,
-                    chunk_type,
-                    content_hash: ContentHash(content_hash),
-                })
+                Self::chunk_from_row(row, 0)
             })
             .map_err(|e| {
                 SwissArmyHammerError::Storage(format!(
@@ -917,6 +1555,24 @@ impl VectorStorage {
             })?;
         }
 
+        // Rebuild the ANN index once too many of its vectors have been removed
+        {
+            let mut ann = self.lock_ann()?;
+            let needs_rebuild = ann
+                .index
+                .as_ref()
+                .is_some_and(|index| index.tombstone_ratio() > ANN_REBUILD_TOMBSTONE_RATIO);
+            if needs_rebuild {
+                if let Some(dimension) = Self::stored_dimension(&conn)? {
+                    tracing::debug!("Rebuilding ANN index");
+                    ann.index = Some(Self::build_ann_index(&conn, dimension)?);
+                    self.mark_ann_dirty(&mut ann);
+                }
+            }
+        }
+        drop(conn);
+        self.save_ann_index()?;
+
         tracing::info!("Database maintenance completed successfully");
         Ok(())
     }
@@ -958,6 +1614,32 @@ impl VectorStorage {
         }
     }
 
+    /// Paths of every indexed file
+    pub fn indexed_paths(&self) -> Result<Vec<PathBuf>> {
+        let conn = self.connection.lock().map_err(|e| {
+            SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
+        })?;
+
+        let mut stmt = conn
+            .prepare("SELECT path FROM indexed_files ORDER BY path")
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to prepare indexed_paths query: {e}"))
+            })?;
+
+        let paths = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| {
+                SwissArmyHammerError::Storage(format!("Failed to execute indexed_paths query: {e}"))
+            })?
+            .map(|path| {
+                path.map(PathBuf::from).map_err(|e| {
+                    SwissArmyHammerError::Storage(format!("Failed to parse indexed path: {e}"))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(paths)
+    }
+
     /// Get statistics about indexed files
     pub fn get_index_stats(&self) -> Result<IndexStats> {
         let conn = self.connection.lock().map_err(|e| {
@@ -1021,6 +1703,12 @@ impl VectorStorage {
             SwissArmyHammerError::Storage(format!("Failed to acquire connection lock: {e}"))
         })?;
 
+        // Fix the vector column size before the transaction if nothing is stored yet
+        let dimension = match embeddings.first() {
+            Some(embedding) => Some(Self::ensure_dimension(&conn, embedding.vector.len())?),
+            None => None,
+        };
+
         // Begin transaction
         conn.execute("BEGIN TRANSACTION", []).map_err(|e| {
             SwissArmyHammerError::Storage(format!("Failed to begin transaction: {e}"))
@@ -1028,23 +1716,7 @@ impl VectorStorage {
 
         // Store chunks
         for chunk in chunks {
-            if let Err(e) = conn.execute(
-                r#"
-                INSERT OR REPLACE INTO code_chunks 
-                (chunk_id, file_path, language, content, start_line, end_line, chunk_type, content_hash)
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-                "#,
-                [
-                    &chunk.id as &dyn ToSql,
-                    &chunk.file_path.to_string_lossy(),
-                    &format!("{:?}", chunk.language),
-                    &chunk.content,
-                    &chunk.start_line as &dyn ToSql,
-                    &chunk.end_line as &dyn ToSql,
-                    &format!("{:?}", chunk.chunk_type),
-                    &chunk.content_hash.0,
-                ],
-            ) {
+            if let Err(e) = Self::insert_chunk(&conn, chunk) {
                 // Rollback on error
                 let _ = conn.execute("ROLLBACK", []);
                 return Err(SwissArmyHammerError::Storage(format!(
@@ -1054,22 +1726,24 @@ impl VectorStorage {
         }
 
         // Store embeddings
-        for embedding in embeddings {
-            let vector_str = serde_json::to_string(&embedding.vector).map_err(|e| {
-                // Rollback on error
-                let _ = conn.execute("ROLLBACK", []);
-                SwissArmyHammerError::Storage(format!("Failed to serialize vector: {e}"))
-            })?;
-
-            if let Err(e) = conn.execute(
-                "INSERT OR REPLACE INTO embeddings (chunk_id, vector) VALUES (?, ?)",
-                [&embedding.chunk_id as &dyn ToSql, &vector_str],
-            ) {
-                // Rollback on error
-                let _ = conn.execute("ROLLBACK", []);
-                return Err(SwissArmyHammerError::Storage(format!(
-                    "Failed to store embedding in transaction: {e}"
-                )));
+        if let Some(dimension) = dimension {
+            for embedding in embeddings {
+                let stored =
+                    Self::check_query_dimension(&embedding.vector, dimension).and_then(|_| {
+                        Self::insert_embedding(
+                            &conn,
+                            &embedding.chunk_id,
+                            &embedding.vector,
+                            dimension,
+                        )
+                    });
+                if let Err(e) = stored {
+                    // Rollback on error
+                    let _ = conn.execute("ROLLBACK", []);
+                    return Err(SwissArmyHammerError::Storage(format!(
+                        "Failed to store embedding in transaction: {e}"
+                    )));
+                }
             }
         }
 
@@ -1079,6 +1753,7 @@ impl VectorStorage {
             let _ = conn.execute("ROLLBACK", []);
             SwissArmyHammerError::Storage(format!("Failed to commit transaction: {e}"))
         })?;
+        self.add_to_ann_index(embeddings)?;
 
         tracing::debug!(
             "Successfully stored {} chunks and {} embeddings in transaction",
@@ -1097,6 +1772,7 @@ impl VectorStorage {
         })?;
 
         let file_path_str = file_path.to_string_lossy();
+        let chunk_ids = Self::file_chunk_ids(&conn, &file_path_str)?;
 
         // Begin transaction
         conn.execute("BEGIN TRANSACTION", []).map_err(|e| {
@@ -1141,6 +1817,9 @@ impl VectorStorage {
             let _ = conn.execute("ROLLBACK", []);
             SwissArmyHammerError::Storage(format!("Failed to commit transaction: {e}"))
         })?;
+        self.remove_from_ann_index(&chunk_ids)?;
+        drop(conn);
+        self.remove_from_lexical_index(file_path)?;
 
         tracing::debug!(
             "Successfully removed file in transaction: {}",
@@ -1157,6 +1836,13 @@ impl VectorStorage {
             "TypeScript" => Language::TypeScript,
             "JavaScript" => Language::JavaScript,
             "Dart" => Language::Dart,
+            "Go" => Language::Go,
+            "Java" => Language::Java,
+            "C" => Language::C,
+            "Cpp" => Language::Cpp,
+            "CSharp" => Language::CSharp,
+            "Ruby" => Language::Ruby,
+            "Markdown" => Language::Markdown,
             _ => Language::Unknown,
         }
     }
@@ -1168,6 +1854,8 @@ impl VectorStorage {
             "Class" => crate::search::types::ChunkType::Class,
             "Module" => crate::search::types::ChunkType::Module,
             "Import" => crate::search::types::ChunkType::Import,
+            "Declaration" => crate::search::types::ChunkType::Declaration,
+            "Section" => crate::search::types::ChunkType::Section,
             _ => crate::search::types::ChunkType::PlainText,
         }
     }
@@ -1177,6 +1865,10 @@ impl VectorStorage {
     /// This method allows for explicit cleanup of DuckDB connections,
     /// which can help prevent assertion failures during Drop.
     pub fn close(&self) -> Result<()> {
+        if let Err(e) = self.save_ann_index() {
+            tracing::warn!("Failed to save ANN index during close: {}", e);
+        }
+
         if let Ok(conn) = self.connection.lock() {
             // Test connection validity before attempting to close
             match conn.execute("SELECT 1", []) {
@@ -1291,6 +1983,7 @@ mod tests {
             end_line: 1,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("test-hash".to_string()),
+            symbol: None,
         }
     }
 
@@ -1491,6 +2184,219 @@ mod tests {
         }
     }
 
+    fn create_test_chunks_and_embeddings(
+        count: usize,
+        dimension: usize,
+    ) -> (Vec<CodeChunk>, Vec<Embedding>) {
+        (0..count)
+            .map(|i| {
+                let mut chunk = create_test_chunk();
+                chunk.id = format!("chunk-{i}");
+                chunk.file_path = PathBuf::from(format!("file-{}.rs", i % 50));
+                let vector = (0..dimension)
+                    .map(|d| {
+                        let mut x = (i as u64 + 1)
+                            .wrapping_mul(6364136223846793005)
+                            .wrapping_add((d as u64).wrapping_mul(1442695040888963407));
+                        x ^= x >> 33;
+                        x = x.wrapping_mul(0xff51afd7ed558ccd);
+                        x ^= x >> 29;
+                        (x % 1000) as f32 / 1000.0 - 0.5
+                    })
+                    .collect();
+                let embedding = Embedding {
+                    chunk_id: chunk.id.clone(),
+                    vector,
+                };
+                (chunk, embedding)
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn test_similarity_search_finds_stored_embedding() {
+        let (config, _guard) = create_test_config();
+        let storage = VectorStorage::new(config).unwrap();
+        storage.initialize().unwrap();
+        storage.store_chunk(&create_test_chunk()).unwrap();
+        let embedding = create_test_embedding();
+        storage.store_embedding(&embedding).unwrap();
+
+        let results = storage
+            .similarity_search(&embedding.vector, 10, 0.5)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].chunk.id, "test-chunk-1");
+        assert!((results[0].similarity_score - 1.0).abs() < 1e-4);
+
+        let details = storage
+            .similarity_search_with_details(&embedding.vector, 10, 0.5)
+            .unwrap();
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].2.len(), 384);
+    }
+
+    #[test]
+    fn test_embedding_dimension_mismatch_is_rejected() {
+        let (config, _guard) = create_test_config();
+        let storage = VectorStorage::new(config).unwrap();
+        storage.initialize().unwrap();
+        storage.store_chunk(&create_test_chunk()).unwrap();
+        storage.store_embedding(&create_test_embedding()).unwrap();
+
+        let short = Embedding {
+            chunk_id: "test-chunk-1".to_string(),
+            vector: vec![0.5; 16],
+        };
+        assert!(storage.store_embedding(&short).is_err());
+        assert!(storage.similarity_search(&[0.5; 16], 10, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_embedding_model_is_recorded_and_checked() {
+        let (config, _guard) = create_test_config();
+        let storage = VectorStorage::new(config).unwrap();
+        storage.initialize().unwrap();
+        let model = |model_id: &str, dimensions| EmbeddingModelInfo {
+            model_id: model_id.to_string(),
+            dimensions,
+            max_sequence_length: 512,
+            quantization: "none".to_string(),
+        };
+
+        // An empty index accepts any model
+        assert_eq!(
+            storage.embedding_model().unwrap(),
+            IndexedEmbeddingModel::default()
+        );
+        storage.check_embedding_model(&model("other", 16)).unwrap();
+
+        storage
+            .record_embedding_model(&model("nomic", 384))
+            .unwrap();
+        storage.store_chunk(&create_test_chunk()).unwrap();
+        storage.store_embedding(&create_test_embedding()).unwrap();
+        assert_eq!(
+            storage.embedding_model().unwrap(),
+            IndexedEmbeddingModel {
+                model_id: Some("nomic".to_string()),
+                dimensions: Some(384),
+            }
+        );
+        storage.check_embedding_model(&model("nomic", 384)).unwrap();
+
+        // Another model of the same size still makes incomparable vectors
+        let error = storage
+            .check_embedding_model(&model("bge", 384))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("nomic (384 dimensions)"), "{error}");
+        assert!(error.contains("--force"), "{error}");
+        assert!(storage.check_embedding_model(&model("nomic", 16)).is_err());
+        assert!(storage.record_embedding_model(&model("bge", 16)).is_err());
+
+        // Clearing forgets the model and everything it indexed
+        storage.clear().unwrap();
+        let stats = storage.get_stats().unwrap();
+        assert_eq!((stats.total_chunks, stats.total_embeddings), (0, 0));
+        assert_eq!(
+            storage.embedding_model().unwrap(),
+            IndexedEmbeddingModel::default()
+        );
+        storage.record_embedding_model(&model("bge", 16)).unwrap();
+        storage.store_chunk(&create_test_chunk()).unwrap();
+        storage
+            .store_embedding(&Embedding {
+                chunk_id: "test-chunk-1".to_string(),
+                vector: vec![0.5; 16],
+            })
+            .unwrap();
+
+        // Vectors stored before models were recorded could be from any model of their size
+        storage.clear().unwrap();
+        storage.store_chunk(&create_test_chunk()).unwrap();
+        storage.store_embedding(&create_test_embedding()).unwrap();
+        let error = storage
+            .check_embedding_model(&model("nomic", 384))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("an unrecorded model (384 dimensions)"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_ann_search_matches_exact_search() {
+        let (config, _guard) = create_test_config();
+        let storage = VectorStorage::new(config.clone()).unwrap();
+        storage.initialize().unwrap();
+        let (chunks, embeddings) = create_test_chunks_and_embeddings(ANN_MIN_EMBEDDINGS + 100, 24);
+        storage
+            .store_chunks_and_embeddings_transaction(&chunks, &embeddings)
+            .unwrap();
+
+        let query = &embeddings[7].vector;
+        let ann = storage.similarity_search(query, 5, 0.0).unwrap();
+        let exact = storage.similarity_search_exact(query, 5, 0.0).unwrap();
+        assert_eq!(ann.len(), 5);
+        assert_eq!(ann[0].chunk.id, exact[0].chunk.id);
+        assert!((ann[0].similarity_score - exact[0].similarity_score).abs() < 1e-4);
+
+        // Removed files disappear from ANN results
+        storage.remove_file(&chunks[7].file_path).unwrap();
+        let ann = storage.similarity_search(query, 5, 0.0).unwrap();
+        assert!(ann
+            .iter()
+            .all(|result| result.chunk.file_path != chunks[7].file_path));
+
+        // The index is persisted next to the database and loaded on the next open
+        storage.close().unwrap();
+        let ann_path = storage.ann_index_path();
+        drop(storage);
+        assert!(ann_path.exists());
+        let reopened = VectorStorage::new(config).unwrap();
+        reopened.initialize().unwrap();
+        assert!(reopened.lock_ann().unwrap().index.is_some());
+        let results = reopened
+            .similarity_search(&embeddings[8].vector, 1, 0.0)
+            .unwrap();
+        assert_eq!(results[0].chunk.id, "chunk-8");
+    }
+
+    #[test]
+    fn test_lexical_index_is_filled_from_stored_chunks() {
+        let (config, _guard) = create_test_config();
+        let storage = VectorStorage::new(config).unwrap();
+        storage.initialize().unwrap();
+        let chunk = create_test_chunk();
+        storage.store_chunk(&chunk).unwrap();
+        storage
+            .store_indexed_file(&create_test_indexed_file())
+            .unwrap();
+
+        // Chunks stored before the lexical index existed are found on first search
+        assert!(!storage.lexical_index_path().exists());
+        let results = storage.lexical_search("println", 5).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "test-chunk-1");
+
+        let other = CodeChunk {
+            id: "test-chunk-2".to_string(),
+            file_path: PathBuf::from("other.rs"),
+            content: "fn validate_branch_name(name: &str) -> bool { true }".to_string(),
+            ..chunk
+        };
+        storage.store_lexical_chunks(&[other]).unwrap();
+        assert_eq!(
+            storage.lexical_search("validate_branch_name", 5).unwrap()[0].0,
+            "test-chunk-2"
+        );
+
+        storage.remove_file(Path::new("test.rs")).unwrap();
+        assert!(storage.lexical_search("println", 5).unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_reproduce_full_search_integration() {
         use crate::search::{EmbeddingEngine, SearchQuery, SemanticSearcher};
@@ -1514,6 +2420,7 @@ mod tests {
                     limit: 10,
                     similarity_threshold: 0.5,
                     language_filter: None,
+                    symbol_filter: None,
                 };
 
                 match searcher.search(&search_query).await {
@@ -62,6 +62,13 @@ mod integration_tests {
             Language::TypeScript,
             Language::JavaScript,
             Language::Dart,
+            Language::Go,
+            Language::Java,
+            Language::C,
+            Language::Cpp,
+            Language::CSharp,
+            Language::Ruby,
+            Language::Markdown,
         ];
 
         for lang in languages {
@@ -107,6 +114,7 @@ mod integration_tests {
             limit: 10,
             similarity_threshold: 0.8,
             language_filter: Some(Language::Rust),
+            symbol_filter: None,
         };
         assert_eq!(query.text, "function test");
         assert_eq!(query.limit, 10);
@@ -182,6 +190,7 @@ mod integration_tests {
             end_line: 1,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("abc123".to_string()),
+            symbol: None,
         };
 
         assert_eq!(chunk.id, "test-1");
@@ -202,6 +211,7 @@ mod integration_tests {
             end_line: 1,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("abc123".to_string()),
+            symbol: None,
         };
 
         let result = SemanticSearchResult {
@@ -1,8 +1,11 @@
 //! Core data structures for semantic search functionality
 
+use crate::outline::OutlineNodeType;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 /// Unique identifier for indexed files
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -25,6 +28,20 @@ pub enum Language {
     JavaScript,
     /// Dart programming language
     Dart,
+    /// Go programming language
+    Go,
+    /// Java programming language
+    Java,
+    /// C programming language
+    C,
+    /// C++ programming language
+    Cpp,
+    /// C# programming language
+    CSharp,
+    /// Ruby programming language
+    Ruby,
+    /// Markdown documents
+    Markdown,
     /// Unknown or unsupported language
     Unknown,
 }
@@ -42,6 +59,31 @@ pub enum ChunkType {
     Import,
     /// Plain text for files that fail TreeSitter parsing
     PlainText,
+    /// Constant, variable, property or type alias declaration
+    Declaration,
+    /// Heading section of a document
+    Section,
+}
+
+impl From<&OutlineNodeType> for ChunkType {
+    fn from(kind: &OutlineNodeType) -> Self {
+        match kind {
+            OutlineNodeType::Function | OutlineNodeType::Method => ChunkType::Function,
+            OutlineNodeType::Class
+            | OutlineNodeType::Struct
+            | OutlineNodeType::Enum
+            | OutlineNodeType::Interface
+            | OutlineNodeType::Trait
+            | OutlineNodeType::Impl => ChunkType::Class,
+            OutlineNodeType::Module => ChunkType::Module,
+            OutlineNodeType::Import => ChunkType::Import,
+            OutlineNodeType::Property
+            | OutlineNodeType::Constant
+            | OutlineNodeType::Variable
+            | OutlineNodeType::TypeAlias => ChunkType::Declaration,
+            OutlineNodeType::Section => ChunkType::Section,
+        }
+    }
 }
 
 /// A chunk of code extracted from a file
@@ -63,6 +105,24 @@ pub struct CodeChunk {
     pub chunk_type: ChunkType,
     /// Hash of the content for change detection
     pub content_hash: ContentHash,
+    /// The symbol this chunk defines, for chunks cut along the code outline
+    #[serde(default)]
+    pub symbol: Option<ChunkSymbol>,
+}
+
+/// Symbol information of a chunk taken from the code outline
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkSymbol {
+    /// Name of the symbol, such as `new`
+    pub name: String,
+    /// Path of the symbol from the module root, such as `git::GitOperations::new`
+    pub qualified_path: String,
+    /// Kind of symbol
+    pub kind: OutlineNodeType,
+    /// Signature of the symbol, if it has one
+    pub signature: Option<String>,
+    /// Documentation comment of the symbol
+    pub docstring: Option<String>,
 }
 
 /// Vector embedding for a code chunk
@@ -113,6 +173,115 @@ pub struct SearchQuery {
     pub similarity_threshold: f32,
     /// Optional language filter
     pub language_filter: Option<Language>,
+    /// Optional filter on the symbol a chunk defines
+    pub symbol_filter: Option<SymbolFilter>,
+}
+
+impl SearchQuery {
+    /// Whether the query filters chunks by language or symbol
+    pub fn is_filtered(&self) -> bool {
+        self.language_filter.is_some()
+            || self
+                .symbol_filter
+                .as_ref()
+                .is_some_and(|filter| !filter.is_empty())
+    }
+
+    /// Whether a chunk passes the language and symbol filters of the query
+    pub fn accepts(&self, chunk: &CodeChunk) -> bool {
+        self.language_filter
+            .as_ref()
+            .is_none_or(|language| chunk.language == *language)
+            && self
+                .symbol_filter
+                .as_ref()
+                .is_none_or(|filter| filter.matches(chunk))
+    }
+}
+
+/// Restricts search results to chunks defining matching symbols
+#[derive(Debug, Clone, Default)]
+pub struct SymbolFilter {
+    /// Kind the symbol must have, such as a trait
+    pub kind: Option<OutlineNodeType>,
+    /// Glob the qualified path must match, such as `git::*`
+    pub path: Option<glob::Pattern>,
+}
+
+impl SymbolFilter {
+    /// Build a filter from a kind name and a path glob, either of which may be absent
+    pub fn parse(kind: Option<&str>, path: Option<&str>) -> std::result::Result<Self, String> {
+        Ok(Self {
+            kind: kind.map(str::parse).transpose()?,
+            path: path
+                .map(|path| {
+                    glob::Pattern::new(path)
+                        .map_err(|e| format!("Invalid symbol path pattern '{path}': {e}"))
+                })
+                .transpose()?,
+        })
+    }
+
+    /// Whether the filter lets every chunk through
+    pub fn is_empty(&self) -> bool {
+        self.kind.is_none() && self.path.is_none()
+    }
+
+    /// Whether a chunk defines a symbol the filter accepts
+    ///
+    /// Chunks without symbol information only pass an empty filter.
+    pub fn matches(&self, chunk: &CodeChunk) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let Some(symbol) = &chunk.symbol else {
+            return false;
+        };
+        self.kind.as_ref().is_none_or(|kind| symbol.kind == *kind)
+            && self
+                .path
+                .as_ref()
+                .is_none_or(|path| path.matches(&symbol.qualified_path))
+    }
+}
+
+/// How code search ranks chunks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMode {
+    /// Rank by embedding similarity
+    #[default]
+    Semantic,
+    /// Rank by BM25 over chunk text and identifiers
+    Lexical,
+    /// Fuse the semantic and lexical rankings
+    Hybrid,
+}
+
+impl fmt::Display for SearchMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SearchMode::Semantic => "semantic",
+            SearchMode::Lexical => "lexical",
+            SearchMode::Hybrid => "hybrid",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for SearchMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "semantic" => Ok(SearchMode::Semantic),
+            "lexical" => Ok(SearchMode::Lexical),
+            "hybrid" => Ok(SearchMode::Hybrid),
+            other => Err(format!(
+                "Unknown search mode '{other}', expected one of semantic, lexical, hybrid"
+            )),
+        }
+    }
 }
 
 /// Configuration for the semantic search system
@@ -189,6 +358,45 @@ pub struct IndexStats {
     pub embedding_count: usize,
 }
 
+/// Embedding model recorded in an index
+///
+/// Indexes built before models were recorded only know their dimension, and
+/// empty indexes know neither.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexedEmbeddingModel {
+    /// Identifier of the model that made the stored vectors
+    pub model_id: Option<String>,
+    /// Number of components of the stored vectors
+    pub dimensions: Option<usize>,
+}
+
+impl IndexedEmbeddingModel {
+    /// Whether vectors of `model` can be compared with the stored ones
+    ///
+    /// Only an empty index accepts any model. Vectors stored without a
+    /// recorded model may come from any model of their size, so they match none.
+    pub fn matches(&self, model: &crate::search::EmbeddingModelInfo) -> bool {
+        match (&self.model_id, self.dimensions) {
+            (None, None) => true,
+            (None, Some(_)) => false,
+            (Some(id), dimensions) => {
+                *id == model.model_id
+                    && dimensions.is_none_or(|dimensions| dimensions == model.dimensions)
+            }
+        }
+    }
+}
+
+impl fmt::Display for IndexedEmbeddingModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.model_id.as_deref().unwrap_or("an unrecorded model"))?;
+        if let Some(dimensions) = self.dimensions {
+            write!(f, " ({dimensions} dimensions)")?;
+        }
+        Ok(())
+    }
+}
+
 impl FileChangeReport {
     /// Create a new empty change report
     pub fn new() -> Self {
@@ -457,6 +665,7 @@ mod tests {
             end_line: 1,
             chunk_type: ChunkType::Function,
             content_hash: ContentHash("hash123".to_string()),
+            symbol: None,
         };
 
         assert_eq!(chunk.id, "test_chunk");
@@ -497,6 +706,7 @@ mod tests {
             limit: 10,
             similarity_threshold: 0.8,
             language_filter: Some(Language::Rust),
+            symbol_filter: None,
         };
 
         assert_eq!(query.limit, 10);
@@ -993,6 +1203,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_search_mode_parsing() {
+        assert_eq!("hybrid".parse(), Ok(SearchMode::Hybrid));
+        assert_eq!(" Lexical ".parse(), Ok(SearchMode::Lexical));
+        assert_eq!(SearchMode::default().to_string(), "semantic");
+        assert!("fuzzy".parse::<SearchMode>().is_err());
+    }
+
     #[test]
     fn test_semantic_config_environment_variable_override() {
         use tempfile::TempDir;
@@ -1049,14 +1267,24 @@ pub struct SearchExplanation {
 pub struct ResultExplanation {
     /// ID of the chunk
     pub chunk_id: String,
-    /// Similarity score with the query
+    /// Similarity score with the query; 0.0 when only the lexical index matched
     pub similarity_score: f32,
+    /// Position of the chunk in the vector similarity ranking, starting at 1
+    pub vector_rank: Option<usize>,
+    /// BM25 score from the lexical index, for lexical and hybrid searches
+    pub lexical_score: Option<f32>,
+    /// Position of the chunk in the lexical ranking, starting at 1
+    pub lexical_rank: Option<usize>,
+    /// Fused rank score, 1.0 for a chunk every signal ranked first
+    pub fused_score: Option<f32>,
+    /// Cross-encoder relevance between 0.0 and 1.0, when results were reranked
+    pub rerank_score: Option<f32>,
     /// Programming language of the chunk
     pub language: Language,
     /// Type of the code chunk
     pub chunk_type: ChunkType,
     /// Preview of the chunk content (first 100 characters)
     pub content_preview: String,
-    /// Whether this result was above the similarity threshold
+    /// Whether this result was above the similarity threshold, or matched lexically
     pub above_threshold: bool,
 }
@@ -88,6 +88,35 @@ impl SemanticUtils {
         }
     }
 
+    /// Merge several rankings of the same items with reciprocal rank fusion
+    ///
+    /// Each item scores `1 / (k + rank)` in every ranking it appears in, with
+    /// ranks starting at 1, and the scores are summed. Items that rank well in
+    /// several lists rise to the top without the lists' own scores having to be
+    /// comparable. `k` damps the weight of the top ranks; 60 is the usual choice.
+    /// Ties keep the order in which items were first seen.
+    pub fn reciprocal_rank_fusion<T>(rankings: &[Vec<T>], k: f32) -> Vec<(T, f32)>
+    where
+        T: Clone + Eq + std::hash::Hash,
+    {
+        let mut scores: Vec<(T, f32)> = Vec::new();
+        let mut positions = std::collections::HashMap::new();
+        for ranking in rankings {
+            for (rank, item) in ranking.iter().enumerate() {
+                let score = 1.0 / (k + rank as f32 + 1.0);
+                match positions.get(item) {
+                    Some(&position) => scores[position].1 += score,
+                    None => {
+                        positions.insert(item.clone(), scores.len());
+                        scores.push((item.clone(), score));
+                    }
+                }
+            }
+        }
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scores
+    }
+
     /// Generate a unique ID for a code chunk
     pub fn generate_chunk_id(file_path: &Path, start_line: usize, end_line: usize) -> String {
         format!("{}:{}:{}", file_path.display(), start_line, end_line)
@@ -119,6 +148,13 @@ impl SemanticUtils {
             Language::TypeScript => vec!["ts", "tsx"],
             Language::JavaScript => vec!["js", "jsx"],
             Language::Dart => vec!["dart"],
+            Language::Go => vec!["go"],
+            Language::Java => vec!["java"],
+            Language::C => vec!["c", "h"],
+            Language::Cpp => vec!["cpp", "cc", "cxx", "hpp", "hh", "hxx"],
+            Language::CSharp => vec!["cs"],
+            Language::Ruby => vec!["rb", "rake"],
+            Language::Markdown => vec!["md", "markdown"],
             Language::Unknown => vec![], // No specific extensions for unknown languages
         }
     }
@@ -285,6 +321,17 @@ mod tests {
         assert_eq!(SemanticUtils::normalize_text(input), expected);
     }
 
+    #[test]
+    fn test_reciprocal_rank_fusion() {
+        let keyword = vec!["a", "b", "c"];
+        let semantic = vec!["c", "a", "d"];
+        let fused = SemanticUtils::reciprocal_rank_fusion(&[keyword, semantic], 60.0);
+        let order: Vec<&str> = fused.iter().map(|(item, _)| *item).collect();
+        assert_eq!(order, vec!["a", "c", "b", "d"]);
+        assert!((fused[0].1 - (1.0 / 61.0 + 1.0 / 62.0)).abs() < 1e-6);
+        assert!((fused[2].1 - 1.0 / 62.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_cosine_similarity() {
         let a = vec![1.0, 0.0, 0.0];
@@ -0,0 +1,531 @@
+//! Keeping the search index current as files change
+//!
+//! [`watch_index`] subscribes to file system events under a directory and
+//! hands the changed paths to [`FileIndexer::apply_changes`], so only files
+//! that actually changed are re-chunked and re-embedded, and deleted or renamed
+//! files have their vectors removed. Bursts of events are collected for a short
+//! debounce and then processed in batches, leaving out anything `.gitignore`
+//! excludes.
+//!
+//! The database is only opened while a batch is written, so searches from
+//! other processes keep working while the watcher runs. The watcher keeps an
+//! [`IndexWatchStatus`] file next to the database so they can also report how
+//! far behind the index is; a separate thread refreshes it, so a long batch
+//! does not make the watcher look dead.
+
+use crate::search::{
+    EmbeddingEngine, FileIndexer, IndexingReport, Result, SemanticConfig, VectorStorage,
+};
+use crate::workflow::TriggerFileWatcher;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::OsStr;
+use std::future::{self, Future};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Default time file changes are collected before they are indexed
+pub const DEFAULT_INDEX_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Default number of changed files indexed together
+pub const DEFAULT_INDEX_BATCH_SIZE: usize = 64;
+
+/// How often a running watcher refreshes its status file
+pub const STATUS_HEARTBEAT: Duration = Duration::from_secs(10);
+
+/// Longest a burst of changes can postpone indexing, in debounce periods
+const MAX_DEBOUNCE_PERIODS: u32 = 10;
+
+/// Options for watching the index
+#[derive(Debug, Clone)]
+pub struct IndexWatchOptions {
+    /// Time changes are collected before they are indexed
+    pub debounce: Duration,
+    /// Most changed files indexed together, their chunks embedded in one batch
+    pub batch_size: usize,
+}
+
+impl Default for IndexWatchOptions {
+    fn default() -> Self {
+        Self {
+            debounce: DEFAULT_INDEX_DEBOUNCE,
+            batch_size: DEFAULT_INDEX_BATCH_SIZE,
+        }
+    }
+}
+
+/// State of a running index watcher, shared through a file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexWatchStatus {
+    /// Process running the watcher
+    pub pid: u32,
+    /// Directory being watched
+    pub root: PathBuf,
+    /// When the watcher started
+    pub started_at: DateTime<Utc>,
+    /// When the status was last written
+    pub updated_at: DateTime<Utc>,
+    /// Changed paths waiting to be indexed
+    pub queue_depth: usize,
+    /// When the oldest change still waiting was seen
+    pub pending_since: Option<DateTime<Utc>>,
+    /// When changes were last written to the index
+    pub last_indexed_at: Option<DateTime<Utc>>,
+    /// Files indexed since the watcher started
+    pub files_indexed: usize,
+    /// Files removed from the index since the watcher started
+    pub files_removed: usize,
+    /// Files that failed to index since the watcher started
+    pub errors: usize,
+    /// The most recent failure
+    pub last_error: Option<String>,
+}
+
+impl IndexWatchStatus {
+    /// Status of a watcher starting now
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let now = Utc::now();
+        Self {
+            pid: std::process::id(),
+            root: root.into(),
+            started_at: now,
+            updated_at: now,
+            queue_depth: 0,
+            pending_since: None,
+            last_indexed_at: None,
+            files_indexed: 0,
+            files_removed: 0,
+            errors: 0,
+            last_error: None,
+        }
+    }
+
+    /// Where the watcher of the database at `database_path` keeps its status
+    pub fn path_for(database_path: &Path) -> PathBuf {
+        database_path.with_extension("watch.json")
+    }
+
+    /// Read a status file, or `None` if no watcher has written one
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Write the status file, replacing it in one step
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    /// Whether the watcher wrote this status recently enough to be running
+    ///
+    /// A watcher that was killed leaves its status file behind; it is
+    /// considered stopped once it misses a few heartbeats.
+    pub fn is_running(&self, now: DateTime<Utc>) -> bool {
+        ChronoDuration::from_std(STATUS_HEARTBEAT * 3)
+            .is_ok_and(|timeout| now - self.updated_at <= timeout)
+    }
+
+    /// How long the oldest change has been waiting to be indexed
+    pub fn staleness(&self, now: DateTime<Utc>) -> ChronoDuration {
+        self.pending_since
+            .map_or_else(ChronoDuration::zero, |since| now - since)
+    }
+
+    /// Count the outcome of indexing a batch of changes
+    fn record(&mut self, report: &IndexingReport) {
+        self.files_indexed += report.files_successful;
+        self.files_removed += report.files_removed;
+        self.errors += report.files_failed;
+        if let Some((path, error)) = report.errors.last() {
+            self.last_error = Some(format!("{}: {error}", path.display()));
+        }
+        if report.files_successful + report.files_removed > 0 {
+            self.last_indexed_at = Some(Utc::now());
+        }
+    }
+}
+
+/// Changed paths waiting to be indexed
+///
+/// Each change postpones indexing by the debounce, so a burst such as a branch
+/// switch is indexed once it settles, but never by more than
+/// [`MAX_DEBOUNCE_PERIODS`] debounces after the first change.
+#[derive(Debug, Default)]
+struct ChangeQueue {
+    pending: BTreeSet<PathBuf>,
+    first_seen: Option<Instant>,
+    due: Option<Instant>,
+    pending_since: Option<DateTime<Utc>>,
+}
+
+impl ChangeQueue {
+    fn push(&mut self, paths: Vec<PathBuf>, debounce: Duration, now: Instant) {
+        if paths.is_empty() {
+            return;
+        }
+        self.pending.extend(paths);
+        let first_seen = *self.first_seen.get_or_insert(now);
+        self.pending_since.get_or_insert_with(Utc::now);
+        self.due = Some((now + debounce).min(first_seen + debounce * MAX_DEBOUNCE_PERIODS));
+    }
+
+    fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    fn next_due(&self) -> Option<Instant> {
+        self.due
+    }
+
+    /// Take up to `limit` paths if the queue is due at `now`
+    ///
+    /// Paths left over stay due, so they are taken by the next call.
+    fn take_due(&mut self, now: Instant, limit: usize) -> Vec<PathBuf> {
+        if self.due.is_none_or(|due| due > now) {
+            return Vec::new();
+        }
+        let taken: Vec<PathBuf> = self.pending.iter().take(limit.max(1)).cloned().collect();
+        for path in &taken {
+            self.pending.remove(path);
+        }
+        if self.pending.is_empty() {
+            *self = Self::default();
+        }
+        taken
+    }
+}
+
+/// Decides which paths `.gitignore` files exclude
+///
+/// Like git, the closest `.gitignore` deciding a path wins, then
+/// `.git/info/exclude` and the global excludes file. Matchers are read when
+/// first needed and dropped when a `.gitignore` changes.
+struct GitignoreFilter {
+    root: PathBuf,
+    matchers: HashMap<PathBuf, Option<Gitignore>>,
+    global: Gitignore,
+}
+
+impl GitignoreFilter {
+    fn new(root: &Path) -> Result<Self> {
+        let (global, error) = Gitignore::global();
+        if let Some(e) = error {
+            tracing::debug!("Failed to read global gitignore: {}", e);
+        }
+        Ok(Self {
+            root: root.canonicalize()?,
+            matchers: HashMap::new(),
+            global,
+        })
+    }
+
+    /// Forget the matchers read so far
+    fn reload(&mut self) {
+        self.matchers.clear();
+    }
+
+    /// Whether a path relative to the root is ignored
+    fn is_ignored(&mut self, path: &Path) -> bool {
+        let full_path = self.root.join(path);
+        let is_dir = full_path.is_dir();
+        for dir in path.ancestors().skip(1) {
+            if let Some(matcher) = self.matcher(dir) {
+                let matched = matcher.matched_path_or_any_parents(&full_path, is_dir);
+                if matched.is_ignore() || matched.is_whitelist() {
+                    return matched.is_ignore();
+                }
+            }
+        }
+        self.global
+            .matched_path_or_any_parents(&full_path, is_dir)
+            .is_ignore()
+    }
+
+    /// The matcher for `.gitignore` in a directory relative to the root
+    fn matcher(&mut self, dir: &Path) -> Option<&Gitignore> {
+        let root = &self.root;
+        self.matchers
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                let dir = root.join(dir);
+                let mut files = vec![dir.join(".gitignore")];
+                if dir == *root {
+                    files.push(root.join(".git").join("info").join("exclude"));
+                }
+
+                let mut builder = GitignoreBuilder::new(&dir);
+                for file in files.iter().filter(|file| file.is_file()) {
+                    if let Some(e) = builder.add(file) {
+                        tracing::warn!("Failed to read {}: {}", file.display(), e);
+                    }
+                }
+                builder.build().ok().filter(|matcher| !matcher.is_empty())
+            })
+            .as_ref()
+    }
+}
+
+/// Writes the status file after every change and on a heartbeat of its own
+///
+/// The heartbeat runs on its own thread so it keeps going while indexing
+/// holds up the watcher's task.
+struct StatusWriter {
+    status: Arc<Mutex<IndexWatchStatus>>,
+    path: PathBuf,
+    stop: Option<mpsc::Sender<()>>,
+    heartbeat: Option<JoinHandle<()>>,
+}
+
+impl StatusWriter {
+    fn start(status: IndexWatchStatus, path: PathBuf) -> Self {
+        let status = Arc::new(Mutex::new(status));
+        let (stop, stopped) = mpsc::channel::<()>();
+        let heartbeat = {
+            let status = Arc::clone(&status);
+            let path = path.clone();
+            std::thread::spawn(move || {
+                while let Err(mpsc::RecvTimeoutError::Timeout) =
+                    stopped.recv_timeout(STATUS_HEARTBEAT)
+                {
+                    if let Ok(mut status) = status.lock() {
+                        Self::write(&mut status, &path);
+                    }
+                }
+            })
+        };
+        Self {
+            status,
+            path,
+            stop: Some(stop),
+            heartbeat: Some(heartbeat),
+        }
+    }
+
+    /// Change the status and write it
+    fn update(&self, change: impl FnOnce(&mut IndexWatchStatus)) {
+        if let Ok(mut status) = self.status.lock() {
+            change(&mut status);
+            Self::write(&mut status, &self.path);
+        }
+    }
+
+    fn write(status: &mut IndexWatchStatus, path: &Path) {
+        status.updated_at = Utc::now();
+        if let Err(e) = status.save(path) {
+            tracing::warn!("Failed to write index watch status: {}", e);
+        }
+    }
+
+    /// Stop the heartbeat and remove the status file
+    fn finish(mut self) {
+        self.stop.take();
+        if let Some(heartbeat) = self.heartbeat.take() {
+            let _ = heartbeat.join();
+        }
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            tracing::debug!("Failed to remove index watch status: {}", e);
+        }
+    }
+}
+
+/// Index a batch of changed paths, opening the database for just this batch
+async fn apply_batch(
+    config: &SemanticConfig,
+    embedding_engine: &EmbeddingEngine,
+    batch: &[PathBuf],
+) -> Result<IndexingReport> {
+    let storage = VectorStorage::new(config.clone())?;
+    storage.initialize()?;
+    let mut indexer =
+        FileIndexer::with_custom_embedding_engine(storage, embedding_engine.clone()).await?;
+    indexer.apply_changes(batch).await
+}
+
+/// Keep the index in `config` current with changes under `root` until
+/// `shutdown` resolves
+///
+/// Paths are passed to the indexer joined to `root` as given, so a relative
+/// root indexes paths the same way `search index` does when run from it.
+/// Progress is written to the status file of the database, which is removed
+/// on shutdown.
+pub async fn watch_index(
+    config: &SemanticConfig,
+    embedding_engine: EmbeddingEngine,
+    root: &Path,
+    options: &IndexWatchOptions,
+    shutdown: impl Future<Output = ()>,
+) -> Result<()> {
+    let status_path = IndexWatchStatus::path_for(&config.database_path);
+    let mut watcher = TriggerFileWatcher::new(root)?;
+    let mut ignores = GitignoreFilter::new(root)?;
+    let mut queue = ChangeQueue::default();
+    let status = StatusWriter::start(IndexWatchStatus::new(root), status_path);
+    status.update(|_| {});
+    tokio::pin!(shutdown);
+
+    tracing::info!("👀 Watching {} for changes to index", root.display());
+
+    loop {
+        let next_due = queue.next_due();
+        let wait = async {
+            match next_due {
+                Some(due) => tokio::time::sleep_until(due.into()).await,
+                None => future::pending().await,
+            }
+        };
+
+        let mut outcome = None;
+        tokio::select! {
+            _ = &mut shutdown => break,
+            changes = watcher.next_changes() => {
+                let Some(paths) = changes else { break };
+                if paths
+                    .iter()
+                    .any(|path| path.file_name() == Some(OsStr::new(".gitignore")))
+                {
+                    ignores.reload();
+                }
+                let relevant: Vec<PathBuf> = paths
+                    .into_iter()
+                    .filter(|path| !ignores.is_ignored(path))
+                    .collect();
+                queue.push(relevant, options.debounce, Instant::now());
+            }
+            _ = wait => {
+                let batch: Vec<PathBuf> = queue
+                    .take_due(Instant::now(), options.batch_size)
+                    .into_iter()
+                    .map(|path| root.join(path))
+                    .collect();
+                status.update(|status| status.queue_depth = queue.len());
+                let result = apply_batch(config, &embedding_engine, &batch).await;
+                outcome = Some((batch.len(), result));
+            }
+        }
+
+        status.update(|status| {
+            match outcome {
+                Some((_, Ok(report))) => {
+                    if report.files_processed + report.files_removed > 0 {
+                        tracing::info!("🔄 {}, {} removed", report.summary(), report.files_removed);
+                    }
+                    status.record(&report);
+                }
+                Some((batch_len, Err(e))) => {
+                    tracing::error!("Failed to index changes: {}", e);
+                    status.errors += batch_len;
+                    status.last_error = Some(e.to_string());
+                }
+                None => {}
+            }
+            status.queue_depth = queue.len();
+            status.pending_since = queue.pending_since;
+        });
+    }
+
+    tracing::info!("Stopping index watch");
+    status.finish();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_change_queue_debounces_and_batches() {
+        let debounce = Duration::from_millis(100);
+        let start = Instant::now();
+        let mut queue = ChangeQueue::default();
+
+        queue.push(vec![PathBuf::from("b.rs")], debounce, start);
+        queue.push(
+            vec![PathBuf::from("a.rs"), PathBuf::from("b.rs")],
+            debounce,
+            start + Duration::from_millis(50),
+        );
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.next_due(), Some(start + Duration::from_millis(150)));
+        assert!(queue
+            .take_due(start + Duration::from_millis(120), 10)
+            .is_empty());
+
+        let due = start + Duration::from_millis(150);
+        assert_eq!(queue.take_due(due, 1), vec![PathBuf::from("a.rs")]);
+        assert_eq!(queue.take_due(due, 1), vec![PathBuf::from("b.rs")]);
+        assert_eq!(queue.next_due(), None);
+        assert!(queue.pending_since.is_none());
+    }
+
+    #[test]
+    fn test_change_queue_caps_debounce() {
+        let debounce = Duration::from_millis(100);
+        let start = Instant::now();
+        let mut queue = ChangeQueue::default();
+
+        for step in 0..20 {
+            queue.push(
+                vec![PathBuf::from("a.rs")],
+                debounce,
+                start + Duration::from_millis(step * 90),
+            );
+        }
+        assert_eq!(queue.next_due(), Some(start + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_gitignore_filter() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join(".gitignore"), "*.log\ngenerated/\n").unwrap();
+        std::fs::create_dir_all(root.join("src/generated")).unwrap();
+        std::fs::create_dir_all(root.join("vendor")).unwrap();
+        std::fs::write(root.join("vendor/.gitignore"), "*.rs\n!keep.rs\n").unwrap();
+
+        let mut filter = GitignoreFilter::new(root).unwrap();
+        assert!(!filter.is_ignored(Path::new("src/main.rs")));
+        assert!(filter.is_ignored(Path::new("debug.log")));
+        assert!(filter.is_ignored(Path::new("src/generated/api.rs")));
+        assert!(filter.is_ignored(Path::new("vendor/lib.rs")));
+        assert!(!filter.is_ignored(Path::new("vendor/keep.rs")));
+
+        std::fs::write(root.join(".gitignore"), "").unwrap();
+        filter.reload();
+        assert!(!filter.is_ignored(Path::new("debug.log")));
+    }
+
+    #[test]
+    fn test_index_watch_status_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let status_path = IndexWatchStatus::path_for(&temp_dir.path().join("semantic.db"));
+        assert_eq!(status_path, temp_dir.path().join("semantic.watch.json"));
+        assert!(IndexWatchStatus::load(&status_path).unwrap().is_none());
+
+        let mut status = IndexWatchStatus::new(".");
+        let now = status.updated_at;
+        assert!(status.is_running(now));
+        assert!(!status.is_running(now + ChronoDuration::minutes(5)));
+        assert_eq!(status.staleness(now), ChronoDuration::zero());
+
+        status.queue_depth = 3;
+        status.pending_since = Some(now - ChronoDuration::seconds(2));
+        assert_eq!(status.staleness(now), ChronoDuration::seconds(2));
+
+        status.save(&status_path).unwrap();
+        assert_eq!(IndexWatchStatus::load(&status_path).unwrap(), Some(status));
+    }
+}
@@ -1,9 +1,10 @@
 //! Action parsing utilities for workflow state descriptions
 
 use crate::workflow::actions::{
-    AbortAction, ActionError, ActionResult, LogAction, LogLevel, PromptAction, SetVariableAction,
-    ShellAction, SubWorkflowAction, WaitAction,
+    AbortAction, ActionError, ActionResult, ApprovalAction, LogAction, LogLevel, PromptAction,
+    SetVariableAction, ShellAction, SubWorkflowAction, WaitAction,
 };
+use crate::workflow::retry::{ErrorClass, RetryPolicy};
 use chumsky::prelude::*;
 use regex::Regex;
 use serde_json::Value;
@@ -277,6 +278,22 @@ impl ActionParser {
         }
     }
 
+    /// Parse an approval action from description
+    /// Format: await approval "message"
+    pub fn parse_approval_action(&self, description: &str) -> ActionResult<Option<ApprovalAction>> {
+        let parser = Self::case_insensitive("await")
+            .then_ignore(Self::whitespace())
+            .then_ignore(Self::case_insensitive("approval"))
+            .then_ignore(Self::whitespace())
+            .ignore_then(Self::quoted_string())
+            .then_ignore(Self::opt_whitespace());
+
+        match parser.parse(description.trim()).into_result() {
+            Ok(message) => Ok(Some(ApprovalAction::new(message))),
+            Err(_) => Ok(None),
+        }
+    }
+
     /// Parse a sub-workflow action from description
     /// Format: Run workflow "workflow-name" with input1="value1" input2="value2"
     /// Format: Delegate to "workflow-name" with input="${data}"
@@ -515,6 +532,150 @@ impl ActionParser {
         }
     }
 
+    /// Parse a retry policy from a `**Retry**:` declaration
+    /// Format: max_attempts=3 backoff=exponential delay=1s max_delay=30s jitter=true retry_on=timeout,command
+    ///
+    /// Every setting is optional and falls back to [`RetryPolicy::default`].
+    pub fn parse_retry_policy(&self, spec: &str) -> ActionResult<RetryPolicy> {
+        let param_parser = Self::argument_key().then_ignore(just('=')).then(choice((
+            Self::quoted_string(),
+            none_of(" \t").repeated().at_least(1).collect::<String>(),
+        )));
+
+        let parser = Self::opt_whitespace()
+            .ignore_then(
+                param_parser
+                    .separated_by(Self::whitespace())
+                    .collect::<Vec<(String, String)>>(),
+            )
+            .then_ignore(Self::opt_whitespace());
+
+        let params = parser.parse(spec.trim()).into_result().map_err(|_| {
+            ActionError::ParseError(format!(
+                "Invalid retry policy '{spec}'. Expected key=value settings such as 'max_attempts=3 backoff=exponential delay=1s'"
+            ))
+        })?;
+
+        self.retry_policy_from_params(params)
+    }
+
+    /// Build a retry policy from a front matter value
+    ///
+    /// The value is either a mapping with the same keys as the `**Retry**:`
+    /// syntax or a number, which is shorthand for `max_attempts`.
+    pub fn retry_policy_from_value(&self, value: &Value) -> ActionResult<RetryPolicy> {
+        match value {
+            Value::Number(_) => {
+                self.retry_policy_from_params(vec![("max_attempts".to_string(), value.to_string())])
+            }
+            Value::Object(settings) => {
+                let params = settings
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = match value {
+                            Value::Array(items) => items
+                                .iter()
+                                .map(|item| self.value_to_string(item))
+                                .collect::<Vec<_>>()
+                                .join(","),
+                            other => self.value_to_string(other),
+                        };
+                        (key.clone(), value)
+                    })
+                    .collect();
+                self.retry_policy_from_params(params)
+            }
+            other => Err(ActionError::ParseError(format!(
+                "Invalid retry policy {other}: expected a mapping of settings or a number of attempts"
+            ))),
+        }
+    }
+
+    /// Parse a duration from a front matter value
+    ///
+    /// Strings use the timeout syntax such as `500ms`, `30s` or `2h`; numbers
+    /// are taken as seconds.
+    pub fn duration_from_value(&self, value: &Value) -> ActionResult<Duration> {
+        match value {
+            Value::String(_) | Value::Number(_) => {
+                self.parse_timeout_value(&self.value_to_string(value))
+            }
+            other => Err(ActionError::ParseError(format!(
+                "Invalid duration {other}: expected a value like '30s' or '2h'"
+            ))),
+        }
+    }
+
+    /// Apply retry settings on top of the default policy
+    fn retry_policy_from_params(&self, params: Vec<(String, String)>) -> ActionResult<RetryPolicy> {
+        let mut policy = RetryPolicy::default();
+        let mut max_delay_set = false;
+
+        for (key, value) in params {
+            match key.as_str() {
+                "max_attempts" | "attempts" => {
+                    policy.max_attempts = value.parse::<u32>().map_err(|_| {
+                        ActionError::ParseError(format!("Invalid max_attempts value: {value}"))
+                    })?;
+                    if policy.max_attempts == 0 {
+                        return Err(ActionError::ParseError(
+                            "max_attempts must be at least 1".to_string(),
+                        ));
+                    }
+                }
+                "backoff" => {
+                    policy.backoff = value.parse().map_err(ActionError::ParseError)?;
+                }
+                "delay" | "initial_delay" => {
+                    policy.delay = self.parse_timeout_value(&value)?;
+                }
+                "max_delay" => {
+                    policy.max_delay = self.parse_timeout_value(&value)?;
+                    max_delay_set = true;
+                }
+                "jitter" => {
+                    policy.jitter = value.parse::<bool>().map_err(|_| {
+                        ActionError::ParseError(format!(
+                            "Invalid jitter value: {value}. Expected true or false"
+                        ))
+                    })?;
+                }
+                "retry_on" | "on" => {
+                    policy.retry_on =
+                        if value.eq_ignore_ascii_case("any") || value.eq_ignore_ascii_case("all") {
+                            ErrorClass::ALL.to_vec()
+                        } else {
+                            value
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|class| !class.is_empty())
+                                .map(|class| class.parse::<ErrorClass>())
+                                .collect::<Result<Vec<_>, _>>()
+                                .map_err(ActionError::ParseError)?
+                        };
+                }
+                _ => {
+                    return Err(ActionError::ParseError(format!(
+                        "Unknown retry policy setting: {key}"
+                    )));
+                }
+            }
+        }
+
+        if policy.max_delay < policy.delay {
+            if !max_delay_set {
+                policy.max_delay = policy.delay;
+                return Ok(policy);
+            }
+            return Err(ActionError::ParseError(format!(
+                "Retry max_delay {:?} is shorter than delay {:?}",
+                policy.max_delay, policy.delay
+            )));
+        }
+
+        Ok(policy)
+    }
+
     /// Safely substitute variables in a string using regex
     pub fn substitute_variables_safe(
         &self,
@@ -837,6 +998,32 @@ mod tests {
         assert!(result.unwrap().is_none());
     }
 
+    #[test]
+    fn test_parse_approval_action() {
+        let parser = ActionParser::new().unwrap();
+
+        let action = parser
+            .parse_approval_action("await approval \"Deploy ${version} to production?\"")
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.message, "Deploy ${version} to production?");
+
+        let action = parser
+            .parse_approval_action("Await Approval \"Merge?\"")
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.message, "Merge?");
+
+        assert!(parser
+            .parse_approval_action("await approval")
+            .unwrap()
+            .is_none());
+        assert!(parser
+            .parse_approval_action("Wait for user approval")
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn test_parse_shell_action_basic() {
         let parser = ActionParser::new().unwrap();
@@ -1074,4 +1261,93 @@ mod tests {
             Some("/home/user/projects/app".to_string())
         );
     }
+
+    #[test]
+    fn test_parse_retry_policy() {
+        use crate::workflow::retry::BackoffStrategy;
+
+        let parser = ActionParser::new().unwrap();
+
+        let policy = parser
+            .parse_retry_policy(
+                "max_attempts=5 backoff=linear delay=250ms max_delay=2s jitter=true retry_on=timeout,rate-limit",
+            )
+            .unwrap();
+        assert_eq!(policy.max_attempts, 5);
+        assert_eq!(policy.backoff, BackoffStrategy::Linear);
+        assert_eq!(policy.delay, Duration::from_millis(250));
+        assert_eq!(policy.max_delay, Duration::from_secs(2));
+        assert!(policy.jitter);
+        assert_eq!(
+            policy.retry_on,
+            vec![ErrorClass::Timeout, ErrorClass::RateLimit]
+        );
+
+        // Unspecified settings use the defaults, and the policy round-trips
+        let policy = parser.parse_retry_policy("attempts=2").unwrap();
+        assert_eq!(
+            policy,
+            RetryPolicy {
+                max_attempts: 2,
+                ..RetryPolicy::default()
+            }
+        );
+        assert_eq!(
+            parser.parse_retry_policy(&policy.to_string()).unwrap(),
+            policy
+        );
+
+        assert!(parser.parse_retry_policy("max_attempts=0").is_err());
+        assert!(parser.parse_retry_policy("backoff=sometimes").is_err());
+        assert!(parser.parse_retry_policy("retry_on=flaky").is_err());
+        assert!(parser.parse_retry_policy("tries=3").is_err());
+        assert!(parser.parse_retry_policy("delay=10s max_delay=1s").is_err());
+    }
+
+    #[test]
+    fn test_retry_policy_from_front_matter_value() {
+        let parser = ActionParser::new().unwrap();
+
+        let policy = parser
+            .retry_policy_from_value(&serde_json::json!({
+                "max_attempts": 4,
+                "delay": "2s",
+                "jitter": false,
+                "retry_on": ["command", "io"]
+            }))
+            .unwrap();
+        assert_eq!(policy.max_attempts, 4);
+        assert_eq!(policy.delay, Duration::from_secs(2));
+        assert_eq!(policy.retry_on, vec![ErrorClass::Command, ErrorClass::Io]);
+
+        let policy = parser
+            .retry_policy_from_value(&serde_json::json!(2))
+            .unwrap();
+        assert_eq!(policy.max_attempts, 2);
+
+        assert!(parser
+            .retry_policy_from_value(&serde_json::json!("often"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_duration_from_value() {
+        let parser = ActionParser::new().unwrap();
+        assert_eq!(
+            parser
+                .duration_from_value(&serde_json::json!("2h"))
+                .unwrap(),
+            Duration::from_secs(7200)
+        );
+        assert_eq!(
+            parser.duration_from_value(&serde_json::json!(45)).unwrap(),
+            Duration::from_secs(45)
+        );
+        assert!(parser
+            .duration_from_value(&serde_json::json!("soon"))
+            .is_err());
+        assert!(parser
+            .duration_from_value(&serde_json::json!(["1s"]))
+            .is_err());
+    }
 }
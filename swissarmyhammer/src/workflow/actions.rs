@@ -8,9 +8,14 @@ use crate::shell_security::{
 };
 
 use crate::workflow::action_parser::ActionParser;
+use crate::workflow::approval::{
+    take_approval_decision, ApprovalDecision, APPROVAL_CONTEXT_KEY, APPROVAL_REQUEST_KEY,
+};
+use crate::workflow::event_log::{record_action_event, EventLevel};
 use crate::workflow::mcp_integration::{response_processing, WorkflowShellContext};
 use crate::workflow::{
-    WorkflowExecutor, WorkflowName, WorkflowRunStatus, WorkflowStorage, WorkflowTemplateContext,
+    ExecutionEventType, WorkflowExecutor, WorkflowName, WorkflowRunStatus, WorkflowStorage,
+    WorkflowTemplateContext,
 };
 
 use serde_json::Value;
@@ -26,9 +31,14 @@ use tokio::time::timeout;
 
 use crate::{PromptLibrary, PromptResolver};
 use async_trait::async_trait;
-use swissarmyhammer_config::agent::{AgentConfig, AgentExecutorType, LlamaAgentConfig};
+use swissarmyhammer_config::agent::{
+    AgentConfig, AgentExecutorType, LlamaAgentConfig, OpenAiCompatibleConfig,
+};
 
-use super::agents::LlamaAgentExecutor;
+use super::agents::{
+    LlamaAgentExecutor, OpenAiCompatibleExecutor, RecordingExecutor, ReplayExecutor,
+    RECORD_CASSETTE_KEY, REPLAY_CASSETTE_KEY, REPLAY_POSITIONS_KEY,
+};
 
 thread_local! {
     /// Thread-local test storage registry for tests
@@ -103,6 +113,11 @@ pub enum ActionError {
     /// Shell security validation error
     #[error("Shell security error: {0}")]
     ShellSecurityError(#[from] ShellSecurityError),
+    /// A person rejected the run at an approval gate
+    ///
+    /// A rejection is a decision rather than a failure, so it is never retried.
+    #[error("Approval rejected by {0}")]
+    ApprovalRejected(String),
 }
 
 /// Result type for action operations
@@ -160,6 +175,19 @@ fn get_llama_config_from_context(
     }
 }
 
+/// Extract OpenAI-compatible endpoint configuration from execution context
+fn get_openai_config_from_context(
+    context: &AgentExecutionContext<'_>,
+) -> ActionResult<OpenAiCompatibleConfig> {
+    match context.agent_config().executor {
+        swissarmyhammer_config::agent::AgentExecutorConfig::OpenAiCompatible(config) => Ok(config),
+        _ => Err(ActionError::ExecutionError(
+            "Expected OpenAI-compatible configuration but found different executor type"
+                .to_string(),
+        )),
+    }
+}
+
 /// Agent execution context for prompt execution
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -190,6 +218,23 @@ impl<'a> AgentExecutionContext<'a> {
     pub fn quiet(&self) -> bool {
         self.agent_config().quiet
     }
+
+    /// Get the cassette path stored under `key`, if any
+    pub fn cassette_path(&self, key: &str) -> Option<std::path::PathBuf> {
+        self.workflow_context
+            .get(key)
+            .and_then(|value| value.as_str())
+            .filter(|path| !path.is_empty())
+            .map(std::path::PathBuf::from)
+    }
+
+    /// Get the replay positions checkpointed under [`REPLAY_POSITIONS_KEY`]
+    pub fn replay_positions(&self) -> HashMap<String, usize> {
+        self.workflow_context
+            .get(REPLAY_POSITIONS_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default()
+    }
 }
 
 /// Agent executor trait for abstracting prompt execution across different AI backends
@@ -222,8 +267,30 @@ pub struct AgentExecutorFactory;
 #[allow(dead_code)]
 impl AgentExecutorFactory {
     /// Create an executor based on the execution context
+    ///
+    /// When the context names a replay cassette, responses are served from it
+    /// and no agent is started. When it names a recording cassette, the
+    /// configured executor is wrapped so its responses are written to it.
     pub async fn create_executor(
         context: &AgentExecutionContext<'_>,
+    ) -> ActionResult<Box<dyn AgentExecutor>> {
+        if let Some(path) = context.cassette_path(REPLAY_CASSETTE_KEY) {
+            let mut executor =
+                ReplayExecutor::resume(path, context.executor_type(), context.replay_positions())?;
+            executor.initialize().await?;
+            return Ok(Box::new(executor));
+        }
+
+        let executor = Self::create_configured_executor(context).await?;
+        match context.cassette_path(RECORD_CASSETTE_KEY) {
+            Some(path) => Ok(Box::new(RecordingExecutor::new(executor, path)?)),
+            None => Ok(executor),
+        }
+    }
+
+    /// Create the executor selected by the agent configuration
+    async fn create_configured_executor(
+        context: &AgentExecutionContext<'_>,
     ) -> ActionResult<Box<dyn AgentExecutor>> {
         match context.executor_type() {
             AgentExecutorType::ClaudeCode => {
@@ -237,6 +304,12 @@ impl AgentExecutorFactory {
                 executor.initialize().await?;
                 Ok(Box::new(executor))
             }
+            AgentExecutorType::OpenAiCompatible => {
+                let openai_config = get_openai_config_from_context(context)?;
+                let mut executor = OpenAiCompatibleExecutor::new(openai_config);
+                executor.initialize().await?;
+                Ok(Box::new(executor))
+            }
         }
     }
 }
@@ -800,6 +873,17 @@ impl PromptAction {
 
         // Log the actual prompt being sent to Claude
         tracing::debug!("Piping prompt to Claude:\n{}", user_prompt);
+        record_action_event(
+            context,
+            ExecutionEventType::PromptRendered,
+            EventLevel::Debug,
+            format!("Rendered prompt '{}'", self.prompt_name),
+            Some(serde_json::json!({
+                "prompt": self.prompt_name,
+                "system_prompt": system_prompt,
+                "user_prompt": user_prompt,
+            })),
+        );
 
         // Check if quiet mode is enabled in the context
         let quiet = self.quiet
@@ -854,6 +938,21 @@ impl PromptAction {
             tracing::info!("{}", yaml_output);
         }
 
+        record_action_event(
+            context,
+            ExecutionEventType::AgentResponse,
+            EventLevel::Info,
+            format!(
+                "Agent responded to prompt '{}' ({} characters)",
+                self.prompt_name,
+                response_text.len()
+            ),
+            Some(serde_json::json!({
+                "prompt": self.prompt_name,
+                "response": response,
+            })),
+        );
+
         // Store result in context if variable name specified
         if let Some(var_name) = &self.result_variable {
             context.insert(var_name.clone(), response.clone());
@@ -1027,11 +1126,27 @@ impl Action for LogAction {
         // Render message with liquid templating (supports {{variable}} syntax)
         let message = render_with_liquid_template(&self.message, &context.to_workflow_hashmap());
 
-        match self.level {
-            LogLevel::Info => tracing::info!("{}", message),
-            LogLevel::Warning => tracing::warn!("{}", message),
-            LogLevel::Error => tracing::error!("{}", message),
-        }
+        let event_level = match self.level {
+            LogLevel::Info => {
+                tracing::info!("{}", message);
+                EventLevel::Info
+            }
+            LogLevel::Warning => {
+                tracing::warn!("{}", message);
+                EventLevel::Warn
+            }
+            LogLevel::Error => {
+                tracing::error!("{}", message);
+                EventLevel::Error
+            }
+        };
+        record_action_event(
+            context,
+            ExecutionEventType::StateExecution,
+            event_level,
+            message.clone(),
+            None,
+        );
 
         // Mark action as successful
         context.insert(LAST_ACTION_RESULT_KEY.to_string(), Value::Bool(true));
@@ -1164,6 +1279,65 @@ impl Action for AbortAction {
     impl_as_any!();
 }
 
+/// Action that pauses the run until a person approves or rejects it
+#[derive(Debug, Clone)]
+pub struct ApprovalAction {
+    /// Message shown to the approver
+    pub message: String,
+}
+
+impl ApprovalAction {
+    /// Create a new approval action
+    pub fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl VariableSubstitution for ApprovalAction {}
+
+#[async_trait::async_trait]
+impl Action for ApprovalAction {
+    async fn execute(&self, context: &mut WorkflowTemplateContext) -> ActionResult<Value> {
+        let Some(record) = take_approval_decision(context) else {
+            // No decision yet, so ask the executor to pause the run until there is one
+            let message = self.substitute_string(&self.message, context);
+            context.insert(APPROVAL_REQUEST_KEY.to_string(), Value::String(message));
+            return Ok(Value::Null);
+        };
+
+        context.insert(
+            APPROVAL_CONTEXT_KEY.to_string(),
+            serde_json::to_value(&record)?,
+        );
+
+        match record.decision {
+            ApprovalDecision::Approved => {
+                context.insert(LAST_ACTION_RESULT_KEY.to_string(), Value::Bool(true));
+                Ok(Value::Bool(true))
+            }
+            ApprovalDecision::Rejected => Err(ActionError::ApprovalRejected(format!(
+                "{}{}",
+                record.approver,
+                record
+                    .reason
+                    .as_deref()
+                    .map(|reason| format!(": {reason}"))
+                    .unwrap_or_default()
+            ))),
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("Await approval: {}", self.message)
+    }
+
+    fn action_type(&self) -> &'static str {
+        "approval"
+    }
+
+    impl_as_any!();
+}
+
 /// Validate that an argument key is safe for command-line use
 fn is_valid_argument_key(key: &str) -> bool {
     !key.is_empty()
@@ -1491,6 +1665,23 @@ impl ShellAction {
 
         // Log command completion with comprehensive security audit logging
         log_shell_completion(command, exit_code as i32, execution_time_ms);
+        record_action_event(
+            context,
+            ExecutionEventType::ShellOutput,
+            if success {
+                EventLevel::Info
+            } else {
+                EventLevel::Warn
+            },
+            format!("Shell command '{command}' exited with code {exit_code}"),
+            Some(serde_json::json!({
+                "command": command,
+                "exit_code": exit_code,
+                "stdout": stdout,
+                "stderr": stderr,
+                "duration_ms": execution_time_ms,
+            })),
+        );
 
         // Return result in existing format for backward compatibility
         if success {
@@ -2118,6 +2309,11 @@ pub fn parse_action_from_description(description: &str) -> ActionResult<Option<B
         return Ok(Some(Box::new(prompt_action)));
     }
 
+    // Checked before waits, whose "wait for user" match could catch an approval message
+    if let Some(approval_action) = parser.parse_approval_action(description)? {
+        return Ok(Some(Box::new(approval_action)));
+    }
+
     if let Some(wait_action) = parser.parse_wait_action(description)? {
         return Ok(Some(Box::new(wait_action)));
     }
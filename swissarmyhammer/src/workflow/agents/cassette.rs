@@ -0,0 +1,383 @@
+//! Cassette files holding recorded agent conversations
+//!
+//! A cassette stores the responses an agent executor gave for each rendered
+//! prompt, keyed by a hash of the prompt text. [`RecordingExecutor`] writes them
+//! and [`ReplayExecutor`] serves them back, so workflows that prompt an agent can
+//! be run deterministically without a live model.
+//!
+//! [`RecordingExecutor`]: super::RecordingExecutor
+//! [`ReplayExecutor`]: super::ReplayExecutor
+
+use crate::{Result, SwissArmyHammerError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Context key naming the cassette that agent responses are recorded to
+pub const RECORD_CASSETTE_KEY: &str = "_agent_record_cassette";
+
+/// Context key naming the cassette that agent responses are replayed from
+pub const REPLAY_CASSETTE_KEY: &str = "_agent_replay_cassette";
+
+/// Context key holding how many responses of each prompt a run has replayed
+///
+/// Checkpointed with the run, so a resumed run continues replaying where it
+/// stopped instead of from the first recorded response.
+pub const REPLAY_POSITIONS_KEY: &str = "_agent_replay_positions";
+
+/// Current cassette file format version
+const CASSETTE_VERSION: u32 = 1;
+
+/// Cassette shared between the executors created for a single process
+pub type SharedCassette = Arc<Mutex<Cassette>>;
+
+/// Cassettes in use by running workflows, keyed by path
+///
+/// A new executor is created for every prompt action, so recorded responses and
+/// replay positions have to outlive the executor that used them. Entries are
+/// removed with [`Cassette::close`] once the run using them stops.
+static OPEN_CASSETTES: Mutex<Option<HashMap<PathBuf, SharedCassette>>> = Mutex::new(None);
+
+/// Responses recorded for a single rendered prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CassetteEntry {
+    /// System prompt sent alongside the rendered prompt
+    #[serde(default)]
+    pub system_prompt: String,
+    /// Rendered prompt the responses belong to
+    pub prompt: String,
+    /// Responses in the order they were recorded
+    pub responses: Vec<Value>,
+}
+
+/// Recorded agent responses keyed by prompt hash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cassette {
+    /// Cassette file format version
+    pub version: u32,
+    /// Recorded entries keyed by [`Cassette::prompt_key`]
+    #[serde(default)]
+    pub interactions: BTreeMap<String, CassetteEntry>,
+    /// File the cassette is stored in
+    #[serde(skip)]
+    path: PathBuf,
+    /// Number of responses already replayed for each key
+    #[serde(skip)]
+    replay_positions: HashMap<String, usize>,
+}
+
+impl Cassette {
+    /// Create an empty cassette stored at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            version: CASSETTE_VERSION,
+            interactions: BTreeMap::new(),
+            path: path.into(),
+            replay_positions: HashMap::new(),
+        }
+    }
+
+    /// Load a cassette from `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            SwissArmyHammerError::Other(format!("Failed to read cassette {}: {e}", path.display()))
+        })?;
+        let mut cassette: Cassette = serde_yaml::from_str(&content).map_err(|e| {
+            SwissArmyHammerError::Other(format!("Failed to parse cassette {}: {e}", path.display()))
+        })?;
+        if cassette.version > CASSETTE_VERSION {
+            return Err(SwissArmyHammerError::Other(format!(
+                "Cassette {} has unsupported version {}",
+                path.display(),
+                cassette.version
+            )));
+        }
+        cassette.path = path.to_path_buf();
+        Ok(cassette)
+    }
+
+    /// Write the cassette to its file
+    ///
+    /// The file is written to a temporary sibling and renamed into place so an
+    /// interrupted run never leaves a truncated cassette behind.
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let content = serde_yaml::to_string(self).map_err(|e| {
+            SwissArmyHammerError::Other(format!("Failed to serialize cassette: {e}"))
+        })?;
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+        std::fs::write(&temp_path, content)?;
+        std::fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+
+    /// Path of the cassette file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Key under which responses to `rendered_prompt` are stored
+    pub fn prompt_key(rendered_prompt: &str) -> String {
+        format!("{:x}", md5::compute(rendered_prompt.as_bytes()))
+    }
+
+    /// Append a response recorded for `rendered_prompt`
+    pub fn record(&mut self, system_prompt: &str, rendered_prompt: &str, response: Value) {
+        self.interactions
+            .entry(Self::prompt_key(rendered_prompt))
+            .or_insert_with(|| CassetteEntry {
+                system_prompt: system_prompt.to_string(),
+                prompt: rendered_prompt.to_string(),
+                responses: Vec::new(),
+            })
+            .responses
+            .push(response);
+    }
+
+    /// Get the next recorded response for `rendered_prompt`
+    ///
+    /// Responses to a prompt that was sent several times are returned in the
+    /// order they were recorded. Fails, naming the prompt and the cassette, if
+    /// the prompt was never recorded or was sent more often than during
+    /// recording, since the workflow no longer matches the cassette either way.
+    pub fn next_response(&mut self, rendered_prompt: &str) -> Result<Value> {
+        let key = Self::prompt_key(rendered_prompt);
+        let first_line = rendered_prompt.lines().next().unwrap_or_default();
+        let Some(entry) = self.interactions.get(&key) else {
+            return Err(SwissArmyHammerError::Other(format!(
+                "No recorded response for prompt {key} in cassette {} (prompt starts with: \
+                 {first_line:?}). The prompt changed since the cassette was recorded; \
+                 re-record it with --record.",
+                self.path.display()
+            )));
+        };
+
+        let position = self.replay_positions.entry(key.clone()).or_insert(0);
+        let Some(response) = entry.responses.get(*position) else {
+            return Err(SwissArmyHammerError::Other(format!(
+                "All {} recorded responses for prompt {key} in cassette {} were already \
+                 replayed (prompt starts with: {first_line:?}). The workflow sent it more \
+                 often than during recording; re-record it with --record.",
+                entry.responses.len(),
+                self.path.display()
+            )));
+        };
+        *position += 1;
+        Ok(response.clone())
+    }
+
+    /// Number of responses already replayed for each prompt key
+    pub fn replay_positions(&self) -> &HashMap<String, usize> {
+        &self.replay_positions
+    }
+
+    /// Start recording a new run to the cassette at `path`
+    ///
+    /// Replaces the file with an empty cassette, so re-recording drops stale
+    /// responses instead of appending to them. Resumed runs skip this and keep
+    /// recording to the file they started.
+    pub fn start_recording(path: impl AsRef<Path>) -> Result<()> {
+        Self::close(&path);
+        Cassette::new(path.as_ref()).save()
+    }
+
+    /// Open the cassette at `path` for recording
+    ///
+    /// The file is loaded the first time it is opened by a run, so a resumed
+    /// run appends to the responses recorded before it stopped. A missing file
+    /// starts an empty cassette.
+    pub fn open_for_recording(path: impl AsRef<Path>) -> Result<SharedCassette> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(cassette) = Self::with_open_cassettes(|cassettes| cassettes.get(&path).cloned())
+        {
+            return Ok(cassette);
+        }
+
+        let cassette = if path.exists() {
+            Cassette::load(&path)?
+        } else {
+            Cassette::new(&path)
+        };
+        Ok(Self::share(path, cassette))
+    }
+
+    /// Open the cassette at `path` for replay
+    ///
+    /// The file is loaded the first time it is opened by a run. Later calls
+    /// share the loaded cassette so replay continues where it left off.
+    pub fn open_for_replay(path: impl AsRef<Path>) -> Result<SharedCassette> {
+        Self::resume_replay(path, HashMap::new())
+    }
+
+    /// Open the cassette at `path` for replay, skipping responses already replayed
+    ///
+    /// `positions` are the [`Cassette::replay_positions`] checkpointed by the
+    /// run. They only apply when the file is loaded; a cassette the run still
+    /// shares already knows its positions.
+    pub fn resume_replay(
+        path: impl AsRef<Path>,
+        positions: HashMap<String, usize>,
+    ) -> Result<SharedCassette> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(cassette) = Self::with_open_cassettes(|cassettes| cassettes.get(&path).cloned())
+        {
+            return Ok(cassette);
+        }
+
+        let mut cassette = Cassette::load(&path)?;
+        cassette.replay_positions = positions;
+        Ok(Self::share(path, cassette))
+    }
+
+    /// Replay positions of the cassette at `path`, if a run has it open
+    pub fn open_replay_positions(path: impl AsRef<Path>) -> Option<HashMap<String, usize>> {
+        let cassette =
+            Self::with_open_cassettes(|cassettes| cassettes.get(path.as_ref()).cloned())?;
+        let cassette = match cassette.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        Some(cassette.replay_positions.clone())
+    }
+
+    /// Stop sharing the cassette at `path`
+    ///
+    /// Called when the run using it stops. A resumed run loads the file again,
+    /// continuing to record to it or replaying from its checkpointed positions.
+    pub fn close(path: impl AsRef<Path>) {
+        Self::with_open_cassettes(|cassettes| cassettes.remove(path.as_ref()));
+    }
+
+    /// Share a loaded cassette, unless another executor opened it first
+    fn share(path: PathBuf, cassette: Cassette) -> SharedCassette {
+        let cassette = Arc::new(Mutex::new(cassette));
+        Self::with_open_cassettes(|cassettes| cassettes.entry(path).or_insert(cassette).clone())
+    }
+
+    /// Run `f` with the registry of open cassettes
+    fn with_open_cassettes<T>(f: impl FnOnce(&mut HashMap<PathBuf, SharedCassette>) -> T) -> T {
+        let mut guard = match OPEN_CASSETTES.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        f(guard.get_or_insert_with(HashMap::new))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cassette_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cassettes").join("plan.yaml");
+
+        let mut cassette = Cassette::new(&path);
+        cassette.record("system", "Write a plan", json!("Step 1\nStep 2"));
+        cassette.save().unwrap();
+
+        let mut loaded = Cassette::load(&path).unwrap();
+        assert_eq!(loaded.path(), path.as_path());
+        let entry = &loaded.interactions[&Cassette::prompt_key("Write a plan")];
+        assert_eq!(entry.system_prompt, "system");
+        assert_eq!(
+            loaded.next_response("Write a plan").unwrap(),
+            json!("Step 1\nStep 2")
+        );
+        assert!(loaded.next_response("Something else").is_err());
+    }
+
+    #[test]
+    fn test_repeated_prompts_replay_in_order_then_fail() {
+        let mut cassette = Cassette::new("exhausted.yaml");
+        cassette.record("", "Is it done?", json!("no"));
+        cassette.record("", "Is it done?", json!("yes"));
+
+        assert_eq!(cassette.next_response("Is it done?").unwrap(), json!("no"));
+        assert_eq!(cassette.next_response("Is it done?").unwrap(), json!("yes"));
+
+        let message = cassette
+            .next_response("Is it done?")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains(&Cassette::prompt_key("Is it done?")),
+            "{message}"
+        );
+        assert!(message.contains("exhausted.yaml"), "{message}");
+        assert!(message.contains("Is it done?"), "{message}");
+    }
+
+    #[test]
+    fn test_close_reloads_re_recorded_cassette() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("rerecorded.yaml");
+        let mut cassette = Cassette::new(&path);
+        cassette.record("", "Status?", json!("old"));
+        cassette.save().unwrap();
+
+        let replay = Cassette::open_for_replay(&path).unwrap();
+        assert_eq!(
+            replay.lock().unwrap().next_response("Status?").unwrap(),
+            json!("old")
+        );
+        Cassette::close(&path);
+
+        let mut cassette = Cassette::new(&path);
+        cassette.record("", "Status?", json!("new"));
+        cassette.save().unwrap();
+
+        let replay = Cassette::open_for_replay(&path).unwrap();
+        assert_eq!(
+            replay.lock().unwrap().next_response("Status?").unwrap(),
+            json!("new")
+        );
+        Cassette::close(&path);
+    }
+
+    #[test]
+    fn test_recording_resumes_unless_started_afresh() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("resumed.yaml");
+        Cassette::start_recording(&path).unwrap();
+
+        let recording = Cassette::open_for_recording(&path).unwrap();
+        recording.lock().unwrap().record("", "Plan", json!("first"));
+        recording.lock().unwrap().save().unwrap();
+        Cassette::close(&path);
+
+        // A resumed run keeps what was recorded before it stopped
+        let recording = Cassette::open_for_recording(&path).unwrap();
+        recording
+            .lock()
+            .unwrap()
+            .record("", "Plan", json!("second"));
+        recording.lock().unwrap().save().unwrap();
+        Cassette::close(&path);
+        let responses =
+            &Cassette::load(&path).unwrap().interactions[&Cassette::prompt_key("Plan")].responses;
+        assert_eq!(responses, &vec![json!("first"), json!("second")]);
+
+        // A new run starts over
+        Cassette::start_recording(&path).unwrap();
+        assert!(Cassette::load(&path).unwrap().interactions.is_empty());
+    }
+
+    #[test]
+    fn test_open_for_replay_requires_existing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(Cassette::open_for_replay(temp_dir.path().join("missing.yaml")).is_err());
+    }
+}
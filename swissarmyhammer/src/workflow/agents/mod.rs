@@ -3,6 +3,16 @@
 //! This module contains implementations of different agent executors that can
 //! be used to execute prompts and interact with AI systems.
 
+pub mod cassette;
 pub mod llama_agent_executor;
+pub mod openai_compatible_executor;
+pub mod record_replay_executor;
+pub mod tools;
 
+pub use cassette::{
+    Cassette, CassetteEntry, RECORD_CASSETTE_KEY, REPLAY_CASSETTE_KEY, REPLAY_POSITIONS_KEY,
+};
 pub use llama_agent_executor::LlamaAgentExecutor;
+pub use openai_compatible_executor::OpenAiCompatibleExecutor;
+pub use record_replay_executor::{RecordingExecutor, ReplayExecutor};
+pub use tools::{agent_tool_provider, set_agent_tool_provider, AgentTool, AgentToolProvider};
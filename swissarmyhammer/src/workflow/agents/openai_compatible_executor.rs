@@ -0,0 +1,592 @@
+//! OpenAI-compatible HTTP executor implementation for SwissArmyHammer workflows
+//!
+//! This module provides an executor that sends prompts to any server implementing
+//! the OpenAI `/v1/chat/completions` API, such as vLLM, LM Studio or Ollama.
+//! Tools registered through [`set_agent_tool_provider`](super::tools::set_agent_tool_provider)
+//! are offered to the model, and tool calls are executed in a loop until the
+//! model produces a final answer.
+
+use super::tools::{agent_tool_provider, AgentTool, AgentToolProvider};
+use crate::workflow::actions::{ActionError, ActionResult, AgentExecutionContext, AgentExecutor};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use swissarmyhammer_config::agent::{AgentExecutorType, OpenAiCompatibleConfig};
+
+/// Default wait before retrying when the server rate limits without a Retry-After header
+const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Response body of a chat completion request
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatChoice>,
+}
+
+/// A single completion choice
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+/// Assistant message returned by the server
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    #[serde(default)]
+    content: Option<String>,
+    /// Some servers send `null` rather than omitting the field
+    #[serde(default)]
+    tool_calls: Option<Vec<ToolCall>>,
+}
+
+/// Tool call requested by the model
+#[derive(Debug, Clone, Deserialize)]
+struct ToolCall {
+    id: String,
+    function: FunctionCall,
+}
+
+/// Function name and JSON-encoded arguments of a tool call
+#[derive(Debug, Clone, Deserialize)]
+struct FunctionCall {
+    name: String,
+    #[serde(default)]
+    arguments: String,
+}
+
+/// Executor that calls an OpenAI-compatible chat completions endpoint
+pub struct OpenAiCompatibleExecutor {
+    /// Endpoint and sampling configuration
+    config: OpenAiCompatibleConfig,
+    /// HTTP client, created on initialization
+    client: Option<reqwest::Client>,
+    /// Tools offered to the model, falling back to the registered provider
+    tool_provider: Option<Arc<dyn AgentToolProvider>>,
+}
+
+impl OpenAiCompatibleExecutor {
+    /// Create a new executor for the given endpoint configuration
+    pub fn new(config: OpenAiCompatibleConfig) -> Self {
+        Self {
+            config,
+            client: None,
+            tool_provider: None,
+        }
+    }
+
+    /// Offer the tools of `provider` to the model instead of the registered provider
+    pub fn with_tool_provider(mut self, provider: Arc<dyn AgentToolProvider>) -> Self {
+        self.tool_provider = Some(provider);
+        self
+    }
+
+    /// Get the endpoint configuration
+    pub fn config(&self) -> &OpenAiCompatibleConfig {
+        &self.config
+    }
+
+    /// URL of the chat completions endpoint
+    fn completions_url(&self) -> String {
+        format!(
+            "{}/chat/completions",
+            self.config.base_url.trim_end_matches('/')
+        )
+    }
+
+    /// Convert tools to the OpenAI function tool format
+    fn tool_specs(tools: &[AgentTool]) -> Vec<Value> {
+        tools
+            .iter()
+            .map(|tool| {
+                json!({
+                    "type": "function",
+                    "function": {
+                        "name": tool.name,
+                        "description": tool.description,
+                        "parameters": tool.parameters,
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Build the request body for the current conversation
+    fn request_body(&self, messages: &[Value], tool_specs: &[Value]) -> Value {
+        let mut body = json!({
+            "model": self.config.model,
+            "messages": messages,
+        });
+        if let Some(temperature) = self.config.temperature {
+            body["temperature"] = json!(temperature);
+        }
+        if let Some(max_tokens) = self.config.max_tokens {
+            body["max_tokens"] = json!(max_tokens);
+        }
+        if !tool_specs.is_empty() {
+            body["tools"] = json!(tool_specs);
+        }
+        body
+    }
+
+    /// Send one chat completion request and return the assistant message
+    async fn complete(
+        &self,
+        client: &reqwest::Client,
+        body: &Value,
+    ) -> ActionResult<(ChatMessage, Value)> {
+        let mut request = client.post(self.completions_url()).json(body);
+        if let Some(api_key) = self.config.api_key() {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            ActionError::executor_error(
+                AgentExecutorType::OpenAiCompatible,
+                format!("Request to {} failed: {e}", self.completions_url()),
+            )
+        })?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let wait_time = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u64>().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_RATE_LIMIT_WAIT);
+            let message = response.text().await.unwrap_or_default();
+            return Err(ActionError::RateLimit { message, wait_time });
+        }
+
+        let text = response.text().await.map_err(|e| {
+            ActionError::executor_error(
+                AgentExecutorType::OpenAiCompatible,
+                format!("Failed to read response: {e}"),
+            )
+        })?;
+
+        if !status.is_success() {
+            return Err(ActionError::executor_error(
+                AgentExecutorType::OpenAiCompatible,
+                format!("Server returned {status}: {text}"),
+            ));
+        }
+
+        let raw: Value = serde_json::from_str(&text)?;
+        let parsed: ChatCompletionResponse = serde_json::from_value(raw.clone())?;
+        let message = parsed.choices.into_iter().next().ok_or_else(|| {
+            ActionError::executor_error(
+                AgentExecutorType::OpenAiCompatible,
+                "Response contained no choices".to_string(),
+            )
+        })?;
+
+        // Keep the raw message so it can be echoed back verbatim in the conversation
+        let raw_message = raw["choices"][0]["message"].clone();
+        Ok((message.message, raw_message))
+    }
+
+    /// Execute the requested tool calls and return the tool result messages
+    async fn run_tool_calls(
+        provider: Option<&Arc<dyn AgentToolProvider>>,
+        tool_calls: &[ToolCall],
+    ) -> Vec<Value> {
+        let mut results = Vec::with_capacity(tool_calls.len());
+        for call in tool_calls {
+            let content = match provider {
+                Some(provider) => {
+                    let arguments = if call.function.arguments.trim().is_empty() {
+                        Ok(json!({}))
+                    } else {
+                        serde_json::from_str::<Value>(&call.function.arguments)
+                    };
+                    match arguments {
+                        Ok(arguments) => {
+                            tracing::debug!("Model called tool {}", call.function.name);
+                            provider
+                                .call_tool(&call.function.name, arguments)
+                                .await
+                                .unwrap_or_else(|e| format!("Error: {e}"))
+                        }
+                        Err(e) => format!("Error: invalid tool arguments: {e}"),
+                    }
+                }
+                None => format!("Error: tool '{}' is not available", call.function.name),
+            };
+
+            results.push(json!({
+                "role": "tool",
+                "tool_call_id": call.id,
+                "content": content,
+            }));
+        }
+        results
+    }
+
+    /// Run the conversation until the model answers without calling a tool
+    async fn run_conversation(
+        &self,
+        system_prompt: String,
+        rendered_prompt: String,
+    ) -> ActionResult<Value> {
+        let client = self.client.as_ref().ok_or_else(|| {
+            ActionError::ExecutionError("OpenAI-compatible executor not initialized".to_string())
+        })?;
+
+        let provider = self.tool_provider.clone().or_else(agent_tool_provider);
+        let tool_specs = provider
+            .as_ref()
+            .map(|provider| Self::tool_specs(&provider.tools()))
+            .unwrap_or_default();
+
+        let mut messages = Vec::new();
+        if !system_prompt.is_empty() {
+            messages.push(json!({"role": "system", "content": system_prompt}));
+        }
+        messages.push(json!({"role": "user", "content": rendered_prompt}));
+
+        for _ in 0..=self.config.max_tool_iterations {
+            let body = self.request_body(&messages, &tool_specs);
+            let (message, raw_message) = self.complete(client, &body).await?;
+
+            let tool_calls = message.tool_calls.unwrap_or_default();
+            if tool_calls.is_empty() {
+                return Ok(Value::String(
+                    message.content.unwrap_or_default().trim().to_string(),
+                ));
+            }
+
+            messages.push(raw_message);
+            messages.extend(Self::run_tool_calls(provider.as_ref(), &tool_calls).await);
+        }
+
+        Err(ActionError::executor_error(
+            AgentExecutorType::OpenAiCompatible,
+            format!(
+                "Model did not finish after {} tool-calling rounds",
+                self.config.max_tool_iterations
+            ),
+        ))
+    }
+}
+
+#[async_trait]
+impl AgentExecutor for OpenAiCompatibleExecutor {
+    async fn execute_prompt(
+        &self,
+        system_prompt: String,
+        rendered_prompt: String,
+        _context: &AgentExecutionContext<'_>,
+        timeout: Duration,
+    ) -> ActionResult<Value> {
+        tracing::debug!(
+            "Sending prompt to {} with model {}",
+            self.completions_url(),
+            self.config.model
+        );
+
+        tokio::time::timeout(
+            timeout,
+            self.run_conversation(system_prompt, rendered_prompt),
+        )
+        .await
+        .map_err(|_| ActionError::Timeout { timeout })?
+    }
+
+    fn executor_type(&self) -> AgentExecutorType {
+        AgentExecutorType::OpenAiCompatible
+    }
+
+    async fn initialize(&mut self) -> ActionResult<()> {
+        if self.client.is_some() {
+            return Ok(());
+        }
+
+        if self.config.model.trim().is_empty() {
+            return Err(ActionError::ExecutionError(
+                "OpenAI-compatible executor requires a model name".to_string(),
+            ));
+        }
+        reqwest::Url::parse(&self.config.base_url).map_err(|e| {
+            ActionError::ExecutionError(format!(
+                "Invalid OpenAI-compatible base URL '{}': {e}",
+                self.config.base_url
+            ))
+        })?;
+
+        let client = reqwest::Client::builder().build().map_err(|e| {
+            ActionError::initialization_error(AgentExecutorType::OpenAiCompatible, Box::new(e))
+        })?;
+        self.client = Some(client);
+
+        tracing::debug!(
+            "OpenAiCompatibleExecutor initialized for {}",
+            self.config.base_url
+        );
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> ActionResult<()> {
+        self.client = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::WorkflowTemplateContext;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use wiremock::matchers::{body_partial_json, body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Tool provider that records calls and echoes its arguments
+    #[derive(Default)]
+    struct EchoToolProvider {
+        calls: Mutex<Vec<(String, Value)>>,
+    }
+
+    #[async_trait]
+    impl AgentToolProvider for EchoToolProvider {
+        fn tools(&self) -> Vec<AgentTool> {
+            vec![AgentTool {
+                name: "echo".to_string(),
+                description: "Echo the message".to_string(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {"message": {"type": "string"}},
+                    "required": ["message"]
+                }),
+            }]
+        }
+
+        async fn call_tool(&self, name: &str, arguments: Value) -> Result<String, String> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((name.to_string(), arguments.clone()));
+            Ok(format!(
+                "echo: {}",
+                arguments["message"].as_str().unwrap_or("")
+            ))
+        }
+    }
+
+    fn completion(content: &str) -> Value {
+        json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": content},
+                "finish_reason": "stop"
+            }]
+        })
+    }
+
+    async fn initialized_executor(server: &MockServer) -> OpenAiCompatibleExecutor {
+        let mut config = OpenAiCompatibleConfig::new(format!("{}/v1", server.uri()), "test-model");
+        config.temperature = Some(0.1);
+        config.max_tokens = Some(256);
+        let mut executor = OpenAiCompatibleExecutor::new(config)
+            .with_tool_provider(Arc::new(EchoToolProvider::default()));
+        executor.initialize().await.unwrap();
+        executor
+    }
+
+    #[tokio::test]
+    async fn test_openai_compatible_executor_returns_completion() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(json!({
+                "model": "test-model",
+                "max_tokens": 256,
+                "messages": [
+                    {"role": "system", "content": "Be brief"},
+                    {"role": "user", "content": "Say hello"}
+                ]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion(" Hello! ")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let executor = initialized_executor(&server).await;
+        let workflow_context = WorkflowTemplateContext::with_vars_for_test(HashMap::new());
+        let context = AgentExecutionContext::new(&workflow_context);
+
+        let response = executor
+            .execute_prompt(
+                "Be brief".to_string(),
+                "Say hello".to_string(),
+                &context,
+                Duration::from_secs(10),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response, Value::String("Hello!".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_openai_compatible_executor_runs_tool_calls() {
+        let server = MockServer::start().await;
+
+        // Once the tool result is in the conversation the model answers
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_string_contains("echo: from the model"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion("Done")))
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // Before that it asks for the echo tool, which must be offered
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(json!({
+                "tools": [{"type": "function", "function": {"name": "echo"}}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "content": null,
+                        "tool_calls": [{
+                            "id": "call_1",
+                            "type": "function",
+                            "function": {
+                                "name": "echo",
+                                "arguments": "{\"message\":\"from the model\"}"
+                            }
+                        }]
+                    },
+                    "finish_reason": "tool_calls"
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = Arc::new(EchoToolProvider::default());
+        let mut executor = OpenAiCompatibleExecutor::new(OpenAiCompatibleConfig::new(
+            format!("{}/v1/", server.uri()),
+            "test-model",
+        ))
+        .with_tool_provider(provider.clone());
+        executor.initialize().await.unwrap();
+
+        let workflow_context = WorkflowTemplateContext::with_vars_for_test(HashMap::new());
+        let context = AgentExecutionContext::new(&workflow_context);
+        let response = executor
+            .execute_prompt(
+                String::new(),
+                "Use the echo tool".to_string(),
+                &context,
+                Duration::from_secs(10),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response, Value::String("Done".to_string()));
+        let calls = provider.calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, "echo");
+        assert_eq!(calls[0].1, json!({"message": "from the model"}));
+    }
+
+    #[tokio::test]
+    async fn test_openai_compatible_executor_sends_api_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header("authorization", "Bearer test-secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(completion("ok")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut config = OpenAiCompatibleConfig::new(format!("{}/v1", server.uri()), "m");
+        config.api_key = Some("test-secret".to_string());
+        let mut executor = OpenAiCompatibleExecutor::new(config);
+        executor.initialize().await.unwrap();
+
+        let workflow_context = WorkflowTemplateContext::with_vars_for_test(HashMap::new());
+        let context = AgentExecutionContext::new(&workflow_context);
+        let response = executor
+            .execute_prompt(
+                String::new(),
+                "hi".to_string(),
+                &context,
+                Duration::from_secs(10),
+            )
+            .await;
+
+        assert_eq!(response.unwrap(), Value::String("ok".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_openai_compatible_executor_maps_http_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("rate limited"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "7"))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("model not loaded"))
+            .mount(&server)
+            .await;
+
+        let executor = initialized_executor(&server).await;
+        let workflow_context = WorkflowTemplateContext::with_vars_for_test(HashMap::new());
+        let context = AgentExecutionContext::new(&workflow_context);
+
+        let error = executor
+            .execute_prompt(
+                String::new(),
+                "rate limited".to_string(),
+                &context,
+                Duration::from_secs(10),
+            )
+            .await
+            .unwrap_err();
+        match error {
+            ActionError::RateLimit { wait_time, .. } => {
+                assert_eq!(wait_time, Duration::from_secs(7))
+            }
+            other => panic!("Expected rate limit error, got {other:?}"),
+        }
+
+        let error = executor
+            .execute_prompt(
+                String::new(),
+                "hello".to_string(),
+                &context,
+                Duration::from_secs(10),
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("model not loaded"));
+    }
+
+    #[tokio::test]
+    async fn test_openai_compatible_executor_rejects_invalid_config() {
+        let mut executor =
+            OpenAiCompatibleExecutor::new(OpenAiCompatibleConfig::new("not a url", "model"));
+        assert!(executor.initialize().await.is_err());
+
+        let mut executor =
+            OpenAiCompatibleExecutor::new(OpenAiCompatibleConfig::new("http://localhost/v1", ""));
+        assert!(executor.initialize().await.is_err());
+        assert_eq!(
+            executor.executor_type(),
+            AgentExecutorType::OpenAiCompatible
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! Recording and replaying agent executors for deterministic workflow tests
+//!
+//! [`RecordingExecutor`] wraps any [`AgentExecutor`] and writes every response to
+//! a [`Cassette`]. [`ReplayExecutor`] answers prompts from a cassette without
+//! contacting a model and fails on any prompt that was not recorded, so a
+//! workflow change that alters a prompt is caught instead of silently passing.
+
+use super::cassette::{Cassette, SharedCassette};
+use crate::workflow::actions::{ActionError, ActionResult, AgentExecutionContext, AgentExecutor};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use swissarmyhammer_config::agent::AgentExecutorType;
+
+/// Lock a shared cassette, recovering from a poisoned lock
+fn lock_cassette(cassette: &SharedCassette) -> std::sync::MutexGuard<'_, Cassette> {
+    match cassette.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Executor that records the responses of another executor to a cassette
+pub struct RecordingExecutor {
+    /// Executor that produces the responses
+    inner: Box<dyn AgentExecutor>,
+    /// Cassette the responses are written to
+    cassette: SharedCassette,
+}
+
+impl RecordingExecutor {
+    /// Record the responses of `inner` to the cassette at `path`
+    ///
+    /// Responses are appended to those the file already holds; call
+    /// [`Cassette::start_recording`] first to record a run afresh.
+    pub fn new(inner: Box<dyn AgentExecutor>, path: impl AsRef<Path>) -> ActionResult<Self> {
+        let cassette = Cassette::open_for_recording(path)
+            .map_err(|e| ActionError::ExecutionError(e.to_string()))?;
+        Ok(Self { inner, cassette })
+    }
+}
+
+#[async_trait]
+impl AgentExecutor for RecordingExecutor {
+    async fn execute_prompt(
+        &self,
+        system_prompt: String,
+        rendered_prompt: String,
+        context: &AgentExecutionContext<'_>,
+        timeout: Duration,
+    ) -> ActionResult<Value> {
+        let response = self
+            .inner
+            .execute_prompt(
+                system_prompt.clone(),
+                rendered_prompt.clone(),
+                context,
+                timeout,
+            )
+            .await?;
+
+        let mut cassette = lock_cassette(&self.cassette);
+        cassette.record(&system_prompt, &rendered_prompt, response.clone());
+        cassette.save().map_err(|e| {
+            ActionError::ExecutionError(format!(
+                "Failed to write cassette {}: {e}",
+                cassette.path().display()
+            ))
+        })?;
+
+        Ok(response)
+    }
+
+    fn executor_type(&self) -> AgentExecutorType {
+        self.inner.executor_type()
+    }
+
+    async fn initialize(&mut self) -> ActionResult<()> {
+        self.inner.initialize().await
+    }
+
+    async fn shutdown(&mut self) -> ActionResult<()> {
+        self.inner.shutdown().await
+    }
+}
+
+/// Executor that answers prompts from a recorded cassette
+pub struct ReplayExecutor {
+    /// Cassette the responses are read from
+    cassette: SharedCassette,
+    /// Executor type the cassette stands in for
+    executor_type: AgentExecutorType,
+}
+
+impl ReplayExecutor {
+    /// Replay responses from the cassette at `path`
+    pub fn new(path: impl AsRef<Path>, executor_type: AgentExecutorType) -> ActionResult<Self> {
+        Self::resume(path, executor_type, HashMap::new())
+    }
+
+    /// Replay responses from the cassette at `path`, skipping those a run already replayed
+    ///
+    /// `positions` are the replay positions checkpointed by the run.
+    pub fn resume(
+        path: impl AsRef<Path>,
+        executor_type: AgentExecutorType,
+        positions: HashMap<String, usize>,
+    ) -> ActionResult<Self> {
+        let cassette = Cassette::resume_replay(path, positions)
+            .map_err(|e| ActionError::ExecutionError(e.to_string()))?;
+        Ok(Self {
+            cassette,
+            executor_type,
+        })
+    }
+}
+
+#[async_trait]
+impl AgentExecutor for ReplayExecutor {
+    async fn execute_prompt(
+        &self,
+        _system_prompt: String,
+        rendered_prompt: String,
+        _context: &AgentExecutionContext<'_>,
+        _timeout: Duration,
+    ) -> ActionResult<Value> {
+        lock_cassette(&self.cassette)
+            .next_response(&rendered_prompt)
+            .map_err(|e| ActionError::ExecutionError(e.to_string()))
+    }
+
+    fn executor_type(&self) -> AgentExecutorType {
+        self.executor_type
+    }
+
+    async fn initialize(&mut self) -> ActionResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> ActionResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::WorkflowTemplateContext;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// Executor that numbers its responses
+    struct CountingExecutor {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl AgentExecutor for CountingExecutor {
+        async fn execute_prompt(
+            &self,
+            _system_prompt: String,
+            rendered_prompt: String,
+            _context: &AgentExecutionContext<'_>,
+            _timeout: Duration,
+        ) -> ActionResult<Value> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(json!(format!("{rendered_prompt} #{call}")))
+        }
+
+        fn executor_type(&self) -> AgentExecutorType {
+            AgentExecutorType::LlamaAgent
+        }
+
+        async fn initialize(&mut self) -> ActionResult<()> {
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> ActionResult<()> {
+            Ok(())
+        }
+    }
+
+    async fn prompt(executor: &dyn AgentExecutor, text: &str) -> ActionResult<Value> {
+        let workflow_context = WorkflowTemplateContext::with_vars_for_test(HashMap::new());
+        let context = AgentExecutionContext::new(&workflow_context);
+        executor
+            .execute_prompt(
+                "system".to_string(),
+                text.to_string(),
+                &context,
+                Duration::from_secs(5),
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("golden.yaml");
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        // Each prompt action gets its own executor, so record through two of them
+        for _ in 0..2 {
+            let recorder = RecordingExecutor::new(
+                Box::new(CountingExecutor {
+                    calls: calls.clone(),
+                }),
+                &path,
+            )
+            .unwrap();
+            assert_eq!(recorder.executor_type(), AgentExecutorType::LlamaAgent);
+            prompt(&recorder, "review").await.unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Replay from a fresh load of the file rather than the process-wide copy
+        let mut cassette = Cassette::load(&path).unwrap();
+        assert_eq!(
+            cassette.next_response("review").unwrap(),
+            json!("review #1")
+        );
+        assert_eq!(
+            cassette.next_response("review").unwrap(),
+            json!("review #2")
+        );
+
+        let replay = ReplayExecutor::new(&path, AgentExecutorType::ClaudeCode).unwrap();
+        assert_eq!(replay.executor_type(), AgentExecutorType::ClaudeCode);
+        assert_eq!(prompt(&replay, "review").await.unwrap(), json!("review #1"));
+        assert_eq!(prompt(&replay, "review").await.unwrap(), json!("review #2"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // A third review was never recorded
+        let error = prompt(&replay, "review").await.unwrap_err().to_string();
+        assert!(error.contains("golden.yaml"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_record_pause_resume() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("paused.yaml");
+        let calls = Arc::new(AtomicUsize::new(0));
+        let recorder = || {
+            RecordingExecutor::new(
+                Box::new(CountingExecutor {
+                    calls: calls.clone(),
+                }),
+                &path,
+            )
+            .unwrap()
+        };
+
+        // The run pauses after its first review, releasing the cassette
+        Cassette::start_recording(&path).unwrap();
+        prompt(&recorder(), "review").await.unwrap();
+        Cassette::close(&path);
+
+        // Resumed, possibly in another process, it records the second review too
+        prompt(&recorder(), "review").await.unwrap();
+        Cassette::close(&path);
+
+        // Replay pauses at the same point and checkpoints how far it got
+        let replay = ReplayExecutor::new(&path, AgentExecutorType::ClaudeCode).unwrap();
+        assert_eq!(prompt(&replay, "review").await.unwrap(), json!("review #1"));
+        let positions = Cassette::open_replay_positions(&path).unwrap();
+        Cassette::close(&path);
+
+        let replay =
+            ReplayExecutor::resume(&path, AgentExecutorType::ClaudeCode, positions).unwrap();
+        assert_eq!(prompt(&replay, "review").await.unwrap(), json!("review #2"));
+        assert!(prompt(&replay, "review").await.is_err());
+        Cassette::close(&path);
+    }
+
+    #[tokio::test]
+    async fn test_replay_fails_loudly_on_cache_miss() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("golden.yaml");
+        let mut cassette = Cassette::new(&path);
+        cassette.record("system", "known prompt", json!("ok"));
+        cassette.save().unwrap();
+
+        let replay = ReplayExecutor::new(&path, AgentExecutorType::ClaudeCode).unwrap();
+        let error = prompt(&replay, "changed prompt\nwith details")
+            .await
+            .unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains(&Cassette::prompt_key("changed prompt\nwith details")));
+        assert!(message.contains("golden.yaml"));
+        assert!(message.contains("changed prompt"));
+        assert!(!message.contains("with details"));
+    }
+
+    #[tokio::test]
+    async fn test_factory_replays_when_context_names_cassette() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("factory.yaml");
+        let mut cassette = Cassette::new(&path);
+        cassette.record("system", "hello", json!("recorded hello"));
+        cassette.save().unwrap();
+
+        let mut vars = HashMap::new();
+        vars.insert(
+            crate::workflow::REPLAY_CASSETTE_KEY.to_string(),
+            json!(path.to_string_lossy()),
+        );
+        let workflow_context = WorkflowTemplateContext::with_vars_for_test(vars);
+        let context = AgentExecutionContext::new(&workflow_context);
+
+        // No Claude CLI or model is needed to serve a recorded response
+        let executor = crate::workflow::AgentExecutorFactory::create_executor(&context)
+            .await
+            .unwrap();
+        let response = executor
+            .execute_prompt(
+                "system".to_string(),
+                "hello".to_string(),
+                &context,
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response, json!("recorded hello"));
+    }
+}
@@ -0,0 +1,53 @@
+//! Tools exposed to agent executors that drive their own tool-calling loop
+//!
+//! The MCP tool registry lives in `swissarmyhammer-tools`, which depends on this
+//! crate, so executors cannot reference it directly. Instead the registry is
+//! adapted to [`AgentToolProvider`] by the tools crate and registered here by the
+//! application at startup.
+
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+
+/// Description of a tool that can be offered to a model
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentTool {
+    /// Unique tool name
+    pub name: String,
+    /// Human-readable description shown to the model
+    pub description: String,
+    /// JSON schema of the tool arguments
+    pub parameters: Value,
+}
+
+/// Source of tools that agent executors can call on behalf of a model
+#[async_trait]
+pub trait AgentToolProvider: Send + Sync {
+    /// List the tools available to the model
+    fn tools(&self) -> Vec<AgentTool>;
+
+    /// Call a tool and return its textual result
+    ///
+    /// Errors are returned as a message so they can be reported back to the
+    /// model rather than aborting the prompt.
+    async fn call_tool(&self, name: &str, arguments: Value) -> Result<String, String>;
+}
+
+/// Tool provider registered for the current process
+static AGENT_TOOL_PROVIDER: RwLock<Option<Arc<dyn AgentToolProvider>>> = RwLock::new(None);
+
+/// Register the tools that agent executors expose to models
+pub fn set_agent_tool_provider(provider: Arc<dyn AgentToolProvider>) {
+    match AGENT_TOOL_PROVIDER.write() {
+        Ok(mut guard) => *guard = Some(provider),
+        Err(poisoned) => *poisoned.into_inner() = Some(provider),
+    }
+}
+
+/// Get the registered agent tool provider, if any
+pub fn agent_tool_provider() -> Option<Arc<dyn AgentToolProvider>> {
+    match AGENT_TOOL_PROVIDER.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
@@ -0,0 +1,208 @@
+//! Human approval gates for workflow runs
+//!
+//! An `await approval "message"` action pauses the run and records a
+//! [`PendingApproval`] on it. The run stays paused until someone approves or
+//! rejects it, for example with `sah flow approve <run_id>`,
+//! `sah flow reject <run_id> --reason ...` or the `flow_approve` MCP tool. The
+//! decision is recorded with [`WorkflowRun::record_approval`], and when the run
+//! resumes the approval action places it in the context under `approval` so
+//! transitions can branch on it.
+
+use crate::workflow::{StateId, WorkflowRun};
+use crate::{Result, SwissArmyHammerError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Run metadata key holding the approval a paused run is waiting for
+pub const PENDING_APPROVAL_METADATA_KEY: &str = "pending_approval";
+
+/// Context key an approval action sets to ask the executor to pause the run
+pub const APPROVAL_REQUEST_KEY: &str = "_approval_request";
+
+/// Context key holding a decision that has not been seen by the approval action yet
+pub const APPROVAL_DECISION_KEY: &str = "_approval_decision";
+
+/// Context key holding the decision once the approval action has run
+pub const APPROVAL_CONTEXT_KEY: &str = "approval";
+
+/// Decision made on a pending approval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    /// The run may continue
+    Approved,
+    /// The run may not continue
+    Rejected,
+}
+
+impl ApprovalDecision {
+    /// Get the string representation of the decision
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApprovalDecision::Approved => "approved",
+            ApprovalDecision::Rejected => "rejected",
+        }
+    }
+}
+
+impl std::fmt::Display for ApprovalDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Approval a paused run is waiting for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingApproval {
+    /// State that asked for approval
+    pub state: StateId,
+    /// Message shown to the approver
+    pub message: String,
+    /// When approval was requested
+    pub requested_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PendingApproval {
+    /// Create a pending approval requested now
+    pub fn new(state: StateId, message: impl Into<String>) -> Self {
+        Self {
+            state,
+            message: message.into(),
+            requested_at: chrono::Utc::now(),
+        }
+    }
+}
+
+/// Decision recorded for an approval
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalRecord {
+    /// Whether the run was approved or rejected
+    pub decision: ApprovalDecision,
+    /// Whether the run was approved, for use in transition conditions
+    pub approved: bool,
+    /// Who made the decision
+    pub approver: String,
+    /// Reason given for the decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// State that asked for approval
+    pub state: StateId,
+    /// Message shown to the approver
+    pub message: String,
+    /// When the decision was made
+    pub decided_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Name recorded as approver when none is given
+///
+/// Uses the current user's login name, falling back to `unknown`.
+pub fn default_approver() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .filter(|user| !user.trim().is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+impl WorkflowRun {
+    /// Get the approval this run is waiting for, if any
+    pub fn pending_approval(&self) -> Option<PendingApproval> {
+        self.metadata
+            .get(PENDING_APPROVAL_METADATA_KEY)
+            .and_then(|pending| serde_json::from_str(pending).ok())
+    }
+
+    /// Record that the run is waiting for approval
+    pub fn set_pending_approval(&mut self, pending: &PendingApproval) {
+        if let Ok(pending) = serde_json::to_string(pending) {
+            self.metadata
+                .insert(PENDING_APPROVAL_METADATA_KEY.to_string(), pending);
+        }
+    }
+
+    /// Decide the approval this run is waiting for
+    ///
+    /// The decision is stored in the run context and takes effect when the run
+    /// is resumed. Fails if the run is not waiting for approval.
+    pub fn record_approval(
+        &mut self,
+        decision: ApprovalDecision,
+        approver: impl Into<String>,
+        reason: Option<String>,
+    ) -> Result<ApprovalRecord> {
+        let pending = self.pending_approval().ok_or_else(|| {
+            SwissArmyHammerError::Other(format!(
+                "Workflow run {} is not waiting for approval",
+                self.id
+            ))
+        })?;
+
+        let record = ApprovalRecord {
+            decision,
+            approved: decision == ApprovalDecision::Approved,
+            approver: approver.into(),
+            reason,
+            state: pending.state,
+            message: pending.message,
+            decided_at: chrono::Utc::now(),
+        };
+        let value = serde_json::to_value(&record).map_err(|e| {
+            SwissArmyHammerError::Other(format!("Failed to serialize approval: {e}"))
+        })?;
+
+        self.context
+            .insert(APPROVAL_DECISION_KEY.to_string(), value);
+        self.metadata.remove(PENDING_APPROVAL_METADATA_KEY);
+        Ok(record)
+    }
+}
+
+/// Take the decision waiting in the context, if any
+pub(crate) fn take_approval_decision(
+    context: &mut crate::workflow::WorkflowTemplateContext,
+) -> Option<ApprovalRecord> {
+    context
+        .remove(APPROVAL_DECISION_KEY)
+        .and_then(|decision: Value| serde_json::from_value(decision).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::test_helpers::{create_state, create_workflow};
+
+    fn paused_run() -> WorkflowRun {
+        let mut workflow = create_workflow("Approval", "Approval test", "gate");
+        workflow.add_state(create_state("gate", "await approval \"Ship it?\"", false));
+        let mut run = WorkflowRun::new(workflow);
+        run.set_pending_approval(&PendingApproval::new(StateId::new("gate"), "Ship it?"));
+        run
+    }
+
+    #[test]
+    fn test_record_approval_moves_decision_into_context() {
+        let mut run = paused_run();
+        assert_eq!(run.pending_approval().unwrap().message, "Ship it?");
+
+        let record = run
+            .record_approval(ApprovalDecision::Rejected, "alice", Some("Not yet".into()))
+            .unwrap();
+        assert!(!record.approved);
+        assert_eq!(record.state, StateId::new("gate"));
+        assert!(run.pending_approval().is_none());
+
+        let taken = take_approval_decision(&mut run.context).unwrap();
+        assert_eq!(taken, record);
+        assert!(take_approval_decision(&mut run.context).is_none());
+    }
+
+    #[test]
+    fn test_record_approval_requires_pending_approval() {
+        let mut run = paused_run();
+        run.record_approval(ApprovalDecision::Approved, "alice", None)
+            .unwrap();
+        assert!(run
+            .record_approval(ApprovalDecision::Approved, "bob", None)
+            .is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! Execution budgets for workflow runs
+//!
+//! A workflow can limit how long a run may take and how many transitions it
+//! may make, and give individual states a deadline. Budgets are declared in
+//! the workflow front matter:
+//!
+//! ```yaml
+//! budget:
+//!   timeout: 2h
+//!   max_transitions: 200
+//!   on_exceeded: cleanup
+//! deadlines:
+//!   review: 10m
+//! ```
+//!
+//! When a budget is exceeded the executor moves the run to the `on_exceeded`
+//! state, or to a registered compensation state, where the same budget applies
+//! again counted from that point. If there is no such state, or the budget is
+//! exceeded again on the way, the executor ends the run as
+//! [`WorkflowRunStatus::Cancelled`](crate::workflow::WorkflowRunStatus::Cancelled)
+//! with the reason recorded in its metadata.
+
+use crate::workflow::{State, StateId, Workflow};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Workflow metadata key holding the wall-clock budget in milliseconds
+pub const TIMEOUT_METADATA_KEY: &str = "timeout_ms";
+
+/// Workflow metadata key holding the maximum number of transitions
+pub const MAX_TRANSITIONS_METADATA_KEY: &str = "max_transitions";
+
+/// Workflow metadata key naming the state a run moves to when a budget is exceeded
+pub const ON_BUDGET_EXCEEDED_METADATA_KEY: &str = "on_budget_exceeded";
+
+/// State metadata key holding the state's deadline in milliseconds
+pub const DEADLINE_METADATA_KEY: &str = "deadline_ms";
+
+/// Key under which the exceeded budget is recorded in the run context and metadata
+pub const BUDGET_EXCEEDED_KEY: &str = "budget_exceeded";
+
+/// Run metadata key holding how much of its budget a run had used when it took its timeout path
+///
+/// The timeout path is held to the same budget again, counted from that point.
+pub const TIMEOUT_PATH_START_KEY: &str = "timeout_path_start";
+
+/// Run metadata key holding the milliseconds a run spent paused or cancelled
+///
+/// Time the run was stopped does not count toward its timeout.
+pub const PAUSED_DURATION_KEY: &str = "paused_ms";
+
+/// Budgets a workflow run has to stay within
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkflowBudget {
+    /// Maximum wall-clock time of the whole run
+    pub timeout: Option<Duration>,
+    /// Maximum number of state transitions of the whole run
+    pub max_transitions: Option<usize>,
+    /// State the run moves to when a budget is exceeded
+    pub on_exceeded: Option<StateId>,
+}
+
+impl WorkflowBudget {
+    /// Read the budget stored in a workflow's metadata
+    pub fn of(workflow: &Workflow) -> Self {
+        let metadata = &workflow.metadata;
+        Self {
+            timeout: metadata
+                .get(TIMEOUT_METADATA_KEY)
+                .and_then(|ms| ms.parse().ok())
+                .map(Duration::from_millis),
+            max_transitions: metadata
+                .get(MAX_TRANSITIONS_METADATA_KEY)
+                .and_then(|limit| limit.parse().ok()),
+            on_exceeded: metadata
+                .get(ON_BUDGET_EXCEEDED_METADATA_KEY)
+                .map(|state| StateId::new(state.as_str())),
+        }
+    }
+
+    /// Store the budget in a workflow's metadata
+    pub fn apply_to(&self, workflow: &mut Workflow) {
+        if let Some(timeout) = self.timeout {
+            workflow.metadata.insert(
+                TIMEOUT_METADATA_KEY.to_string(),
+                timeout.as_millis().to_string(),
+            );
+        }
+        if let Some(max_transitions) = self.max_transitions {
+            workflow.metadata.insert(
+                MAX_TRANSITIONS_METADATA_KEY.to_string(),
+                max_transitions.to_string(),
+            );
+        }
+        if let Some(state) = &self.on_exceeded {
+            workflow.metadata.insert(
+                ON_BUDGET_EXCEEDED_METADATA_KEY.to_string(),
+                state.to_string(),
+            );
+        }
+    }
+}
+
+/// Get the deadline of a state, if it declares one
+pub fn state_deadline(state: &State) -> Option<Duration> {
+    state
+        .metadata
+        .get(DEADLINE_METADATA_KEY)
+        .and_then(|ms| ms.parse().ok())
+        .map(Duration::from_millis)
+}
+
+/// Kind of budget a run exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetKind {
+    /// The run took longer than the workflow timeout
+    Timeout,
+    /// The run needed more transitions than allowed
+    MaxTransitions,
+    /// A state ran past its deadline
+    Deadline,
+}
+
+/// Details of an exceeded budget
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetExceeded {
+    /// Kind of budget that was exceeded
+    pub kind: BudgetKind,
+    /// State the run was in
+    pub state: StateId,
+    /// Human-readable reason
+    pub reason: String,
+}
+
+impl BudgetExceeded {
+    /// The run exceeded the workflow timeout
+    pub fn timeout(limit: Duration, state: StateId) -> Self {
+        Self {
+            kind: BudgetKind::Timeout,
+            reason: format!("Workflow exceeded its timeout of {limit:?} in state {state}"),
+            state,
+        }
+    }
+
+    /// The run used up its transitions
+    pub fn max_transitions(limit: usize, state: StateId) -> Self {
+        Self {
+            kind: BudgetKind::MaxTransitions,
+            reason: format!("Workflow reached its limit of {limit} transitions in state {state}"),
+            state,
+        }
+    }
+
+    /// A state ran past its deadline
+    pub fn deadline(limit: Duration, state: StateId) -> Self {
+        Self {
+            kind: BudgetKind::Deadline,
+            reason: format!("State {state} exceeded its deadline of {limit:?}"),
+            state,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::test_helpers::create_state;
+    use crate::workflow::WorkflowName;
+
+    #[test]
+    fn test_budget_round_trips_through_workflow_metadata() {
+        let mut workflow = Workflow::new(
+            WorkflowName::new("Budget"),
+            "Budget test".to_string(),
+            StateId::new("start"),
+        );
+        assert_eq!(WorkflowBudget::of(&workflow), WorkflowBudget::default());
+
+        let budget = WorkflowBudget {
+            timeout: Some(Duration::from_secs(90)),
+            max_transitions: Some(25),
+            on_exceeded: Some(StateId::new("cleanup")),
+        };
+        budget.apply_to(&mut workflow);
+        assert_eq!(workflow.metadata[TIMEOUT_METADATA_KEY], "90000");
+        assert_eq!(WorkflowBudget::of(&workflow), budget);
+    }
+
+    #[test]
+    fn test_state_deadline() {
+        let mut state = create_state("review", "Review the code", false);
+        assert_eq!(state_deadline(&state), None);
+
+        state
+            .metadata
+            .insert(DEADLINE_METADATA_KEY.to_string(), "1500".to_string());
+        assert_eq!(state_deadline(&state), Some(Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn test_budget_exceeded_reasons() {
+        let exceeded = BudgetExceeded::max_transitions(10, StateId::new("loop"));
+        assert_eq!(exceeded.kind, BudgetKind::MaxTransitions);
+        assert_eq!(
+            exceeded.reason,
+            "Workflow reached its limit of 10 transitions in state loop"
+        );
+        assert_eq!(
+            serde_json::to_value(&exceeded).unwrap()["kind"],
+            "max_transitions"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Structured per-run event log
+//!
+//! Every [`ExecutionEvent`] logged by the executor for a run is appended as one
+//! JSON object per line to `<log dir>/<run id>.jsonl`. The log lives outside the
+//! run checkpoint directory so it is still available after a completed run's
+//! checkpoint has been removed.
+
+use crate::workflow::{ExecutionEvent, ExecutionEventType, WorkflowRunId, WorkflowTemplateContext};
+use crate::{Result, SwissArmyHammerError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Context key under which actions queue events for the executor to log
+pub const ACTION_EVENTS_KEY: &str = "_action_events";
+
+/// Severity of an execution event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventLevel {
+    /// Detailed diagnostic output such as rendered prompts and variable changes
+    Debug,
+    /// Normal progress of the run
+    Info,
+    /// Something unexpected that did not stop the run
+    Warn,
+    /// A failure
+    Error,
+}
+
+impl EventLevel {
+    /// Get the string representation of the level
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventLevel::Debug => "debug",
+            EventLevel::Info => "info",
+            EventLevel::Warn => "warn",
+            EventLevel::Error => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for EventLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for EventLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "debug" | "trace" => Ok(EventLevel::Debug),
+            "info" => Ok(EventLevel::Info),
+            "warn" | "warning" => Ok(EventLevel::Warn),
+            "error" => Ok(EventLevel::Error),
+            _ => Err(format!(
+                "Invalid log level '{s}': expected one of debug, info, warn, error"
+            )),
+        }
+    }
+}
+
+/// Append-only JSONL event log for a single workflow run
+#[derive(Debug, Clone)]
+pub struct RunEventLog {
+    path: PathBuf,
+}
+
+impl RunEventLog {
+    /// Create the event log for `run_id` inside `log_dir`
+    pub fn new(log_dir: impl AsRef<Path>, run_id: &WorkflowRunId) -> Self {
+        Self {
+            path: Self::path_for(log_dir, run_id),
+        }
+    }
+
+    /// Path of the event log for `run_id` inside `log_dir`
+    pub fn path_for(log_dir: impl AsRef<Path>, run_id: &WorkflowRunId) -> PathBuf {
+        log_dir.as_ref().join(format!("{run_id}.jsonl"))
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an event to the log
+    ///
+    /// The file is opened in append mode for every event so that each line is
+    /// flushed as soon as it is written and can be followed while the run is
+    /// still in progress.
+    pub fn append(&self, event: &ExecutionEvent) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Read all events from the log
+    pub fn read(&self) -> Result<Vec<ExecutionEvent>> {
+        Self::read_from(&self.path)
+    }
+
+    /// Read all events from the log file at `path`
+    ///
+    /// A trailing line that is still being written is skipped rather than
+    /// reported as an error.
+    pub fn read_from(path: &Path) -> Result<Vec<ExecutionEvent>> {
+        if !path.exists() {
+            return Err(SwissArmyHammerError::WorkflowRunNotFound(format!(
+                "No event log at {}",
+                path.display()
+            )));
+        }
+
+        let file = std::fs::File::open(path)?;
+        let mut events = Vec::new();
+        for line in std::io::BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<ExecutionEvent>(&line) {
+                Ok(event) => events.push(event),
+                Err(e) => tracing::debug!("Skipping unreadable event log line: {}", e),
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// Queue an event for the executor to record in the run's event log
+///
+/// Actions do not have access to the executor, so they report prompts, agent
+/// responses and command output through the context. The executor removes the
+/// queued events once the action returns.
+pub fn record_action_event(
+    context: &mut WorkflowTemplateContext,
+    event_type: ExecutionEventType,
+    level: EventLevel,
+    details: impl Into<String>,
+    data: Option<Value>,
+) {
+    let mut events = match context.remove(ACTION_EVENTS_KEY) {
+        Some(Value::Array(events)) => events,
+        _ => Vec::new(),
+    };
+
+    let mut event = ExecutionEvent::new(event_type, details.into()).with_level(level);
+    event.data = data;
+    match serde_json::to_value(&event) {
+        Ok(value) => events.push(value),
+        Err(e) => tracing::warn!("Failed to queue action event: {}", e),
+    }
+
+    context.insert(ACTION_EVENTS_KEY.to_string(), Value::Array(events));
+}
+
+/// Remove and return the events queued by actions
+pub fn take_action_events(context: &mut WorkflowTemplateContext) -> Vec<ExecutionEvent> {
+    match context.remove(ACTION_EVENTS_KEY) {
+        Some(Value::Array(events)) => events
+            .into_iter()
+            .filter_map(|event| serde_json::from_value(event).ok())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_event_level_parsing_and_ordering() {
+        assert_eq!("warn".parse::<EventLevel>().unwrap(), EventLevel::Warn);
+        assert_eq!("WARNING".parse::<EventLevel>().unwrap(), EventLevel::Warn);
+        assert_eq!("error".parse::<EventLevel>().unwrap(), EventLevel::Error);
+        assert!("verbose".parse::<EventLevel>().is_err());
+        assert!(EventLevel::Debug < EventLevel::Info);
+        assert!(EventLevel::Warn < EventLevel::Error);
+    }
+
+    #[test]
+    fn test_run_event_log_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_id = WorkflowRunId::new();
+        let log = RunEventLog::new(temp_dir.path().join("logs"), &run_id);
+
+        log.append(&ExecutionEvent::new(
+            ExecutionEventType::Started,
+            "Started".to_string(),
+        ))
+        .unwrap();
+        let mut output = ExecutionEvent::new(ExecutionEventType::ShellOutput, "out".to_string())
+            .with_level(EventLevel::Warn);
+        output.data = Some(serde_json::json!({"exit_code": 1}));
+        log.append(&output).unwrap();
+
+        // A partially written line is ignored
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(log.path())
+            .unwrap();
+        file.write_all(b"{\"timestamp\":").unwrap();
+
+        let events = log.read().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0].event_type, ExecutionEventType::Started));
+        assert_eq!(events[0].level, EventLevel::Info);
+        assert_eq!(events[1].level, EventLevel::Warn);
+        assert_eq!(events[1].data, Some(serde_json::json!({"exit_code": 1})));
+    }
+
+    #[test]
+    fn test_read_missing_event_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = RunEventLog::new(temp_dir.path(), &WorkflowRunId::new());
+        assert!(log.read().is_err());
+    }
+
+    #[test]
+    fn test_action_events_are_queued_and_taken() {
+        let mut context = WorkflowTemplateContext::with_vars_for_test(Default::default());
+        record_action_event(
+            &mut context,
+            ExecutionEventType::PromptRendered,
+            EventLevel::Debug,
+            "Rendered prompt",
+            Some(Value::String("hello".to_string())),
+        );
+        record_action_event(
+            &mut context,
+            ExecutionEventType::AgentResponse,
+            EventLevel::Info,
+            "Agent responded",
+            None,
+        );
+
+        let events = take_action_events(&mut context);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[0].event_type,
+            ExecutionEventType::PromptRendered
+        ));
+        assert_eq!(events[0].data, Some(Value::String("hello".to_string())));
+        assert!(!context.contains_key(ACTION_EVENTS_KEY));
+        assert!(take_action_events(&mut context).is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+//! Pausing runs that wait for human approval
+
+use super::checkpoint::CheckpointPhase;
+use super::core::WorkflowExecutor;
+use super::{ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult};
+use crate::workflow::approval::APPROVAL_REQUEST_KEY;
+use crate::workflow::{PendingApproval, WorkflowRun, WorkflowRunStatus};
+
+impl WorkflowExecutor {
+    /// Pause the run if the action that just ran asked for approval
+    ///
+    /// The pending approval is recorded on the run, which is left paused. The
+    /// state's action runs again when the run is resumed, and picks up the
+    /// decision recorded in the meantime.
+    pub(super) fn pause_for_approval_request(
+        &mut self,
+        run: &mut WorkflowRun,
+    ) -> ExecutorResult<()> {
+        let Some(request) = run.context.remove(APPROVAL_REQUEST_KEY) else {
+            return Ok(());
+        };
+        let message = request.as_str().unwrap_or_default().to_string();
+        let pending = PendingApproval::new(run.current_state.clone(), message);
+
+        run.set_pending_approval(&pending);
+        run.status = WorkflowRunStatus::Paused;
+        // The action has not finished, so a resumed run executes it again
+        self.checkpoint(run, CheckpointPhase::StateEntered);
+        self.log_event_with(
+            ExecutionEvent::new(
+                ExecutionEventType::ApprovalRequested,
+                format!(
+                    "State {} is waiting for approval: {}",
+                    pending.state, pending.message
+                ),
+            )
+            .with_data(serde_json::to_value(&pending).unwrap_or_default()),
+        );
+
+        Err(ExecutorError::ManualInterventionRequired(format!(
+            "State {} is waiting for approval of run {}",
+            pending.state, run.id
+        )))
+    }
+}
@@ -0,0 +1,251 @@
+//! Enforcing workflow budgets and state deadlines
+//!
+//! Before each state runs, the executor checks the run against the workflow's
+//! [`WorkflowBudget`], and bounds the state's execution by whatever is left of
+//! the timeout and by the state's own deadline. An exceeded budget moves the
+//! run onto its timeout path once, where the budget is checked again before
+//! every state, counted from the point the path was taken. Exceeding it on that
+//! path, or having no path to take, cancels the run, so a timeout path that
+//! loops back into the work cannot overspend without bound.
+
+use super::checkpoint::CHECKPOINT_TIMESTAMP_KEY;
+use super::core::WorkflowExecutor;
+use super::{ExecutionEvent, ExecutionEventType, ExecutorResult};
+use crate::workflow::{
+    state_deadline, BudgetExceeded, CompensationKey, WorkflowBudget, WorkflowRun,
+    WorkflowRunStatus, BUDGET_EXCEEDED_KEY, PAUSED_DURATION_KEY, TIMEOUT_PATH_START_KEY,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Budget a run had used when it took its timeout path
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct BudgetUsage {
+    /// Time the run had been going, excluding time spent paused
+    elapsed_ms: u64,
+    /// Transitions the run had made
+    transitions: usize,
+}
+
+impl BudgetUsage {
+    /// Budget the run has used so far
+    fn of(run: &WorkflowRun) -> Self {
+        Self {
+            elapsed_ms: u64::try_from(WorkflowExecutor::elapsed(run).as_millis())
+                .unwrap_or(u64::MAX),
+            transitions: run.history.len().saturating_sub(1),
+        }
+    }
+
+    /// Budget used before the run took its timeout path, or nothing if it has not
+    fn before_timeout_path(run: &WorkflowRun) -> Self {
+        run.metadata
+            .get(TIMEOUT_PATH_START_KEY)
+            .and_then(|usage| serde_json::from_str(usage).ok())
+            .unwrap_or_default()
+    }
+
+    /// Budget used since the run took its timeout path, or since it started
+    fn since_timeout_path(run: &WorkflowRun) -> Self {
+        let current = Self::of(run);
+        let before = Self::before_timeout_path(run);
+        Self {
+            elapsed_ms: current.elapsed_ms.saturating_sub(before.elapsed_ms),
+            transitions: current.transitions.saturating_sub(before.transitions),
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.elapsed_ms)
+    }
+}
+
+impl WorkflowExecutor {
+    /// Execute a single cycle unless the run is out of budget
+    ///
+    /// Returns whether a transition was performed, like
+    /// [`execute_single_cycle`](WorkflowExecutor::execute_single_cycle).
+    pub(super) async fn execute_cycle_within_budget(
+        &mut self,
+        run: &mut WorkflowRun,
+    ) -> ExecutorResult<bool> {
+        if Self::is_terminal_state(run) {
+            return self.execute_single_cycle(run).await;
+        }
+
+        if let Some(exceeded) = Self::exceeded_budget(run) {
+            return self.handle_budget_exceeded(run, exceeded).await;
+        }
+
+        let Some((time_limit, exceeded)) = Self::cycle_time_limit(run) else {
+            return self.execute_single_cycle(run).await;
+        };
+        match tokio::time::timeout(time_limit, self.execute_single_cycle(run)).await {
+            Ok(result) => result,
+            Err(_) => self.handle_budget_exceeded(run, exceeded).await,
+        }
+    }
+
+    /// Whether the run already took its timeout path
+    fn is_on_timeout_path(run: &WorkflowRun) -> bool {
+        run.metadata.contains_key(BUDGET_EXCEEDED_KEY)
+    }
+
+    /// Whether the current state ends the run, which is always allowed
+    fn is_terminal_state(run: &WorkflowRun) -> bool {
+        run.current_state.as_str() == "[*]"
+            || run
+                .workflow
+                .states
+                .get(&run.current_state)
+                .map(|state| state.is_terminal)
+                .unwrap_or(false)
+    }
+
+    /// Time the run has been going, excluding any time it spent paused
+    fn elapsed(run: &WorkflowRun) -> Duration {
+        (chrono::Utc::now() - run.started_at)
+            .to_std()
+            .unwrap_or(Duration::ZERO)
+            .saturating_sub(Self::paused_duration(run))
+    }
+
+    /// Total time the run spent paused or cancelled before it was resumed
+    fn paused_duration(run: &WorkflowRun) -> Duration {
+        run.metadata
+            .get(PAUSED_DURATION_KEY)
+            .and_then(|ms| ms.parse().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Prepare the budget of a stopped run that is about to resume
+    ///
+    /// The time since the run stopped is added to its paused duration. A run
+    /// that was on its timeout path stays on it, so resuming never grants the
+    /// path a fresh allowance or takes it a second time.
+    pub(super) fn restore_budget(run: &mut WorkflowRun) {
+        if !matches!(
+            run.status,
+            WorkflowRunStatus::Paused | WorkflowRunStatus::Cancelled
+        ) {
+            return;
+        }
+
+        let stopped_at = run.completed_at.or_else(|| {
+            run.metadata
+                .get(CHECKPOINT_TIMESTAMP_KEY)
+                .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+                .map(|at| at.with_timezone(&chrono::Utc))
+        });
+        if let Some(stopped_at) = stopped_at {
+            let stopped_for = (chrono::Utc::now() - stopped_at)
+                .to_std()
+                .unwrap_or(Duration::ZERO);
+            let paused = Self::paused_duration(run) + stopped_for;
+            run.metadata.insert(
+                PAUSED_DURATION_KEY.to_string(),
+                paused.as_millis().to_string(),
+            );
+        }
+    }
+
+    /// Check the workflow budget before the current state executes
+    ///
+    /// A run may make at most `max_transitions` transitions, so a state that is
+    /// entered by the last allowed transition and would have to leave again is
+    /// not executed. On the timeout path the budget is counted from the point
+    /// the path was taken.
+    fn exceeded_budget(run: &WorkflowRun) -> Option<BudgetExceeded> {
+        let budget = WorkflowBudget::of(&run.workflow);
+        let used = BudgetUsage::since_timeout_path(run);
+        if let Some(timeout) = budget.timeout {
+            if used.elapsed() >= timeout {
+                return Some(BudgetExceeded::timeout(timeout, run.current_state.clone()));
+            }
+        }
+        if let Some(max_transitions) = budget.max_transitions {
+            if used.transitions >= max_transitions {
+                return Some(BudgetExceeded::max_transitions(
+                    max_transitions,
+                    run.current_state.clone(),
+                ));
+            }
+        }
+        None
+    }
+
+    /// Longest time the current state may execute, and the budget it would exceed
+    fn cycle_time_limit(run: &WorkflowRun) -> Option<(Duration, BudgetExceeded)> {
+        let state = run.current_state.clone();
+        let remaining = WorkflowBudget::of(&run.workflow).timeout.map(|timeout| {
+            (
+                timeout.saturating_sub(BudgetUsage::since_timeout_path(run).elapsed()),
+                BudgetExceeded::timeout(timeout, state.clone()),
+            )
+        });
+        let deadline = run
+            .workflow
+            .states
+            .get(&state)
+            .and_then(state_deadline)
+            .map(|deadline| (deadline, BudgetExceeded::deadline(deadline, state)));
+
+        match (remaining, deadline) {
+            (Some(remaining), Some(deadline)) if deadline.0 < remaining.0 => Some(deadline),
+            (Some(remaining), _) => Some(remaining),
+            (None, deadline) => deadline,
+        }
+    }
+
+    /// Move the run onto its timeout path, or cancel it if there is none
+    ///
+    /// Returns whether a transition was performed.
+    async fn handle_budget_exceeded(
+        &mut self,
+        run: &mut WorkflowRun,
+        exceeded: BudgetExceeded,
+    ) -> ExecutorResult<bool> {
+        self.log_event_with(
+            ExecutionEvent::new(ExecutionEventType::BudgetExceeded, exceeded.reason.clone())
+                .with_data(serde_json::to_value(&exceeded).unwrap_or_default()),
+        );
+
+        let on_timeout_path = Self::is_on_timeout_path(run);
+        run.metadata
+            .insert(BUDGET_EXCEEDED_KEY.to_string(), exceeded.reason.clone());
+        run.context.insert(
+            BUDGET_EXCEEDED_KEY.to_string(),
+            serde_json::to_value(&exceeded).unwrap_or_default(),
+        );
+
+        if !on_timeout_path {
+            // The timeout path is held to the same budget again from here
+            if let Ok(usage) = serde_json::to_string(&BudgetUsage::of(run)) {
+                run.metadata
+                    .insert(TIMEOUT_PATH_START_KEY.to_string(), usage);
+            }
+
+            if let Some(timeout_state) = WorkflowBudget::of(&run.workflow).on_exceeded {
+                self.log_event(
+                    ExecutionEventType::StateTransition,
+                    format!("Budget exceeded, transitioning to {timeout_state}"),
+                );
+                self.perform_transition(run, timeout_state)?;
+                return Ok(true);
+            }
+
+            let has_compensation = run
+                .context
+                .iter()
+                .any(|(key, _)| CompensationKey::is_compensation_key(key));
+            if has_compensation {
+                self.execute_compensation(run).await?;
+                return Ok(true);
+            }
+        }
+
+        run.cancel(exceeded.reason);
+        Ok(false)
+    }
+}
@@ -0,0 +1,312 @@
+//! Durable checkpointing of workflow runs
+//!
+//! The executor writes a checkpoint of the [`WorkflowRun`] after every state
+//! transition and every action result. Each checkpoint records how far the
+//! current state got, so a run that was killed mid-execution can be resumed
+//! without repeating an action that already finished. An action that failed is
+//! run again on resume.
+//!
+//! Each checkpoint also records the process executing the run. A run still
+//! marked running whose process has exited was abandoned by a crash, kill or
+//! timeout and can be resumed like a paused one.
+
+use super::core::WorkflowExecutor;
+use super::fork_join::ParallelBranch;
+use super::{ExecutionEventType, ExecutorError, ExecutorResult};
+use crate::workflow::{StateId, WorkflowRun, WorkflowRunStatus, CANCEL_REASON_KEY};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Run metadata key recording how far execution of the current state got
+pub const CHECKPOINT_PHASE_KEY: &str = "checkpoint_phase";
+
+/// Run metadata key recording when the last checkpoint was taken
+pub const CHECKPOINT_TIMESTAMP_KEY: &str = "checkpointed_at";
+
+/// Run metadata key holding the id of the process that took the last checkpoint
+pub const CHECKPOINT_PID_KEY: &str = "checkpoint_pid";
+
+/// Run metadata key holding the error of an action that failed before the last checkpoint
+pub const CHECKPOINT_ERROR_KEY: &str = "checkpoint_error";
+
+/// How long a running run without a live process may go without a checkpoint before it is abandoned
+///
+/// Only used when the process executing the run cannot be checked.
+pub const ABANDONED_RUN_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Run metadata key holding the branches of a fork that already completed
+pub const FORK_PROGRESS_KEY: &str = "fork_progress";
+
+/// How far execution of the current state had progressed when a checkpoint was taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointPhase {
+    /// The run entered the current state but its action has not finished
+    StateEntered,
+    /// The current state's action finished and only the transition is outstanding
+    ActionCompleted,
+    /// The current state's action failed and must run again
+    ActionFailed,
+}
+
+impl CheckpointPhase {
+    /// Get the string representation stored in run metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckpointPhase::StateEntered => "state_entered",
+            CheckpointPhase::ActionCompleted => "action_completed",
+            CheckpointPhase::ActionFailed => "action_failed",
+        }
+    }
+
+    /// Parse a phase from its stored string representation
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "state_entered" => Some(CheckpointPhase::StateEntered),
+            "action_completed" => Some(CheckpointPhase::ActionCompleted),
+            "action_failed" => Some(CheckpointPhase::ActionFailed),
+            _ => None,
+        }
+    }
+
+    /// Read the phase recorded in a run's metadata
+    pub fn of(run: &WorkflowRun) -> Option<Self> {
+        run.metadata
+            .get(CHECKPOINT_PHASE_KEY)
+            .and_then(|phase| Self::parse(phase))
+    }
+}
+
+/// Branches of a fork state that completed before the last checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkProgress {
+    /// The fork state the branches belong to
+    pub fork_state: StateId,
+    /// Branches that reached the join state, in execution order
+    pub completed_branches: Vec<ParallelBranch>,
+}
+
+impl WorkflowRun {
+    /// Whether the run is marked running but nothing is executing it any more
+    ///
+    /// A run is abandoned once the process that took its last checkpoint has
+    /// exited. Where that process cannot be checked, a run is abandoned once it
+    /// has not been checkpointed for [`ABANDONED_RUN_TIMEOUT`].
+    pub fn is_abandoned(&self, now: DateTime<Utc>) -> bool {
+        if self.status != WorkflowRunStatus::Running {
+            return false;
+        }
+
+        let pid = self
+            .metadata
+            .get(CHECKPOINT_PID_KEY)
+            .and_then(|pid| pid.parse::<u32>().ok());
+        if let Some(alive) = pid.and_then(process_is_alive) {
+            return !alive;
+        }
+
+        self.metadata
+            .get(CHECKPOINT_TIMESTAMP_KEY)
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .zip(ChronoDuration::from_std(ABANDONED_RUN_TIMEOUT).ok())
+            .is_some_and(|(at, timeout)| now - at.with_timezone(&Utc) > timeout)
+    }
+
+    /// Whether the run was interrupted and can be resumed
+    ///
+    /// Paused and cancelled runs can be resumed unless they ran out of
+    /// [budget](Self::is_budget_cancelled); running runs only once they are
+    /// [abandoned](Self::is_abandoned).
+    pub fn is_resumable(&self) -> bool {
+        (self.status.is_resumable() && !self.is_budget_cancelled()) || self.is_abandoned(Utc::now())
+    }
+}
+
+/// Whether the process with the given id is still running, if that can be checked
+#[cfg(unix)]
+fn process_is_alive(pid: u32) -> Option<bool> {
+    if pid == std::process::id() {
+        return Some(true);
+    }
+    let pid = libc::pid_t::try_from(pid).ok()?;
+    // Signal 0 checks that the process exists without signalling it
+    // SAFETY: kill with signal 0 has no side effects
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return Some(true);
+    }
+    match std::io::Error::last_os_error().raw_os_error() {
+        Some(libc::EPERM) => Some(true),
+        Some(libc::ESRCH) => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether the process with the given id is still running, if that can be checked
+#[cfg(not(unix))]
+fn process_is_alive(pid: u32) -> Option<bool> {
+    (pid == std::process::id()).then_some(true)
+}
+
+impl WorkflowExecutor {
+    /// Record the run's progress and persist it to the checkpoint storage, if configured
+    ///
+    /// Checkpoint failures are logged rather than returned so that a storage
+    /// problem never aborts an otherwise healthy run.
+    pub fn checkpoint(&mut self, run: &mut WorkflowRun, phase: CheckpointPhase) {
+        run.metadata
+            .insert(CHECKPOINT_PHASE_KEY.to_string(), phase.as_str().to_string());
+        run.metadata.insert(
+            CHECKPOINT_TIMESTAMP_KEY.to_string(),
+            chrono::Utc::now().to_rfc3339(),
+        );
+        run.metadata.insert(
+            CHECKPOINT_PID_KEY.to_string(),
+            std::process::id().to_string(),
+        );
+        if phase != CheckpointPhase::ActionFailed {
+            run.metadata.remove(CHECKPOINT_ERROR_KEY);
+        }
+        Self::save_replay_positions(run);
+
+        if let Some(storage) = self.checkpoint_storage.as_mut() {
+            if let Err(e) = storage.store_run(run) {
+                tracing::warn!("Failed to checkpoint workflow run {}: {}", run.id, e);
+            }
+        }
+    }
+
+    /// Record that the current state's action failed and checkpoint the run
+    ///
+    /// A run resumed from this checkpoint executes the action again rather than
+    /// skipping it.
+    pub fn checkpoint_failure(&mut self, run: &mut WorkflowRun, error: &str) {
+        run.metadata
+            .insert(CHECKPOINT_ERROR_KEY.to_string(), error.to_string());
+        self.checkpoint(run, CheckpointPhase::ActionFailed);
+    }
+
+    /// Prepare a run loaded from a checkpoint for further execution
+    ///
+    /// Paused and cancelled runs are set back to running, with the time they were
+    /// stopped excluded from their budget. If the checkpoint shows
+    /// the current state's action already finished, the pending transition is
+    /// performed now so the action is not executed a second time. If the action
+    /// failed, the state is left to execute again.
+    ///
+    /// # Errors
+    /// The run was cancelled because it ran out of budget.
+    pub async fn restore_checkpoint(&mut self, run: &mut WorkflowRun) -> ExecutorResult<()> {
+        if run.is_budget_cancelled() {
+            return Err(ExecutorError::ExecutionFailed(format!(
+                "Run {} cannot be resumed: it was cancelled because it ran out of budget ({}). \
+                 Start a new run instead.",
+                run.id,
+                run.cancel_reason().unwrap_or_default()
+            )));
+        }
+
+        self.attach_event_log(run);
+        Self::restore_budget(run);
+
+        if matches!(
+            run.status,
+            WorkflowRunStatus::Paused | WorkflowRunStatus::Cancelled
+        ) {
+            run.status = WorkflowRunStatus::Running;
+            run.completed_at = None;
+            run.metadata.remove(CANCEL_REASON_KEY);
+        }
+
+        match CheckpointPhase::of(run) {
+            Some(CheckpointPhase::ActionCompleted) => {}
+            Some(CheckpointPhase::ActionFailed) => {
+                self.log_event(
+                    ExecutionEventType::StateExecution,
+                    format!(
+                        "Action for state {} failed before checkpoint ({}), running it again",
+                        run.current_state,
+                        run.metadata
+                            .get(CHECKPOINT_ERROR_KEY)
+                            .map(String::as_str)
+                            .unwrap_or("unknown error")
+                    ),
+                );
+                return Ok(());
+            }
+            _ => return Ok(()),
+        }
+
+        self.log_event(
+            ExecutionEventType::StateExecution,
+            format!(
+                "Action for state {} already completed before checkpoint, continuing with transition",
+                run.current_state
+            ),
+        );
+
+        let is_terminal = run
+            .workflow
+            .states
+            .get(&run.current_state)
+            .map(|state| state.is_terminal)
+            .unwrap_or(false);
+
+        if is_terminal {
+            run.complete();
+            self.checkpoint(run, CheckpointPhase::ActionCompleted);
+            return Ok(());
+        }
+
+        self.evaluate_and_perform_transition(run, None).await?;
+        Ok(())
+    }
+
+    /// Load the branches of `fork_state` that completed before the last checkpoint
+    pub fn load_fork_progress(
+        &self,
+        run: &WorkflowRun,
+        fork_state: &StateId,
+    ) -> Vec<ParallelBranch> {
+        run.metadata
+            .get(FORK_PROGRESS_KEY)
+            .and_then(|json| serde_json::from_str::<ForkProgress>(json).ok())
+            .filter(|progress| &progress.fork_state == fork_state)
+            .map(|progress| progress.completed_branches)
+            .unwrap_or_default()
+    }
+
+    /// Record a completed fork branch and checkpoint the run
+    pub fn record_fork_branch(
+        &mut self,
+        run: &mut WorkflowRun,
+        fork_state: &StateId,
+        branch: &ParallelBranch,
+    ) {
+        let mut completed_branches = self.load_fork_progress(run, fork_state);
+        completed_branches.push(branch.clone());
+
+        let progress = ForkProgress {
+            fork_state: fork_state.clone(),
+            completed_branches,
+        };
+
+        match serde_json::to_string(&progress) {
+            Ok(json) => {
+                run.metadata.insert(FORK_PROGRESS_KEY.to_string(), json);
+                self.checkpoint(run, CheckpointPhase::StateEntered);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to serialize fork progress for {}: {}",
+                    fork_state,
+                    e
+                );
+            }
+        }
+    }
+
+    /// Discard recorded fork progress once all branches have been merged
+    pub fn clear_fork_progress(&self, run: &mut WorkflowRun) {
+        run.metadata.remove(FORK_PROGRESS_KEY);
+    }
+}
@@ -1,17 +1,21 @@
 //! Core workflow execution logic
 
+use super::checkpoint::CheckpointPhase;
 use super::{
     ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult, DEFAULT_MAX_HISTORY_SIZE,
-    LAST_ACTION_RESULT_KEY, MAX_TRANSITIONS,
+    LAST_ACTION_RESULT_KEY, MAX_TRANSITIONS, RUN_STATUS_KEY,
 };
+use crate::workflow::event_log::{take_action_events, RunEventLog};
 use crate::workflow::{
     metrics::{MemoryMetrics, WorkflowMetrics},
-    parse_action_from_description_with_context, ActionError, CompensationKey, ErrorContext,
-    StateId, TransitionKey, TransitionPath, Workflow, WorkflowCacheManager, WorkflowRun,
-    WorkflowRunStatus,
+    parse_action_from_description_with_context, retry_declaration, ActionError, Cassette,
+    CompensationKey, ErrorContext, StateId, TransitionKey, TransitionPath, Workflow,
+    WorkflowCacheManager, WorkflowRun, WorkflowRunStatus, WorkflowRunStorageBackend,
+    RECORD_CASSETTE_KEY, REPLAY_CASSETTE_KEY, REPLAY_POSITIONS_KEY,
 };
 use cel_interpreter::Program;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -29,6 +33,14 @@ pub struct WorkflowExecutor {
     test_storage: Option<Arc<crate::workflow::storage::WorkflowStorage>>,
     /// Working directory for file operations (including abort file)
     working_dir: std::path::PathBuf,
+    /// Optional run storage that receives a checkpoint after every state transition and action result
+    pub(super) checkpoint_storage: Option<Box<dyn WorkflowRunStorageBackend>>,
+    /// Optional directory that receives a JSONL event log for every run
+    event_log_dir: Option<std::path::PathBuf>,
+    /// Event log of the run currently being executed
+    event_log: Option<RunEventLog>,
+    /// State the current run is in, recorded on every logged event
+    active_state: Option<StateId>,
 }
 
 impl WorkflowExecutor {
@@ -41,6 +53,10 @@ impl WorkflowExecutor {
             cache_manager: WorkflowCacheManager::new(),
             test_storage: None,
             working_dir: std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")),
+            checkpoint_storage: None,
+            event_log_dir: None,
+            event_log: None,
+            active_state: None,
         }
     }
 
@@ -53,6 +69,10 @@ impl WorkflowExecutor {
             cache_manager: WorkflowCacheManager::new(),
             test_storage: None,
             working_dir: working_dir.as_ref().to_path_buf(),
+            checkpoint_storage: None,
+            event_log_dir: None,
+            event_log: None,
+            active_state: None,
         }
     }
 
@@ -65,6 +85,50 @@ impl WorkflowExecutor {
             cache_manager: WorkflowCacheManager::new(),
             test_storage: Some(storage),
             working_dir: std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")),
+            checkpoint_storage: None,
+            event_log_dir: None,
+            event_log: None,
+            active_state: None,
+        }
+    }
+
+    /// Persist a checkpoint of the run to the given storage after every state transition and action result
+    pub fn with_checkpoint_storage(mut self, storage: Box<dyn WorkflowRunStorageBackend>) -> Self {
+        self.checkpoint_storage = Some(storage);
+        self
+    }
+
+    /// Set the storage that receives run checkpoints
+    pub fn set_checkpoint_storage(&mut self, storage: Box<dyn WorkflowRunStorageBackend>) {
+        self.checkpoint_storage = Some(storage);
+    }
+
+    /// Get the storage that receives run checkpoints, if any
+    pub fn checkpoint_storage(&self) -> Option<&dyn WorkflowRunStorageBackend> {
+        self.checkpoint_storage.as_deref()
+    }
+
+    /// Record the events of every run in a JSONL event log inside `dir`
+    pub fn with_event_log_dir<P: AsRef<std::path::Path>>(mut self, dir: P) -> Self {
+        self.set_event_log_dir(dir);
+        self
+    }
+
+    /// Record the events of every run in a JSONL event log inside `dir`
+    pub fn set_event_log_dir<P: AsRef<std::path::Path>>(&mut self, dir: P) {
+        self.event_log_dir = Some(dir.as_ref().to_path_buf());
+    }
+
+    /// Get the event log of the run currently being executed, if event logging is enabled
+    pub fn event_log(&self) -> Option<&RunEventLog> {
+        self.event_log.as_ref()
+    }
+
+    /// Direct subsequent events to the event log of `run`
+    pub(super) fn attach_event_log(&mut self, run: &WorkflowRun) {
+        self.active_state = Some(run.current_state.clone());
+        if let Some(dir) = &self.event_log_dir {
+            self.event_log = Some(RunEventLog::new(dir, &run.id));
         }
     }
 
@@ -91,6 +155,7 @@ impl WorkflowExecutor {
         // Start metrics tracking for this run
         self.metrics.start_run(run.id, run.workflow.name.clone());
 
+        self.attach_event_log(&run);
         self.log_event(
             ExecutionEventType::Started,
             format!("Started workflow: {}", run.workflow.name),
@@ -134,6 +199,7 @@ impl WorkflowExecutor {
         // Start metrics tracking for resumed run
         self.metrics.start_run(run.id, run.workflow.name.clone());
 
+        self.attach_event_log(&run);
         self.log_event(
             ExecutionEventType::Started,
             format!(
@@ -142,10 +208,16 @@ impl WorkflowExecutor {
             ),
         );
 
-        // Continue execution from current state with transition limit
-        let result = self
-            .execute_state_with_limit(&mut run, MAX_TRANSITIONS)
-            .await;
+        // Continue execution from current state with transition limit, skipping
+        // any work the last checkpoint shows was already done
+        let result = match self.restore_checkpoint(&mut run).await {
+            Ok(()) if self.is_workflow_finished(&run) => Ok(()),
+            Ok(()) => {
+                self.execute_state_with_limit(&mut run, MAX_TRANSITIONS)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
 
         // Complete metrics tracking
         match &result {
@@ -173,6 +245,15 @@ impl WorkflowExecutor {
         // Execute the state and capture any errors
         let state_error = self.execute_state_and_capture_errors(run).await?;
 
+        // Record that the state's action has finished so a resumed run does not repeat it.
+        // A failed action is recorded as such so a resumed run retries it, and a state
+        // still waiting on manual intervention has not finished and must run again.
+        match &state_error {
+            Some(ExecutorError::ManualInterventionRequired(_)) => {}
+            Some(error) => self.checkpoint_failure(run, &error.to_string()),
+            None => self.checkpoint(run, CheckpointPhase::ActionCompleted),
+        }
+
         // Check if abort was requested via context variable (after state execution)
         if let Some(abort_reason_value) = run.context.get_workflow_var("__ABORT_REQUESTED__") {
             if let Some(abort_reason) = abort_reason_value.as_str() {
@@ -218,7 +299,7 @@ impl WorkflowExecutor {
     }
 
     /// Evaluate transitions and perform them if available
-    async fn evaluate_and_perform_transition(
+    pub(super) async fn evaluate_and_perform_transition(
         &mut self,
         run: &mut WorkflowRun,
         state_error: Option<ExecutorError>,
@@ -242,10 +323,57 @@ impl WorkflowExecutor {
     }
 
     /// Execute states with a maximum transition limit to prevent infinite loops
+    ///
+    /// Cassettes the run records to or replays from are released once execution
+    /// stops, so they are not kept in memory after the run.
     pub async fn execute_state_with_limit(
         &mut self,
         run: &mut WorkflowRun,
         remaining_transitions: usize,
+    ) -> ExecutorResult<()> {
+        let result = self
+            .execute_transitions_with_limit(run, remaining_transitions)
+            .await;
+        Self::close_cassettes(run);
+        result
+    }
+
+    /// Copy the replay positions of the run's cassette into its context
+    ///
+    /// Checkpointed with the context, they let a resumed run replay from where
+    /// it stopped.
+    pub(super) fn save_replay_positions(run: &mut WorkflowRun) {
+        let Some(path) = run
+            .context
+            .get(REPLAY_CASSETTE_KEY)
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+        else {
+            return;
+        };
+        if let Some(positions) = Cassette::open_replay_positions(&path) {
+            run.context.insert(
+                REPLAY_POSITIONS_KEY.to_string(),
+                serde_json::to_value(positions).unwrap_or_default(),
+            );
+        }
+    }
+
+    /// Release the cassettes the run records to or replays from
+    fn close_cassettes(run: &mut WorkflowRun) {
+        Self::save_replay_positions(run);
+        for key in [RECORD_CASSETTE_KEY, REPLAY_CASSETTE_KEY] {
+            if let Some(path) = run.context.get(key).and_then(|value| value.as_str()) {
+                Cassette::close(path);
+            }
+        }
+    }
+
+    /// Run the state execution loop until the run stops or the limit is reached
+    async fn execute_transitions_with_limit(
+        &mut self,
+        run: &mut WorkflowRun,
+        remaining_transitions: usize,
     ) -> ExecutorResult<()> {
         // Abort file checking happens at the flow command level before execution begins
 
@@ -276,7 +404,7 @@ impl WorkflowExecutor {
                 "Workflow execution loop - current state: {}",
                 run.current_state
             );
-            let transition_performed = self.execute_single_cycle(run).await?;
+            let transition_performed = self.execute_cycle_within_budget(run).await?;
 
             if !transition_performed {
                 // Either workflow finished or no transitions available
@@ -303,6 +431,7 @@ impl WorkflowExecutor {
     /// Execute a single state without transitioning
     pub async fn execute_single_state(&mut self, run: &mut WorkflowRun) -> ExecutorResult<()> {
         let current_state_id = run.current_state.clone();
+        self.active_state = Some(current_state_id.clone());
 
         // Skip execution for terminal states (they have no actions)
         if current_state_id.as_str() == "[*]" {
@@ -327,6 +456,11 @@ impl WorkflowExecutor {
             return self.execute_choice_state(run).await;
         }
 
+        // Check if this is a map state
+        if self.is_map_state(run, &current_state_id) {
+            return self.execute_map_state(run).await;
+        }
+
         // Get the current state
         let current_state = run
             .workflow
@@ -452,6 +586,10 @@ impl WorkflowExecutor {
 
         // Update the run
         run.transition_to(next_state);
+        self.active_state = Some(run.current_state.clone());
+
+        // Persist the new position before the state starts executing
+        self.checkpoint(run, CheckpointPhase::StateEntered);
 
         Ok(())
     }
@@ -514,17 +652,36 @@ impl WorkflowExecutor {
         if let Some(action) =
             parse_action_from_description_with_context(&action_text, &context_hashmap)?
         {
-            self.log_event(
-                ExecutionEventType::StateExecution,
-                format!("Executing action: {}", action.description()),
+            let retry_policy = self.retry_policy_for_state(run, state_description)?;
+            self.log_event_with(
+                ExecutionEvent::new(
+                    ExecutionEventType::ActionStarted,
+                    format!("Executing action: {}", action.description()),
+                )
+                .with_data(serde_json::json!({
+                    "action_type": action.action_type(),
+                    "action": action_text,
+                    "store_as": store_as_var,
+                    "retry": retry_policy.as_ref().map(|policy| policy.to_string()),
+                })),
             );
 
-            // Execute the action and handle result
-            let result = self.execute_action_direct(run, action).await;
+            // Execute the action, retrying if the state allows it, and record what it reported
+            let vars_before = run.context.workflow_vars();
+            let result = self
+                .execute_action_with_retry(run, action.as_ref(), retry_policy.as_ref())
+                .await;
+            self.log_action_events(&mut run.context);
+            if result.is_ok() {
+                self.pause_for_approval_request(run)?;
+            }
 
             // Handle the result and optionally store it in the Store As variable
-            self.handle_action_result_with_store_as(run, result, store_as_var)
-                .await?;
+            let outcome = self
+                .handle_action_result_with_store_as(run, result, store_as_var)
+                .await;
+            self.log_variable_changes(&vars_before, &run.context.workflow_vars());
+            outcome?;
             Ok(true)
         } else {
             tracing::warn!("No action could be parsed from: '{}'", action_text);
@@ -569,26 +726,21 @@ impl WorkflowExecutor {
             }
         }
 
-        // If no action found, use the entire description as fallback
+        // If no action found, use the entire description as fallback,
+        // leaving out the retry policy declaration
         if action_text.is_empty() {
-            action_text = state_description.to_string();
+            action_text = state_description
+                .lines()
+                .filter(|line| retry_declaration(line).is_none())
+                .collect::<Vec<_>>()
+                .join("\n");
         }
 
         (action_text, store_as_var)
     }
 
-    /// Execute action directly without retry logic
-    async fn execute_action_direct(
-        &mut self,
-        run: &mut WorkflowRun,
-        action: Box<dyn crate::workflow::Action>,
-    ) -> Result<Value, ActionError> {
-        // Execute action with mutable WorkflowTemplateContext directly
-        action.execute(&mut run.context).await
-    }
-
     /// Handle the result of action execution with optional Store As variable
-    async fn handle_action_result_with_store_as(
+    pub(super) async fn handle_action_result_with_store_as(
         &mut self,
         run: &mut WorkflowRun,
         result: Result<Value, ActionError>,
@@ -670,8 +822,11 @@ impl WorkflowExecutor {
         let error_details = self.format_action_error(&action_error);
         self.log_event(ExecutionEventType::Failed, error_details);
 
+        // A rejected approval is final, so it is neither dead-lettered nor skipped
+        let rejected = matches!(action_error, ActionError::ApprovalRejected(_));
+
         // Check for dead letter state configuration
-        if let Some(dead_letter_state) = self.get_dead_letter_state(run) {
+        if let Some(dead_letter_state) = self.get_dead_letter_state(run).filter(|_| !rejected) {
             return self
                 .handle_dead_letter_transition(run, dead_letter_state, &action_error)
                 .await;
@@ -686,7 +841,7 @@ impl WorkflowExecutor {
         }
 
         // Check if this state should be skipped on failure
-        if self.should_skip_on_failure(run) {
+        if !rejected && self.should_skip_on_failure(run) {
             self.log_event(
                 ExecutionEventType::StateExecution,
                 "Skipped failed state due to skip_on_failure configuration".to_string(),
@@ -703,7 +858,14 @@ impl WorkflowExecutor {
 
     /// Capture error context for the action error
     fn capture_error_context(&mut self, run: &mut WorkflowRun, action_error: &ActionError) {
-        let error_context = ErrorContext::new(action_error.to_string(), run.current_state.clone());
+        let error_context = match Self::retry_attempts(run) {
+            Some(attempts) => ErrorContext::with_retries(
+                action_error.to_string(),
+                run.current_state.clone(),
+                attempts,
+            ),
+            None => ErrorContext::new(action_error.to_string(), run.current_state.clone()),
+        };
         let error_context_json = serde_json::to_value(&error_context).unwrap_or(Value::Null);
         run.context
             .insert(ErrorContext::CONTEXT_KEY.to_string(), error_context_json);
@@ -733,6 +895,7 @@ impl WorkflowExecutor {
             ActionError::ShellSecurityError(security_error) => {
                 format!("Shell security violation: {security_error}")
             }
+            ActionError::ApprovalRejected(msg) => format!("Approval rejected by {msg}"),
         }
     }
 
@@ -786,7 +949,10 @@ impl WorkflowExecutor {
     }
 
     /// Execute compensation states in reverse order
-    async fn execute_compensation(&mut self, run: &mut WorkflowRun) -> ExecutorResult<()> {
+    pub(super) async fn execute_compensation(
+        &mut self,
+        run: &mut WorkflowRun,
+    ) -> ExecutorResult<()> {
         self.log_event(
             ExecutionEventType::StateExecution,
             "Starting compensation/rollback".to_string(),
@@ -821,14 +987,31 @@ impl WorkflowExecutor {
         Ok(())
     }
 
-    /// Log an execution event
+    /// Log an execution event at the default level for its type
     pub fn log_event(&mut self, event_type: ExecutionEventType, details: String) {
-        tracing::trace!("{}: {}", event_type, &details);
-        let event = ExecutionEvent {
-            timestamp: chrono::Utc::now(),
-            event_type,
-            details,
-        };
+        self.log_event_with(ExecutionEvent::new(event_type, details));
+    }
+
+    /// Log a fully specified execution event
+    ///
+    /// The event is kept in the in-memory history and appended to the run's
+    /// event log if one is attached.
+    pub fn log_event_with(&mut self, mut event: ExecutionEvent) {
+        tracing::trace!("{}: {}", event.event_type, &event.details);
+        if event.state.is_none() {
+            event.state = self.active_state.clone();
+        }
+
+        if let Some(event_log) = &self.event_log {
+            if let Err(e) = event_log.append(&event) {
+                tracing::warn!(
+                    "Failed to write event log {}: {}",
+                    event_log.path().display(),
+                    e
+                );
+            }
+        }
+
         self.execution_history.push(event);
 
         // Trim history if it exceeds max size
@@ -838,6 +1021,94 @@ impl WorkflowExecutor {
         }
     }
 
+    /// Log the events that actions queued in the context while they ran
+    pub(super) fn log_action_events(
+        &mut self,
+        context: &mut crate::workflow::WorkflowTemplateContext,
+    ) {
+        for event in take_action_events(context) {
+            self.log_event_with(event);
+        }
+    }
+
+    /// Log every workflow variable an action set, changed or removed
+    ///
+    /// Internal variables (prefixed with `_`) are not logged.
+    pub(super) fn log_variable_changes(
+        &mut self,
+        before: &HashMap<String, Value>,
+        after: &HashMap<String, Value>,
+    ) {
+        let mut changed: Vec<(&String, Option<&Value>, Option<&Value>)> = after
+            .iter()
+            .filter(|(key, value)| before.get(*key) != Some(*value))
+            .map(|(key, value)| (key, before.get(key), Some(value)))
+            .chain(
+                before
+                    .iter()
+                    .filter(|(key, _)| !after.contains_key(*key))
+                    .map(|(key, value)| (key, Some(value), None)),
+            )
+            .filter(|(key, _, _)| !key.starts_with('_'))
+            .collect();
+        changed.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (key, old, new) in changed {
+            let details = match new {
+                Some(value) => format!("Variable {key} = {value}"),
+                None => format!("Variable {key} removed"),
+            };
+            self.log_event_with(
+                ExecutionEvent::new(ExecutionEventType::VariableChanged, details)
+                    .with_data(serde_json::json!({ "name": key, "old": old, "new": new })),
+            );
+        }
+    }
+
+    /// Log the final status of a run so followers of the event log know it has stopped
+    pub fn log_run_outcome(&mut self, run: &WorkflowRun) {
+        let (event_type, details) = match run.status {
+            WorkflowRunStatus::Completed => (
+                ExecutionEventType::Completed,
+                format!("Workflow {} completed", run.workflow.name),
+            ),
+            WorkflowRunStatus::Failed => (
+                ExecutionEventType::Failed,
+                format!("Workflow {} failed", run.workflow.name),
+            ),
+            WorkflowRunStatus::Cancelled => (
+                ExecutionEventType::StateExecution,
+                format!(
+                    "Workflow {} cancelled: {}",
+                    run.workflow.name,
+                    run.cancel_reason().unwrap_or("no reason given")
+                ),
+            ),
+            status => (
+                ExecutionEventType::StateExecution,
+                format!(
+                    "Workflow {} stopped with status {:?}",
+                    run.workflow.name, status
+                ),
+            ),
+        };
+        self.log_event_with(
+            ExecutionEvent::new(event_type, details)
+                .with_data(serde_json::json!({ RUN_STATUS_KEY: run.status })),
+        );
+    }
+
+    /// Log that a run failed with `error`, in place of [`Self::log_run_outcome`]
+    pub fn log_run_failure(&mut self, run: &WorkflowRun, error: &str) {
+        self.log_event_with(
+            ExecutionEvent::new(
+                ExecutionEventType::Failed,
+                format!("Workflow {} failed: {error}", run.workflow.name),
+            )
+            .with_data(serde_json::json!({ RUN_STATUS_KEY: run.status })),
+        );
+    }
+
     /// Get the execution history
     pub fn get_history(&self) -> &[ExecutionEvent] {
         &self.execution_history
@@ -1,15 +1,18 @@
 //! Fork/join parallel execution functionality
 
 use super::core::WorkflowExecutor;
-use super::{ExecutionEventType, ExecutorError, ExecutorResult, LAST_ACTION_RESULT_KEY};
+use super::{
+    ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult, LAST_ACTION_RESULT_KEY,
+};
 use crate::workflow::{
     parse_action_from_description_with_context, StateId, StateType, Workflow, WorkflowRun,
     WorkflowTemplateContext,
 };
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// Represents a parallel execution branch
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParallelBranch {
     /// The state this branch is currently in
     pub current_state: StateId,
@@ -19,6 +22,13 @@ pub struct ParallelBranch {
     pub history: Vec<(StateId, chrono::DateTime<chrono::Utc>)>,
 }
 
+impl ParallelBranch {
+    /// The state this branch started from
+    pub fn origin_state(&self) -> Option<&StateId> {
+        self.history.first().map(|(state_id, _)| state_id)
+    }
+}
+
 impl WorkflowExecutor {
     /// Check if a state matches a specific state type
     pub fn is_state_type(
@@ -140,6 +150,7 @@ impl WorkflowExecutor {
 
         // Merge contexts from all branches
         self.merge_branch_contexts(run, completed_branches)?;
+        self.clear_fork_progress(run);
 
         // Transition to the join state
         run.transition_to(join_state);
@@ -216,6 +227,8 @@ impl WorkflowExecutor {
     /// Execute parallel branches
     ///
     /// Executes all branches sequentially with isolated contexts until they reach the join state.
+    /// Each completed branch is checkpointed, and branches recorded as completed by an
+    /// earlier checkpoint of this fork are reused instead of being executed again.
     ///
     /// # Arguments
     /// - `run`: The workflow run context
@@ -227,13 +240,27 @@ impl WorkflowExecutor {
     /// - `Err(ExecutorError)`: If any branch execution fails
     async fn execute_parallel_branches(
         &mut self,
-        run: &WorkflowRun,
+        run: &mut WorkflowRun,
         branch_states: &[StateId],
         join_state: &StateId,
     ) -> ExecutorResult<Vec<ParallelBranch>> {
+        let fork_state = run.current_state.clone();
+        let mut checkpointed_branches = self.load_fork_progress(run, &fork_state);
         let mut completed_branches = Vec::new();
 
         for branch_state in branch_states {
+            if let Some(index) = checkpointed_branches
+                .iter()
+                .position(|branch| branch.origin_state() == Some(branch_state))
+            {
+                self.log_event(
+                    ExecutionEventType::StateExecution,
+                    format!("Branch {branch_state} already completed before checkpoint"),
+                );
+                completed_branches.push(checkpointed_branches.remove(index));
+                continue;
+            }
+
             // Create a branch with a copy of the current context
             let mut branch = ParallelBranch {
                 current_state: branch_state.clone(),
@@ -250,6 +277,7 @@ impl WorkflowExecutor {
                 format!("Branch {branch_state} completed"),
             );
 
+            self.record_fork_branch(run, &fork_state, &branch);
             completed_branches.push(branch);
         }
 
@@ -338,25 +366,43 @@ impl WorkflowExecutor {
                 &current_state.description,
                 &branch.context.to_workflow_hashmap(),
             )? {
-                self.log_event(
-                    ExecutionEventType::StateExecution,
-                    format!("Branch executing action: {}", action.description()),
+                self.log_event_with(
+                    ExecutionEvent::new(
+                        ExecutionEventType::ActionStarted,
+                        format!("Branch executing action: {}", action.description()),
+                    )
+                    .with_data(serde_json::json!({
+                        "action_type": action.action_type(),
+                        "branch_state": branch.current_state,
+                    })),
                 );
 
-                match action.execute(&mut branch.context).await {
+                let vars_before = branch.context.workflow_vars();
+                let result = action.execute(&mut branch.context).await;
+                self.log_action_events(&mut branch.context);
+
+                match result {
                     Ok(_result) => {
                         // Mark action as successful
                         branch
                             .context
                             .insert(LAST_ACTION_RESULT_KEY.to_string(), Value::Bool(true));
                     }
-                    Err(_action_error) => {
+                    Err(action_error) => {
+                        self.log_event(
+                            ExecutionEventType::Failed,
+                            format!(
+                                "Branch action in state {} failed: {}",
+                                branch.current_state, action_error
+                            ),
+                        );
                         // Mark action as failed
                         branch
                             .context
                             .insert(LAST_ACTION_RESULT_KEY.to_string(), Value::Bool(false));
                     }
                 }
+                self.log_variable_changes(&vars_before, &branch.context.workflow_vars());
             }
 
             // Find next transition based on conditions
@@ -0,0 +1,251 @@
+//! Map state execution: fanning out over a list variable
+//!
+//! A map state runs its sub-workflow or action for every element of a list
+//! variable. Each element gets its own copy of the run context, and at most
+//! `max_concurrency` elements are processed at the same time. Elements run
+//! concurrently on the executor's own task rather than in spawned tasks, so
+//! they see the same workflow storage as the rest of the run. Results, and
+//! the variables each element set, are gathered in list order and stored like
+//! the result of a state action, so the usual `OnSuccess`/`OnFailure`
+//! transitions apply.
+
+use super::core::WorkflowExecutor;
+use super::{
+    ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult, LAST_ACTION_RESULT_KEY,
+};
+use crate::workflow::map::map_items;
+use crate::workflow::{
+    map_spec, parse_action_from_description_with_context, Action, ActionError, MapFailurePolicy,
+    MapItemError, MapSpec, StateId, StateType, SubWorkflowAction, WorkflowRun,
+    WorkflowTemplateContext, MAP_INDEX_VARIABLE,
+};
+use futures::stream::{self, StreamExt};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Outcome of processing one element of a map state
+struct MapItemOutcome {
+    index: usize,
+    item: Value,
+    result: Result<Value, ActionError>,
+    context: WorkflowTemplateContext,
+}
+
+impl WorkflowExecutor {
+    /// Check if a state is a map state
+    pub fn is_map_state(&self, run: &WorkflowRun, state_id: &StateId) -> bool {
+        self.is_state_type(run, state_id, StateType::Map)
+    }
+
+    /// Execute a map state - run its sub-workflow or action for each element of a list
+    ///
+    /// # Errors
+    /// - The state has no map specification
+    /// - The list variable is missing or does not hold a list
+    /// - An element failed and the state fails fast
+    pub async fn execute_map_state(&mut self, run: &mut WorkflowRun) -> ExecutorResult<()> {
+        let map_state = run.current_state.clone();
+        let spec = run
+            .workflow
+            .states
+            .get(&map_state)
+            .and_then(map_spec)
+            .ok_or_else(|| {
+                ExecutorError::ExecutionFailed(format!(
+                    "Map state '{map_state}' has no map specification"
+                ))
+            })?;
+
+        let items = match run.context.get(&spec.over).and_then(map_items) {
+            Some(items) => items,
+            None => {
+                let error = ActionError::VariableError(format!(
+                    "Map state '{map_state}' expects variable '{}' to hold a list",
+                    spec.over
+                ));
+                return self
+                    .handle_action_result_with_store_as(run, Err(error), None)
+                    .await;
+            }
+        };
+
+        self.log_event_with(
+            ExecutionEvent::new(
+                ExecutionEventType::StateExecution,
+                format!(
+                    "Map state {map_state} processing {} items of '{}' with concurrency {}",
+                    items.len(),
+                    spec.over,
+                    spec.max_concurrency
+                ),
+            )
+            .with_data(serde_json::json!({
+                "items": items.len(),
+                "over": spec.over,
+                "workflow": spec.workflow,
+                "action": spec.action,
+                "max_concurrency": spec.max_concurrency,
+                "on_error": spec.on_error.as_str(),
+            })),
+        );
+
+        let vars_before = run.context.workflow_vars();
+        let item_count = items.len();
+        let outcomes = Self::run_map_items(run, &spec, items).await;
+
+        let mut results = vec![Value::Null; item_count];
+        let mut outputs = vec![Value::Null; item_count];
+        let mut errors = Vec::new();
+        for mut outcome in outcomes {
+            self.log_action_events(&mut outcome.context);
+            outputs[outcome.index] = Self::map_item_outputs(&spec, &vars_before, &outcome.context);
+            match outcome.result {
+                Ok(value) => results[outcome.index] = value,
+                Err(error) => {
+                    self.log_event(
+                        ExecutionEventType::Failed,
+                        format!(
+                            "Map state {map_state} item {} failed: {error}",
+                            outcome.index
+                        ),
+                    );
+                    errors.push((
+                        MapItemError {
+                            index: outcome.index,
+                            item: outcome.item,
+                            error: error.to_string(),
+                        },
+                        error,
+                    ));
+                }
+            }
+        }
+        errors.sort_by_key(|(item_error, _)| item_error.index);
+
+        let result = match spec.on_error {
+            MapFailurePolicy::FailFast if !errors.is_empty() => {
+                let (_, error) = errors.remove(0);
+                Err(error)
+            }
+            _ => {
+                let item_errors: Vec<MapItemError> = errors
+                    .into_iter()
+                    .map(|(item_error, _)| item_error)
+                    .collect();
+                run.context.insert(
+                    spec.errors_variable(),
+                    serde_json::to_value(&item_errors).unwrap_or_default(),
+                );
+                run.context
+                    .insert(spec.outputs_variable(), Value::Array(outputs));
+                self.log_event(
+                    ExecutionEventType::StateExecution,
+                    format!(
+                        "Map state {map_state} completed {item_count} items, {} failed",
+                        item_errors.len()
+                    ),
+                );
+                Ok(Value::Array(results))
+            }
+        };
+
+        let outcome = self
+            .handle_action_result_with_store_as(run, result, Some(spec.result_variable.clone()))
+            .await;
+        self.log_variable_changes(&vars_before, &run.context.workflow_vars());
+        outcome
+    }
+
+    /// Process the elements with at most `max_concurrency` of them in flight
+    ///
+    /// When the state fails fast, elements still running after the first
+    /// failure are cancelled and left out of the outcomes.
+    async fn run_map_items(
+        run: &WorkflowRun,
+        spec: &MapSpec,
+        items: Vec<Value>,
+    ) -> Vec<MapItemOutcome> {
+        let mut pending = stream::iter(items.into_iter().enumerate())
+            .map(|(index, item)| {
+                let mut context = run.context.clone();
+                context.insert(spec.item_variable.clone(), item.clone());
+                context.insert(MAP_INDEX_VARIABLE.to_string(), Value::from(index));
+                let action = Self::map_item_action(spec, &item, index, &context);
+
+                async move {
+                    let result = match action {
+                        Ok(action) => action.execute(&mut context).await,
+                        Err(error) => Err(error),
+                    };
+                    MapItemOutcome {
+                        index,
+                        item,
+                        result,
+                        context,
+                    }
+                }
+            })
+            .buffer_unordered(spec.max_concurrency);
+
+        // Dropping the stream cancels the elements that are still running
+        let mut outcomes = Vec::new();
+        while let Some(outcome) = pending.next().await {
+            let failed = outcome.result.is_err();
+            outcomes.push(outcome);
+            if failed && spec.on_error == MapFailurePolicy::FailFast {
+                break;
+            }
+        }
+        outcomes
+    }
+
+    /// Variables an element's action set or changed in its copy of the context
+    ///
+    /// The element variables and internal variables are left out.
+    fn map_item_outputs(
+        spec: &MapSpec,
+        vars_before: &HashMap<String, Value>,
+        context: &WorkflowTemplateContext,
+    ) -> Value {
+        Value::Object(
+            context
+                .iter()
+                .filter(|(key, _)| {
+                    !key.starts_with('_')
+                        && **key != spec.item_variable
+                        && key.as_str() != MAP_INDEX_VARIABLE
+                        && key.as_str() != LAST_ACTION_RESULT_KEY
+                })
+                .filter(|(key, value)| vars_before.get(*key) != Some(*value))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        )
+    }
+
+    /// Build the action that processes one element
+    fn map_item_action(
+        spec: &MapSpec,
+        item: &Value,
+        index: usize,
+        context: &WorkflowTemplateContext,
+    ) -> Result<Box<dyn Action>, ActionError> {
+        if let Some(workflow) = &spec.workflow {
+            // Sub-workflow inputs are strings, so structured elements are passed as JSON
+            let item_input = match item {
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            return Ok(Box::new(
+                SubWorkflowAction::new(workflow.clone())
+                    .with_input(spec.item_variable.clone(), item_input)
+                    .with_input(MAP_INDEX_VARIABLE.to_string(), index.to_string()),
+            ));
+        }
+
+        let action_text = spec.action.as_deref().unwrap_or_default();
+        parse_action_from_description_with_context(action_text, &context.to_workflow_hashmap())?
+            .ok_or_else(|| {
+                ActionError::ParseError(format!("No action could be parsed from '{action_text}'"))
+            })
+    }
+}
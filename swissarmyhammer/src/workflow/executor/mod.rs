@@ -1,12 +1,19 @@
 //! Workflow execution engine
 
+pub mod approval;
+pub mod budget;
+pub mod checkpoint;
 pub mod core;
 pub mod fork_join;
+pub mod map;
+pub mod retry;
 #[cfg(test)]
 mod tests;
 pub mod validation;
 
-use crate::workflow::{ActionError, StateId};
+use crate::workflow::event_log::EventLevel;
+use crate::workflow::{ActionError, StateId, WorkflowRunStatus};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Errors that can occur during workflow execution
@@ -59,19 +66,64 @@ pub const DEFAULT_MAX_HISTORY_SIZE: usize = 10000;
 /// Context key for last action result
 pub const LAST_ACTION_RESULT_KEY: &str = "last_action_result";
 
+/// Event data key holding the final status of a run
+pub const RUN_STATUS_KEY: &str = "run_status";
+
 /// Event recorded during workflow execution
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionEvent {
     /// When the event occurred
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Severity of the event
+    pub level: EventLevel,
     /// Type of execution event
     pub event_type: ExecutionEventType,
+    /// State the run was in when the event occurred
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<StateId>,
     /// Human-readable details about the event
     pub details: String,
+    /// Structured payload such as action inputs, prompts or command output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl ExecutionEvent {
+    /// Create an event at the default level for its type
+    pub fn new(event_type: ExecutionEventType, details: String) -> Self {
+        Self {
+            timestamp: chrono::Utc::now(),
+            level: event_type.default_level(),
+            event_type,
+            state: None,
+            details,
+            data: None,
+        }
+    }
+
+    /// Set the severity of the event
+    pub fn with_level(mut self, level: EventLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Attach a structured payload to the event
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Final run status recorded by this event, if it marks the end of a run
+    pub fn run_status(&self) -> Option<WorkflowRunStatus> {
+        self.data
+            .as_ref()
+            .and_then(|data| data.get(RUN_STATUS_KEY))
+            .and_then(|status| serde_json::from_value(status.clone()).ok())
+    }
 }
 
 /// Types of events that can occur during workflow execution
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ExecutionEventType {
     /// Workflow execution started
     Started,
@@ -85,6 +137,36 @@ pub enum ExecutionEventType {
     Completed,
     /// Workflow execution failed
     Failed,
+    /// An action is about to run with the given inputs
+    ActionStarted,
+    /// A prompt was rendered for the agent
+    PromptRendered,
+    /// The agent responded to a prompt
+    AgentResponse,
+    /// A shell command produced output
+    ShellOutput,
+    /// A workflow variable was set, changed or removed
+    VariableChanged,
+    /// A failed action is about to be attempted again
+    Retrying,
+    /// The run exceeded its timeout, transition budget or a state deadline
+    BudgetExceeded,
+    /// The run paused to wait for a person to approve or reject it
+    ApprovalRequested,
+}
+
+impl ExecutionEventType {
+    /// Level at which events of this type are logged unless specified otherwise
+    pub fn default_level(&self) -> EventLevel {
+        match self {
+            ExecutionEventType::Failed => EventLevel::Error,
+            ExecutionEventType::Retrying | ExecutionEventType::BudgetExceeded => EventLevel::Warn,
+            ExecutionEventType::ConditionEvaluated
+            | ExecutionEventType::PromptRendered
+            | ExecutionEventType::VariableChanged => EventLevel::Debug,
+            _ => EventLevel::Info,
+        }
+    }
 }
 
 // Implement Display for ExecutionEventType
@@ -97,10 +179,20 @@ impl std::fmt::Display for ExecutionEventType {
             ExecutionEventType::ConditionEvaluated => "ConditionEvaluated",
             ExecutionEventType::Completed => "Completed",
             ExecutionEventType::Failed => "Failed",
+            ExecutionEventType::ActionStarted => "ActionStarted",
+            ExecutionEventType::PromptRendered => "PromptRendered",
+            ExecutionEventType::AgentResponse => "AgentResponse",
+            ExecutionEventType::ShellOutput => "ShellOutput",
+            ExecutionEventType::VariableChanged => "VariableChanged",
+            ExecutionEventType::Retrying => "Retrying",
+            ExecutionEventType::BudgetExceeded => "BudgetExceeded",
+            ExecutionEventType::ApprovalRequested => "ApprovalRequested",
         };
         write!(f, "{s}")
     }
 }
 
 // Re-export main types
+pub use checkpoint::{CheckpointPhase, ForkProgress};
 pub use core::WorkflowExecutor;
+pub use retry::RETRY_ATTEMPTS_KEY_PREFIX;
@@ -0,0 +1,150 @@
+//! Retrying failed state actions
+//!
+//! States can declare a [`RetryPolicy`]. When the state's action fails with a
+//! retryable [`ErrorClass`], the executor waits according to the policy's
+//! backoff and runs the action again, up to the policy's attempt limit. The
+//! number of attempts is recorded in run metadata, metrics and the event log.
+
+use super::core::WorkflowExecutor;
+use super::{ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult};
+use crate::workflow::action_parser::ActionParser;
+use crate::workflow::{
+    retry_declaration, Action, ActionError, ErrorClass, RetryPolicy, WorkflowRun,
+    RETRY_POLICY_METADATA_KEY,
+};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Prefix of the run metadata keys recording how many attempts a state took
+///
+/// The full key is the prefix followed by the state ID.
+pub const RETRY_ATTEMPTS_KEY_PREFIX: &str = "retry_attempts.";
+
+impl WorkflowExecutor {
+    /// Get the retry policy of the current state, if it declares one
+    ///
+    /// A `**Retry**:` line in the state description takes precedence over a
+    /// policy declared in the workflow front matter.
+    pub(super) fn retry_policy_for_state(
+        &self,
+        run: &WorkflowRun,
+        state_description: &str,
+    ) -> ExecutorResult<Option<RetryPolicy>> {
+        let spec = retry_declaration(state_description).or_else(|| {
+            run.workflow
+                .states
+                .get(&run.current_state)
+                .and_then(|state| state.metadata.get(RETRY_POLICY_METADATA_KEY))
+                .map(String::as_str)
+        });
+
+        match spec {
+            Some(spec) => ActionParser::new()
+                .and_then(|parser| parser.parse_retry_policy(spec))
+                .map(Some)
+                .map_err(ExecutorError::ActionError),
+            None => Ok(None),
+        }
+    }
+
+    /// Execute an action, retrying failed attempts according to `policy`
+    ///
+    /// A shell command that exits with a non-zero code does not return an error,
+    /// but counts as a [`ErrorClass::Command`] failure here so it can be retried.
+    /// The result of the last attempt is returned.
+    pub(super) async fn execute_action_with_retry(
+        &mut self,
+        run: &mut WorkflowRun,
+        action: &dyn Action,
+        policy: Option<&RetryPolicy>,
+    ) -> Result<Value, ActionError> {
+        let Some(policy) = policy else {
+            return action.execute(&mut run.context).await;
+        };
+
+        let mut attempt = 1;
+        loop {
+            let result = action.execute(&mut run.context).await;
+            run.metadata.insert(
+                format!("{RETRY_ATTEMPTS_KEY_PREFIX}{}", run.current_state),
+                attempt.to_string(),
+            );
+
+            let Some((class, reason, minimum_delay)) = Self::attempt_failure(run, action, &result)
+            else {
+                return result;
+            };
+            if !policy.should_retry(attempt, class) {
+                return result;
+            }
+
+            // Honor the wait requested by a rate limit if it is longer than the backoff
+            let delay = policy.delay_for(attempt).max(minimum_delay);
+            self.log_action_events(&mut run.context);
+            self.log_event_with(
+                ExecutionEvent::new(
+                    ExecutionEventType::Retrying,
+                    format!(
+                        "Attempt {attempt}/{} of state {} failed ({class}): {reason}. Retrying in {delay:?}",
+                        policy.max_attempts, run.current_state
+                    ),
+                )
+                .with_data(serde_json::json!({
+                    "attempt": attempt,
+                    "max_attempts": policy.max_attempts,
+                    "error_class": class.as_str(),
+                    "error": reason,
+                    "delay_ms": delay.as_millis() as u64,
+                })),
+            );
+            self.get_metrics_mut()
+                .record_retry(&run.id, run.current_state.clone());
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Number of attempts the current state took, if it has a retry policy
+    pub(super) fn retry_attempts(run: &WorkflowRun) -> Option<usize> {
+        run.metadata
+            .get(&format!("{RETRY_ATTEMPTS_KEY_PREFIX}{}", run.current_state))
+            .and_then(|attempts| attempts.parse().ok())
+    }
+
+    /// Classify a failed attempt, returning `None` if it succeeded or must not be retried
+    ///
+    /// A rejected approval is a decision, so it ends the attempts like a success does.
+    fn attempt_failure(
+        run: &WorkflowRun,
+        action: &dyn Action,
+        result: &Result<Value, ActionError>,
+    ) -> Option<(ErrorClass, String, Duration)> {
+        match result {
+            Err(ActionError::ApprovalRejected(_)) => None,
+            Err(error) => {
+                let minimum_delay = match error {
+                    ActionError::RateLimit { wait_time, .. } => *wait_time,
+                    _ => Duration::ZERO,
+                };
+                Some((ErrorClass::of(error), error.to_string(), minimum_delay))
+            }
+            Ok(_)
+                if action.action_type() == "shell"
+                    && run.context.get("success") == Some(&Value::Bool(false)) =>
+            {
+                let exit_code = run
+                    .context
+                    .get("exit_code")
+                    .map(|code| code.to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+                Some((
+                    ErrorClass::Command,
+                    format!("command exited with code {exit_code}"),
+                    Duration::ZERO,
+                ))
+            }
+            Ok(_) => None,
+        }
+    }
+}
@@ -1896,3 +1896,793 @@ async fn test_abort_file_not_present_normal_execution() {
     assert_eq!(run.current_state.as_str(), "end");
     assert_eq!(run.status, WorkflowRunStatus::Completed);
 }
+
+#[tokio::test]
+async fn test_checkpoint_written_after_each_transition() {
+    let _test_env = IsolatedTestEnvironment::new().expect("Failed to create test environment");
+    let mut executor = WorkflowExecutor::new()
+        .with_checkpoint_storage(Box::new(crate::workflow::MemoryWorkflowRunStorage::new()));
+
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Checkpoint Test"),
+        "Test checkpointing".to_string(),
+        StateId::new("start"),
+    );
+    workflow.add_state(create_state("start", "Set step=\"started\"", false));
+    workflow.add_state(create_state("end", "End state", true));
+    workflow.add_transition(create_transition("start", "end", ConditionType::Always));
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+
+    let checkpoint = executor
+        .checkpoint_storage()
+        .expect("checkpoint storage should be configured")
+        .get_run(&run.id)
+        .expect("run should have been checkpointed");
+
+    assert_eq!(checkpoint.status, WorkflowRunStatus::Completed);
+    assert_eq!(checkpoint.current_state, StateId::new("end"));
+    assert_eq!(
+        CheckpointPhase::of(&checkpoint),
+        Some(CheckpointPhase::ActionCompleted)
+    );
+    assert_eq!(
+        checkpoint.context.get("step"),
+        Some(&Value::String("started".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_resume_skips_action_completed_before_checkpoint() {
+    let _test_env = IsolatedTestEnvironment::new().expect("Failed to create test environment");
+    let mut executor = WorkflowExecutor::new();
+
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Checkpoint Resume Test"),
+        "Test resuming from a checkpoint".to_string(),
+        StateId::new("start"),
+    );
+    workflow.add_state(create_state("start", "Start state", false));
+    workflow.add_state(create_state("work", "Set work=\"repeated\"", false));
+    workflow.add_state(create_state("end", "End state", true));
+    workflow.add_transition(create_transition("start", "work", ConditionType::Always));
+    workflow.add_transition(create_transition("work", "end", ConditionType::Always));
+
+    // Simulate a run that was killed after the action in `work` finished
+    let mut run = executor.start_workflow(workflow).unwrap();
+    run.transition_to(StateId::new("work"));
+    run.context
+        .insert("work".to_string(), Value::String("original".to_string()));
+    run.metadata.insert(
+        super::checkpoint::CHECKPOINT_PHASE_KEY.to_string(),
+        CheckpointPhase::ActionCompleted.as_str().to_string(),
+    );
+    run.status = WorkflowRunStatus::Cancelled;
+
+    let run = executor.resume_workflow(run).await.unwrap();
+
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+    assert_eq!(run.current_state, StateId::new("end"));
+    assert_eq!(
+        run.context.get("work"),
+        Some(&Value::String("original".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_resume_retries_action_that_failed_before_checkpoint() {
+    let _test_env = IsolatedTestEnvironment::new().expect("Failed to create test environment");
+    let mut executor = WorkflowExecutor::new();
+
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Checkpoint Failure Test"),
+        "Test resuming after a failed action".to_string(),
+        StateId::new("start"),
+    );
+    workflow.add_state(create_state("start", "Start state", false));
+    workflow.add_state(create_state("work", "Set work=\"retried\"", false));
+    workflow.add_state(create_state("end", "End state", true));
+    workflow.add_transition(create_transition("start", "work", ConditionType::Always));
+    workflow.add_transition(create_transition("work", "end", ConditionType::OnSuccess));
+
+    // Simulate a run that was killed after the action in `work` failed
+    let mut run = executor.start_workflow(workflow).unwrap();
+    run.transition_to(StateId::new("work"));
+    run.metadata.insert(
+        super::checkpoint::CHECKPOINT_PHASE_KEY.to_string(),
+        CheckpointPhase::ActionFailed.as_str().to_string(),
+    );
+    run.metadata.insert(
+        super::checkpoint::CHECKPOINT_ERROR_KEY.to_string(),
+        "agent unavailable".to_string(),
+    );
+
+    let run = executor.resume_workflow(run).await.unwrap();
+
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+    assert_eq!(run.current_state, StateId::new("end"));
+    assert_eq!(
+        run.context.get("work"),
+        Some(&Value::String("retried".to_string()))
+    );
+    assert!(!run
+        .metadata
+        .contains_key(super::checkpoint::CHECKPOINT_ERROR_KEY));
+}
+
+#[tokio::test]
+async fn test_resume_reuses_checkpointed_fork_branches() {
+    let _test_env = IsolatedTestEnvironment::new().expect("Failed to create test environment");
+    let mut executor = WorkflowExecutor::new();
+
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Fork Checkpoint Test"),
+        "Test resuming a fork".to_string(),
+        StateId::new("fork1"),
+    );
+    workflow.add_state(create_state_with_type(
+        "fork1",
+        "Fork state",
+        StateType::Fork,
+        false,
+    ));
+    workflow.add_state(create_state("branch1", "Set branch1=\"executed\"", false));
+    workflow.add_state(create_state("branch2", "Set branch2=\"executed\"", false));
+    workflow.add_state(create_state_with_type(
+        "join1",
+        "Join state",
+        StateType::Join,
+        false,
+    ));
+    workflow.add_state(create_state("end", "End state", true));
+    workflow.add_transition(create_transition("fork1", "branch1", ConditionType::Always));
+    workflow.add_transition(create_transition("fork1", "branch2", ConditionType::Always));
+    workflow.add_transition(create_transition("branch1", "join1", ConditionType::Always));
+    workflow.add_transition(create_transition("branch2", "join1", ConditionType::Always));
+    workflow.add_transition(create_transition("join1", "end", ConditionType::Always));
+
+    // Simulate a run that was killed after branch1 had reached the join state
+    let mut run = executor.start_workflow(workflow).unwrap();
+    let mut branch_context = run.context.clone();
+    branch_context.insert(
+        "branch1".to_string(),
+        Value::String("checkpointed".to_string()),
+    );
+    let branch = super::fork_join::ParallelBranch {
+        current_state: StateId::new("join1"),
+        context: branch_context,
+        history: vec![
+            (StateId::new("branch1"), chrono::Utc::now()),
+            (StateId::new("join1"), chrono::Utc::now()),
+        ],
+    };
+    executor.record_fork_branch(&mut run, &StateId::new("fork1"), &branch);
+
+    let run = executor.resume_workflow(run).await.unwrap();
+
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+    assert_eq!(
+        run.context.get("branch1"),
+        Some(&Value::String("checkpointed".to_string()))
+    );
+    assert_eq!(
+        run.context.get("branch2"),
+        Some(&Value::String("executed".to_string()))
+    );
+    assert!(!run
+        .metadata
+        .contains_key(super::checkpoint::FORK_PROGRESS_KEY));
+}
+
+#[tokio::test]
+async fn test_event_log_records_actions_and_variable_changes() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut executor = WorkflowExecutor::new().with_event_log_dir(temp_dir.path());
+
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Event Log Test"),
+        "Test the structured event log".to_string(),
+        StateId::new("start"),
+    );
+    workflow.add_state(create_state("start", "Set greeting=\"hello\"", false));
+    workflow.add_state(create_state("warn", "Log warning \"Careful\"", false));
+    workflow.add_state(create_state("end", "End state", true));
+    workflow.add_transition(create_transition("start", "warn", ConditionType::Always));
+    workflow.add_transition(create_transition("warn", "end", ConditionType::Always));
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+    executor.log_run_outcome(&run);
+
+    let log_path = crate::workflow::RunEventLog::path_for(temp_dir.path(), &run.id);
+    assert_eq!(executor.event_log().unwrap().path(), log_path.as_path());
+    let events = crate::workflow::RunEventLog::read_from(&log_path).unwrap();
+
+    assert!(matches!(events[0].event_type, ExecutionEventType::Started));
+
+    let action_started = events
+        .iter()
+        .find(|e| e.event_type == ExecutionEventType::ActionStarted)
+        .unwrap();
+    assert_eq!(action_started.state, Some(StateId::new("start")));
+    assert_eq!(
+        action_started.data.as_ref().unwrap()["action_type"],
+        Value::String("set_variable".to_string())
+    );
+
+    let greeting = events
+        .iter()
+        .find(|e| {
+            e.event_type == ExecutionEventType::VariableChanged
+                && e.data.as_ref().unwrap()["name"] == "greeting"
+        })
+        .unwrap();
+    assert_eq!(greeting.level, crate::workflow::EventLevel::Debug);
+    assert_eq!(greeting.data.as_ref().unwrap()["new"], "hello");
+    assert_eq!(greeting.data.as_ref().unwrap()["old"], Value::Null);
+
+    let warning = events.iter().find(|e| e.details == "Careful").unwrap();
+    assert_eq!(warning.level, crate::workflow::EventLevel::Warn);
+    assert_eq!(warning.state, Some(StateId::new("warn")));
+
+    // The internal queue of action events never leaks into the run context
+    assert!(!run
+        .context
+        .contains_key(crate::workflow::event_log::ACTION_EVENTS_KEY));
+
+    let outcome = events.last().unwrap();
+    assert_eq!(outcome.run_status(), Some(WorkflowRunStatus::Completed));
+}
+
+/// Build a workflow whose `flaky` state fails by running a missing sub-workflow
+fn create_failing_retry_workflow(retry: &str) -> Workflow {
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Retry Test"),
+        "Test retry policies".to_string(),
+        StateId::new("flaky"),
+    );
+
+    workflow.add_state(create_state(
+        "flaky",
+        &format!("Run workflow \"retry-test-missing-workflow\"\n**Retry**: {retry}"),
+        false,
+    ));
+    workflow.add_state(create_state("recovered", "Recovered state", true));
+    workflow.add_state(create_state("end", "End state", true));
+
+    workflow.add_transition(create_transition("flaky", "end", ConditionType::OnSuccess));
+    workflow.add_transition(create_transition(
+        "flaky",
+        "recovered",
+        ConditionType::OnFailure,
+    ));
+    workflow
+}
+
+#[tokio::test]
+async fn test_retry_policy_retries_transient_failures() {
+    let _test_env = IsolatedTestEnvironment::new().expect("Failed to create test environment");
+    let mut executor = WorkflowExecutor::new();
+    let workflow = create_failing_retry_workflow("max_attempts=3 backoff=fixed delay=10ms");
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+
+    assert_eq!(run.current_state, StateId::new("recovered"));
+    assert_eq!(
+        run.metadata.get("retry_attempts.flaky"),
+        Some(&"3".to_string())
+    );
+
+    let retries: Vec<_> = executor
+        .get_history()
+        .iter()
+        .filter(|e| e.event_type == ExecutionEventType::Retrying)
+        .collect();
+    assert_eq!(retries.len(), 2);
+    let data = retries[0].data.as_ref().unwrap();
+    assert_eq!(data["attempt"], 1);
+    assert_eq!(data["error_class"], "execution");
+    assert_eq!(data["delay_ms"], 10);
+
+    let metrics = executor.get_metrics().run_metrics.get(&run.id).unwrap();
+    assert_eq!(metrics.retry_counts.get(&StateId::new("flaky")), Some(&2));
+    assert_eq!(metrics.total_retries(), 2);
+
+    let error_context: ErrorContext =
+        serde_json::from_value(run.context.get(ErrorContext::CONTEXT_KEY).unwrap().clone())
+            .unwrap();
+    assert_eq!(error_context.retry_attempts, Some(3));
+}
+
+#[tokio::test]
+async fn test_retry_policy_skips_unlisted_error_classes() {
+    let _test_env = IsolatedTestEnvironment::new().expect("Failed to create test environment");
+    let mut executor = WorkflowExecutor::new();
+    let workflow = create_failing_retry_workflow("max_attempts=5 delay=10ms retry_on=timeout");
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+
+    assert_eq!(run.current_state, StateId::new("recovered"));
+    assert_eq!(
+        run.metadata.get("retry_attempts.flaky"),
+        Some(&"1".to_string())
+    );
+    assert!(!executor
+        .get_history()
+        .iter()
+        .any(|e| e.event_type == ExecutionEventType::Retrying));
+}
+
+/// Build a workflow whose `loop` state never moves on to `end`
+fn create_endless_loop_workflow() -> Workflow {
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Budget Test"),
+        "Test run budgets".to_string(),
+        StateId::new("loop"),
+    );
+
+    workflow.add_state(create_state("loop", "Log \"Are we there yet?\"", false));
+    workflow.add_state(create_state("cleanup", "Log \"Giving up\"", false));
+    workflow.add_state(create_state("end", "End state", true));
+
+    workflow.add_transition(create_transition("loop", "loop", ConditionType::Always));
+    workflow.add_transition(create_transition("loop", "end", ConditionType::Never));
+    workflow.add_transition(create_transition("cleanup", "end", ConditionType::Always));
+    workflow
+}
+
+#[tokio::test]
+async fn test_max_transitions_budget_cancels_run() {
+    let mut executor = WorkflowExecutor::new();
+    let mut workflow = create_endless_loop_workflow();
+    crate::workflow::WorkflowBudget {
+        max_transitions: Some(5),
+        ..Default::default()
+    }
+    .apply_to(&mut workflow);
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+
+    assert_eq!(run.status, WorkflowRunStatus::Cancelled);
+    assert_eq!(run.history.len(), 6);
+    assert_eq!(
+        run.cancel_reason(),
+        Some("Workflow reached its limit of 5 transitions in state loop")
+    );
+    let exceeded = executor
+        .get_history()
+        .iter()
+        .find(|e| e.event_type == ExecutionEventType::BudgetExceeded)
+        .unwrap();
+    assert_eq!(exceeded.data.as_ref().unwrap()["kind"], "max_transitions");
+}
+
+#[tokio::test]
+async fn test_exceeded_budget_moves_run_to_timeout_state() {
+    let mut executor = WorkflowExecutor::new();
+    let mut workflow = create_endless_loop_workflow();
+    crate::workflow::WorkflowBudget {
+        max_transitions: Some(3),
+        on_exceeded: Some(StateId::new("cleanup")),
+        ..Default::default()
+    }
+    .apply_to(&mut workflow);
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+    assert_eq!(run.current_state, StateId::new("end"));
+    assert!(run
+        .history
+        .iter()
+        .any(|(state, _)| state == &StateId::new("cleanup")));
+    assert_eq!(
+        run.context
+            .get(crate::workflow::BUDGET_EXCEEDED_KEY)
+            .unwrap()["kind"],
+        "max_transitions"
+    );
+}
+
+#[tokio::test]
+async fn test_timeout_path_that_continues_the_work_is_cancelled() {
+    let mut executor = WorkflowExecutor::new();
+    let mut workflow = create_endless_loop_workflow();
+    crate::workflow::WorkflowBudget {
+        max_transitions: Some(3),
+        on_exceeded: Some(StateId::new("loop")),
+        ..Default::default()
+    }
+    .apply_to(&mut workflow);
+
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+
+    // Three transitions before the budget ran out and three on the timeout path
+    assert_eq!(run.status, WorkflowRunStatus::Cancelled);
+    assert_eq!(run.history.len(), 7);
+    assert_eq!(
+        run.cancel_reason(),
+        Some("Workflow reached its limit of 3 transitions in state loop")
+    );
+    assert_eq!(
+        executor
+            .get_history()
+            .iter()
+            .filter(|e| e.event_type == ExecutionEventType::BudgetExceeded)
+            .count(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_resume_excludes_paused_time() {
+    let mut executor = WorkflowExecutor::new();
+    let mut workflow = create_endless_loop_workflow();
+    crate::workflow::WorkflowBudget {
+        timeout: Some(std::time::Duration::from_secs(60)),
+        max_transitions: Some(3),
+        ..Default::default()
+    }
+    .apply_to(&mut workflow);
+
+    // Started two hours ago, but cancelled for all but a moment of that time
+    let mut run = WorkflowRun::new(workflow);
+    run.started_at = chrono::Utc::now() - chrono::Duration::hours(2);
+    run.cancel("Cancelled by user");
+    run.completed_at = Some(chrono::Utc::now() - chrono::Duration::hours(2));
+    assert!(run.is_resumable());
+
+    executor.restore_checkpoint(&mut run).await.unwrap();
+
+    assert_eq!(run.status, WorkflowRunStatus::Running);
+    let paused_ms: u64 = run.metadata[crate::workflow::PAUSED_DURATION_KEY]
+        .parse()
+        .unwrap();
+    assert!(paused_ms >= 2 * 60 * 60 * 1000);
+
+    // The transition budget is enforced again, rather than the long-gone timeout
+    executor
+        .execute_state_with_limit(&mut run, 10)
+        .await
+        .unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Cancelled);
+    assert_eq!(
+        run.cancel_reason(),
+        Some("Workflow reached its limit of 3 transitions in state loop")
+    );
+
+    // Having run out of budget, the run cannot be resumed again
+    assert!(!run.is_resumable());
+    let error = executor
+        .restore_checkpoint(&mut run)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("ran out of budget"), "{error}");
+    assert_eq!(run.status, WorkflowRunStatus::Cancelled);
+    assert!(run
+        .metadata
+        .contains_key(crate::workflow::BUDGET_EXCEEDED_KEY));
+}
+
+#[tokio::test]
+async fn test_state_deadline_cancels_slow_state() {
+    let mut executor = WorkflowExecutor::new();
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Deadline Test"),
+        "Test state deadlines".to_string(),
+        StateId::new("slow"),
+    );
+
+    // Waits are capped at 50ms under test, which is still well past the deadline
+    let mut slow = create_state("slow", "Wait 30 seconds", false);
+    slow.metadata.insert(
+        crate::workflow::DEADLINE_METADATA_KEY.to_string(),
+        "10".to_string(),
+    );
+    workflow.add_state(slow);
+    workflow.add_state(create_state("end", "End state", true));
+    workflow.add_transition(create_transition("slow", "end", ConditionType::Always));
+
+    let started = std::time::Instant::now();
+    let run = executor.start_and_execute_workflow(workflow).await.unwrap();
+
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+    assert_eq!(run.status, WorkflowRunStatus::Cancelled);
+    assert_eq!(run.current_state, StateId::new("slow"));
+    assert_eq!(
+        run.cancel_reason(),
+        Some("State slow exceeded its deadline of 10ms")
+    );
+}
+
+/// Build a workflow that waits for approval before shipping
+fn create_approval_workflow() -> Workflow {
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Approval Test"),
+        "Test approval gates".to_string(),
+        StateId::new("gate"),
+    );
+
+    workflow.add_state(create_state("gate", "await approval \"Ship it?\"", false));
+    workflow.add_state(create_state("shipped", "Shipped", true));
+    workflow.add_state(create_state("held", "Held back", true));
+
+    workflow.add_transition(create_transition(
+        "gate",
+        "shipped",
+        ConditionType::OnSuccess,
+    ));
+    workflow.add_transition(create_transition("gate", "held", ConditionType::OnFailure));
+    workflow
+}
+
+#[tokio::test]
+async fn test_approval_pauses_run_until_approved() {
+    let mut executor = WorkflowExecutor::new();
+    let run = executor
+        .start_and_execute_workflow(create_approval_workflow())
+        .await
+        .unwrap();
+
+    assert_eq!(run.status, WorkflowRunStatus::Paused);
+    assert_eq!(run.current_state, StateId::new("gate"));
+    let pending = run.pending_approval().unwrap();
+    assert_eq!(pending.message, "Ship it?");
+    assert!(executor
+        .get_history()
+        .iter()
+        .any(|e| e.event_type == ExecutionEventType::ApprovalRequested));
+
+    // Resuming without a decision pauses again
+    let mut run = executor.resume_workflow(run).await.unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Paused);
+
+    run.record_approval(crate::workflow::ApprovalDecision::Approved, "alice", None)
+        .unwrap();
+    let run = executor.resume_workflow(run).await.unwrap();
+
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+    assert_eq!(run.current_state, StateId::new("shipped"));
+    let approval = run
+        .context
+        .get(crate::workflow::APPROVAL_CONTEXT_KEY)
+        .unwrap();
+    assert_eq!(approval["decision"], "approved");
+    assert_eq!(approval["approver"], "alice");
+    assert!(run.pending_approval().is_none());
+}
+
+#[tokio::test]
+async fn test_rejected_approval_takes_failure_transition() {
+    let mut executor = WorkflowExecutor::new();
+    let mut run = executor
+        .start_and_execute_workflow(create_approval_workflow())
+        .await
+        .unwrap();
+
+    run.record_approval(
+        crate::workflow::ApprovalDecision::Rejected,
+        "bob",
+        Some("Tests are red".to_string()),
+    )
+    .unwrap();
+    let run = executor.resume_workflow(run).await.unwrap();
+
+    assert_eq!(run.current_state, StateId::new("held"));
+    let approval = run
+        .context
+        .get(crate::workflow::APPROVAL_CONTEXT_KEY)
+        .unwrap();
+    assert_eq!(approval["decision"], "rejected");
+    assert_eq!(approval["reason"], "Tests are red");
+    assert_eq!(
+        run.context.get("result").unwrap(),
+        "Approval rejected by bob: Tests are red"
+    );
+}
+
+#[tokio::test]
+async fn test_rejected_approval_is_not_retried() {
+    let mut executor = WorkflowExecutor::new();
+    let mut workflow = create_approval_workflow();
+    workflow
+        .states
+        .get_mut(&StateId::new("gate"))
+        .unwrap()
+        .description =
+        "await approval \"Ship it?\"\n**Retry**: max_attempts=3 delay=10ms".to_string();
+    let mut run = executor.start_and_execute_workflow(workflow).await.unwrap();
+
+    run.record_approval(crate::workflow::ApprovalDecision::Rejected, "bob", None)
+        .unwrap();
+    let run = executor.resume_workflow(run).await.unwrap();
+
+    assert_eq!(run.current_state, StateId::new("held"));
+    assert!(run.pending_approval().is_none());
+    assert!(!executor
+        .get_history()
+        .iter()
+        .any(|e| e.event_type == ExecutionEventType::Retrying));
+}
+
+fn create_map_workflow(spec: serde_json::Value) -> Workflow {
+    let mut workflow = Workflow::new(
+        WorkflowName::new("Map Test"),
+        "Test map states".to_string(),
+        StateId::new("fan_out"),
+    );
+
+    let mut fan_out = create_state("fan_out", "Process every issue", false);
+    crate::workflow::MapSpec::from_value(&spec)
+        .unwrap()
+        .apply_to(&mut fan_out);
+    workflow.add_state(fan_out);
+    workflow.add_state(create_state("done", "All issues processed", true));
+    workflow.add_state(create_state("failed", "Processing failed", true));
+
+    workflow.add_transition(create_transition(
+        "fan_out",
+        "done",
+        ConditionType::OnSuccess,
+    ));
+    workflow.add_transition(create_transition(
+        "fan_out",
+        "failed",
+        ConditionType::OnFailure,
+    ));
+    workflow
+}
+
+async fn run_map_workflow(spec: serde_json::Value) -> WorkflowRun {
+    let mut executor = WorkflowExecutor::new();
+    let mut run = executor.start_workflow(create_map_workflow(spec)).unwrap();
+    run.context
+        .insert("issues".to_string(), serde_json::json!(["a", "b", "c"]));
+    executor.execute_state(&mut run).await.unwrap();
+    run
+}
+
+#[tokio::test]
+async fn test_map_state_collects_results_in_list_order() {
+    let run = run_map_workflow(serde_json::json!({
+        "over": "issues",
+        "as": "issue",
+        "action": "Set label=\"{{ issue }}-{{ index }}\"",
+        "max_concurrency": 2,
+        "into": "labels",
+    }))
+    .await;
+
+    assert_eq!(run.status, WorkflowRunStatus::Completed);
+    assert_eq!(run.current_state, StateId::new("done"));
+    assert_eq!(
+        run.context.get("labels").unwrap(),
+        &serde_json::json!(["a-0", "b-1", "c-2"])
+    );
+    assert_eq!(
+        run.context.get("labels_errors").unwrap(),
+        &serde_json::json!([])
+    );
+    assert_eq!(
+        run.context.get("labels_outputs").unwrap(),
+        &serde_json::json!([{"label": "a-0"}, {"label": "b-1"}, {"label": "c-2"}])
+    );
+    // Element variables stay in the per-element contexts
+    assert!(run.context.get("issue").is_none());
+    assert!(run.context.get("label").is_none());
+}
+
+#[tokio::test]
+async fn test_map_state_runs_sub_workflows_from_test_storage() {
+    use crate::workflow::actions::{clear_test_storage, set_test_storage};
+    use crate::workflow::{
+        MemoryWorkflowRunStorage, MemoryWorkflowStorage, MermaidParser, WorkflowStorage,
+        WorkflowStorageBackend,
+    };
+
+    let child = MermaidParser::parse(
+        r#"---
+name: map-test-child
+title: Map Test Child
+description: Labels one issue
+---
+
+# Map Test Child
+
+```mermaid
+stateDiagram-v2
+    [*] --> label
+    label --> [*]
+```
+
+## Actions
+
+- label: Set label="{{ issue }}"
+"#,
+        "map-test-child",
+    )
+    .unwrap();
+    let mut workflow_storage = MemoryWorkflowStorage::new();
+    workflow_storage.store_workflow(child).unwrap();
+    set_test_storage(std::sync::Arc::new(WorkflowStorage::new(
+        std::sync::Arc::new(workflow_storage),
+        std::sync::Arc::new(MemoryWorkflowRunStorage::new()),
+    )));
+
+    let run = run_map_workflow(serde_json::json!({
+        "over": "issues",
+        "as": "issue",
+        "workflow": "map-test-child",
+    }))
+    .await;
+    clear_test_storage();
+
+    assert_eq!(run.current_state, StateId::new("done"));
+    let results = run.context.get("results").unwrap().as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[1]["label"], "b");
+}
+
+#[tokio::test]
+async fn test_map_state_fails_fast_on_first_error() {
+    let run = run_map_workflow(serde_json::json!({
+        "over": "issues",
+        "workflow": "map-test-missing-workflow",
+        "max_concurrency": 1,
+    }))
+    .await;
+
+    assert_eq!(run.current_state, StateId::new("failed"));
+    assert!(run.context.get("results").is_none());
+}
+
+#[tokio::test]
+async fn test_map_state_collects_errors() {
+    let run = run_map_workflow(serde_json::json!({
+        "over": "issues",
+        "workflow": "map-test-missing-workflow",
+        "on_error": "collect",
+    }))
+    .await;
+
+    assert_eq!(run.current_state, StateId::new("done"));
+    assert_eq!(
+        run.context.get("results").unwrap(),
+        &serde_json::json!([null, null, null])
+    );
+    let errors = run
+        .context
+        .get("results_errors")
+        .and_then(|errors| errors.as_array())
+        .unwrap();
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[1]["index"], 1);
+    assert_eq!(errors[1]["item"], "b");
+    assert!(errors[1]["error"]
+        .as_str()
+        .unwrap()
+        .contains("map-test-missing-workflow"));
+}
+
+#[tokio::test]
+async fn test_map_state_requires_a_list() {
+    let mut executor = WorkflowExecutor::new();
+    let mut run = executor
+        .start_workflow(create_map_workflow(serde_json::json!({
+            "over": "issues",
+            "action": "Log \"{{ item }}\"",
+        })))
+        .unwrap();
+    run.context
+        .insert("issues".to_string(), serde_json::json!("not a list"));
+    executor.execute_state(&mut run).await.unwrap();
+
+    assert_eq!(run.current_state, StateId::new("failed"));
+    assert!(run
+        .context
+        .get("result")
+        .and_then(|result| result.as_str())
+        .unwrap()
+        .contains("expects variable 'issues' to hold a list"));
+}
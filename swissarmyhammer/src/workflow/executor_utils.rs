@@ -32,6 +32,10 @@ pub async fn validate_executor_availability(executor_type: AgentExecutorType) ->
             // TODO: Add actual validation when LlamaAgent integration is complete
             Ok(())
         }
+        AgentExecutorType::OpenAiCompatible => {
+            // Reachability depends on the configured server, which is checked on first request
+            Ok(())
+        }
     }
 }
 
@@ -41,5 +45,6 @@ pub fn get_recommended_timeout(executor_type: AgentExecutorType) -> Duration {
     match executor_type {
         AgentExecutorType::ClaudeCode => Duration::from_secs(30),
         AgentExecutorType::LlamaAgent => Duration::from_secs(60),
+        AgentExecutorType::OpenAiCompatible => Duration::from_secs(120),
     }
 }
@@ -0,0 +1,254 @@
+//! Map states that fan out over a list variable
+//!
+//! A map state runs a sub-workflow or an action once for every element of a
+//! list held in the run context, with a bounded number of elements in flight,
+//! and gathers the results into an array variable. Map states are declared in
+//! the workflow front matter:
+//!
+//! ```yaml
+//! map:
+//!   implement:
+//!     over: issues
+//!     as: issue
+//!     workflow: code_issue
+//!     max_concurrency: 3
+//!     into: implemented
+//!     on_error: collect
+//! ```
+//!
+//! Instead of `workflow`, a state can name an `action`, written like a state
+//! action, e.g. `action: 'Shell "cargo test -p {{ issue }}"'`. Each element
+//! runs with the element in the variable named by `as` and its position in
+//! `index`. The variables each element set are gathered, in list order, into
+//! `<into>_outputs`.
+
+use crate::workflow::State;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+/// State metadata key holding the map specification as JSON
+pub const MAP_METADATA_KEY: &str = "map";
+
+/// Default name of the variable holding the current element
+pub const DEFAULT_MAP_ITEM_VARIABLE: &str = "item";
+
+/// Default name of the variable the results are gathered into
+pub const DEFAULT_MAP_RESULT_VARIABLE: &str = "results";
+
+/// Default number of elements processed at the same time
+pub const DEFAULT_MAP_CONCURRENCY: usize = 4;
+
+/// Variable holding the position of the current element
+pub const MAP_INDEX_VARIABLE: &str = "index";
+
+/// What a map state does when processing an element fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MapFailurePolicy {
+    /// Stop at the first failure and fail the state
+    #[default]
+    FailFast,
+    /// Process every element and record failures next to the results
+    #[serde(alias = "collect_errors")]
+    Collect,
+}
+
+impl MapFailurePolicy {
+    /// Get the string representation of the policy
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MapFailurePolicy::FailFast => "fail_fast",
+            MapFailurePolicy::Collect => "collect",
+        }
+    }
+}
+
+impl FromStr for MapFailurePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "fail_fast" => Ok(MapFailurePolicy::FailFast),
+            "collect" | "collect_errors" => Ok(MapFailurePolicy::Collect),
+            _ => Err(format!(
+                "Invalid map failure policy '{s}': expected fail_fast or collect"
+            )),
+        }
+    }
+}
+
+/// What a map state runs for each element of a list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MapSpec {
+    /// Context variable holding the list
+    pub over: String,
+    /// Variable holding the current element
+    #[serde(rename = "as", default = "default_item_variable")]
+    pub item_variable: String,
+    /// Sub-workflow run for each element
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
+    /// Action run for each element
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    /// Maximum number of elements processed at the same time
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// Variable the results are gathered into
+    #[serde(rename = "into", default = "default_result_variable")]
+    pub result_variable: String,
+    /// What to do when processing an element fails
+    #[serde(default)]
+    pub on_error: MapFailurePolicy,
+}
+
+fn default_item_variable() -> String {
+    DEFAULT_MAP_ITEM_VARIABLE.to_string()
+}
+
+fn default_result_variable() -> String {
+    DEFAULT_MAP_RESULT_VARIABLE.to_string()
+}
+
+fn default_max_concurrency() -> usize {
+    DEFAULT_MAP_CONCURRENCY
+}
+
+impl MapSpec {
+    /// Read a map specification from a front matter value
+    pub fn from_value(value: &Value) -> Result<Self, String> {
+        let spec: MapSpec = serde_json::from_value(value.clone()).map_err(|e| e.to_string())?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Check that the specification can be executed
+    pub fn validate(&self) -> Result<(), String> {
+        if self.over.trim().is_empty() {
+            return Err("'over' must name the list variable".to_string());
+        }
+        match (&self.workflow, &self.action) {
+            (Some(_), Some(_)) => {
+                return Err("set either 'workflow' or 'action', not both".to_string())
+            }
+            (None, None) => return Err("set a 'workflow' or an 'action' to run".to_string()),
+            _ => {}
+        }
+        if self.max_concurrency == 0 {
+            return Err("'max_concurrency' must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    /// Variable the errors of a collecting map state are recorded in
+    pub fn errors_variable(&self) -> String {
+        format!("{}_errors", self.result_variable)
+    }
+
+    /// Variable the variables set by each element are recorded in
+    pub fn outputs_variable(&self) -> String {
+        format!("{}_outputs", self.result_variable)
+    }
+
+    /// Store the specification in a state's metadata and make it a map state
+    pub fn apply_to(&self, state: &mut State) {
+        if let Ok(spec) = serde_json::to_string(self) {
+            state.metadata.insert(MAP_METADATA_KEY.to_string(), spec);
+        }
+        state.state_type = crate::workflow::StateType::Map;
+        state.allows_parallel = true;
+    }
+}
+
+/// Get the map specification of a state, if it is a map state
+pub fn map_spec(state: &State) -> Option<MapSpec> {
+    state
+        .metadata
+        .get(MAP_METADATA_KEY)
+        .and_then(|spec| serde_json::from_str(spec).ok())
+}
+
+/// Failure of a single element of a map state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapItemError {
+    /// Position of the element in the list
+    pub index: usize,
+    /// The element itself
+    pub item: Value,
+    /// Why processing the element failed
+    pub error: String,
+}
+
+/// Read the elements of a list variable
+///
+/// Variables passed on the command line are strings, so a string holding a
+/// JSON array is accepted as well.
+pub fn map_items(value: &Value) -> Option<Vec<Value>> {
+    match value {
+        Value::Array(items) => Some(items.clone()),
+        Value::String(text) => match serde_json::from_str(text) {
+            Ok(Value::Array(items)) => Some(items),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::test_helpers::create_state;
+    use crate::workflow::StateType;
+    use serde_json::json;
+
+    #[test]
+    fn test_map_spec_defaults_and_round_trip() {
+        let spec =
+            MapSpec::from_value(&json!({"over": "issues", "workflow": "code_issue"})).unwrap();
+        assert_eq!(spec.item_variable, "item");
+        assert_eq!(spec.result_variable, "results");
+        assert_eq!(spec.max_concurrency, DEFAULT_MAP_CONCURRENCY);
+        assert_eq!(spec.on_error, MapFailurePolicy::FailFast);
+        assert_eq!(spec.errors_variable(), "results_errors");
+        assert_eq!(spec.outputs_variable(), "results_outputs");
+
+        let mut state = create_state("implement", "Implement each issue", false);
+        assert_eq!(map_spec(&state), None);
+        spec.apply_to(&mut state);
+        assert_eq!(state.state_type, StateType::Map);
+        assert_eq!(map_spec(&state), Some(spec));
+    }
+
+    #[test]
+    fn test_map_spec_validation() {
+        for invalid in [
+            json!({"over": "issues"}),
+            json!({"over": "issues", "workflow": "a", "action": "Log \"b\""}),
+            json!({"over": "issues", "workflow": "a", "max_concurrency": 0}),
+            json!({"over": "issues", "workflow": "a", "on_error": "ignore"}),
+            json!({"over": "issues", "workflow": "a", "parallel": true}),
+        ] {
+            assert!(
+                MapSpec::from_value(&invalid).is_err(),
+                "{invalid} should fail"
+            );
+        }
+        assert_eq!(
+            "collect-errors".parse::<MapFailurePolicy>().unwrap(),
+            MapFailurePolicy::Collect
+        );
+    }
+
+    #[test]
+    fn test_map_items_accepts_json_strings() {
+        assert_eq!(
+            map_items(&json!(["a", "b"])),
+            Some(vec![json!("a"), json!("b")])
+        );
+        assert_eq!(map_items(&json!("[1, 2]")), Some(vec![json!(1), json!(2)]));
+        assert_eq!(map_items(&json!("a, b")), None);
+        assert_eq!(map_items(&json!({"a": 1})), None);
+    }
+}
@@ -57,12 +57,22 @@ pub struct RunMetrics {
     pub state_durations: HashMap<StateId, Duration>,
     /// Number of state transitions
     pub transition_count: usize,
+    /// Number of retried attempts per state
+    #[serde(default)]
+    pub retry_counts: HashMap<StateId, usize>,
     /// Memory usage metrics
     pub memory_metrics: MemoryMetrics,
     /// Error details if run failed
     pub error_details: Option<String>,
 }
 
+impl RunMetrics {
+    /// Total number of retried attempts across all states
+    pub fn total_retries(&self) -> usize {
+        self.retry_counts.values().sum()
+    }
+}
+
 /// Memory usage metrics for a workflow run
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryMetrics {
@@ -173,6 +183,7 @@ impl WorkflowMetrics {
             total_duration: None,
             state_durations: HashMap::new(),
             transition_count: 0,
+            retry_counts: HashMap::new(),
             memory_metrics: MemoryMetrics::new(),
             error_details: None,
         };
@@ -215,6 +226,17 @@ impl WorkflowMetrics {
         }
     }
 
+    /// Record that a state's action is being retried
+    pub fn record_retry(&mut self, run_id: &WorkflowRunId, state_id: StateId) {
+        if !Self::is_valid_state_id(&state_id) {
+            return;
+        }
+
+        if let Some(run_metrics) = self.run_metrics.get_mut(run_id) {
+            *run_metrics.retry_counts.entry(state_id).or_insert(0) += 1;
+        }
+    }
+
     /// Complete a workflow run
     pub fn complete_run(
         &mut self,
@@ -641,6 +663,23 @@ mod tests {
         assert_eq!(run_metrics.transition_count, 0);
     }
 
+    #[test]
+    fn test_record_retry() {
+        let mut metrics = WorkflowMetrics::new();
+        let run_id = WorkflowRunId::new();
+        metrics.start_run(run_id, WorkflowName::new("test_workflow"));
+
+        metrics.record_retry(&run_id, StateId::new("fetch"));
+        metrics.record_retry(&run_id, StateId::new("fetch"));
+        metrics.record_retry(&run_id, StateId::new("upload"));
+
+        let run_metrics = metrics
+            .get_run_metrics(&run_id)
+            .expect("Run metrics should exist after start_run");
+        assert_eq!(run_metrics.retry_counts[&StateId::new("fetch")], 2);
+        assert_eq!(run_metrics.total_retries(), 3);
+    }
+
     #[test]
     fn test_record_state_execution() {
         let mut metrics = WorkflowMetrics::new();
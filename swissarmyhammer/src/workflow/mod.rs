@@ -8,9 +8,12 @@ pub mod actions;
 #[cfg(test)]
 mod actions_tests;
 mod agents;
+mod approval;
+mod budget;
 mod cache;
 mod definition;
 mod error_utils;
+pub mod event_log;
 #[cfg(test)]
 mod examples_tests;
 mod executor;
@@ -19,9 +22,11 @@ mod executor_utils;
 mod graph;
 #[cfg(test)]
 mod graph_tests;
+mod map;
 mod mcp_integration;
 mod metrics;
 mod parser;
+mod retry;
 mod run;
 mod state;
 mod storage;
@@ -34,6 +39,7 @@ mod test_helpers;
 mod test_liquid_rendering;
 mod transition;
 mod transition_key;
+mod triggers;
 mod visualization;
 #[cfg(test)]
 mod visualization_tests;
@@ -43,10 +49,22 @@ pub use actions::{
     parse_action_from_description_with_context, validate_command,
     validate_environment_variables_security, validate_working_directory_security, Action,
     ActionError, ActionResult, AgentExecutionContext, AgentExecutor, AgentExecutorFactory,
-    LogAction, LogLevel, PromptAction, SetVariableAction, ShellAction, SubWorkflowAction,
-    WaitAction,
+    ApprovalAction, LogAction, LogLevel, PromptAction, SetVariableAction, ShellAction,
+    SubWorkflowAction, WaitAction,
+};
+pub use agents::{
+    agent_tool_provider, set_agent_tool_provider, AgentTool, AgentToolProvider, Cassette,
+    CassetteEntry, LlamaAgentExecutor, OpenAiCompatibleExecutor, RecordingExecutor, ReplayExecutor,
+    RECORD_CASSETTE_KEY, REPLAY_CASSETTE_KEY, REPLAY_POSITIONS_KEY,
+};
+pub use approval::{
+    default_approver, ApprovalDecision, ApprovalRecord, PendingApproval, APPROVAL_CONTEXT_KEY,
+    PENDING_APPROVAL_METADATA_KEY,
+};
+pub use budget::{
+    state_deadline, BudgetExceeded, BudgetKind, WorkflowBudget, BUDGET_EXCEEDED_KEY,
+    DEADLINE_METADATA_KEY, PAUSED_DURATION_KEY, TIMEOUT_PATH_START_KEY,
 };
-pub use agents::LlamaAgentExecutor;
 pub use cache::{
     CacheStats, CelProgramCache, TransitionCache, TransitionPath, WorkflowCache,
     WorkflowCacheManager,
@@ -56,28 +74,41 @@ pub use error_utils::{
     command_succeeded, extract_stderr, extract_stdout, handle_claude_command_error,
     handle_command_error, handle_command_error_with_mapper,
 };
+pub use event_log::{record_action_event, EventLevel, RunEventLog};
 pub use executor::{
-    ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult, WorkflowExecutor,
+    CheckpointPhase, ExecutionEvent, ExecutionEventType, ExecutorError, ExecutorResult,
+    ForkProgress, WorkflowExecutor,
 };
 pub use graph::{GraphError, GraphResult, WorkflowGraphAnalyzer};
+pub use map::{
+    map_spec, MapFailurePolicy, MapItemError, MapSpec, MAP_INDEX_VARIABLE, MAP_METADATA_KEY,
+};
 pub use mcp_integration::{response_processing, WorkflowShellContext};
 pub use metrics::{
     GlobalMetrics, MemoryMetrics, ResourceTrends, RunMetrics, StateExecutionCount, WorkflowMetrics,
     WorkflowSummaryMetrics,
 };
 pub use parser::{MermaidParser, ParseError, ParseResult};
-pub use run::{WorkflowRun, WorkflowRunId, WorkflowRunStatus};
+pub use retry::{
+    retry_declaration, BackoffStrategy, ErrorClass, RetryPolicy, RETRY_POLICY_METADATA_KEY,
+};
+pub use run::{WorkflowRun, WorkflowRunId, WorkflowRunStatus, CANCEL_REASON_KEY};
 pub use state::{
     CompensationKey, ErrorContext, State, StateError, StateId, StateResult, StateType,
 };
 pub use storage::{
     CompressedWorkflowStorage, FileSystemWorkflowRunStorage, FileSystemWorkflowStorage,
     MemoryWorkflowRunStorage, MemoryWorkflowStorage, WorkflowResolver, WorkflowRunStorageBackend,
-    WorkflowStorage, WorkflowStorageBackend,
+    WorkflowStorage, WorkflowStorageBackend, LOCAL_WORKFLOW_RUNS_DIR,
 };
 pub use template_context::WorkflowTemplateContext;
 pub use transition::{ConditionType, Transition, TransitionCondition};
 pub use transition_key::TransitionKey;
+pub use triggers::{
+    is_ignored_trigger_path, set_workflow_triggers, workflow_triggers, CronSchedule, GitHookEvent,
+    TriggerDebouncer, TriggerEvent, TriggerFileWatcher, TriggerGuard, WorkflowTrigger,
+    DEFAULT_TRIGGER_DEBOUNCE, TRIGGERS_METADATA_KEY, TRIGGER_CONTEXT_KEY,
+};
 pub use visualization::{
     ColorScheme, ExecutionStep, ExecutionTrace, ExecutionVisualizer, VisualizationFormat,
     VisualizationOptions,
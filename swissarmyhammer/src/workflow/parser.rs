@@ -3,6 +3,13 @@
 //! This module integrates the mermaid_parser library to parse Mermaid state diagrams
 //! and convert them to our internal Workflow types.
 
+use crate::workflow::action_parser::ActionParser;
+use crate::workflow::budget::{WorkflowBudget, DEADLINE_METADATA_KEY};
+use crate::workflow::map::MapSpec;
+use crate::workflow::retry::{retry_declaration, RETRY_POLICY_METADATA_KEY};
+use crate::workflow::triggers::{
+    set_workflow_triggers, GitHookEvent, WorkflowTrigger, DEFAULT_TRIGGER_DEBOUNCE,
+};
 use crate::workflow::{
     ConditionType, State, StateId, StateType, Transition, TransitionCondition, Workflow,
     WorkflowName,
@@ -12,6 +19,7 @@ use mermaid_parser::{
     parse_diagram,
 };
 use std::collections::HashMap;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur during Mermaid parsing
@@ -120,21 +128,43 @@ impl MermaidParser {
         // Extract actions from the markdown content
         let actions = Self::extract_actions_from_markdown(input);
 
+        // Parse the front matter once for every setting it can hold
+        let frontmatter = Self::parse_frontmatter_metadata(input)?;
+
         // Extract parameters from frontmatter
-        let parameters = Self::extract_parameters_from_frontmatter(input)?;
+        let parameters = Self::extract_parameters_from_frontmatter(&frontmatter);
+
+        // Extract per-state retry policies from frontmatter
+        let retry_policies = Self::extract_retry_policies_from_frontmatter(&frontmatter)?;
+
+        // Extract the run budget and per-state deadlines from frontmatter
+        let budget = Self::extract_budget_from_frontmatter(&frontmatter)?;
+        let deadlines = Self::extract_deadlines_from_frontmatter(&frontmatter)?;
+
+        // Extract map states from frontmatter
+        let map_states = Self::extract_map_states_from_frontmatter(&frontmatter)?;
+
+        // Extract the events that start the workflow from frontmatter
+        let triggers = Self::extract_triggers_from_frontmatter(&frontmatter)?;
 
         // Attempt to parse the diagram
         match parse_diagram(&mermaid_content) {
             Ok(diagram) => match diagram {
                 DiagramType::State(state_diagram) => {
-                    Self::convert_state_diagram_with_actions_metadata_and_parameters(
-                        state_diagram,
-                        workflow_name.into(),
-                        actions,
-                        title,
-                        description,
-                        parameters,
-                    )
+                    let mut workflow =
+                        Self::convert_state_diagram_with_actions_metadata_and_parameters(
+                            state_diagram,
+                            workflow_name.into(),
+                            actions,
+                            title,
+                            description,
+                            parameters,
+                        )?;
+                    Self::apply_retry_policies(&mut workflow, retry_policies)?;
+                    Self::apply_budget(&mut workflow, budget, deadlines)?;
+                    Self::apply_map_states(&mut workflow, map_states)?;
+                    set_workflow_triggers(&mut workflow, &triggers);
+                    Ok(workflow)
                 }
                 _ => Err(ParseError::WrongDiagramType {
                     diagram_type: format!("{diagram:?}"),
@@ -194,27 +224,26 @@ impl MermaidParser {
         Ok(mermaid_lines.join("\n"))
     }
 
-    /// Parse frontmatter and extract workflow parameters
-    fn extract_parameters_from_frontmatter(
-        input: &str,
-    ) -> ParseResult<Vec<crate::common::Parameter>> {
-        let mut parameters = Vec::new();
-
-        // Use shared frontmatter parsing
+    /// Parse the YAML front matter of a workflow
+    ///
+    /// Input without front matter yields `Null`, which holds no settings.
+    fn parse_frontmatter_metadata(input: &str) -> ParseResult<serde_json::Value> {
         let frontmatter = crate::frontmatter::parse_frontmatter(input).map_err(|e| {
             ParseError::InvalidStructure {
                 message: e.to_string(),
             }
         })?;
+        Ok(frontmatter.metadata.unwrap_or_default())
+    }
 
-        // Extract parameters from frontmatter if present
-        let frontmatter_value = match frontmatter.metadata {
-            Some(value) => value,
-            None => return Ok(parameters),
-        };
+    /// Extract workflow parameters from the front matter
+    fn extract_parameters_from_frontmatter(
+        frontmatter: &serde_json::Value,
+    ) -> Vec<crate::common::Parameter> {
+        let mut parameters = Vec::new();
 
         // Extract parameters from frontmatter if present
-        if let Some(params_value) = frontmatter_value.get("parameters") {
+        if let Some(params_value) = frontmatter.get("parameters") {
             if let Some(params_array) = params_value.as_array() {
                 for param_value in params_array {
                     if let Some(param_obj) = param_value.as_object() {
@@ -287,7 +316,348 @@ impl MermaidParser {
             }
         }
 
-        Ok(parameters)
+        parameters
+    }
+
+    /// Extract retry policies keyed by state name from the front matter
+    ///
+    /// Policies are validated here and stored in their `**Retry**:` line form.
+    fn extract_retry_policies_from_frontmatter(
+        frontmatter: &serde_json::Value,
+    ) -> ParseResult<HashMap<String, String>> {
+        let mut policies = HashMap::new();
+
+        let Some(retry_value) = frontmatter.get("retry") else {
+            return Ok(policies);
+        };
+
+        let states = retry_value
+            .as_object()
+            .ok_or_else(|| ParseError::InvalidStructure {
+                message: "'retry' in front matter must map state names to retry policies"
+                    .to_string(),
+            })?;
+
+        let action_parser = ActionParser::new().map_err(|e| ParseError::InvalidStructure {
+            message: e.to_string(),
+        })?;
+        for (state_name, policy_value) in states {
+            let policy = action_parser
+                .retry_policy_from_value(policy_value)
+                .map_err(|e| ParseError::InvalidStructure {
+                    message: format!("Invalid retry policy for state '{state_name}': {e}"),
+                })?;
+            policies.insert(state_name.clone(), policy.to_string());
+        }
+
+        Ok(policies)
+    }
+
+    /// Attach front matter retry policies to their states
+    fn apply_retry_policies(
+        workflow: &mut Workflow,
+        policies: HashMap<String, String>,
+    ) -> ParseResult<()> {
+        for (state_name, policy) in policies {
+            let state = workflow
+                .states
+                .get_mut(&StateId::new(state_name.as_str()))
+                .ok_or_else(|| ParseError::InvalidStructure {
+                    message: format!("Retry policy declared for unknown state '{state_name}'"),
+                })?;
+            state
+                .metadata
+                .insert(RETRY_POLICY_METADATA_KEY.to_string(), policy);
+        }
+        Ok(())
+    }
+
+    /// Extract the `budget` of a run from the front matter
+    fn extract_budget_from_frontmatter(
+        frontmatter: &serde_json::Value,
+    ) -> ParseResult<WorkflowBudget> {
+        let mut budget = WorkflowBudget::default();
+
+        let Some(budget_value) = frontmatter.get("budget") else {
+            return Ok(budget);
+        };
+
+        let settings = budget_value
+            .as_object()
+            .ok_or_else(|| ParseError::InvalidStructure {
+                message: "'budget' in front matter must be a mapping of settings".to_string(),
+            })?;
+
+        let action_parser = ActionParser::new().map_err(|e| ParseError::InvalidStructure {
+            message: e.to_string(),
+        })?;
+        for (key, value) in settings {
+            match key.as_str() {
+                "timeout" => {
+                    let timeout = action_parser.duration_from_value(value).map_err(|e| {
+                        ParseError::InvalidStructure {
+                            message: format!("Invalid budget timeout: {e}"),
+                        }
+                    })?;
+                    if timeout.is_zero() {
+                        return Err(ParseError::InvalidStructure {
+                            message: "Budget timeout must be greater than zero".to_string(),
+                        });
+                    }
+                    budget.timeout = Some(timeout);
+                }
+                "max_transitions" => {
+                    let limit = value
+                        .as_u64()
+                        .filter(|limit| *limit > 0)
+                        .ok_or_else(|| ParseError::InvalidStructure {
+                            message: format!(
+                                "Invalid budget max_transitions {value}: expected a positive number"
+                            ),
+                        })?;
+                    budget.max_transitions = Some(limit as usize);
+                }
+                "on_exceeded" => {
+                    let state = value
+                        .as_str()
+                        .and_then(|state| StateId::try_new(state).ok())
+                        .ok_or_else(|| ParseError::InvalidStructure {
+                            message: format!(
+                                "Invalid budget on_exceeded {value}: expected a state name"
+                            ),
+                        })?;
+                    budget.on_exceeded = Some(state);
+                }
+                other => {
+                    return Err(ParseError::InvalidStructure {
+                        message: format!(
+                            "Unknown budget setting '{other}': expected timeout, max_transitions or on_exceeded"
+                        ),
+                    })
+                }
+            }
+        }
+
+        Ok(budget)
+    }
+
+    /// Extract state `deadlines` keyed by state name from the front matter
+    fn extract_deadlines_from_frontmatter(
+        frontmatter: &serde_json::Value,
+    ) -> ParseResult<HashMap<String, Duration>> {
+        let mut deadlines = HashMap::new();
+
+        let Some(deadlines_value) = frontmatter.get("deadlines") else {
+            return Ok(deadlines);
+        };
+
+        let states = deadlines_value
+            .as_object()
+            .ok_or_else(|| ParseError::InvalidStructure {
+                message: "'deadlines' in front matter must map state names to durations"
+                    .to_string(),
+            })?;
+
+        let action_parser = ActionParser::new().map_err(|e| ParseError::InvalidStructure {
+            message: e.to_string(),
+        })?;
+        for (state_name, value) in states {
+            let deadline = action_parser
+                .duration_from_value(value)
+                .ok()
+                .filter(|deadline| !deadline.is_zero())
+                .ok_or_else(|| ParseError::InvalidStructure {
+                    message: format!(
+                        "Invalid deadline {value} for state '{state_name}': expected a duration like '10m'"
+                    ),
+                })?;
+            deadlines.insert(state_name.clone(), deadline);
+        }
+
+        Ok(deadlines)
+    }
+
+    /// Attach the front matter budget to the workflow and deadlines to their states
+    fn apply_budget(
+        workflow: &mut Workflow,
+        budget: WorkflowBudget,
+        deadlines: HashMap<String, Duration>,
+    ) -> ParseResult<()> {
+        if let Some(state) = &budget.on_exceeded {
+            if !workflow.states.contains_key(state) {
+                return Err(ParseError::InvalidStructure {
+                    message: format!("Budget on_exceeded names unknown state '{state}'"),
+                });
+            }
+        }
+        budget.apply_to(workflow);
+
+        for (state_name, deadline) in deadlines {
+            let state = workflow
+                .states
+                .get_mut(&StateId::new(state_name.as_str()))
+                .ok_or_else(|| ParseError::InvalidStructure {
+                    message: format!("Deadline declared for unknown state '{state_name}'"),
+                })?;
+            state.metadata.insert(
+                DEADLINE_METADATA_KEY.to_string(),
+                deadline.as_millis().to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Extract `map` state specifications keyed by state name from the front matter
+    fn extract_map_states_from_frontmatter(
+        frontmatter: &serde_json::Value,
+    ) -> ParseResult<HashMap<String, MapSpec>> {
+        let mut map_states = HashMap::new();
+
+        let Some(map_value) = frontmatter.get("map") else {
+            return Ok(map_states);
+        };
+
+        let states = map_value
+            .as_object()
+            .ok_or_else(|| ParseError::InvalidStructure {
+                message: "'map' in front matter must map state names to map specifications"
+                    .to_string(),
+            })?;
+
+        for (state_name, spec_value) in states {
+            let spec =
+                MapSpec::from_value(spec_value).map_err(|e| ParseError::InvalidStructure {
+                    message: format!("Invalid map specification for state '{state_name}': {e}"),
+                })?;
+            map_states.insert(state_name.clone(), spec);
+        }
+
+        Ok(map_states)
+    }
+
+    /// Turn the states named in the front matter `map` into map states
+    fn apply_map_states(
+        workflow: &mut Workflow,
+        map_states: HashMap<String, MapSpec>,
+    ) -> ParseResult<()> {
+        for (state_name, spec) in map_states {
+            let state = workflow
+                .states
+                .get_mut(&StateId::new(state_name.as_str()))
+                .ok_or_else(|| ParseError::InvalidStructure {
+                    message: format!("Map specification declared for unknown state '{state_name}'"),
+                })?;
+            if matches!(state.state_type, StateType::Fork | StateType::Join) {
+                return Err(ParseError::InvalidStructure {
+                    message: format!(
+                        "State '{state_name}' is a {} state and cannot also be a map state",
+                        state.state_type.as_str()
+                    ),
+                });
+            }
+            spec.apply_to(state);
+        }
+        Ok(())
+    }
+
+    /// Extract the `triggers` that start the workflow from the front matter
+    fn extract_triggers_from_frontmatter(
+        frontmatter: &serde_json::Value,
+    ) -> ParseResult<Vec<WorkflowTrigger>> {
+        let Some(triggers_value) = frontmatter.get("triggers") else {
+            return Ok(Vec::new());
+        };
+
+        let entries = triggers_value
+            .as_array()
+            .ok_or_else(|| ParseError::InvalidStructure {
+                message: "'triggers' in front matter must be a list of triggers".to_string(),
+            })?;
+
+        let action_parser = ActionParser::new().map_err(|e| ParseError::InvalidStructure {
+            message: e.to_string(),
+        })?;
+        entries
+            .iter()
+            .map(|entry| {
+                Self::parse_trigger(&action_parser, entry).map_err(|message| {
+                    ParseError::InvalidStructure {
+                        message: format!("Invalid trigger {entry}: {message}"),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Parse one entry of the front matter `triggers` list
+    fn parse_trigger(
+        action_parser: &ActionParser,
+        entry: &serde_json::Value,
+    ) -> Result<WorkflowTrigger, String> {
+        let settings = entry
+            .as_object()
+            .ok_or("expected one of files, git or schedule")?;
+
+        let kinds: Vec<&str> = ["files", "git", "schedule"]
+            .into_iter()
+            .filter(|kind| settings.contains_key(*kind))
+            .collect();
+        let [kind] = kinds[..] else {
+            return Err("set exactly one of files, git or schedule".to_string());
+        };
+        if let Some(unknown) = settings
+            .keys()
+            .find(|key| key.as_str() != kind && !(kind == "files" && key.as_str() == "debounce"))
+        {
+            return Err(format!("unknown setting '{unknown}'"));
+        }
+
+        let value = &settings[kind];
+        match kind {
+            "files" => {
+                let patterns = match value {
+                    serde_json::Value::String(pattern) => vec![pattern.clone()],
+                    serde_json::Value::Array(patterns) => patterns
+                        .iter()
+                        .map(|pattern| pattern.as_str().map(str::to_string))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or("files must be glob patterns")?,
+                    _ => return Err("files must be a glob pattern or a list of them".to_string()),
+                };
+                let debounce = match settings.get("debounce") {
+                    Some(debounce) => action_parser
+                        .duration_from_value(debounce)
+                        .map_err(|_| format!("debounce {debounce} is not a duration like '2s'"))?,
+                    None => DEFAULT_TRIGGER_DEBOUNCE,
+                };
+                WorkflowTrigger::files(patterns, debounce)
+            }
+            "git" => {
+                let event = value
+                    .as_str()
+                    .ok_or("git must name a hook")?
+                    .parse::<GitHookEvent>()?;
+                Ok(WorkflowTrigger::Git { event })
+            }
+            _ => WorkflowTrigger::schedule(value.as_str().ok_or("schedule must be a string")?),
+        }
+    }
+
+    /// Check that every `**Retry**:` line in a state description is valid
+    fn validate_retry_declarations(workflow: &Workflow) -> ParseResult<()> {
+        let action_parser = ActionParser::new().map_err(|e| ParseError::InvalidStructure {
+            message: e.to_string(),
+        })?;
+        for state in workflow.states.values() {
+            if let Some(spec) = retry_declaration(&state.description) {
+                action_parser.parse_retry_policy(spec).map_err(|e| {
+                    ParseError::InvalidStructure {
+                        message: format!("Invalid retry policy for state '{}': {e}", state.id),
+                    }
+                })?;
+            }
+        }
+        Ok(())
     }
 
     /// Extract actions from markdown content
@@ -296,6 +666,7 @@ impl MermaidParser {
 
         // First try to extract from ## Actions section (legacy format)
         let mut in_actions_section = false;
+        let mut last_state: Option<String> = None;
         for line in input.lines() {
             let trimmed = line.trim();
             if trimmed.eq_ignore_ascii_case("## Actions")
@@ -310,6 +681,15 @@ impl MermaidParser {
                 break;
             }
 
+            // A **Retry**: line below an action applies to that action's state
+            if in_actions_section && retry_declaration(trimmed).is_some() {
+                if let Some(action) = last_state.as_ref().and_then(|s| actions.get_mut(s)) {
+                    action.push('\n');
+                    action.push_str(trimmed);
+                }
+                continue;
+            }
+
             if in_actions_section && line.trim().starts_with("-") {
                 // Parse action line: - StateName: Action description
                 let content = line.trim_start_matches('-').trim();
@@ -317,6 +697,7 @@ impl MermaidParser {
                     let state_name = content[..colon_pos].trim();
                     let action = content[colon_pos + 1..].trim();
                     actions.insert(state_name.to_string(), action.to_string());
+                    last_state = Some(state_name.to_string());
                 }
             }
         }
@@ -765,7 +1146,7 @@ impl MermaidParser {
             });
         }
 
-        Ok(())
+        Self::validate_retry_declarations(workflow)
     }
 
     /// Find all states reachable from the initial state using DFS
@@ -1409,12 +1790,218 @@ stateDiagram-v2
         ));
     }
 
+    #[test]
+    fn test_parse_workflow_with_retry_policies() {
+        let input = r#"---
+title: Retry Workflow
+description: Tests retry policies in front matter
+retry:
+  Fetch:
+    max_attempts: 4
+    backoff: linear
+    delay: 2s
+    retry_on: [command, timeout]
+---
+
+```mermaid
+stateDiagram-v2
+    [*] --> Fetch
+    Fetch --> [*]
+```
+"#;
+
+        let workflow = MermaidParser::parse_with_metadata(input, "retry_workflow", None, None)
+            .expect("retry policies in front matter should parse");
+        let policy = &workflow.states[&StateId::new("Fetch")].metadata[RETRY_POLICY_METADATA_KEY];
+        assert_eq!(
+            policy,
+            "max_attempts=4 backoff=linear delay=2000ms max_delay=60000ms jitter=false retry_on=command,timeout"
+        );
+
+        let unknown_state = input.replace("  Fetch:", "  Missing:");
+        assert!(
+            MermaidParser::parse_with_metadata(&unknown_state, "retry_workflow", None, None)
+                .is_err()
+        );
+
+        let invalid_policy = input.replace("backoff: linear", "backoff: sometimes");
+        assert!(
+            MermaidParser::parse_with_metadata(&invalid_policy, "retry_workflow", None, None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_workflow_with_budget_and_deadlines() {
+        let input = r#"---
+title: Budget Workflow
+description: Tests run budgets in front matter
+budget:
+  timeout: 2h
+  max_transitions: 50
+  on_exceeded: Cleanup
+deadlines:
+  Review: 10m
+---
+
+```mermaid
+stateDiagram-v2
+    [*] --> Review
+    Review --> Review
+    Review --> [*]
+    Cleanup --> [*]
+```
+
+## Actions
+
+- Review: Log "Reviewing"
+- Cleanup: Log "Out of budget"
+"#;
+
+        let workflow = MermaidParser::parse_with_metadata(input, "budget_workflow", None, None)
+            .expect("budget in front matter should parse");
+        let budget = WorkflowBudget::of(&workflow);
+        assert_eq!(budget.timeout, Some(Duration::from_secs(7200)));
+        assert_eq!(budget.max_transitions, Some(50));
+        assert_eq!(budget.on_exceeded, Some(StateId::new("Cleanup")));
+        assert_eq!(
+            crate::workflow::state_deadline(&workflow.states[&StateId::new("Review")]),
+            Some(Duration::from_secs(600))
+        );
+
+        for invalid in [
+            input.replace("on_exceeded: Cleanup", "on_exceeded: Missing"),
+            input.replace("  Review: 10m", "  Missing: 10m"),
+            input.replace("max_transitions: 50", "max_transitions: 0"),
+            input.replace("timeout: 2h", "timeout: eventually"),
+            input.replace("timeout: 2h", "wall_clock: 2h"),
+        ] {
+            assert!(
+                MermaidParser::parse_with_metadata(&invalid, "budget_workflow", None, None)
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_workflow_with_map_state() {
+        let input = r#"---
+title: Map Workflow
+description: Tests map states in front matter
+map:
+  Implement:
+    over: issues
+    as: issue
+    workflow: code_issue
+    max_concurrency: 2
+    into: implemented
+    on_error: collect
+---
+
+```mermaid
+stateDiagram-v2
+    [*] --> Implement
+    Implement --> [*]
+```
+
+## Actions
+
+- Implement: Log "Implementing issues"
+"#;
+
+        let workflow = MermaidParser::parse_with_metadata(input, "map_workflow", None, None)
+            .expect("map state in front matter should parse");
+        let state = &workflow.states[&StateId::new("Implement")];
+        assert_eq!(state.state_type, StateType::Map);
+        let spec = crate::workflow::map_spec(state).unwrap();
+        assert_eq!(spec.over, "issues");
+        assert_eq!(spec.item_variable, "issue");
+        assert_eq!(spec.workflow.as_deref(), Some("code_issue"));
+        assert_eq!(spec.max_concurrency, 2);
+        assert_eq!(spec.result_variable, "implemented");
+        assert_eq!(spec.on_error, crate::workflow::MapFailurePolicy::Collect);
+
+        for invalid in [
+            input.replace("  Implement:\n", "  Missing:\n"),
+            input.replace("max_concurrency: 2", "max_concurrency: 0"),
+            input.replace("    workflow: code_issue\n", ""),
+            input.replace("on_error: collect", "on_error: retry"),
+        ] {
+            assert!(
+                MermaidParser::parse_with_metadata(&invalid, "map_workflow", None, None).is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_workflow_with_triggers() {
+        let input = r#"---
+title: Triggered Workflow
+description: Tests triggers in front matter
+triggers:
+  - files: ["src/**/*.rs", "Cargo.toml"]
+    debounce: 2s
+  - files: docs/*.md
+  - git: pre-push
+  - schedule: "0 9 * * 1-5"
+---
+
+```mermaid
+stateDiagram-v2
+    [*] --> Check
+    Check --> [*]
+```
+
+## Actions
+
+- Check: Log "Checking {{ trigger.kind }}"
+"#;
+
+        let workflow = MermaidParser::parse_with_metadata(input, "triggered", None, None)
+            .expect("triggers in front matter should parse");
+        let triggers = crate::workflow::workflow_triggers(&workflow);
+        assert_eq!(
+            triggers,
+            vec![
+                WorkflowTrigger::Files {
+                    patterns: vec!["src/**/*.rs".to_string(), "Cargo.toml".to_string()],
+                    debounce_ms: 2000,
+                },
+                WorkflowTrigger::Files {
+                    patterns: vec!["docs/*.md".to_string()],
+                    debounce_ms: DEFAULT_TRIGGER_DEBOUNCE.as_millis() as u64,
+                },
+                WorkflowTrigger::Git {
+                    event: GitHookEvent::PrePush,
+                },
+                WorkflowTrigger::Schedule {
+                    cron: "0 9 * * 1-5".to_string(),
+                },
+            ]
+        );
+
+        for invalid in [
+            input.replace("git: pre-push", "git: pre-rebase"),
+            input.replace("\"0 9 * * 1-5\"", "\"0 25 * * *\""),
+            input.replace("debounce: 2s", "debounce: soon"),
+            input.replace(
+                "  - files: docs/*.md\n",
+                "  - files: docs/*.md\n    git: post-commit\n",
+            ),
+            input.replace(
+                "  - git: pre-push\n",
+                "  - git: pre-push\n    branch: main\n",
+            ),
+        ] {
+            assert!(MermaidParser::parse_with_metadata(&invalid, "triggered", None, None).is_err());
+        }
+    }
+
     #[test]
     fn test_extract_parameters_from_frontmatter_empty() {
         let input = "No frontmatter here";
-        let result = MermaidParser::extract_parameters_from_frontmatter(input);
-        assert!(result.is_ok());
-        assert!(result.unwrap().is_empty());
+        let frontmatter = MermaidParser::parse_frontmatter_metadata(input).unwrap();
+        assert!(MermaidParser::extract_parameters_from_frontmatter(&frontmatter).is_empty());
     }
 
     #[test]
@@ -1425,9 +2012,8 @@ description: No parameters
 ---
 Content here
 "#;
-        let result = MermaidParser::extract_parameters_from_frontmatter(input);
-        assert!(result.is_ok());
-        assert!(result.unwrap().is_empty());
+        let frontmatter = MermaidParser::parse_frontmatter_metadata(input).unwrap();
+        assert!(MermaidParser::extract_parameters_from_frontmatter(&frontmatter).is_empty());
     }
 
     #[test]
@@ -0,0 +1,352 @@
+//! Retry policies for workflow states
+//!
+//! A state can declare how its action is retried when it fails, either with a
+//! `**Retry**:` line next to its action or in the workflow front matter:
+//!
+//! ```markdown
+//! ### fetch
+//! **Action**: Shell "curl -sf https://example.com/data.json"
+//! **Retry**: max_attempts=3 backoff=exponential delay=1s max_delay=30s jitter=true retry_on=command,timeout
+//! ```
+//!
+//! ```yaml
+//! retry:
+//!   fetch:
+//!     max_attempts: 3
+//!     backoff: exponential
+//!     delay: 1s
+//!     retry_on: [command, timeout]
+//! ```
+//!
+//! Both forms are parsed by the action parser when the workflow is loaded and
+//! honored by the executor each time the state runs.
+
+use crate::workflow::ActionError;
+use rand::Rng;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// State metadata key holding the retry policy declared in front matter
+pub const RETRY_POLICY_METADATA_KEY: &str = "retry";
+
+/// Default number of attempts, including the first one
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first retry
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Default upper bound for the delay between attempts
+pub const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Get the settings of the `**Retry**:` line in a state description, if any
+pub fn retry_declaration(description: &str) -> Option<&str> {
+    description.lines().find_map(|line| {
+        let line = line.trim();
+        ["**Retry**:", "**retry**:"]
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix))
+            .map(str::trim)
+    })
+}
+
+/// How the delay between attempts grows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackoffStrategy {
+    /// Wait the same delay before every retry
+    Fixed,
+    /// Wait `delay * n` before the n-th retry
+    Linear,
+    /// Wait `delay * 2^(n-1)` before the n-th retry
+    #[default]
+    Exponential,
+}
+
+impl BackoffStrategy {
+    /// Get the string representation of the strategy
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackoffStrategy::Fixed => "fixed",
+            BackoffStrategy::Linear => "linear",
+            BackoffStrategy::Exponential => "exponential",
+        }
+    }
+}
+
+impl FromStr for BackoffStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fixed" | "constant" => Ok(BackoffStrategy::Fixed),
+            "linear" => Ok(BackoffStrategy::Linear),
+            "exponential" | "exp" => Ok(BackoffStrategy::Exponential),
+            _ => Err(format!(
+                "Invalid backoff strategy '{s}': expected fixed, linear or exponential"
+            )),
+        }
+    }
+}
+
+/// Class of failure used to decide whether an attempt is retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    /// The action timed out
+    Timeout,
+    /// The agent or API reported a rate limit
+    RateLimit,
+    /// The agent failed to produce a response
+    Agent,
+    /// The action failed while executing
+    Execution,
+    /// A file system or process I/O error
+    Io,
+    /// A shell command ran but exited with a non-zero code
+    Command,
+    /// A variable could not be read or written
+    Variable,
+    /// The action could not be parsed
+    Parse,
+    /// JSON could not be read or written
+    Json,
+    /// A command was rejected by shell security checks
+    Security,
+}
+
+impl ErrorClass {
+    /// All error classes, in declaration order
+    pub const ALL: [ErrorClass; 10] = [
+        ErrorClass::Timeout,
+        ErrorClass::RateLimit,
+        ErrorClass::Agent,
+        ErrorClass::Execution,
+        ErrorClass::Io,
+        ErrorClass::Command,
+        ErrorClass::Variable,
+        ErrorClass::Parse,
+        ErrorClass::Json,
+        ErrorClass::Security,
+    ];
+
+    /// Classes retried when a policy does not list any
+    ///
+    /// Parse, variable, JSON and security failures are deterministic, so
+    /// repeating the same action would fail the same way.
+    pub const TRANSIENT: [ErrorClass; 6] = [
+        ErrorClass::Timeout,
+        ErrorClass::RateLimit,
+        ErrorClass::Agent,
+        ErrorClass::Execution,
+        ErrorClass::Io,
+        ErrorClass::Command,
+    ];
+
+    /// Classify an action error
+    pub fn of(error: &ActionError) -> Self {
+        match error {
+            ActionError::Timeout { .. } => ErrorClass::Timeout,
+            ActionError::RateLimit { .. } => ErrorClass::RateLimit,
+            ActionError::ClaudeError(_) => ErrorClass::Agent,
+            ActionError::ExecutionError(_) | ActionError::ApprovalRejected(_) => {
+                ErrorClass::Execution
+            }
+            ActionError::IoError(_) => ErrorClass::Io,
+            ActionError::VariableError(_) => ErrorClass::Variable,
+            ActionError::ParseError(_) => ErrorClass::Parse,
+            ActionError::JsonError(_) => ErrorClass::Json,
+            ActionError::ShellSecurityError(_) => ErrorClass::Security,
+        }
+    }
+
+    /// Get the string representation of the class
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorClass::Timeout => "timeout",
+            ErrorClass::RateLimit => "rate_limit",
+            ErrorClass::Agent => "agent",
+            ErrorClass::Execution => "execution",
+            ErrorClass::Io => "io",
+            ErrorClass::Command => "command",
+            ErrorClass::Variable => "variable",
+            ErrorClass::Parse => "parse",
+            ErrorClass::Json => "json",
+            ErrorClass::Security => "security",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ErrorClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase().replace('-', "_");
+        ErrorClass::ALL
+            .into_iter()
+            .find(|class| class.as_str() == normalized)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid error class '{s}': expected one of {}",
+                    ErrorClass::ALL.map(|class| class.as_str()).join(", ")
+                )
+            })
+    }
+}
+
+/// How a failing state action is retried
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one
+    pub max_attempts: u32,
+    /// How the delay grows between attempts
+    pub backoff: BackoffStrategy,
+    /// Delay before the first retry
+    pub delay: Duration,
+    /// Upper bound for the delay between attempts
+    pub max_delay: Duration,
+    /// Whether to randomize each delay to spread out retries
+    pub jitter: bool,
+    /// Error classes that are retried
+    pub retry_on: Vec<ErrorClass>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            backoff: BackoffStrategy::default(),
+            delay: DEFAULT_RETRY_DELAY,
+            max_delay: DEFAULT_MAX_RETRY_DELAY,
+            jitter: false,
+            retry_on: ErrorClass::TRANSIENT.to_vec(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Whether another attempt is made after `attempt` failed with `class`
+    pub fn should_retry(&self, attempt: u32, class: ErrorClass) -> bool {
+        attempt < self.max_attempts && self.retry_on.contains(&class)
+    }
+
+    /// Delay before the attempt following `attempt`, without jitter
+    pub fn base_delay(&self, attempt: u32) -> Duration {
+        let retry = attempt.max(1);
+        let delay = match self.backoff {
+            BackoffStrategy::Fixed => self.delay,
+            BackoffStrategy::Linear => self.delay.saturating_mul(retry),
+            BackoffStrategy::Exponential => self
+                .delay
+                .saturating_mul(2u32.saturating_pow((retry - 1).min(31))),
+        };
+        delay.min(self.max_delay)
+    }
+
+    /// Delay before the attempt following `attempt`
+    ///
+    /// With jitter the delay is drawn uniformly from half to all of the base
+    /// delay, so runs that failed together do not retry in lockstep.
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay(attempt);
+        if !self.jitter || delay.is_zero() {
+            return delay;
+        }
+        let factor = rand::thread_rng().gen_range(0.5..=1.0);
+        delay.mul_f64(factor)
+    }
+}
+
+impl std::fmt::Display for RetryPolicy {
+    /// Format the policy in the `**Retry**:` line syntax
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "max_attempts={} backoff={} delay={}ms max_delay={}ms jitter={} retry_on={}",
+            self.max_attempts,
+            self.backoff.as_str(),
+            self.delay.as_millis(),
+            self.max_delay.as_millis(),
+            self.jitter,
+            self.retry_on
+                .iter()
+                .map(|class| class.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(backoff: BackoffStrategy) -> RetryPolicy {
+        RetryPolicy {
+            backoff,
+            delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(350),
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff_delays_are_capped() {
+        let fixed = policy(BackoffStrategy::Fixed);
+        assert_eq!(fixed.base_delay(1), Duration::from_millis(100));
+        assert_eq!(fixed.base_delay(4), Duration::from_millis(100));
+
+        let linear = policy(BackoffStrategy::Linear);
+        assert_eq!(linear.base_delay(2), Duration::from_millis(200));
+        assert_eq!(linear.base_delay(5), Duration::from_millis(350));
+
+        let exponential = policy(BackoffStrategy::Exponential);
+        assert_eq!(exponential.base_delay(1), Duration::from_millis(100));
+        assert_eq!(exponential.base_delay(2), Duration::from_millis(200));
+        assert_eq!(exponential.base_delay(3), Duration::from_millis(350));
+        assert_eq!(exponential.base_delay(100), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_half_to_full_delay() {
+        let mut jittered = policy(BackoffStrategy::Fixed);
+        jittered.jitter = true;
+        for _ in 0..50 {
+            let delay = jittered.delay_for(1);
+            assert!(delay >= Duration::from_millis(50));
+            assert!(delay <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_retry_declaration() {
+        let description = "**Action**: Shell \"make test\"\n  **Retry**: max_attempts=2\n";
+        assert_eq!(retry_declaration(description), Some("max_attempts=2"));
+        assert_eq!(retry_declaration("Shell \"make test\""), None);
+    }
+
+    #[test]
+    fn test_should_retry_respects_attempts_and_classes() {
+        let policy = RetryPolicy::default();
+        assert!(policy.should_retry(1, ErrorClass::Timeout));
+        assert!(policy.should_retry(2, ErrorClass::Command));
+        assert!(!policy.should_retry(3, ErrorClass::Timeout));
+        assert!(!policy.should_retry(1, ErrorClass::Parse));
+
+        assert_eq!(
+            ErrorClass::of(&ActionError::RateLimit {
+                message: "slow down".to_string(),
+                wait_time: Duration::from_secs(1),
+            }),
+            ErrorClass::RateLimit
+        );
+        assert_eq!(
+            "rate-limit".parse::<ErrorClass>().unwrap(),
+            ErrorClass::RateLimit
+        );
+        assert!("flaky".parse::<ErrorClass>().is_err());
+    }
+}
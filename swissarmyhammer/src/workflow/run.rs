@@ -1,7 +1,7 @@
 //! Workflow runtime execution types
 
 use crate::common::generate_monotonic_ulid;
-use crate::workflow::{StateId, Workflow, WorkflowTemplateContext};
+use crate::workflow::{StateId, Workflow, WorkflowTemplateContext, BUDGET_EXCEEDED_KEY};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use ulid::Ulid;
@@ -51,6 +51,23 @@ pub enum WorkflowRunStatus {
     Paused,
 }
 
+impl WorkflowRunStatus {
+    /// Whether a run with this status was interrupted and can be resumed
+    ///
+    /// Running runs are excluded since they may still be executing in another
+    /// process; see [`WorkflowRun::is_resumable`] for runs left running by a
+    /// crash and for cancelled runs that ran out of budget.
+    pub fn is_resumable(&self) -> bool {
+        matches!(
+            self,
+            WorkflowRunStatus::Paused | WorkflowRunStatus::Cancelled
+        )
+    }
+}
+
+/// Run metadata key holding the reason a run was cancelled
+pub const CANCEL_REASON_KEY: &str = "cancel_reason";
+
 /// Runtime execution context for a workflow
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WorkflowRun {
@@ -139,6 +156,30 @@ impl WorkflowRun {
         self.status = WorkflowRunStatus::Failed;
         self.completed_at = Some(chrono::Utc::now());
     }
+
+    /// Mark the run as cancelled, recording why
+    pub fn cancel(&mut self, reason: impl Into<String>) {
+        self.status = WorkflowRunStatus::Cancelled;
+        self.completed_at = Some(chrono::Utc::now());
+        self.metadata
+            .insert(CANCEL_REASON_KEY.to_string(), reason.into());
+    }
+
+    /// Get the reason the run was cancelled, if it was
+    pub fn cancel_reason(&self) -> Option<&str> {
+        self.metadata.get(CANCEL_REASON_KEY).map(String::as_str)
+    }
+
+    /// Whether the run was cancelled because it ran out of budget
+    ///
+    /// Such a run has spent its budget and taken its timeout path, if it had
+    /// one, so it cannot be resumed.
+    pub fn is_budget_cancelled(&self) -> bool {
+        self.status == WorkflowRunStatus::Cancelled
+            && self.cancel_reason().is_some_and(|reason| {
+                self.metadata.get(BUDGET_EXCEEDED_KEY).map(String::as_str) == Some(reason)
+            })
+    }
 }
 
 #[cfg(test)]
@@ -254,6 +295,21 @@ mod tests {
         assert!(run.completed_at.is_some());
     }
 
+    #[test]
+    fn test_workflow_run_cancellation_records_reason() {
+        let mut workflow = create_workflow("Test Workflow", "A test workflow", "start");
+        workflow.add_state(create_state("start", "Start state", false));
+
+        let mut run = WorkflowRun::new(workflow);
+        assert_eq!(run.cancel_reason(), None);
+
+        run.cancel("Out of time");
+
+        assert_eq!(run.status, WorkflowRunStatus::Cancelled);
+        assert!(run.completed_at.is_some());
+        assert_eq!(run.cancel_reason(), Some("Out of time"));
+    }
+
     #[test]
     fn test_workflow_run_id_monotonic_generation() {
         let id1 = WorkflowRunId::new();
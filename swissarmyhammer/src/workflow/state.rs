@@ -16,6 +16,8 @@ pub enum StateType {
     Join,
     /// Choice state for conditional branching
     Choice,
+    /// Map state running an action or sub-workflow for each element of a list
+    Map,
 }
 
 impl StateType {
@@ -26,6 +28,7 @@ impl StateType {
             StateType::Fork => "Fork",
             StateType::Join => "Join",
             StateType::Choice => "Choice",
+            StateType::Map => "Map",
         }
     }
 }
@@ -8,6 +8,9 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Directory, relative to the working directory, that local workflow runs are stored in
+pub const LOCAL_WORKFLOW_RUNS_DIR: &str = ".swissarmyhammer/workflow-runs";
+
 // Include the generated builtin workflows
 include!(concat!(env!("OUT_DIR"), "/builtin_workflows.rs"));
 
@@ -219,6 +222,15 @@ pub trait WorkflowRunStorageBackend: Send + Sync {
     /// Clean up old runs (older than specified days)
     fn cleanup_old_runs(&mut self, days: u32) -> Result<u32>;
 
+    /// Find the most recently started run that was interrupted and can be resumed
+    fn latest_resumable_run(&self) -> Result<Option<WorkflowRun>> {
+        Ok(self
+            .list_runs()?
+            .into_iter()
+            .filter(|run| run.is_resumable())
+            .max_by_key(|run| (run.started_at, run.id)))
+    }
+
     /// Check if a run exists
     fn run_exists(&self, id: &WorkflowRunId) -> Result<bool> {
         self.get_run(id).map(|_| true).or_else(|e| match e {
@@ -581,9 +593,17 @@ impl WorkflowRunStorageBackend for FileSystemWorkflowRunStorage {
             std::fs::create_dir_all(&run_dir)?;
         }
 
+        // Write to a temporary file and rename it into place so a crash mid-write
+        // never leaves a truncated run.json behind
         let path = self.run_path(&run.id);
+        let temp_path = path.with_extension("json.tmp");
         let content = serde_json::to_string_pretty(run)?;
-        std::fs::write(&path, content)?;
+        {
+            let mut file = std::fs::File::create(&temp_path)?;
+            std::io::Write::write_all(&mut file, content.as_bytes())?;
+            file.sync_all()?;
+        }
+        std::fs::rename(&temp_path, &path)?;
 
         self.cache.insert(run.id, run.clone());
         Ok(())
@@ -944,7 +964,7 @@ impl WorkflowStorage {
 mod tests {
     use super::*;
     use crate::test_utils::IsolatedTestEnvironment;
-    use crate::workflow::{State, StateId, StateType};
+    use crate::workflow::{State, StateId, StateType, WorkflowRunStatus};
 
     fn create_test_workflow() -> Workflow {
         let mut workflow = Workflow::new(
@@ -1035,6 +1055,129 @@ mod tests {
         assert_eq!(remaining[0].id, recent_run.id);
     }
 
+    #[test]
+    fn test_latest_resumable_run() {
+        let mut storage = MemoryWorkflowRunStorage::new();
+        let workflow = create_test_workflow();
+
+        let mut older = WorkflowRun::new(workflow.clone());
+        older.started_at = chrono::Utc::now() - chrono::Duration::hours(2);
+        older.status = WorkflowRunStatus::Paused;
+
+        let mut newer = WorkflowRun::new(workflow.clone());
+        newer.started_at = chrono::Utc::now() - chrono::Duration::hours(1);
+        newer.status = WorkflowRunStatus::Cancelled;
+
+        let mut completed = WorkflowRun::new(workflow.clone());
+        completed.complete();
+
+        // A run that ran out of budget has nothing left to resume with
+        let mut out_of_budget = WorkflowRun::new(workflow.clone());
+        let reason = "Workflow reached its limit of 3 transitions in state start";
+        out_of_budget.metadata.insert(
+            crate::workflow::BUDGET_EXCEEDED_KEY.to_string(),
+            reason.to_string(),
+        );
+        out_of_budget.cancel(reason);
+
+        // A running run may still be executing elsewhere
+        let running = WorkflowRun::new(workflow);
+        assert_eq!(running.status, WorkflowRunStatus::Running);
+
+        storage.store_run(&older).unwrap();
+        storage.store_run(&newer).unwrap();
+        storage.store_run(&completed).unwrap();
+        storage.store_run(&out_of_budget).unwrap();
+        storage.store_run(&running).unwrap();
+
+        let latest = storage.latest_resumable_run().unwrap().unwrap();
+        assert_eq!(latest.id, newer.id);
+
+        storage.remove_run(&newer.id).unwrap();
+        let latest = storage.latest_resumable_run().unwrap().unwrap();
+        assert_eq!(latest.id, older.id);
+    }
+
+    #[test]
+    fn test_latest_resumable_run_includes_abandoned_running_run() {
+        use crate::workflow::executor::checkpoint::{CHECKPOINT_PID_KEY, CHECKPOINT_TIMESTAMP_KEY};
+
+        let mut storage = MemoryWorkflowRunStorage::new();
+        let workflow = create_test_workflow();
+
+        // Still being executed by this process
+        let mut live = WorkflowRun::new(workflow.clone());
+        live.metadata.insert(
+            CHECKPOINT_PID_KEY.to_string(),
+            std::process::id().to_string(),
+        );
+        live.metadata.insert(
+            CHECKPOINT_TIMESTAMP_KEY.to_string(),
+            chrono::Utc::now().to_rfc3339(),
+        );
+
+        // Left running without a checkpoint for longer than the timeout
+        let mut abandoned = WorkflowRun::new(workflow);
+        abandoned.started_at = chrono::Utc::now() - chrono::Duration::days(3);
+        abandoned.metadata.insert(
+            CHECKPOINT_TIMESTAMP_KEY.to_string(),
+            (chrono::Utc::now() - chrono::Duration::days(2)).to_rfc3339(),
+        );
+
+        storage.store_run(&live).unwrap();
+        storage.store_run(&abandoned).unwrap();
+
+        assert!(!live.is_resumable());
+        assert!(abandoned.is_resumable());
+        let latest = storage.latest_resumable_run().unwrap().unwrap();
+        assert_eq!(latest.id, abandoned.id);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_running_run_is_abandoned_once_its_process_exits() {
+        use crate::workflow::executor::checkpoint::{CHECKPOINT_PID_KEY, CHECKPOINT_TIMESTAMP_KEY};
+
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+
+        let mut run = WorkflowRun::new(create_test_workflow());
+        run.metadata
+            .insert(CHECKPOINT_PID_KEY.to_string(), pid.to_string());
+        run.metadata.insert(
+            CHECKPOINT_TIMESTAMP_KEY.to_string(),
+            chrono::Utc::now().to_rfc3339(),
+        );
+
+        assert!(run.is_abandoned(chrono::Utc::now()));
+        assert!(run.is_resumable());
+    }
+
+    #[test]
+    fn test_file_system_run_storage_replaces_run_atomically() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage = FileSystemWorkflowRunStorage::new(temp_dir.path()).unwrap();
+        let mut run = WorkflowRun::new(create_test_workflow());
+
+        storage.store_run(&run).unwrap();
+        run.transition_to(StateId::new("end"));
+        storage.store_run(&run).unwrap();
+
+        let run_dir = storage.run_dir(&run.id);
+        let files: Vec<_> = std::fs::read_dir(&run_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(files, vec![std::ffi::OsString::from("run.json")]);
+
+        let reloaded = FileSystemWorkflowRunStorage::new(temp_dir.path()).unwrap();
+        assert_eq!(
+            reloaded.get_run(&run.id).unwrap().current_state,
+            StateId::new("end")
+        );
+    }
+
     #[test]
     fn test_combined_workflow_storage() {
         let mut storage = WorkflowStorage::memory();
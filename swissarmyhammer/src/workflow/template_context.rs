@@ -452,6 +452,12 @@ impl WorkflowTemplateContext {
                     ModelSource::Local { filename } => filename.to_string_lossy().to_string(),
                 })
                 .unwrap_or_else(|| "unknown".to_string()),
+            AgentExecutorType::OpenAiCompatible => match &self.get_agent_config().executor {
+                swissarmyhammer_config::agent::AgentExecutorConfig::OpenAiCompatible(config) => {
+                    config.model.clone()
+                }
+                _ => "unknown".to_string(),
+            },
         }
     }
 }
@@ -0,0 +1,785 @@
+//! Event triggers that start workflow runs
+//!
+//! A workflow can declare in its front matter the events that start it when
+//! `sah flow watch` is running, or when a git hook installed by
+//! `sah flow hooks` fires:
+//!
+//! ```yaml
+//! triggers:
+//!   - files: ["src/**/*.rs", "Cargo.toml"]
+//!     debounce: 2s
+//!   - git: post-commit
+//!   - schedule: "0 9 * * 1-5"
+//! ```
+//!
+//! The event that started a run is placed in the run context under `trigger`,
+//! e.g. the changed paths or the commit sha. Bursts of file changes are
+//! merged by a [`TriggerDebouncer`], and a [`TriggerGuard`] makes sure a
+//! workflow only has one triggered run at a time.
+
+use crate::common::mcp_errors::ToSwissArmyHammerError;
+use crate::workflow::{Workflow, WorkflowName, WorkflowTemplateContext};
+use chrono::{Datelike, Duration as ChronoDuration, NaiveDateTime, Timelike};
+use notify::event::{Event, EventKind};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Workflow metadata key holding the declared triggers as JSON
+pub const TRIGGERS_METADATA_KEY: &str = "triggers";
+
+/// Context key holding the event that started a run
+pub const TRIGGER_CONTEXT_KEY: &str = "trigger";
+
+/// Default time file changes are collected before a run starts
+pub const DEFAULT_TRIGGER_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Directories whose changes never fire file triggers
+const IGNORED_DIRECTORIES: &[&str] = &[".git", ".swissarmyhammer", "target"];
+
+/// Git hooks that can start workflows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitHookEvent {
+    /// After a commit has been created
+    PostCommit,
+    /// Before commits are pushed; a failing run stops the push
+    PrePush,
+}
+
+impl GitHookEvent {
+    /// All supported git hooks
+    pub const ALL: [GitHookEvent; 2] = [GitHookEvent::PostCommit, GitHookEvent::PrePush];
+
+    /// Get the name of the hook as git knows it
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GitHookEvent::PostCommit => "post-commit",
+            GitHookEvent::PrePush => "pre-push",
+        }
+    }
+}
+
+impl std::fmt::Display for GitHookEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for GitHookEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase().replace('_', "-");
+        GitHookEvent::ALL
+            .into_iter()
+            .find(|event| event.as_str() == normalized)
+            .ok_or_else(|| format!("Invalid git trigger '{s}': expected post-commit or pre-push"))
+    }
+}
+
+/// An event a workflow declares it is started by
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WorkflowTrigger {
+    /// Files matching any of the glob patterns changed
+    Files {
+        /// Glob patterns relative to the watched directory
+        patterns: Vec<String>,
+        /// Time changes are collected before a run starts, in milliseconds
+        debounce_ms: u64,
+    },
+    /// A git hook fired
+    Git {
+        /// The hook
+        event: GitHookEvent,
+    },
+    /// A cron schedule came due
+    Schedule {
+        /// Five-field cron expression
+        cron: String,
+    },
+}
+
+impl WorkflowTrigger {
+    /// Trigger on changes to files matching `patterns`
+    pub fn files(patterns: Vec<String>, debounce: Duration) -> Result<Self, String> {
+        if patterns.is_empty() {
+            return Err("a files trigger needs at least one glob pattern".to_string());
+        }
+        for pattern in &patterns {
+            glob::Pattern::new(pattern)
+                .map_err(|e| format!("Invalid glob pattern '{pattern}': {e}"))?;
+        }
+        Ok(WorkflowTrigger::Files {
+            patterns,
+            debounce_ms: debounce.as_millis() as u64,
+        })
+    }
+
+    /// Trigger on a cron schedule
+    pub fn schedule(cron: impl Into<String>) -> Result<Self, String> {
+        let cron = cron.into();
+        CronSchedule::parse(&cron)?;
+        Ok(WorkflowTrigger::Schedule { cron })
+    }
+
+    /// Whether a changed path, relative to the watched directory, fires this trigger
+    pub fn matches_path(&self, path: &Path) -> bool {
+        let WorkflowTrigger::Files { patterns, .. } = self else {
+            return false;
+        };
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        patterns.iter().any(|pattern| {
+            glob::Pattern::new(pattern)
+                .map(|pattern| pattern.matches_path_with(path, options))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Time file changes are collected before a run starts
+    pub fn debounce(&self) -> Duration {
+        match self {
+            WorkflowTrigger::Files { debounce_ms, .. } => Duration::from_millis(*debounce_ms),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// Get the triggers a workflow declares
+pub fn workflow_triggers(workflow: &Workflow) -> Vec<WorkflowTrigger> {
+    workflow
+        .metadata
+        .get(TRIGGERS_METADATA_KEY)
+        .and_then(|triggers| serde_json::from_str(triggers).ok())
+        .unwrap_or_default()
+}
+
+/// Store the triggers of a workflow in its metadata
+pub fn set_workflow_triggers(workflow: &mut Workflow, triggers: &[WorkflowTrigger]) {
+    if triggers.is_empty() {
+        workflow.metadata.remove(TRIGGERS_METADATA_KEY);
+    } else if let Ok(triggers) = serde_json::to_string(triggers) {
+        workflow
+            .metadata
+            .insert(TRIGGERS_METADATA_KEY.to_string(), triggers);
+    }
+}
+
+/// The event that started a workflow run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TriggerEvent {
+    /// Files changed
+    Files {
+        /// Changed paths relative to the watched directory, sorted
+        paths: Vec<String>,
+    },
+    /// A git hook fired
+    Git {
+        /// The hook
+        event: GitHookEvent,
+        /// The commit `HEAD` pointed to
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sha: Option<String>,
+        /// Arguments git passed to the hook
+        #[serde(default)]
+        args: Vec<String>,
+        /// Lines git wrote to the hook's standard input, e.g. the refs being pushed
+        #[serde(default)]
+        input: Vec<String>,
+    },
+    /// A schedule came due
+    Schedule {
+        /// The cron expression
+        cron: String,
+        /// When the run was due, in local time
+        scheduled_at: NaiveDateTime,
+    },
+}
+
+impl TriggerEvent {
+    /// Files changed
+    pub fn files<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let paths: BTreeSet<String> = paths
+            .into_iter()
+            .map(|path| path.as_ref().to_string_lossy().replace('\\', "/"))
+            .collect();
+        TriggerEvent::Files {
+            paths: paths.into_iter().collect(),
+        }
+    }
+
+    /// Fold a later event into this one
+    ///
+    /// Changed paths are combined; any other later event replaces this one.
+    pub fn merge(&mut self, later: TriggerEvent) {
+        match (self, later) {
+            (TriggerEvent::Files { paths }, TriggerEvent::Files { paths: later }) => {
+                let combined: BTreeSet<String> = paths.drain(..).chain(later).collect();
+                *paths = combined.into_iter().collect();
+            }
+            (this, later) => *this = later,
+        }
+    }
+
+    /// Place the event in a run's context under `trigger`
+    pub fn inject_into(&self, context: &mut WorkflowTemplateContext) {
+        context.insert(
+            TRIGGER_CONTEXT_KEY.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        );
+    }
+}
+
+/// Collects triggers per workflow until they have been quiet for their debounce time
+#[derive(Debug, Default)]
+pub struct TriggerDebouncer {
+    pending: HashMap<WorkflowName, (TriggerEvent, Instant)>,
+}
+
+impl TriggerDebouncer {
+    /// Create an empty debouncer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an event, postponing the workflow's run until `debounce` after `now`
+    pub fn push(
+        &mut self,
+        workflow: WorkflowName,
+        event: TriggerEvent,
+        debounce: Duration,
+        now: Instant,
+    ) {
+        let due = now + debounce;
+        match self.pending.get_mut(&workflow) {
+            Some((pending, pending_due)) => {
+                pending.merge(event);
+                *pending_due = due;
+            }
+            None => {
+                self.pending.insert(workflow, (event, due));
+            }
+        }
+    }
+
+    /// Earliest time a pending event comes due
+    pub fn next_due(&self) -> Option<Instant> {
+        self.pending.values().map(|(_, due)| *due).min()
+    }
+
+    /// Take the events that are due at `now`
+    pub fn take_due(&mut self, now: Instant) -> Vec<(WorkflowName, TriggerEvent)> {
+        let due: Vec<WorkflowName> = self
+            .pending
+            .iter()
+            .filter(|(_, (_, due))| *due <= now)
+            .map(|(workflow, _)| workflow.clone())
+            .collect();
+        due.into_iter()
+            .filter_map(|workflow| {
+                self.pending
+                    .remove(&workflow)
+                    .map(|(event, _)| (workflow, event))
+            })
+            .collect()
+    }
+}
+
+/// Allows one triggered run per workflow at a time
+///
+/// Events arriving while a workflow runs are merged into a single follow-up
+/// run that starts once the current one has finished.
+#[derive(Debug, Default)]
+pub struct TriggerGuard {
+    running: HashSet<WorkflowName>,
+    queued: HashMap<WorkflowName, TriggerEvent>,
+}
+
+impl TriggerGuard {
+    /// Create a guard with nothing running
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a triggered run of the workflow is in progress
+    pub fn is_running(&self, workflow: &WorkflowName) -> bool {
+        self.running.contains(workflow)
+    }
+
+    /// Start a run for the event, or queue the event if the workflow is running
+    ///
+    /// Returns the event to start a run with now.
+    pub fn start(&mut self, workflow: &WorkflowName, event: TriggerEvent) -> Option<TriggerEvent> {
+        if self.running.insert(workflow.clone()) {
+            return Some(event);
+        }
+        match self.queued.get_mut(workflow) {
+            Some(queued) => queued.merge(event),
+            None => {
+                self.queued.insert(workflow.clone(), event);
+            }
+        }
+        None
+    }
+
+    /// Record that a run finished, returning the queued event to run next
+    pub fn finish(&mut self, workflow: &WorkflowName) -> Option<TriggerEvent> {
+        match self.queued.remove(workflow) {
+            Some(event) => Some(event),
+            None => {
+                self.running.remove(workflow);
+                None
+            }
+        }
+    }
+}
+
+/// A five-field cron schedule: minute, hour, day of month, month and day of week
+///
+/// Fields accept `*`, numbers, ranges `a-b`, steps `*/n` or `a-b/n`, and
+/// comma-separated lists. Day of week runs from 0 (Sunday) to 7 (Sunday
+/// again). As in cron, when both day fields are restricted a day matching
+/// either one is due. `@hourly`, `@daily`, `@weekly`, `@monthly` and
+/// `@yearly` are accepted as shorthands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: BTreeSet<u32>,
+    hours: BTreeSet<u32>,
+    days_of_month: BTreeSet<u32>,
+    months: BTreeSet<u32>,
+    days_of_week: BTreeSet<u32>,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Parse a cron expression
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expanded = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(format!(
+                "Invalid cron schedule '{expression}': expected 5 fields (minute hour day month weekday)"
+            ));
+        };
+
+        let field = |value: &str, name: &str, min: u32, max: u32| {
+            parse_cron_field(value, min, max)
+                .map_err(|e| format!("Invalid {name} in cron schedule '{expression}': {e}"))
+        };
+        let mut days_of_week = field(day_of_week, "day of week", 0, 7)?;
+        if days_of_week.remove(&7) {
+            days_of_week.insert(0);
+        }
+
+        Ok(Self {
+            minutes: field(minute, "minute", 0, 59)?,
+            hours: field(hour, "hour", 0, 23)?,
+            days_of_month: field(day_of_month, "day of month", 1, 31)?,
+            months: field(month, "month", 1, 12)?,
+            days_of_week,
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+
+    /// Whether the schedule is due on the day of `time`
+    fn matches_day(&self, time: &NaiveDateTime) -> bool {
+        if !self.months.contains(&time.month()) {
+            return false;
+        }
+        let day_of_month = self.days_of_month.contains(&time.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(&time.weekday().num_days_from_sunday());
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            (true, false) => day_of_month,
+            (false, true) => day_of_week,
+            (false, false) => true,
+        }
+    }
+
+    /// First minute strictly after `after` the schedule is due
+    ///
+    /// Returns `None` for schedules that can never be due, like February 30th.
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut time = after.with_second(0)?.with_nanosecond(0)? + ChronoDuration::minutes(1);
+        // Every schedule that can be due is due within eight years (leap days)
+        let limit = time + ChronoDuration::days(8 * 366);
+
+        while time < limit {
+            if !self.matches_day(&time) {
+                time = time.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.hours.contains(&time.hour()) {
+                time = time.with_minute(0)? + ChronoDuration::hours(1);
+                continue;
+            }
+            if !self.minutes.contains(&time.minute()) {
+                time += ChronoDuration::minutes(1);
+                continue;
+            }
+            return Some(time);
+        }
+        None
+    }
+
+    /// Next time the schedule is due after the current local time
+    ///
+    /// Returns the due time together with how long to wait for it.
+    pub fn next_local(&self) -> Option<(NaiveDateTime, Duration)> {
+        let now = chrono::Local::now().naive_local();
+        let next = self.next_after(now)?;
+        Some((next, (next - now).to_std().unwrap_or(Duration::ZERO)))
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CronSchedule::parse(s)
+    }
+}
+
+/// Whether a path, relative to the watched directory, lies in a directory
+/// that never fires file triggers
+pub fn is_ignored_trigger_path(path: &Path) -> bool {
+    path.components().any(|component| match component {
+        Component::Normal(name) => IGNORED_DIRECTORIES
+            .iter()
+            .any(|ignored| name == std::ffi::OsStr::new(ignored)),
+        _ => false,
+    })
+}
+
+/// Watches a directory tree for changes that can fire file triggers
+///
+/// Changed paths are reported relative to the watched directory, leaving out
+/// version control, build output and SwissArmyHammer's own state.
+pub struct TriggerFileWatcher {
+    root: PathBuf,
+    changes: mpsc::UnboundedReceiver<Vec<PathBuf>>,
+    // Dropping the watcher stops the notifications
+    _watcher: RecommendedWatcher,
+}
+
+impl TriggerFileWatcher {
+    /// Start watching `root` recursively
+    pub fn new(root: impl AsRef<Path>) -> crate::Result<Self> {
+        let root = root
+            .as_ref()
+            .canonicalize()
+            .to_swiss_error_with_context("Failed to resolve watched directory")?;
+
+        let (tx, changes) = mpsc::unbounded_channel();
+        let mut watcher = RecommendedWatcher::new(
+            move |result: std::result::Result<Event, notify::Error>| match result {
+                Ok(event) => {
+                    if matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    ) {
+                        let _ = tx.send(event.paths);
+                    }
+                }
+                Err(e) => tracing::warn!("File watch error: {}", e),
+            },
+            notify::Config::default(),
+        )
+        .to_swiss_error_with_context("Failed to create file watcher")?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .to_swiss_error_with_context(&format!("Failed to watch directory {root:?}"))?;
+
+        Ok(Self {
+            root,
+            changes,
+            _watcher: watcher,
+        })
+    }
+
+    /// Wait for the next batch of relevant changes
+    ///
+    /// Returns `None` once the watcher has stopped.
+    pub async fn next_changes(&mut self) -> Option<Vec<PathBuf>> {
+        loop {
+            let paths = self.changes.recv().await?;
+            let relevant: Vec<PathBuf> = paths
+                .iter()
+                .filter_map(|path| path.strip_prefix(&self.root).ok())
+                .filter(|path| !path.as_os_str().is_empty() && !is_ignored_trigger_path(path))
+                .map(Path::to_path_buf)
+                .collect();
+            if !relevant.is_empty() {
+                return Some(relevant);
+            }
+        }
+    }
+}
+
+/// Parse one cron field into the values it allows
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Result<BTreeSet<u32>, String> {
+    let number = |value: &str| -> Result<u32, String> {
+        let number: u32 = value
+            .parse()
+            .map_err(|_| format!("'{value}' is not a number"))?;
+        if number < min || number > max {
+            return Err(format!("{number} is outside {min}-{max}"));
+        }
+        Ok(number)
+    };
+
+    let mut values = BTreeSet::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("invalid step '{step}'"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (number(start)?, number(end)?),
+                // A single value with a step runs to the end of the range, as in cron
+                None if step > 1 => (number(range)?, max),
+                None => (number(range)?, number(range)?),
+            },
+        };
+        if start > end {
+            return Err(format!("range '{range}' runs backwards"));
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        let weekdays = CronSchedule::parse("30 9 * * 1-5").unwrap();
+        // Friday 2024-03-08 10:00 -> Monday 09:30
+        assert_eq!(
+            weekdays.next_after(at(2024, 3, 8, 10, 0)),
+            Some(at(2024, 3, 11, 9, 30))
+        );
+        assert_eq!(
+            weekdays.next_after(at(2024, 3, 11, 9, 29)),
+            Some(at(2024, 3, 11, 9, 30))
+        );
+
+        let every_quarter = CronSchedule::parse("*/15 * * * *").unwrap();
+        assert_eq!(
+            every_quarter.next_after(at(2024, 12, 31, 23, 50)),
+            Some(at(2025, 1, 1, 0, 0))
+        );
+
+        let leap_day = CronSchedule::parse("0 0 29 2 *").unwrap();
+        assert_eq!(
+            leap_day.next_after(at(2024, 3, 1, 0, 0)),
+            Some(at(2028, 2, 29, 0, 0))
+        );
+        assert_eq!(
+            CronSchedule::parse("0 0 30 2 *")
+                .unwrap()
+                .next_after(at(2024, 1, 1, 0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cron_day_fields_match_either_when_both_restricted() {
+        // The 1st of the month or any Sunday
+        let schedule = CronSchedule::parse("0 12 1 * 7").unwrap();
+        // Saturday 2024-03-02 -> Sunday 2024-03-03
+        assert_eq!(
+            schedule.next_after(at(2024, 3, 2, 13, 0)),
+            Some(at(2024, 3, 3, 12, 0))
+        );
+        assert_eq!(
+            CronSchedule::parse("@daily").unwrap(),
+            CronSchedule::parse("0 0 * * *").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cron_rejects_invalid_schedules() {
+        for invalid in [
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+        ] {
+            assert!(
+                CronSchedule::parse(invalid).is_err(),
+                "{invalid} should fail"
+            );
+        }
+    }
+
+    #[test]
+    fn test_files_trigger_matches_relative_paths() {
+        let trigger = WorkflowTrigger::files(
+            vec!["src/**/*.rs".to_string(), "Cargo.toml".to_string()],
+            DEFAULT_TRIGGER_DEBOUNCE,
+        )
+        .unwrap();
+        assert!(trigger.matches_path(Path::new("src/workflow/mod.rs")));
+        assert!(trigger.matches_path(Path::new("Cargo.toml")));
+        assert!(!trigger.matches_path(Path::new("crates/a/Cargo.toml")));
+        assert!(!trigger.matches_path(Path::new("README.md")));
+        assert_eq!(trigger.debounce(), DEFAULT_TRIGGER_DEBOUNCE);
+
+        assert!(WorkflowTrigger::files(vec![], DEFAULT_TRIGGER_DEBOUNCE).is_err());
+        assert!(
+            WorkflowTrigger::files(vec!["src/[".to_string()], DEFAULT_TRIGGER_DEBOUNCE).is_err()
+        );
+    }
+
+    #[test]
+    fn test_ignored_trigger_paths() {
+        assert!(is_ignored_trigger_path(Path::new(".git/index")));
+        assert!(is_ignored_trigger_path(Path::new(
+            ".swissarmyhammer/workflow-runs/run.json"
+        )));
+        assert!(is_ignored_trigger_path(Path::new(
+            "crates/a/target/debug/a"
+        )));
+        assert!(!is_ignored_trigger_path(Path::new("src/targets.rs")));
+    }
+
+    #[test]
+    fn test_triggers_round_trip_through_workflow_metadata() {
+        let mut workflow = Workflow::new(
+            WorkflowName::new("watched"),
+            "Watched workflow".to_string(),
+            crate::workflow::StateId::new("start"),
+        );
+        assert!(workflow_triggers(&workflow).is_empty());
+
+        let triggers = vec![
+            WorkflowTrigger::Git {
+                event: GitHookEvent::PrePush,
+            },
+            WorkflowTrigger::schedule("@hourly").unwrap(),
+        ];
+        set_workflow_triggers(&mut workflow, &triggers);
+        assert_eq!(workflow_triggers(&workflow), triggers);
+        assert_eq!("post_commit".parse(), Ok(GitHookEvent::PostCommit));
+    }
+
+    #[test]
+    fn test_debouncer_merges_bursts() {
+        let workflow = WorkflowName::new("lint");
+        let start = Instant::now();
+        let debounce = Duration::from_millis(100);
+        let mut debouncer = TriggerDebouncer::new();
+
+        debouncer.push(
+            workflow.clone(),
+            TriggerEvent::files(["b.rs"]),
+            debounce,
+            start,
+        );
+        debouncer.push(
+            workflow.clone(),
+            TriggerEvent::files(["a.rs", "b.rs"]),
+            debounce,
+            start + Duration::from_millis(50),
+        );
+        assert_eq!(
+            debouncer.next_due(),
+            Some(start + Duration::from_millis(150))
+        );
+        assert!(debouncer
+            .take_due(start + Duration::from_millis(120))
+            .is_empty());
+
+        let due = debouncer.take_due(start + Duration::from_millis(150));
+        assert_eq!(due, vec![(workflow, TriggerEvent::files(["a.rs", "b.rs"]))]);
+        assert_eq!(debouncer.next_due(), None);
+    }
+
+    #[test]
+    fn test_guard_runs_one_at_a_time_and_coalesces() {
+        let workflow = WorkflowName::new("lint");
+        let mut guard = TriggerGuard::new();
+
+        assert!(guard
+            .start(&workflow, TriggerEvent::files(["a.rs"]))
+            .is_some());
+        assert!(guard.is_running(&workflow));
+        assert!(guard
+            .start(&workflow, TriggerEvent::files(["b.rs"]))
+            .is_none());
+        assert!(guard
+            .start(&workflow, TriggerEvent::files(["c.rs"]))
+            .is_none());
+
+        assert_eq!(
+            guard.finish(&workflow),
+            Some(TriggerEvent::files(["b.rs", "c.rs"]))
+        );
+        assert!(guard.is_running(&workflow));
+        assert_eq!(guard.finish(&workflow), None);
+        assert!(!guard.is_running(&workflow));
+    }
+
+    #[test]
+    fn test_trigger_event_is_injected_into_context() {
+        let mut context = WorkflowTemplateContext::with_vars_for_test(HashMap::new());
+        TriggerEvent::Git {
+            event: GitHookEvent::PostCommit,
+            sha: Some("abc123".to_string()),
+            args: vec![],
+            input: vec![],
+        }
+        .inject_into(&mut context);
+
+        let trigger = context.get(TRIGGER_CONTEXT_KEY).unwrap();
+        assert_eq!(trigger["kind"], "git");
+        assert_eq!(trigger["event"], "post-commit");
+        assert_eq!(trigger["sha"], "abc123");
+    }
+}
@@ -51,6 +51,7 @@ fn create_sample_run_metrics() -> RunMetrics {
         total_duration: Some(Duration::from_millis(650)),
         state_durations,
         transition_count: 2,
+        retry_counts: HashMap::new(),
         memory_metrics: MemoryMetrics {
             peak_memory_bytes: 1024 * 1024,
             initial_memory_bytes: 512 * 1024,
@@ -0,0 +1,517 @@
+//! Integration tests for issue storage synced with a remote issue tracker
+//!
+//! A small in-memory tracker speaking the GitHub issues API runs on a local
+//! mock server.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use swissarmyhammer::error::SwissArmyHammerError;
+use swissarmyhammer::issues::remote::{
+    ConflictStrategy, HttpIssueStorage, RemoteIssueClient, RestMapping,
+};
+use swissarmyhammer::issues::IssueStorage;
+use tempfile::TempDir;
+use wiremock::matchers::path_regex;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+const ISSUES_PATH: &str = "/repos/acme/widgets/issues";
+
+/// In-memory GitHub-style tracker
+#[derive(Clone, Default)]
+struct FakeTracker {
+    issues: Arc<Mutex<BTreeMap<u64, Value>>>,
+    clock: Arc<AtomicU64>,
+    offline: Arc<AtomicBool>,
+    /// Number of issues the list returns, as a truncated page would
+    list_limit: Arc<AtomicUsize>,
+}
+
+impl FakeTracker {
+    fn tick(&self) -> String {
+        format!(
+            "2024-01-01T00:00:{:02}Z",
+            self.clock.fetch_add(1, Ordering::SeqCst)
+        )
+    }
+
+    /// Add or edit an issue as another user of the tracker would
+    fn put(&self, number: u64, title: &str, body: &str, state: &str) {
+        let updated_at = self.tick();
+        self.issues.lock().unwrap().insert(
+            number,
+            json!({
+                "number": number,
+                "title": title,
+                "body": body,
+                "state": state,
+                "updated_at": updated_at,
+            }),
+        );
+    }
+
+    fn issue(&self, number: u64) -> Value {
+        self.issues.lock().unwrap()[&number].clone()
+    }
+
+    fn find(&self, title: &str) -> Option<Value> {
+        self.issues
+            .lock()
+            .unwrap()
+            .values()
+            .find(|issue| issue["title"] == title)
+            .cloned()
+    }
+}
+
+impl Respond for FakeTracker {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        if self.offline.load(Ordering::SeqCst) {
+            return ResponseTemplate::new(503);
+        }
+
+        let number = request
+            .url
+            .path()
+            .strip_prefix(ISSUES_PATH)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .and_then(|rest| rest.parse::<u64>().ok());
+        let body: Value = serde_json::from_slice(&request.body).unwrap_or(Value::Null);
+        let mut issues = self.issues.lock().unwrap();
+
+        match (request.method.as_str(), number) {
+            ("GET", None) => {
+                let limit = match self.list_limit.load(Ordering::SeqCst) {
+                    0 => usize::MAX,
+                    limit => limit,
+                };
+                ResponseTemplate::new(200)
+                    .set_body_json(issues.values().take(limit).cloned().collect::<Vec<_>>())
+            }
+            ("GET", Some(number)) => match issues.get(&number) {
+                Some(issue) => ResponseTemplate::new(200).set_body_json(issue.clone()),
+                None => ResponseTemplate::new(404),
+            },
+            ("POST", None) => {
+                let number = issues.keys().max().copied().unwrap_or(0) + 1;
+                let issue = json!({
+                    "number": number,
+                    "title": body["title"],
+                    "body": body["body"],
+                    "state": "open",
+                    "updated_at": self.tick(),
+                });
+                issues.insert(number, issue.clone());
+                ResponseTemplate::new(201).set_body_json(issue)
+            }
+            ("PATCH", Some(number)) => {
+                let Some(issue) = issues.get_mut(&number) else {
+                    return ResponseTemplate::new(404);
+                };
+                for field in ["title", "body", "state"] {
+                    if let Some(value) = body.get(field) {
+                        issue[field] = value.clone();
+                    }
+                }
+                issue["updated_at"] = json!(self.tick());
+                ResponseTemplate::new(200).set_body_json(issue.clone())
+            }
+            _ => ResponseTemplate::new(405),
+        }
+    }
+}
+
+async fn setup() -> (HttpIssueStorage, FakeTracker, MockServer, TempDir) {
+    let server = MockServer::start().await;
+    let tracker = FakeTracker::default();
+    Mock::given(path_regex(format!("^{ISSUES_PATH}")))
+        .respond_with(tracker.clone())
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let storage = open_storage(&server, &temp_dir);
+    (storage, tracker, server, temp_dir)
+}
+
+fn open_storage(server: &MockServer, temp_dir: &TempDir) -> HttpIssueStorage {
+    let client = RemoteIssueClient::new(
+        server.uri(),
+        RestMapping::github("acme", "widgets"),
+        Some("Bearer test-token".to_string()),
+        Duration::from_secs(5),
+    )
+    .unwrap();
+    HttpIssueStorage::new(temp_dir.path().join("issues"), client).unwrap()
+}
+
+/// In-memory Jira-style tracker serving at most two issues per search page
+#[derive(Clone, Default)]
+struct FakeJira {
+    issues: Arc<Mutex<BTreeMap<u64, Value>>>,
+}
+
+impl FakeJira {
+    const PAGE_LIMIT: usize = 2;
+
+    fn put(&self, number: u64, summary: &str) {
+        self.issues.lock().unwrap().insert(
+            number,
+            json!({
+                "key": format!("OPS-{number}"),
+                "fields": {
+                    "summary": summary,
+                    "description": format!("# {summary}"),
+                    "status": { "name": "To Do" },
+                    "updated": "2024-02-02T10:00:00.000+0000",
+                }
+            }),
+        );
+    }
+}
+
+impl Respond for FakeJira {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let query = |name: &str| {
+            request
+                .url
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .and_then(|(_, value)| value.parse::<usize>().ok())
+        };
+        let path = request.url.path();
+        let mut issues = self.issues.lock().unwrap();
+
+        match (request.method.as_str(), path) {
+            ("GET", "/rest/api/2/search") => {
+                let start = query("startAt").unwrap_or(0);
+                let max = query("maxResults").unwrap_or(50).min(Self::PAGE_LIMIT);
+                let page: Vec<Value> = issues.values().skip(start).take(max).cloned().collect();
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "startAt": start,
+                    "maxResults": max,
+                    "total": issues.len(),
+                    "issues": page,
+                }))
+            }
+            ("POST", "/rest/api/2/issue") => {
+                let body: Value = serde_json::from_slice(&request.body).unwrap();
+                let number = issues.keys().max().copied().unwrap_or(0) + 1;
+                let key = format!("OPS-{number}");
+                issues.insert(
+                    number,
+                    json!({
+                        "key": key,
+                        "fields": {
+                            "summary": body["fields"]["summary"],
+                            "description": body["fields"]["description"],
+                            "status": { "name": "To Do" },
+                            "updated": "2024-02-03T10:00:00.000+0000",
+                        }
+                    }),
+                );
+                // Jira only answers with the new issue's identifiers
+                ResponseTemplate::new(201).set_body_json(json!({
+                    "id": number.to_string(),
+                    "key": key,
+                    "self": format!("/rest/api/2/issue/{number}"),
+                }))
+            }
+            ("GET", path) => {
+                let issue = path
+                    .strip_prefix("/rest/api/2/issue/OPS-")
+                    .and_then(|number| number.parse::<u64>().ok())
+                    .and_then(|number| issues.get(&number));
+                match issue {
+                    Some(issue) => ResponseTemplate::new(200).set_body_json(issue.clone()),
+                    None => ResponseTemplate::new(404),
+                }
+            }
+            _ => ResponseTemplate::new(405),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_local_changes_are_pushed() {
+    let (storage, tracker, _server, temp_dir) = setup().await;
+
+    storage
+        .create_issue("fix_login".to_string(), "# Fix login".to_string())
+        .await
+        .unwrap();
+    let remote = tracker.find("fix_login").expect("remote issue created");
+    assert_eq!(remote["body"], "# Fix login");
+
+    storage
+        .update_issue("fix_login", "# Fix login\n\nDone".to_string())
+        .await
+        .unwrap();
+    assert_eq!(tracker.issue(1)["body"], "# Fix login\n\nDone");
+
+    storage.complete_issue("fix_login").await.unwrap();
+    assert_eq!(tracker.issue(1)["state"], "closed");
+
+    // The local working copy is unchanged by the tracker
+    assert!(temp_dir
+        .path()
+        .join("issues/complete/fix_login.md")
+        .exists());
+    assert!(storage.all_issues_completed().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_sync_pulls_remote_issues_and_changes() {
+    let (storage, tracker, _server, _temp_dir) = setup().await;
+    tracker.put(1, "Add dark mode", "# Dark mode", "open");
+
+    let report = storage
+        .sync(ConflictStrategy::Report)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(report.created_local, vec!["Add_dark_mode"]);
+    assert_eq!(
+        storage.get_issue("Add_dark_mode").await.unwrap().content,
+        "# Dark mode"
+    );
+
+    // Closed and edited in the tracker
+    tracker.put(1, "Add dark mode", "# Dark mode\n\nShipped", "closed");
+    let report = storage
+        .sync(ConflictStrategy::Report)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(report.pulled, vec!["Add_dark_mode"]);
+    let info = storage.get_issue_info("Add_dark_mode").await.unwrap();
+    assert!(info.completed);
+    assert_eq!(info.issue.content, "# Dark mode\n\nShipped");
+
+    // Pushing keeps the original remote title
+    storage
+        .update_issue("Add_dark_mode", "# Dark mode\n\nShipped in 2.0".to_string())
+        .await
+        .unwrap();
+    assert_eq!(tracker.issue(1)["title"], "Add dark mode");
+
+    let report = storage
+        .sync(ConflictStrategy::Report)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        report.is_empty(),
+        "unexpected changes: {}",
+        report.summary()
+    );
+}
+
+#[tokio::test]
+async fn test_concurrent_edits_are_conflicts() {
+    let (storage, tracker, _server, temp_dir) = setup().await;
+    storage
+        .create_issue("parser".to_string(), "# Parser".to_string())
+        .await
+        .unwrap();
+
+    // Someone edits the remote issue; a local update must not overwrite it
+    tracker.put(1, "parser", "# Parser\n\nRemote notes", "open");
+    let error = storage
+        .update_issue("parser", "# Parser\n\nLocal notes".to_string())
+        .await
+        .unwrap_err();
+    assert!(matches!(error, SwissArmyHammerError::IssueConflict(_)));
+    assert_eq!(
+        storage.get_issue("parser").await.unwrap().content,
+        "# Parser"
+    );
+
+    // Edited on both sides behind the storage's back
+    std::fs::write(
+        temp_dir.path().join("issues/parser.md"),
+        "# Parser\n\nLocal notes",
+    )
+    .unwrap();
+    let report = storage
+        .sync(ConflictStrategy::Report)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].name, "parser");
+    assert_eq!(tracker.issue(1)["body"], "# Parser\n\nRemote notes");
+
+    let report = storage
+        .sync(ConflictStrategy::Local)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(report.pushed, vec!["parser"]);
+    assert_eq!(tracker.issue(1)["body"], "# Parser\n\nLocal notes");
+}
+
+#[tokio::test]
+async fn test_offline_changes_are_queued() {
+    let (storage, tracker, server, temp_dir) = setup().await;
+    tracker.offline.store(true, Ordering::SeqCst);
+
+    storage
+        .create_issue("offline_work".to_string(), "# Offline".to_string())
+        .await
+        .unwrap();
+    storage
+        .update_issue("offline_work", "# Offline\n\nMore".to_string())
+        .await
+        .unwrap();
+    assert_eq!(storage.sync_state().await.queue.len(), 1);
+
+    let report = storage
+        .sync(ConflictStrategy::Report)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(report.offline);
+    assert_eq!(report.queued, 1);
+
+    // The queue survives a restart
+    drop(storage);
+    let storage = open_storage(&server, &temp_dir);
+    tracker.offline.store(false, Ordering::SeqCst);
+
+    let report = storage
+        .sync(ConflictStrategy::Report)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(report.created_remote, vec!["offline_work"]);
+    assert_eq!(report.queued, 0);
+    assert_eq!(
+        tracker.find("offline_work").unwrap()["body"],
+        "# Offline\n\nMore"
+    );
+}
+
+#[tokio::test]
+async fn test_delete_closes_remote_issue() {
+    let (storage, tracker, _server, _temp_dir) = setup().await;
+    storage
+        .create_issue("obsolete".to_string(), "# Obsolete".to_string())
+        .await
+        .unwrap();
+
+    storage.delete_issue("obsolete").await.unwrap();
+    assert_eq!(tracker.issue(1)["state"], "closed");
+    assert!(storage.sync_state().await.issues.is_empty());
+}
+
+#[tokio::test]
+async fn test_archive_and_reopen_follow_remote_state() {
+    let (storage, tracker, _server, _temp_dir) = setup().await;
+    storage
+        .create_issue("stale".to_string(), "# Stale".to_string())
+        .await
+        .unwrap();
+
+    storage.archive_issue("stale").await.unwrap();
+    assert_eq!(tracker.issue(1)["state"], "closed");
+
+    // An archived issue stays linked rather than being pulled again
+    let report = storage
+        .sync(ConflictStrategy::Report)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        report.is_empty(),
+        "unexpected changes: {}",
+        report.summary()
+    );
+
+    storage.reopen_issue("stale").await.unwrap();
+    assert_eq!(tracker.issue(1)["state"], "open");
+    assert!(!storage.all_issues_completed().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_issue_missing_from_list_is_kept_until_deleted_remotely() {
+    let (storage, tracker, _server, _temp_dir) = setup().await;
+    for name in ["first", "second"] {
+        storage
+            .create_issue(name.to_string(), format!("# {name}"))
+            .await
+            .unwrap();
+    }
+
+    // A truncated list is not a deletion
+    tracker.list_limit.store(1, Ordering::SeqCst);
+    let report = storage
+        .sync(ConflictStrategy::Remote)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        report.is_empty(),
+        "unexpected changes: {}",
+        report.summary()
+    );
+    assert!(storage.get_issue("second").await.is_ok());
+
+    tracker.list_limit.store(0, Ordering::SeqCst);
+    tracker.issues.lock().unwrap().remove(&2);
+    let report = storage
+        .sync(ConflictStrategy::Remote)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(report.pulled, vec!["second"]);
+    assert!(storage.get_issue("second").await.is_err());
+    assert!(storage.get_issue("first").await.is_ok());
+}
+
+#[tokio::test]
+async fn test_jira_list_is_paged_and_created_issues_are_not_conflicts() {
+    let server = MockServer::start().await;
+    let tracker = FakeJira::default();
+    for number in 1..=5 {
+        tracker.put(number, &format!("task_{number}"));
+    }
+    Mock::given(path_regex("^/rest/api/2/"))
+        .respond_with(tracker.clone())
+        .mount(&server)
+        .await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = RemoteIssueClient::new(
+        server.uri(),
+        RestMapping::jira("OPS", "31", "11"),
+        Some("Bearer test-token".to_string()),
+        Duration::from_secs(5),
+    )
+    .unwrap();
+    let storage = HttpIssueStorage::new(temp_dir.path().join("issues"), client).unwrap();
+
+    let report = storage
+        .sync(ConflictStrategy::Report)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(report.created_local.len(), 5, "{}", report.summary());
+
+    storage
+        .create_issue("task_6".to_string(), "# task_6".to_string())
+        .await
+        .unwrap();
+    let report = storage
+        .sync(ConflictStrategy::Report)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        report.is_empty(),
+        "unexpected changes: {}",
+        report.summary()
+    );
+}